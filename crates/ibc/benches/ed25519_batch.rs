@@ -0,0 +1,65 @@
+//! Benchmarks the verification of the commit signatures of Tendermint headers, one by one with
+//! the `tendermint` verifier, and as a batch with the host verifier of the Tendermint client.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use ibc::clients::ics07_tendermint::verifier::HostVerifier;
+use ibc::host_functions::HostFunctionsManager;
+use tendermint::Time;
+use tendermint_light_client_verifier::types::UntrustedBlockState;
+use tendermint_light_client_verifier::{ProdVerifier, Verdict};
+use tendermint_testgen::light_block::TmLightBlock;
+use tendermint_testgen::{Commit, Generator, Header, LightBlock, Validator};
+
+/// Generates a light block at height 5, signed by all of `n` validators of equal voting power.
+fn light_block(n: usize) -> TmLightBlock {
+    let validators: Vec<Validator> = (0..n)
+        .map(|i| Validator::new(&format!("validator-{i}")).voting_power(10))
+        .collect();
+    let header = Header::new(&validators)
+        .height(5)
+        .chain_id("test-chain")
+        .next_validators(&validators)
+        .time(Time::from_unix_timestamp(5, 0).unwrap());
+    let commit = Commit::new(header.clone(), 1);
+
+    LightBlock::new(header, commit)
+        .validators(&validators)
+        .next_validators(&validators)
+        .generate()
+        .unwrap()
+}
+
+fn untrusted_state(light_block: &TmLightBlock) -> UntrustedBlockState<'_> {
+    UntrustedBlockState {
+        signed_header: &light_block.signed_header,
+        validators: &light_block.validators,
+        next_validators: Some(&light_block.next_validators),
+    }
+}
+
+fn verify_commit(c: &mut Criterion) {
+    let mut group = c.benchmark_group("verify_commit");
+    group.sample_size(20);
+
+    let prod_verifier = ProdVerifier::default();
+    let host_verifier = HostVerifier::<HostFunctionsManager>::default();
+
+    for n in [100, 150] {
+        let light_block = light_block(n);
+        let untrusted = untrusted_state(&light_block);
+        assert_eq!(prod_verifier.verify_commit(&untrusted), Verdict::Success);
+        assert_eq!(host_verifier.verify_commit(&untrusted), Verdict::Success);
+
+        group.bench_with_input(BenchmarkId::new("individual", n), &untrusted, |b, u| {
+            b.iter(|| prod_verifier.verify_commit(u))
+        });
+        group.bench_with_input(BenchmarkId::new("batch", n), &untrusted, |b, u| {
+            b.iter(|| host_verifier.verify_commit(u))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, verify_commit);
+criterion_main!(benches);
@@ -22,6 +22,11 @@ use crate::core::ics26_routing::context::{ModuleOutputBuilder, OnRecvPacketAck};
 use crate::prelude::*;
 use crate::signer::Signer;
 
+#[cfg(feature = "val_exec_ctx")]
+use crate::applications::transfer::acknowledgement::ACK_ERR_STR;
+#[cfg(feature = "val_exec_ctx")]
+use crate::handler::HandlerOutput;
+
 pub trait TokenTransferKeeper: BankKeeper {
     fn store_send_packet_result(&mut self, result: SendPacketResult) -> Result<(), PacketError> {
         self.store_next_sequence_send(
@@ -188,6 +193,55 @@ pub fn on_chan_open_init(
     Ok((ModuleExtras::empty(), Version::new(VERSION.to_string())))
 }
 
+#[cfg(feature = "val_exec_ctx")]
+#[allow(clippy::too_many_arguments)]
+pub fn on_chan_open_init_validate(
+    ctx: &impl TokenTransferContext,
+    order: Order,
+    _connection_hops: &[ConnectionId],
+    port_id: &PortId,
+    _channel_id: &ChannelId,
+    _counterparty: &Counterparty,
+    version: &Version,
+) -> Result<Version, TokenTransferError> {
+    if order != Order::Unordered {
+        return Err(TokenTransferError::ChannelNotUnordered {
+            expect_order: Order::Unordered,
+            got_order: order,
+        });
+    }
+    let bound_port = ctx.get_port()?;
+    if port_id != &bound_port {
+        return Err(TokenTransferError::InvalidPort {
+            port_id: port_id.clone(),
+            exp_port_id: bound_port,
+        });
+    }
+
+    if !version.is_empty() && version != &Version::new(VERSION.to_string()) {
+        return Err(TokenTransferError::InvalidVersion {
+            expect_version: Version::new(VERSION.to_string()),
+            got_version: version.clone(),
+        });
+    }
+
+    Ok(Version::new(VERSION.to_string()))
+}
+
+#[cfg(feature = "val_exec_ctx")]
+#[allow(clippy::too_many_arguments)]
+pub fn on_chan_open_init_execute(
+    _ctx: &mut impl TokenTransferContext,
+    _order: Order,
+    _connection_hops: &[ConnectionId],
+    _port_id: &PortId,
+    _channel_id: &ChannelId,
+    _counterparty: &Counterparty,
+    _version: &Version,
+) -> Result<(ModuleExtras, Version), TokenTransferError> {
+    Ok((ModuleExtras::empty(), Version::new(VERSION.to_string())))
+}
+
 #[cfg(feature = "val_exec_ctx")]
 #[allow(clippy::too_many_arguments)]
 pub fn on_chan_open_try_validate(
@@ -255,6 +309,33 @@ pub fn on_chan_open_try(
     Ok((ModuleExtras::empty(), Version::new(VERSION.to_string())))
 }
 
+#[cfg(feature = "val_exec_ctx")]
+pub fn on_chan_open_ack_validate(
+    _ctx: &impl TokenTransferContext,
+    _port_id: &PortId,
+    _channel_id: &ChannelId,
+    counterparty_version: &Version,
+) -> Result<(), TokenTransferError> {
+    if counterparty_version != &Version::new(VERSION.to_string()) {
+        return Err(TokenTransferError::InvalidCounterpartyVersion {
+            expect_version: Version::new(VERSION.to_string()),
+            got_version: counterparty_version.clone(),
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "val_exec_ctx")]
+pub fn on_chan_open_ack_execute(
+    _ctx: &mut impl TokenTransferContext,
+    _port_id: &PortId,
+    _channel_id: &ChannelId,
+    _counterparty_version: &Version,
+) -> Result<ModuleExtras, TokenTransferError> {
+    Ok(ModuleExtras::empty())
+}
+
 pub fn on_chan_open_ack(
     _ctx: &mut impl TokenTransferContext,
     _port_id: &PortId,
@@ -271,6 +352,24 @@ pub fn on_chan_open_ack(
     Ok(ModuleExtras::empty())
 }
 
+#[cfg(feature = "val_exec_ctx")]
+pub fn on_chan_open_confirm_validate(
+    _ctx: &impl TokenTransferContext,
+    _port_id: &PortId,
+    _channel_id: &ChannelId,
+) -> Result<(), TokenTransferError> {
+    Ok(())
+}
+
+#[cfg(feature = "val_exec_ctx")]
+pub fn on_chan_open_confirm_execute(
+    _ctx: &mut impl TokenTransferContext,
+    _port_id: &PortId,
+    _channel_id: &ChannelId,
+) -> Result<ModuleExtras, TokenTransferError> {
+    Ok(ModuleExtras::empty())
+}
+
 pub fn on_chan_open_confirm(
     _ctx: &mut impl TokenTransferContext,
     _port_id: &PortId,
@@ -279,6 +378,24 @@ pub fn on_chan_open_confirm(
     Ok(ModuleExtras::empty())
 }
 
+#[cfg(feature = "val_exec_ctx")]
+pub fn on_chan_close_init_validate(
+    _ctx: &impl TokenTransferContext,
+    _port_id: &PortId,
+    _channel_id: &ChannelId,
+) -> Result<(), TokenTransferError> {
+    Err(TokenTransferError::CantCloseChannel)
+}
+
+#[cfg(feature = "val_exec_ctx")]
+pub fn on_chan_close_init_execute(
+    _ctx: &mut impl TokenTransferContext,
+    _port_id: &PortId,
+    _channel_id: &ChannelId,
+) -> Result<ModuleExtras, TokenTransferError> {
+    Err(TokenTransferError::CantCloseChannel)
+}
+
 pub fn on_chan_close_init(
     _ctx: &mut impl TokenTransferContext,
     _port_id: &PortId,
@@ -287,6 +404,24 @@ pub fn on_chan_close_init(
     Err(TokenTransferError::CantCloseChannel)
 }
 
+#[cfg(feature = "val_exec_ctx")]
+pub fn on_chan_close_confirm_validate(
+    _ctx: &impl TokenTransferContext,
+    _port_id: &PortId,
+    _channel_id: &ChannelId,
+) -> Result<(), TokenTransferError> {
+    Ok(())
+}
+
+#[cfg(feature = "val_exec_ctx")]
+pub fn on_chan_close_confirm_execute(
+    _ctx: &mut impl TokenTransferContext,
+    _port_id: &PortId,
+    _channel_id: &ChannelId,
+) -> Result<ModuleExtras, TokenTransferError> {
+    Ok(ModuleExtras::empty())
+}
+
 pub fn on_chan_close_confirm(
     _ctx: &mut impl TokenTransferContext,
     _port_id: &PortId,
@@ -295,6 +430,149 @@ pub fn on_chan_close_confirm(
     Ok(ModuleExtras::empty())
 }
 
+#[cfg(feature = "val_exec_ctx")]
+pub fn on_chan_upgrade_init_validate(
+    ctx: &impl TokenTransferContext,
+    port_id: &PortId,
+    _channel_id: &ChannelId,
+    order: Order,
+    _connection_hops: &[ConnectionId],
+    version: &Version,
+) -> Result<Version, TokenTransferError> {
+    if order != Order::Unordered {
+        return Err(TokenTransferError::ChannelNotUnordered {
+            expect_order: Order::Unordered,
+            got_order: order,
+        });
+    }
+    let bound_port = ctx.get_port()?;
+    if port_id != &bound_port {
+        return Err(TokenTransferError::InvalidPort {
+            port_id: port_id.clone(),
+            exp_port_id: bound_port,
+        });
+    }
+
+    if !version.is_empty() && version != &Version::new(VERSION.to_string()) {
+        return Err(TokenTransferError::InvalidVersion {
+            expect_version: Version::new(VERSION.to_string()),
+            got_version: version.clone(),
+        });
+    }
+
+    Ok(Version::new(VERSION.to_string()))
+}
+
+#[cfg(feature = "val_exec_ctx")]
+pub fn on_chan_upgrade_init_execute(
+    _ctx: &mut impl TokenTransferContext,
+    _port_id: &PortId,
+    _channel_id: &ChannelId,
+    _order: Order,
+    _connection_hops: &[ConnectionId],
+    _version: &Version,
+) -> Result<(ModuleExtras, Version), TokenTransferError> {
+    Ok((ModuleExtras::empty(), Version::new(VERSION.to_string())))
+}
+
+#[cfg(feature = "val_exec_ctx")]
+pub fn on_chan_upgrade_try_validate(
+    _ctx: &impl TokenTransferContext,
+    _port_id: &PortId,
+    _channel_id: &ChannelId,
+    order: Order,
+    _connection_hops: &[ConnectionId],
+    counterparty_version: &Version,
+) -> Result<Version, TokenTransferError> {
+    if order != Order::Unordered {
+        return Err(TokenTransferError::ChannelNotUnordered {
+            expect_order: Order::Unordered,
+            got_order: order,
+        });
+    }
+    if counterparty_version != &Version::new(VERSION.to_string()) {
+        return Err(TokenTransferError::InvalidCounterpartyVersion {
+            expect_version: Version::new(VERSION.to_string()),
+            got_version: counterparty_version.clone(),
+        });
+    }
+
+    Ok(Version::new(VERSION.to_string()))
+}
+
+#[cfg(feature = "val_exec_ctx")]
+pub fn on_chan_upgrade_try_execute(
+    _ctx: &mut impl TokenTransferContext,
+    _port_id: &PortId,
+    _channel_id: &ChannelId,
+    _order: Order,
+    _connection_hops: &[ConnectionId],
+    _counterparty_version: &Version,
+) -> Result<(ModuleExtras, Version), TokenTransferError> {
+    Ok((ModuleExtras::empty(), Version::new(VERSION.to_string())))
+}
+
+#[cfg(feature = "val_exec_ctx")]
+pub fn on_chan_upgrade_ack_validate(
+    _ctx: &impl TokenTransferContext,
+    _port_id: &PortId,
+    _channel_id: &ChannelId,
+    counterparty_version: &Version,
+) -> Result<(), TokenTransferError> {
+    if counterparty_version != &Version::new(VERSION.to_string()) {
+        return Err(TokenTransferError::InvalidCounterpartyVersion {
+            expect_version: Version::new(VERSION.to_string()),
+            got_version: counterparty_version.clone(),
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "val_exec_ctx")]
+pub fn on_chan_upgrade_ack_execute(
+    _ctx: &mut impl TokenTransferContext,
+    _port_id: &PortId,
+    _channel_id: &ChannelId,
+    _counterparty_version: &Version,
+) -> Result<ModuleExtras, TokenTransferError> {
+    Ok(ModuleExtras::empty())
+}
+
+#[cfg(feature = "val_exec_ctx")]
+pub fn on_recv_packet_execute<Ctx: 'static + TokenTransferContext>(
+    ctx: &mut Ctx,
+    packet: &Packet,
+) -> (ModuleExtras, Acknowledgement) {
+    let data = match serde_json::from_slice::<PacketData>(&packet.data) {
+        Ok(data) => data,
+        Err(_) => {
+            let ack =
+                Acknowledgement::Error(TokenTransferError::PacketDataDeserialization.to_string());
+            return (ModuleExtras::empty(), ack);
+        }
+    };
+
+    let mut output = ModuleOutputBuilder::new();
+    let ack = match process_recv_packet(ctx, &mut output, packet, data.clone()) {
+        Ok(write_fn) => match write_fn(ctx) {
+            Ok(()) => Acknowledgement::success(),
+            Err(e) => Acknowledgement::Error(format!("{ACK_ERR_STR}: {e}")),
+        },
+        Err(e) => Acknowledgement::from_error(e),
+    };
+
+    let recv_event = RecvEvent {
+        receiver: data.receiver,
+        denom: data.token.denom,
+        amount: data.token.amount,
+        success: matches!(ack, Acknowledgement::Success(_)),
+    };
+    output.emit(recv_event.into());
+
+    (into_module_extras(output), ack)
+}
+
 pub fn on_recv_packet<Ctx: 'static + TokenTransferContext>(
     ctx: &Ctx,
     output: &mut ModuleOutputBuilder,
@@ -326,6 +604,35 @@ pub fn on_recv_packet<Ctx: 'static + TokenTransferContext>(
     ack
 }
 
+#[cfg(feature = "val_exec_ctx")]
+pub fn on_acknowledgement_packet_validate(
+    _ctx: &impl TokenTransferContext,
+    packet: &Packet,
+    acknowledgement: &GenericAcknowledgement,
+    _relayer: &Signer,
+) -> Result<(), TokenTransferError> {
+    serde_json::from_slice::<PacketData>(&packet.data)
+        .map_err(|_| TokenTransferError::PacketDataDeserialization)?;
+
+    serde_json::from_slice::<Acknowledgement>(acknowledgement.as_ref())
+        .map_err(|_| TokenTransferError::AckDeserialization)?;
+
+    Ok(())
+}
+
+#[cfg(feature = "val_exec_ctx")]
+pub fn on_acknowledgement_packet_execute(
+    ctx: &mut impl TokenTransferContext,
+    packet: &Packet,
+    acknowledgement: &GenericAcknowledgement,
+    relayer: &Signer,
+) -> Result<ModuleExtras, TokenTransferError> {
+    let mut output = ModuleOutputBuilder::new();
+    on_acknowledgement_packet(ctx, &mut output, packet, acknowledgement, relayer)?;
+
+    Ok(into_module_extras(output))
+}
+
 pub fn on_acknowledgement_packet(
     ctx: &mut impl TokenTransferContext,
     output: &mut ModuleOutputBuilder,
@@ -353,6 +660,30 @@ pub fn on_acknowledgement_packet(
     Ok(())
 }
 
+#[cfg(feature = "val_exec_ctx")]
+pub fn on_timeout_packet_validate(
+    _ctx: &impl TokenTransferContext,
+    packet: &Packet,
+    _relayer: &Signer,
+) -> Result<(), TokenTransferError> {
+    serde_json::from_slice::<PacketData>(&packet.data)
+        .map_err(|_| TokenTransferError::PacketDataDeserialization)?;
+
+    Ok(())
+}
+
+#[cfg(feature = "val_exec_ctx")]
+pub fn on_timeout_packet_execute(
+    ctx: &mut impl TokenTransferContext,
+    packet: &Packet,
+    relayer: &Signer,
+) -> Result<ModuleExtras, TokenTransferError> {
+    let mut output = ModuleOutputBuilder::new();
+    on_timeout_packet(ctx, &mut output, packet, relayer)?;
+
+    Ok(into_module_extras(output))
+}
+
 pub fn on_timeout_packet(
     ctx: &mut impl TokenTransferContext,
     output: &mut ModuleOutputBuilder,
@@ -374,6 +705,12 @@ pub fn on_timeout_packet(
     Ok(())
 }
 
+#[cfg(feature = "val_exec_ctx")]
+fn into_module_extras(output: ModuleOutputBuilder) -> ModuleExtras {
+    let HandlerOutput { log, events, .. } = output.with_result(());
+    ModuleExtras { events, log }
+}
+
 #[cfg(test)]
 pub(crate) mod test {
     use super::*;
@@ -0,0 +1,1234 @@
+use crate::prelude::*;
+
+use core::time::Duration;
+
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::core::channel::v1::Channel as RawChannel;
+use ibc_proto::ibc::core::commitment::v1::MerkleProof;
+use ibc_proto::ibc::core::connection::v1::ConnectionEnd as RawConnectionEnd;
+use ibc_proto::ibc::lightclients::solomachine::v1::{
+    ChannelStateData, ClientState as RawSmClientState, ClientStateData, ConnectionStateData,
+    ConsensusStateData, DataType, NextSequenceRecvData, PacketAcknowledgementData,
+    PacketCommitmentData, PacketReceiptAbsenceData, TimestampedSignatureData,
+};
+use ibc_proto::protobuf::Protobuf;
+use prost::Message;
+
+use crate::clients::ics06_solomachine::consensus_state::ConsensusState as SmConsensusState;
+use crate::clients::ics06_solomachine::error::Error;
+use crate::clients::ics06_solomachine::header::Header as SmHeader;
+use crate::clients::ics06_solomachine::misbehaviour::Misbehaviour as SmMisbehaviour;
+use crate::clients::ics06_solomachine::proof::{path_bytes, sign_bytes, verify_signature};
+use crate::core::ics02_client::client_state::{
+    downcast_client_state, ClientState as Ics2ClientState, Status, UpdatedState, UpgradeOptions,
+};
+use crate::core::ics02_client::client_type::ClientType;
+use crate::core::ics02_client::consensus_state::ConsensusState;
+use crate::core::ics02_client::context::ClientReader;
+use crate::core::ics02_client::error::ClientError;
+use crate::core::ics04_channel::context::ChannelReader;
+use crate::core::ics23_commitment::commitment::{
+    CommitmentPrefix, CommitmentProofBytes, CommitmentRoot,
+};
+use crate::core::ics24_host::identifier::{ChainId, ClientId};
+use crate::core::ics24_host::Path;
+use crate::timestamp::Timestamp;
+use crate::Height;
+
+use super::client_type as sm_client_type;
+
+#[cfg(feature = "val_exec_ctx")]
+use crate::core::context::ContextError;
+#[cfg(feature = "val_exec_ctx")]
+use crate::core::ValidationContext;
+
+pub const SOLOMACHINE_CLIENT_STATE_TYPE_URL: &str = "/ibc.lightclients.solomachine.v1.ClientState";
+
+/// The client state of a solo machine, i.e. a standalone process, such as an off-chain service or
+/// a wallet, which holds a single key and signs over its state.
+///
+/// The solo machine sequence is the revision height of the client's latest height, and the
+/// revision number is always zero. Each header or proof is signed at the current sequence, which
+/// is incremented after every client update and every verified proof, as in ibc-go, so that no
+/// signature can be replayed.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClientState {
+    pub sequence: u64,
+    pub frozen_sequence: Option<u64>,
+    pub consensus_state: SmConsensusState,
+    /// Whether the client may be recovered through a substitute client
+    pub allow_update_after_proposal: bool,
+}
+
+impl ClientState {
+    pub fn new(
+        sequence: u64,
+        consensus_state: SmConsensusState,
+        allow_update_after_proposal: bool,
+    ) -> Result<Self, Error> {
+        if sequence == 0 {
+            return Err(Error::ZeroSequence);
+        }
+
+        Ok(Self {
+            sequence,
+            frozen_sequence: None,
+            consensus_state,
+            allow_update_after_proposal,
+        })
+    }
+
+    pub fn latest_height(&self) -> Height {
+        Height::new(0, self.sequence).expect("sequence cannot be zero")
+    }
+
+    pub fn with_frozen_sequence(self, frozen_sequence: u64) -> Self {
+        Self {
+            frozen_sequence: Some(frozen_sequence),
+            ..self
+        }
+    }
+
+    /// Verify a header, and return the client and consensus states it updates this client to.
+    pub fn check_header(&self, header: SmHeader) -> Result<(Self, SmConsensusState), Error> {
+        if header.sequence != self.sequence {
+            return Err(Error::SequenceMismatch {
+                sequence: header.sequence,
+                latest_sequence: self.sequence,
+            });
+        }
+
+        if header.timestamp.nanoseconds() < self.consensus_state.timestamp.nanoseconds() {
+            return Err(Error::TimestampTooLow {
+                timestamp: header.timestamp,
+                consensus_timestamp: self.consensus_state.timestamp,
+            });
+        }
+
+        let sign_bytes = header.sign_bytes(self.consensus_state.diversifier.clone());
+        verify_signature(
+            &self.consensus_state.public_key,
+            &sign_bytes,
+            &header.signature,
+        )?;
+
+        let consensus_state = SmConsensusState::new(
+            header.new_public_key,
+            header.new_diversifier,
+            header.timestamp,
+        );
+
+        let client_state = Self {
+            sequence: self.sequence + 1,
+            consensus_state: consensus_state.clone(),
+            ..self.clone()
+        };
+
+        Ok((client_state, consensus_state))
+    }
+
+    /// Verify a misbehaviour, i.e. that the current public key signed over two different
+    /// messages at the same sequence, and return the frozen client state.
+    ///
+    /// The timestamps of the signatures are not checked, so that past misbehaviour can be
+    /// submitted.
+    pub fn check_misbehaviour(&self, misbehaviour: SmMisbehaviour) -> Result<Self, Error> {
+        misbehaviour
+            .signature_one()
+            .verify(&self.consensus_state, misbehaviour.sequence())?;
+        misbehaviour
+            .signature_two()
+            .verify(&self.consensus_state, misbehaviour.sequence())?;
+
+        Ok(self.clone().with_frozen_sequence(misbehaviour.sequence()))
+    }
+
+    /// Verify that `proof` holds a valid signature of the solo machine over `data`, at the
+    /// current sequence. A verified proof consumes that sequence, and its timestamp becomes the
+    /// timestamp of the consensus state.
+    fn verify_signed_data(
+        &mut self,
+        proof: &CommitmentProofBytes,
+        data_type: DataType,
+        data: Vec<u8>,
+    ) -> Result<(), Error> {
+        let proof = Vec::<u8>::from(proof.clone());
+        let timestamped_signature_data =
+            TimestampedSignatureData::decode(proof.as_slice()).map_err(Error::Decode)?;
+        let timestamp = Timestamp::from_nanoseconds(timestamped_signature_data.timestamp)
+            .map_err(Error::InvalidTimestamp)?;
+
+        if timestamp.nanoseconds() < self.consensus_state.timestamp.nanoseconds() {
+            return Err(Error::TimestampTooLow {
+                timestamp,
+                consensus_timestamp: self.consensus_state.timestamp,
+            });
+        }
+
+        let sign_bytes = sign_bytes(
+            self.sequence,
+            timestamp,
+            self.consensus_state.diversifier.clone(),
+            data_type,
+            data,
+        );
+
+        verify_signature(
+            &self.consensus_state.public_key,
+            &sign_bytes,
+            &timestamped_signature_data.signature_data,
+        )?;
+
+        self.sequence += 1;
+        self.consensus_state.timestamp = timestamp;
+
+        Ok(())
+    }
+
+    /// Verify that `proof` holds a valid signature of the solo machine over `value` stored at
+    /// `path`, wrapped in the signed data type of that path.
+    fn verify_value(
+        &mut self,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        path: Path,
+        value: Vec<u8>,
+    ) -> Result<(), ClientError> {
+        let signed_path = path_bytes(prefix, path.clone());
+
+        let (data_type, data) = match path {
+            Path::ClientState(_) => {
+                let data = ClientStateData {
+                    path: signed_path,
+                    client_state: Some(Any::decode(value.as_slice()).map_err(Error::Decode)?),
+                };
+                (DataType::ClientState, data.encode_to_vec())
+            }
+            Path::ClientConsensusState(_) => {
+                let data = ConsensusStateData {
+                    path: signed_path,
+                    consensus_state: Some(Any::decode(value.as_slice()).map_err(Error::Decode)?),
+                };
+                (DataType::ConsensusState, data.encode_to_vec())
+            }
+            Path::Connections(_) => {
+                let data = ConnectionStateData {
+                    path: signed_path,
+                    connection: Some(
+                        RawConnectionEnd::decode(value.as_slice()).map_err(Error::Decode)?,
+                    ),
+                };
+                (DataType::ConnectionState, data.encode_to_vec())
+            }
+            Path::ChannelEnds(_) => {
+                let data = ChannelStateData {
+                    path: signed_path,
+                    channel: Some(RawChannel::decode(value.as_slice()).map_err(Error::Decode)?),
+                };
+                (DataType::ChannelState, data.encode_to_vec())
+            }
+            Path::Commitments(_) => {
+                let data = PacketCommitmentData {
+                    path: signed_path,
+                    commitment: value,
+                };
+                (DataType::PacketCommitment, data.encode_to_vec())
+            }
+            Path::Acks(_) => {
+                let data = PacketAcknowledgementData {
+                    path: signed_path,
+                    acknowledgement: value,
+                };
+                (DataType::PacketAcknowledgement, data.encode_to_vec())
+            }
+            Path::SeqRecvs(_) => {
+                let data = NextSequenceRecvData {
+                    path: signed_path,
+                    next_seq_recv: u64::decode(value.as_slice()).map_err(Error::Decode)?,
+                };
+                (DataType::NextSequenceRecv, data.encode_to_vec())
+            }
+            path => {
+                return Err(Error::UnsupportedPath {
+                    path: path.to_string(),
+                }
+                .into())
+            }
+        };
+
+        self.verify_signed_data(proof, data_type, data)
+            .map_err(Into::into)
+    }
+
+    /// Verify that `proof` holds a valid signature of the solo machine over the absence of a
+    /// value at `path`.
+    fn verify_absence(
+        &mut self,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        path: Path,
+    ) -> Result<(), ClientError> {
+        let signed_path = path_bytes(prefix, path.clone());
+
+        match path {
+            Path::Receipts(_) => {
+                let data = PacketReceiptAbsenceData { path: signed_path };
+
+                self.verify_signed_data(proof, DataType::PacketReceiptAbsence, data.encode_to_vec())
+                    .map_err(Into::into)
+            }
+            path => Err(Error::UnsupportedPath {
+                path: path.to_string(),
+            }
+            .into()),
+        }
+    }
+}
+
+impl Ics2ClientState for ClientState {
+    /// Solo machines are not chains, so the default chain identifier is returned.
+    fn chain_id(&self) -> ChainId {
+        ChainId::default()
+    }
+
+    fn client_type(&self) -> ClientType {
+        sm_client_type()
+    }
+
+    fn latest_height(&self) -> Height {
+        self.latest_height()
+    }
+
+    fn frozen_height(&self) -> Option<Height> {
+        self.frozen_sequence
+            .map(|sequence| Height::new(0, sequence).expect("sequence cannot be zero"))
+    }
+
+    /// Solo machines cannot be upgraded, so this is a no-op.
+    fn upgrade(
+        &mut self,
+        _upgrade_height: Height,
+        _upgrade_options: &dyn UpgradeOptions,
+        _chain_id: ChainId,
+    ) {
+    }
+
+    /// Solo machine consensus states never expire.
+    fn expired(&self, _elapsed: Duration) -> bool {
+        false
+    }
+
+    /// The consensus state of a solo machine is part of its client state, and never expires: the
+    /// client is `Active` unless it is frozen. In particular, no consensus state is stored at the
+    /// sequences consumed by proofs.
+    fn status(&self, _ctx: &dyn ClientReader, _client_id: &ClientId) -> Status {
+        if self.is_frozen() {
+            Status::Frozen
+        } else {
+            Status::Active
+        }
+    }
+
+    #[cfg(feature = "val_exec_ctx")]
+    fn new_status(&self, _ctx: &dyn ValidationContext, _client_id: &ClientId) -> Status {
+        if self.is_frozen() {
+            Status::Frozen
+        } else {
+            Status::Active
+        }
+    }
+
+    fn initialise(&self, consensus_state: Any) -> Result<Box<dyn ConsensusState>, ClientError> {
+        SmConsensusState::try_from(consensus_state).map(SmConsensusState::into_box)
+    }
+
+    fn check_header_and_update_state(
+        &self,
+        _ctx: &dyn ClientReader,
+        _client_id: ClientId,
+        header: Any,
+    ) -> Result<UpdatedState, ClientError> {
+        let (client_state, consensus_state) = self.check_header(SmHeader::try_from(header)?)?;
+
+        Ok(UpdatedState {
+            client_state: client_state.into_box(),
+            consensus_state: consensus_state.into_box(),
+        })
+    }
+
+    #[cfg(feature = "val_exec_ctx")]
+    fn new_check_header_and_update_state(
+        &self,
+        _ctx: &dyn ValidationContext,
+        _client_id: ClientId,
+        header: Any,
+    ) -> Result<UpdatedState, ClientError> {
+        let (client_state, consensus_state) = self.check_header(SmHeader::try_from(header)?)?;
+
+        Ok(UpdatedState {
+            client_state: client_state.into_box(),
+            consensus_state: consensus_state.into_box(),
+        })
+    }
+
+    fn check_misbehaviour_and_update_state(
+        &self,
+        _ctx: &dyn ClientReader,
+        _client_id: ClientId,
+        misbehaviour: Any,
+    ) -> Result<Box<dyn Ics2ClientState>, ClientError> {
+        let misbehaviour = SmMisbehaviour::try_from(misbehaviour)?;
+
+        Ok(self.check_misbehaviour(misbehaviour)?.into_box())
+    }
+
+    #[cfg(feature = "val_exec_ctx")]
+    fn new_check_misbehaviour_and_update_state(
+        &self,
+        _ctx: &dyn ValidationContext,
+        _client_id: ClientId,
+        misbehaviour: Any,
+    ) -> Result<Box<dyn Ics2ClientState>, ContextError> {
+        let misbehaviour = SmMisbehaviour::try_from(misbehaviour)?;
+
+        Ok(self
+            .check_misbehaviour(misbehaviour)
+            .map_err(ClientError::from)?
+            .into_box())
+    }
+
+    fn verify_upgrade_and_update_state(
+        &self,
+        _upgraded_client_state: Any,
+        _upgraded_consensus_state: Any,
+        _proof_upgrade_client: MerkleProof,
+        _proof_upgrade_consensus_state: MerkleProof,
+        _root: &CommitmentRoot,
+    ) -> Result<UpdatedState, ClientError> {
+        Err(Error::CannotUpgrade.into())
+    }
+
+    /// A solo machine client may only be recovered if it allows updates after a proposal. The
+    /// recovered client takes the sequence and consensus state of the substitute.
+    fn check_substitute_and_update_state(
+        &self,
+        substitute_client_state: &dyn Ics2ClientState,
+    ) -> Result<Box<dyn Ics2ClientState>, ClientError> {
+        if !self.allow_update_after_proposal {
+            return Err(Error::SubstituteNotAllowed.into());
+        }
+
+        let substitute_client_state = downcast_client_state::<Self>(substitute_client_state)
+            .ok_or_else(|| ClientError::ClientArgsTypeMismatch {
+                client_type: sm_client_type(),
+            })?;
+
+        Ok(Self {
+            sequence: substitute_client_state.sequence,
+            frozen_sequence: None,
+            consensus_state: substitute_client_state.consensus_state.clone(),
+            allow_update_after_proposal: self.allow_update_after_proposal,
+        }
+        .into_box())
+    }
+
+    /// Proofs are signed at the current sequence, whatever their height. The proof height only
+    /// refers to a sequence at which the client was created or updated, and so cannot be ahead of
+    /// the current sequence.
+    fn validate_proof_height(&self, proof_height: Height) -> Result<(), ClientError> {
+        if proof_height.revision_number() != 0 {
+            return Err(Error::NonZeroRevisionNumber {
+                revision_number: proof_height.revision_number(),
+            }
+            .into());
+        }
+
+        let sequence = proof_height.revision_height();
+        if sequence > self.sequence {
+            return Err(Error::SequenceMismatch {
+                sequence,
+                latest_sequence: self.sequence,
+            }
+            .into());
+        }
+
+        Ok(())
+    }
+
+    fn verify_membership(
+        &mut self,
+        _ctx: &dyn ChannelReader,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        path: Path,
+        value: Vec<u8>,
+    ) -> Result<(), ClientError> {
+        self.verify_value(prefix, proof, path, value)
+    }
+
+    fn verify_non_membership(
+        &mut self,
+        _ctx: &dyn ChannelReader,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        path: Path,
+    ) -> Result<(), ClientError> {
+        self.verify_absence(prefix, proof, path)
+    }
+
+    #[cfg(feature = "val_exec_ctx")]
+    fn new_verify_membership(
+        &mut self,
+        _ctx: &dyn ValidationContext,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        path: Path,
+        value: Vec<u8>,
+    ) -> Result<(), ClientError> {
+        self.verify_value(prefix, proof, path, value)
+    }
+
+    #[cfg(feature = "val_exec_ctx")]
+    fn new_verify_non_membership(
+        &mut self,
+        _ctx: &dyn ValidationContext,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        path: Path,
+    ) -> Result<(), ClientError> {
+        self.verify_absence(prefix, proof, path)
+    }
+}
+
+impl Protobuf<RawSmClientState> for ClientState {}
+
+impl TryFrom<RawSmClientState> for ClientState {
+    type Error = Error;
+
+    fn try_from(raw: RawSmClientState) -> Result<Self, Self::Error> {
+        let consensus_state: SmConsensusState = raw
+            .consensus_state
+            .ok_or_else(|| Error::InvalidRawClientState {
+                reason: "missing consensus state".into(),
+            })?
+            .try_into()?;
+
+        if consensus_state.timestamp == Timestamp::none() {
+            return Err(Error::InvalidRawClientState {
+                reason: "consensus state timestamp cannot be zero".into(),
+            });
+        }
+
+        if !consensus_state.diversifier.is_empty() && consensus_state.diversifier.trim().is_empty()
+        {
+            return Err(Error::InvalidRawClientState {
+                reason: "diversifier cannot contain only spaces".into(),
+            });
+        }
+
+        let client_state = Self::new(
+            raw.sequence,
+            consensus_state,
+            raw.allow_update_after_proposal,
+        )?;
+
+        Ok(match raw.frozen_sequence {
+            0 => client_state,
+            frozen_sequence => client_state.with_frozen_sequence(frozen_sequence),
+        })
+    }
+}
+
+impl From<ClientState> for RawSmClientState {
+    fn from(value: ClientState) -> Self {
+        RawSmClientState {
+            sequence: value.sequence,
+            frozen_sequence: value.frozen_sequence.unwrap_or_default(),
+            consensus_state: Some(value.consensus_state.into()),
+            allow_update_after_proposal: value.allow_update_after_proposal,
+        }
+    }
+}
+
+impl Protobuf<Any> for ClientState {}
+
+impl TryFrom<Any> for ClientState {
+    type Error = ClientError;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        use bytes::Buf;
+        use core::ops::Deref;
+
+        fn decode_client_state<B: Buf>(buf: B) -> Result<ClientState, Error> {
+            RawSmClientState::decode(buf)
+                .map_err(Error::Decode)?
+                .try_into()
+        }
+
+        match raw.type_url.as_str() {
+            SOLOMACHINE_CLIENT_STATE_TYPE_URL => {
+                decode_client_state(raw.value.deref()).map_err(Into::into)
+            }
+            _ => Err(ClientError::UnknownClientStateType {
+                client_state_type: raw.type_url,
+            }),
+        }
+    }
+}
+
+impl From<ClientState> for Any {
+    fn from(client_state: ClientState) -> Self {
+        Any {
+            type_url: SOLOMACHINE_CLIENT_STATE_TYPE_URL.to_string(),
+            value: Protobuf::<RawSmClientState>::encode_vec(&client_state)
+                .expect("encoding to `Any` from `SmClientState`"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    use ibc_proto::ibc::lightclients::solomachine::v1::SignatureAndData as RawSignatureAndData;
+    use tendermint::PublicKey;
+
+    use crate::clients::ics06_solomachine::client_state::test_util::{
+        get_dummy_solomachine_client_state, get_dummy_solomachine_keypair, sign, signature_proof,
+    };
+    use crate::clients::ics06_solomachine::misbehaviour::SignatureAndData;
+    use crate::core::ics02_client::msgs::create_client::MsgCreateClient;
+    use crate::core::ics02_client::msgs::misbehaviour::MsgSubmitMisbehaviour;
+    use crate::core::ics02_client::msgs::update_client::MsgUpdateClient;
+    use crate::core::ics02_client::msgs::ClientMsg;
+    use crate::core::ics03_connection::connection::{ConnectionEnd, Counterparty, State};
+    use crate::core::ics03_connection::context::ConnectionReader;
+    use crate::core::ics03_connection::msgs::conn_open_confirm::MsgConnectionOpenConfirm;
+    use crate::core::ics03_connection::msgs::ConnectionMsg;
+    use crate::core::ics03_connection::version::get_compatible_versions;
+    use crate::core::ics24_host::identifier::{ClientId, ConnectionId};
+    use crate::core::ics24_host::path::ConnectionsPath;
+    use crate::core::ics26_routing::msgs::MsgEnvelope;
+    use crate::mock::context::MockContext;
+    use crate::test_utils::get_dummy_account_id;
+
+    const DIVERSIFIER: &str = "diversifier";
+
+    fn timestamp(seconds: u64) -> Timestamp {
+        Timestamp::from_nanoseconds(seconds * 1_000_000_000).unwrap()
+    }
+
+    fn connection_end() -> ConnectionEnd {
+        ConnectionEnd::new(
+            State::Init,
+            ClientId::default(),
+            Counterparty::new(ClientId::default(), None, CommitmentPrefix::default()),
+            get_compatible_versions(),
+            Duration::from_secs(0),
+        )
+    }
+
+    fn connection_proof(
+        keypair: &tendermint::private_key::Ed25519,
+        sequence: u64,
+        timestamp: Timestamp,
+        prefix: &CommitmentPrefix,
+        connection_id: &ConnectionId,
+        connection_end: &ConnectionEnd,
+    ) -> CommitmentProofBytes {
+        let data = ConnectionStateData {
+            path: path_bytes(
+                prefix,
+                Path::Connections(ConnectionsPath(connection_id.clone())),
+            ),
+            connection: Some(connection_end.clone().into()),
+        };
+
+        signature_proof(
+            keypair,
+            sequence,
+            timestamp,
+            DIVERSIFIER.to_string(),
+            DataType::ConnectionState,
+            data.encode_to_vec(),
+        )
+    }
+
+    #[test]
+    fn client_state_proto_roundtrip() {
+        let keypair = get_dummy_solomachine_keypair("solo");
+        let client_state =
+            get_dummy_solomachine_client_state(&keypair, 1, DIVERSIFIER.to_string(), timestamp(10))
+                .with_frozen_sequence(3);
+
+        let any: Any = client_state.clone().into();
+        assert_eq!(ClientState::try_from(any).unwrap(), client_state);
+
+        let raw = RawSmClientState {
+            sequence: 0,
+            ..client_state.into()
+        };
+        assert!(ClientState::try_from(raw).is_err());
+    }
+
+    #[test]
+    fn client_state_check_header() {
+        let keypair = get_dummy_solomachine_keypair("solo");
+        let new_keypair = get_dummy_solomachine_keypair("solo-new");
+        let client_state =
+            get_dummy_solomachine_client_state(&keypair, 1, DIVERSIFIER.to_string(), timestamp(10));
+
+        let mut header = SmHeader {
+            sequence: 1,
+            timestamp: timestamp(20),
+            signature: vec![],
+            new_public_key: PublicKey::from(new_keypair.public),
+            new_diversifier: "new-diversifier".to_string(),
+        };
+        header.signature = sign(&keypair, &header.sign_bytes(DIVERSIFIER.to_string()));
+
+        let (new_client_state, new_consensus_state) =
+            client_state.check_header(header.clone()).unwrap();
+        assert_eq!(new_client_state.sequence, 2);
+        assert_eq!(new_consensus_state.public_key, header.new_public_key);
+        assert_eq!(new_consensus_state.diversifier, header.new_diversifier);
+        assert_eq!(new_client_state.consensus_state, new_consensus_state);
+
+        // The header must be signed by the current public key
+        let mut forged_header = header.clone();
+        forged_header.signature = sign(&new_keypair, &header.sign_bytes(DIVERSIFIER.to_string()));
+        assert!(client_state.check_header(forged_header).is_err());
+
+        // The header must be at the current sequence
+        assert!(new_client_state.check_header(header.clone()).is_err());
+
+        // The header timestamp cannot go backwards
+        let mut old_header = SmHeader {
+            timestamp: timestamp(5),
+            ..header
+        };
+        old_header.signature = sign(&keypair, &old_header.sign_bytes(DIVERSIFIER.to_string()));
+        assert!(client_state.check_header(old_header).is_err());
+    }
+
+    #[test]
+    fn client_state_check_misbehaviour() {
+        let keypair = get_dummy_solomachine_keypair("solo");
+        let client_state =
+            get_dummy_solomachine_client_state(&keypair, 1, DIVERSIFIER.to_string(), timestamp(10));
+
+        let signature_and_data = |data: Vec<u8>, keypair| {
+            let sign_bytes = sign_bytes(
+                1,
+                timestamp(20),
+                DIVERSIFIER.to_string(),
+                DataType::ConnectionState,
+                data.clone(),
+            );
+            SignatureAndData::try_from(RawSignatureAndData {
+                signature: sign(keypair, &sign_bytes),
+                data_type: DataType::ConnectionState.into(),
+                data,
+                timestamp: timestamp(20).nanoseconds(),
+            })
+            .unwrap()
+        };
+
+        let misbehaviour = SmMisbehaviour::new(
+            ClientId::default(),
+            1,
+            signature_and_data(b"one".to_vec(), &keypair),
+            signature_and_data(b"two".to_vec(), &keypair),
+        )
+        .unwrap();
+        let frozen_client_state = client_state.check_misbehaviour(misbehaviour).unwrap();
+        assert_eq!(frozen_client_state.frozen_sequence, Some(1));
+        assert!(frozen_client_state.is_frozen());
+
+        let other_keypair = get_dummy_solomachine_keypair("other");
+        let misbehaviour = SmMisbehaviour::new(
+            ClientId::default(),
+            1,
+            signature_and_data(b"one".to_vec(), &keypair),
+            signature_and_data(b"two".to_vec(), &other_keypair),
+        )
+        .unwrap();
+        assert!(client_state.check_misbehaviour(misbehaviour).is_err());
+    }
+
+    #[test]
+    fn client_state_verify_connection_state() {
+        let keypair = get_dummy_solomachine_keypair("solo");
+        let client_state =
+            get_dummy_solomachine_client_state(&keypair, 3, DIVERSIFIER.to_string(), timestamp(10));
+        let prefix = CommitmentPrefix::try_from(b"ibc".to_vec()).unwrap();
+        let connection_id = ConnectionId::new(0);
+        let connection_end = connection_end();
+        let height = Height::new(0, 3).unwrap();
+
+        let proof = connection_proof(
+            &keypair,
+            3,
+            timestamp(20),
+            &prefix,
+            &connection_id,
+            &connection_end,
+        );
+        let ctx = MockContext::default();
+        let path = Path::from(ConnectionsPath(connection_id.clone()));
+        let root = client_state.consensus_state.root();
+
+        client_state.validate_proof_height(height).unwrap();
+        client_state
+            .clone()
+            .verify_membership(
+                &ctx,
+                &prefix,
+                &proof,
+                root,
+                path.clone(),
+                connection_end.encode_vec().unwrap(),
+            )
+            .unwrap();
+
+        // Proving a different connection end must fail
+        let mut other_connection_end = connection_end.clone();
+        other_connection_end.set_state(State::Open);
+        assert!(client_state
+            .clone()
+            .verify_membership(
+                &ctx,
+                &prefix,
+                &proof,
+                root,
+                path.clone(),
+                other_connection_end.encode_vec().unwrap(),
+            )
+            .is_err());
+
+        // Proofs cannot refer to a sequence ahead of the client
+        assert!(client_state
+            .validate_proof_height(Height::new(0, 4).unwrap())
+            .is_err());
+
+        // Proofs cannot be older than the consensus state
+        let old_proof = connection_proof(
+            &keypair,
+            3,
+            timestamp(5),
+            &prefix,
+            &connection_id,
+            &connection_end,
+        );
+        assert!(client_state
+            .clone()
+            .verify_membership(
+                &ctx,
+                &prefix,
+                &old_proof,
+                root,
+                path,
+                connection_end.encode_vec().unwrap(),
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn client_state_proofs_consume_sequences() {
+        let keypair = get_dummy_solomachine_keypair("solo");
+        let client_state =
+            get_dummy_solomachine_client_state(&keypair, 3, DIVERSIFIER.to_string(), timestamp(10));
+        let prefix = CommitmentPrefix::try_from(b"ibc".to_vec()).unwrap();
+        let connection_id = ConnectionId::new(0);
+        let connection_end = connection_end();
+        let mut open_connection_end = connection_end.clone();
+        open_connection_end.set_state(State::Open);
+        let ctx = MockContext::default();
+        let path = Path::from(ConnectionsPath(connection_id.clone()));
+        let root = client_state.consensus_state.root();
+
+        // The solo machine legitimately signs the connection end at two successive sequences
+        let proof_one = connection_proof(
+            &keypair,
+            3,
+            timestamp(20),
+            &prefix,
+            &connection_id,
+            &connection_end,
+        );
+        let proof_two = connection_proof(
+            &keypair,
+            4,
+            timestamp(20),
+            &prefix,
+            &connection_id,
+            &open_connection_end,
+        );
+
+        let mut next_client_state = client_state.clone();
+        next_client_state
+            .verify_membership(
+                &ctx,
+                &prefix,
+                &proof_one,
+                root,
+                path.clone(),
+                connection_end.encode_vec().unwrap(),
+            )
+            .unwrap();
+
+        assert_eq!(
+            next_client_state.latest_height(),
+            Height::new(0, 4).unwrap()
+        );
+        assert_eq!(next_client_state.consensus_state.timestamp, timestamp(20));
+        next_client_state
+            .verify_membership(
+                &ctx,
+                &prefix,
+                &proof_two,
+                root,
+                path.clone(),
+                open_connection_end.encode_vec().unwrap(),
+            )
+            .unwrap();
+
+        // The first proof cannot be replayed
+        assert!(next_client_state
+            .verify_membership(
+                &ctx,
+                &prefix,
+                &proof_one,
+                root,
+                path,
+                connection_end.encode_vec().unwrap(),
+            )
+            .is_err());
+
+        // The two signatures are at different sequences, so they are not misbehaviour
+        let signature_and_data = |sequence: u64, connection_end: &ConnectionEnd| {
+            let data = ConnectionStateData {
+                path: path_bytes(
+                    &prefix,
+                    Path::Connections(ConnectionsPath(connection_id.clone())),
+                ),
+                connection: Some(connection_end.clone().into()),
+            }
+            .encode_to_vec();
+            let sign_bytes = sign_bytes(
+                sequence,
+                timestamp(20),
+                DIVERSIFIER.to_string(),
+                DataType::ConnectionState,
+                data.clone(),
+            );
+            SignatureAndData::try_from(RawSignatureAndData {
+                signature: sign(&keypair, &sign_bytes),
+                data_type: DataType::ConnectionState.into(),
+                data,
+                timestamp: timestamp(20).nanoseconds(),
+            })
+            .unwrap()
+        };
+        for sequence in [3, 4] {
+            let misbehaviour = SmMisbehaviour::new(
+                ClientId::default(),
+                sequence,
+                signature_and_data(3, &connection_end),
+                signature_and_data(4, &open_connection_end),
+            )
+            .unwrap();
+            assert!(client_state.check_misbehaviour(misbehaviour).is_err());
+        }
+    }
+
+    #[test]
+    fn solomachine_proof_verification_advances_sequence() {
+        let keypair = get_dummy_solomachine_keypair("solo");
+        let client_state =
+            get_dummy_solomachine_client_state(&keypair, 1, DIVERSIFIER.to_string(), timestamp(10));
+        let client_id_on_b = ClientId::new(sm_client_type(), 0).unwrap();
+        let conn_id_on_a = ConnectionId::new(1);
+        let conn_id_on_b = ConnectionId::new(0);
+        let prefix_on_a = CommitmentPrefix::try_from(b"solo".to_vec()).unwrap();
+
+        let conn_end_on_b = ConnectionEnd::new(
+            State::TryOpen,
+            client_id_on_b.clone(),
+            Counterparty::new(
+                ClientId::default(),
+                Some(conn_id_on_a.clone()),
+                prefix_on_a.clone(),
+            ),
+            get_compatible_versions(),
+            Duration::from_secs(0),
+        );
+        let context = || {
+            let mut ctx =
+                MockContext::default().with_connection(conn_id_on_b.clone(), conn_end_on_b.clone());
+            let msg = MsgCreateClient::new(
+                client_state.clone().into(),
+                client_state.consensus_state.clone().into(),
+                get_dummy_account_id(),
+            );
+            ctx.deliver(MsgEnvelope::Client(ClientMsg::CreateClient(msg)))
+                .unwrap();
+            ctx
+        };
+
+        let expected_conn_end_on_a = ConnectionEnd::new(
+            State::Open,
+            ClientId::default(),
+            Counterparty::new(
+                client_id_on_b.clone(),
+                Some(conn_id_on_b.clone()),
+                ConnectionReader::commitment_prefix(&MockContext::default()),
+            ),
+            get_compatible_versions(),
+            Duration::from_secs(0),
+        );
+        let msg = MsgEnvelope::Connection(ConnectionMsg::OpenConfirm(MsgConnectionOpenConfirm {
+            conn_id_on_b: conn_id_on_b.clone(),
+            proof_conn_end_on_a: connection_proof(
+                &keypair,
+                1,
+                timestamp(20),
+                &prefix_on_a,
+                &conn_id_on_a,
+                &expected_conn_end_on_a,
+            ),
+            proof_height_on_a: Height::new(0, 1).unwrap(),
+            signer: get_dummy_account_id(),
+        }));
+
+        let mut ctx = context();
+        ctx.deliver(msg.clone()).unwrap();
+
+        let client_state_on_b = ClientReader::client_state(&ctx, &client_id_on_b).unwrap();
+        assert_eq!(
+            client_state_on_b.latest_height(),
+            Height::new(0, 2).unwrap()
+        );
+        assert!(client_state_on_b.status(&ctx, &client_id_on_b).is_active());
+
+        #[cfg(feature = "val_exec_ctx")]
+        {
+            use crate::core::{ExecutionContext, ValidationContext};
+
+            let mut ctx = context();
+            ValidationContext::validate(&ctx, msg.clone()).unwrap();
+            ExecutionContext::execute(&mut ctx, msg).unwrap();
+
+            let client_state_on_b = ValidationContext::client_state(&ctx, &client_id_on_b).unwrap();
+            assert_eq!(
+                client_state_on_b.latest_height(),
+                Height::new(0, 2).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn solomachine_client_lifecycle() {
+        let mut ctx = MockContext::default();
+        let keypair = get_dummy_solomachine_keypair("solo");
+        let new_keypair = get_dummy_solomachine_keypair("solo-new");
+        let client_state =
+            get_dummy_solomachine_client_state(&keypair, 1, DIVERSIFIER.to_string(), timestamp(10));
+        let client_id = ClientId::new(sm_client_type(), 0).unwrap();
+
+        let msg = MsgCreateClient::new(
+            client_state.clone().into(),
+            client_state.consensus_state.clone().into(),
+            get_dummy_account_id(),
+        );
+        ctx.deliver(MsgEnvelope::Client(ClientMsg::CreateClient(msg)))
+            .unwrap();
+
+        let mut header = SmHeader {
+            sequence: 1,
+            timestamp: timestamp(20),
+            signature: vec![],
+            new_public_key: PublicKey::from(new_keypair.public),
+            new_diversifier: DIVERSIFIER.to_string(),
+        };
+        header.signature = sign(&keypair, &header.sign_bytes(DIVERSIFIER.to_string()));
+
+        let msg = MsgUpdateClient {
+            client_id: client_id.clone(),
+            header: header.clone().into(),
+            signer: get_dummy_account_id(),
+        };
+        ctx.deliver(MsgEnvelope::Client(ClientMsg::UpdateClient(msg)))
+            .unwrap();
+
+        let updated_client_state = ClientReader::client_state(&ctx, &client_id).unwrap();
+        assert_eq!(
+            updated_client_state.latest_height(),
+            Height::new(0, 2).unwrap()
+        );
+        assert!(
+            ClientReader::consensus_state(&ctx, &client_id, &Height::new(0, 2).unwrap()).is_ok()
+        );
+
+        // The replayed header is at a stale sequence
+        let msg = MsgUpdateClient {
+            client_id: client_id.clone(),
+            header: header.into(),
+            signer: get_dummy_account_id(),
+        };
+        assert!(ctx
+            .deliver(MsgEnvelope::Client(ClientMsg::UpdateClient(msg)))
+            .is_err());
+
+        // The new key signs two different messages at the same sequence
+        let signature_and_data = |data: Vec<u8>| {
+            let sign_bytes = sign_bytes(
+                2,
+                timestamp(30),
+                DIVERSIFIER.to_string(),
+                DataType::ChannelState,
+                data.clone(),
+            );
+            RawSignatureAndData {
+                signature: sign(&new_keypair, &sign_bytes),
+                data_type: DataType::ChannelState.into(),
+                data,
+                timestamp: timestamp(30).nanoseconds(),
+            }
+            .try_into()
+            .unwrap()
+        };
+        let misbehaviour = SmMisbehaviour::new(
+            client_id.clone(),
+            2,
+            signature_and_data(b"one".to_vec()),
+            signature_and_data(b"two".to_vec()),
+        )
+        .unwrap();
+
+        let msg = MsgSubmitMisbehaviour {
+            client_id: client_id.clone(),
+            misbehaviour: misbehaviour.into(),
+            signer: get_dummy_account_id(),
+        };
+        ctx.deliver(MsgEnvelope::Client(ClientMsg::Misbehaviour(msg)))
+            .unwrap();
+
+        let frozen_client_state = ClientReader::client_state(&ctx, &client_id).unwrap();
+        assert_eq!(
+            frozen_client_state.frozen_height(),
+            Some(Height::new(0, 2).unwrap())
+        );
+    }
+
+    #[test]
+    fn client_state_check_substitute_and_update_state() {
+        let keypair = get_dummy_solomachine_keypair("solo");
+        let subject =
+            get_dummy_solomachine_client_state(&keypair, 1, DIVERSIFIER.to_string(), timestamp(10))
+                .with_frozen_sequence(1);
+
+        let substitute_keypair = get_dummy_solomachine_keypair("substitute");
+        let substitute = get_dummy_solomachine_client_state(
+            &substitute_keypair,
+            4,
+            DIVERSIFIER.to_string(),
+            timestamp(20),
+        );
+
+        let recovered = subject
+            .check_substitute_and_update_state(&substitute)
+            .unwrap();
+        let recovered = downcast_client_state::<ClientState>(recovered.as_ref()).unwrap();
+        assert_eq!(recovered.sequence, 4);
+        assert_eq!(recovered.frozen_sequence, None);
+        assert_eq!(recovered.consensus_state, substitute.consensus_state);
+
+        let subject = ClientState {
+            allow_update_after_proposal: false,
+            ..subject
+        };
+        assert!(subject
+            .check_substitute_and_update_state(&substitute)
+            .is_err());
+    }
+}
+
+#[cfg(any(test, feature = "mocks"))]
+pub mod test_util {
+    use crate::prelude::*;
+
+    use ibc_proto::cosmos::tx::signing::v1beta1::signature_descriptor::{
+        data::{Single, Sum},
+        Data,
+    };
+    use ibc_proto::cosmos::tx::signing::v1beta1::SignMode;
+    use ibc_proto::ibc::lightclients::solomachine::v1::{DataType, TimestampedSignatureData};
+    use prost::Message;
+    use tendermint::private_key::Ed25519 as Ed25519Keypair;
+    use tendermint::signature::Signer;
+    use tendermint::PublicKey;
+    use tendermint_testgen::Validator;
+
+    use crate::clients::ics06_solomachine::client_state::ClientState;
+    use crate::clients::ics06_solomachine::consensus_state::ConsensusState;
+    use crate::clients::ics06_solomachine::proof::sign_bytes;
+    use crate::core::ics23_commitment::commitment::CommitmentProofBytes;
+    use crate::timestamp::Timestamp;
+
+    /// Returns a deterministic Ed25519 keypair derived from `id`.
+    pub fn get_dummy_solomachine_keypair(id: &str) -> Ed25519Keypair {
+        Validator::new(id).get_private_key().unwrap()
+    }
+
+    pub fn get_dummy_solomachine_client_state(
+        keypair: &Ed25519Keypair,
+        sequence: u64,
+        diversifier: String,
+        timestamp: Timestamp,
+    ) -> ClientState {
+        let consensus_state =
+            ConsensusState::new(PublicKey::from(keypair.public), diversifier, timestamp);
+
+        ClientState::new(sequence, consensus_state, true).unwrap()
+    }
+
+    /// Signs `sign_bytes` with `keypair`, and encodes the signature the way the Cosmos SDK does.
+    pub fn sign(keypair: &Ed25519Keypair, sign_bytes: &[u8]) -> Vec<u8> {
+        let signature = keypair.sign(sign_bytes);
+
+        Data {
+            sum: Some(Sum::Single(Single {
+                mode: SignMode::Direct.into(),
+                signature: signature.to_bytes().to_vec(),
+            })),
+        }
+        .encode_to_vec()
+    }
+
+    /// Returns a proof that the solo machine signed over `data` at `sequence`.
+    pub fn signature_proof(
+        keypair: &Ed25519Keypair,
+        sequence: u64,
+        timestamp: Timestamp,
+        diversifier: String,
+        data_type: DataType,
+        data: Vec<u8>,
+    ) -> CommitmentProofBytes {
+        let sign_bytes = sign_bytes(sequence, timestamp, diversifier, data_type, data);
+
+        TimestampedSignatureData {
+            signature_data: sign(keypair, &sign_bytes),
+            timestamp: timestamp.nanoseconds(),
+        }
+        .encode_to_vec()
+        .try_into()
+        .unwrap()
+    }
+}
@@ -0,0 +1,159 @@
+use crate::prelude::*;
+
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::lightclients::solomachine::v1::ConsensusState as RawConsensusState;
+use ibc_proto::protobuf::Protobuf;
+use prost::Message;
+use tendermint::PublicKey;
+
+use crate::clients::ics06_solomachine::error::Error;
+use crate::core::ics02_client::error::ClientError;
+use crate::core::ics23_commitment::commitment::CommitmentRoot;
+use crate::timestamp::Timestamp;
+
+pub const SOLOMACHINE_CONSENSUS_STATE_TYPE_URL: &str =
+    "/ibc.lightclients.solomachine.v1.ConsensusState";
+
+pub const ED25519_PUBLIC_KEY_TYPE_URL: &str = "/cosmos.crypto.ed25519.PubKey";
+
+pub const SECP256K1_PUBLIC_KEY_TYPE_URL: &str = "/cosmos.crypto.secp256k1.PubKey";
+
+/// Raw `cosmos.crypto.ed25519.PubKey` or `cosmos.crypto.secp256k1.PubKey`, which are not part of
+/// `ibc-proto`.
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+struct RawPublicKey {
+    #[prost(bytes = "vec", tag = "1")]
+    key: Vec<u8>,
+}
+
+/// The consensus state of a solo machine, i.e. the public key that signs its headers and proofs.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConsensusState {
+    pub public_key: PublicKey,
+    /// Allows the same public key to be re-used across different solo machine clients
+    /// (potentially on different chains) without being considered misbehaviour.
+    pub diversifier: String,
+    pub timestamp: Timestamp,
+    /// Solo machines do not commit to any state, so the public key stands in for the root.
+    root: CommitmentRoot,
+}
+
+impl ConsensusState {
+    pub fn new(public_key: PublicKey, diversifier: String, timestamp: Timestamp) -> Self {
+        Self {
+            root: CommitmentRoot::from_bytes(&public_key.to_bytes()),
+            public_key,
+            diversifier,
+            timestamp,
+        }
+    }
+}
+
+impl crate::core::ics02_client::consensus_state::ConsensusState for ConsensusState {
+    fn root(&self) -> &CommitmentRoot {
+        &self.root
+    }
+
+    fn timestamp(&self) -> Timestamp {
+        self.timestamp
+    }
+}
+
+impl Protobuf<RawConsensusState> for ConsensusState {}
+
+impl TryFrom<RawConsensusState> for ConsensusState {
+    type Error = Error;
+
+    fn try_from(raw: RawConsensusState) -> Result<Self, Self::Error> {
+        let public_key =
+            decode_public_key(raw.public_key.ok_or(Error::InvalidRawConsensusState {
+                reason: "missing public key".into(),
+            })?)?;
+        let timestamp =
+            Timestamp::from_nanoseconds(raw.timestamp).map_err(Error::InvalidTimestamp)?;
+
+        Ok(Self::new(public_key, raw.diversifier, timestamp))
+    }
+}
+
+impl From<ConsensusState> for RawConsensusState {
+    fn from(value: ConsensusState) -> Self {
+        RawConsensusState {
+            public_key: Some(encode_public_key(value.public_key)),
+            diversifier: value.diversifier,
+            timestamp: value.timestamp.nanoseconds(),
+        }
+    }
+}
+
+impl Protobuf<Any> for ConsensusState {}
+
+impl TryFrom<Any> for ConsensusState {
+    type Error = ClientError;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        use bytes::Buf;
+        use core::ops::Deref;
+
+        fn decode_consensus_state<B: Buf>(buf: B) -> Result<ConsensusState, Error> {
+            RawConsensusState::decode(buf)
+                .map_err(Error::Decode)?
+                .try_into()
+        }
+
+        match raw.type_url.as_str() {
+            SOLOMACHINE_CONSENSUS_STATE_TYPE_URL => {
+                decode_consensus_state(raw.value.deref()).map_err(Into::into)
+            }
+            _ => Err(ClientError::UnknownConsensusStateType {
+                consensus_state_type: raw.type_url,
+            }),
+        }
+    }
+}
+
+impl From<ConsensusState> for Any {
+    fn from(consensus_state: ConsensusState) -> Self {
+        Any {
+            type_url: SOLOMACHINE_CONSENSUS_STATE_TYPE_URL.to_string(),
+            value: Protobuf::<RawConsensusState>::encode_vec(&consensus_state)
+                .expect("encoding to `Any` from `SmConsensusState`"),
+        }
+    }
+}
+
+/// Decodes a public key from its Cosmos SDK `Any` encoding. Only Ed25519 keys are supported.
+pub(crate) fn decode_public_key(raw: Any) -> Result<PublicKey, Error> {
+    match raw.type_url.as_str() {
+        ED25519_PUBLIC_KEY_TYPE_URL => {
+            let raw_public_key =
+                RawPublicKey::decode(raw.value.as_slice()).map_err(Error::Decode)?;
+            PublicKey::from_raw_ed25519(&raw_public_key.key).ok_or(Error::InvalidPublicKey {
+                reason: "invalid Ed25519 public key bytes".into(),
+            })
+        }
+        _ => Err(Error::UnsupportedPublicKeyType {
+            type_url: raw.type_url,
+        }),
+    }
+}
+
+/// Encodes a public key into its Cosmos SDK `Any` encoding.
+pub(crate) fn encode_public_key(public_key: PublicKey) -> Any {
+    // Secp256k1 keys are only available if `tendermint/secp256k1` is enabled
+    #[allow(unreachable_patterns)]
+    let type_url = match public_key {
+        PublicKey::Ed25519(_) => ED25519_PUBLIC_KEY_TYPE_URL,
+        _ => SECP256K1_PUBLIC_KEY_TYPE_URL,
+    };
+
+    // Both key types share the same encoding, i.e. a message holding the raw key bytes
+    Any {
+        type_url: type_url.to_string(),
+        value: RawPublicKey {
+            key: public_key.to_bytes(),
+        }
+        .encode_to_vec(),
+    }
+}
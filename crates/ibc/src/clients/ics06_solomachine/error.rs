@@ -0,0 +1,74 @@
+use crate::prelude::*;
+
+use displaydoc::Display;
+use tendermint::Error as TendermintError;
+
+use crate::core::ics02_client::error::ClientError;
+use crate::timestamp::{ParseTimestampError, Timestamp};
+
+#[derive(Debug, Display)]
+pub enum Error {
+    /// invalid raw client state: `{reason}`
+    InvalidRawClientState { reason: String },
+    /// invalid raw consensus state: `{reason}`
+    InvalidRawConsensusState { reason: String },
+    /// invalid raw header: `{reason}`
+    InvalidRawHeader { reason: String },
+    /// invalid raw misbehaviour: `{reason}`
+    InvalidRawMisbehaviour { reason: String },
+    /// invalid raw client id: `{client_id}`
+    InvalidRawClientId { client_id: String },
+    /// invalid timestamp error: `{0}`
+    InvalidTimestamp(ParseTimestampError),
+    /// unsupported public key type: `{type_url}`
+    UnsupportedPublicKeyType { type_url: String },
+    /// invalid public key: `{reason}`
+    InvalidPublicKey { reason: String },
+    /// invalid signature data: `{reason}`
+    InvalidSignatureData { reason: String },
+    /// signature verification failed error: `{0}`
+    SignatureVerification(TendermintError),
+    /// decode error: `{0}`
+    Decode(prost::DecodeError),
+    /// sequence cannot be zero
+    ZeroSequence,
+    /// revision number must be zero for a solo machine, got: `{revision_number}`
+    NonZeroRevisionNumber { revision_number: u64 },
+    /// proof or header sequence `{sequence}` does not match the latest sequence `{latest_sequence}`
+    SequenceMismatch { sequence: u64, latest_sequence: u64 },
+    /// timestamp `{timestamp}` is lower than the consensus state timestamp `{consensus_timestamp}`
+    TimestampTooLow {
+        timestamp: Timestamp,
+        consensus_timestamp: Timestamp,
+    },
+    /// signatures of the misbehaviour must differ
+    MisbehaviourIdenticalSignatures,
+    /// data signed in the misbehaviour must differ
+    MisbehaviourIdenticalData,
+    /// solo machine clients cannot be upgraded
+    CannotUpgrade,
+    /// solo machine client does not allow updates through a substitute client
+    SubstituteNotAllowed,
+    /// solo machine client cannot verify values stored at `{path}`
+    UnsupportedPath { path: String },
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self {
+            Self::InvalidTimestamp(e) => Some(e),
+            Self::SignatureVerification(e) => Some(e),
+            Self::Decode(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<Error> for ClientError {
+    fn from(e: Error) -> Self {
+        Self::ClientSpecific {
+            description: e.to_string(),
+        }
+    }
+}
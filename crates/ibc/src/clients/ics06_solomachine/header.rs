@@ -0,0 +1,152 @@
+use crate::prelude::*;
+
+use bytes::Buf;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::lightclients::solomachine::v1::{
+    DataType, Header as RawHeader, HeaderData as RawHeaderData,
+};
+use ibc_proto::protobuf::Protobuf;
+use prost::Message;
+use tendermint::PublicKey;
+
+use crate::clients::ics06_solomachine::consensus_state::{decode_public_key, encode_public_key};
+use crate::clients::ics06_solomachine::error::Error;
+use crate::clients::ics06_solomachine::proof::sign_bytes;
+use crate::core::ics02_client::error::ClientError;
+use crate::timestamp::Timestamp;
+use crate::Height;
+
+pub const SOLOMACHINE_HEADER_TYPE_URL: &str = "/ibc.lightclients.solomachine.v1.Header";
+
+/// Solo machine header, which rotates the public key and diversifier of the solo machine at a
+/// given sequence. It is signed by the current public key.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Header {
+    pub sequence: u64,
+    pub timestamp: Timestamp,
+    /// Protobuf encoding of the Cosmos SDK `SignatureDescriptor.Data` over the header sign bytes
+    pub signature: Vec<u8>,
+    pub new_public_key: PublicKey,
+    pub new_diversifier: String,
+}
+
+impl Header {
+    pub fn height(&self) -> Height {
+        Height::new(0, self.sequence).expect("sequence cannot be zero")
+    }
+
+    /// The bytes the current public key of the solo machine, registered with `diversifier`,
+    /// signs over to attest to this header.
+    pub fn sign_bytes(&self, diversifier: String) -> Vec<u8> {
+        let data = RawHeaderData {
+            new_pub_key: Some(encode_public_key(self.new_public_key)),
+            new_diversifier: self.new_diversifier.clone(),
+        };
+
+        sign_bytes(
+            self.sequence,
+            self.timestamp,
+            diversifier,
+            DataType::Header,
+            data.encode_to_vec(),
+        )
+    }
+}
+
+impl crate::core::ics02_client::header::Header for Header {
+    fn height(&self) -> Height {
+        self.height()
+    }
+
+    fn timestamp(&self) -> Timestamp {
+        self.timestamp
+    }
+}
+
+impl Protobuf<RawHeader> for Header {}
+
+impl TryFrom<RawHeader> for Header {
+    type Error = Error;
+
+    fn try_from(raw: RawHeader) -> Result<Self, Self::Error> {
+        if raw.sequence == 0 {
+            return Err(Error::ZeroSequence);
+        }
+
+        if raw.timestamp == 0 {
+            return Err(Error::InvalidRawHeader {
+                reason: "timestamp cannot be zero".into(),
+            });
+        }
+
+        if raw.signature.is_empty() {
+            return Err(Error::InvalidRawHeader {
+                reason: "signature cannot be empty".into(),
+            });
+        }
+
+        if !raw.new_diversifier.is_empty() && raw.new_diversifier.trim().is_empty() {
+            return Err(Error::InvalidRawHeader {
+                reason: "diversifier cannot contain only spaces".into(),
+            });
+        }
+
+        let new_public_key =
+            decode_public_key(raw.new_public_key.ok_or(Error::InvalidRawHeader {
+                reason: "missing new public key".into(),
+            })?)?;
+
+        Ok(Self {
+            sequence: raw.sequence,
+            timestamp: Timestamp::from_nanoseconds(raw.timestamp)
+                .map_err(Error::InvalidTimestamp)?,
+            signature: raw.signature,
+            new_public_key,
+            new_diversifier: raw.new_diversifier,
+        })
+    }
+}
+
+impl From<Header> for RawHeader {
+    fn from(value: Header) -> Self {
+        RawHeader {
+            sequence: value.sequence,
+            timestamp: value.timestamp.nanoseconds(),
+            signature: value.signature,
+            new_public_key: Some(encode_public_key(value.new_public_key)),
+            new_diversifier: value.new_diversifier,
+        }
+    }
+}
+
+impl Protobuf<Any> for Header {}
+
+impl TryFrom<Any> for Header {
+    type Error = ClientError;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        use core::ops::Deref;
+
+        match raw.type_url.as_str() {
+            SOLOMACHINE_HEADER_TYPE_URL => decode_header(raw.value.deref()).map_err(Into::into),
+            _ => Err(ClientError::UnknownHeaderType {
+                header_type: raw.type_url,
+            }),
+        }
+    }
+}
+
+impl From<Header> for Any {
+    fn from(header: Header) -> Self {
+        Any {
+            type_url: SOLOMACHINE_HEADER_TYPE_URL.to_string(),
+            value: Protobuf::<RawHeader>::encode_vec(&header)
+                .expect("encoding to `Any` from `SmHeader`"),
+        }
+    }
+}
+
+pub fn decode_header<B: Buf>(buf: B) -> Result<Header, Error> {
+    RawHeader::decode(buf).map_err(Error::Decode)?.try_into()
+}
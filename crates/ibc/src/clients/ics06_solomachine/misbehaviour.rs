@@ -0,0 +1,234 @@
+use crate::prelude::*;
+
+use bytes::Buf;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::lightclients::solomachine::v1::{
+    DataType, Misbehaviour as RawMisbehaviour, SignatureAndData as RawSignatureAndData,
+};
+use ibc_proto::protobuf::Protobuf;
+use prost::Message;
+
+use crate::clients::ics06_solomachine::consensus_state::ConsensusState;
+use crate::clients::ics06_solomachine::error::Error;
+use crate::clients::ics06_solomachine::proof::{sign_bytes, verify_signature};
+use crate::core::ics02_client::error::ClientError;
+use crate::core::ics24_host::identifier::ClientId;
+use crate::timestamp::Timestamp;
+use crate::Height;
+
+pub const SOLOMACHINE_MISBEHAVIOUR_TYPE_URL: &str = "/ibc.lightclients.solomachine.v1.Misbehaviour";
+
+/// A signature over some data, as found in a solo machine misbehaviour.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignatureAndData {
+    /// Protobuf encoding of the Cosmos SDK `SignatureDescriptor.Data` over the sign bytes
+    pub signature: Vec<u8>,
+    pub data_type: DataType,
+    pub data: Vec<u8>,
+    pub timestamp: Timestamp,
+}
+
+impl SignatureAndData {
+    /// Verifies the signature against the public key of `consensus_state`, at `sequence`.
+    pub fn verify(&self, consensus_state: &ConsensusState, sequence: u64) -> Result<(), Error> {
+        let sign_bytes = sign_bytes(
+            sequence,
+            self.timestamp,
+            consensus_state.diversifier.clone(),
+            self.data_type,
+            self.data.clone(),
+        );
+
+        verify_signature(&consensus_state.public_key, &sign_bytes, &self.signature)
+    }
+}
+
+impl TryFrom<RawSignatureAndData> for SignatureAndData {
+    type Error = Error;
+
+    fn try_from(raw: RawSignatureAndData) -> Result<Self, Self::Error> {
+        if raw.signature.is_empty() {
+            return Err(Error::InvalidRawMisbehaviour {
+                reason: "signature cannot be empty".into(),
+            });
+        }
+
+        if raw.data.is_empty() {
+            return Err(Error::InvalidRawMisbehaviour {
+                reason: "data for signature cannot be empty".into(),
+            });
+        }
+
+        let data_type = match DataType::from_i32(raw.data_type) {
+            Some(DataType::UninitializedUnspecified) | None => {
+                return Err(Error::InvalidRawMisbehaviour {
+                    reason: format!("invalid data type `{}`", raw.data_type),
+                })
+            }
+            Some(data_type) => data_type,
+        };
+
+        if raw.timestamp == 0 {
+            return Err(Error::InvalidRawMisbehaviour {
+                reason: "timestamp cannot be zero".into(),
+            });
+        }
+
+        Ok(Self {
+            signature: raw.signature,
+            data_type,
+            data: raw.data,
+            timestamp: Timestamp::from_nanoseconds(raw.timestamp)
+                .map_err(Error::InvalidTimestamp)?,
+        })
+    }
+}
+
+impl From<SignatureAndData> for RawSignatureAndData {
+    fn from(value: SignatureAndData) -> Self {
+        RawSignatureAndData {
+            signature: value.signature,
+            data_type: value.data_type.into(),
+            data: value.data,
+            timestamp: value.timestamp.nanoseconds(),
+        }
+    }
+}
+
+/// Evidence of a solo machine signing two different messages at the same sequence.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Misbehaviour {
+    client_id: ClientId,
+    sequence: u64,
+    signature_one: SignatureAndData,
+    signature_two: SignatureAndData,
+}
+
+impl Misbehaviour {
+    pub fn new(
+        client_id: ClientId,
+        sequence: u64,
+        signature_one: SignatureAndData,
+        signature_two: SignatureAndData,
+    ) -> Result<Self, Error> {
+        if sequence == 0 {
+            return Err(Error::ZeroSequence);
+        }
+
+        if signature_one.signature == signature_two.signature {
+            return Err(Error::MisbehaviourIdenticalSignatures);
+        }
+
+        if signature_one.data == signature_two.data {
+            return Err(Error::MisbehaviourIdenticalData);
+        }
+
+        Ok(Self {
+            client_id,
+            sequence,
+            signature_one,
+            signature_two,
+        })
+    }
+
+    pub fn client_id(&self) -> &ClientId {
+        &self.client_id
+    }
+
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    pub fn signature_one(&self) -> &SignatureAndData {
+        &self.signature_one
+    }
+
+    pub fn signature_two(&self) -> &SignatureAndData {
+        &self.signature_two
+    }
+}
+
+impl crate::core::ics02_client::misbehaviour::Misbehaviour for Misbehaviour {
+    fn client_id(&self) -> &ClientId {
+        &self.client_id
+    }
+
+    fn height(&self) -> Height {
+        Height::new(0, self.sequence).expect("sequence cannot be zero")
+    }
+}
+
+impl Protobuf<RawMisbehaviour> for Misbehaviour {}
+
+impl TryFrom<RawMisbehaviour> for Misbehaviour {
+    type Error = Error;
+
+    fn try_from(raw: RawMisbehaviour) -> Result<Self, Self::Error> {
+        let client_id = raw
+            .client_id
+            .parse()
+            .map_err(|_| Error::InvalidRawClientId {
+                client_id: raw.client_id.clone(),
+            })?;
+        let signature_one = raw
+            .signature_one
+            .ok_or_else(|| Error::InvalidRawMisbehaviour {
+                reason: "missing signature one".into(),
+            })?
+            .try_into()?;
+        let signature_two = raw
+            .signature_two
+            .ok_or_else(|| Error::InvalidRawMisbehaviour {
+                reason: "missing signature two".into(),
+            })?
+            .try_into()?;
+
+        Self::new(client_id, raw.sequence, signature_one, signature_two)
+    }
+}
+
+impl From<Misbehaviour> for RawMisbehaviour {
+    fn from(value: Misbehaviour) -> Self {
+        RawMisbehaviour {
+            client_id: value.client_id.to_string(),
+            sequence: value.sequence,
+            signature_one: Some(value.signature_one.into()),
+            signature_two: Some(value.signature_two.into()),
+        }
+    }
+}
+
+impl Protobuf<Any> for Misbehaviour {}
+
+impl TryFrom<Any> for Misbehaviour {
+    type Error = ClientError;
+
+    fn try_from(raw: Any) -> Result<Self, ClientError> {
+        use core::ops::Deref;
+
+        match raw.type_url.as_str() {
+            SOLOMACHINE_MISBEHAVIOUR_TYPE_URL => {
+                decode_misbehaviour(raw.value.deref()).map_err(Into::into)
+            }
+            _ => Err(ClientError::UnknownMisbehaviourType {
+                misbehaviour_type: raw.type_url,
+            }),
+        }
+    }
+}
+
+impl From<Misbehaviour> for Any {
+    fn from(misbehaviour: Misbehaviour) -> Self {
+        Any {
+            type_url: SOLOMACHINE_MISBEHAVIOUR_TYPE_URL.to_string(),
+            value: Protobuf::<RawMisbehaviour>::encode_vec(&misbehaviour)
+                .expect("encoding to `Any` from `SmMisbehaviour`"),
+        }
+    }
+}
+
+pub fn decode_misbehaviour<B: Buf>(buf: B) -> Result<Misbehaviour, Error> {
+    RawMisbehaviour::decode(buf)
+        .map_err(Error::Decode)?
+        .try_into()
+}
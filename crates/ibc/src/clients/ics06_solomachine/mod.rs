@@ -0,0 +1,20 @@
+//! ICS 06: Solo Machine Client implements a client verification algorithm for standalone
+//! processes, such as off-chain services or wallets, which hold a single key and sign over their
+//! state at an incrementing sequence.
+
+use alloc::string::ToString;
+
+use crate::core::ics02_client::client_type::ClientType;
+
+pub mod client_state;
+pub mod consensus_state;
+pub mod error;
+pub mod header;
+pub mod misbehaviour;
+pub mod proof;
+
+pub(crate) const SOLOMACHINE_CLIENT_TYPE: &str = "06-solomachine";
+
+pub fn client_type() -> ClientType {
+    ClientType::new(SOLOMACHINE_CLIENT_TYPE.to_string())
+}
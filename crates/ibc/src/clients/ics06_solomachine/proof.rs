@@ -0,0 +1,122 @@
+//! Signature verification for solo machine headers, misbehaviours and proofs. A solo machine
+//! proves a statement by signing over the `SignBytes` of its data, at its latest sequence.
+
+use crate::prelude::*;
+
+use ibc_proto::cosmos::tx::signing::v1beta1::signature_descriptor::{
+    data::Sum as RawSignatureDataSum, Data as RawSignatureData,
+};
+use ibc_proto::ibc::lightclients::solomachine::v1::{DataType, SignBytes};
+use prost::Message;
+use tendermint::{PublicKey, Signature};
+
+use crate::clients::ics06_solomachine::error::Error;
+use crate::core::ics23_commitment::commitment::CommitmentPrefix;
+use crate::core::ics24_host::Path;
+use crate::timestamp::Timestamp;
+
+/// Builds the bytes a solo machine signs over to attest to `data` at `sequence`.
+pub(crate) fn sign_bytes(
+    sequence: u64,
+    timestamp: Timestamp,
+    diversifier: String,
+    data_type: DataType,
+    data: Vec<u8>,
+) -> Vec<u8> {
+    SignBytes {
+        sequence,
+        timestamp: timestamp.nanoseconds(),
+        diversifier,
+        data_type: data_type.into(),
+        data,
+    }
+    .encode_to_vec()
+}
+
+/// Verifies that `signature_data`, the protobuf encoding of a Cosmos SDK single signer
+/// `SignatureDescriptor.Data`, holds a valid signature of `sign_bytes` by `public_key`.
+pub(crate) fn verify_signature(
+    public_key: &PublicKey,
+    sign_bytes: &[u8],
+    signature_data: &[u8],
+) -> Result<(), Error> {
+    let signature = match RawSignatureData::decode(signature_data)
+        .map_err(Error::Decode)?
+        .sum
+    {
+        Some(RawSignatureDataSum::Single(single)) => single.signature,
+        Some(RawSignatureDataSum::Multi(_)) => {
+            return Err(Error::InvalidSignatureData {
+                reason: "multisignatures are not supported".into(),
+            })
+        }
+        None => {
+            return Err(Error::InvalidSignatureData {
+                reason: "missing signature".into(),
+            })
+        }
+    };
+
+    let signature = Signature::try_from(signature).map_err(Error::SignatureVerification)?;
+
+    public_key
+        .verify(sign_bytes, &signature)
+        .map_err(Error::SignatureVerification)
+}
+
+/// Returns the bytes of `path` under `prefix` that a solo machine signs over, i.e. the string
+/// representation of the corresponding merkle path in ibc-go.
+pub(crate) fn path_bytes(prefix: &CommitmentPrefix, path: impl Into<Path>) -> Vec<u8> {
+    let prefix = String::from_utf8_lossy(prefix.as_bytes());
+    let path = path.into().to_string();
+
+    format!(
+        "/{}/{}",
+        escape_path_segment(&prefix),
+        escape_path_segment(&path)
+    )
+    .into_bytes()
+}
+
+/// Escapes a path segment the same way Go's `url.PathEscape` does.
+fn escape_path_segment(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|b| match b {
+            b'a'..=b'z'
+            | b'A'..=b'Z'
+            | b'0'..=b'9'
+            | b'-'
+            | b'_'
+            | b'.'
+            | b'~'
+            | b'$'
+            | b'&'
+            | b'+'
+            | b':'
+            | b'='
+            | b'@' => (b as char).to_string(),
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    use crate::core::ics24_host::identifier::ConnectionId;
+    use crate::core::ics24_host::path::ConnectionsPath;
+
+    #[test]
+    fn path_bytes_escape_keys() {
+        let prefix = CommitmentPrefix::try_from(b"ibc".to_vec()).unwrap();
+        let path = ConnectionsPath(ConnectionId::new(0));
+
+        assert_eq!(
+            path_bytes(&prefix, path),
+            b"/ibc/connections%2Fconnection-0".to_vec()
+        );
+    }
+}
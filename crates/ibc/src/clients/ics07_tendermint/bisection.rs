@@ -0,0 +1,296 @@
+//! Bisection of the headers which update a Tendermint client from a trusted height to a target
+//! height, for clients which fell too far behind to verify the target header directly.
+
+use crate::prelude::*;
+
+use tendermint::validator::Set as ValidatorSet;
+use tendermint::Time;
+use tendermint_light_client_verifier::types::LightBlock;
+use tendermint_light_client_verifier::{Verdict, Verifier};
+
+use crate::clients::ics07_tendermint::client_state::ClientState;
+use crate::clients::ics07_tendermint::consensus_state::ConsensusState;
+use crate::clients::ics07_tendermint::error::Error;
+use crate::clients::ics07_tendermint::header::Header;
+use crate::host_functions::HostFunctionsProvider;
+use crate::Height;
+
+/// Provides the light blocks of the chain tracked by a Tendermint client, e.g. from the RPC
+/// endpoint of a full node.
+pub trait HeaderProvider {
+    /// Returns the light block at `height`.
+    fn light_block(&self, height: Height) -> Result<LightBlock, Error>;
+}
+
+/// Returns the headers to submit, in order, to update a Tendermint client from the consensus
+/// state at `trusted_height` to the `target` light block.
+///
+/// Whenever the validators the client trusts do not hold enough voting power, with respect to
+/// its trust level, in the commit of the target, the client is first updated to the highest
+/// header they can verify, which is found by bisection. This yields the fewest headers, as long
+/// as the trusted validators keep losing voting power with the height. Headers are verified at
+/// time `now`, with the same verifier and options as on client updates.
+pub fn bisect_headers<H: HostFunctionsProvider>(
+    client_state: &ClientState<H>,
+    trusted_height: Height,
+    trusted_consensus_state: &ConsensusState,
+    target: LightBlock,
+    provider: &impl HeaderProvider,
+    now: Time,
+) -> Result<Vec<Header>, Error> {
+    let revision_number = trusted_height.revision_number();
+    let target_height = Height::new(revision_number, target.height().value()).map_err(|_| {
+        Error::InvalidHeaderHeight {
+            height: target.height().value(),
+        }
+    })?;
+    if target_height <= trusted_height {
+        return Err(Error::InvalidBisectionTarget {
+            trusted_height,
+            target_height,
+        });
+    }
+
+    let trusted_validator_set = provider.light_block(trusted_height)?.next_validators;
+    let trusted_val_hash = trusted_validator_set.hash();
+    if trusted_val_hash != trusted_consensus_state.next_validators_hash {
+        return Err(Error::TrustedValidatorHashMismatch {
+            next_validators_hash: trusted_consensus_state.next_validators_hash,
+            trusted_val_hash,
+        });
+    }
+
+    let chain_id = client_state.chain_id.clone().into();
+    let options = client_state.as_light_client_options()?;
+
+    let header_of = |trusted: &TrustedHeader, light_block: &LightBlock| Header {
+        signed_header: light_block.signed_header.clone(),
+        validator_set: light_block.validators.clone(),
+        trusted_height: trusted.height,
+        trusted_validator_set: trusted.next_validators.clone(),
+    };
+    let verify = |trusted: &TrustedHeader, header: &Header| -> Result<Verdict, Error> {
+        Ok(client_state.verifier().verify(
+            header.as_untrusted_block_state(),
+            header.as_trusted_block_state(&trusted.consensus_state, &chain_id)?,
+            &options,
+            now,
+        ))
+    };
+
+    let mut trusted = TrustedHeader {
+        height: trusted_height,
+        consensus_state: trusted_consensus_state.clone(),
+        next_validators: trusted_validator_set,
+    };
+    let mut headers = Vec::new();
+
+    loop {
+        let header = header_of(&trusted, &target);
+        let reason = match verify(&trusted, &header)? {
+            Verdict::Success => {
+                headers.push(header);
+                return Ok(headers);
+            }
+            Verdict::NotEnoughTrust(reason) => reason,
+            Verdict::Invalid(detail) => return Err(Error::VerificationError { detail }),
+        };
+
+        // Search for the highest header below the target which the trusted validators can
+        // verify. Adjacent headers are verified against the trusted next validators rather than
+        // with the trust level, so there is one unless the chain is invalid.
+        let mut low = None;
+        let mut low_height = trusted.height.revision_height();
+        let mut high_height = target_height.revision_height();
+        while high_height - low_height > 1 {
+            let mid_height = low_height + (high_height - low_height) / 2;
+            let light_block = provider.light_block(
+                Height::new(revision_number, mid_height).expect("height is above the trusted one"),
+            )?;
+
+            let header = header_of(&trusted, &light_block);
+            match verify(&trusted, &header)? {
+                Verdict::Success => {
+                    low = Some((header, light_block.next_validators));
+                    low_height = mid_height;
+                }
+                Verdict::NotEnoughTrust(_) => high_height = mid_height,
+                Verdict::Invalid(detail) => return Err(Error::VerificationError { detail }),
+            }
+        }
+
+        let (header, next_validators) = low.ok_or(Error::NotEnoughTrustedValsSigned { reason })?;
+
+        trusted = TrustedHeader {
+            height: header.height(),
+            consensus_state: ConsensusState::from(header.clone()),
+            next_validators,
+        };
+        headers.push(header);
+    }
+}
+
+/// The latest header the client is updated with.
+struct TrustedHeader {
+    height: Height,
+    consensus_state: ConsensusState,
+    next_validators: ValidatorSet,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::collections::btree_map::BTreeMap;
+
+    use tendermint_testgen::{Commit, Generator, Header as TestgenHeader, Validator};
+    use test_log::test;
+
+    use crate::clients::ics07_tendermint::client_state::test_util::get_dummy_tendermint_client_state;
+
+    /// Serves the light blocks of a chain from memory.
+    struct InMemoryProvider {
+        light_blocks: BTreeMap<Height, LightBlock>,
+    }
+
+    impl HeaderProvider for InMemoryProvider {
+        fn light_block(&self, height: Height) -> Result<LightBlock, Error> {
+            self.light_blocks
+                .get(&height)
+                .cloned()
+                .ok_or_else(|| Error::HeaderProviderFailure {
+                    reason: format!("no light block at height {height}"),
+                })
+        }
+    }
+
+    fn validators(ids: &[&str]) -> Vec<Validator> {
+        ids.iter()
+            .map(|id| Validator::new(id).voting_power(50))
+            .collect()
+    }
+
+    fn light_block(height: u64, validators: &[Validator], next: &[Validator]) -> LightBlock {
+        let header = TestgenHeader::new(validators)
+            .height(height)
+            .chain_id("test-chain")
+            .next_validators(next)
+            .time(Time::from_unix_timestamp(height as i64, 0).unwrap());
+        let commit = Commit::new(header.clone(), 1);
+        let light_block = tendermint_testgen::LightBlock::new(header, commit)
+            .validators(validators)
+            .next_validators(next)
+            .generate()
+            .unwrap();
+
+        LightBlock::new(
+            light_block.signed_header,
+            light_block.validators,
+            light_block.next_validators,
+            light_block.provider,
+        )
+    }
+
+    /// Generates the light blocks at heights `1..=20` of a chain whose validators `a` and `b`
+    /// hand over to validators `c` and `d` from the block at `handover_height` on.
+    fn provider(handover_height: u64) -> InMemoryProvider {
+        let (old, new) = (validators(&["a", "b"]), validators(&["c", "d"]));
+        let validators_at = |height| {
+            if height < handover_height {
+                &old
+            } else {
+                &new
+            }
+        };
+
+        let light_blocks = (1..=20)
+            .map(|height| {
+                (
+                    Height::new(0, height).unwrap(),
+                    light_block(height, validators_at(height), validators_at(height + 1)),
+                )
+            })
+            .collect();
+
+        InMemoryProvider { light_blocks }
+    }
+
+    fn bisect(
+        provider: &InMemoryProvider,
+        trusted_height: u64,
+        target_height: u64,
+    ) -> Result<Vec<Header>, Error> {
+        let trusted_height = Height::new(0, trusted_height).unwrap();
+        let trusted_block = provider.light_block(trusted_height).unwrap();
+        let target = provider
+            .light_block(Height::new(0, target_height).unwrap())
+            .unwrap();
+        let client_state =
+            get_dummy_tendermint_client_state(trusted_block.signed_header.header.clone());
+        let trusted_consensus_state = ConsensusState::from(trusted_block.signed_header.header);
+
+        bisect_headers(
+            &client_state,
+            trusted_height,
+            &trusted_consensus_state,
+            target,
+            provider,
+            Time::from_unix_timestamp(100, 0).unwrap(),
+        )
+    }
+
+    fn heights(headers: &[Header]) -> Vec<(u64, u64)> {
+        headers
+            .iter()
+            .map(|h| {
+                (
+                    h.trusted_height.revision_height(),
+                    h.height().revision_height(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn verifies_target_directly() {
+        let provider = provider(u64::MAX);
+
+        let headers = bisect(&provider, 1, 20).unwrap();
+        assert_eq!(heights(&headers), vec![(1, 20)]);
+    }
+
+    #[test]
+    fn bisects_across_validator_set_change() {
+        let provider = provider(11);
+
+        let headers = bisect(&provider, 1, 20).unwrap();
+        assert_eq!(heights(&headers), vec![(1, 10), (10, 20)]);
+
+        // The validators trusted by each header are the next validators of its trusted header
+        assert_eq!(
+            headers[1].trusted_validator_set,
+            provider
+                .light_block(Height::new(0, 10).unwrap())
+                .unwrap()
+                .next_validators
+        );
+    }
+
+    #[test]
+    fn updates_to_highest_verifiable_header() {
+        let provider = provider(8);
+
+        let headers = bisect(&provider, 1, 20).unwrap();
+        assert_eq!(heights(&headers), vec![(1, 7), (7, 20)]);
+    }
+
+    #[test]
+    fn rejects_target_below_trusted_height() {
+        let provider = provider(u64::MAX);
+
+        assert!(matches!(
+            bisect(&provider, 10, 10),
+            Err(Error::InvalidBisectionTarget { .. })
+        ));
+    }
+}
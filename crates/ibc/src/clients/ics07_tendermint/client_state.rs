@@ -5,6 +5,7 @@ use core::time::Duration;
 
 use ibc_proto::google::protobuf::Any;
 use ibc_proto::ibc::core::client::v1::Height as RawHeight;
+use ibc_proto::ibc::core::commitment::v1::MerklePath;
 use ibc_proto::ibc::core::commitment::v1::MerkleProof as RawMerkleProof;
 use ibc_proto::ibc::lightclients::tendermint::v1::ClientState as RawTmClientState;
 use ibc_proto::protobuf::Protobuf;
@@ -13,35 +14,31 @@ use tendermint::chain::id::MAX_LENGTH as MaxChainIdLen;
 use tendermint::trust_threshold::TrustThresholdFraction as TendermintTrustThresholdFraction;
 use tendermint_light_client_verifier::options::Options;
 use tendermint_light_client_verifier::types::{TrustedBlockState, UntrustedBlockState};
-use tendermint_light_client_verifier::{ProdVerifier, Verifier};
+use tendermint_light_client_verifier::Verifier;
 
 use crate::clients::ics07_tendermint::consensus_state::ConsensusState as TmConsensusState;
 use crate::clients::ics07_tendermint::error::{Error, IntoResult};
 use crate::clients::ics07_tendermint::header::{Header as TmHeader, Header};
 use crate::clients::ics07_tendermint::misbehaviour::Misbehaviour as TmMisbehaviour;
+use crate::clients::ics07_tendermint::verifier::HostVerifier;
 use crate::core::ics02_client::client_state::{
-    ClientState as Ics2ClientState, UpdatedState, UpgradeOptions as CoreUpgradeOptions,
+    ClientState as Ics2ClientState, Status, UpdatedState, UpgradeOptions as CoreUpgradeOptions,
 };
 use crate::core::ics02_client::client_type::ClientType;
 use crate::core::ics02_client::consensus_state::ConsensusState;
 use crate::core::ics02_client::context::ClientReader;
 use crate::core::ics02_client::error::ClientError;
 use crate::core::ics02_client::trust_threshold::TrustThreshold;
-use crate::core::ics03_connection::connection::ConnectionEnd;
-use crate::core::ics04_channel::commitment::{AcknowledgementCommitment, PacketCommitment};
 use crate::core::ics04_channel::context::ChannelReader;
-use crate::core::ics04_channel::packet::Sequence;
 use crate::core::ics23_commitment::commitment::{
     CommitmentPrefix, CommitmentProofBytes, CommitmentRoot,
 };
 use crate::core::ics23_commitment::merkle::{apply_prefix, MerkleProof};
 use crate::core::ics23_commitment::specs::ProofSpecs;
-use crate::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId};
-use crate::core::ics24_host::path::{
-    AcksPath, ChannelEndsPath, ClientConsensusStatePath, ClientStatePath, CommitmentsPath,
-    ConnectionsPath, ReceiptsPath, SeqRecvsPath,
-};
+use crate::core::ics24_host::identifier::{ChainId, ClientId};
+use crate::core::ics24_host::path::ClientUpgradePath;
 use crate::core::ics24_host::Path;
+use crate::host_functions::{HostFunctionsManager, HostFunctionsProvider};
 use crate::timestamp::{Timestamp, ZERO_DURATION};
 use crate::Height;
 
@@ -53,10 +50,13 @@ use crate::core::context::ContextError;
 use crate::core::ValidationContext;
 
 pub const TENDERMINT_CLIENT_STATE_TYPE_URL: &str = "/ibc.lightclients.tendermint.v1.ClientState";
+/// Stand-in root of the consensus state created upon a client upgrade.
+pub const SENTINEL_ROOT: &str = "sentinel_root";
 
+/// The Tendermint client, which verifies headers and proofs with the host functions `H`.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub struct ClientState {
+pub struct ClientState<H = HostFunctionsManager> {
     pub chain_id: ChainId,
     pub trust_level: TrustThreshold,
     pub trusting_period: Duration,
@@ -68,7 +68,7 @@ pub struct ClientState {
     allow_update: AllowUpdate,
     frozen_height: Option<Height>,
     #[cfg_attr(feature = "serde", serde(skip))]
-    verifier: ProdVerifier,
+    verifier: HostVerifier<H>,
 }
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -78,7 +78,7 @@ pub struct AllowUpdate {
     pub after_misbehaviour: bool,
 }
 
-impl ClientState {
+impl<H: HostFunctionsProvider> ClientState<H> {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         chain_id: ChainId,
@@ -91,7 +91,7 @@ impl ClientState {
         upgrade_path: Vec<String>,
         allow_update: AllowUpdate,
         frozen_height: Option<Height>,
-    ) -> Result<ClientState, Error> {
+    ) -> Result<Self, Error> {
         if chain_id.as_str().len() > MaxChainIdLen {
             return Err(Error::ChainIdTooLong {
                 chain_id: chain_id.clone(),
@@ -181,7 +181,7 @@ impl ClientState {
             upgrade_path,
             allow_update,
             frozen_height,
-            verifier: ProdVerifier::default(),
+            verifier: HostVerifier::default(),
         })
     }
 
@@ -189,6 +189,18 @@ impl ClientState {
         self.latest_height
     }
 
+    /// Resets all fields chosen by the client (i.e. by the relayer that created it) to their
+    /// zero values, leaving only the chain-chosen fields in place. This is the form in which
+    /// a chain commits to the client state it upgrades to.
+    pub fn zero_custom_fields(&mut self) {
+        self.trusting_period = ZERO_DURATION;
+        self.trust_level = TrustThreshold::ZERO;
+        self.allow_update.after_expiry = false;
+        self.allow_update.after_misbehaviour = false;
+        self.frozen_height = None;
+        self.max_clock_drift = ZERO_DURATION;
+    }
+
     pub fn with_header(self, h: TmHeader) -> Result<Self, Error> {
         Ok(ClientState {
             latest_height: Height::new(
@@ -214,6 +226,10 @@ impl ClientState {
         Some(2 * self.trusting_period / 3)
     }
 
+    pub(crate) fn verifier(&self) -> &HostVerifier<H> {
+        &self.verifier
+    }
+
     /// Helper method to produce a [`Options`] struct for use in
     /// Tendermint-specific light client verification.
     pub fn as_light_client_options(&self) -> Result<Options, Error> {
@@ -359,7 +375,7 @@ pub struct UpgradeOptions {
 
 impl CoreUpgradeOptions for UpgradeOptions {}
 
-impl Ics2ClientState for ClientState {
+impl<H: HostFunctionsProvider> Ics2ClientState for ClientState<H> {
     fn chain_id(&self) -> ChainId {
         self.chain_id.clone()
     }
@@ -388,12 +404,7 @@ impl Ics2ClientState for ClientState {
             .expect("UpgradeOptions not of type Tendermint");
 
         // Reset custom fields to zero values
-        self.trusting_period = ZERO_DURATION;
-        self.trust_level = TrustThreshold::ZERO;
-        self.allow_update.after_expiry = false;
-        self.allow_update.after_misbehaviour = false;
-        self.frozen_height = None;
-        self.max_clock_drift = ZERO_DURATION;
+        self.zero_custom_fields();
 
         // Upgrade the client state
         self.latest_height = upgrade_height;
@@ -405,6 +416,30 @@ impl Ics2ClientState for ClientState {
         elapsed > self.trusting_period
     }
 
+    /// A frozen client may only be recovered if it allows updates after misbehaviour, and an
+    /// expired one if it allows updates after expiry.
+    ///
+    /// `AllowUpdate` does not bear on the status itself: a frozen or expired client stays
+    /// inactive whatever its flags, and only a recovery or an upgrade, where allowed, may
+    /// re-activate it.
+    fn allows_recovery(&self, status: Status) -> bool {
+        match status {
+            Status::Frozen => self.allow_update.after_misbehaviour,
+            Status::Expired => self.allow_update.after_expiry,
+            Status::Active | Status::Unknown => false,
+        }
+    }
+
+    /// An expired client may be upgraded if it allows updates after expiry, whereas a frozen
+    /// client may never be.
+    fn allows_upgrade(&self, status: Status) -> bool {
+        match status {
+            Status::Active => true,
+            Status::Expired => self.allow_update.after_expiry,
+            Status::Frozen | Status::Unknown => false,
+        }
+    }
+
     fn initialise(&self, consensus_state: Any) -> Result<Box<dyn ConsensusState>, ClientError> {
         TmConsensusState::try_from(consensus_state).map(TmConsensusState::into_box)
     }
@@ -432,7 +467,7 @@ impl Ics2ClientState for ClientState {
             }
         }
 
-        let client_state = downcast_tm_client_state(self)?.clone();
+        let client_state = downcast_tm_client_state::<H>(self)?.clone();
         let header = TmHeader::try_from(header)?;
 
         if header.height().revision_number() != client_state.chain_id().version() {
@@ -511,7 +546,9 @@ impl Ics2ClientState for ClientState {
 
         // If the header has verified, but its corresponding consensus state
         // differs from the existing consensus state for that height, freeze the
-        // client and return the installed consensus state.
+        // client and return the installed consensus state. The same goes for
+        // the timestamp checks below, which return the neighbouring consensus
+        // state that the header conflicts with.
         if let Some(cs) = existing_consensus_state {
             if cs != header_consensus_state {
                 return Ok(UpdatedState {
@@ -531,15 +568,13 @@ impl Ics2ClientState for ClientState {
                 .transpose()?;
 
             if let Some(next_cs) = maybe_next_cs {
-                // New (untrusted) header timestamp cannot occur after next
-                // consensus state's height
-                if header.signed_header.header().time > next_cs.timestamp {
-                    return Err(ClientError::ClientSpecific {
-                        description: Error::HeaderTimestampTooHigh {
-                            actual: header.signed_header.header().time.to_string(),
-                            max: next_cs.timestamp.to_string(),
-                        }
-                        .to_string(),
+                // BFT time must be monotonic: a verified header at a lower
+                // height than the next consensus state, but with a timestamp
+                // that is not before it, is evidence of misbehaviour.
+                if header.signed_header.header().time >= next_cs.timestamp {
+                    return Ok(UpdatedState {
+                        client_state: client_state.with_frozen_height(header.height()).into_box(),
+                        consensus_state: next_cs.into_box(),
                     });
                 }
             }
@@ -554,15 +589,12 @@ impl Ics2ClientState for ClientState {
                 .transpose()?;
 
             if let Some(prev_cs) = maybe_prev_cs {
-                // New (untrusted) header timestamp cannot occur before the
-                // previous consensus state's height
-                if header.signed_header.header().time < prev_cs.timestamp {
-                    return Err(ClientError::ClientSpecific {
-                        description: Error::HeaderTimestampTooLow {
-                            actual: header.signed_header.header().time.to_string(),
-                            min: prev_cs.timestamp.to_string(),
-                        }
-                        .to_string(),
+                // Likewise, a verified header at a greater height than the
+                // previous consensus state must have a later timestamp.
+                if header.signed_header.header().time <= prev_cs.timestamp {
+                    return Ok(UpdatedState {
+                        client_state: client_state.with_frozen_height(header.height()).into_box(),
+                        consensus_state: prev_cs.into_box(),
                     });
                 }
             }
@@ -591,11 +623,10 @@ impl Ics2ClientState for ClientState {
             {
                 return Err(Error::MisbehaviourHeadersBlockHashesEqual.into());
             }
-        } else {
-            // BFT time violation
-            if header_1.signed_header.header.time > header_2.signed_header.header.time {
-                return Err(Error::MisbehaviourHeadersNotAtSameHeight.into());
-            }
+        } else if !misbehaviour.is_bft_time_violation() {
+            // Headers at different heights are only misbehaviour if they
+            // violate BFT time monotonicity
+            return Err(Error::MisbehaviourHeadersNotAtSameHeight.into());
         }
 
         let consensus_state_1 = {
@@ -627,7 +658,7 @@ impl Ics2ClientState for ClientState {
         self.verify_header_commit_against_trusted(header_1, &consensus_state_1)?;
         self.verify_header_commit_against_trusted(header_2, &consensus_state_2)?;
 
-        let client_state = downcast_tm_client_state(self)?.clone();
+        let client_state = downcast_tm_client_state::<H>(self)?.clone();
         Ok(client_state
             .with_frozen_height(Height::new(0, 1).unwrap())
             .into_box())
@@ -653,13 +684,12 @@ impl Ics2ClientState for ClientState {
                     Error::MisbehaviourHeadersBlockHashesEqual.into(),
                 ));
             }
-        } else {
-            // BFT time violation
-            if header_1.signed_header.header.time > header_2.signed_header.header.time {
-                return Err(ContextError::ClientError(
-                    Error::MisbehaviourHeadersNotAtSameHeight.into(),
-                ));
-            }
+        } else if !misbehaviour.is_bft_time_violation() {
+            // Headers at different heights are only misbehaviour if they
+            // violate BFT time monotonicity
+            return Err(ContextError::ClientError(
+                Error::MisbehaviourHeadersNotAtSameHeight.into(),
+            ));
         }
 
         let consensus_state_1 = {
@@ -693,7 +723,7 @@ impl Ics2ClientState for ClientState {
         self.verify_header_commit_against_trusted(header_1, &consensus_state_1)?;
         self.verify_header_commit_against_trusted(header_2, &consensus_state_2)?;
 
-        let client_state = downcast_tm_client_state(self)?.clone();
+        let client_state = downcast_tm_client_state::<H>(self)?.clone();
         Ok(client_state
             .with_frozen_height(Height::new(0, 1).unwrap())
             .into_box())
@@ -720,7 +750,7 @@ impl Ics2ClientState for ClientState {
             }
         }
 
-        let client_state = downcast_tm_client_state(self)?.clone();
+        let client_state = downcast_tm_client_state::<H>(self)?.clone();
         let header = TmHeader::try_from(header)?;
 
         if header.height().revision_number() != client_state.chain_id().version() {
@@ -805,7 +835,9 @@ impl Ics2ClientState for ClientState {
 
         // If the header has verified, but its corresponding consensus state
         // differs from the existing consensus state for that height, freeze the
-        // client and return the installed consensus state.
+        // client and return the installed consensus state. The same goes for
+        // the timestamp checks below, which return the neighbouring consensus
+        // state that the header conflicts with.
         if let Some(cs) = existing_consensus_state {
             if cs != header_consensus_state {
                 return Ok(UpdatedState {
@@ -829,15 +861,13 @@ impl Ics2ClientState for ClientState {
                 .transpose()?;
 
             if let Some(next_cs) = maybe_next_cs {
-                // New (untrusted) header timestamp cannot occur after next
-                // consensus state's height
-                if header.signed_header.header().time > next_cs.timestamp {
-                    return Err(ClientError::ClientSpecific {
-                        description: Error::HeaderTimestampTooHigh {
-                            actual: header.signed_header.header().time.to_string(),
-                            max: next_cs.timestamp.to_string(),
-                        }
-                        .to_string(),
+                // BFT time must be monotonic: a verified header at a lower
+                // height than the next consensus state, but with a timestamp
+                // that is not before it, is evidence of misbehaviour.
+                if header.signed_header.header().time >= next_cs.timestamp {
+                    return Ok(UpdatedState {
+                        client_state: client_state.with_frozen_height(header.height()).into_box(),
+                        consensus_state: next_cs.into_box(),
                     });
                 }
             }
@@ -856,15 +886,12 @@ impl Ics2ClientState for ClientState {
                 .transpose()?;
 
             if let Some(prev_cs) = maybe_prev_cs {
-                // New (untrusted) header timestamp cannot occur before the
-                // previous consensus state's height
-                if header.signed_header.header().time < prev_cs.timestamp {
-                    return Err(ClientError::ClientSpecific {
-                        description: Error::HeaderTimestampTooLow {
-                            actual: header.signed_header.header().time.to_string(),
-                            min: prev_cs.timestamp.to_string(),
-                        }
-                        .to_string(),
+                // Likewise, a verified header at a greater height than the
+                // previous consensus state must have a later timestamp.
+                if header.signed_header.header().time <= prev_cs.timestamp {
+                    return Ok(UpdatedState {
+                        client_state: client_state.with_frozen_height(header.height()).into_box(),
+                        consensus_state: prev_cs.into_box(),
                     });
                 }
             }
@@ -878,234 +905,199 @@ impl Ics2ClientState for ClientState {
 
     fn verify_upgrade_and_update_state(
         &self,
-        _consensus_state: Any,
-        _proof_upgrade_client: RawMerkleProof,
-        _proof_upgrade_consensus_state: RawMerkleProof,
+        upgraded_client_state: Any,
+        upgraded_consensus_state: Any,
+        proof_upgrade_client: RawMerkleProof,
+        proof_upgrade_consensus_state: RawMerkleProof,
+        root: &CommitmentRoot,
     ) -> Result<UpdatedState, ClientError> {
-        unimplemented!()
-    }
+        // The upgraded client and consensus states must be of Tendermint type
+        let upgraded_tm_client_state = Self::try_from(upgraded_client_state)?;
+        let upgraded_tm_cons_state = TmConsensusState::try_from(upgraded_consensus_state)?;
+
+        if self.latest_height >= upgraded_tm_client_state.latest_height {
+            return Err(ClientError::LowUpgradeHeight {
+                upgraded_height: upgraded_tm_client_state.latest_height,
+                client_height: self.latest_height,
+            });
+        }
 
-    fn verify_client_consensus_state(
-        &self,
-        height: Height,
-        prefix: &CommitmentPrefix,
-        proof: &CommitmentProofBytes,
-        root: &CommitmentRoot,
-        client_id: &ClientId,
-        consensus_height: Height,
-        expected_consensus_state: &dyn ConsensusState,
-    ) -> Result<(), ClientError> {
-        let client_state = downcast_tm_client_state(self)?;
-        client_state.verify_height(height)?;
+        if self.upgrade_path.is_empty() {
+            return Err(ClientError::ClientSpecific {
+                description: "cannot upgrade client as no upgrade path has been set".to_string(),
+            });
+        }
 
-        let path = ClientConsensusStatePath {
-            client_id: client_id.clone(),
-            epoch: consensus_height.revision_number(),
-            height: consensus_height.revision_height(),
-        };
-        let value = expected_consensus_state
-            .encode_vec()
-            .map_err(ClientError::InvalidAnyConsensusState)?;
+        // The counterparty commits to the upgraded states under the upgrade path, at the last
+        // height of the current revision
+        let last_height = self.latest_height.revision_height();
+
+        // Only the chain-chosen fields of the upgraded client state are committed to
+        let mut committed_client_state = upgraded_tm_client_state.clone();
+        committed_client_state.zero_custom_fields();
+
+        MerkleProof::from(proof_upgrade_client)
+            .verify_membership::<H>(
+                &self.proof_specs,
+                root.clone().into(),
+                upgrade_merkle_path(
+                    &self.upgrade_path,
+                    ClientUpgradePath::UpgradedClientState(last_height),
+                ),
+                Any::from(committed_client_state).encode_to_vec(),
+                0,
+            )
+            .map_err(ClientError::InvalidUpgradeClientProof)?;
+
+        MerkleProof::from(proof_upgrade_consensus_state)
+            .verify_membership::<H>(
+                &self.proof_specs,
+                root.clone().into(),
+                upgrade_merkle_path(
+                    &self.upgrade_path,
+                    ClientUpgradePath::UpgradedClientConsensusState(last_height),
+                ),
+                Any::from(upgraded_tm_cons_state.clone()).encode_to_vec(),
+                0,
+            )
+            .map_err(ClientError::InvalidUpgradeConsensusStateProof)?;
+
+        // All chain-chosen parameters come from the committed client state, whereas all
+        // client-chosen parameters (i.e. those picked by the relayer that created the client)
+        // are kept from the current client state.
+        let new_client_state = Self::new(
+            upgraded_tm_client_state.chain_id,
+            self.trust_level,
+            self.trusting_period,
+            upgraded_tm_client_state.unbonding_period,
+            self.max_clock_drift,
+            upgraded_tm_client_state.latest_height,
+            upgraded_tm_client_state.proof_specs,
+            upgraded_tm_client_state.upgrade_path,
+            self.allow_update,
+            None,
+        )?;
 
-        verify_membership(client_state, prefix, proof, root, path, value)
+        // The new consensus state is merely used as a trusted kernel against which headers on
+        // the new chain can be verified. Its root cannot be known in advance, so a sentinel value
+        // is used instead, against which no proof verification will pass. The timestamp and
+        // next validators hash are those of the last block committed by the old chain, which
+        // allows the first block of the new chain to be verified against the last validators of
+        // the old chain, as long as it is submitted within the trusting period.
+        let new_consensus_state = TmConsensusState::new(
+            SENTINEL_ROOT.as_bytes().to_vec().into(),
+            upgraded_tm_cons_state.timestamp,
+            upgraded_tm_cons_state.next_validators_hash,
+        );
+
+        Ok(UpdatedState {
+            client_state: new_client_state.into_box(),
+            consensus_state: new_consensus_state.into_box(),
+        })
     }
 
-    fn verify_connection_state(
+    fn check_substitute_and_update_state(
         &self,
-        height: Height,
-        prefix: &CommitmentPrefix,
-        proof: &CommitmentProofBytes,
-        root: &CommitmentRoot,
-        connection_id: &ConnectionId,
-        expected_connection_end: &ConnectionEnd,
-    ) -> Result<(), ClientError> {
-        let client_state = downcast_tm_client_state(self)?;
-        client_state.verify_height(height)?;
-
-        let path = ConnectionsPath(connection_id.clone());
-        let value = expected_connection_end
-            .encode_vec()
-            .map_err(ClientError::InvalidConnectionEnd)?;
-        verify_membership(client_state, prefix, proof, root, path, value)
+        substitute_client_state: &dyn Ics2ClientState,
+    ) -> Result<Box<dyn Ics2ClientState>, ClientError> {
+        let substitute_client_state = downcast_tm_client_state::<H>(substitute_client_state)?;
+
+        let mut recovered_client_state = self.clone();
+        recovered_client_state.latest_height = substitute_client_state.latest_height;
+        recovered_client_state.frozen_height = None;
+
+        let mut unfrozen_substitute_client_state = substitute_client_state.clone();
+        unfrozen_substitute_client_state.frozen_height = None;
+
+        if recovered_client_state != unfrozen_substitute_client_state {
+            return Err(ClientError::MismatchedSubstituteClientState);
+        }
+
+        Ok(recovered_client_state.into_box())
     }
 
-    fn verify_channel_state(
-        &self,
-        height: Height,
-        prefix: &CommitmentPrefix,
-        proof: &CommitmentProofBytes,
-        root: &CommitmentRoot,
-        port_id: &PortId,
-        channel_id: &ChannelId,
-        expected_channel_end: &crate::core::ics04_channel::channel::ChannelEnd,
-    ) -> Result<(), ClientError> {
-        let client_state = downcast_tm_client_state(self)?;
-        client_state.verify_height(height)?;
-
-        let path = ChannelEndsPath(port_id.clone(), channel_id.clone());
-        let value = expected_channel_end
-            .encode_vec()
-            .map_err(ClientError::InvalidChannelEnd)?;
-        verify_membership(client_state, prefix, proof, root, path, value)
+    fn validate_proof_height(&self, proof_height: Height) -> Result<(), ClientError> {
+        self.verify_height(proof_height).map_err(ClientError::from)
     }
 
-    fn verify_client_full_state(
-        &self,
-        height: Height,
+    fn verify_membership(
+        &mut self,
+        _ctx: &dyn ChannelReader,
         prefix: &CommitmentPrefix,
         proof: &CommitmentProofBytes,
         root: &CommitmentRoot,
-        client_id: &ClientId,
-        expected_client_state: Any,
+        path: Path,
+        value: Vec<u8>,
     ) -> Result<(), ClientError> {
-        let client_state = downcast_tm_client_state(self)?;
-        client_state.verify_height(height)?;
-
-        let path = ClientStatePath(client_id.clone());
-        let value = expected_client_state.encode_to_vec();
-        verify_membership(client_state, prefix, proof, root, path, value)
+        verify_membership(self, prefix, proof, root, path, value)
     }
 
-    fn verify_packet_data(
-        &self,
-        ctx: &dyn ChannelReader,
-        height: Height,
-        connection_end: &ConnectionEnd,
+    fn verify_non_membership(
+        &mut self,
+        _ctx: &dyn ChannelReader,
+        prefix: &CommitmentPrefix,
         proof: &CommitmentProofBytes,
         root: &CommitmentRoot,
-        port_id: &PortId,
-        channel_id: &ChannelId,
-        sequence: Sequence,
-        commitment: PacketCommitment,
+        path: Path,
     ) -> Result<(), ClientError> {
-        let client_state = downcast_tm_client_state(self)?;
-        client_state.verify_height(height)?;
-        verify_delay_passed(ctx, height, connection_end)?;
-
-        let commitment_path = CommitmentsPath {
-            port_id: port_id.clone(),
-            channel_id: channel_id.clone(),
-            sequence,
-        };
-
-        verify_membership(
-            client_state,
-            connection_end.counterparty().prefix(),
-            proof,
-            root,
-            commitment_path,
-            commitment.into_vec(),
-        )
+        verify_non_membership(self, prefix, proof, root, path)
     }
 
-    fn verify_packet_acknowledgement(
-        &self,
-        ctx: &dyn ChannelReader,
-        height: Height,
-        connection_end: &ConnectionEnd,
+    #[cfg(feature = "val_exec_ctx")]
+    fn new_verify_membership(
+        &mut self,
+        _ctx: &dyn ValidationContext,
+        prefix: &CommitmentPrefix,
         proof: &CommitmentProofBytes,
         root: &CommitmentRoot,
-        port_id: &PortId,
-        channel_id: &ChannelId,
-        sequence: Sequence,
-        ack_commitment: AcknowledgementCommitment,
+        path: Path,
+        value: Vec<u8>,
     ) -> Result<(), ClientError> {
-        let client_state = downcast_tm_client_state(self)?;
-        client_state.verify_height(height)?;
-        verify_delay_passed(ctx, height, connection_end)?;
-
-        let ack_path = AcksPath {
-            port_id: port_id.clone(),
-            channel_id: channel_id.clone(),
-            sequence,
-        };
-        verify_membership(
-            client_state,
-            connection_end.counterparty().prefix(),
-            proof,
-            root,
-            ack_path,
-            ack_commitment.into_vec(),
-        )
+        verify_membership(self, prefix, proof, root, path, value)
     }
 
-    fn verify_next_sequence_recv(
-        &self,
-        ctx: &dyn ChannelReader,
-        height: Height,
-        connection_end: &ConnectionEnd,
+    #[cfg(feature = "val_exec_ctx")]
+    fn new_verify_non_membership(
+        &mut self,
+        _ctx: &dyn ValidationContext,
+        prefix: &CommitmentPrefix,
         proof: &CommitmentProofBytes,
         root: &CommitmentRoot,
-        port_id: &PortId,
-        channel_id: &ChannelId,
-        sequence: Sequence,
+        path: Path,
     ) -> Result<(), ClientError> {
-        let client_state = downcast_tm_client_state(self)?;
-        client_state.verify_height(height)?;
-        verify_delay_passed(ctx, height, connection_end)?;
-
-        let mut seq_bytes = Vec::new();
-        u64::from(sequence)
-            .encode(&mut seq_bytes)
-            .expect("buffer size too small");
-
-        let seq_path = SeqRecvsPath(port_id.clone(), channel_id.clone());
-
-        verify_membership(
-            client_state,
-            connection_end.counterparty().prefix(),
-            proof,
-            root,
-            seq_path,
-            seq_bytes,
-        )
+        verify_non_membership(self, prefix, proof, root, path)
     }
+}
 
-    fn verify_packet_receipt_absence(
-        &self,
-        ctx: &dyn ChannelReader,
-        height: Height,
-        connection_end: &ConnectionEnd,
-        proof: &CommitmentProofBytes,
-        root: &CommitmentRoot,
-        port_id: &PortId,
-        channel_id: &ChannelId,
-        sequence: Sequence,
-    ) -> Result<(), ClientError> {
-        let client_state = downcast_tm_client_state(self)?;
-        client_state.verify_height(height)?;
-        verify_delay_passed(ctx, height, connection_end)?;
-
-        let receipt_path = ReceiptsPath {
-            port_id: port_id.clone(),
-            channel_id: channel_id.clone(),
-            sequence,
-        };
-        verify_non_membership(
-            client_state,
-            connection_end.counterparty().prefix(),
-            proof,
-            root,
-            receipt_path,
-        )
-    }
+/// Builds the merkle path under which the counterparty commits to an upgraded client or
+/// consensus state, by replacing the last key of the upgrade path with the given
+/// `ClientUpgradePath`, e.g. `["upgrade", "upgradedIBCState/42/upgradedClient"]`.
+fn upgrade_merkle_path(
+    upgrade_path: &[String],
+    client_upgrade_path: ClientUpgradePath,
+) -> MerklePath {
+    let mut key_path = upgrade_path.to_vec();
+    key_path.pop();
+    key_path.push(client_upgrade_path.to_string());
+
+    MerklePath { key_path }
 }
 
-fn verify_membership(
-    client_state: &ClientState,
+fn verify_membership<H: HostFunctionsProvider>(
+    client_state: &ClientState<H>,
     prefix: &CommitmentPrefix,
     proof: &CommitmentProofBytes,
     root: &CommitmentRoot,
-    path: impl Into<Path>,
+    path: Path,
     value: Vec<u8>,
 ) -> Result<(), ClientError> {
-    let merkle_path = apply_prefix(prefix, vec![path.into().to_string()]);
+    let merkle_path = apply_prefix(prefix, vec![path.to_string()]);
     let merkle_proof: MerkleProof = RawMerkleProof::try_from(proof.clone())
         .map_err(ClientError::InvalidCommitmentProof)?
         .into();
 
     merkle_proof
-        .verify_membership(
+        .verify_membership::<H>(
             &client_state.proof_specs,
             root.clone().into(),
             merkle_path,
@@ -1115,69 +1107,31 @@ fn verify_membership(
         .map_err(ClientError::Ics23Verification)
 }
 
-fn verify_non_membership(
-    client_state: &ClientState,
+fn verify_non_membership<H: HostFunctionsProvider>(
+    client_state: &ClientState<H>,
     prefix: &CommitmentPrefix,
     proof: &CommitmentProofBytes,
     root: &CommitmentRoot,
-    path: impl Into<Path>,
+    path: Path,
 ) -> Result<(), ClientError> {
-    let merkle_path = apply_prefix(prefix, vec![path.into().to_string()]);
+    let merkle_path = apply_prefix(prefix, vec![path.to_string()]);
     let merkle_proof: MerkleProof = RawMerkleProof::try_from(proof.clone())
         .map_err(ClientError::InvalidCommitmentProof)?
         .into();
 
     merkle_proof
-        .verify_non_membership(&client_state.proof_specs, root.clone().into(), merkle_path)
+        .verify_non_membership::<H>(&client_state.proof_specs, root.clone().into(), merkle_path)
         .map_err(ClientError::Ics23Verification)
 }
 
-fn verify_delay_passed(
-    ctx: &dyn ChannelReader,
-    height: Height,
-    connection_end: &ConnectionEnd,
-) -> Result<(), ClientError> {
-    let current_timestamp = ctx.host_timestamp().map_err(|e| ClientError::Other {
-        description: e.to_string(),
-    })?;
-    let current_height = ctx.host_height().map_err(|e| ClientError::Other {
-        description: e.to_string(),
-    })?;
-
-    let client_id = connection_end.client_id();
-    let processed_time =
-        ctx.client_update_time(client_id, &height)
-            .map_err(|_| Error::ProcessedTimeNotFound {
-                client_id: client_id.clone(),
-                height,
-            })?;
-    let processed_height = ctx.client_update_height(client_id, &height).map_err(|_| {
-        Error::ProcessedHeightNotFound {
-            client_id: client_id.clone(),
-            height,
-        }
-    })?;
-
-    let delay_period_time = connection_end.delay_period();
-    let delay_period_height = ctx.block_delay(&delay_period_time);
-
-    ClientState::verify_delay_passed(
-        current_timestamp,
-        current_height,
-        processed_time,
-        processed_height,
-        delay_period_time,
-        delay_period_height,
-    )
-    .map_err(|e| e.into())
-}
-
-fn downcast_tm_client_state(cs: &dyn Ics2ClientState) -> Result<&ClientState, ClientError> {
-    cs.as_any()
-        .downcast_ref::<ClientState>()
-        .ok_or_else(|| ClientError::ClientArgsTypeMismatch {
+fn downcast_tm_client_state<H: HostFunctionsProvider>(
+    cs: &dyn Ics2ClientState,
+) -> Result<&ClientState<H>, ClientError> {
+    cs.as_any().downcast_ref::<ClientState<H>>().ok_or_else(|| {
+        ClientError::ClientArgsTypeMismatch {
             client_type: tm_client_type(),
-        })
+        }
+    })
 }
 
 fn downcast_tm_consensus_state(cs: &dyn ConsensusState) -> Result<TmConsensusState, ClientError> {
@@ -1189,9 +1143,9 @@ fn downcast_tm_consensus_state(cs: &dyn ConsensusState) -> Result<TmConsensusSta
         .map(Clone::clone)
 }
 
-impl Protobuf<RawTmClientState> for ClientState {}
+impl<H: HostFunctionsProvider> Protobuf<RawTmClientState> for ClientState<H> {}
 
-impl TryFrom<RawTmClientState> for ClientState {
+impl<H: HostFunctionsProvider> TryFrom<RawTmClientState> for ClientState<H> {
     type Error = Error;
 
     fn try_from(raw: RawTmClientState) -> Result<Self, Self::Error> {
@@ -1248,7 +1202,7 @@ impl TryFrom<RawTmClientState> for ClientState {
             after_misbehaviour: raw.allow_update_after_misbehaviour,
         };
 
-        let client_state = ClientState::new(
+        let client_state = Self::new(
             chain_id,
             trust_level,
             trusting_period,
@@ -1265,8 +1219,8 @@ impl TryFrom<RawTmClientState> for ClientState {
     }
 }
 
-impl From<ClientState> for RawTmClientState {
-    fn from(value: ClientState) -> Self {
+impl<H: HostFunctionsProvider> From<ClientState<H>> for RawTmClientState {
+    fn from(value: ClientState<H>) -> Self {
         #[allow(deprecated)]
         Self {
             chain_id: value.chain_id.to_string(),
@@ -1289,16 +1243,18 @@ impl From<ClientState> for RawTmClientState {
     }
 }
 
-impl Protobuf<Any> for ClientState {}
+impl<H: HostFunctionsProvider> Protobuf<Any> for ClientState<H> {}
 
-impl TryFrom<Any> for ClientState {
+impl<H: HostFunctionsProvider> TryFrom<Any> for ClientState<H> {
     type Error = ClientError;
 
     fn try_from(raw: Any) -> Result<Self, Self::Error> {
         use bytes::Buf;
         use core::ops::Deref;
 
-        fn decode_client_state<B: Buf>(buf: B) -> Result<ClientState, Error> {
+        fn decode_client_state<H: HostFunctionsProvider, B: Buf>(
+            buf: B,
+        ) -> Result<ClientState<H>, Error> {
             RawTmClientState::decode(buf)
                 .map_err(Error::Decode)?
                 .try_into()
@@ -1315,8 +1271,8 @@ impl TryFrom<Any> for ClientState {
     }
 }
 
-impl From<ClientState> for Any {
-    fn from(client_state: ClientState) -> Self {
+impl<H: HostFunctionsProvider> From<ClientState<H>> for Any {
+    fn from(client_state: ClientState<H>) -> Self {
         Any {
             type_url: TENDERMINT_CLIENT_STATE_TYPE_URL.to_string(),
             value: Protobuf::<RawTmClientState>::encode_vec(&client_state)
@@ -1334,10 +1290,25 @@ mod tests {
 
     use ibc_proto::ics23::ProofSpec as Ics23ProofSpec;
 
-    use crate::clients::ics07_tendermint::client_state::{AllowUpdate, ClientState};
+    use ibc_proto::google::protobuf::Any;
+    use prost::Message;
+    use tendermint::{Hash, Time};
+
+    use crate::clients::ics07_tendermint::client_state::test_util::get_dummy_upgrade_proofs;
+    use crate::clients::ics07_tendermint::client_state::{AllowUpdate, ClientState, SENTINEL_ROOT};
+    use crate::clients::ics07_tendermint::consensus_state::ConsensusState as TmConsensusState;
+    use crate::core::ics02_client::client_state::{
+        downcast_client_state, ClientState as Ics2ClientState, Status, UpdatedState,
+    };
+    use crate::core::ics02_client::consensus_state::downcast_consensus_state;
+    use crate::core::ics02_client::error::ClientError;
     use crate::core::ics02_client::trust_threshold::TrustThreshold;
+    use crate::core::ics23_commitment::commitment::CommitmentRoot;
     use crate::core::ics23_commitment::specs::ProofSpecs;
     use crate::core::ics24_host::identifier::ChainId;
+    use crate::host_functions::HostFunctionsManager;
+    use crate::mock::client_state::MockClientState;
+    use crate::mock::header::MockHeader;
     use crate::timestamp::{Timestamp, ZERO_DURATION};
 
     #[derive(Clone, Debug, PartialEq)]
@@ -1496,7 +1467,7 @@ mod tests {
         for test in tests {
             let p = test.params.clone();
 
-            let cs_result = ClientState::new(
+            let cs_result: Result<ClientState, _> = ClientState::new(
                 p.id,
                 p.trust_level,
                 p.trusting_period,
@@ -1578,7 +1549,7 @@ mod tests {
         ];
 
         for test in tests {
-            let res = ClientState::verify_delay_passed(
+            let res = ClientState::<HostFunctionsManager>::verify_delay_passed(
                 test.params.current_time,
                 test.params.current_height,
                 test.params.processed_time,
@@ -1677,6 +1648,251 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn client_state_verify_upgrade_and_update_state() {
+        let upgrade_path = vec!["upgrade".to_string(), "upgradedIBCState".to_string()];
+        let client_state = ClientState::new(
+            ChainId::new("ibc".to_string(), 0),
+            TrustThreshold::ONE_THIRD,
+            Duration::new(64000, 0),
+            Duration::new(128000, 0),
+            Duration::new(3, 0),
+            Height::new(0, 10).unwrap(),
+            ProofSpecs::default(),
+            upgrade_path.clone(),
+            AllowUpdate {
+                after_expiry: true,
+                after_misbehaviour: false,
+            },
+            None,
+        )
+        .unwrap();
+
+        // The client-chosen fields of the upgraded client state are ignored
+        let upgraded_client_state: ClientState = ClientState::new(
+            ChainId::new("ibc".to_string(), 1),
+            TrustThreshold::TWO_THIRDS,
+            Duration::new(96000, 0),
+            Duration::new(192000, 0),
+            Duration::new(5, 0),
+            Height::new(1, 1).unwrap(),
+            ProofSpecs::default(),
+            upgrade_path.clone(),
+            AllowUpdate {
+                after_expiry: false,
+                after_misbehaviour: false,
+            },
+            None,
+        )
+        .unwrap();
+        let upgraded_consensus_state = TmConsensusState::new(
+            vec![1; 32].into(),
+            Time::from_unix_timestamp(1_000_000, 0).unwrap(),
+            Hash::Sha256([2; 32]),
+        );
+
+        let mut committed_client_state = upgraded_client_state.clone();
+        committed_client_state.zero_custom_fields();
+        let (proof_upgrade_client, proof_upgrade_consensus_state, root) = get_dummy_upgrade_proofs(
+            &upgrade_path,
+            10,
+            Any::from(committed_client_state).encode_to_vec(),
+            Any::from(upgraded_consensus_state.clone()).encode_to_vec(),
+        );
+
+        let verify = |client_state: &ClientState, root: &CommitmentRoot| {
+            client_state.verify_upgrade_and_update_state(
+                upgraded_client_state.clone().into(),
+                upgraded_consensus_state.clone().into(),
+                proof_upgrade_client.clone(),
+                proof_upgrade_consensus_state.clone(),
+                root,
+            )
+        };
+
+        let UpdatedState {
+            client_state: new_client_state,
+            consensus_state: new_consensus_state,
+        } = verify(&client_state, &root).unwrap();
+
+        let new_client_state = downcast_client_state::<ClientState>(new_client_state.as_ref())
+            .unwrap()
+            .clone();
+        assert_eq!(
+            new_client_state,
+            ClientState::new(
+                ChainId::new("ibc".to_string(), 1),
+                client_state.trust_level,
+                client_state.trusting_period,
+                upgraded_client_state.unbonding_period,
+                client_state.max_clock_drift,
+                Height::new(1, 1).unwrap(),
+                ProofSpecs::default(),
+                upgrade_path,
+                client_state.allow_update,
+                None,
+            )
+            .unwrap()
+        );
+
+        let new_consensus_state =
+            downcast_consensus_state::<TmConsensusState>(new_consensus_state.as_ref()).unwrap();
+        assert_eq!(
+            new_consensus_state.root,
+            SENTINEL_ROOT.as_bytes().to_vec().into()
+        );
+        assert_eq!(
+            new_consensus_state.timestamp,
+            upgraded_consensus_state.timestamp
+        );
+        assert_eq!(
+            new_consensus_state.next_validators_hash,
+            upgraded_consensus_state.next_validators_hash
+        );
+
+        // Proofs must be verified against the root of the client's latest consensus state
+        assert!(matches!(
+            verify(&client_state, &vec![3; 32].into()),
+            Err(ClientError::InvalidUpgradeClientProof(_))
+        ));
+
+        // Proofs are bound to the last height of the current revision
+        let mut later_client_state = client_state.clone();
+        later_client_state.latest_height = Height::new(0, 11).unwrap();
+        assert!(matches!(
+            verify(&later_client_state, &root),
+            Err(ClientError::InvalidUpgradeClientProof(_))
+        ));
+
+        // The client cannot be upgraded without an upgrade path
+        let mut no_path_client_state = client_state;
+        no_path_client_state.upgrade_path = vec![];
+        assert!(matches!(
+            verify(&no_path_client_state, &root),
+            Err(ClientError::ClientSpecific { .. })
+        ));
+    }
+
+    #[test]
+    fn client_state_check_substitute_and_update_state() {
+        let client_state_at = |latest_height: Height, frozen_height: Option<Height>| {
+            ClientState::new(
+                ChainId::new("ibc".to_string(), 0),
+                TrustThreshold::ONE_THIRD,
+                Duration::new(64000, 0),
+                Duration::new(128000, 0),
+                Duration::new(3, 0),
+                latest_height,
+                ProofSpecs::default(),
+                vec![],
+                AllowUpdate {
+                    after_expiry: false,
+                    after_misbehaviour: false,
+                },
+                frozen_height,
+            )
+            .unwrap()
+        };
+
+        let subject_client_state = client_state_at(
+            Height::new(0, 10).unwrap(),
+            Some(Height::new(0, 10).unwrap()),
+        );
+        let substitute_client_state = client_state_at(Height::new(0, 20).unwrap(), None);
+
+        let recovered_client_state = subject_client_state
+            .check_substitute_and_update_state(&substitute_client_state)
+            .unwrap();
+        assert_eq!(
+            downcast_client_state::<ClientState>(recovered_client_state.as_ref()).unwrap(),
+            &substitute_client_state
+        );
+
+        // All other fields must match
+        let mut mismatched_client_state = substitute_client_state;
+        mismatched_client_state.trusting_period = Duration::new(32000, 0);
+        assert!(matches!(
+            subject_client_state.check_substitute_and_update_state(&mismatched_client_state),
+            Err(ClientError::MismatchedSubstituteClientState)
+        ));
+
+        // The substitute must be a Tendermint client
+        let mock_client_state = MockClientState::new(MockHeader::new(Height::new(0, 20).unwrap()));
+        assert!(matches!(
+            subject_client_state.check_substitute_and_update_state(&mock_client_state),
+            Err(ClientError::ClientArgsTypeMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn client_state_allow_update() {
+        // (after_expiry, after_misbehaviour) -> allowed recoveries and upgrades for the statuses
+        // `[Active, Frozen, Expired, Unknown]`
+        let tests = [
+            (
+                (false, false),
+                [false, false, false, false],
+                [true, false, false, false],
+            ),
+            (
+                (true, false),
+                [false, false, true, false],
+                [true, false, true, false],
+            ),
+            (
+                (false, true),
+                [false, true, false, false],
+                [true, false, false, false],
+            ),
+            (
+                (true, true),
+                [false, true, true, false],
+                [true, false, true, false],
+            ),
+        ];
+
+        for ((after_expiry, after_misbehaviour), recoveries, upgrades) in tests {
+            let client_state: ClientState = ClientState::new(
+                ChainId::new("ibc".to_string(), 0),
+                TrustThreshold::ONE_THIRD,
+                Duration::new(64000, 0),
+                Duration::new(128000, 0),
+                Duration::new(3, 0),
+                Height::new(0, 10).unwrap(),
+                ProofSpecs::default(),
+                vec![],
+                AllowUpdate {
+                    after_expiry,
+                    after_misbehaviour,
+                },
+                None,
+            )
+            .unwrap();
+
+            let statuses = [
+                Status::Active,
+                Status::Frozen,
+                Status::Expired,
+                Status::Unknown,
+            ];
+            for ((status, recovery), upgrade) in statuses.into_iter().zip(recoveries).zip(upgrades)
+            {
+                assert_eq!(
+                    client_state.allows_recovery(status),
+                    recovery,
+                    "recovery of {status} client with {:?}",
+                    client_state.allow_update
+                );
+                assert_eq!(
+                    client_state.allows_upgrade(status),
+                    upgrade,
+                    "upgrade of {status} client with {:?}",
+                    client_state.allow_update
+                );
+            }
+        }
+    }
 }
 
 #[cfg(all(test, feature = "serde"))]
@@ -1707,9 +1923,16 @@ pub mod test_util {
 
     use tendermint::block::Header;
 
+    use ibc_proto::ibc::core::commitment::v1::MerkleProof as RawMerkleProof;
+    use ics23::calculate_existence_root;
+
     use crate::clients::ics07_tendermint::client_state::{AllowUpdate, ClientState};
     use crate::core::ics02_client::height::Height;
+    use crate::core::ics23_commitment::commitment::CommitmentRoot;
+    use crate::core::ics23_commitment::merkle::MerkleProof;
     use crate::core::ics24_host::identifier::ChainId;
+    use crate::core::ics24_host::path::ClientUpgradePath;
+    use crate::host_functions::HostFunctionsManager;
 
     pub fn get_dummy_tendermint_client_state(tm_header: Header) -> ClientState {
         ClientState::new(
@@ -1733,4 +1956,90 @@ pub mod test_util {
         )
         .unwrap()
     }
+
+    /// Builds proofs of the upgraded client and consensus states, as committed by a Cosmos SDK
+    /// chain: an IAVL tree holding both states in the `upgrade` store, under a multi-store with
+    /// a single store. Returns the proofs along with the root of the multi-store.
+    pub fn get_dummy_upgrade_proofs(
+        upgrade_path: &[String],
+        last_height: u64,
+        client_value: Vec<u8>,
+        cons_value: Vec<u8>,
+    ) -> (RawMerkleProof, RawMerkleProof, CommitmentRoot) {
+        let existence_root = |proof: &ics23::ExistenceProof| {
+            calculate_existence_root::<HostFunctionsManager>(proof).unwrap()
+        };
+        let iavl_leaf = |key: String, value: Vec<u8>| ics23::ExistenceProof {
+            key: key.into_bytes(),
+            value,
+            leaf: Some(ics23::LeafOp {
+                hash: ics23::HashOp::Sha256.into(),
+                prehash_key: ics23::HashOp::NoHash.into(),
+                prehash_value: ics23::HashOp::Sha256.into(),
+                length: ics23::LengthOp::VarProto.into(),
+                // height 0, size 1, version 1
+                prefix: vec![0, 2, 2],
+            }),
+            path: vec![],
+        };
+
+        let store_key = upgrade_path[0].clone();
+
+        let mut client_proof = iavl_leaf(
+            ClientUpgradePath::UpgradedClientState(last_height).to_string(),
+            client_value,
+        );
+        let mut cons_proof = iavl_leaf(
+            ClientUpgradePath::UpgradedClientConsensusState(last_height).to_string(),
+            cons_value,
+        );
+        let client_leaf_hash = existence_root(&client_proof);
+        let cons_leaf_hash = existence_root(&cons_proof);
+
+        // The inner node at height 1, size 2 and version 1 holding both leaves
+        let inner_prefix = vec![2, 4, 2, 32];
+        client_proof.path.push(ics23::InnerOp {
+            hash: ics23::HashOp::Sha256.into(),
+            prefix: inner_prefix.clone(),
+            suffix: [vec![32], cons_leaf_hash].concat(),
+        });
+        cons_proof.path.push(ics23::InnerOp {
+            hash: ics23::HashOp::Sha256.into(),
+            prefix: [inner_prefix, client_leaf_hash, vec![32]].concat(),
+            suffix: vec![],
+        });
+        let store_root = existence_root(&client_proof);
+        assert_eq!(store_root, existence_root(&cons_proof));
+
+        let multi_store_proof = ics23::ExistenceProof {
+            key: store_key.into_bytes(),
+            value: store_root,
+            leaf: Some(ics23::LeafOp {
+                hash: ics23::HashOp::Sha256.into(),
+                prehash_key: ics23::HashOp::NoHash.into(),
+                prehash_value: ics23::HashOp::Sha256.into(),
+                length: ics23::LengthOp::VarProto.into(),
+                prefix: vec![0],
+            }),
+            path: vec![],
+        };
+        let root = existence_root(&multi_store_proof);
+
+        let into_raw_proof = |store_proof: ics23::ExistenceProof| {
+            RawMerkleProof::from(MerkleProof {
+                proofs: [store_proof, multi_store_proof.clone()]
+                    .into_iter()
+                    .map(|proof| ics23::CommitmentProof {
+                        proof: Some(ics23::commitment_proof::Proof::Exist(proof)),
+                    })
+                    .collect(),
+            })
+        };
+
+        (
+            into_raw_proof(client_proof),
+            into_raw_proof(cons_proof),
+            root.into(),
+        )
+    }
 }
@@ -62,10 +62,6 @@ pub enum Error {
     InvalidRawMisbehaviour { reason: String },
     /// decode error: `{0}`
     Decode(prost::DecodeError),
-    /// given other previous updates, header timestamp should be at most `{max}`, but was `{actual}`
-    HeaderTimestampTooHigh { actual: String, max: String },
-    /// given other previous updates, header timestamp should be at least `{min}`, but was `{actual}`
-    HeaderTimestampTooLow { actual: String, min: String },
     /// timestamp overflowed error: `{0}`
     TimestampOverflow(TimestampOverflowError),
     /// not enough time elapsed, current timestamp `{current_time}` is still less than earliest acceptable timestamp `{earliest_time}`
@@ -125,6 +121,24 @@ pub enum Error {
     },
     /// invalid raw client id: `{client_id}`
     InvalidRawClientId { client_id: String },
+    /// the bisection target height `{target_height}` is not greater than the trusted height `{trusted_height}`
+    InvalidBisectionTarget {
+        trusted_height: Height,
+        target_height: Height,
+    },
+    /// trusted validators do not hash to the next validators hash of the trusted consensus state. Expected: `{next_validators_hash}`, got: `{trusted_val_hash}`
+    TrustedValidatorHashMismatch {
+        next_validators_hash: Hash,
+        trusted_val_hash: Hash,
+    },
+    /// header provider failed: `{reason}`
+    HeaderProviderFailure { reason: String },
+    /// validators at height `{height}` do not hash to the validators hash of the header. Expected: `{validators_hash}`, got: `{val_hash}`
+    ValidatorSetHashMismatch {
+        height: u64,
+        validators_hash: Hash,
+        val_hash: Hash,
+    },
 }
 
 #[cfg(feature = "std")]
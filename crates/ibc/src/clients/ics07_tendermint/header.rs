@@ -1,4 +1,5 @@
 use alloc::string::ToString;
+use alloc::vec::Vec;
 use core::cmp::Ordering;
 use core::fmt::{Display, Error as FmtError, Formatter};
 
@@ -9,8 +10,9 @@ use ibc_proto::protobuf::Protobuf;
 use prost::Message;
 use tendermint::block::signed_header::SignedHeader;
 use tendermint::chain::Id as TmChainId;
-use tendermint::validator::Set as ValidatorSet;
-use tendermint_light_client_verifier::types::{TrustedBlockState, UntrustedBlockState};
+use tendermint::validator::{Info as ValidatorInfo, Set as ValidatorSet};
+use tendermint::Hash;
+use tendermint_light_client_verifier::types::{LightBlock, TrustedBlockState, UntrustedBlockState};
 
 use crate::clients::ics07_tendermint::consensus_state::ConsensusState;
 use crate::clients::ics07_tendermint::error::Error;
@@ -46,6 +48,80 @@ impl Display for Header {
 }
 
 impl Header {
+    /// Builds the header which updates a client from the `trusted` light block, at a height the
+    /// client has a consensus state for, to the `target` one.
+    pub fn from_light_blocks(trusted: &LightBlock, target: LightBlock) -> Result<Self, Error> {
+        check_validators_hash(
+            &trusted.next_validators,
+            trusted.signed_header.header.next_validators_hash,
+            trusted.height().increment().value(),
+        )?;
+        check_validators_hash(
+            &target.validators,
+            target.signed_header.header.validators_hash,
+            target.height().value(),
+        )?;
+
+        let trusted_height = Height::new(
+            ChainId::chain_version(trusted.signed_header.header.chain_id.as_str()),
+            trusted.height().value(),
+        )
+        .map_err(|_| Error::InvalidHeaderHeight {
+            height: trusted.height().value(),
+        })?;
+
+        let header = Self {
+            signed_header: target.signed_header,
+            validator_set: target.validators,
+            trusted_height,
+            trusted_validator_set: trusted.next_validators.clone(),
+        };
+        header.validate_trusted_height()?;
+
+        Ok(header)
+    }
+
+    /// Builds the header which updates a client to the block of `signed_header`, signed by
+    /// `validators`, from the trusted block right below the one of `trusted_next_signed_header`,
+    /// signed by `trusted_next_validators`.
+    ///
+    /// These are the results of the `commit` and `validators` RPC queries at the target height,
+    /// and at the height after the trusted one, whose header names the proposer of the trusted
+    /// validator set.
+    pub fn from_rpc_responses(
+        signed_header: SignedHeader,
+        validators: Vec<ValidatorInfo>,
+        trusted_next_signed_header: &SignedHeader,
+        trusted_next_validators: Vec<ValidatorInfo>,
+    ) -> Result<Self, Error> {
+        let validator_set = validator_set_of(&signed_header, validators)?;
+        let trusted_validator_set =
+            validator_set_of(trusted_next_signed_header, trusted_next_validators)?;
+
+        let trusted_revision_height = trusted_next_signed_header
+            .header
+            .height
+            .value()
+            .saturating_sub(1);
+        let trusted_height = Height::new(
+            ChainId::chain_version(trusted_next_signed_header.header.chain_id.as_str()),
+            trusted_revision_height,
+        )
+        .map_err(|_| Error::InvalidHeaderHeight {
+            height: trusted_revision_height,
+        })?;
+
+        let header = Self {
+            signed_header,
+            validator_set,
+            trusted_height,
+            trusted_validator_set,
+        };
+        header.validate_trusted_height()?;
+
+        Ok(header)
+    }
+
     pub fn height(&self) -> Height {
         Height::new(
             ChainId::chain_version(self.signed_header.header.chain_id.as_str()),
@@ -58,6 +134,17 @@ impl Header {
         headers_compatible(&self.signed_header, &other_header.signed_header)
     }
 
+    fn validate_trusted_height(&self) -> Result<(), Error> {
+        if self.height().revision_number() != self.trusted_height.revision_number() {
+            return Err(Error::MismatchedRevisions {
+                current_revision: self.trusted_height.revision_number(),
+                update_revision: self.height().revision_number(),
+            });
+        }
+
+        Ok(())
+    }
+
     pub(crate) fn as_untrusted_block_state(&self) -> UntrustedBlockState<'_> {
         UntrustedBlockState {
             signed_header: &self.signed_header,
@@ -87,6 +174,44 @@ impl Header {
     }
 }
 
+/// Builds the validator set which signed `signed_header`, with the proposer it names.
+fn validator_set_of(
+    signed_header: &SignedHeader,
+    validators: Vec<ValidatorInfo>,
+) -> Result<ValidatorSet, Error> {
+    let validator_set =
+        ValidatorSet::with_proposer(validators, signed_header.header.proposer_address).map_err(
+            |e| Error::InvalidHeader {
+                reason: "proposer is not a validator".to_string(),
+                error: e,
+            },
+        )?;
+    check_validators_hash(
+        &validator_set,
+        signed_header.header.validators_hash,
+        signed_header.header.height.value(),
+    )?;
+
+    Ok(validator_set)
+}
+
+fn check_validators_hash(
+    validator_set: &ValidatorSet,
+    validators_hash: Hash,
+    height: u64,
+) -> Result<(), Error> {
+    let val_hash = validator_set.hash();
+    if val_hash != validators_hash {
+        return Err(Error::ValidatorSetHashMismatch {
+            height,
+            validators_hash,
+            val_hash,
+        });
+    }
+
+    Ok(())
+}
+
 pub fn headers_compatible(header: &SignedHeader, other: &SignedHeader) -> bool {
     let ibc_client_height = other.header.height;
     let self_header_height = header.header.height;
@@ -148,12 +273,7 @@ impl TryFrom<RawHeader> for Header {
                 .map_err(Error::InvalidRawHeader)?,
         };
 
-        if header.height().revision_number() != header.trusted_height.revision_number() {
-            return Err(Error::MismatchedRevisions {
-                current_revision: header.trusted_height.revision_number(),
-                update_revision: header.height().revision_number(),
-            });
-        }
+        header.validate_trusted_height()?;
 
         Ok(header)
     }
@@ -282,3 +402,117 @@ pub mod test_util {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tendermint::node::Id as PeerId;
+    use tendermint_rpc::endpoint::{commit, validators};
+    use tendermint_rpc::Response;
+    use test_log::test;
+
+    // The fixtures are the `commit` and `validators` RPC responses of a chain whose validators
+    // change at height 11.
+    fn commit_at(height: u64) -> SignedHeader {
+        let json = match height {
+            10 => include_str!("../../../tests/support/rpc/commit_at_height_10.json"),
+            11 => include_str!("../../../tests/support/rpc/commit_at_height_11.json"),
+            20 => include_str!("../../../tests/support/rpc/commit_at_height_20.json"),
+            _ => panic!("no commit fixture at height {height}"),
+        };
+        commit::Response::from_string(json).unwrap().signed_header
+    }
+
+    fn validators_at(height: u64) -> Vec<ValidatorInfo> {
+        let json = match height {
+            10 => include_str!("../../../tests/support/rpc/validators_at_height_10.json"),
+            11 => include_str!("../../../tests/support/rpc/validators_at_height_11.json"),
+            20 => include_str!("../../../tests/support/rpc/validators_at_height_20.json"),
+            _ => panic!("no validators fixture at height {height}"),
+        };
+        validators::Response::from_string(json).unwrap().validators
+    }
+
+    fn light_block(height: u64, next_height: u64) -> LightBlock {
+        let signed_header = commit_at(height);
+        let validators = validator_set_of(&signed_header, validators_at(height)).unwrap();
+        let next_validators =
+            validator_set_of(&commit_at(next_height), validators_at(next_height)).unwrap();
+
+        LightBlock::new(
+            signed_header,
+            validators,
+            next_validators,
+            PeerId::new([0; 20]),
+        )
+    }
+
+    #[test]
+    fn header_from_rpc_responses() {
+        let header = Header::from_rpc_responses(
+            commit_at(20),
+            validators_at(20),
+            &commit_at(11),
+            validators_at(11),
+        )
+        .unwrap();
+
+        assert_eq!(header.height(), Height::new(1, 20).unwrap());
+        assert_eq!(header.trusted_height, Height::new(1, 10).unwrap());
+        assert_eq!(
+            header.trusted_validator_set.hash(),
+            commit_at(10).header.next_validators_hash
+        );
+        assert!(header.trusted_validator_set.proposer().is_some());
+
+        // The header goes through the validation of the client messages
+        let decoded = Header::try_from(RawHeader::from(header.clone())).unwrap();
+        assert_eq!(decoded, header);
+    }
+
+    #[test]
+    fn header_from_light_blocks() {
+        let header = Header::from_light_blocks(&light_block(10, 11), light_block(20, 20)).unwrap();
+
+        let rpc_header = Header::from_rpc_responses(
+            commit_at(20),
+            validators_at(20),
+            &commit_at(11),
+            validators_at(11),
+        )
+        .unwrap();
+        assert_eq!(header, rpc_header);
+    }
+
+    #[test]
+    fn header_with_mismatched_validators() {
+        // The validators of the target block are missing one which is not the proposer
+        let proposer_address = commit_at(20).header.proposer_address;
+        let validators = validators_at(20)
+            .into_iter()
+            .filter(|v| v.address == proposer_address)
+            .collect();
+
+        let result = Header::from_rpc_responses(
+            commit_at(20),
+            validators,
+            &commit_at(11),
+            validators_at(11),
+        );
+        assert!(matches!(
+            result,
+            Err(Error::ValidatorSetHashMismatch { height: 20, .. })
+        ));
+
+        // The trusted validators are not the next validators of the trusted block
+        let mut trusted = light_block(10, 11);
+        trusted.next_validators = trusted.validators.clone();
+
+        let result = Header::from_light_blocks(&trusted, light_block(20, 20));
+        assert!(matches!(
+            result,
+            Err(Error::ValidatorSetHashMismatch { height: 11, .. })
+        ));
+    }
+}
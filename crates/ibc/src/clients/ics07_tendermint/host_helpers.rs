@@ -13,13 +13,15 @@ use crate::Height;
 
 use tendermint::trust_threshold::TrustThresholdFraction as TendermintTrustThresholdFraction;
 
-/// Provides an implementation of `ValidationContext::validate_self_client` for
-/// Tendermint-based hosts.
+/// Provides an implementation of `ConnectionReader::validate_self_client` and
+/// `ValidationContext::validate_self_client` for Tendermint-based hosts.
 pub trait ValidateSelfClientContext {
     fn validate_self_client(&self, counterparty_client_state: Any) -> Result<(), ConnectionError> {
-        let counterparty_client_state = TmClientState::try_from(counterparty_client_state)
-            .map_err(|_| ConnectionError::InvalidClientState {
-                reason: "client must be a tendermint client".to_string(),
+        let counterparty_client_state: TmClientState =
+            TmClientState::try_from(counterparty_client_state).map_err(|_| {
+                ConnectionError::InvalidClientState {
+                    reason: "client must be a tendermint client".to_string(),
+                }
             })?;
 
         if counterparty_client_state.is_frozen() {
@@ -75,6 +75,13 @@ impl Misbehaviour {
         &self.header2
     }
 
+    /// Whether the headers break the monotonicity of BFT time, i.e. `header1` is at a greater
+    /// height than `header2` (which is enforced by the ctor) but its timestamp is not later.
+    pub fn is_bft_time_violation(&self) -> bool {
+        self.header1.height() > self.header2.height()
+            && self.header1.signed_header.header.time <= self.header2.signed_header.header.time
+    }
+
     pub fn chain_id_matches(&self, chain_id: &ChainId) -> bool {
         assert_eq!(
             self.header1.signed_header.header.chain_id, self.header2.signed_header.header.chain_id,
@@ -5,12 +5,14 @@ use alloc::string::ToString;
 
 use crate::core::ics02_client::client_type::ClientType;
 
+pub mod bisection;
 pub mod client_state;
 pub mod consensus_state;
 pub mod error;
 pub mod header;
 pub mod host_helpers;
 pub mod misbehaviour;
+pub mod verifier;
 
 pub(crate) const TENDERMINT_CLIENT_TYPE: &str = "07-tendermint";
 
@@ -0,0 +1,461 @@
+//! The light client verifier of the Tendermint client, which hashes headers and validator sets,
+//! and verifies the signatures of commits, with the host functions of the chain it runs on.
+
+use crate::prelude::*;
+
+use alloc::collections::BTreeSet;
+use core::marker::PhantomData;
+
+use tendermint::block::{CommitSig, Header};
+use tendermint::merkle::HASH_SIZE;
+use tendermint::validator::Set as ValidatorSet;
+use tendermint::vote::{SignedVote, ValidatorIndex, Vote};
+use tendermint::Hash;
+use tendermint_light_client_verifier::errors::VerificationError;
+use tendermint_light_client_verifier::operations::{
+    Hasher, ProdCommitValidator, VotingPowerCalculator, VotingPowerTally,
+};
+use tendermint_light_client_verifier::predicates::ProdPredicates;
+use tendermint_light_client_verifier::types::{Commit, SignedHeader, TrustThreshold};
+use tendermint_light_client_verifier::PredicateVerifier;
+use tendermint_proto::Protobuf;
+
+use crate::host_functions::HostFunctionsProvider;
+
+/// The verifier of the Tendermint client, which runs the verification predicates of the light
+/// client with the host functions `H`. Commits are checked for their structure only by
+/// `ProdCommitValidator`, which does no cryptography.
+pub type HostVerifier<H> = PredicateVerifier<
+    ProdPredicates,
+    HostVotingPowerCalculator<H>,
+    ProdCommitValidator,
+    HostHasher<H>,
+>;
+
+/// Hashes headers and validator sets with the sha256 host function.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HostHasher<H>(PhantomData<H>);
+
+impl<H: HostFunctionsProvider> HostHasher<H> {
+    /// Computes the root of the simple Merkle tree whose leaves are `byte_vecs`, as specified by
+    /// Tendermint.
+    fn simple_hash_from_byte_vectors(byte_vecs: &[Vec<u8>]) -> [u8; HASH_SIZE] {
+        match byte_vecs.len() {
+            0 => H::sha2_256(&[]),
+            1 => H::sha2_256(&[&[0x00], byte_vecs[0].as_slice()].concat()),
+            len => {
+                // The largest power of 2 less than `len`
+                let k = len.next_power_of_two() / 2;
+                let left = Self::simple_hash_from_byte_vectors(&byte_vecs[..k]);
+                let right = Self::simple_hash_from_byte_vectors(&byte_vecs[k..]);
+                H::sha2_256(&[&[0x01], left.as_slice(), right.as_slice()].concat())
+            }
+        }
+    }
+}
+
+impl<H: HostFunctionsProvider> Hasher for HostHasher<H> {
+    fn hash_header(&self, header: &Header) -> Hash {
+        // Encoding never fails on a well-formed header, as in `Header::hash`
+        let fields_bytes = vec![
+            header.version.encode_vec().unwrap(),
+            header.chain_id.encode_vec().unwrap(),
+            header.height.encode_vec().unwrap(),
+            header.time.encode_vec().unwrap(),
+            header
+                .last_block_id
+                .unwrap_or_default()
+                .encode_vec()
+                .unwrap(),
+            header
+                .last_commit_hash
+                .unwrap_or_default()
+                .encode_vec()
+                .unwrap(),
+            header.data_hash.unwrap_or_default().encode_vec().unwrap(),
+            header.validators_hash.encode_vec().unwrap(),
+            header.next_validators_hash.encode_vec().unwrap(),
+            header.consensus_hash.encode_vec().unwrap(),
+            header.app_hash.encode_vec().unwrap(),
+            header
+                .last_results_hash
+                .unwrap_or_default()
+                .encode_vec()
+                .unwrap(),
+            header
+                .evidence_hash
+                .unwrap_or_default()
+                .encode_vec()
+                .unwrap(),
+            header.proposer_address.encode_vec().unwrap(),
+        ];
+
+        Hash::Sha256(Self::simple_hash_from_byte_vectors(&fields_bytes))
+    }
+
+    fn hash_validator_set(&self, validator_set: &ValidatorSet) -> Hash {
+        let validator_bytes: Vec<Vec<u8>> = validator_set
+            .validators()
+            .iter()
+            .map(|validator| validator.hash_bytes())
+            .collect();
+
+        Hash::Sha256(Self::simple_hash_from_byte_vectors(&validator_bytes))
+    }
+}
+
+/// Tallies the voting power in a commit, verifying the signatures of the votes with the ed25519
+/// and secp256k1 host functions. The ed25519 signatures are first verified as a batch.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HostVotingPowerCalculator<H>(PhantomData<H>);
+
+impl<H: HostFunctionsProvider> VotingPowerCalculator for HostVotingPowerCalculator<H> {
+    fn voting_power_in(
+        &self,
+        signed_header: &SignedHeader,
+        validator_set: &ValidatorSet,
+        trust_threshold: TrustThreshold,
+    ) -> Result<VotingPowerTally, VerificationError> {
+        let mut seen_validators = BTreeSet::new();
+
+        let non_absent_votes =
+            signed_header
+                .commit
+                .signatures
+                .iter()
+                .enumerate()
+                .flat_map(|(idx, signature)| {
+                    non_absent_vote(
+                        signature,
+                        ValidatorIndex::try_from(idx).unwrap(),
+                        &signed_header.commit,
+                    )
+                    .map(|vote| (signature, vote))
+                });
+
+        let mut signed_votes = Vec::new();
+        for (signature, vote) in non_absent_votes {
+            // Ensure we only count a validator's power once
+            if !seen_validators.insert(vote.validator_address) {
+                return Err(VerificationError::duplicate_validator(
+                    vote.validator_address,
+                ));
+            }
+
+            let validator = match validator_set.validator(vote.validator_address) {
+                Some(validator) => validator,
+                // Cannot find matching validator, so we skip the vote
+                None => continue,
+            };
+
+            let signed_vote =
+                SignedVote::from_vote(vote.clone(), signed_header.header.chain_id.clone())
+                    .ok_or_else(VerificationError::missing_signature)?;
+
+            signed_votes.push((signature.is_commit(), validator, signed_vote));
+        }
+
+        let sign_bytes: Vec<Vec<u8>> = signed_votes
+            .iter()
+            .map(|(_, _, signed_vote)| signed_vote.sign_bytes())
+            .collect();
+
+        // Try to verify all the ed25519 signatures at once, and only verify them one by one if
+        // the batch fails, to find out which signature is invalid
+        let ed25519_votes: Vec<_> = signed_votes
+            .iter()
+            .zip(&sign_bytes)
+            .filter_map(|((_, validator, signed_vote), sign_bytes)| {
+                validator
+                    .pub_key
+                    .ed25519()
+                    .map(|public_key| (signed_vote.signature().as_bytes(), sign_bytes, public_key))
+            })
+            .collect();
+        let ed25519_batch: Vec<(&[u8], &[u8], &[u8])> = ed25519_votes
+            .iter()
+            .map(|(signature, sign_bytes, public_key)| {
+                (
+                    *signature,
+                    sign_bytes.as_slice(),
+                    public_key.as_bytes().as_slice(),
+                )
+            })
+            .collect();
+        let ed25519_batch_valid = H::ed25519_batch_verify(&ed25519_batch);
+
+        let mut tallied_voting_power = 0_u64;
+        for ((is_commit, validator, signed_vote), sign_bytes) in
+            signed_votes.into_iter().zip(sign_bytes)
+        {
+            let signature_bytes = signed_vote.signature().as_bytes();
+            let is_valid = match validator.pub_key.ed25519() {
+                Some(_) if ed25519_batch_valid => true,
+                Some(public_key) => {
+                    H::ed25519_verify(signature_bytes, &sign_bytes, public_key.as_bytes())
+                }
+                None => {
+                    H::secp256k1_verify(signature_bytes, &sign_bytes, &validator.pub_key.to_bytes())
+                }
+            };
+            if !is_valid {
+                return Err(VerificationError::invalid_signature(
+                    signature_bytes.to_vec(),
+                    Box::new(validator),
+                    sign_bytes,
+                ));
+            }
+
+            // Votes for nil are verified, but only votes for the block are tallied
+            if is_commit {
+                tallied_voting_power += validator.power();
+            }
+        }
+
+        Ok(VotingPowerTally {
+            total: self.total_power_of(validator_set),
+            tallied: tallied_voting_power,
+            trust_threshold,
+        })
+    }
+}
+
+/// Returns the vote of a commit signature, unless the validator did not vote.
+fn non_absent_vote(
+    commit_sig: &CommitSig,
+    validator_index: ValidatorIndex,
+    commit: &Commit,
+) -> Option<Vote> {
+    let (validator_address, timestamp, signature, block_id) = match commit_sig {
+        CommitSig::BlockIdFlagAbsent => return None,
+        CommitSig::BlockIdFlagCommit {
+            validator_address,
+            timestamp,
+            signature,
+        } => (
+            *validator_address,
+            *timestamp,
+            signature,
+            Some(commit.block_id),
+        ),
+        CommitSig::BlockIdFlagNil {
+            validator_address,
+            timestamp,
+            signature,
+        } => (*validator_address, *timestamp, signature, None),
+    };
+
+    Some(Vote {
+        vote_type: tendermint::vote::Type::Precommit,
+        height: commit.height,
+        round: commit.round,
+        block_id,
+        timestamp: Some(timestamp),
+        validator_address,
+        validator_index,
+        signature: signature.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tendermint::Time;
+    use tendermint_light_client_verifier::types::UntrustedBlockState;
+    use tendermint_light_client_verifier::Verdict;
+    use tendermint_testgen::light_block::TmLightBlock;
+    use tendermint_testgen::{Generator, LightBlock as TestgenLightBlock, Validator};
+    use test_log::test;
+
+    use crate::host_functions::HostFunctionsManager;
+
+    /// Implements the `ics23` host functions of a test host with those of `HostFunctionsManager`.
+    macro_rules! impl_ics23_host_functions {
+        ($host:ty) => {
+            impl ics23::HostFunctionsProvider for $host {
+                fn sha2_256(message: &[u8]) -> [u8; 32] {
+                    HostFunctionsManager::sha2_256(message)
+                }
+
+                fn sha2_512(message: &[u8]) -> [u8; 64] {
+                    HostFunctionsManager::sha2_512(message)
+                }
+
+                fn sha2_512_truncated(message: &[u8]) -> [u8; 32] {
+                    HostFunctionsManager::sha2_512_truncated(message)
+                }
+
+                fn sha3_512(message: &[u8]) -> [u8; 64] {
+                    HostFunctionsManager::sha3_512(message)
+                }
+
+                fn ripemd160(message: &[u8]) -> [u8; 20] {
+                    HostFunctionsManager::ripemd160(message)
+                }
+            }
+        };
+    }
+
+    /// Host functions which reject every signature.
+    #[derive(Clone, Debug, Default, PartialEq, Eq)]
+    struct RejectingHost;
+
+    impl_ics23_host_functions!(RejectingHost);
+
+    impl HostFunctionsProvider for RejectingHost {
+        fn ed25519_verify(_signature: &[u8], _message: &[u8], _public_key: &[u8]) -> bool {
+            false
+        }
+
+        fn secp256k1_verify(_signature: &[u8], _message: &[u8], _public_key: &[u8]) -> bool {
+            false
+        }
+    }
+
+    /// Host functions which reject every batch of signatures, but verify single signatures.
+    #[derive(Clone, Debug, Default, PartialEq, Eq)]
+    struct BatchRejectingHost;
+
+    impl_ics23_host_functions!(BatchRejectingHost);
+
+    impl HostFunctionsProvider for BatchRejectingHost {
+        fn ed25519_verify(signature: &[u8], message: &[u8], public_key: &[u8]) -> bool {
+            HostFunctionsManager::ed25519_verify(signature, message, public_key)
+        }
+
+        fn secp256k1_verify(_signature: &[u8], _message: &[u8], _public_key: &[u8]) -> bool {
+            false
+        }
+
+        fn ed25519_batch_verify(_batch: &[(&[u8], &[u8], &[u8])]) -> bool {
+            false
+        }
+    }
+
+    /// A light block signed by three validators, so that the Merkle trees hashed have leaves
+    /// which are not a power of two.
+    fn light_block() -> TmLightBlock {
+        let validators = [
+            Validator::new("1").voting_power(40),
+            Validator::new("2").voting_power(30),
+            Validator::new("3").voting_power(30),
+        ];
+        let header = tendermint_testgen::Header::new(&validators)
+            .height(5)
+            .chain_id("test-chain")
+            .next_validators(&validators)
+            .time(Time::from_unix_timestamp(5, 0).unwrap());
+        let commit = tendermint_testgen::Commit::new(header.clone(), 1);
+
+        TestgenLightBlock::new(header, commit)
+            .validators(&validators)
+            .next_validators(&validators)
+            .generate()
+            .unwrap()
+    }
+
+    fn untrusted_state(light_block: &TmLightBlock) -> UntrustedBlockState<'_> {
+        UntrustedBlockState {
+            signed_header: &light_block.signed_header,
+            validators: &light_block.validators,
+            next_validators: Some(&light_block.next_validators),
+        }
+    }
+
+    #[test]
+    fn host_hasher_matches_tendermint() {
+        let light_block = light_block();
+        let hasher = HostHasher::<HostFunctionsManager>::default();
+
+        assert_eq!(
+            hasher.hash_header(&light_block.signed_header.header),
+            light_block.signed_header.header.hash()
+        );
+        assert_eq!(
+            hasher.hash_validator_set(&light_block.validators),
+            light_block.validators.hash()
+        );
+    }
+
+    #[test]
+    fn host_verifier_verifies_commit() {
+        let light_block = light_block();
+        let verifier = HostVerifier::<HostFunctionsManager>::default();
+
+        assert_eq!(
+            verifier.verify_validator_sets(&untrusted_state(&light_block)),
+            Verdict::Success
+        );
+        assert_eq!(
+            verifier.verify_commit(&untrusted_state(&light_block)),
+            Verdict::Success
+        );
+    }
+
+    #[test]
+    fn host_verifier_uses_host_signature_verification() {
+        let light_block = light_block();
+        let verifier = HostVerifier::<RejectingHost>::default();
+
+        assert!(matches!(
+            verifier.verify_commit(&untrusted_state(&light_block)),
+            Verdict::Invalid(_)
+        ));
+    }
+
+    #[test]
+    fn host_verifier_falls_back_on_batch_failure() {
+        let light_block = light_block();
+        let verifier = HostVerifier::<BatchRejectingHost>::default();
+
+        assert_eq!(
+            verifier.verify_commit(&untrusted_state(&light_block)),
+            Verdict::Success
+        );
+    }
+
+    #[test]
+    fn host_batch_verification_agrees_with_single_verification() {
+        // The identity point as public key, and as the `R` part of a signature with a zero `s`.
+        // ZIP-215 accepts this signature of any message, one by one as well as in a batch.
+        let mut public_key = [0_u8; 32];
+        public_key[0] = 1;
+        let mut signature = [0_u8; 64];
+        signature[0] = 1;
+        let message = b"message".as_slice();
+
+        assert!(HostFunctionsManager::ed25519_verify(
+            &signature,
+            message,
+            &public_key
+        ));
+        assert!(HostFunctionsManager::ed25519_batch_verify(&[
+            (signature.as_slice(), message, public_key.as_slice()),
+            (signature.as_slice(), message, public_key.as_slice()),
+        ]));
+    }
+
+    #[test]
+    fn host_verifier_rejects_invalid_signature_in_batch() {
+        let mut light_block = light_block();
+
+        // Swap in the signature of another validator, which is valid, but not for this vote
+        let signatures = &mut light_block.signed_header.commit.signatures;
+        let other_signature = match &signatures[1] {
+            CommitSig::BlockIdFlagCommit { signature, .. } => signature.clone(),
+            _ => panic!("validator did not sign the commit"),
+        };
+        match &mut signatures[0] {
+            CommitSig::BlockIdFlagCommit { signature, .. } => *signature = other_signature,
+            _ => panic!("validator did not sign the commit"),
+        }
+
+        let verifier = HostVerifier::<HostFunctionsManager>::default();
+
+        assert!(matches!(
+            verifier.verify_commit(&untrusted_state(&light_block)),
+            Verdict::Invalid(_)
+        ));
+    }
+}
@@ -0,0 +1,82 @@
+use crate::prelude::*;
+
+use bytes::Buf;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::protobuf::Protobuf;
+use prost::Message;
+
+use crate::clients::ics08_wasm::error::Error;
+use crate::core::ics02_client::error::ClientError;
+
+pub const WASM_CLIENT_MESSAGE_TYPE_URL: &str = "/ibc.lightclients.wasm.v1.ClientMessage";
+
+/// Raw Wasm client message, as defined by the `08-wasm` module of ibc-go. There is no such
+/// message in `ibc-proto`.
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct RawClientMessage {
+    #[prost(bytes = "vec", tag = "1")]
+    pub data: Vec<u8>,
+}
+
+/// A header or a misbehaviour submitted to a Wasm client. Its data is opaque to the host, and is
+/// only interpreted by the light client contract.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClientMessage {
+    pub data: Vec<u8>,
+}
+
+impl ClientMessage {
+    pub fn new(data: Vec<u8>) -> Self {
+        Self { data }
+    }
+}
+
+impl Protobuf<RawClientMessage> for ClientMessage {}
+
+impl From<RawClientMessage> for ClientMessage {
+    fn from(raw: RawClientMessage) -> Self {
+        Self::new(raw.data)
+    }
+}
+
+impl From<ClientMessage> for RawClientMessage {
+    fn from(value: ClientMessage) -> Self {
+        RawClientMessage { data: value.data }
+    }
+}
+
+impl Protobuf<Any> for ClientMessage {}
+
+impl TryFrom<Any> for ClientMessage {
+    type Error = ClientError;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        use core::ops::Deref;
+
+        match raw.type_url.as_str() {
+            WASM_CLIENT_MESSAGE_TYPE_URL => {
+                decode_client_message(raw.value.deref()).map_err(Into::into)
+            }
+            _ => Err(ClientError::UnknownHeaderType {
+                header_type: raw.type_url,
+            }),
+        }
+    }
+}
+
+impl From<ClientMessage> for Any {
+    fn from(client_message: ClientMessage) -> Self {
+        Any {
+            type_url: WASM_CLIENT_MESSAGE_TYPE_URL.to_string(),
+            value: Protobuf::<RawClientMessage>::encode_vec(&client_message)
+                .expect("encoding to `Any` from `WasmClientMessage`"),
+        }
+    }
+}
+
+pub fn decode_client_message<B: Buf>(buf: B) -> Result<ClientMessage, Error> {
+    RawClientMessage::decode(buf)
+        .map(Into::into)
+        .map_err(Error::Decode)
+}
@@ -0,0 +1,629 @@
+use crate::prelude::*;
+
+use alloc::sync::Arc;
+use core::time::Duration;
+
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::core::commitment::v1::MerkleProof;
+use ibc_proto::protobuf::Protobuf;
+use prost::Message;
+
+use crate::clients::ics08_wasm::client_message::ClientMessage;
+use crate::clients::ics08_wasm::consensus_state::ConsensusState as WasmConsensusState;
+use crate::clients::ics08_wasm::engine::WasmEngine;
+use crate::clients::ics08_wasm::error::Error;
+use crate::clients::ics08_wasm::CHECKSUM_LEN;
+use crate::core::ics02_client::client_state::{
+    downcast_client_state, ClientState as Ics2ClientState, UpdatedState, UpgradeOptions,
+};
+use crate::core::ics02_client::client_type::ClientType;
+use crate::core::ics02_client::consensus_state::ConsensusState;
+use crate::core::ics02_client::context::ClientReader;
+use crate::core::ics02_client::error::ClientError;
+use crate::core::ics04_channel::context::ChannelReader;
+use crate::core::ics23_commitment::commitment::{
+    CommitmentPrefix, CommitmentProofBytes, CommitmentRoot,
+};
+use crate::core::ics24_host::identifier::{ChainId, ClientId};
+use crate::core::ics24_host::Path;
+use crate::Height;
+
+use super::client_type as wasm_client_type;
+
+#[cfg(feature = "val_exec_ctx")]
+use crate::core::{ContextError, ValidationContext};
+
+pub const WASM_CLIENT_STATE_TYPE_URL: &str = "/ibc.lightclients.wasm.v1.ClientState";
+
+/// Raw Wasm client state, as defined by the `08-wasm` module of ibc-go. There is no such message
+/// in `ibc-proto`.
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct RawClientState {
+    #[prost(bytes = "vec", tag = "1")]
+    pub data: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub checksum: Vec<u8>,
+    #[prost(message, optional, tag = "3")]
+    pub latest_height: Option<ibc_proto::ibc::core::client::v1::Height>,
+}
+
+/// The client state of a Wasm client: the opaque state of a light client contract, along with
+/// the checksum of the contract code.
+///
+/// All client verification is delegated to the [`WasmEngine`] attached to the client state. A
+/// client state decoded from its protobuf encoding has no engine, so the host must attach one
+/// with [`ClientState::with_engine`] before handing the client state to the core handlers.
+/// Without an engine, the client is considered expired and every verification fails.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug)]
+pub struct ClientState {
+    pub data: Vec<u8>,
+    pub checksum: Vec<u8>,
+    pub latest_height: Height,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    engine: Option<Arc<dyn WasmEngine>>,
+}
+
+impl ClientState {
+    pub fn new(data: Vec<u8>, checksum: Vec<u8>, latest_height: Height) -> Result<Self, Error> {
+        if checksum.len() != CHECKSUM_LEN {
+            return Err(Error::InvalidChecksum {
+                len: checksum.len(),
+                expected: CHECKSUM_LEN,
+            });
+        }
+
+        Ok(Self {
+            data,
+            checksum,
+            latest_height,
+            engine: None,
+        })
+    }
+
+    /// Attaches the engine which runs the light client contract of this client.
+    pub fn with_engine(self, engine: Arc<dyn WasmEngine>) -> Self {
+        Self {
+            engine: Some(engine),
+            ..self
+        }
+    }
+
+    pub fn latest_height(&self) -> Height {
+        self.latest_height
+    }
+
+    fn engine(&self) -> Result<&Arc<dyn WasmEngine>, Error> {
+        self.engine.as_ref().ok_or(Error::MissingEngine)
+    }
+
+    /// Runs the engine on a header, given the latest consensus state of the client.
+    fn update(
+        &self,
+        latest_consensus_state: &dyn ConsensusState,
+        header: Any,
+    ) -> Result<UpdatedState, ClientError> {
+        let engine = self.engine()?;
+        let client_message = ClientMessage::try_from(header)?;
+        let latest_consensus_state = downcast_wasm_consensus_state(latest_consensus_state)?;
+
+        let (client_state, consensus_state) =
+            engine.update_state(self, latest_consensus_state, &client_message)?;
+
+        Ok(UpdatedState {
+            client_state: client_state.with_engine(engine.clone()).into_box(),
+            consensus_state: consensus_state.into_box(),
+        })
+    }
+
+    fn misbehave(&self, misbehaviour: Any) -> Result<Box<dyn Ics2ClientState>, ClientError> {
+        let engine = self.engine()?;
+        let client_message = ClientMessage::try_from(misbehaviour)?;
+
+        let client_state = engine.check_misbehaviour(self, &client_message)?;
+
+        Ok(client_state.with_engine(engine.clone()).into_box())
+    }
+
+    /// Asks the engine whether a client message is a misbehaviour, as both headers and
+    /// misbehaviours are wrapped in the same `ClientMessage` type.
+    fn is_misbehaviour(&self, client_message: Any) -> Result<bool, ClientError> {
+        let engine = self.engine()?;
+        let client_message = ClientMessage::try_from(client_message)?;
+
+        engine.check_for_misbehaviour(self, &client_message)
+    }
+
+    fn verify_membership(
+        &self,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        path: Path,
+        value: Vec<u8>,
+    ) -> Result<(), ClientError> {
+        self.engine()?
+            .verify_membership(self, prefix, proof, root, path, value)
+    }
+
+    fn verify_non_membership(
+        &self,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        path: Path,
+    ) -> Result<(), ClientError> {
+        self.engine()?
+            .verify_non_membership(self, prefix, proof, root, path)
+    }
+}
+
+/// The engine is an execution environment, not part of the state, so it is ignored.
+impl PartialEq for ClientState {
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
+            && self.checksum == other.checksum
+            && self.latest_height == other.latest_height
+    }
+}
+
+impl Eq for ClientState {}
+
+impl Ics2ClientState for ClientState {
+    /// The chain tracked by a Wasm client is only known to its contract.
+    fn chain_id(&self) -> ChainId {
+        ChainId::default()
+    }
+
+    fn client_type(&self) -> ClientType {
+        wasm_client_type()
+    }
+
+    fn latest_height(&self) -> Height {
+        self.latest_height
+    }
+
+    fn frozen_height(&self) -> Option<Height> {
+        self.engine
+            .as_ref()
+            .and_then(|engine| engine.frozen_height(self))
+    }
+
+    /// Upgrades are carried out by the contract, in `verify_upgrade_and_update_state`, so this is
+    /// a no-op.
+    fn upgrade(
+        &mut self,
+        _upgrade_height: Height,
+        _upgrade_options: &dyn UpgradeOptions,
+        _chain_id: ChainId,
+    ) {
+    }
+
+    fn expired(&self, elapsed: Duration) -> bool {
+        self.engine
+            .as_ref()
+            .map_or(true, |engine| engine.expired(self, elapsed))
+    }
+
+    fn initialise(&self, consensus_state: Any) -> Result<Box<dyn ConsensusState>, ClientError> {
+        WasmConsensusState::try_from(consensus_state).map(WasmConsensusState::into_box)
+    }
+
+    fn check_header_and_update_state(
+        &self,
+        ctx: &dyn ClientReader,
+        client_id: ClientId,
+        header: Any,
+    ) -> Result<UpdatedState, ClientError> {
+        let latest_consensus_state = ctx.consensus_state(&client_id, &self.latest_height)?;
+
+        self.update(latest_consensus_state.as_ref(), header)
+    }
+
+    #[cfg(feature = "val_exec_ctx")]
+    fn new_check_header_and_update_state(
+        &self,
+        ctx: &dyn ValidationContext,
+        client_id: ClientId,
+        header: Any,
+    ) -> Result<UpdatedState, ClientError> {
+        let latest_consensus_state = ctx
+            .consensus_state(&client_id, &self.latest_height)
+            .map_err(|e| ClientError::Other {
+                description: e.to_string(),
+            })?;
+
+        self.update(latest_consensus_state.as_ref(), header)
+    }
+
+    fn check_for_misbehaviour(
+        &self,
+        _ctx: &dyn ClientReader,
+        _client_id: &ClientId,
+        client_message: Any,
+    ) -> Result<bool, ClientError> {
+        self.is_misbehaviour(client_message)
+    }
+
+    #[cfg(feature = "val_exec_ctx")]
+    fn new_check_for_misbehaviour(
+        &self,
+        _ctx: &dyn ValidationContext,
+        _client_id: &ClientId,
+        client_message: Any,
+    ) -> Result<bool, ClientError> {
+        self.is_misbehaviour(client_message)
+    }
+
+    fn check_misbehaviour_and_update_state(
+        &self,
+        _ctx: &dyn ClientReader,
+        _client_id: ClientId,
+        misbehaviour: Any,
+    ) -> Result<Box<dyn Ics2ClientState>, ClientError> {
+        self.misbehave(misbehaviour)
+    }
+
+    #[cfg(feature = "val_exec_ctx")]
+    fn new_check_misbehaviour_and_update_state(
+        &self,
+        _ctx: &dyn ValidationContext,
+        _client_id: ClientId,
+        misbehaviour: Any,
+    ) -> Result<Box<dyn Ics2ClientState>, ContextError> {
+        self.misbehave(misbehaviour).map_err(ContextError::from)
+    }
+
+    fn verify_upgrade_and_update_state(
+        &self,
+        upgraded_client_state: Any,
+        upgraded_consensus_state: Any,
+        proof_upgrade_client: MerkleProof,
+        proof_upgrade_consensus_state: MerkleProof,
+        root: &CommitmentRoot,
+    ) -> Result<UpdatedState, ClientError> {
+        let engine = self.engine()?;
+        let upgraded_client_state =
+            ClientState::try_from(upgraded_client_state)?.with_engine(engine.clone());
+        let upgraded_consensus_state = WasmConsensusState::try_from(upgraded_consensus_state)?;
+
+        let (client_state, consensus_state) = engine.verify_upgrade(
+            self,
+            &upgraded_client_state,
+            &upgraded_consensus_state,
+            proof_upgrade_client,
+            proof_upgrade_consensus_state,
+            root,
+        )?;
+
+        Ok(UpdatedState {
+            client_state: client_state.with_engine(engine.clone()).into_box(),
+            consensus_state: consensus_state.into_box(),
+        })
+    }
+
+    fn check_substitute_and_update_state(
+        &self,
+        substitute_client_state: &dyn Ics2ClientState,
+    ) -> Result<Box<dyn Ics2ClientState>, ClientError> {
+        let engine = self.engine()?;
+        let substitute_client_state = downcast_client_state::<ClientState>(substitute_client_state)
+            .ok_or_else(|| ClientError::ClientArgsTypeMismatch {
+                client_type: wasm_client_type(),
+            })?;
+
+        if substitute_client_state.checksum != self.checksum {
+            return Err(Error::MismatchedChecksum.into());
+        }
+
+        let client_state = engine.check_substitute(self, substitute_client_state)?;
+
+        Ok(client_state.with_engine(engine.clone()).into_box())
+    }
+
+    fn validate_proof_height(&self, proof_height: Height) -> Result<(), ClientError> {
+        if self.latest_height < proof_height {
+            return Err(Error::InsufficientHeight {
+                latest_height: self.latest_height,
+                target_height: proof_height,
+            }
+            .into());
+        }
+
+        match self.frozen_height() {
+            Some(frozen_height) if frozen_height <= proof_height => Err(Error::ClientFrozen {
+                frozen_height,
+                target_height: proof_height,
+            }
+            .into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn verify_membership(
+        &mut self,
+        _ctx: &dyn ChannelReader,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        path: Path,
+        value: Vec<u8>,
+    ) -> Result<(), ClientError> {
+        ClientState::verify_membership(self, prefix, proof, root, path, value)
+    }
+
+    fn verify_non_membership(
+        &mut self,
+        _ctx: &dyn ChannelReader,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        path: Path,
+    ) -> Result<(), ClientError> {
+        ClientState::verify_non_membership(self, prefix, proof, root, path)
+    }
+
+    #[cfg(feature = "val_exec_ctx")]
+    fn new_verify_membership(
+        &mut self,
+        _ctx: &dyn ValidationContext,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        path: Path,
+        value: Vec<u8>,
+    ) -> Result<(), ClientError> {
+        ClientState::verify_membership(self, prefix, proof, root, path, value)
+    }
+
+    #[cfg(feature = "val_exec_ctx")]
+    fn new_verify_non_membership(
+        &mut self,
+        _ctx: &dyn ValidationContext,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        path: Path,
+    ) -> Result<(), ClientError> {
+        ClientState::verify_non_membership(self, prefix, proof, root, path)
+    }
+}
+
+fn downcast_wasm_consensus_state(
+    cs: &dyn ConsensusState,
+) -> Result<&WasmConsensusState, ClientError> {
+    cs.as_any()
+        .downcast_ref::<WasmConsensusState>()
+        .ok_or_else(|| ClientError::ClientArgsTypeMismatch {
+            client_type: wasm_client_type(),
+        })
+}
+
+impl Protobuf<RawClientState> for ClientState {}
+
+impl TryFrom<RawClientState> for ClientState {
+    type Error = Error;
+
+    fn try_from(raw: RawClientState) -> Result<Self, Self::Error> {
+        let latest_height = raw
+            .latest_height
+            .ok_or_else(|| Error::InvalidRawClientState {
+                reason: "missing latest height".into(),
+            })?
+            .try_into()
+            .map_err(|_| Error::InvalidRawClientState {
+                reason: "latest height cannot be zero".into(),
+            })?;
+
+        Self::new(raw.data, raw.checksum, latest_height)
+    }
+}
+
+impl From<ClientState> for RawClientState {
+    fn from(value: ClientState) -> Self {
+        RawClientState {
+            data: value.data,
+            checksum: value.checksum,
+            latest_height: Some(value.latest_height.into()),
+        }
+    }
+}
+
+impl Protobuf<Any> for ClientState {}
+
+impl TryFrom<Any> for ClientState {
+    type Error = ClientError;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        use bytes::Buf;
+        use core::ops::Deref;
+
+        fn decode_client_state<B: Buf>(buf: B) -> Result<ClientState, Error> {
+            RawClientState::decode(buf)
+                .map_err(Error::Decode)?
+                .try_into()
+        }
+
+        match raw.type_url.as_str() {
+            WASM_CLIENT_STATE_TYPE_URL => {
+                decode_client_state(raw.value.deref()).map_err(Into::into)
+            }
+            _ => Err(ClientError::UnknownClientStateType {
+                client_state_type: raw.type_url,
+            }),
+        }
+    }
+}
+
+impl From<ClientState> for Any {
+    fn from(client_state: ClientState) -> Self {
+        Any {
+            type_url: WASM_CLIENT_STATE_TYPE_URL.to_string(),
+            value: Protobuf::<RawClientState>::encode_vec(&client_state)
+                .expect("encoding to `Any` from `WasmClientState`"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    use ibc_proto::ibc::mock::{Header as RawMockHeader, Misbehaviour as RawMisbehaviour};
+
+    use crate::core::ics02_client::client_state::Status;
+    use crate::core::ics02_client::msgs::create_client::MsgCreateClient;
+    use crate::core::ics02_client::msgs::misbehaviour::MsgSubmitMisbehaviour;
+    use crate::core::ics02_client::msgs::update_client::MsgUpdateClient;
+    use crate::core::ics02_client::msgs::ClientMsg;
+    use crate::core::ics26_routing::msgs::MsgEnvelope;
+    use crate::mock::context::MockContext;
+    use crate::mock::header::MockHeader;
+    use crate::mock::wasm_engine::MockWasmEngine;
+    use crate::test_utils::get_dummy_account_id;
+    use crate::timestamp::Timestamp;
+
+    fn checksum() -> Vec<u8> {
+        vec![0x42; CHECKSUM_LEN]
+    }
+
+    fn client_message(header: MockHeader) -> ClientMessage {
+        ClientMessage::new(RawMockHeader::from(header).encode_to_vec())
+    }
+
+    #[test]
+    fn client_state_new_checks_checksum() {
+        let height = Height::new(0, 1).unwrap();
+
+        assert!(ClientState::new(vec![], checksum(), height).is_ok());
+        assert!(ClientState::new(vec![], vec![0x42; 20], height).is_err());
+    }
+
+    #[test]
+    fn client_state_without_engine() {
+        let client_state =
+            ClientState::new(vec![], checksum(), Height::new(0, 1).unwrap()).unwrap();
+        let consensus_state =
+            WasmConsensusState::new(vec![], Timestamp::now(), CommitmentRoot::from(vec![0]));
+        let header = client_message(MockHeader::new(Height::new(0, 2).unwrap()));
+
+        assert!(client_state.expired(Duration::ZERO));
+        assert!(client_state
+            .update(&consensus_state, header.clone().into())
+            .is_err());
+
+        let client_state = client_state.with_engine(Arc::new(MockWasmEngine));
+        assert!(!client_state.expired(Duration::ZERO));
+        assert!(client_state.update(&consensus_state, header.into()).is_ok());
+    }
+
+    #[test]
+    fn client_state_encoding_round_trip() {
+        let client_state = ClientState::new(vec![1, 2, 3], checksum(), Height::new(0, 1).unwrap())
+            .unwrap()
+            .with_engine(Arc::new(MockWasmEngine));
+
+        let decoded = ClientState::try_from(Any::from(client_state.clone())).unwrap();
+        assert_eq!(decoded, client_state);
+        assert!(decoded.engine.is_none());
+    }
+
+    #[test]
+    fn wasm_client_lifecycle() {
+        let mut ctx = MockContext::default();
+        let client_id = ClientId::new(wasm_client_type(), 0).unwrap();
+        let latest_height = Height::new(0, 1).unwrap();
+        let now = ClientReader::host_timestamp(&ctx).unwrap();
+
+        let client_state = ClientState::new(vec![], checksum(), latest_height).unwrap();
+        let consensus_state = WasmConsensusState::new(vec![], now, CommitmentRoot::from(vec![0]));
+        let msg = MsgCreateClient::new(
+            client_state.into(),
+            consensus_state.into(),
+            get_dummy_account_id(),
+        );
+        ctx.deliver(MsgEnvelope::Client(ClientMsg::CreateClient(msg)))
+            .unwrap();
+
+        let header_height = Height::new(0, 2).unwrap();
+        let header = MockHeader::new(header_height).with_timestamp(now);
+        let msg = MsgUpdateClient {
+            client_id: client_id.clone(),
+            header: client_message(header).into(),
+            signer: get_dummy_account_id(),
+        };
+        ctx.deliver(MsgEnvelope::Client(ClientMsg::UpdateClient(msg)))
+            .unwrap();
+
+        let client_state = ClientReader::client_state(&ctx, &client_id).unwrap();
+        assert_eq!(client_state.latest_height(), header_height);
+        assert_eq!(client_state.status(&ctx, &client_id), Status::Active);
+        assert!(ClientReader::consensus_state(&ctx, &client_id, &header_height).is_ok());
+
+        // The contract rejects headers which do not advance the client
+        let msg = MsgUpdateClient {
+            client_id: client_id.clone(),
+            header: client_message(header).into(),
+            signer: get_dummy_account_id(),
+        };
+        assert!(ctx
+            .deliver(MsgEnvelope::Client(ClientMsg::UpdateClient(msg)))
+            .is_err());
+
+        let misbehaviour_height = Height::new(0, 3).unwrap();
+        let misbehaviour = RawMisbehaviour {
+            client_id: client_id.to_string(),
+            header1: Some(MockHeader::new(misbehaviour_height).into()),
+            header2: Some(MockHeader::new(misbehaviour_height).into()),
+        };
+        let msg = MsgSubmitMisbehaviour {
+            client_id: client_id.clone(),
+            misbehaviour: ClientMessage::new(misbehaviour.encode_to_vec()).into(),
+            signer: get_dummy_account_id(),
+        };
+        ctx.deliver(MsgEnvelope::Client(ClientMsg::Misbehaviour(msg)))
+            .unwrap();
+
+        let client_state = ClientReader::client_state(&ctx, &client_id).unwrap();
+        assert_eq!(client_state.frozen_height(), Some(misbehaviour_height));
+        assert_eq!(client_state.status(&ctx, &client_id), Status::Frozen);
+    }
+
+    #[test]
+    fn wasm_client_misbehaviour_in_update() {
+        let mut ctx = MockContext::default();
+        let client_id = ClientId::new(wasm_client_type(), 0).unwrap();
+        let now = ClientReader::host_timestamp(&ctx).unwrap();
+
+        let client_state =
+            ClientState::new(vec![], checksum(), Height::new(0, 1).unwrap()).unwrap();
+        let consensus_state = WasmConsensusState::new(vec![], now, CommitmentRoot::from(vec![0]));
+        let msg = MsgCreateClient::new(
+            client_state.into(),
+            consensus_state.into(),
+            get_dummy_account_id(),
+        );
+        ctx.deliver(MsgEnvelope::Client(ClientMsg::CreateClient(msg)))
+            .unwrap();
+
+        // Headers and misbehaviours share a type URL, so the contract tells them apart
+        let misbehaviour_height = Height::new(0, 2).unwrap();
+        let misbehaviour = RawMisbehaviour {
+            client_id: client_id.to_string(),
+            header1: Some(MockHeader::new(misbehaviour_height).into()),
+            header2: Some(MockHeader::new(misbehaviour_height).into()),
+        };
+        let msg = MsgUpdateClient {
+            client_id: client_id.clone(),
+            header: ClientMessage::new(misbehaviour.encode_to_vec()).into(),
+            signer: get_dummy_account_id(),
+        };
+        ctx.deliver(MsgEnvelope::Client(ClientMsg::UpdateClient(msg)))
+            .unwrap();
+
+        let client_state = ClientReader::client_state(&ctx, &client_id).unwrap();
+        assert_eq!(client_state.frozen_height(), Some(misbehaviour_height));
+        assert!(ClientReader::consensus_state(&ctx, &client_id, &misbehaviour_height).is_err());
+    }
+}
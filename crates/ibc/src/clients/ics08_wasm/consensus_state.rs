@@ -0,0 +1,119 @@
+use crate::prelude::*;
+
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::core::commitment::v1::MerkleRoot;
+use ibc_proto::protobuf::Protobuf;
+use prost::Message;
+
+use crate::clients::ics08_wasm::error::Error;
+use crate::core::ics02_client::error::ClientError;
+use crate::core::ics23_commitment::commitment::CommitmentRoot;
+use crate::timestamp::Timestamp;
+
+pub const WASM_CONSENSUS_STATE_TYPE_URL: &str = "/ibc.lightclients.wasm.v1.ConsensusState";
+
+/// Raw Wasm consensus state. There is no such message in `ibc-proto`.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RawConsensusState {
+    #[prost(bytes = "vec", tag = "1")]
+    pub data: Vec<u8>,
+    #[prost(uint64, tag = "2")]
+    pub timestamp: u64,
+    #[prost(message, optional, tag = "3")]
+    pub root: Option<MerkleRoot>,
+}
+
+/// The consensus state of a Wasm client. Its data is opaque to the host, while the timestamp and
+/// commitment root are exposed by the light client contract so that the core handlers can use
+/// them.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConsensusState {
+    pub data: Vec<u8>,
+    pub timestamp: Timestamp,
+    pub root: CommitmentRoot,
+}
+
+impl ConsensusState {
+    pub fn new(data: Vec<u8>, timestamp: Timestamp, root: CommitmentRoot) -> Self {
+        Self {
+            data,
+            timestamp,
+            root,
+        }
+    }
+}
+
+impl crate::core::ics02_client::consensus_state::ConsensusState for ConsensusState {
+    fn root(&self) -> &CommitmentRoot {
+        &self.root
+    }
+
+    fn timestamp(&self) -> Timestamp {
+        self.timestamp
+    }
+}
+
+impl Protobuf<RawConsensusState> for ConsensusState {}
+
+impl TryFrom<RawConsensusState> for ConsensusState {
+    type Error = Error;
+
+    fn try_from(raw: RawConsensusState) -> Result<Self, Self::Error> {
+        let root = raw.root.ok_or_else(|| Error::InvalidRawConsensusState {
+            reason: "missing commitment root".into(),
+        })?;
+        let timestamp =
+            Timestamp::from_nanoseconds(raw.timestamp).map_err(Error::InvalidTimestamp)?;
+
+        Ok(Self::new(raw.data, timestamp, root.hash.into()))
+    }
+}
+
+impl From<ConsensusState> for RawConsensusState {
+    fn from(value: ConsensusState) -> Self {
+        RawConsensusState {
+            data: value.data,
+            timestamp: value.timestamp.nanoseconds(),
+            root: Some(MerkleRoot {
+                hash: value.root.into_vec(),
+            }),
+        }
+    }
+}
+
+impl Protobuf<Any> for ConsensusState {}
+
+impl TryFrom<Any> for ConsensusState {
+    type Error = ClientError;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        use bytes::Buf;
+        use core::ops::Deref;
+
+        fn decode_consensus_state<B: Buf>(buf: B) -> Result<ConsensusState, Error> {
+            RawConsensusState::decode(buf)
+                .map_err(Error::Decode)?
+                .try_into()
+        }
+
+        match raw.type_url.as_str() {
+            WASM_CONSENSUS_STATE_TYPE_URL => {
+                decode_consensus_state(raw.value.deref()).map_err(Into::into)
+            }
+            _ => Err(ClientError::UnknownConsensusStateType {
+                consensus_state_type: raw.type_url,
+            }),
+        }
+    }
+}
+
+impl From<ConsensusState> for Any {
+    fn from(consensus_state: ConsensusState) -> Self {
+        Any {
+            type_url: WASM_CONSENSUS_STATE_TYPE_URL.to_string(),
+            value: Protobuf::<RawConsensusState>::encode_vec(&consensus_state)
+                .expect("encoding to `Any` from `WasmConsensusState`"),
+        }
+    }
+}
@@ -0,0 +1,101 @@
+//! The interface through which a Wasm client delegates client verification to the light client
+//! contract identified by its code checksum.
+
+use crate::prelude::*;
+
+use core::time::Duration;
+
+use ibc_proto::ibc::core::commitment::v1::MerkleProof;
+
+use crate::clients::ics08_wasm::client_message::ClientMessage;
+use crate::clients::ics08_wasm::client_state::ClientState;
+use crate::clients::ics08_wasm::consensus_state::ConsensusState;
+use crate::core::ics02_client::error::ClientError;
+use crate::core::ics23_commitment::commitment::{
+    CommitmentPrefix, CommitmentProofBytes, CommitmentRoot,
+};
+use crate::core::ics24_host::Path;
+use crate::Height;
+
+/// Executes light client contracts on behalf of Wasm clients. It is supplied by the host, which
+/// is responsible for storing the contract code under its checksum, and for running the contract
+/// named by `client_state.checksum` on every call.
+///
+/// The engine is given the full client state, whose opaque data it is expected to interpret, and
+/// returns the states the client should be updated to. It never writes to the host store itself.
+pub trait WasmEngine: core::fmt::Debug + Send + Sync {
+    /// Height at which the contract considers the client frozen, if any.
+    fn frozen_height(&self, client_state: &ClientState) -> Option<Height>;
+
+    /// Whether the client is expired, when `elapsed` time has passed since the timestamp of its
+    /// latest consensus state.
+    fn expired(&self, client_state: &ClientState, elapsed: Duration) -> bool;
+
+    /// Verifies a header against the client, whose latest consensus state is
+    /// `latest_consensus_state`, and returns the updated client state along with the consensus
+    /// state at the height of the header.
+    fn update_state(
+        &self,
+        client_state: &ClientState,
+        latest_consensus_state: &ConsensusState,
+        client_message: &ClientMessage,
+    ) -> Result<(ClientState, ConsensusState), ClientError>;
+
+    /// Whether the contract interprets `client_message` as evidence of misbehaviour, rather than
+    /// as a header.
+    fn check_for_misbehaviour(
+        &self,
+        client_state: &ClientState,
+        client_message: &ClientMessage,
+    ) -> Result<bool, ClientError>;
+
+    /// Verifies a misbehaviour against the client, and returns the frozen client state.
+    fn check_misbehaviour(
+        &self,
+        client_state: &ClientState,
+        client_message: &ClientMessage,
+    ) -> Result<ClientState, ClientError>;
+
+    /// Verifies the upgraded client and consensus states against `root`, and returns the states
+    /// the client should be upgraded to.
+    fn verify_upgrade(
+        &self,
+        client_state: &ClientState,
+        upgraded_client_state: &ClientState,
+        upgraded_consensus_state: &ConsensusState,
+        proof_upgrade_client: MerkleProof,
+        proof_upgrade_consensus_state: MerkleProof,
+        root: &CommitmentRoot,
+    ) -> Result<(ClientState, ConsensusState), ClientError>;
+
+    /// Checks that `substitute_client_state` may take the place of the client, and returns the
+    /// client state it should be recovered to. Both clients run the same contract.
+    fn check_substitute(
+        &self,
+        client_state: &ClientState,
+        substitute_client_state: &ClientState,
+    ) -> Result<ClientState, ClientError>;
+
+    /// Verifies a `proof` that `value` is stored at `path` under `prefix` on the counterparty,
+    /// against the commitment `root`.
+    fn verify_membership(
+        &self,
+        client_state: &ClientState,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        path: Path,
+        value: Vec<u8>,
+    ) -> Result<(), ClientError>;
+
+    /// Verifies a `proof` that no value is stored at `path` under `prefix` on the counterparty,
+    /// against the commitment `root`.
+    fn verify_non_membership(
+        &self,
+        client_state: &ClientState,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        path: Path,
+    ) -> Result<(), ClientError>;
+}
@@ -0,0 +1,54 @@
+use crate::prelude::*;
+
+use displaydoc::Display;
+
+use crate::core::ics02_client::error::ClientError;
+use crate::timestamp::ParseTimestampError;
+use crate::Height;
+
+#[derive(Debug, Display)]
+pub enum Error {
+    /// invalid raw client state: `{reason}`
+    InvalidRawClientState { reason: String },
+    /// invalid raw consensus state: `{reason}`
+    InvalidRawConsensusState { reason: String },
+    /// invalid code checksum of length `{len}`, expected `{expected}` bytes
+    InvalidChecksum { len: usize, expected: usize },
+    /// invalid timestamp error: `{0}`
+    InvalidTimestamp(ParseTimestampError),
+    /// decode error: `{0}`
+    Decode(prost::DecodeError),
+    /// no Wasm engine is attached to the client state
+    MissingEngine,
+    /// substitute client code checksum does not match the subject client's
+    MismatchedChecksum,
+    /// the height is insufficient: latest_height=`{latest_height}` target_height=`{target_height}`
+    InsufficientHeight {
+        latest_height: Height,
+        target_height: Height,
+    },
+    /// the client is frozen: frozen_height=`{frozen_height}` target_height=`{target_height}`
+    ClientFrozen {
+        frozen_height: Height,
+        target_height: Height,
+    },
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self {
+            Self::InvalidTimestamp(e) => Some(e),
+            Self::Decode(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<Error> for ClientError {
+    fn from(e: Error) -> Self {
+        Self::ClientSpecific {
+            description: e.to_string(),
+        }
+    }
+}
@@ -0,0 +1,27 @@
+//! ICS 08: Wasm Client implements a proxy light client, whose states are opaque bytes interpreted
+//! by a light client contract. The contract is identified by the checksum of its code, and every
+//! client verification is delegated to it through a [`WasmEngine`](engine::WasmEngine) supplied
+//! by the host.
+//!
+//! This lets chains support new consensus algorithms by deploying contracts, instead of adding
+//! clients to this crate. The host attaches its engine to the Wasm client states it decodes, with
+//! [`ClientState::with_engine`](client_state::ClientState::with_engine).
+
+use alloc::string::ToString;
+
+use crate::core::ics02_client::client_type::ClientType;
+
+pub mod client_message;
+pub mod client_state;
+pub mod consensus_state;
+pub mod engine;
+pub mod error;
+
+pub(crate) const WASM_CLIENT_TYPE: &str = "08-wasm";
+
+/// Length of the SHA-256 checksum identifying the code of a light client contract.
+pub const CHECKSUM_LEN: usize = 32;
+
+pub fn client_type() -> ClientType {
+    ClientType::new(WASM_CLIENT_TYPE.to_string())
+}
@@ -0,0 +1,603 @@
+use crate::prelude::*;
+
+use core::time::Duration;
+
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::core::commitment::v1::MerkleProof;
+use ibc_proto::ibc::lightclients::localhost::v1::ClientState as RawLocalhostClientState;
+use ibc_proto::protobuf::Protobuf;
+use prost::Message;
+
+use crate::clients::ics09_localhost::consensus_state::ConsensusState as LocalhostConsensusState;
+use crate::clients::ics09_localhost::error::Error;
+use crate::core::ics02_client::client_state::{
+    ClientState as Ics2ClientState, UpdatedState, UpgradeOptions,
+};
+use crate::core::ics02_client::client_type::ClientType;
+use crate::core::ics02_client::consensus_state::ConsensusState;
+use crate::core::ics02_client::context::ClientReader;
+use crate::core::ics02_client::error::ClientError;
+use crate::core::ics04_channel::commitment::{AcknowledgementCommitment, PacketCommitment};
+use crate::core::ics04_channel::context::ChannelReader;
+use crate::core::ics04_channel::packet::Sequence;
+use crate::core::ics23_commitment::commitment::{
+    CommitmentPrefix, CommitmentProofBytes, CommitmentRoot,
+};
+use crate::core::ics24_host::identifier::{ChainId, ClientId};
+use crate::core::ics24_host::path::{
+    AcksPath, ChannelEndsPath, CommitmentsPath, ReceiptsPath, SeqRecvsPath,
+};
+use crate::core::ics24_host::Path;
+use crate::timestamp::Timestamp;
+use crate::Height;
+
+use super::client_type as localhost_client_type;
+
+#[cfg(feature = "val_exec_ctx")]
+use crate::core::context::ContextError;
+#[cfg(feature = "val_exec_ctx")]
+use crate::core::ValidationContext;
+
+pub const LOCALHOST_CLIENT_STATE_TYPE_URL: &str = "/ibc.lightclients.localhost.v1.ClientState";
+
+/// The client state of the localhost client, which tracks the host chain itself.
+///
+/// Proofs are never checked by this client: the value to verify at a path is read from the host
+/// store, through the context it is given. Only the channel and packet values can
+/// be verified this way, since the localhost client does not take part in connection handshakes.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClientState {
+    pub chain_id: ChainId,
+    pub latest_height: Height,
+}
+
+impl ClientState {
+    pub fn new(chain_id: ChainId, latest_height: Height) -> Self {
+        Self {
+            chain_id,
+            latest_height,
+        }
+    }
+
+    pub fn latest_height(&self) -> Height {
+        self.latest_height
+    }
+
+    /// Returns the states tracking the host at `host_height` and `host_timestamp`.
+    pub fn update(&self, host_height: Height, host_timestamp: Timestamp) -> UpdatedState {
+        UpdatedState {
+            client_state: Self::new(self.chain_id.clone(), host_height).into_box(),
+            consensus_state: LocalhostConsensusState::new(host_timestamp).into_box(),
+        }
+    }
+}
+
+/// Reads the value stored at `path` from the host store, encoded as the handlers encode the
+/// values they verify, or `None` if no value is stored there.
+fn read_value(ctx: &dyn ChannelReader, path: &Path) -> Result<Option<Vec<u8>>, ClientError> {
+    let value = match path {
+        Path::ChannelEnds(ChannelEndsPath(port_id, channel_id)) => ctx
+            .channel_end(port_id, channel_id)
+            .ok()
+            .map(|channel_end| channel_end.encode_vec())
+            .transpose()
+            .map_err(ClientError::InvalidChannelEnd)?,
+        Path::Commitments(CommitmentsPath {
+            port_id,
+            channel_id,
+            sequence,
+        }) => ctx
+            .get_packet_commitment(port_id, channel_id, sequence)
+            .ok()
+            .map(PacketCommitment::into_vec),
+        Path::Acks(AcksPath {
+            port_id,
+            channel_id,
+            sequence,
+        }) => ctx
+            .get_packet_acknowledgement(port_id, channel_id, sequence)
+            .ok()
+            .map(AcknowledgementCommitment::into_vec),
+        Path::SeqRecvs(SeqRecvsPath(port_id, channel_id)) => ctx
+            .get_next_sequence_recv(port_id, channel_id)
+            .ok()
+            .map(sequence_bytes),
+        Path::Receipts(ReceiptsPath {
+            port_id,
+            channel_id,
+            sequence,
+        }) => ctx
+            .get_packet_receipt(port_id, channel_id, sequence)
+            .ok()
+            .map(|_| Vec::new()),
+        path => return Err(unsupported_path(path)),
+    };
+
+    Ok(value)
+}
+
+#[cfg(feature = "val_exec_ctx")]
+fn new_read_value(
+    ctx: &dyn ValidationContext,
+    path: &Path,
+) -> Result<Option<Vec<u8>>, ClientError> {
+    let value = match path {
+        Path::ChannelEnds(ChannelEndsPath(port_id, channel_id)) => ctx
+            .channel_end(&(port_id.clone(), channel_id.clone()))
+            .ok()
+            .map(|channel_end| channel_end.encode_vec())
+            .transpose()
+            .map_err(ClientError::InvalidChannelEnd)?,
+        Path::Commitments(CommitmentsPath {
+            port_id,
+            channel_id,
+            sequence,
+        }) => ctx
+            .get_packet_commitment(&(port_id.clone(), channel_id.clone(), *sequence))
+            .ok()
+            .map(PacketCommitment::into_vec),
+        Path::Acks(AcksPath {
+            port_id,
+            channel_id,
+            sequence,
+        }) => ctx
+            .get_packet_acknowledgement(&(port_id.clone(), channel_id.clone(), *sequence))
+            .ok()
+            .map(AcknowledgementCommitment::into_vec),
+        Path::SeqRecvs(SeqRecvsPath(port_id, channel_id)) => ctx
+            .get_next_sequence_recv(&(port_id.clone(), channel_id.clone()))
+            .ok()
+            .map(sequence_bytes),
+        Path::Receipts(ReceiptsPath {
+            port_id,
+            channel_id,
+            sequence,
+        }) => ctx
+            .get_packet_receipt(&(port_id.clone(), channel_id.clone(), *sequence))
+            .ok()
+            .map(|_| Vec::new()),
+        path => return Err(unsupported_path(path)),
+    };
+
+    Ok(value)
+}
+
+/// The error for paths which cannot be read from the host store by the localhost client.
+fn unsupported_path(path: &Path) -> ClientError {
+    match path {
+        Path::ClientState(_) | Path::ClientConsensusState(_) | Path::Connections(_) => {
+            Error::ConnectionHandshakeUnsupported.into()
+        }
+        path => Error::UnsupportedPath {
+            path: path.to_string(),
+        }
+        .into(),
+    }
+}
+
+/// Encodes a next sequence to receive, as the handlers encode it.
+fn sequence_bytes(sequence: Sequence) -> Vec<u8> {
+    let mut seq_bytes = Vec::new();
+    u64::from(sequence)
+        .encode(&mut seq_bytes)
+        .expect("buffer size too small");
+    seq_bytes
+}
+
+/// Checks that the value read from the host store at `path` is the `expected` one.
+fn verify_value(path: &Path, stored: Option<Vec<u8>>, expected: &[u8]) -> Result<(), ClientError> {
+    match stored {
+        Some(stored) if stored == expected => Ok(()),
+        Some(_) => Err(Error::MismatchedValue {
+            path: path.to_string(),
+        }
+        .into()),
+        None => Err(Error::MissingValue {
+            path: path.to_string(),
+        }
+        .into()),
+    }
+}
+
+/// Checks that no value could be read from the host store at `path`.
+fn verify_absence(path: &Path, stored: Option<Vec<u8>>) -> Result<(), ClientError> {
+    match stored {
+        Some(_) => Err(Error::UnexpectedValue {
+            path: path.to_string(),
+        }
+        .into()),
+        None => Ok(()),
+    }
+}
+
+impl Ics2ClientState for ClientState {
+    fn chain_id(&self) -> ChainId {
+        self.chain_id.clone()
+    }
+
+    fn client_type(&self) -> ClientType {
+        localhost_client_type()
+    }
+
+    fn latest_height(&self) -> Height {
+        self.latest_height
+    }
+
+    /// The localhost client cannot misbehave, and is thus never frozen.
+    fn frozen_height(&self) -> Option<Height> {
+        None
+    }
+
+    /// The localhost client cannot be upgraded, so this is a no-op.
+    fn upgrade(
+        &mut self,
+        _upgrade_height: Height,
+        _upgrade_options: &dyn UpgradeOptions,
+        _chain_id: ChainId,
+    ) {
+    }
+
+    fn expired(&self, _elapsed: Duration) -> bool {
+        false
+    }
+
+    fn initialise(&self, consensus_state: Any) -> Result<Box<dyn ConsensusState>, ClientError> {
+        LocalhostConsensusState::try_from(consensus_state).map(LocalhostConsensusState::into_box)
+    }
+
+    /// The header is ignored, and the client is updated to the current height of the host.
+    fn check_header_and_update_state(
+        &self,
+        ctx: &dyn ClientReader,
+        _client_id: ClientId,
+        _header: Any,
+    ) -> Result<UpdatedState, ClientError> {
+        Ok(self.update(ctx.host_height()?, ctx.host_timestamp()?))
+    }
+
+    #[cfg(feature = "val_exec_ctx")]
+    fn new_check_header_and_update_state(
+        &self,
+        ctx: &dyn ValidationContext,
+        _client_id: ClientId,
+        _header: Any,
+    ) -> Result<UpdatedState, ClientError> {
+        let host_height = ctx.host_height().map_err(|e| ClientError::Other {
+            description: e.to_string(),
+        })?;
+        let host_timestamp = ctx.host_timestamp().map_err(|e| ClientError::Other {
+            description: e.to_string(),
+        })?;
+
+        Ok(self.update(host_height, host_timestamp))
+    }
+
+    fn check_misbehaviour_and_update_state(
+        &self,
+        _ctx: &dyn ClientReader,
+        _client_id: ClientId,
+        _misbehaviour: Any,
+    ) -> Result<Box<dyn Ics2ClientState>, ClientError> {
+        Err(Error::MisbehaviourUnsupported.into())
+    }
+
+    #[cfg(feature = "val_exec_ctx")]
+    fn new_check_misbehaviour_and_update_state(
+        &self,
+        _ctx: &dyn ValidationContext,
+        _client_id: ClientId,
+        _misbehaviour: Any,
+    ) -> Result<Box<dyn Ics2ClientState>, ContextError> {
+        Err(ClientError::from(Error::MisbehaviourUnsupported).into())
+    }
+
+    fn verify_upgrade_and_update_state(
+        &self,
+        _upgraded_client_state: Any,
+        _upgraded_consensus_state: Any,
+        _proof_upgrade_client: MerkleProof,
+        _proof_upgrade_consensus_state: MerkleProof,
+        _root: &CommitmentRoot,
+    ) -> Result<UpdatedState, ClientError> {
+        Err(Error::CannotUpgrade.into())
+    }
+
+    fn check_substitute_and_update_state(
+        &self,
+        _substitute_client_state: &dyn Ics2ClientState,
+    ) -> Result<Box<dyn Ics2ClientState>, ClientError> {
+        Err(Error::SubstituteNotAllowed.into())
+    }
+
+    /// Values are read from the current state of the host store, whatever the proof height.
+    fn validate_proof_height(&self, _proof_height: Height) -> Result<(), ClientError> {
+        Ok(())
+    }
+
+    fn verify_membership(
+        &mut self,
+        ctx: &dyn ChannelReader,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        path: Path,
+        value: Vec<u8>,
+    ) -> Result<(), ClientError> {
+        verify_value(&path, read_value(ctx, &path)?, &value)
+    }
+
+    fn verify_non_membership(
+        &mut self,
+        ctx: &dyn ChannelReader,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        path: Path,
+    ) -> Result<(), ClientError> {
+        verify_absence(&path, read_value(ctx, &path)?)
+    }
+
+    #[cfg(feature = "val_exec_ctx")]
+    fn new_verify_membership(
+        &mut self,
+        ctx: &dyn ValidationContext,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        path: Path,
+        value: Vec<u8>,
+    ) -> Result<(), ClientError> {
+        verify_value(&path, new_read_value(ctx, &path)?, &value)
+    }
+
+    #[cfg(feature = "val_exec_ctx")]
+    fn new_verify_non_membership(
+        &mut self,
+        ctx: &dyn ValidationContext,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        path: Path,
+    ) -> Result<(), ClientError> {
+        verify_absence(&path, new_read_value(ctx, &path)?)
+    }
+}
+
+impl Protobuf<RawLocalhostClientState> for ClientState {}
+
+impl TryFrom<RawLocalhostClientState> for ClientState {
+    type Error = Error;
+
+    fn try_from(raw: RawLocalhostClientState) -> Result<Self, Self::Error> {
+        let latest_height = raw
+            .height
+            .ok_or_else(|| Error::InvalidRawClientState {
+                reason: "missing height".into(),
+            })?
+            .try_into()
+            .map_err(|_| Error::InvalidRawClientState {
+                reason: "height cannot be zero".into(),
+            })?;
+
+        Ok(Self::new(
+            ChainId::from_string(&raw.chain_id),
+            latest_height,
+        ))
+    }
+}
+
+impl From<ClientState> for RawLocalhostClientState {
+    fn from(value: ClientState) -> Self {
+        RawLocalhostClientState {
+            chain_id: value.chain_id.to_string(),
+            height: Some(value.latest_height.into()),
+        }
+    }
+}
+
+impl Protobuf<Any> for ClientState {}
+
+impl TryFrom<Any> for ClientState {
+    type Error = ClientError;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        use bytes::Buf;
+        use core::ops::Deref;
+
+        fn decode_client_state<B: Buf>(buf: B) -> Result<ClientState, Error> {
+            RawLocalhostClientState::decode(buf)
+                .map_err(Error::Decode)?
+                .try_into()
+        }
+
+        match raw.type_url.as_str() {
+            LOCALHOST_CLIENT_STATE_TYPE_URL => {
+                decode_client_state(raw.value.deref()).map_err(Into::into)
+            }
+            _ => Err(ClientError::UnknownClientStateType {
+                client_state_type: raw.type_url,
+            }),
+        }
+    }
+}
+
+impl From<ClientState> for Any {
+    fn from(client_state: ClientState) -> Self {
+        Any {
+            type_url: LOCALHOST_CLIENT_STATE_TYPE_URL.to_string(),
+            value: Protobuf::<RawLocalhostClientState>::encode_vec(&client_state)
+                .expect("encoding to `Any` from `LocalhostClientState`"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    use crate::clients::ics09_localhost::{
+        client_id, connection_end, connection_id, sentinel_proof,
+    };
+    use crate::core::ics02_client::msgs::create_client::MsgCreateClient;
+    use crate::core::ics02_client::msgs::update_client::MsgUpdateClient;
+    use crate::core::ics02_client::msgs::ClientMsg;
+    use crate::core::ics03_connection::context::ConnectionReader;
+    use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+    use crate::core::ics04_channel::Version;
+    use crate::core::ics24_host::identifier::{ChannelId, PortId};
+    use crate::core::ics24_host::path::ConnectionsPath;
+    use crate::core::ics26_routing::msgs::MsgEnvelope;
+    use crate::mock::context::MockContext;
+    use crate::test_utils::get_dummy_account_id;
+
+    fn create_localhost_client(ctx: &mut MockContext) -> Result<(), String> {
+        let client_state =
+            ClientState::new(ChainId::default(), ClientReader::host_height(ctx).unwrap());
+        let consensus_state =
+            LocalhostConsensusState::new(ClientReader::host_timestamp(ctx).unwrap());
+        let msg = MsgCreateClient::new(
+            client_state.into(),
+            consensus_state.into(),
+            get_dummy_account_id(),
+        );
+
+        ctx.deliver(MsgEnvelope::Client(ClientMsg::CreateClient(msg)))
+            .map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn localhost_client_creation() {
+        let mut ctx = MockContext::default();
+        create_localhost_client(&mut ctx).unwrap();
+
+        let client_state = ClientReader::client_state(&ctx, &client_id()).unwrap();
+        assert_eq!(client_state.client_type(), localhost_client_type());
+
+        // The sentinel connection is open, without any connection handshake
+        let connection = ConnectionReader::connection_end(&ctx, &connection_id()).unwrap();
+        assert_eq!(
+            connection,
+            connection_end(ConnectionReader::commitment_prefix(&ctx))
+        );
+
+        // There can only be one localhost client
+        assert!(create_localhost_client(&mut ctx).is_err());
+    }
+
+    #[test]
+    fn localhost_client_update() {
+        let mut ctx = MockContext::default();
+        create_localhost_client(&mut ctx).unwrap();
+
+        let host_height = ClientReader::host_height(&ctx).unwrap();
+        let msg = MsgUpdateClient {
+            client_id: client_id(),
+            header: Any::default(),
+            signer: get_dummy_account_id(),
+        };
+        ctx.deliver(MsgEnvelope::Client(ClientMsg::UpdateClient(msg)))
+            .unwrap();
+
+        let client_state = ClientReader::client_state(&ctx, &client_id()).unwrap();
+        assert_eq!(client_state.latest_height(), host_height);
+        assert!(ClientReader::consensus_state(&ctx, &client_id(), &host_height).is_ok());
+    }
+
+    #[test]
+    fn localhost_client_verify_from_host_store() {
+        let port_id = PortId::transfer();
+        let channel_id = ChannelId::new(0);
+        let channel_end = ChannelEnd::new(
+            State::Init,
+            Order::Unordered,
+            Counterparty::new(PortId::transfer(), None),
+            vec![connection_id()],
+            Version::empty(),
+        );
+        let sequence = Sequence::from(1);
+        let commitment = PacketCommitment::from(vec![1, 2, 3]);
+
+        let ctx = MockContext::default()
+            .with_channel(port_id.clone(), channel_id.clone(), channel_end.clone())
+            .with_packet_commitment(
+                port_id.clone(),
+                channel_id.clone(),
+                sequence,
+                commitment.clone(),
+            );
+        let mut client_state =
+            ClientState::new(ChainId::default(), ClientReader::host_height(&ctx).unwrap());
+        let prefix = ConnectionReader::commitment_prefix(&ctx);
+        let proof = sentinel_proof();
+        let root = CommitmentRoot::from_bytes(&[]);
+
+        let channel_path = Path::from(ChannelEndsPath(port_id.clone(), channel_id.clone()));
+        client_state
+            .verify_membership(
+                &ctx,
+                &prefix,
+                &proof,
+                &root,
+                channel_path.clone(),
+                channel_end.encode_vec().unwrap(),
+            )
+            .unwrap();
+
+        let mut open_channel_end = channel_end;
+        open_channel_end.set_state(State::Open);
+        assert!(client_state
+            .verify_membership(
+                &ctx,
+                &prefix,
+                &proof,
+                &root,
+                channel_path,
+                open_channel_end.encode_vec().unwrap(),
+            )
+            .is_err());
+
+        let commitment_path = Path::from(CommitmentsPath {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            sequence,
+        });
+        client_state
+            .verify_membership(
+                &ctx,
+                &prefix,
+                &proof,
+                &root,
+                commitment_path.clone(),
+                commitment.into_vec(),
+            )
+            .unwrap();
+
+        assert!(client_state
+            .verify_membership(&ctx, &prefix, &proof, &root, commitment_path, vec![4, 5, 6],)
+            .is_err());
+
+        let receipt_path = Path::from(ReceiptsPath {
+            port_id,
+            channel_id,
+            sequence,
+        });
+        client_state
+            .verify_non_membership(&ctx, &prefix, &proof, &root, receipt_path)
+            .unwrap();
+
+        // The localhost client does not take part in connection handshakes
+        let connection_path = Path::from(ConnectionsPath(connection_id()));
+        assert!(client_state
+            .verify_membership(
+                &ctx,
+                &prefix,
+                &proof,
+                &root,
+                connection_path,
+                connection_end(prefix.clone()).encode_vec().unwrap(),
+            )
+            .is_err());
+    }
+}
@@ -0,0 +1,107 @@
+use crate::prelude::*;
+
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::protobuf::Protobuf;
+use prost::Message;
+
+use crate::clients::ics09_localhost::error::Error;
+use crate::core::ics02_client::error::ClientError;
+use crate::core::ics23_commitment::commitment::CommitmentRoot;
+use crate::timestamp::Timestamp;
+
+pub const LOCALHOST_CONSENSUS_STATE_TYPE_URL: &str =
+    "/ibc.lightclients.localhost.v1.ConsensusState";
+
+/// Raw localhost consensus state. ibc-go stores no consensus state for the localhost client, so
+/// there is no such message in `ibc-proto`.
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct RawConsensusState {
+    #[prost(uint64, tag = "1")]
+    pub timestamp: u64,
+}
+
+/// The consensus state of the localhost client, i.e. the host timestamp at the height the client
+/// was updated to. It is only stored so that the core handlers can look the client up at a given
+/// proof height, and its root is always empty.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConsensusState {
+    pub timestamp: Timestamp,
+    root: CommitmentRoot,
+}
+
+impl ConsensusState {
+    pub fn new(timestamp: Timestamp) -> Self {
+        Self {
+            timestamp,
+            root: CommitmentRoot::from_bytes(&[]),
+        }
+    }
+}
+
+impl crate::core::ics02_client::consensus_state::ConsensusState for ConsensusState {
+    fn root(&self) -> &CommitmentRoot {
+        &self.root
+    }
+
+    fn timestamp(&self) -> Timestamp {
+        self.timestamp
+    }
+}
+
+impl Protobuf<RawConsensusState> for ConsensusState {}
+
+impl TryFrom<RawConsensusState> for ConsensusState {
+    type Error = Error;
+
+    fn try_from(raw: RawConsensusState) -> Result<Self, Self::Error> {
+        let timestamp =
+            Timestamp::from_nanoseconds(raw.timestamp).map_err(Error::InvalidTimestamp)?;
+
+        Ok(Self::new(timestamp))
+    }
+}
+
+impl From<ConsensusState> for RawConsensusState {
+    fn from(value: ConsensusState) -> Self {
+        RawConsensusState {
+            timestamp: value.timestamp.nanoseconds(),
+        }
+    }
+}
+
+impl Protobuf<Any> for ConsensusState {}
+
+impl TryFrom<Any> for ConsensusState {
+    type Error = ClientError;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        use bytes::Buf;
+        use core::ops::Deref;
+
+        fn decode_consensus_state<B: Buf>(buf: B) -> Result<ConsensusState, Error> {
+            RawConsensusState::decode(buf)
+                .map_err(Error::Decode)?
+                .try_into()
+        }
+
+        match raw.type_url.as_str() {
+            LOCALHOST_CONSENSUS_STATE_TYPE_URL => {
+                decode_consensus_state(raw.value.deref()).map_err(Into::into)
+            }
+            _ => Err(ClientError::UnknownConsensusStateType {
+                consensus_state_type: raw.type_url,
+            }),
+        }
+    }
+}
+
+impl From<ConsensusState> for Any {
+    fn from(consensus_state: ConsensusState) -> Self {
+        Any {
+            type_url: LOCALHOST_CONSENSUS_STATE_TYPE_URL.to_string(),
+            value: Protobuf::<RawConsensusState>::encode_vec(&consensus_state)
+                .expect("encoding to `Any` from `LocalhostConsensusState`"),
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use crate::prelude::*;
+
+use displaydoc::Display;
+
+use crate::core::ics02_client::error::ClientError;
+use crate::timestamp::ParseTimestampError;
+
+#[derive(Debug, Display)]
+pub enum Error {
+    /// invalid raw client state: `{reason}`
+    InvalidRawClientState { reason: String },
+    /// invalid timestamp error: `{0}`
+    InvalidTimestamp(ParseTimestampError),
+    /// decode error: `{0}`
+    Decode(prost::DecodeError),
+    /// the localhost client does not take part in connection handshakes, its sentinel connection is used instead
+    ConnectionHandshakeUnsupported,
+    /// no value stored at `{path}` in the host store
+    MissingValue { path: String },
+    /// value stored at `{path}` does not match the expected value
+    MismatchedValue { path: String },
+    /// value found at `{path}`, where none was expected
+    UnexpectedValue { path: String },
+    /// the localhost client cannot verify values stored at `{path}`
+    UnsupportedPath { path: String },
+    /// the localhost client cannot misbehave
+    MisbehaviourUnsupported,
+    /// the localhost client cannot be upgraded
+    CannotUpgrade,
+    /// the localhost client cannot be recovered, as it never becomes inactive
+    SubstituteNotAllowed,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self {
+            Self::InvalidTimestamp(e) => Some(e),
+            Self::Decode(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<Error> for ClientError {
+    fn from(e: Error) -> Self {
+        Self::ClientSpecific {
+            description: e.to_string(),
+        }
+    }
+}
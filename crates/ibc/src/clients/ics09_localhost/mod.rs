@@ -0,0 +1,62 @@
+//! ICS 09: Localhost Client implements a loopback client, through which IBC applications on the
+//! same chain can talk to one another. Instead of verifying proofs, the client reads the values
+//! to verify directly from the host's own store.
+//!
+//! The localhost client always has the sentinel identifier [`LOCALHOST_CLIENT_ID`], and comes with
+//! the open sentinel connection [`LOCALHOST_CONNECTION_ID`], so that no connection handshake is
+//! needed before opening channels over it. The client is brought up to the current height of
+//! the host by any `MsgUpdateClient`, whose header is ignored.
+
+use alloc::string::ToString;
+use core::str::FromStr;
+
+use crate::core::ics02_client::client_type::ClientType;
+use crate::core::ics03_connection::connection::{ConnectionEnd, Counterparty, State};
+use crate::core::ics03_connection::version::get_compatible_versions;
+use crate::core::ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes};
+use crate::core::ics24_host::identifier::{ClientId, ConnectionId};
+use crate::timestamp::ZERO_DURATION;
+
+pub mod client_state;
+pub mod consensus_state;
+pub mod error;
+
+pub(crate) const LOCALHOST_CLIENT_TYPE: &str = "09-localhost";
+
+/// The identifier of the (unique) localhost client.
+pub const LOCALHOST_CLIENT_ID: &str = "09-localhost";
+
+/// The identifier of the sentinel connection of the localhost client.
+pub const LOCALHOST_CONNECTION_ID: &str = "connection-localhost";
+
+/// The proof to submit along with messages relayed over the localhost client. It is never
+/// verified, but messages cannot hold empty proofs.
+pub const SENTINEL_PROOF: &[u8] = &[0x01];
+
+pub fn client_type() -> ClientType {
+    ClientType::new(LOCALHOST_CLIENT_TYPE.to_string())
+}
+
+pub fn client_id() -> ClientId {
+    ClientId::from_str(LOCALHOST_CLIENT_ID).expect("localhost client identifier is valid")
+}
+
+pub fn connection_id() -> ConnectionId {
+    ConnectionId::from_str(LOCALHOST_CONNECTION_ID)
+        .expect("localhost connection identifier is valid")
+}
+
+/// The sentinel connection of the localhost client, which is open and its own counterparty.
+pub fn connection_end(prefix: CommitmentPrefix) -> ConnectionEnd {
+    ConnectionEnd::new(
+        State::Open,
+        client_id(),
+        Counterparty::new(client_id(), Some(connection_id()), prefix),
+        get_compatible_versions(),
+        ZERO_DURATION,
+    )
+}
+
+pub fn sentinel_proof() -> CommitmentProofBytes {
+    CommitmentProofBytes::try_from(SENTINEL_PROOF.to_vec()).expect("sentinel proof is not empty")
+}
@@ -1,3 +1,6 @@
 //! Implementations of client verification algorithms for specific types of chains.
 
+pub mod ics06_solomachine;
 pub mod ics07_tendermint;
+pub mod ics08_wasm;
+pub mod ics09_localhost;
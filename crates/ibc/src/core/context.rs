@@ -69,32 +69,64 @@ mod val_exec_ctx {
     use crate::core::ics02_client::client_state::ClientState;
     use crate::core::ics02_client::client_type::ClientType;
     use crate::core::ics02_client::consensus_state::ConsensusState;
+    use crate::core::ics02_client::params::ClientParams;
+    use crate::core::ics02_client::registry::ClientTypeRegistry;
     use crate::core::ics03_connection::connection::ConnectionEnd;
     use crate::core::ics03_connection::error::ConnectionError;
+    use crate::core::ics03_connection::params::ConnectionParams;
     use crate::core::ics03_connection::version::{
         get_compatible_versions, pick_version, Version as ConnectionVersion,
     };
-    use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, State};
+    use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
     use crate::core::ics04_channel::commitment::{AcknowledgementCommitment, PacketCommitment};
     use crate::core::ics04_channel::context::calculate_block_delay;
-    use crate::core::ics04_channel::events::OpenTry;
-    use crate::core::ics04_channel::handler::chan_open_try;
-    use crate::core::ics04_channel::msgs::acknowledgement::Acknowledgement;
+    use crate::core::ics04_channel::events::{
+        AcknowledgePacket, ChannelClosed, CloseConfirm, CloseInit, OpenAck, OpenConfirm, OpenInit,
+        OpenTry, ReceivePacket, TimeoutPacket, UpgradeAck, UpgradeCancel, UpgradeConfirm,
+        UpgradeInit, UpgradeOpen, UpgradeTimeout as UpgradeTimeoutEvent, UpgradeTry,
+        WriteAcknowledgement,
+    };
+    use crate::core::ics04_channel::handler::{
+        acknowledgement, chan_close_confirm, chan_close_init, chan_open_ack, chan_open_confirm,
+        chan_open_init, chan_open_try, chan_upgrade_ack, chan_upgrade_cancel, chan_upgrade_confirm,
+        chan_upgrade_init, chan_upgrade_open, chan_upgrade_timeout, chan_upgrade_try, recv_packet,
+        timeout, timeout_on_close, ModuleExtras,
+    };
+    use crate::core::ics04_channel::msgs::acknowledgement::{Acknowledgement, MsgAcknowledgement};
+    use crate::core::ics04_channel::msgs::chan_close_confirm::MsgChannelCloseConfirm;
+    use crate::core::ics04_channel::msgs::chan_close_init::MsgChannelCloseInit;
+    use crate::core::ics04_channel::msgs::chan_open_ack::MsgChannelOpenAck;
+    use crate::core::ics04_channel::msgs::chan_open_confirm::MsgChannelOpenConfirm;
+    use crate::core::ics04_channel::msgs::chan_open_init::MsgChannelOpenInit;
     use crate::core::ics04_channel::msgs::chan_open_try::MsgChannelOpenTry;
-    use crate::core::ics04_channel::msgs::ChannelMsg;
-    use crate::core::ics04_channel::packet::{Receipt, Sequence};
+    use crate::core::ics04_channel::msgs::chan_upgrade_ack::MsgChannelUpgradeAck;
+    use crate::core::ics04_channel::msgs::chan_upgrade_cancel::MsgChannelUpgradeCancel;
+    use crate::core::ics04_channel::msgs::chan_upgrade_confirm::MsgChannelUpgradeConfirm;
+    use crate::core::ics04_channel::msgs::chan_upgrade_init::MsgChannelUpgradeInit;
+    use crate::core::ics04_channel::msgs::chan_upgrade_open::MsgChannelUpgradeOpen;
+    use crate::core::ics04_channel::msgs::chan_upgrade_timeout::MsgChannelUpgradeTimeout;
+    use crate::core::ics04_channel::msgs::chan_upgrade_try::MsgChannelUpgradeTry;
+    use crate::core::ics04_channel::msgs::recv_packet::MsgRecvPacket;
+    use crate::core::ics04_channel::msgs::timeout::MsgTimeout;
+    use crate::core::ics04_channel::msgs::timeout_on_close::MsgTimeoutOnClose;
+    use crate::core::ics04_channel::msgs::{ChannelMsg, PacketMsg};
+    use crate::core::ics04_channel::packet::{Packet, Receipt, Sequence};
     use crate::core::ics04_channel::timeout::TimeoutHeight;
+    use crate::core::ics04_channel::upgrade::{
+        ErrorReceipt, Upgrade, UpgradeFields, UpgradeTimeout, DEFAULT_UPGRADE_TIMEOUT,
+    };
     use crate::core::ics05_port::error::PortError::UnknownPort;
     use crate::core::ics23_commitment::commitment::CommitmentPrefix;
     use crate::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
     use crate::core::ics24_host::path::{
-        ClientConnectionsPath, ClientConsensusStatePath, ClientStatePath, ClientTypePath,
-        CommitmentsPath, ConnectionsPath, ReceiptsPath,
+        ChannelUpgradeErrorPath, ChannelUpgradePath, ClientConnectionsPath,
+        ClientConsensusStatePath, ClientStatePath, ClientTypePath, ClientUpdateHeightPath,
+        ClientUpdateTimePath, CommitmentsPath, ConnectionsPath, ReceiptsPath,
     };
     use crate::core::ics26_routing::context::{Module, ModuleId};
     use crate::core::{
         ics02_client::{
-            handler::{create_client, misbehaviour, update_client, upgrade_client},
+            handler::{create_client, misbehaviour, recover_client, update_client, upgrade_client},
             msgs::ClientMsg,
         },
         ics03_connection::{
@@ -105,6 +137,7 @@ mod val_exec_ctx {
         ics26_routing::{error::RouterError, msgs::MsgEnvelope},
     };
     use crate::events::IbcEvent;
+    use crate::signer::Signer;
     use crate::timestamp::Timestamp;
     use crate::Height;
 
@@ -131,6 +164,28 @@ mod val_exec_ctx {
                 ChannelMsg::OpenConfirm(msg) => &msg.port_id_on_b,
                 ChannelMsg::CloseInit(msg) => &msg.port_id_on_a,
                 ChannelMsg::CloseConfirm(msg) => &msg.port_id_on_b,
+                ChannelMsg::UpgradeInit(msg) => &msg.port_id_on_a,
+                ChannelMsg::UpgradeTry(msg) => &msg.port_id_on_b,
+                ChannelMsg::UpgradeAck(msg) => &msg.port_id_on_a,
+                ChannelMsg::UpgradeConfirm(msg) => &msg.port_id_on_b,
+                ChannelMsg::UpgradeOpen(msg) => &msg.port_id,
+                ChannelMsg::UpgradeTimeout(msg) => &msg.port_id,
+                ChannelMsg::UpgradeCancel(msg) => &msg.port_id,
+            };
+            let module_id = self
+                .lookup_module_by_port(port_id)
+                .ok_or(ChannelError::Port(UnknownPort {
+                    port_id: port_id.clone(),
+                }))?;
+            Ok(module_id)
+        }
+
+        fn lookup_module_packet(&self, msg: &PacketMsg) -> Result<ModuleId, ChannelError> {
+            let port_id = match msg {
+                PacketMsg::Recv(msg) => &msg.packet.port_on_b,
+                PacketMsg::Ack(msg) => &msg.packet.port_on_a,
+                PacketMsg::Timeout(msg) => &msg.packet.port_on_a,
+                PacketMsg::TimeoutOnClose(msg) => &msg.packet.port_on_a,
             };
             let module_id = self
                 .lookup_module_by_port(port_id)
@@ -153,6 +208,7 @@ mod val_exec_ctx {
                     ClientMsg::UpdateClient(message) => update_client::validate(self, message),
                     ClientMsg::Misbehaviour(message) => misbehaviour::validate(self, message),
                     ClientMsg::UpgradeClient(message) => upgrade_client::validate(self, message),
+                    ClientMsg::RecoverClient(message) => recover_client::validate(self, message),
                 }
                 .map_err(RouterError::ContextError),
                 MsgEnvelope::Connection(message) => match message {
@@ -173,29 +229,93 @@ mod val_exec_ctx {
                     }
 
                     match message {
-                        ChannelMsg::OpenInit(_) => todo!(),
+                        ChannelMsg::OpenInit(message) => {
+                            chan_open_init_validate(self, module_id, message)
+                        }
                         ChannelMsg::OpenTry(message) => {
                             chan_open_try_validate(self, module_id, message)
                         }
-                        ChannelMsg::OpenAck(_) => todo!(),
-                        ChannelMsg::OpenConfirm(_) => todo!(),
-                        ChannelMsg::CloseInit(_) => todo!(),
-                        ChannelMsg::CloseConfirm(_) => todo!(),
+                        ChannelMsg::OpenAck(message) => {
+                            chan_open_ack_validate(self, module_id, message)
+                        }
+                        ChannelMsg::OpenConfirm(message) => {
+                            chan_open_confirm_validate(self, module_id, message)
+                        }
+                        ChannelMsg::CloseInit(message) => {
+                            chan_close_init_validate(self, module_id, message)
+                        }
+                        ChannelMsg::CloseConfirm(message) => {
+                            chan_close_confirm_validate(self, module_id, message)
+                        }
+                        ChannelMsg::UpgradeInit(message) => {
+                            chan_upgrade_init_validate(self, module_id, message)
+                        }
+                        ChannelMsg::UpgradeTry(message) => {
+                            chan_upgrade_try_validate(self, module_id, message)
+                        }
+                        ChannelMsg::UpgradeAck(message) => {
+                            chan_upgrade_ack_validate(self, module_id, message)
+                        }
+                        ChannelMsg::UpgradeConfirm(message) => {
+                            chan_upgrade_confirm_validate(self, module_id, message)
+                        }
+                        ChannelMsg::UpgradeOpen(message) => {
+                            chan_upgrade_open_validate(self, module_id, message)
+                        }
+                        ChannelMsg::UpgradeTimeout(message) => {
+                            chan_upgrade_timeout_validate(self, module_id, message)
+                        }
+                        ChannelMsg::UpgradeCancel(message) => {
+                            chan_upgrade_cancel_validate(self, module_id, message)
+                        }
+                    }
+                    .map_err(RouterError::ContextError)
+                }
+                MsgEnvelope::Packet(message) => {
+                    let module_id = self
+                        .lookup_module_packet(&message)
+                        .map_err(ContextError::from)?;
+                    if !self.has_route(&module_id) {
+                        return Err(RouterError::ContextError(PacketError::RouteNotFound.into()));
+                    }
+
+                    match message {
+                        PacketMsg::Recv(message) => recv_packet_validate(self, module_id, message),
+                        PacketMsg::Ack(message) => {
+                            acknowledgement_packet_validate(self, module_id, message)
+                        }
+                        PacketMsg::Timeout(message) => timeout_packet_validate(
+                            self,
+                            module_id,
+                            TimeoutMsgType::Timeout(message),
+                        ),
+                        PacketMsg::TimeoutOnClose(message) => timeout_packet_validate(
+                            self,
+                            module_id,
+                            TimeoutMsgType::TimeoutOnClose(message),
+                        ),
                     }
                     .map_err(RouterError::ContextError)
                 }
-                MsgEnvelope::Packet(_message) => todo!(),
             }
         }
 
         /// Returns the ClientState for the given identifier `client_id`.
         fn client_state(&self, client_id: &ClientId) -> Result<Box<dyn ClientState>, ContextError>;
 
-        /// Tries to decode the given `client_state` into a concrete light client state.
+        /// Returns the registry of the client types supported by the host.
+        fn client_type_registry(&self) -> &ClientTypeRegistry;
+
+        /// Tries to decode the given `client_state` into a concrete light client state, through
+        /// the client type registry of the host.
         fn decode_client_state(
             &self,
             client_state: Any,
-        ) -> Result<Box<dyn ClientState>, ContextError>;
+        ) -> Result<Box<dyn ClientState>, ContextError> {
+            self.client_type_registry()
+                .decode_client_state(client_state)
+                .map_err(ContextError::ClientError)
+        }
 
         /// Retrieve the consensus state for the given client ID at the specified
         /// height.
@@ -221,6 +341,13 @@ mod val_exec_ctx {
             height: &Height,
         ) -> Result<Option<Box<dyn ConsensusState>>, ContextError>;
 
+        /// Returns the heights of all the consensus states stored for the client, in ascending
+        /// order.
+        fn consensus_state_heights(
+            &self,
+            client_id: &ClientId,
+        ) -> Result<Vec<Height>, ContextError>;
+
         /// Returns the current height of the local chain.
         fn host_height(&self) -> Result<Height, ContextError>;
 
@@ -245,6 +372,19 @@ mod val_exec_ctx {
         /// The value of this counter should increase only via method `ClientKeeper::increase_client_counter`.
         fn client_counter(&self) -> Result<u64, ContextError>;
 
+        /// Returns the parameters of the client submodule.
+        fn client_params(&self) -> Result<ClientParams, ContextError>;
+
+        /// Validates that `signer` is authorized to recover a frozen or expired client by
+        /// substituting it with a healthy one, e.g. that it is the address of the governance
+        /// module. Client recovery is disallowed by default.
+        fn validate_client_recovery_authority(&self, signer: &Signer) -> Result<(), ContextError> {
+            Err(ClientError::UnauthorizedClientRecovery {
+                signer: signer.clone(),
+            }
+            .into())
+        }
+
         /// Returns the ConnectionEnd for the given identifier `conn_id`.
         fn connection_end(&self, conn_id: &ConnectionId) -> Result<ConnectionEnd, ContextError>;
 
@@ -260,6 +400,9 @@ mod val_exec_ctx {
         /// Returns a counter on how many connections have been created thus far.
         fn connection_counter(&self) -> Result<u64, ContextError>;
 
+        /// Returns the parameters of the connection submodule.
+        fn connection_params(&self) -> Result<ConnectionParams, ContextError>;
+
         /// Function required by ICS 03. Returns the list of all possible versions that the connection
         /// handshake protocol supports.
         fn get_compatible_versions(&self) -> Vec<ConnectionVersion> {
@@ -369,13 +512,41 @@ mod val_exec_ctx {
         /// `ChannelKeeper::increase_channel_counter`.
         fn channel_counter(&self) -> Result<u64, ContextError>;
 
-        /// Returns the maximum expected time per block
-        fn max_expected_time_per_block(&self) -> Duration;
-
         /// Calculates the block delay period using the connection's delay period and the maximum
-        /// expected time per block.
-        fn block_delay(&self, delay_period_time: &Duration) -> u64 {
-            calculate_block_delay(delay_period_time, &self.max_expected_time_per_block())
+        /// expected time per block of the connection parameters.
+        fn block_delay(&self, delay_period_time: &Duration) -> Result<u64, ContextError> {
+            Ok(calculate_block_delay(
+                delay_period_time,
+                &self.connection_params()?,
+            ))
+        }
+
+        /// Returns the upgrade in progress of the channel end with the given `port_id` and
+        /// `chan_id`.
+        fn channel_upgrade(
+            &self,
+            port_channel_id: &(PortId, ChannelId),
+        ) -> Result<Upgrade, ContextError>;
+
+        /// Returns the error receipt of the last aborted upgrade of the channel end with the
+        /// given `port_id` and `chan_id`.
+        fn channel_upgrade_error(
+            &self,
+            port_channel_id: &(PortId, ChannelId),
+        ) -> Result<ErrorReceipt, ContextError>;
+
+        /// Returns `true` if some packets sent on the channel end have neither been
+        /// acknowledged nor timed out yet, i.e. if some of its packet commitments are still
+        /// stored.
+        fn has_inflight_packets(
+            &self,
+            port_channel_id: &(PortId, ChannelId),
+        ) -> Result<bool, ContextError>;
+
+        /// Returns the period given to the counterparty chain to complete the upgrade of a
+        /// channel, past which the upgrade can be timed out.
+        fn channel_upgrade_timeout(&self) -> Duration {
+            DEFAULT_UPGRADE_TIMEOUT
         }
     }
 
@@ -391,6 +562,7 @@ mod val_exec_ctx {
                     ClientMsg::UpdateClient(message) => update_client::execute(self, message),
                     ClientMsg::Misbehaviour(message) => misbehaviour::execute(self, message),
                     ClientMsg::UpgradeClient(message) => upgrade_client::execute(self, message),
+                    ClientMsg::RecoverClient(message) => recover_client::execute(self, message),
                 }
                 .map_err(RouterError::ContextError),
                 MsgEnvelope::Connection(message) => match message {
@@ -411,18 +583,74 @@ mod val_exec_ctx {
                     }
 
                     match message {
-                        ChannelMsg::OpenInit(_) => todo!(),
+                        ChannelMsg::OpenInit(message) => {
+                            chan_open_init_execute(self, module_id, message)
+                        }
                         ChannelMsg::OpenTry(message) => {
                             chan_open_try_execute(self, module_id, message)
                         }
-                        ChannelMsg::OpenAck(_) => todo!(),
-                        ChannelMsg::OpenConfirm(_) => todo!(),
-                        ChannelMsg::CloseInit(_) => todo!(),
-                        ChannelMsg::CloseConfirm(_) => todo!(),
+                        ChannelMsg::OpenAck(message) => {
+                            chan_open_ack_execute(self, module_id, message)
+                        }
+                        ChannelMsg::OpenConfirm(message) => {
+                            chan_open_confirm_execute(self, module_id, message)
+                        }
+                        ChannelMsg::CloseInit(message) => {
+                            chan_close_init_execute(self, module_id, message)
+                        }
+                        ChannelMsg::CloseConfirm(message) => {
+                            chan_close_confirm_execute(self, module_id, message)
+                        }
+                        ChannelMsg::UpgradeInit(message) => {
+                            chan_upgrade_init_execute(self, module_id, message)
+                        }
+                        ChannelMsg::UpgradeTry(message) => {
+                            chan_upgrade_try_execute(self, module_id, message)
+                        }
+                        ChannelMsg::UpgradeAck(message) => {
+                            chan_upgrade_ack_execute(self, module_id, message)
+                        }
+                        ChannelMsg::UpgradeConfirm(message) => {
+                            chan_upgrade_confirm_execute(self, module_id, message)
+                        }
+                        ChannelMsg::UpgradeOpen(message) => {
+                            chan_upgrade_open_execute(self, module_id, message)
+                        }
+                        ChannelMsg::UpgradeTimeout(message) => {
+                            chan_upgrade_timeout_execute(self, module_id, message)
+                        }
+                        ChannelMsg::UpgradeCancel(message) => {
+                            chan_upgrade_cancel_execute(self, module_id, message)
+                        }
+                    }
+                    .map_err(RouterError::ContextError)
+                }
+                MsgEnvelope::Packet(message) => {
+                    let module_id = self
+                        .lookup_module_packet(&message)
+                        .map_err(ContextError::from)?;
+                    if !self.has_route(&module_id) {
+                        return Err(RouterError::ContextError(PacketError::RouteNotFound.into()));
+                    }
+
+                    match message {
+                        PacketMsg::Recv(message) => recv_packet_execute(self, module_id, message),
+                        PacketMsg::Ack(message) => {
+                            acknowledgement_packet_execute(self, module_id, message)
+                        }
+                        PacketMsg::Timeout(message) => timeout_packet_execute(
+                            self,
+                            module_id,
+                            TimeoutMsgType::Timeout(message),
+                        ),
+                        PacketMsg::TimeoutOnClose(message) => timeout_packet_execute(
+                            self,
+                            module_id,
+                            TimeoutMsgType::TimeoutOnClose(message),
+                        ),
                     }
                     .map_err(RouterError::ContextError)
                 }
-                MsgEnvelope::Packet(_message) => todo!(),
             }
         }
 
@@ -447,31 +675,57 @@ mod val_exec_ctx {
             consensus_state: Box<dyn ConsensusState>,
         ) -> Result<(), ContextError>;
 
+        /// Called upon successful client update, to prune the consensus states of the client which
+        /// have expired.
+        fn delete_consensus_state(
+            &mut self,
+            consensus_state_path: ClientConsensusStatePath,
+        ) -> Result<(), ContextError>;
+
         /// Called upon client creation.
         /// Increases the counter which keeps track of how many clients have been created.
         /// Should never fail.
         fn increase_client_counter(&mut self);
 
-        /// Called upon successful client update.
+        /// Stores the parameters of the client submodule, e.g. upon a governance proposal.
+        fn store_client_params(&mut self, params: ClientParams) -> Result<(), ContextError>;
+
+        /// Called upon successful client creation, update and upgrade.
         /// Implementations are expected to use this to record the specified time as the time at which
-        /// this update (or header) was processed.
+        /// the consensus state at the height of the path was processed, next to that consensus
+        /// state.
         fn store_update_time(
             &mut self,
-            client_id: ClientId,
-            height: Height,
+            client_update_time_path: ClientUpdateTimePath,
             timestamp: Timestamp,
         ) -> Result<(), ContextError>;
 
-        /// Called upon successful client update.
+        /// Called upon successful client creation, update and upgrade.
         /// Implementations are expected to use this to record the specified height as the height at
-        /// at which this update (or header) was processed.
+        /// which the consensus state at the height of the path was processed, next to that consensus
+        /// state.
         fn store_update_height(
             &mut self,
-            client_id: ClientId,
-            height: Height,
+            client_update_height_path: ClientUpdateHeightPath,
             host_height: Height,
         ) -> Result<(), ContextError>;
 
+        /// Called upon successful client update, along with `delete_consensus_state`.
+        /// Implementations are expected to delete the time recorded by `store_update_time` for
+        /// the pruned consensus state.
+        fn delete_update_time(
+            &mut self,
+            client_update_time_path: ClientUpdateTimePath,
+        ) -> Result<(), ContextError>;
+
+        /// Called upon successful client update, along with `delete_consensus_state`.
+        /// Implementations are expected to delete the height recorded by `store_update_height` for
+        /// the pruned consensus state.
+        fn delete_update_height(
+            &mut self,
+            client_update_height_path: ClientUpdateHeightPath,
+        ) -> Result<(), ContextError>;
+
         /// Stores the given connection_end at path
         fn store_connection(
             &mut self,
@@ -491,6 +745,10 @@ mod val_exec_ctx {
         /// Should never fail.
         fn increase_connection_counter(&mut self);
 
+        /// Stores the parameters of the connection submodule, e.g. upon a governance proposal.
+        fn store_connection_params(&mut self, params: ConnectionParams)
+            -> Result<(), ContextError>;
+
         fn store_packet_commitment(
             &mut self,
             commitments_path: CommitmentsPath,
@@ -522,6 +780,14 @@ mod val_exec_ctx {
             port_channel_id: (PortId, ChannelId),
         ) -> Result<(), ContextError>;
 
+        /// Removes a channel from those of a connection, once it was upgraded to another
+        /// connection.
+        fn delete_connection_channels(
+            &mut self,
+            conn_id: ConnectionId,
+            port_channel_id: (PortId, ChannelId),
+        ) -> Result<(), ContextError>;
+
         /// Stores the given channel_end at a path associated with the port_id and channel_id.
         fn store_channel(
             &mut self,
@@ -552,6 +818,27 @@ mod val_exec_ctx {
         /// Should never fail.
         fn increase_channel_counter(&mut self);
 
+        /// Stores the upgrade in progress of a channel end, upon upgrade init and try.
+        fn store_channel_upgrade(
+            &mut self,
+            channel_upgrade_path: ChannelUpgradePath,
+            upgrade: Upgrade,
+        ) -> Result<(), ContextError>;
+
+        /// Deletes the upgrade of a channel end, once it is complete or aborted.
+        fn delete_channel_upgrade(
+            &mut self,
+            channel_upgrade_path: ChannelUpgradePath,
+        ) -> Result<(), ContextError>;
+
+        /// Stores the error receipt of an aborted channel upgrade, which the counterparty chain
+        /// proves to cancel the upgrade on its side.
+        fn store_channel_upgrade_error(
+            &mut self,
+            channel_upgrade_error_path: ChannelUpgradeErrorPath,
+            error_receipt: ErrorReceipt,
+        ) -> Result<(), ContextError>;
+
         /// Ibc events
         fn emit_ibc_event(&mut self, event: IbcEvent);
 
@@ -559,6 +846,100 @@ mod val_exec_ctx {
         fn log_message(&mut self, message: String);
     }
 
+    fn chan_open_init_validate<ValCtx>(
+        ctx_a: &ValCtx,
+        module_id: ModuleId,
+        msg: MsgChannelOpenInit,
+    ) -> Result<(), ContextError>
+    where
+        ValCtx: ValidationContext,
+    {
+        chan_open_init::validate(ctx_a, &msg)?;
+        let chan_id_on_a = ChannelId::new(ctx_a.channel_counter()?);
+
+        let module = ctx_a
+            .get_route(&module_id)
+            .ok_or(ChannelError::RouteNotFound)?;
+        let _ = module.on_chan_open_init_validate(
+            msg.ordering,
+            &msg.connection_hops_on_a,
+            &msg.port_id_on_a,
+            &chan_id_on_a,
+            &Counterparty::new(msg.port_id_on_b.clone(), None),
+            &msg.version_proposal,
+        )?;
+
+        Ok(())
+    }
+
+    fn chan_open_init_execute<ExecCtx>(
+        ctx_a: &mut ExecCtx,
+        module_id: ModuleId,
+        msg: MsgChannelOpenInit,
+    ) -> Result<(), ContextError>
+    where
+        ExecCtx: ExecutionContext,
+    {
+        let chan_id_on_a = ChannelId::new(ctx_a.channel_counter()?);
+        ctx_a.log_message(format!(
+            "success: channel open init with channel identifier: {chan_id_on_a}"
+        ));
+
+        let module = ctx_a
+            .get_route_mut(&module_id)
+            .ok_or(ChannelError::RouteNotFound)?;
+
+        let (extras, version) = module.on_chan_open_init_execute(
+            msg.ordering,
+            &msg.connection_hops_on_a,
+            &msg.port_id_on_a,
+            &chan_id_on_a,
+            &Counterparty::new(msg.port_id_on_b.clone(), None),
+            &msg.version_proposal,
+        )?;
+
+        let conn_id_on_a = msg.connection_hops_on_a[0].clone();
+        let port_channel_id_on_a = (msg.port_id_on_a.clone(), chan_id_on_a.clone());
+
+        // emit events and logs
+        {
+            let core_event = IbcEvent::OpenInitChannel(OpenInit::new(
+                msg.port_id_on_a.clone(),
+                chan_id_on_a,
+                msg.port_id_on_b.clone(),
+                conn_id_on_a.clone(),
+                version.clone(),
+            ));
+            ctx_a.emit_ibc_event(core_event);
+
+            emit_module_extras(ctx_a, extras);
+        }
+
+        {
+            let channel_end = ChannelEnd::new(
+                State::Init,
+                msg.ordering,
+                Counterparty::new(msg.port_id_on_b, None),
+                msg.connection_hops_on_a,
+                version,
+            );
+
+            ctx_a.store_channel(port_channel_id_on_a.clone(), channel_end)?;
+
+            ctx_a.increase_channel_counter();
+
+            // Associate also the channel end to its connection.
+            ctx_a.store_connection_channels(conn_id_on_a, port_channel_id_on_a.clone())?;
+
+            // Initialize send, recv, and ack sequence numbers.
+            ctx_a.store_next_sequence_send(port_channel_id_on_a.clone(), 1.into())?;
+            ctx_a.store_next_sequence_recv(port_channel_id_on_a.clone(), 1.into())?;
+            ctx_a.store_next_sequence_ack(port_channel_id_on_a, 1.into())?;
+        }
+
+        Ok(())
+    }
+
     fn chan_open_try_validate<ValCtx>(
         ctx_b: &ValCtx,
         module_id: ModuleId,
@@ -594,6 +975,8 @@ mod val_exec_ctx {
         ExecCtx: ExecutionContext,
     {
         let chan_id_on_b = ChannelId::new(ctx_b.channel_counter()?);
+        let conn_end_on_b = ctx_b.connection_end(&msg.connection_hops_on_b[0])?;
+        let client_state_of_a_on_b = chan_open_try::verify_proofs(ctx_b, &msg, &conn_end_on_b)?;
         ctx_b.log_message(format!(
             "success: channel open try with channel identifier: {chan_id_on_b}"
         ));
@@ -626,13 +1009,7 @@ mod val_exec_ctx {
             ));
             ctx_b.emit_ibc_event(core_event);
 
-            for module_event in extras.events {
-                ctx_b.emit_ibc_event(IbcEvent::AppModule(module_event));
-            }
-
-            for log_message in extras.log {
-                ctx_b.log_message(log_message);
-            }
+            emit_module_extras(ctx_b, extras);
         }
 
         {
@@ -649,14 +1026,1374 @@ mod val_exec_ctx {
             ctx_b.increase_channel_counter();
 
             // Associate also the channel end to its connection.
-            ctx_b.store_connection_channels(conn_id_on_b, port_channel_id_on_b.clone())?;
+            ctx_b.store_connection_channels(conn_id_on_b.clone(), port_channel_id_on_b.clone())?;
 
             // Initialize send, recv, and ack sequence numbers.
             ctx_b.store_next_sequence_send(port_channel_id_on_b.clone(), 1.into())?;
             ctx_b.store_next_sequence_recv(port_channel_id_on_b.clone(), 1.into())?;
             ctx_b.store_next_sequence_ack(port_channel_id_on_b, 1.into())?;
+
+            store_verified_client_state(ctx_b, conn_end_on_b.client_id(), client_state_of_a_on_b)?;
+        }
+
+        Ok(())
+    }
+    fn chan_open_ack_validate<ValCtx>(
+        ctx_a: &ValCtx,
+        module_id: ModuleId,
+        msg: MsgChannelOpenAck,
+    ) -> Result<(), ContextError>
+    where
+        ValCtx: ValidationContext,
+    {
+        chan_open_ack::validate(ctx_a, &msg)?;
+
+        let module = ctx_a
+            .get_route(&module_id)
+            .ok_or(ChannelError::RouteNotFound)?;
+        module.on_chan_open_ack_validate(
+            &msg.port_id_on_a,
+            &msg.chan_id_on_a,
+            &msg.version_on_b,
+        )?;
+
+        Ok(())
+    }
+
+    fn chan_open_ack_execute<ExecCtx>(
+        ctx_a: &mut ExecCtx,
+        module_id: ModuleId,
+        msg: MsgChannelOpenAck,
+    ) -> Result<(), ContextError>
+    where
+        ExecCtx: ExecutionContext,
+    {
+        let port_channel_id_on_a = (msg.port_id_on_a.clone(), msg.chan_id_on_a.clone());
+        let chan_end_on_a = ctx_a.channel_end(&port_channel_id_on_a)?;
+        let conn_id_on_a = chan_end_on_a.connection_hops()[0].clone();
+        let conn_end_on_a = ctx_a.connection_end(&conn_id_on_a)?;
+        let client_state_of_b_on_a =
+            chan_open_ack::verify_proofs(ctx_a, &msg, &chan_end_on_a, &conn_end_on_a)?;
+
+        ctx_a.log_message("success: channel open ack".to_string());
+
+        let module = ctx_a
+            .get_route_mut(&module_id)
+            .ok_or(ChannelError::RouteNotFound)?;
+        let extras = module.on_chan_open_ack_execute(
+            &msg.port_id_on_a,
+            &msg.chan_id_on_a,
+            &msg.version_on_b,
+        )?;
+
+        // emit events and logs
+        {
+            let core_event = IbcEvent::OpenAckChannel(OpenAck::new(
+                msg.port_id_on_a.clone(),
+                msg.chan_id_on_a.clone(),
+                chan_end_on_a.counterparty().port_id.clone(),
+                msg.chan_id_on_b.clone(),
+                conn_id_on_a.clone(),
+            ));
+            ctx_a.emit_ibc_event(core_event);
+
+            emit_module_extras(ctx_a, extras);
+        }
+
+        // Transition the channel end to the new state & pick a version.
+        {
+            let mut chan_end_on_a = chan_end_on_a;
+
+            chan_end_on_a.set_state(State::Open);
+            chan_end_on_a.set_version(msg.version_on_b);
+            chan_end_on_a.set_counterparty_channel_id(msg.chan_id_on_b);
+
+            ctx_a.store_channel(port_channel_id_on_a, chan_end_on_a)?;
+
+            store_verified_client_state(ctx_a, conn_end_on_a.client_id(), client_state_of_b_on_a)?;
+        }
+
+        Ok(())
+    }
+
+    fn chan_open_confirm_validate<ValCtx>(
+        ctx_b: &ValCtx,
+        module_id: ModuleId,
+        msg: MsgChannelOpenConfirm,
+    ) -> Result<(), ContextError>
+    where
+        ValCtx: ValidationContext,
+    {
+        chan_open_confirm::validate(ctx_b, &msg)?;
+
+        let module = ctx_b
+            .get_route(&module_id)
+            .ok_or(ChannelError::RouteNotFound)?;
+        module.on_chan_open_confirm_validate(&msg.port_id_on_b, &msg.chan_id_on_b)?;
+
+        Ok(())
+    }
+
+    fn chan_open_confirm_execute<ExecCtx>(
+        ctx_b: &mut ExecCtx,
+        module_id: ModuleId,
+        msg: MsgChannelOpenConfirm,
+    ) -> Result<(), ContextError>
+    where
+        ExecCtx: ExecutionContext,
+    {
+        let port_channel_id_on_b = (msg.port_id_on_b.clone(), msg.chan_id_on_b.clone());
+        let chan_end_on_b = ctx_b.channel_end(&port_channel_id_on_b)?;
+        let conn_id_on_b = chan_end_on_b.connection_hops()[0].clone();
+        let chan_id_on_a = chan_end_on_b
+            .counterparty()
+            .channel_id()
+            .cloned()
+            .ok_or(ChannelError::InvalidCounterpartyChannelId)?;
+        let conn_end_on_b = ctx_b.connection_end(&conn_id_on_b)?;
+        let client_state_of_a_on_b =
+            chan_open_confirm::verify_proofs(ctx_b, &msg, &chan_end_on_b, &conn_end_on_b)?;
+
+        ctx_b.log_message("success: channel open confirm".to_string());
+
+        let module = ctx_b
+            .get_route_mut(&module_id)
+            .ok_or(ChannelError::RouteNotFound)?;
+        let extras = module.on_chan_open_confirm_execute(&msg.port_id_on_b, &msg.chan_id_on_b)?;
+
+        // emit events and logs
+        {
+            let core_event = IbcEvent::OpenConfirmChannel(OpenConfirm::new(
+                msg.port_id_on_b.clone(),
+                msg.chan_id_on_b.clone(),
+                chan_end_on_b.counterparty().port_id.clone(),
+                chan_id_on_a,
+                conn_id_on_b.clone(),
+            ));
+            ctx_b.emit_ibc_event(core_event);
+
+            emit_module_extras(ctx_b, extras);
+        }
+
+        // Transition the channel end to the new state.
+        {
+            let mut chan_end_on_b = chan_end_on_b;
+            chan_end_on_b.set_state(State::Open);
+
+            ctx_b.store_channel(port_channel_id_on_b, chan_end_on_b)?;
+
+            store_verified_client_state(ctx_b, conn_end_on_b.client_id(), client_state_of_a_on_b)?;
         }
 
         Ok(())
     }
+
+    fn chan_close_init_validate<ValCtx>(
+        ctx_a: &ValCtx,
+        module_id: ModuleId,
+        msg: MsgChannelCloseInit,
+    ) -> Result<(), ContextError>
+    where
+        ValCtx: ValidationContext,
+    {
+        chan_close_init::validate(ctx_a, &msg)?;
+
+        let module = ctx_a
+            .get_route(&module_id)
+            .ok_or(ChannelError::RouteNotFound)?;
+        module.on_chan_close_init_validate(&msg.port_id_on_a, &msg.chan_id_on_a)?;
+
+        Ok(())
+    }
+
+    fn chan_close_init_execute<ExecCtx>(
+        ctx_a: &mut ExecCtx,
+        module_id: ModuleId,
+        msg: MsgChannelCloseInit,
+    ) -> Result<(), ContextError>
+    where
+        ExecCtx: ExecutionContext,
+    {
+        let port_channel_id_on_a = (msg.port_id_on_a.clone(), msg.chan_id_on_a.clone());
+        let chan_end_on_a = ctx_a.channel_end(&port_channel_id_on_a)?;
+        let conn_id_on_a = chan_end_on_a.connection_hops()[0].clone();
+        let chan_id_on_b = chan_end_on_a
+            .counterparty()
+            .channel_id()
+            .cloned()
+            .ok_or(ChannelError::InvalidCounterpartyChannelId)?;
+
+        ctx_a.log_message("success: channel close init".to_string());
+
+        let module = ctx_a
+            .get_route_mut(&module_id)
+            .ok_or(ChannelError::RouteNotFound)?;
+        let extras = module.on_chan_close_init_execute(&msg.port_id_on_a, &msg.chan_id_on_a)?;
+
+        // emit events and logs
+        {
+            let core_event = IbcEvent::CloseInitChannel(CloseInit::new(
+                msg.port_id_on_a.clone(),
+                msg.chan_id_on_a.clone(),
+                chan_end_on_a.counterparty().port_id.clone(),
+                chan_id_on_b,
+                conn_id_on_a,
+            ));
+            ctx_a.emit_ibc_event(core_event);
+
+            emit_module_extras(ctx_a, extras);
+        }
+
+        {
+            let mut chan_end_on_a = chan_end_on_a;
+            chan_end_on_a.set_state(State::Closed);
+
+            ctx_a.store_channel(port_channel_id_on_a, chan_end_on_a)?;
+        }
+
+        Ok(())
+    }
+
+    fn chan_close_confirm_validate<ValCtx>(
+        ctx_b: &ValCtx,
+        module_id: ModuleId,
+        msg: MsgChannelCloseConfirm,
+    ) -> Result<(), ContextError>
+    where
+        ValCtx: ValidationContext,
+    {
+        chan_close_confirm::validate(ctx_b, &msg)?;
+
+        let module = ctx_b
+            .get_route(&module_id)
+            .ok_or(ChannelError::RouteNotFound)?;
+        module.on_chan_close_confirm_validate(&msg.port_id_on_b, &msg.chan_id_on_b)?;
+
+        Ok(())
+    }
+
+    fn chan_close_confirm_execute<ExecCtx>(
+        ctx_b: &mut ExecCtx,
+        module_id: ModuleId,
+        msg: MsgChannelCloseConfirm,
+    ) -> Result<(), ContextError>
+    where
+        ExecCtx: ExecutionContext,
+    {
+        let port_channel_id_on_b = (msg.port_id_on_b.clone(), msg.chan_id_on_b.clone());
+        let chan_end_on_b = ctx_b.channel_end(&port_channel_id_on_b)?;
+        let conn_id_on_b = chan_end_on_b.connection_hops()[0].clone();
+        let chan_id_on_a = chan_end_on_b
+            .counterparty()
+            .channel_id()
+            .cloned()
+            .ok_or(ChannelError::InvalidCounterpartyChannelId)?;
+        let conn_end_on_b = ctx_b.connection_end(&conn_id_on_b)?;
+        let client_state_of_a_on_b =
+            chan_close_confirm::verify_proofs(ctx_b, &msg, &chan_end_on_b, &conn_end_on_b)?;
+
+        ctx_b.log_message("success: channel close confirm".to_string());
+
+        let module = ctx_b
+            .get_route_mut(&module_id)
+            .ok_or(ChannelError::RouteNotFound)?;
+        let extras = module.on_chan_close_confirm_execute(&msg.port_id_on_b, &msg.chan_id_on_b)?;
+
+        // emit events and logs
+        {
+            let core_event = IbcEvent::CloseConfirmChannel(CloseConfirm::new(
+                msg.port_id_on_b.clone(),
+                msg.chan_id_on_b.clone(),
+                chan_end_on_b.counterparty().port_id.clone(),
+                chan_id_on_a,
+                conn_id_on_b.clone(),
+            ));
+            ctx_b.emit_ibc_event(core_event);
+
+            emit_module_extras(ctx_b, extras);
+        }
+
+        {
+            let mut chan_end_on_b = chan_end_on_b;
+            chan_end_on_b.set_state(State::Closed);
+
+            ctx_b.store_channel(port_channel_id_on_b, chan_end_on_b)?;
+
+            store_verified_client_state(ctx_b, conn_end_on_b.client_id(), client_state_of_a_on_b)?;
+        }
+
+        Ok(())
+    }
+
+    fn chan_upgrade_init_validate<ValCtx>(
+        ctx_a: &ValCtx,
+        module_id: ModuleId,
+        msg: MsgChannelUpgradeInit,
+    ) -> Result<(), ContextError>
+    where
+        ValCtx: ValidationContext,
+    {
+        chan_upgrade_init::validate(ctx_a, &msg)?;
+
+        let module = ctx_a
+            .get_route(&module_id)
+            .ok_or(ChannelError::RouteNotFound)?;
+        module.on_chan_upgrade_init_validate(
+            &msg.port_id_on_a,
+            &msg.chan_id_on_a,
+            msg.fields.ordering,
+            &msg.fields.connection_hops,
+            &msg.fields.version,
+        )?;
+
+        Ok(())
+    }
+
+    fn chan_upgrade_init_execute<ExecCtx>(
+        ctx_a: &mut ExecCtx,
+        module_id: ModuleId,
+        msg: MsgChannelUpgradeInit,
+    ) -> Result<(), ContextError>
+    where
+        ExecCtx: ExecutionContext,
+    {
+        let port_channel_id_on_a = (msg.port_id_on_a.clone(), msg.chan_id_on_a.clone());
+        let chan_end_on_a = ctx_a.channel_end(&port_channel_id_on_a)?;
+        let chan_id_on_b = chan_end_on_a
+            .counterparty()
+            .channel_id()
+            .cloned()
+            .ok_or(ChannelError::InvalidCounterpartyChannelId)?;
+        let upgrade_sequence = chan_end_on_a.upgrade_sequence().increment();
+
+        let module = ctx_a
+            .get_route_mut(&module_id)
+            .ok_or(ChannelError::RouteNotFound)?;
+        let (extras, version) = module.on_chan_upgrade_init_execute(
+            &msg.port_id_on_a,
+            &msg.chan_id_on_a,
+            msg.fields.ordering,
+            &msg.fields.connection_hops,
+            &msg.fields.version,
+        )?;
+
+        let upgrade_fields =
+            UpgradeFields::new(msg.fields.ordering, msg.fields.connection_hops, version);
+
+        // state changes
+        {
+            let upgrade_path =
+                ChannelUpgradePath(msg.port_id_on_a.clone(), msg.chan_id_on_a.clone());
+
+            // A new upgrade supersedes the one in progress, which the counterparty may then
+            // cancel.
+            if ctx_a.channel_upgrade(&port_channel_id_on_a).is_ok() {
+                ctx_a.store_channel_upgrade_error(
+                    ChannelUpgradeErrorPath(upgrade_path.0.clone(), upgrade_path.1.clone()),
+                    ErrorReceipt::new(
+                        chan_end_on_a.upgrade_sequence(),
+                        "superseded by a new upgrade".to_string(),
+                    ),
+                )?;
+            }
+
+            let mut chan_end_on_a = chan_end_on_a.clone();
+            chan_end_on_a.set_upgrade_sequence(upgrade_sequence);
+            ctx_a.store_channel(port_channel_id_on_a, chan_end_on_a)?;
+
+            // The timeout and the next sequence are only set once the counterparty replied.
+            let upgrade = Upgrade::new(
+                upgrade_fields.clone(),
+                UpgradeTimeout::default(),
+                Sequence::default(),
+            );
+            ctx_a.store_channel_upgrade(upgrade_path, upgrade)?;
+        }
+
+        // emit events and logs
+        {
+            let core_event = IbcEvent::UpgradeInitChannel(UpgradeInit::new(
+                msg.port_id_on_a,
+                msg.chan_id_on_a,
+                chan_end_on_a.counterparty().port_id.clone(),
+                chan_id_on_b,
+                upgrade_fields.connection_hops[0].clone(),
+                upgrade_fields.version,
+                upgrade_fields.ordering,
+                upgrade_sequence,
+            ));
+            ctx_a.emit_ibc_event(core_event);
+
+            ctx_a.log_message("success: channel upgrade init".to_string());
+
+            emit_module_extras(ctx_a, extras);
+        }
+
+        Ok(())
+    }
+
+    fn chan_upgrade_try_validate<ValCtx>(
+        ctx_b: &ValCtx,
+        module_id: ModuleId,
+        msg: MsgChannelUpgradeTry,
+    ) -> Result<(), ContextError>
+    where
+        ValCtx: ValidationContext,
+    {
+        chan_upgrade_try::validate(ctx_b, &msg)?;
+
+        let module = ctx_b
+            .get_route(&module_id)
+            .ok_or(ChannelError::RouteNotFound)?;
+        module.on_chan_upgrade_try_validate(
+            &msg.port_id_on_b,
+            &msg.chan_id_on_b,
+            msg.counterparty_upgrade_fields.ordering,
+            &msg.proposed_connection_hops,
+            &msg.counterparty_upgrade_fields.version,
+        )?;
+
+        Ok(())
+    }
+
+    fn chan_upgrade_try_execute<ExecCtx>(
+        ctx_b: &mut ExecCtx,
+        module_id: ModuleId,
+        msg: MsgChannelUpgradeTry,
+    ) -> Result<(), ContextError>
+    where
+        ExecCtx: ExecutionContext,
+    {
+        let port_channel_id_on_b = (msg.port_id_on_b.clone(), msg.chan_id_on_b.clone());
+        let chan_end_on_b = ctx_b.channel_end(&port_channel_id_on_b)?;
+        let conn_id_on_b = chan_end_on_b.connection_hops()[0].clone();
+        let client_state_of_a_on_b = chan_upgrade_try::verify_proofs(ctx_b, &msg, &chan_end_on_b)?;
+        let port_id_on_a = chan_end_on_b.counterparty().port_id.clone();
+        let chan_id_on_a = chan_end_on_b
+            .counterparty()
+            .channel_id()
+            .cloned()
+            .ok_or(ChannelError::InvalidCounterpartyChannelId)?;
+
+        let module = ctx_b
+            .get_route_mut(&module_id)
+            .ok_or(ChannelError::RouteNotFound)?;
+        let (extras, version) = module.on_chan_upgrade_try_execute(
+            &msg.port_id_on_b,
+            &msg.chan_id_on_b,
+            msg.counterparty_upgrade_fields.ordering,
+            &msg.proposed_connection_hops,
+            &msg.counterparty_upgrade_fields.version,
+        )?;
+
+        let upgrade_fields = UpgradeFields::new(
+            msg.counterparty_upgrade_fields.ordering,
+            msg.proposed_connection_hops,
+            version,
+        );
+
+        // state changes
+        {
+            // Chain A must acknowledge the upgrade before the timeout, measured on its own
+            // clock.
+            let timeout_timestamp = (ctx_b.host_timestamp()? + ctx_b.channel_upgrade_timeout())
+                .map_err(|e| ChannelError::InvalidUpgrade {
+                    reason: e.to_string(),
+                })?;
+            let upgrade = Upgrade::new(
+                upgrade_fields.clone(),
+                UpgradeTimeout::new(TimeoutHeight::Never, timeout_timestamp),
+                ctx_b.get_next_sequence_send(&port_channel_id_on_b)?,
+            );
+            ctx_b.store_channel_upgrade(
+                ChannelUpgradePath(msg.port_id_on_b.clone(), msg.chan_id_on_b.clone()),
+                upgrade,
+            )?;
+
+            let mut chan_end_on_b = chan_end_on_b;
+            chan_end_on_b.set_upgrade_sequence(msg.counterparty_upgrade_sequence);
+            chan_end_on_b.set_state(State::Flushing);
+            ctx_b.store_channel(port_channel_id_on_b, chan_end_on_b)?;
+
+            store_channel_verified_client_state(ctx_b, &conn_id_on_b, client_state_of_a_on_b)?;
+        }
+
+        // emit events and logs
+        {
+            let core_event = IbcEvent::UpgradeTryChannel(UpgradeTry::new(
+                msg.port_id_on_b,
+                msg.chan_id_on_b,
+                port_id_on_a,
+                chan_id_on_a,
+                upgrade_fields.connection_hops[0].clone(),
+                upgrade_fields.version,
+                upgrade_fields.ordering,
+                msg.counterparty_upgrade_sequence,
+            ));
+            ctx_b.emit_ibc_event(core_event);
+
+            ctx_b.log_message("success: channel upgrade try".to_string());
+
+            emit_module_extras(ctx_b, extras);
+        }
+
+        Ok(())
+    }
+
+    fn chan_upgrade_ack_validate<ValCtx>(
+        ctx_a: &ValCtx,
+        module_id: ModuleId,
+        msg: MsgChannelUpgradeAck,
+    ) -> Result<(), ContextError>
+    where
+        ValCtx: ValidationContext,
+    {
+        chan_upgrade_ack::validate(ctx_a, &msg)?;
+
+        let module = ctx_a
+            .get_route(&module_id)
+            .ok_or(ChannelError::RouteNotFound)?;
+        module.on_chan_upgrade_ack_validate(
+            &msg.port_id_on_a,
+            &msg.chan_id_on_a,
+            &msg.counterparty_upgrade.fields.version,
+        )?;
+
+        Ok(())
+    }
+
+    fn chan_upgrade_ack_execute<ExecCtx>(
+        ctx_a: &mut ExecCtx,
+        module_id: ModuleId,
+        msg: MsgChannelUpgradeAck,
+    ) -> Result<(), ContextError>
+    where
+        ExecCtx: ExecutionContext,
+    {
+        let port_channel_id_on_a = (msg.port_id_on_a.clone(), msg.chan_id_on_a.clone());
+        let chan_end_on_a = ctx_a.channel_end(&port_channel_id_on_a)?;
+        let conn_id_on_a = chan_end_on_a.connection_hops()[0].clone();
+        let client_state_of_b_on_a = chan_upgrade_ack::verify_proofs(ctx_a, &msg, &chan_end_on_a)?;
+        let upgrade_on_a = ctx_a.channel_upgrade(&port_channel_id_on_a)?;
+        let port_id_on_b = chan_end_on_a.counterparty().port_id.clone();
+        let chan_id_on_b = chan_end_on_a
+            .counterparty()
+            .channel_id()
+            .cloned()
+            .ok_or(ChannelError::InvalidCounterpartyChannelId)?;
+        let upgrade_sequence = chan_end_on_a.upgrade_sequence();
+        let upgrade_fields = upgrade_on_a.fields.clone();
+
+        let module = ctx_a
+            .get_route_mut(&module_id)
+            .ok_or(ChannelError::RouteNotFound)?;
+        let extras = module.on_chan_upgrade_ack_execute(
+            &msg.port_id_on_a,
+            &msg.chan_id_on_a,
+            &msg.counterparty_upgrade.fields.version,
+        )?;
+
+        // state changes
+        {
+            let mut upgrade_on_a = upgrade_on_a;
+            upgrade_on_a.timeout = msg.counterparty_upgrade.timeout;
+            upgrade_on_a.next_sequence_send =
+                ctx_a.get_next_sequence_send(&port_channel_id_on_a)?;
+
+            let mut chan_end_on_a = chan_end_on_a;
+            if ctx_a.has_inflight_packets(&port_channel_id_on_a)? {
+                chan_end_on_a.set_state(State::Flushing);
+            } else {
+                chan_end_on_a.set_state(State::FlushComplete);
+            }
+
+            ctx_a.store_channel_upgrade(
+                ChannelUpgradePath(msg.port_id_on_a.clone(), msg.chan_id_on_a.clone()),
+                upgrade_on_a,
+            )?;
+            ctx_a.store_channel(port_channel_id_on_a, chan_end_on_a)?;
+
+            store_channel_verified_client_state(ctx_a, &conn_id_on_a, client_state_of_b_on_a)?;
+        }
+
+        // emit events and logs
+        {
+            let core_event = IbcEvent::UpgradeAckChannel(UpgradeAck::new(
+                msg.port_id_on_a,
+                msg.chan_id_on_a,
+                port_id_on_b,
+                chan_id_on_b,
+                upgrade_fields.connection_hops[0].clone(),
+                upgrade_fields.version,
+                upgrade_fields.ordering,
+                upgrade_sequence,
+            ));
+            ctx_a.emit_ibc_event(core_event);
+
+            ctx_a.log_message("success: channel upgrade ack".to_string());
+
+            emit_module_extras(ctx_a, extras);
+        }
+
+        Ok(())
+    }
+
+    fn chan_upgrade_confirm_validate<ValCtx>(
+        ctx_b: &ValCtx,
+        _module_id: ModuleId,
+        msg: MsgChannelUpgradeConfirm,
+    ) -> Result<(), ContextError>
+    where
+        ValCtx: ValidationContext,
+    {
+        chan_upgrade_confirm::validate(ctx_b, &msg)
+    }
+
+    fn chan_upgrade_confirm_execute<ExecCtx>(
+        ctx_b: &mut ExecCtx,
+        module_id: ModuleId,
+        msg: MsgChannelUpgradeConfirm,
+    ) -> Result<(), ContextError>
+    where
+        ExecCtx: ExecutionContext,
+    {
+        let port_channel_id_on_b = (msg.port_id_on_b.clone(), msg.chan_id_on_b.clone());
+        let mut chan_end_on_b = ctx_b.channel_end(&port_channel_id_on_b)?;
+        let conn_id_on_b = chan_end_on_b.connection_hops()[0].clone();
+        let chan_id_on_a = chan_end_on_b
+            .counterparty()
+            .channel_id()
+            .cloned()
+            .ok_or(ChannelError::InvalidCounterpartyChannelId)?;
+
+        let client_state_of_a_on_b =
+            chan_upgrade_confirm::verify_proofs(ctx_b, &msg, &chan_end_on_b)?;
+        store_channel_verified_client_state(ctx_b, &conn_id_on_b, client_state_of_a_on_b)?;
+
+        if !ctx_b.has_inflight_packets(&port_channel_id_on_b)? {
+            chan_end_on_b.set_state(State::FlushComplete);
+        }
+
+        // emit events and logs
+        {
+            let core_event = IbcEvent::UpgradeConfirmChannel(UpgradeConfirm::new(
+                msg.port_id_on_b,
+                msg.chan_id_on_b,
+                chan_end_on_b.counterparty().port_id.clone(),
+                chan_id_on_a,
+                *chan_end_on_b.state(),
+                chan_end_on_b.upgrade_sequence(),
+            ));
+            ctx_b.emit_ibc_event(core_event);
+
+            ctx_b.log_message("success: channel upgrade confirm".to_string());
+        }
+
+        // Both ends have flushed their packets: the upgrade can be completed right away.
+        if chan_end_on_b.state_matches(&State::FlushComplete)
+            && msg.counterparty_chan_state == State::FlushComplete
+        {
+            chan_upgrade_open(ctx_b, module_id, port_channel_id_on_b, chan_end_on_b)
+        } else {
+            ctx_b.store_channel(port_channel_id_on_b, chan_end_on_b)
+        }
+    }
+
+    fn chan_upgrade_open_validate<ValCtx>(
+        ctx: &ValCtx,
+        _module_id: ModuleId,
+        msg: MsgChannelUpgradeOpen,
+    ) -> Result<(), ContextError>
+    where
+        ValCtx: ValidationContext,
+    {
+        chan_upgrade_open::validate(ctx, &msg)
+    }
+
+    fn chan_upgrade_open_execute<ExecCtx>(
+        ctx: &mut ExecCtx,
+        module_id: ModuleId,
+        msg: MsgChannelUpgradeOpen,
+    ) -> Result<(), ContextError>
+    where
+        ExecCtx: ExecutionContext,
+    {
+        let port_channel_id = (msg.port_id.clone(), msg.chan_id.clone());
+        let chan_end = ctx.channel_end(&port_channel_id)?;
+
+        let client_state_of_cp = chan_upgrade_open::verify_proofs(ctx, &msg, &chan_end)?;
+        store_channel_verified_client_state(
+            ctx,
+            &chan_end.connection_hops()[0],
+            client_state_of_cp,
+        )?;
+
+        chan_upgrade_open(ctx, module_id, port_channel_id, chan_end)
+    }
+
+    fn chan_upgrade_timeout_validate<ValCtx>(
+        ctx: &ValCtx,
+        _module_id: ModuleId,
+        msg: MsgChannelUpgradeTimeout,
+    ) -> Result<(), ContextError>
+    where
+        ValCtx: ValidationContext,
+    {
+        chan_upgrade_timeout::validate(ctx, &msg)
+    }
+
+    fn chan_upgrade_timeout_execute<ExecCtx>(
+        ctx: &mut ExecCtx,
+        _module_id: ModuleId,
+        msg: MsgChannelUpgradeTimeout,
+    ) -> Result<(), ContextError>
+    where
+        ExecCtx: ExecutionContext,
+    {
+        let port_channel_id = (msg.port_id.clone(), msg.chan_id.clone());
+        let chan_end = ctx.channel_end(&port_channel_id)?;
+        let counterparty_chan_id = chan_end
+            .counterparty()
+            .channel_id()
+            .cloned()
+            .ok_or(ChannelError::InvalidCounterpartyChannelId)?;
+
+        let client_state_of_cp = chan_upgrade_timeout::verify_proofs(ctx, &msg, &chan_end)?;
+        store_channel_verified_client_state(
+            ctx,
+            &chan_end.connection_hops()[0],
+            client_state_of_cp,
+        )?;
+
+        // emit events and logs
+        {
+            let core_event = IbcEvent::UpgradeTimeoutChannel(UpgradeTimeoutEvent::new(
+                port_channel_id.0.clone(),
+                port_channel_id.1.clone(),
+                chan_end.counterparty().port_id.clone(),
+                counterparty_chan_id,
+                chan_end.upgrade_sequence(),
+            ));
+            ctx.emit_ibc_event(core_event);
+
+            ctx.log_message("success: channel upgrade timeout".to_string());
+        }
+
+        chan_upgrade_abort(
+            ctx,
+            port_channel_id,
+            chan_end,
+            "upgrade timed out".to_string(),
+        )
+    }
+
+    fn chan_upgrade_cancel_validate<ValCtx>(
+        ctx: &ValCtx,
+        _module_id: ModuleId,
+        msg: MsgChannelUpgradeCancel,
+    ) -> Result<(), ContextError>
+    where
+        ValCtx: ValidationContext,
+    {
+        chan_upgrade_cancel::validate(ctx, &msg)
+    }
+
+    fn chan_upgrade_cancel_execute<ExecCtx>(
+        ctx: &mut ExecCtx,
+        _module_id: ModuleId,
+        msg: MsgChannelUpgradeCancel,
+    ) -> Result<(), ContextError>
+    where
+        ExecCtx: ExecutionContext,
+    {
+        let port_channel_id = (msg.port_id.clone(), msg.chan_id.clone());
+        let mut chan_end = ctx.channel_end(&port_channel_id)?;
+        let counterparty_chan_id = chan_end
+            .counterparty()
+            .channel_id()
+            .cloned()
+            .ok_or(ChannelError::InvalidCounterpartyChannelId)?;
+
+        let client_state_of_cp = chan_upgrade_cancel::verify_proofs(ctx, &msg, &chan_end)?;
+        store_channel_verified_client_state(
+            ctx,
+            &chan_end.connection_hops()[0],
+            client_state_of_cp,
+        )?;
+
+        // Catch up with the upgrade sequence of the counterparty, so that the next upgrade
+        // is newer on both chains.
+        chan_end.set_upgrade_sequence(msg.error_receipt.sequence);
+
+        // emit events and logs
+        {
+            let core_event = IbcEvent::UpgradeCancelChannel(UpgradeCancel::new(
+                port_channel_id.0.clone(),
+                port_channel_id.1.clone(),
+                chan_end.counterparty().port_id.clone(),
+                counterparty_chan_id,
+                chan_end.upgrade_sequence(),
+            ));
+            ctx.emit_ibc_event(core_event);
+
+            ctx.log_message("success: channel upgrade cancel".to_string());
+        }
+
+        chan_upgrade_abort(
+            ctx,
+            port_channel_id,
+            chan_end,
+            "upgrade cancelled by the counterparty".to_string(),
+        )
+    }
+
+    /// Applies the upgrade in progress to the channel end, and opens it again.
+    fn chan_upgrade_open<ExecCtx>(
+        ctx: &mut ExecCtx,
+        module_id: ModuleId,
+        port_channel_id: (PortId, ChannelId),
+        chan_end: ChannelEnd,
+    ) -> Result<(), ContextError>
+    where
+        ExecCtx: ExecutionContext,
+    {
+        let upgrade = ctx.channel_upgrade(&port_channel_id)?;
+        let fields = upgrade.fields;
+        let counterparty_port_id = chan_end.counterparty().port_id.clone();
+        let counterparty_chan_id = chan_end
+            .counterparty()
+            .channel_id()
+            .cloned()
+            .ok_or(ChannelError::InvalidCounterpartyChannelId)?;
+        let upgrade_sequence = chan_end.upgrade_sequence();
+
+        // state changes
+        {
+            let mut chan_end = chan_end;
+            if chan_end.connection_hops != fields.connection_hops {
+                ctx.delete_connection_channels(
+                    chan_end.connection_hops[0].clone(),
+                    port_channel_id.clone(),
+                )?;
+                ctx.store_connection_channels(
+                    fields.connection_hops[0].clone(),
+                    port_channel_id.clone(),
+                )?;
+            }
+
+            chan_end.ordering = fields.ordering;
+            chan_end.connection_hops = fields.connection_hops.clone();
+            chan_end.set_version(fields.version.clone());
+            chan_end.set_state(State::Open);
+
+            ctx.store_channel(port_channel_id.clone(), chan_end)?;
+            ctx.delete_channel_upgrade(ChannelUpgradePath(
+                port_channel_id.0.clone(),
+                port_channel_id.1.clone(),
+            ))?;
+        }
+
+        let module = ctx
+            .get_route_mut(&module_id)
+            .ok_or(ChannelError::RouteNotFound)?;
+        let extras = module.on_chan_upgrade_open_execute(
+            &port_channel_id.0,
+            &port_channel_id.1,
+            fields.ordering,
+            &fields.connection_hops,
+            &fields.version,
+        );
+
+        // emit events and logs
+        {
+            let core_event = IbcEvent::UpgradeOpenChannel(UpgradeOpen::new(
+                port_channel_id.0,
+                port_channel_id.1,
+                counterparty_port_id,
+                counterparty_chan_id,
+                fields.connection_hops[0].clone(),
+                fields.version,
+                fields.ordering,
+                upgrade_sequence,
+            ));
+            ctx.emit_ibc_event(core_event);
+
+            ctx.log_message("success: channel upgrade open".to_string());
+
+            emit_module_extras(ctx, extras);
+        }
+
+        Ok(())
+    }
+
+    /// Restores the channel end as it was before the upgrade in progress, and records why it
+    /// was aborted for the counterparty.
+    fn chan_upgrade_abort<ExecCtx>(
+        ctx: &mut ExecCtx,
+        port_channel_id: (PortId, ChannelId),
+        chan_end: ChannelEnd,
+        message: String,
+    ) -> Result<(), ContextError>
+    where
+        ExecCtx: ExecutionContext,
+    {
+        let (port_id, chan_id) = port_channel_id.clone();
+        let error_receipt = ErrorReceipt::new(chan_end.upgrade_sequence(), message);
+
+        let mut chan_end = chan_end;
+        chan_end.set_state(State::Open);
+
+        ctx.store_channel(port_channel_id, chan_end)?;
+        ctx.delete_channel_upgrade(ChannelUpgradePath(port_id.clone(), chan_id.clone()))?;
+        ctx.store_channel_upgrade_error(ChannelUpgradeErrorPath(port_id, chan_id), error_receipt)?;
+
+        Ok(())
+    }
+
+    fn recv_packet_validate<ValCtx>(
+        ctx_b: &ValCtx,
+        module_id: ModuleId,
+        msg: MsgRecvPacket,
+    ) -> Result<(), ContextError>
+    where
+        ValCtx: ValidationContext,
+    {
+        recv_packet::validate(ctx_b, &msg)?;
+
+        let module = ctx_b
+            .get_route(&module_id)
+            .ok_or(PacketError::RouteNotFound)?;
+        module.on_recv_packet_validate(&msg.packet, &msg.signer)?;
+
+        Ok(())
+    }
+
+    fn recv_packet_execute<ExecCtx>(
+        ctx_b: &mut ExecCtx,
+        module_id: ModuleId,
+        msg: MsgRecvPacket,
+    ) -> Result<(), ContextError>
+    where
+        ExecCtx: ExecutionContext,
+    {
+        let port_chan_id_on_b = (msg.packet.port_on_b.clone(), msg.packet.chan_on_b.clone());
+        let chan_end_on_b = ctx_b.channel_end(&port_chan_id_on_b)?;
+        let conn_id_on_b = chan_end_on_b.connection_hops()[0].clone();
+
+        // Check if another relayer already relayed the packet. This is a no-op, and
+        // must not fail the transaction.
+        {
+            let packet_already_received = match chan_end_on_b.ordering {
+                Order::None => false,
+                Order::Unordered => ctx_b
+                    .get_packet_receipt(&(
+                        msg.packet.port_on_b.clone(),
+                        msg.packet.chan_on_b.clone(),
+                        msg.packet.sequence,
+                    ))
+                    .is_ok(),
+                Order::Ordered | Order::OrderedAllowTimeout => {
+                    let next_seq_recv = ctx_b.get_next_sequence_recv(&port_chan_id_on_b)?;
+                    msg.packet.sequence < next_seq_recv
+                }
+            };
+
+            if packet_already_received {
+                return Ok(());
+            }
+        }
+
+        let conn_end_on_b = ctx_b.connection_end(&conn_id_on_b)?;
+        let client_state_of_a_on_b = recv_packet::verify_proofs(ctx_b, &msg, &conn_end_on_b)?;
+
+        // A timed out packet on a channel that allows timeouts only gets its
+        // sequence skipped, which the timeout receipt proves to the sender; it
+        // never reaches the module.
+        if chan_end_on_b.order_matches(&Order::OrderedAllowTimeout)
+            && recv_packet::verify_packet_not_timed_out(
+                &msg.packet,
+                ctx_b.host_height()?,
+                ctx_b.host_timestamp()?,
+            )
+            .is_err()
+        {
+            store_verified_client_state(ctx_b, conn_end_on_b.client_id(), client_state_of_a_on_b)?;
+
+            let path = ReceiptsPath {
+                port_id: msg.packet.port_on_b.clone(),
+                channel_id: msg.packet.chan_on_b.clone(),
+                sequence: msg.packet.sequence,
+            };
+            ctx_b.store_packet_receipt(path, Receipt::Timeout)?;
+
+            let next_seq_recv = ctx_b.get_next_sequence_recv(&port_chan_id_on_b)?;
+            ctx_b.store_next_sequence_recv(port_chan_id_on_b, next_seq_recv.increment())?;
+            ctx_b.log_message("success: timed out packet skipped".to_string());
+
+            return Ok(());
+        }
+
+        let module = ctx_b
+            .get_route_mut(&module_id)
+            .ok_or(PacketError::RouteNotFound)?;
+        let (extras, acknowledgement) = module.on_recv_packet_execute(&msg.packet, &msg.signer);
+        // The acknowledgement is checked before any state change, so that an
+        // invalid acknowledgement leaves the store untouched.
+        let acknowledgement: Option<Acknowledgement> = acknowledgement
+            .map(|ack| (*ack).as_ref().to_vec().try_into())
+            .transpose()?;
+
+        // state changes
+        {
+            store_verified_client_state(ctx_b, conn_end_on_b.client_id(), client_state_of_a_on_b)?;
+
+            match chan_end_on_b.ordering {
+                Order::Unordered => {
+                    let path = ReceiptsPath {
+                        port_id: msg.packet.port_on_b.clone(),
+                        channel_id: msg.packet.chan_on_b.clone(),
+                        sequence: msg.packet.sequence,
+                    };
+
+                    ctx_b.store_packet_receipt(path, Receipt::Ok)?;
+                }
+                Order::Ordered | Order::OrderedAllowTimeout => {
+                    let next_seq_recv = ctx_b.get_next_sequence_recv(&port_chan_id_on_b)?;
+
+                    ctx_b.store_next_sequence_recv(
+                        port_chan_id_on_b.clone(),
+                        next_seq_recv.increment(),
+                    )?;
+                }
+                Order::None => {}
+            }
+
+            if let Some(acknowledgement) = &acknowledgement {
+                write_acknowledgement(ctx_b, &msg.packet, acknowledgement)?;
+            }
+        }
+
+        // emit events and logs
+        {
+            ctx_b.log_message("success: packet receive".to_string());
+            ctx_b.emit_ibc_event(IbcEvent::ReceivePacket(ReceivePacket::new(
+                msg.packet.clone(),
+                chan_end_on_b.ordering,
+                conn_id_on_b.clone(),
+            )));
+
+            if let Some(acknowledgement) = acknowledgement {
+                ctx_b.log_message("success: packet write acknowledgement".to_string());
+                ctx_b.emit_ibc_event(IbcEvent::WriteAcknowledgement(WriteAcknowledgement::new(
+                    msg.packet,
+                    acknowledgement,
+                    conn_id_on_b,
+                )));
+            }
+
+            emit_module_extras(ctx_b, extras);
+        }
+
+        Ok(())
+    }
+
+    /// Stores the commitment of an acknowledgement written synchronously by the
+    /// `on_recv_packet_execute` callback.
+    fn write_acknowledgement<ExecCtx>(
+        ctx_b: &mut ExecCtx,
+        packet: &Packet,
+        acknowledgement: &Acknowledgement,
+    ) -> Result<(), ContextError>
+    where
+        ExecCtx: ExecutionContext,
+    {
+        let ack_commitment = ctx_b.ack_commitment(acknowledgement);
+        ctx_b.store_packet_acknowledgement(
+            (
+                packet.port_on_b.clone(),
+                packet.chan_on_b.clone(),
+                packet.sequence,
+            ),
+            ack_commitment,
+        )
+    }
+
+    fn acknowledgement_packet_validate<ValCtx>(
+        ctx_a: &ValCtx,
+        module_id: ModuleId,
+        msg: MsgAcknowledgement,
+    ) -> Result<(), ContextError>
+    where
+        ValCtx: ValidationContext,
+    {
+        acknowledgement::validate(ctx_a, &msg)?;
+
+        let module = ctx_a
+            .get_route(&module_id)
+            .ok_or(PacketError::RouteNotFound)?;
+        module.on_acknowledgement_packet_validate(
+            &msg.packet,
+            &msg.acknowledgement,
+            &msg.signer,
+        )?;
+
+        Ok(())
+    }
+
+    fn acknowledgement_packet_execute<ExecCtx>(
+        ctx_a: &mut ExecCtx,
+        module_id: ModuleId,
+        msg: MsgAcknowledgement,
+    ) -> Result<(), ContextError>
+    where
+        ExecCtx: ExecutionContext,
+    {
+        let port_chan_id_on_a = (msg.packet.port_on_a.clone(), msg.packet.chan_on_a.clone());
+        let chan_end_on_a = ctx_a.channel_end(&port_chan_id_on_a)?;
+        let conn_id_on_a = chan_end_on_a.connection_hops()[0].clone();
+        let conn_end_on_a = ctx_a.connection_end(&conn_id_on_a)?;
+        let client_state_of_b_on_a = acknowledgement::verify_proofs(ctx_a, &msg, &conn_end_on_a)?;
+
+        let module = ctx_a
+            .get_route_mut(&module_id)
+            .ok_or(PacketError::RouteNotFound)?;
+        let extras = module.on_acknowledgement_packet_execute(
+            &msg.packet,
+            &msg.acknowledgement,
+            &msg.signer,
+        )?;
+
+        // state changes
+        {
+            let commitment_path = CommitmentsPath {
+                port_id: msg.packet.port_on_a.clone(),
+                channel_id: msg.packet.chan_on_a.clone(),
+                sequence: msg.packet.sequence,
+            };
+            ctx_a.delete_packet_commitment(commitment_path)?;
+
+            store_verified_client_state(ctx_a, conn_end_on_a.client_id(), client_state_of_b_on_a)?;
+
+            if chan_end_on_a.ordering.is_ordered() {
+                // Note: in validation, we verified that `msg.packet.sequence == next_seq_ack`
+                // (where `next_seq_ack` is the value in the store)
+                ctx_a.store_next_sequence_ack(
+                    port_chan_id_on_a.clone(),
+                    msg.packet.sequence.increment(),
+                )?;
+            }
+
+            // The last in-flight packet of a flushing channel completes the flush.
+            if chan_end_on_a.state_matches(&State::Flushing)
+                && !ctx_a.has_inflight_packets(&port_chan_id_on_a)?
+            {
+                let mut chan_end_on_a = chan_end_on_a.clone();
+                chan_end_on_a.set_state(State::FlushComplete);
+
+                ctx_a.store_channel(port_chan_id_on_a, chan_end_on_a)?;
+            }
+        }
+
+        // emit events and logs
+        {
+            ctx_a.log_message("success: packet ack".to_string());
+            ctx_a.emit_ibc_event(IbcEvent::AcknowledgePacket(AcknowledgePacket::new(
+                msg.packet,
+                chan_end_on_a.ordering,
+                conn_id_on_a,
+            )));
+
+            emit_module_extras(ctx_a, extras);
+        }
+
+        Ok(())
+    }
+
+    /// `MsgTimeout` and `MsgTimeoutOnClose` only differ in how they are validated; their
+    /// execution is the same.
+    enum TimeoutMsgType {
+        Timeout(MsgTimeout),
+        TimeoutOnClose(MsgTimeoutOnClose),
+    }
+
+    impl TimeoutMsgType {
+        fn into_packet_and_signer(self) -> (Packet, Signer) {
+            match self {
+                TimeoutMsgType::Timeout(msg) => (msg.packet, msg.signer),
+                TimeoutMsgType::TimeoutOnClose(msg) => (msg.packet, msg.signer),
+            }
+        }
+    }
+
+    fn timeout_packet_validate<ValCtx>(
+        ctx_a: &ValCtx,
+        module_id: ModuleId,
+        timeout_msg_type: TimeoutMsgType,
+    ) -> Result<(), ContextError>
+    where
+        ValCtx: ValidationContext,
+    {
+        match &timeout_msg_type {
+            TimeoutMsgType::Timeout(msg) => timeout::validate(ctx_a, msg),
+            TimeoutMsgType::TimeoutOnClose(msg) => timeout_on_close::validate(ctx_a, msg),
+        }?;
+
+        let (packet, signer) = timeout_msg_type.into_packet_and_signer();
+        let module = ctx_a
+            .get_route(&module_id)
+            .ok_or(PacketError::RouteNotFound)?;
+        module.on_timeout_packet_validate(&packet, &signer)?;
+
+        Ok(())
+    }
+
+    fn timeout_packet_execute<ExecCtx>(
+        ctx_a: &mut ExecCtx,
+        module_id: ModuleId,
+        timeout_msg_type: TimeoutMsgType,
+    ) -> Result<(), ContextError>
+    where
+        ExecCtx: ExecutionContext,
+    {
+        let packet = match &timeout_msg_type {
+            TimeoutMsgType::Timeout(msg) => &msg.packet,
+            TimeoutMsgType::TimeoutOnClose(msg) => &msg.packet,
+        };
+        let port_chan_id_on_a = (packet.port_on_a.clone(), packet.chan_on_a.clone());
+        let chan_end_on_a = ctx_a.channel_end(&port_chan_id_on_a)?;
+        let conn_id_on_a = chan_end_on_a.connection_hops()[0].clone();
+        let conn_end_on_a = ctx_a.connection_end(&conn_id_on_a)?;
+        let client_state_of_b_on_a = match &timeout_msg_type {
+            TimeoutMsgType::Timeout(msg) => {
+                timeout::verify_proofs(ctx_a, msg, &chan_end_on_a, &conn_end_on_a)
+            }
+            TimeoutMsgType::TimeoutOnClose(msg) => {
+                timeout_on_close::verify_proofs(ctx_a, msg, &chan_end_on_a, &conn_end_on_a)
+            }
+        }?;
+        let (packet, signer) = timeout_msg_type.into_packet_and_signer();
+
+        let module = ctx_a
+            .get_route_mut(&module_id)
+            .ok_or(PacketError::RouteNotFound)?;
+        let extras = module.on_timeout_packet_execute(&packet, &signer)?;
+
+        // state changes
+        {
+            let commitment_path = CommitmentsPath {
+                port_id: packet.port_on_a.clone(),
+                channel_id: packet.chan_on_a.clone(),
+                sequence: packet.sequence,
+            };
+            ctx_a.delete_packet_commitment(commitment_path)?;
+
+            store_verified_client_state(ctx_a, conn_end_on_a.client_id(), client_state_of_b_on_a)?;
+
+            // A channel that allows timeouts stays open, and moves on to the next
+            // packet to acknowledge.
+            if chan_end_on_a.order_matches(&Order::OrderedAllowTimeout) {
+                // Note: in validation, we verified that `packet.sequence == next_seq_ack`
+                ctx_a.store_next_sequence_ack(
+                    port_chan_id_on_a.clone(),
+                    packet.sequence.increment(),
+                )?;
+            }
+
+            // An ordered channel is closed as soon as one of its packets times out, which
+            // aborts its upgrade in progress if any.
+            if chan_end_on_a.order_matches(&Order::Ordered) {
+                if chan_end_on_a.is_flushing() {
+                    chan_upgrade_abort(
+                        ctx_a,
+                        port_chan_id_on_a.clone(),
+                        chan_end_on_a.clone(),
+                        "channel closed by a packet timeout".to_string(),
+                    )?;
+                }
+
+                let mut chan_end_on_a = chan_end_on_a.clone();
+                chan_end_on_a.set_state(State::Closed);
+
+                ctx_a.store_channel(port_chan_id_on_a, chan_end_on_a)?;
+            } else if chan_end_on_a.state_matches(&State::Flushing)
+                && !ctx_a.has_inflight_packets(&port_chan_id_on_a)?
+            {
+                // The last in-flight packet of a flushing channel completes the flush.
+                let mut chan_end_on_a = chan_end_on_a.clone();
+                chan_end_on_a.set_state(State::FlushComplete);
+
+                ctx_a.store_channel(port_chan_id_on_a, chan_end_on_a)?;
+            }
+        }
+
+        // emit events and logs
+        {
+            ctx_a.log_message("success: packet timeout".to_string());
+            ctx_a.emit_ibc_event(IbcEvent::TimeoutPacket(TimeoutPacket::new(
+                packet.clone(),
+                chan_end_on_a.ordering,
+            )));
+
+            if chan_end_on_a.order_matches(&Order::Ordered) {
+                ctx_a.emit_ibc_event(IbcEvent::ChannelClosed(ChannelClosed::new(
+                    packet.port_on_a,
+                    packet.chan_on_a,
+                    chan_end_on_a.counterparty().port_id.clone(),
+                    chan_end_on_a.counterparty().channel_id.clone(),
+                    conn_id_on_a,
+                    chan_end_on_a.ordering,
+                )));
+            }
+
+            emit_module_extras(ctx_a, extras);
+        }
+
+        Ok(())
+    }
+
+    /// Stores the state of a client once the proofs of a message were verified against it, if
+    /// verifying them changed the state of the client.
+    pub(crate) fn store_verified_client_state<ExecCtx>(
+        ctx: &mut ExecCtx,
+        client_id: &ClientId,
+        client_state: Box<dyn ClientState>,
+    ) -> Result<(), ContextError>
+    where
+        ExecCtx: ExecutionContext,
+    {
+        if *ctx.client_state(client_id)? == *client_state {
+            return Ok(());
+        }
+
+        ctx.store_client_state(ClientStatePath(client_id.clone()), client_state)
+    }
+
+    /// Same as `store_verified_client_state`, for the client underlying the connection `conn_id`
+    /// of a channel.
+    fn store_channel_verified_client_state<ExecCtx>(
+        ctx: &mut ExecCtx,
+        conn_id: &ConnectionId,
+        client_state: Box<dyn ClientState>,
+    ) -> Result<(), ContextError>
+    where
+        ExecCtx: ExecutionContext,
+    {
+        let client_id = ctx.connection_end(conn_id)?.client_id().clone();
+
+        store_verified_client_state(ctx, &client_id, client_state)
+    }
+
+    /// Emits the events and log messages that a `Module` callback returned.
+    fn emit_module_extras<ExecCtx>(ctx: &mut ExecCtx, extras: ModuleExtras)
+    where
+        ExecCtx: ExecutionContext,
+    {
+        for module_event in extras.events {
+            ctx.emit_ibc_event(IbcEvent::AppModule(module_event));
+        }
+
+        for log_message in extras.log {
+            ctx.log_message(log_message);
+        }
+    }
 }
@@ -1,3 +1,4 @@
+use core::fmt::{Display, Error as FmtError, Formatter};
 use core::marker::{Send, Sync};
 use core::time::Duration;
 
@@ -8,18 +9,16 @@ use ibc_proto::protobuf::Protobuf as ErasedProtobuf;
 
 use crate::core::ics02_client::client_type::ClientType;
 use crate::core::ics02_client::error::ClientError;
-use crate::core::ics03_connection::connection::ConnectionEnd;
-use crate::core::ics04_channel::channel::ChannelEnd;
-use crate::core::ics04_channel::commitment::{AcknowledgementCommitment, PacketCommitment};
 use crate::core::ics04_channel::context::ChannelReader;
-use crate::core::ics04_channel::packet::Sequence;
 use crate::core::ics23_commitment::commitment::{
     CommitmentPrefix, CommitmentProofBytes, CommitmentRoot,
 };
-use crate::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId};
+use crate::core::ics24_host::identifier::{ChainId, ClientId};
+use crate::core::ics24_host::Path;
 use crate::dynamic_typing::AsAny;
 use crate::erased::ErasedSerialize;
 use crate::prelude::*;
+use crate::timestamp::Timestamp;
 use crate::Height;
 
 use super::consensus_state::ConsensusState;
@@ -60,6 +59,57 @@ pub trait ClientState:
     /// state timestamp
     fn expired(&self, elapsed: Duration) -> bool;
 
+    /// Returns the status of the client. Only an `Active` client may be updated, or have proofs
+    /// verified against it.
+    ///
+    /// By default, a client is `Frozen` if it has a frozen height, and `Expired` if its latest
+    /// consensus state is missing or older than its trusting period.
+    fn status(&self, ctx: &dyn ClientReader, client_id: &ClientId) -> Status {
+        if self.is_frozen() {
+            return Status::Frozen;
+        }
+
+        let latest_consensus_state = match ctx.consensus_state(client_id, &self.latest_height()) {
+            Ok(latest_consensus_state) => latest_consensus_state,
+            Err(_) => return Status::Expired,
+        };
+
+        match ctx.host_timestamp() {
+            Ok(now) => elapsed_status(self, latest_consensus_state.timestamp(), now),
+            Err(_) => Status::Unknown,
+        }
+    }
+
+    /// XXX: temporary solution until we get rid of `ClientReader`
+    #[cfg(feature = "val_exec_ctx")]
+    fn new_status(&self, ctx: &dyn ValidationContext, client_id: &ClientId) -> Status {
+        if self.is_frozen() {
+            return Status::Frozen;
+        }
+
+        let latest_consensus_state = match ctx.consensus_state(client_id, &self.latest_height()) {
+            Ok(latest_consensus_state) => latest_consensus_state,
+            Err(_) => return Status::Expired,
+        };
+
+        match ctx.host_timestamp() {
+            Ok(now) => elapsed_status(self, latest_consensus_state.timestamp(), now),
+            Err(_) => Status::Unknown,
+        }
+    }
+
+    /// Returns whether the client may be recovered with a substitute client while it has the
+    /// inactive `status`. By default, any frozen or expired client may be recovered.
+    fn allows_recovery(&self, status: Status) -> bool {
+        matches!(status, Status::Frozen | Status::Expired)
+    }
+
+    /// Returns whether the client may be upgraded while it has the given `status`. By default,
+    /// only an `Active` client may be upgraded.
+    fn allows_upgrade(&self, status: Status) -> bool {
+        status.is_active()
+    }
+
     /// Helper function to verify the upgrade client procedure.
     /// Resets all fields except the blockchain-specific ones,
     /// and updates the given fields.
@@ -112,125 +162,188 @@ pub trait ClientState:
         misbehaviour: Any,
     ) -> Result<Box<dyn ClientState>, ContextError>;
 
-    fn verify_upgrade_and_update_state(
+    /// Verifies a client message, i.e. a header or a misbehaviour submitted to the client.
+    ///
+    /// Client messages are submitted through `MsgUpdateClient`, and the client decides on its own
+    /// whether a message is a header or evidence of misbehaviour. By default, client messages
+    /// are verified along with the update of the client, in `update_state` and
+    /// `update_state_on_misbehaviour`, so this is a no-op.
+    fn verify_client_message(
         &self,
-        consensus_state: Any,
-        proof_upgrade_client: MerkleProof,
-        proof_upgrade_consensus_state: MerkleProof,
-    ) -> Result<UpdatedState, ClientError>;
+        _ctx: &dyn ClientReader,
+        _client_id: &ClientId,
+        _client_message: Any,
+    ) -> Result<(), ClientError> {
+        Ok(())
+    }
 
-    /// Verification functions as specified in:
-    /// <https://github.com/cosmos/ibc/tree/master/spec/core/ics-002-client-semantics>
+    /// XXX: temporary solution until we get rid of `ClientReader`
+    #[cfg(feature = "val_exec_ctx")]
+    fn new_verify_client_message(
+        &self,
+        _ctx: &dyn ValidationContext,
+        _client_id: &ClientId,
+        _client_message: Any,
+    ) -> Result<(), ClientError> {
+        Ok(())
+    }
+
+    /// Checks whether a verified client message is evidence of misbehaviour.
     ///
-    /// Verify a `proof` that the consensus state of a given client (at height `consensus_height`)
-    /// matches the input `consensus_state`. The parameter `counterparty_height` represent the
-    /// height of the counterparty chain that this proof assumes (i.e., the height at which this
-    /// proof was computed).
-    #[allow(clippy::too_many_arguments)]
-    fn verify_client_consensus_state(
+    /// By default, a client message is a misbehaviour if its type URL is registered as such in the
+    /// client type registry of the host.
+    fn check_for_misbehaviour(
         &self,
-        proof_height: Height,
-        counterparty_prefix: &CommitmentPrefix,
-        proof: &CommitmentProofBytes,
-        root: &CommitmentRoot,
-        counterparty_client_id: &ClientId,
-        consensus_height: Height,
-        expected_consensus_state: &dyn ConsensusState,
-    ) -> Result<(), ClientError>;
+        ctx: &dyn ClientReader,
+        _client_id: &ClientId,
+        client_message: Any,
+    ) -> Result<bool, ClientError> {
+        Ok(ctx
+            .client_type_registry()
+            .is_misbehaviour(&client_message.type_url))
+    }
 
-    /// Verify a `proof` that a connection state matches that of the input `connection_end`.
-    #[allow(clippy::too_many_arguments)]
-    fn verify_connection_state(
+    /// XXX: temporary solution until we get rid of `ClientReader`
+    #[cfg(feature = "val_exec_ctx")]
+    fn new_check_for_misbehaviour(
         &self,
-        proof_height: Height,
-        counterparty_prefix: &CommitmentPrefix,
-        proof: &CommitmentProofBytes,
-        root: &CommitmentRoot,
-        counterparty_connection_id: &ConnectionId,
-        expected_counterparty_connection_end: &ConnectionEnd,
-    ) -> Result<(), ClientError>;
+        ctx: &dyn ValidationContext,
+        _client_id: &ClientId,
+        client_message: Any,
+    ) -> Result<bool, ClientError> {
+        Ok(ctx
+            .client_type_registry()
+            .is_misbehaviour(&client_message.type_url))
+    }
 
-    /// Verify a `proof` that a channel state matches that of the input `channel_end`.
-    #[allow(clippy::too_many_arguments)]
-    fn verify_channel_state(
+    /// Returns the states the client should be updated to, given a verified header.
+    fn update_state(
         &self,
-        proof_height: Height,
-        counterparty_prefix: &CommitmentPrefix,
-        proof: &CommitmentProofBytes,
-        root: &CommitmentRoot,
-        counterparty_port_id: &PortId,
-        counterparty_channel_id: &ChannelId,
-        expected_counterparty_channel_end: &ChannelEnd,
-    ) -> Result<(), ClientError>;
+        ctx: &dyn ClientReader,
+        client_id: &ClientId,
+        client_message: Any,
+    ) -> Result<UpdatedState, ClientError> {
+        self.check_header_and_update_state(ctx, client_id.clone(), client_message)
+    }
 
-    /// Verify the client state for this chain that it is stored on the counterparty chain.
-    #[allow(clippy::too_many_arguments)]
-    fn verify_client_full_state(
+    /// XXX: temporary solution until we get rid of `ClientReader`
+    #[cfg(feature = "val_exec_ctx")]
+    fn new_update_state(
         &self,
-        proof_height: Height,
-        counterparty_prefix: &CommitmentPrefix,
-        proof: &CommitmentProofBytes,
-        root: &CommitmentRoot,
+        ctx: &dyn ValidationContext,
         client_id: &ClientId,
-        expected_client_state: Any,
-    ) -> Result<(), ClientError>;
+        client_message: Any,
+    ) -> Result<UpdatedState, ClientError> {
+        self.new_check_header_and_update_state(ctx, client_id.clone(), client_message)
+    }
 
-    /// Verify a `proof` that a packet has been commited.
-    #[allow(clippy::too_many_arguments)]
-    fn verify_packet_data(
+    /// Returns the frozen client state, given a verified misbehaviour.
+    fn update_state_on_misbehaviour(
         &self,
+        ctx: &dyn ClientReader,
+        client_id: &ClientId,
+        client_message: Any,
+    ) -> Result<Box<dyn ClientState>, ClientError> {
+        self.check_misbehaviour_and_update_state(ctx, client_id.clone(), client_message)
+    }
+
+    /// XXX: temporary solution until we get rid of `ClientReader`
+    #[cfg(feature = "val_exec_ctx")]
+    fn new_update_state_on_misbehaviour(
+        &self,
+        ctx: &dyn ValidationContext,
+        client_id: &ClientId,
+        client_message: Any,
+    ) -> Result<Box<dyn ClientState>, ContextError> {
+        self.new_check_misbehaviour_and_update_state(ctx, client_id.clone(), client_message)
+    }
+
+    /// Verify the upgraded client and consensus states against the `root` of this client's
+    /// latest consensus state, and return the states the client should be upgraded to.
+    ///
+    /// Note that no proof height is given, as an upgrade is expected to be proven at the last
+    /// height committed by the current revision. Clients are responsible for encoding that height
+    /// in the proof verification process, so that a plan which is later cancelled or modified by
+    /// the counterparty cannot trigger a premature upgrade.
+    fn verify_upgrade_and_update_state(
+        &self,
+        upgraded_client_state: Any,
+        upgraded_consensus_state: Any,
+        proof_upgrade_client: MerkleProof,
+        proof_upgrade_consensus_state: MerkleProof,
+        root: &CommitmentRoot,
+    ) -> Result<UpdatedState, ClientError>;
+
+    /// Check that the `substitute_client_state` may take the place of this (frozen or expired)
+    /// client, and return the client state this client should be recovered to.
+    ///
+    /// Both client states must match on all their fields, except for the latest height and the
+    /// frozen height. The recovered client state is unfrozen, and has the latest height of the
+    /// substitute.
+    fn check_substitute_and_update_state(
+        &self,
+        substitute_client_state: &dyn ClientState,
+    ) -> Result<Box<dyn ClientState>, ClientError>;
+
+    /// Verify that the client is at a sufficient height and unfrozen at the height of a proof,
+    /// before verifying that proof against the consensus state of the client at that height.
+    fn validate_proof_height(&self, proof_height: Height) -> Result<(), ClientError>;
+
+    /// Verification functions as specified in:
+    /// <https://github.com/cosmos/ibc/tree/master/spec/core/ics-002-client-semantics>
+    ///
+    /// Verify a `proof` that `value` is stored at `path` on the counterparty chain, under
+    /// `prefix`, against the commitment `root` of one of the client's consensus states.
+    ///
+    /// The handlers build the path and the expected value, so that a client implements proof
+    /// checking once for all paths. The context gives access to the host store, for clients
+    /// which track the host itself.
+    ///
+    /// Clients whose state changes with each verified proof, such as solo machines which sign
+    /// each proof at a new sequence, update themselves here. The handlers store the client state
+    /// along with the other effects of the message, once all of its proofs were verified.
+    fn verify_membership(
+        &mut self,
         ctx: &dyn ChannelReader,
-        height: Height,
-        connection_end: &ConnectionEnd,
+        prefix: &CommitmentPrefix,
         proof: &CommitmentProofBytes,
         root: &CommitmentRoot,
-        port_id: &PortId,
-        channel_id: &ChannelId,
-        sequence: Sequence,
-        commitment: PacketCommitment,
+        path: Path,
+        value: Vec<u8>,
     ) -> Result<(), ClientError>;
 
-    /// Verify a `proof` that a packet has been commited.
-    #[allow(clippy::too_many_arguments)]
-    fn verify_packet_acknowledgement(
-        &self,
+    /// Verify a `proof` that no value is stored at `path` on the counterparty chain, under
+    /// `prefix`, against the commitment `root` of one of the client's consensus states.
+    fn verify_non_membership(
+        &mut self,
         ctx: &dyn ChannelReader,
-        height: Height,
-        connection_end: &ConnectionEnd,
+        prefix: &CommitmentPrefix,
         proof: &CommitmentProofBytes,
         root: &CommitmentRoot,
-        port_id: &PortId,
-        channel_id: &ChannelId,
-        sequence: Sequence,
-        ack: AcknowledgementCommitment,
+        path: Path,
     ) -> Result<(), ClientError>;
 
-    /// Verify a `proof` that of the next_seq_received.
-    #[allow(clippy::too_many_arguments)]
-    fn verify_next_sequence_recv(
-        &self,
-        ctx: &dyn ChannelReader,
-        height: Height,
-        connection_end: &ConnectionEnd,
+    /// XXX: temporary solution until we get rid of `ChannelReader`
+    #[cfg(feature = "val_exec_ctx")]
+    fn new_verify_membership(
+        &mut self,
+        ctx: &dyn ValidationContext,
+        prefix: &CommitmentPrefix,
         proof: &CommitmentProofBytes,
         root: &CommitmentRoot,
-        port_id: &PortId,
-        channel_id: &ChannelId,
-        sequence: Sequence,
+        path: Path,
+        value: Vec<u8>,
     ) -> Result<(), ClientError>;
 
-    /// Verify a `proof` that a packet has not been received.
-    #[allow(clippy::too_many_arguments)]
-    fn verify_packet_receipt_absence(
-        &self,
-        ctx: &dyn ChannelReader,
-        height: Height,
-        connection_end: &ConnectionEnd,
+    /// XXX: temporary solution until we get rid of `ChannelReader`
+    #[cfg(feature = "val_exec_ctx")]
+    fn new_verify_non_membership(
+        &mut self,
+        ctx: &dyn ValidationContext,
+        prefix: &CommitmentPrefix,
         proof: &CommitmentProofBytes,
         root: &CommitmentRoot,
-        port_id: &PortId,
-        channel_id: &ChannelId,
-        sequence: Sequence,
+        path: Path,
     ) -> Result<(), ClientError>;
 }
 
@@ -260,6 +373,61 @@ pub fn downcast_client_state<CS: ClientState>(h: &dyn ClientState) -> Option<&CS
 
 pub trait UpgradeOptions: AsAny {}
 
+/// The status of a client, as defined in ICS-02.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// The client may be updated, and have proofs verified against it.
+    Active,
+    /// The client is frozen, e.g. following a proof of misbehaviour.
+    Frozen,
+    /// The client's latest consensus state is older than its trusting period.
+    Expired,
+    /// The status of the client cannot be determined.
+    Unknown,
+}
+
+impl Status {
+    pub fn is_active(&self) -> bool {
+        *self == Status::Active
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        *self == Status::Frozen
+    }
+
+    pub fn is_expired(&self) -> bool {
+        *self == Status::Expired
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Status::Active => write!(f, "Active"),
+            Status::Frozen => write!(f, "Frozen"),
+            Status::Expired => write!(f, "Expired"),
+            Status::Unknown => write!(f, "Unknown"),
+        }
+    }
+}
+
+/// Computes the status of a client which is not frozen, from the timestamp of its latest
+/// consensus state and the current host timestamp.
+fn elapsed_status<CS: ClientState + ?Sized>(
+    client_state: &CS,
+    latest_timestamp: Timestamp,
+    now: Timestamp,
+) -> Status {
+    match now.duration_since(&latest_timestamp) {
+        Some(elapsed) if client_state.expired(elapsed) => Status::Expired,
+        Some(_) => Status::Active,
+        // The latest consensus state may be ahead of the host, within the allowed clock drift
+        None if latest_timestamp.after(&now) => Status::Active,
+        None => Status::Unknown,
+    }
+}
+
 pub struct UpdatedState {
     pub client_state: Box<dyn ClientState>,
     pub consensus_state: Box<dyn ConsensusState>,
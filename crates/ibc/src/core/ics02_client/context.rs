@@ -3,6 +3,7 @@
 //! "ADR 003: IBC protocol implementation" for more details.
 
 use alloc::boxed::Box;
+use alloc::vec::Vec;
 
 use ibc_proto::google::protobuf::Any;
 
@@ -10,8 +11,11 @@ use crate::core::ics02_client::client_state::ClientState;
 use crate::core::ics02_client::client_type::ClientType;
 use crate::core::ics02_client::consensus_state::ConsensusState;
 use crate::core::ics02_client::error::ClientError;
-use crate::core::ics02_client::handler::ClientResult::{self, Create, Update, Upgrade};
+use crate::core::ics02_client::handler::ClientResult::{self, Create, Recover, Update, Upgrade};
+use crate::core::ics02_client::params::ClientParams;
+use crate::core::ics02_client::registry::ClientTypeRegistry;
 use crate::core::ics24_host::identifier::ClientId;
+use crate::signer::Signer;
 use crate::timestamp::Timestamp;
 use crate::Height;
 
@@ -23,8 +27,15 @@ pub trait ClientReader {
     /// Returns the ClientState for the given identifier `client_id`.
     fn client_state(&self, client_id: &ClientId) -> Result<Box<dyn ClientState>, ClientError>;
 
-    /// Tries to decode the given `client_state` into a concrete light client state.
-    fn decode_client_state(&self, client_state: Any) -> Result<Box<dyn ClientState>, ClientError>;
+    /// Returns the registry of the client types supported by the host.
+    fn client_type_registry(&self) -> &ClientTypeRegistry;
+
+    /// Tries to decode the given `client_state` into a concrete light client state, through the
+    /// client type registry of the host.
+    fn decode_client_state(&self, client_state: Any) -> Result<Box<dyn ClientState>, ClientError> {
+        self.client_type_registry()
+            .decode_client_state(client_state)
+    }
 
     /// Retrieve the consensus state for the given client ID at the specified
     /// height.
@@ -50,6 +61,9 @@ pub trait ClientReader {
         height: &Height,
     ) -> Result<Option<Box<dyn ConsensusState>>, ClientError>;
 
+    /// Returns the heights of all the consensus states stored for the client, in ascending order.
+    fn consensus_state_heights(&self, client_id: &ClientId) -> Result<Vec<Height>, ClientError>;
+
     /// Returns the current height of the local chain.
     fn host_height(&self) -> Result<Height, ClientError>;
 
@@ -71,6 +85,18 @@ pub trait ClientReader {
     /// Returns a natural number, counting how many clients have been created thus far.
     /// The value of this counter should increase only via method `ClientKeeper::increase_client_counter`.
     fn client_counter(&self) -> Result<u64, ClientError>;
+
+    /// Returns the parameters of the client submodule.
+    fn client_params(&self) -> Result<ClientParams, ClientError>;
+
+    /// Validates that `signer` is authorized to recover a frozen or expired client by
+    /// substituting it with a healthy one, e.g. that it is the address of the governance module.
+    /// Client recovery is disallowed by default.
+    fn validate_client_recovery_authority(&self, signer: &Signer) -> Result<(), ClientError> {
+        Err(ClientError::UnauthorizedClientRecovery {
+            signer: signer.clone(),
+        })
+    }
 }
 
 /// Defines the write-only part of ICS2 (client functions) context.
@@ -99,6 +125,11 @@ pub trait ClientKeeper {
                 Ok(())
             }
             Update(res) => {
+                for height in res.pruned_heights {
+                    self.delete_consensus_state(res.client_id.clone(), height)?;
+                    self.delete_update_time(res.client_id.clone(), height)?;
+                    self.delete_update_height(res.client_id.clone(), height)?;
+                }
                 self.store_client_state(res.client_id.clone(), res.client_state.clone())?;
                 self.store_consensus_state(
                     res.client_id.clone(),
@@ -124,11 +155,40 @@ pub trait ClientKeeper {
                     res.client_state.latest_height(),
                     res.consensus_state,
                 )?;
+                self.store_update_time(
+                    res.client_id.clone(),
+                    res.client_state.latest_height(),
+                    res.processed_time,
+                )?;
+                self.store_update_height(
+                    res.client_id,
+                    res.client_state.latest_height(),
+                    res.processed_height,
+                )?;
                 Ok(())
             }
             ClientResult::Misbehaviour(res) => {
                 self.store_client_state(res.client_id, res.client_state)
             }
+            Recover(res) => {
+                self.store_client_state(res.client_id.clone(), res.client_state.clone())?;
+                self.store_consensus_state(
+                    res.client_id.clone(),
+                    res.client_state.latest_height(),
+                    res.consensus_state,
+                )?;
+                self.store_update_time(
+                    res.client_id.clone(),
+                    res.client_state.latest_height(),
+                    res.processed_time,
+                )?;
+                self.store_update_height(
+                    res.client_id,
+                    res.client_state.latest_height(),
+                    res.processed_height,
+                )?;
+                Ok(())
+            }
         }
     }
 
@@ -154,14 +214,26 @@ pub trait ClientKeeper {
         consensus_state: Box<dyn ConsensusState>,
     ) -> Result<(), ClientError>;
 
+    /// Called upon successful client update, to prune the consensus states of the client which
+    /// have expired.
+    fn delete_consensus_state(
+        &mut self,
+        client_id: ClientId,
+        height: Height,
+    ) -> Result<(), ClientError>;
+
     /// Called upon client creation.
     /// Increases the counter which keeps track of how many clients have been created.
     /// Should never fail.
     fn increase_client_counter(&mut self);
 
-    /// Called upon successful client update.
+    /// Stores the parameters of the client submodule, e.g. upon a governance proposal.
+    fn store_client_params(&mut self, params: ClientParams) -> Result<(), ClientError>;
+
+    /// Called upon successful client creation, update and upgrade.
     /// Implementations are expected to use this to record the specified time as the time at which
-    /// this update (or header) was processed.
+    /// the consensus state at `height` was processed, next to that consensus state, e.g. under
+    /// `ClientUpdateTimePath`.
     fn store_update_time(
         &mut self,
         client_id: ClientId,
@@ -169,13 +241,32 @@ pub trait ClientKeeper {
         timestamp: Timestamp,
     ) -> Result<(), ClientError>;
 
-    /// Called upon successful client update.
+    /// Called upon successful client creation, update and upgrade.
     /// Implementations are expected to use this to record the specified height as the height at
-    /// at which this update (or header) was processed.
+    /// which the consensus state at `height` was processed, next to that consensus state, e.g.
+    /// under `ClientUpdateHeightPath`.
     fn store_update_height(
         &mut self,
         client_id: ClientId,
         height: Height,
         host_height: Height,
     ) -> Result<(), ClientError>;
+
+    /// Called upon successful client update, along with `delete_consensus_state`.
+    /// Implementations are expected to delete the time recorded by `store_update_time` for the
+    /// pruned consensus state.
+    fn delete_update_time(
+        &mut self,
+        client_id: ClientId,
+        height: Height,
+    ) -> Result<(), ClientError>;
+
+    /// Called upon successful client update, along with `delete_consensus_state`.
+    /// Implementations are expected to delete the height recorded by `store_update_height` for
+    /// the pruned consensus state.
+    fn delete_update_height(
+        &mut self,
+        client_id: ClientId,
+        height: Height,
+    ) -> Result<(), ClientError>;
 }
@@ -3,11 +3,12 @@ use crate::prelude::*;
 use displaydoc::Display;
 use ibc_proto::protobuf::Error as TendermintProtoError;
 
+use crate::core::ics02_client::client_state::Status;
 use crate::core::ics02_client::client_type::ClientType;
 use crate::core::ics23_commitment::error::CommitmentError;
 use crate::core::ics24_host::error::ValidationError;
 use crate::core::ics24_host::identifier::ClientId;
-use crate::signer::SignerError;
+use crate::signer::{Signer, SignerError};
 use crate::timestamp::Timestamp;
 use crate::Height;
 
@@ -21,8 +22,10 @@ pub enum ClientError {
     },
     /// client not found: `{client_id}`
     ClientNotFound { client_id: ClientId },
-    /// client is frozen: `{client_id}`
-    ClientFrozen { client_id: ClientId },
+    /// client already exists: `{client_id}`
+    ClientAlreadyExists { client_id: ClientId },
+    /// client `{client_id}` is not active, status: `{status}`
+    ClientNotActive { client_id: ClientId, status: Status },
     /// consensus state not found at: `{client_id}` at height `{height}`
     ConsensusStateNotFound { client_id: ClientId, height: Height },
     /// implementation specific error
@@ -43,6 +46,14 @@ pub enum ClientError {
     UnknownHeaderType { header_type: String },
     /// unknown misbehaviour type: `{misbehaviour_type}`
     UnknownMisbehaviourType { misbehaviour_type: String },
+    /// type url `{type_url}` is already registered
+    TypeUrlAlreadyRegistered { type_url: String },
+    /// type url `{type_url}` is registered for client type `{registered}`, not `{client_type}`
+    MismatchedTypeUrl {
+        type_url: String,
+        registered: ClientType,
+        client_type: ClientType,
+    },
     /// missing raw client state
     MissingRawClientState,
     /// missing raw client consensus state
@@ -106,6 +117,28 @@ pub enum ClientError {
     Ics23Verification(CommitmentError),
     /// misbehaviour handling failed with reason: `{reason}`
     MisbehaviourHandlingFailure { reason: String },
+    /// signer `{signer}` is not authorized to recover clients
+    UnauthorizedClientRecovery { signer: Signer },
+    /// subject client `{client_id}` is active and cannot be recovered
+    ActiveSubjectClient { client_id: ClientId },
+    /// subject client `{client_id}` does not allow recovery while its status is `{status}`
+    RecoveryNotAllowed { client_id: ClientId, status: Status },
+    /// subject client type `{subject_client_type}` does not match substitute client type `{substitute_client_type}`
+    MismatchedRecoveryClientTypes {
+        subject_client_type: ClientType,
+        substitute_client_type: ClientType,
+    },
+    /// substitute client height `{substitute_height}` must be greater than subject client height `{subject_height}`
+    LowSubstituteClientHeight {
+        substitute_height: Height,
+        subject_height: Height,
+    },
+    /// substitute client state does not match the subject client state on all fields but the latest and frozen heights
+    MismatchedSubstituteClientState,
+    /// client type `{client_type}` is not allowed by the client parameters
+    ClientTypeNotAllowed { client_type: ClientType },
+    /// invalid client parameters: `{reason}`
+    InvalidClientParams { reason: String },
     /// client specific error: `{description}`
     ClientSpecific { description: String },
     /// other error: `{description}`
@@ -14,6 +14,10 @@ use crate::prelude::*;
 /// The content of the `key` field for the attribute containing the client identifier.
 pub const CLIENT_ID_ATTRIBUTE_KEY: &str = "client_id";
 
+/// The content of the `key` field for the attribute containing the identifier of the recovered
+/// client.
+pub const SUBJECT_CLIENT_ID_ATTRIBUTE_KEY: &str = "subject_client_id";
+
 /// The content of the `key` field for the attribute containing the client type.
 pub const CLIENT_TYPE_ATTRIBUTE_KEY: &str = "client_type";
 
@@ -49,6 +53,34 @@ impl From<ClientIdAttribute> for abci::EventAttribute {
     }
 }
 
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, From, PartialEq, Eq)]
+struct SubjectClientIdAttribute {
+    subject_client_id: ClientId,
+}
+
+impl From<SubjectClientIdAttribute> for abci::EventAttribute {
+    fn from(attr: SubjectClientIdAttribute) -> Self {
+        (
+            SUBJECT_CLIENT_ID_ATTRIBUTE_KEY,
+            attr.subject_client_id.as_str(),
+        )
+            .into()
+    }
+}
+
 #[cfg_attr(
     feature = "parity-scale-codec",
     derive(
@@ -390,3 +422,50 @@ impl From<UpgradeClient> for abci::Event {
         }
     }
 }
+
+/// Signals the recovery of a frozen or expired on-chain client (IBC Client) by substituting it
+/// with a healthy one.
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecoverClient {
+    subject_client_id: SubjectClientIdAttribute,
+    client_type: ClientTypeAttribute,
+}
+
+impl RecoverClient {
+    pub fn new(subject_client_id: ClientId, client_type: ClientType) -> Self {
+        Self {
+            subject_client_id: SubjectClientIdAttribute::from(subject_client_id),
+            client_type: ClientTypeAttribute::from(client_type),
+        }
+    }
+
+    pub fn subject_client_id(&self) -> &ClientId {
+        &self.subject_client_id.subject_client_id
+    }
+
+    pub fn client_type(&self) -> &ClientType {
+        &self.client_type.client_type
+    }
+}
+
+impl From<RecoverClient> for abci::Event {
+    fn from(r: RecoverClient) -> Self {
+        Self {
+            kind: IbcEventType::RecoverClient.as_str().to_owned(),
+            attributes: vec![r.subject_client_id.into(), r.client_type.into()],
+        }
+    }
+}
@@ -7,6 +7,7 @@ use crate::handler::HandlerOutput;
 
 pub mod create_client;
 pub mod misbehaviour;
+pub mod recover_client;
 pub mod update_client;
 pub mod upgrade_client;
 
@@ -16,6 +17,7 @@ pub enum ClientResult {
     Update(update_client::UpdateClientResult),
     Upgrade(upgrade_client::UpgradeClientResult),
     Misbehaviour(misbehaviour::MisbehaviourResult),
+    Recover(recover_client::RecoverClientResult),
 }
 
 /// General entry point for processing any message related to ICS2 (client functions) protocols.
@@ -31,5 +33,6 @@ where
         ClientMsg::UpdateClient(msg) => update_client::process(ctx, msg),
         ClientMsg::UpgradeClient(msg) => upgrade_client::process(ctx, msg),
         ClientMsg::Misbehaviour(msg) => misbehaviour::process(ctx, msg),
+        ClientMsg::RecoverClient(msg) => recover_client::process(ctx, msg),
     }
 }
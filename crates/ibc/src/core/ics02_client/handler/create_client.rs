@@ -10,12 +10,17 @@ use crate::core::ics24_host::path::ClientConsensusStatePath;
 use crate::core::ics24_host::path::ClientStatePath;
 #[cfg(feature = "val_exec_ctx")]
 use crate::core::ics24_host::path::ClientTypePath;
+#[cfg(feature = "val_exec_ctx")]
+use crate::core::ics24_host::path::{ClientConnectionsPath, ConnectionsPath};
+#[cfg(feature = "val_exec_ctx")]
+use crate::core::ics24_host::path::{ClientUpdateHeightPath, ClientUpdateTimePath};
 
 #[cfg(feature = "val_exec_ctx")]
 use crate::core::ExecutionContext;
 #[cfg(feature = "val_exec_ctx")]
 use crate::core::ValidationContext;
 
+use crate::clients::ics09_localhost as localhost;
 use crate::core::ics02_client::client_state::ClientState;
 use crate::core::ics02_client::client_type::ClientType;
 use crate::core::ics02_client::consensus_state::ConsensusState;
@@ -48,7 +53,7 @@ where
 {
     let MsgCreateClient {
         client_state,
-        consensus_state: _,
+        consensus_state,
         signer: _,
     } = msg;
 
@@ -59,13 +64,18 @@ where
 
     let client_type = client_state.client_type();
 
-    let _client_id = ClientId::new(client_type, id_counter).map_err(|e| {
-        ClientError::ClientIdentifierConstructor {
-            client_type: client_state.client_type(),
-            counter: id_counter,
-            validation_error: e,
-        }
-    })?;
+    if !ctx.client_params()?.is_allowed_client(&client_type) {
+        return Err(ClientError::ClientTypeNotAllowed { client_type }.into());
+    }
+
+    ctx.client_type_registry()
+        .check_client_type(&client_type, &consensus_state.type_url)?;
+
+    let client_id = client_id(client_type, id_counter)?;
+
+    if client_id == localhost::client_id() && ctx.client_state(&client_id).is_ok() {
+        return Err(ClientError::ClientAlreadyExists { client_id }.into());
+    }
 
     Ok(())
 }
@@ -88,13 +98,7 @@ where
 
     let client_type = client_state.client_type();
 
-    let client_id = ClientId::new(client_type.clone(), id_counter).map_err(|e| {
-        ContextError::from(ClientError::ClientIdentifierConstructor {
-            client_type: client_state.client_type(),
-            counter: id_counter,
-            validation_error: e,
-        })
-    })?;
+    let client_id = client_id(client_type.clone(), id_counter)?;
     let consensus_state = client_state.initialise(consensus_state)?;
 
     ctx.store_client_type(ClientTypePath(client_id.clone()), client_type.clone())?;
@@ -105,16 +109,27 @@ where
     )?;
     ctx.increase_client_counter();
     ctx.store_update_time(
-        client_id.clone(),
-        client_state.latest_height(),
+        ClientUpdateTimePath::new(client_id.clone(), client_state.latest_height()),
         ctx.host_timestamp()?,
     )?;
     ctx.store_update_height(
-        client_id.clone(),
-        client_state.latest_height(),
+        ClientUpdateHeightPath::new(client_id.clone(), client_state.latest_height()),
         ctx.host_height()?,
     )?;
 
+    // The localhost client comes with its sentinel connection, so that no connection handshake
+    // is needed to open channels over it
+    if client_id == localhost::client_id() {
+        ctx.store_connection(
+            ConnectionsPath(localhost::connection_id()),
+            localhost::connection_end(ctx.commitment_prefix()),
+        )?;
+        ctx.store_connection_to_client(
+            ClientConnectionsPath(client_id.clone()),
+            localhost::connection_id(),
+        )?;
+    }
+
     ctx.emit_ibc_event(IbcEvent::CreateClient(CreateClient::new(
         client_id.clone(),
         client_type,
@@ -147,13 +162,18 @@ pub(crate) fn process(
 
     let client_type = client_state.client_type();
 
-    let client_id = ClientId::new(client_type.clone(), id_counter).map_err(|e| {
-        ClientError::ClientIdentifierConstructor {
-            client_type: client_state.client_type(),
-            counter: id_counter,
-            validation_error: e,
-        }
-    })?;
+    if !ctx.client_params()?.is_allowed_client(&client_type) {
+        return Err(ClientError::ClientTypeNotAllowed { client_type });
+    }
+
+    ctx.client_type_registry()
+        .check_client_type(&client_type, &consensus_state.type_url)?;
+
+    let client_id = client_id(client_type.clone(), id_counter)?;
+
+    if client_id == localhost::client_id() && ctx.client_state(&client_id).is_ok() {
+        return Err(ClientError::ClientAlreadyExists { client_id });
+    }
 
     let consensus_state = client_state.initialise(consensus_state)?;
 
@@ -181,6 +201,22 @@ pub(crate) fn process(
     Ok(output.with_result(result))
 }
 
+/// Returns the identifier of a new client of type `client_type`, built from the client counter.
+/// The localhost client is the exception, as it always has the sentinel identifier.
+fn client_id(client_type: ClientType, id_counter: u64) -> Result<ClientId, ClientError> {
+    if client_type == localhost::client_type() {
+        return Ok(localhost::client_id());
+    }
+
+    ClientId::new(client_type.clone(), id_counter).map_err(|e| {
+        ClientError::ClientIdentifierConstructor {
+            client_type,
+            counter: id_counter,
+            validation_error: e,
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use crate::clients::ics07_tendermint::client_type as tm_client_type;
@@ -194,13 +230,16 @@ mod tests {
     };
     use crate::clients::ics07_tendermint::consensus_state::ConsensusState as TmConsensusState;
     use crate::clients::ics07_tendermint::header::test_util::get_dummy_tendermint_header;
+    use crate::core::ics02_client::error::ClientError;
     use crate::core::ics02_client::handler::{dispatch, ClientResult};
     use crate::core::ics02_client::msgs::create_client::MsgCreateClient;
     use crate::core::ics02_client::msgs::ClientMsg;
+    use crate::core::ics02_client::params::ClientParams;
     use crate::core::ics02_client::trust_threshold::TrustThreshold;
     use crate::core::ics23_commitment::specs::ProofSpecs;
     use crate::core::ics24_host::identifier::ClientId;
     use crate::handler::HandlerOutput;
+    use crate::host_functions::HostFunctionsManager;
     use crate::mock::client_state::{client_type as mock_client_type, MockClientState};
     use crate::mock::consensus_state::MockConsensusState;
     use crate::mock::context::MockContext;
@@ -249,6 +288,57 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_create_client_mismatched_consensus_state() {
+        let ctx = MockContext::default();
+        let height = Height::new(0, 42).unwrap();
+        let tm_header = get_dummy_tendermint_header();
+
+        // The consensus state type is registered for the Tendermint client type
+        let msg = MsgCreateClient::new(
+            MockClientState::new(MockHeader::new(height)).into(),
+            TmConsensusState::from(tm_header).into(),
+            get_dummy_account_id(),
+        );
+
+        let output = dispatch(&ctx, ClientMsg::CreateClient(msg));
+
+        assert!(matches!(output, Err(ClientError::MismatchedTypeUrl { .. })));
+    }
+
+    #[test]
+    fn test_create_client_not_allowed() {
+        let ctx = MockContext::default().with_client_params(ClientParams {
+            allowed_clients: vec![tm_client_type()],
+        });
+        let height = Height::new(0, 42).unwrap();
+
+        let msg = MsgCreateClient::new(
+            MockClientState::new(MockHeader::new(height)).into(),
+            MockConsensusState::new(MockHeader::new(height)).into(),
+            get_dummy_account_id(),
+        );
+
+        #[cfg(feature = "val_exec_ctx")]
+        {
+            use crate::core::ContextError;
+
+            assert!(matches!(
+                super::validate(&ctx, msg.clone()),
+                Err(ContextError::ClientError(
+                    ClientError::ClientTypeNotAllowed { .. }
+                ))
+            ));
+        }
+
+        let output = dispatch(&ctx, ClientMsg::CreateClient(msg));
+
+        assert!(matches!(
+            output,
+            Err(ClientError::ClientTypeNotAllowed { client_type }) if client_type == mock_client_type()
+        ));
+    }
+
     #[test]
     fn test_create_client_ok_multiple() {
         let existing_client_id = ClientId::default();
@@ -323,7 +413,7 @@ mod tests {
 
         let tm_header = get_dummy_tendermint_header();
 
-        let tm_client_state = TmClientState::new(
+        let tm_client_state = TmClientState::<HostFunctionsManager>::new(
             tm_header.chain_id.clone().into(),
             TrustThreshold::ONE_THIRD,
             Duration::from_secs(64000),
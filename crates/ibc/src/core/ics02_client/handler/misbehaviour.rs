@@ -38,10 +38,14 @@ where
     // Read client state from the host chain store.
     let client_state = ctx.client_state(&client_id)?;
 
-    if client_state.is_frozen() {
-        return Err(ClientError::ClientFrozen { client_id }.into());
+    let status = client_state.new_status(ctx, &client_id);
+    if !status.is_active() {
+        return Err(ClientError::ClientNotActive { client_id, status }.into());
     }
 
+    ctx.client_type_registry()
+        .check_client_type(&client_state.client_type(), &misbehaviour.type_url)?;
+
     let _ = client_state
         .new_check_misbehaviour_and_update_state(ctx, client_id.clone(), misbehaviour)
         .map_err(|e| ClientError::MisbehaviourHandlingFailure {
@@ -65,8 +69,9 @@ where
     // Read client state from the host chain store.
     let client_state = ctx.client_state(&client_id)?;
 
-    if client_state.is_frozen() {
-        return Err(ClientError::ClientFrozen { client_id }.into());
+    let status = client_state.new_status(ctx, &client_id);
+    if !status.is_active() {
+        return Err(ClientError::ClientNotActive { client_id, status }.into());
     }
 
     let client_state = client_state
@@ -98,10 +103,14 @@ pub(crate) fn process(
     // Read client state from the host chain store.
     let client_state = ctx.client_state(&client_id)?;
 
-    if client_state.is_frozen() {
-        return Err(ClientError::ClientFrozen { client_id });
+    let status = client_state.status(ctx, &client_id);
+    if !status.is_active() {
+        return Err(ClientError::ClientNotActive { client_id, status });
     }
 
+    ctx.client_type_registry()
+        .check_client_type(&client_state.client_type(), &misbehaviour.type_url)?;
+
     let client_state = client_state
         .check_misbehaviour_and_update_state(ctx, client_id.clone(), misbehaviour)
         .map_err(|e| ClientError::MisbehaviourHandlingFailure {
@@ -124,6 +133,7 @@ pub(crate) fn process(
 #[cfg(test)]
 mod tests {
     use core::str::FromStr;
+    use core::time::Duration;
     use test_log::test;
 
     use crate::clients::ics07_tendermint::client_type as tm_client_type;
@@ -393,4 +403,123 @@ mod tests {
         let output = dispatch(&ctx_a, ClientMsg::Misbehaviour(msg));
         ensure_misbehaviour_result(output, &client_id, &tm_client_type());
     }
+
+    /// Tests misbehaviour handling for the synthetic Tendermint client.
+    /// Misbehaviour evidence consists of headers at different heights, where the header at the
+    /// greater height does not have a later timestamp.
+    #[test]
+    fn test_misbehaviour_synthetic_tendermint_bft_time_violation() {
+        let client_id = ClientId::new(tm_client_type(), 0).unwrap();
+        let client_height = Height::new(1, 20).unwrap();
+        let chain_id_b = ChainId::new("mockgaiaB".to_string(), 1);
+
+        let ctx_a = MockContext::new(
+            ChainId::new("mockgaiaA".to_string(), 1),
+            HostType::Mock,
+            5,
+            Height::new(1, 1).unwrap(),
+        )
+        .with_client_parametrized_with_chain_id(
+            chain_id_b.clone(),
+            &client_id,
+            client_height,
+            Some(tm_client_type()),
+            Some(client_height),
+        );
+
+        let trusted_timestamp = ctx_a
+            .latest_consensus_states(&client_id, &client_height)
+            .timestamp();
+
+        // `header1` is at a greater height than `header2`, but has an earlier timestamp
+        let header1 = {
+            let mut tm_block = HostBlock::generate_tm_block(
+                chain_id_b.clone(),
+                22,
+                (trusted_timestamp + Duration::from_secs(1)).unwrap(),
+            );
+            tm_block.trusted_height = client_height;
+            tm_block
+        };
+        let header2 = {
+            let mut tm_block = HostBlock::generate_tm_block(
+                chain_id_b,
+                21,
+                (trusted_timestamp + Duration::from_secs(2)).unwrap(),
+            );
+            tm_block.trusted_height = client_height;
+            tm_block
+        };
+
+        let msg = MsgSubmitMisbehaviour {
+            client_id: client_id.clone(),
+            misbehaviour: TmMisbehaviour::new(client_id.clone(), header1.into(), header2.into())
+                .unwrap()
+                .into(),
+            signer: get_dummy_account_id(),
+        };
+
+        let output = dispatch(&ctx_a, ClientMsg::Misbehaviour(msg));
+        ensure_misbehaviour_result(output, &client_id, &tm_client_type());
+    }
+
+    /// Headers at different heights, with monotonically increasing timestamps, are not evidence of
+    /// misbehaviour.
+    #[test]
+    fn test_misbehaviour_synthetic_tendermint_monotonic_time() {
+        let client_id = ClientId::new(tm_client_type(), 0).unwrap();
+        let client_height = Height::new(1, 20).unwrap();
+        let chain_id_b = ChainId::new("mockgaiaB".to_string(), 1);
+
+        let ctx_a = MockContext::new(
+            ChainId::new("mockgaiaA".to_string(), 1),
+            HostType::Mock,
+            5,
+            Height::new(1, 1).unwrap(),
+        )
+        .with_client_parametrized_with_chain_id(
+            chain_id_b.clone(),
+            &client_id,
+            client_height,
+            Some(tm_client_type()),
+            Some(client_height),
+        );
+
+        let trusted_timestamp = ctx_a
+            .latest_consensus_states(&client_id, &client_height)
+            .timestamp();
+
+        let header1 = {
+            let mut tm_block = HostBlock::generate_tm_block(
+                chain_id_b.clone(),
+                22,
+                (trusted_timestamp + Duration::from_secs(2)).unwrap(),
+            );
+            tm_block.trusted_height = client_height;
+            tm_block
+        };
+        let header2 = {
+            let mut tm_block = HostBlock::generate_tm_block(
+                chain_id_b,
+                21,
+                (trusted_timestamp + Duration::from_secs(1)).unwrap(),
+            );
+            tm_block.trusted_height = client_height;
+            tm_block
+        };
+
+        let msg = MsgSubmitMisbehaviour {
+            client_id: client_id.clone(),
+            misbehaviour: TmMisbehaviour::new(client_id, header1.into(), header2.into())
+                .unwrap()
+                .into(),
+            signer: get_dummy_account_id(),
+        };
+
+        let output = dispatch(&ctx_a, ClientMsg::Misbehaviour(msg));
+        assert!(matches!(
+            output,
+            Err(ClientError::MisbehaviourHandlingFailure { .. })
+        ));
+    }
 }
@@ -0,0 +1,489 @@
+//! Protocol logic specific to processing ICS2 messages of type `MsgRecoverClient`.
+
+use crate::prelude::*;
+
+use crate::core::ics02_client::client_state::ClientState;
+use crate::core::ics02_client::consensus_state::ConsensusState;
+use crate::core::ics02_client::context::ClientReader;
+use crate::core::ics02_client::error::ClientError;
+use crate::core::ics02_client::events::RecoverClient;
+use crate::core::ics02_client::handler::ClientResult;
+use crate::core::ics02_client::height::Height;
+use crate::core::ics02_client::msgs::recover_client::MsgRecoverClient;
+use crate::core::ics24_host::identifier::ClientId;
+use crate::events::IbcEvent;
+use crate::handler::{HandlerOutput, HandlerResult};
+use crate::timestamp::Timestamp;
+
+#[cfg(feature = "val_exec_ctx")]
+use crate::core::context::ContextError;
+#[cfg(feature = "val_exec_ctx")]
+use crate::core::ics24_host::path::{
+    ClientConsensusStatePath, ClientStatePath, ClientUpdateHeightPath, ClientUpdateTimePath,
+};
+#[cfg(feature = "val_exec_ctx")]
+use crate::core::{ExecutionContext, ValidationContext};
+
+/// The result following the successful processing of a `MsgRecoverClient` message.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecoverClientResult {
+    pub client_id: ClientId,
+    pub client_state: Box<dyn ClientState>,
+    pub consensus_state: Box<dyn ConsensusState>,
+    pub processed_time: Timestamp,
+    pub processed_height: Height,
+}
+
+#[cfg(feature = "val_exec_ctx")]
+pub(crate) fn validate<Ctx>(ctx: &Ctx, msg: MsgRecoverClient) -> Result<(), ContextError>
+where
+    Ctx: ValidationContext,
+{
+    let MsgRecoverClient {
+        subject_client_id,
+        substitute_client_id,
+        signer,
+    } = msg;
+
+    ctx.validate_client_recovery_authority(&signer)?;
+
+    // Only a frozen or expired client may be recovered, if it allows it.
+    let subject_client_state = ctx.client_state(&subject_client_id)?;
+    let status = subject_client_state.new_status(ctx, &subject_client_id);
+    if status.is_active() {
+        return Err(ClientError::ActiveSubjectClient {
+            client_id: subject_client_id,
+        }
+        .into());
+    }
+    if !subject_client_state.allows_recovery(status) {
+        return Err(ClientError::RecoveryNotAllowed {
+            client_id: subject_client_id,
+            status,
+        }
+        .into());
+    }
+
+    // The substitute client must be healthy.
+    let substitute_client_state = ctx.client_state(&substitute_client_id)?;
+    let status = substitute_client_state.new_status(ctx, &substitute_client_id);
+    if !status.is_active() {
+        return Err(ClientError::ClientNotActive {
+            client_id: substitute_client_id,
+            status,
+        }
+        .into());
+    }
+
+    let _ = check_substitute(
+        subject_client_state.as_ref(),
+        substitute_client_state.as_ref(),
+    )?;
+
+    Ok(())
+}
+
+#[cfg(feature = "val_exec_ctx")]
+pub(crate) fn execute<Ctx>(ctx: &mut Ctx, msg: MsgRecoverClient) -> Result<(), ContextError>
+where
+    Ctx: ExecutionContext,
+{
+    let MsgRecoverClient {
+        subject_client_id,
+        substitute_client_id,
+        signer: _,
+    } = msg;
+
+    let subject_client_state = ctx.client_state(&subject_client_id)?;
+    let substitute_client_state = ctx.client_state(&substitute_client_id)?;
+
+    let client_state = check_substitute(
+        subject_client_state.as_ref(),
+        substitute_client_state.as_ref(),
+    )?;
+
+    // The subject client resumes from the latest consensus state of the substitute.
+    let consensus_state = ctx.consensus_state(
+        &substitute_client_id,
+        &substitute_client_state.latest_height(),
+    )?;
+
+    ctx.store_client_state(
+        ClientStatePath(subject_client_id.clone()),
+        client_state.clone(),
+    )?;
+    ctx.store_consensus_state(
+        ClientConsensusStatePath::new(subject_client_id.clone(), client_state.latest_height()),
+        consensus_state,
+    )?;
+    ctx.store_update_time(
+        ClientUpdateTimePath::new(subject_client_id.clone(), client_state.latest_height()),
+        ctx.host_timestamp()?,
+    )?;
+    ctx.store_update_height(
+        ClientUpdateHeightPath::new(subject_client_id.clone(), client_state.latest_height()),
+        ctx.host_height()?,
+    )?;
+
+    ctx.emit_ibc_event(IbcEvent::RecoverClient(RecoverClient::new(
+        subject_client_id,
+        client_state.client_type(),
+    )));
+
+    Ok(())
+}
+
+pub(crate) fn process<Ctx: ClientReader>(
+    ctx: &Ctx,
+    msg: MsgRecoverClient,
+) -> HandlerResult<ClientResult, ClientError> {
+    let mut output = HandlerOutput::builder();
+
+    let MsgRecoverClient {
+        subject_client_id,
+        substitute_client_id,
+        signer,
+    } = msg;
+
+    ctx.validate_client_recovery_authority(&signer)?;
+
+    // Only a frozen or expired client may be recovered, if it allows it.
+    let subject_client_state = ctx.client_state(&subject_client_id)?;
+    let status = subject_client_state.status(ctx, &subject_client_id);
+    if status.is_active() {
+        return Err(ClientError::ActiveSubjectClient {
+            client_id: subject_client_id,
+        });
+    }
+    if !subject_client_state.allows_recovery(status) {
+        return Err(ClientError::RecoveryNotAllowed {
+            client_id: subject_client_id,
+            status,
+        });
+    }
+
+    // The substitute client must be healthy.
+    let substitute_client_state = ctx.client_state(&substitute_client_id)?;
+    let status = substitute_client_state.status(ctx, &substitute_client_id);
+    if !status.is_active() {
+        return Err(ClientError::ClientNotActive {
+            client_id: substitute_client_id,
+            status,
+        });
+    }
+
+    let client_state = check_substitute(
+        subject_client_state.as_ref(),
+        substitute_client_state.as_ref(),
+    )?;
+
+    // The subject client resumes from the latest consensus state of the substitute.
+    let consensus_state = ctx.consensus_state(
+        &substitute_client_id,
+        &substitute_client_state.latest_height(),
+    )?;
+
+    output.emit(IbcEvent::RecoverClient(RecoverClient::new(
+        subject_client_id.clone(),
+        client_state.client_type(),
+    )));
+
+    let result = ClientResult::Recover(RecoverClientResult {
+        client_id: subject_client_id,
+        client_state,
+        consensus_state,
+        processed_time: ctx.host_timestamp()?,
+        processed_height: ctx.host_height()?,
+    });
+
+    Ok(output.with_result(result))
+}
+
+/// Checks that the substitute client may take the place of the subject client, and returns the
+/// client state the subject client is recovered to.
+fn check_substitute(
+    subject_client_state: &dyn ClientState,
+    substitute_client_state: &dyn ClientState,
+) -> Result<Box<dyn ClientState>, ClientError> {
+    if subject_client_state.client_type() != substitute_client_state.client_type() {
+        return Err(ClientError::MismatchedRecoveryClientTypes {
+            subject_client_type: subject_client_state.client_type(),
+            substitute_client_type: substitute_client_state.client_type(),
+        });
+    }
+
+    if substitute_client_state.latest_height() <= subject_client_state.latest_height() {
+        return Err(ClientError::LowSubstituteClientHeight {
+            substitute_height: substitute_client_state.latest_height(),
+            subject_height: subject_client_state.latest_height(),
+        });
+    }
+
+    subject_client_state.check_substitute_and_update_state(substitute_client_state)
+}
+
+#[cfg(test)]
+mod tests {
+    use core::str::FromStr;
+    use test_log::test;
+
+    use crate::clients::ics07_tendermint::client_state::ClientState as TmClientState;
+    use crate::clients::ics07_tendermint::client_type as tm_client_type;
+    use crate::core::ics02_client::client_state::{downcast_client_state, ClientState, Status};
+    use crate::core::ics02_client::context::{ClientKeeper, ClientReader};
+    use crate::core::ics02_client::error::ClientError;
+    use crate::core::ics02_client::handler::dispatch;
+    use crate::core::ics02_client::handler::ClientResult::Recover;
+    use crate::core::ics02_client::msgs::recover_client::MsgRecoverClient;
+    use crate::core::ics02_client::msgs::ClientMsg;
+    use crate::core::ics24_host::identifier::ClientId;
+    use crate::events::IbcEvent;
+    use crate::handler::HandlerOutput;
+    use crate::mock::client_state::{client_type as mock_client_type, MockClientState};
+    use crate::mock::context::MockContext;
+    use crate::mock::header::MockHeader;
+    use crate::test_utils::get_dummy_account_id;
+    use crate::Height;
+    use crate::{downcast, prelude::*};
+
+    #[test]
+    fn test_recover_client_ok() {
+        let subject_client_id = ClientId::from_str("mockclient1").unwrap();
+        let substitute_client_id = ClientId::from_str("mockclient2").unwrap();
+        let subject_height = Height::new(0, 42).unwrap();
+
+        let mut ctx = MockContext::default()
+            .with_client(&subject_client_id, subject_height)
+            .with_client(&substitute_client_id, Height::new(0, 50).unwrap())
+            .with_client_recovery_authority(get_dummy_account_id());
+
+        let frozen_client_state = MockClientState::new(MockHeader::new(subject_height))
+            .with_frozen_height(subject_height);
+        ClientKeeper::store_client_state(
+            &mut ctx,
+            subject_client_id.clone(),
+            frozen_client_state.into_box(),
+        )
+        .unwrap();
+
+        let msg = MsgRecoverClient::new(
+            subject_client_id,
+            substitute_client_id,
+            get_dummy_account_id(),
+        );
+
+        #[cfg(feature = "val_exec_ctx")]
+        {
+            use crate::core::ValidationContext;
+
+            let mut ctx = ctx.clone();
+            super::validate(&ctx, msg.clone()).unwrap();
+            super::execute(&mut ctx, msg.clone()).unwrap();
+
+            let client_state =
+                ValidationContext::client_state(&ctx, &msg.subject_client_id).unwrap();
+            assert!(!client_state.is_frozen());
+            assert_eq!(client_state.latest_height(), Height::new(0, 50).unwrap());
+            assert!(ValidationContext::consensus_state(
+                &ctx,
+                &msg.subject_client_id,
+                &Height::new(0, 50).unwrap()
+            )
+            .is_ok());
+            assert!(matches!(
+                ctx.ibc_store.lock().events.last(),
+                Some(IbcEvent::RecoverClient(_))
+            ));
+        }
+
+        let output = dispatch(&ctx, ClientMsg::RecoverClient(msg.clone()));
+
+        match output {
+            Ok(HandlerOutput {
+                result,
+                mut events,
+                log: _,
+            }) => {
+                let event = downcast!(events.pop().unwrap() => IbcEvent::RecoverClient).unwrap();
+                assert_eq!(event.subject_client_id(), &msg.subject_client_id);
+                assert_eq!(event.client_type(), &mock_client_type());
+                assert!(events.is_empty());
+
+                match result {
+                    Recover(res) => {
+                        assert_eq!(res.client_id, msg.subject_client_id);
+                        assert!(!res.client_state.is_frozen());
+                        assert_eq!(
+                            res.client_state.latest_height(),
+                            Height::new(0, 50).unwrap()
+                        );
+                    }
+                    _ => panic!("Recover handler result has incorrect type"),
+                }
+            }
+            Err(err) => {
+                panic!("unexpected error: {}", err);
+            }
+        }
+    }
+
+    #[test]
+    fn test_recover_client_invalid() {
+        struct Test {
+            name: String,
+            ctx: MockContext,
+            msg: MsgRecoverClient,
+            want_err: fn(&ClientError) -> bool,
+        }
+
+        let subject_client_id = ClientId::from_str("mockclient1").unwrap();
+        let substitute_client_id = ClientId::from_str("mockclient2").unwrap();
+        let subject_height = Height::new(0, 42).unwrap();
+
+        let context = |substitute_height: Height, frozen: bool| {
+            let mut ctx = MockContext::default()
+                .with_client(&subject_client_id, subject_height)
+                .with_client(&substitute_client_id, substitute_height)
+                .with_client_recovery_authority(get_dummy_account_id());
+
+            if frozen {
+                let frozen_client_state = MockClientState::new(MockHeader::new(subject_height))
+                    .with_frozen_height(subject_height);
+                ClientKeeper::store_client_state(
+                    &mut ctx,
+                    subject_client_id.clone(),
+                    frozen_client_state.into_box(),
+                )
+                .unwrap();
+            }
+
+            ctx
+        };
+
+        let msg = MsgRecoverClient {
+            subject_client_id: subject_client_id.clone(),
+            substitute_client_id: substitute_client_id.clone(),
+            signer: get_dummy_account_id(),
+        };
+
+        let tests: Vec<Test> = vec![
+            Test {
+                name: "Signer is not the recovery authority".to_string(),
+                ctx: context(Height::new(0, 50).unwrap(), true),
+                msg: MsgRecoverClient {
+                    signer: "cosmos1wxeyh7zgn4tctjzs0vtqpc6p5cxq5t2muzl7ng"
+                        .parse()
+                        .unwrap(),
+                    ..msg.clone()
+                },
+                want_err: |e| matches!(e, ClientError::UnauthorizedClientRecovery { .. }),
+            },
+            Test {
+                name: "Subject client is active".to_string(),
+                ctx: context(Height::new(0, 50).unwrap(), false),
+                msg: msg.clone(),
+                want_err: |e| matches!(e, ClientError::ActiveSubjectClient { .. }),
+            },
+            Test {
+                name: "Substitute client is below the subject client".to_string(),
+                ctx: context(Height::new(0, 40).unwrap(), true),
+                msg,
+                want_err: |e| matches!(e, ClientError::LowSubstituteClientHeight { .. }),
+            },
+        ];
+
+        for test in tests {
+            #[cfg(feature = "val_exec_ctx")]
+            {
+                use crate::core::ContextError;
+
+                match super::validate(&test.ctx, test.msg.clone()) {
+                    Err(ContextError::ClientError(e)) => assert!(
+                        (test.want_err)(&e),
+                        "recover_client: unexpected validation error for test {}: {:?}",
+                        test.name,
+                        e
+                    ),
+                    res => panic!(
+                        "recover_client: unexpected validation result for test {}: {:?}",
+                        test.name, res
+                    ),
+                }
+            }
+
+            let res = dispatch(&test.ctx, ClientMsg::RecoverClient(test.msg));
+            match res {
+                Ok(_) => panic!("recover_client: test {} passed unexpectedly", test.name),
+                Err(e) => assert!(
+                    (test.want_err)(&e),
+                    "recover_client: unexpected error for test {}: {:?}",
+                    test.name,
+                    e
+                ),
+            }
+        }
+    }
+
+    #[test]
+    fn test_recover_client_not_allowed() {
+        let subject_height = Height::new(0, 42).unwrap();
+        let msg = MsgRecoverClient {
+            subject_client_id: ClientId::from_str("mockclient1").unwrap(),
+            substitute_client_id: ClientId::from_str("mockclient2").unwrap(),
+            signer: get_dummy_account_id(),
+        };
+
+        // The Tendermint subject client does not allow updates after misbehaviour
+        let mut ctx = MockContext::default()
+            .with_client_parametrized(
+                &msg.subject_client_id,
+                subject_height,
+                Some(tm_client_type()),
+                None,
+            )
+            .with_client_parametrized(
+                &msg.substitute_client_id,
+                Height::new(0, 50).unwrap(),
+                Some(tm_client_type()),
+                None,
+            )
+            .with_client_recovery_authority(get_dummy_account_id());
+
+        let subject_client_state =
+            ClientReader::client_state(&ctx, &msg.subject_client_id).unwrap();
+        let frozen_client_state =
+            downcast_client_state::<TmClientState>(subject_client_state.as_ref())
+                .unwrap()
+                .clone()
+                .with_frozen_height(subject_height);
+        ClientKeeper::store_client_state(
+            &mut ctx,
+            msg.subject_client_id.clone(),
+            frozen_client_state.into_box(),
+        )
+        .unwrap();
+
+        #[cfg(feature = "val_exec_ctx")]
+        {
+            use crate::core::ContextError;
+
+            assert!(matches!(
+                super::validate(&ctx, msg.clone()),
+                Err(ContextError::ClientError(ClientError::RecoveryNotAllowed {
+                    status: Status::Frozen,
+                    ..
+                }))
+            ));
+        }
+
+        let output = dispatch(&ctx, ClientMsg::RecoverClient(msg));
+
+        assert!(matches!(
+            output,
+            Err(ClientError::RecoveryNotAllowed {
+                status: Status::Frozen,
+                ..
+            })
+        ));
+    }
+}
@@ -1,14 +1,13 @@
 //! Protocol logic specific to processing ICS2 messages of type `MsgUpdateAnyClient`.
 
-use tracing::debug;
-
 use crate::prelude::*;
 
 use crate::core::ics02_client::client_state::{ClientState, UpdatedState};
 use crate::core::ics02_client::consensus_state::ConsensusState;
 use crate::core::ics02_client::context::ClientReader;
 use crate::core::ics02_client::error::ClientError;
-use crate::core::ics02_client::events::UpdateClient;
+use crate::core::ics02_client::events::{ClientMisbehaviour, UpdateClient};
+use crate::core::ics02_client::handler::misbehaviour::MisbehaviourResult;
 use crate::core::ics02_client::handler::ClientResult;
 use crate::core::ics02_client::height::Height;
 use crate::core::ics02_client::msgs::update_client::MsgUpdateClient;
@@ -20,7 +19,9 @@ use crate::timestamp::Timestamp;
 #[cfg(feature = "val_exec_ctx")]
 use crate::core::context::ContextError;
 #[cfg(feature = "val_exec_ctx")]
-use crate::core::ics24_host::path::{ClientConsensusStatePath, ClientStatePath};
+use crate::core::ics24_host::path::{
+    ClientConsensusStatePath, ClientStatePath, ClientUpdateHeightPath, ClientUpdateTimePath,
+};
 #[cfg(feature = "val_exec_ctx")]
 use crate::core::{ExecutionContext, ValidationContext};
 
@@ -32,6 +33,8 @@ pub struct UpdateClientResult {
     pub consensus_state: Box<dyn ConsensusState>,
     pub processed_time: Timestamp,
     pub processed_height: Height,
+    /// Heights of the consensus states of the client which expired, and are pruned by the update.
+    pub pruned_heights: Vec<Height>,
 }
 
 #[cfg(feature = "val_exec_ctx")]
@@ -49,42 +52,34 @@ where
     // Read client state from the host chain store.
     let client_state = ctx.client_state(&client_id)?;
 
-    if client_state.is_frozen() {
-        return Err(ClientError::ClientFrozen { client_id }.into());
+    let status = client_state.new_status(ctx, &client_id);
+    if !status.is_active() {
+        return Err(ClientError::ClientNotActive { client_id, status }.into());
     }
 
-    // Read consensus state from the host chain store.
-    let latest_consensus_state = ctx
-        .consensus_state(&client_id, &client_state.latest_height())
-        .map_err(|_| ClientError::ConsensusStateNotFound {
-            client_id: client_id.clone(),
-            height: client_state.latest_height(),
-        })?;
-
-    debug!("latest consensus state: {:?}", latest_consensus_state);
-
-    let now = ctx.host_timestamp()?;
-    let duration = now
-        .duration_since(&latest_consensus_state.timestamp())
-        .ok_or_else(|| ClientError::InvalidConsensusStateTimestamp {
-            time1: latest_consensus_state.timestamp(),
-            time2: now,
-        })?;
-
-    if client_state.expired(duration) {
-        return Err(ClientError::HeaderNotWithinTrustPeriod {
-            latest_time: latest_consensus_state.timestamp(),
-            update_time: now,
-        }
-        .into());
-    }
+    ctx.client_type_registry()
+        .check_client_type(&client_state.client_type(), &header.type_url)?;
 
-    let _ = client_state
-        .new_check_header_and_update_state(ctx, client_id.clone(), header)
+    client_state
+        .new_verify_client_message(ctx, &client_id, header.clone())
         .map_err(|e| ClientError::HeaderVerificationFailure {
             reason: e.to_string(),
         })?;
 
+    if client_state.new_check_for_misbehaviour(ctx, &client_id, header.clone())? {
+        let _ = client_state
+            .new_update_state_on_misbehaviour(ctx, &client_id, header)
+            .map_err(|e| ClientError::MisbehaviourHandlingFailure {
+                reason: e.to_string(),
+            })?;
+    } else {
+        let _ = client_state
+            .new_update_state(ctx, &client_id, header)
+            .map_err(|e| ClientError::HeaderVerificationFailure {
+                reason: e.to_string(),
+            })?;
+    }
+
     Ok(())
 }
 
@@ -103,28 +98,56 @@ where
     // Read client state from the host chain store.
     let client_state = ctx.client_state(&client_id)?;
 
+    if client_state.new_check_for_misbehaviour(ctx, &client_id, header.clone())? {
+        let client_state = client_state
+            .new_update_state_on_misbehaviour(ctx, &client_id, header)
+            .map_err(|e| ClientError::MisbehaviourHandlingFailure {
+                reason: e.to_string(),
+            })?;
+
+        ctx.emit_ibc_event(IbcEvent::ClientMisbehaviour(ClientMisbehaviour::new(
+            client_id.clone(),
+            client_state.client_type(),
+        )));
+
+        return ctx.store_client_state(ClientStatePath(client_id), client_state);
+    }
+
     let UpdatedState {
         client_state,
         consensus_state,
     } = client_state
-        .new_check_header_and_update_state(ctx, client_id.clone(), header.clone())
+        .new_update_state(ctx, &client_id, header.clone())
         .map_err(|e| ClientError::HeaderVerificationFailure {
             reason: e.to_string(),
         })?;
 
+    if client_state.frozen_height().is_some() {
+        ctx.emit_ibc_event(IbcEvent::ClientMisbehaviour(ClientMisbehaviour::new(
+            client_id.clone(),
+            client_state.client_type(),
+        )));
+
+        return ctx.store_client_state(ClientStatePath(client_id), client_state);
+    }
+
+    for height in new_expired_consensus_heights(ctx, &client_id, client_state.as_ref())? {
+        ctx.delete_consensus_state(ClientConsensusStatePath::new(client_id.clone(), height))?;
+        ctx.delete_update_time(ClientUpdateTimePath::new(client_id.clone(), height))?;
+        ctx.delete_update_height(ClientUpdateHeightPath::new(client_id.clone(), height))?;
+    }
+
     ctx.store_client_state(ClientStatePath(client_id.clone()), client_state.clone())?;
     ctx.store_consensus_state(
         ClientConsensusStatePath::new(client_id.clone(), client_state.latest_height()),
         consensus_state,
     )?;
     ctx.store_update_time(
-        client_id.clone(),
-        client_state.latest_height(),
+        ClientUpdateTimePath::new(client_id.clone(), client_state.latest_height()),
         ctx.host_timestamp()?,
     )?;
     ctx.store_update_height(
-        client_id.clone(),
-        client_state.latest_height(),
+        ClientUpdateHeightPath::new(client_id.clone(), client_state.latest_height()),
         ctx.host_height()?,
     )?;
 
@@ -159,34 +182,40 @@ pub(crate) fn process<Ctx: ClientReader>(
     // Read client state from the host chain store.
     let client_state = ctx.client_state(&client_id)?;
 
-    if client_state.is_frozen() {
-        return Err(ClientError::ClientFrozen { client_id });
+    let status = client_state.status(ctx, &client_id);
+    if !status.is_active() {
+        return Err(ClientError::ClientNotActive { client_id, status });
     }
 
-    // Read consensus state from the host chain store.
-    let latest_consensus_state =
-        ClientReader::consensus_state(ctx, &client_id, &client_state.latest_height()).map_err(
-            |_| ClientError::ConsensusStateNotFound {
-                client_id: client_id.clone(),
-                height: client_state.latest_height(),
-            },
-        )?;
-
-    debug!("latest consensus state: {:?}", latest_consensus_state);
+    ctx.client_type_registry()
+        .check_client_type(&client_state.client_type(), &header.type_url)?;
 
-    let now = ClientReader::host_timestamp(ctx)?;
-    let duration = now
-        .duration_since(&latest_consensus_state.timestamp())
-        .ok_or_else(|| ClientError::InvalidConsensusStateTimestamp {
-            time1: latest_consensus_state.timestamp(),
-            time2: now,
+    client_state
+        .verify_client_message(ctx, &client_id, header.clone())
+        .map_err(|e| ClientError::HeaderVerificationFailure {
+            reason: e.to_string(),
         })?;
 
-    if client_state.expired(duration) {
-        return Err(ClientError::HeaderNotWithinTrustPeriod {
-            latest_time: latest_consensus_state.timestamp(),
-            update_time: now,
+    // The client message may be evidence of misbehaviour rather than a header, in which case the
+    // client is frozen instead of updated.
+    if client_state.check_for_misbehaviour(ctx, &client_id, header.clone())? {
+        let client_state = client_state
+            .update_state_on_misbehaviour(ctx, &client_id, header)
+            .map_err(|e| ClientError::MisbehaviourHandlingFailure {
+                reason: e.to_string(),
+            })?;
+
+        output.emit(IbcEvent::ClientMisbehaviour(ClientMisbehaviour::new(
+            client_id.clone(),
+            client_state.client_type(),
+        )));
+
+        let result = ClientResult::Misbehaviour(MisbehaviourResult {
+            client_id,
+            client_state,
         });
+
+        return Ok(output.with_result(result));
     }
 
     // Use client_state to validate the new header against the latest consensus_state.
@@ -196,13 +225,30 @@ pub(crate) fn process<Ctx: ClientReader>(
         client_state,
         consensus_state,
     } = client_state
-        .check_header_and_update_state(ctx, client_id.clone(), header.clone())
+        .update_state(ctx, &client_id, header.clone())
         .map_err(|e| ClientError::HeaderVerificationFailure {
             reason: e.to_string(),
         })?;
 
+    // A verified header which conflicts with the consensus states of the client is itself
+    // evidence of misbehaviour, in which case the client returns its state frozen.
+    if client_state.frozen_height().is_some() {
+        output.emit(IbcEvent::ClientMisbehaviour(ClientMisbehaviour::new(
+            client_id.clone(),
+            client_state.client_type(),
+        )));
+
+        let result = ClientResult::Misbehaviour(MisbehaviourResult {
+            client_id,
+            client_state,
+        });
+
+        return Ok(output.with_result(result));
+    }
+
     let client_type = client_state.client_type();
     let consensus_height = client_state.latest_height();
+    let pruned_heights = expired_consensus_heights(ctx, &client_id, client_state.as_ref())?;
 
     let result = ClientResult::Update(UpdateClientResult {
         client_id: client_id.clone(),
@@ -210,6 +256,7 @@ pub(crate) fn process<Ctx: ClientReader>(
         consensus_state,
         processed_time: ClientReader::host_timestamp(ctx)?,
         processed_height: ctx.host_height()?,
+        pruned_heights,
     });
 
     output.emit(IbcEvent::UpdateClient(UpdateClient::new(
@@ -223,6 +270,58 @@ pub(crate) fn process<Ctx: ClientReader>(
     Ok(output.with_result(result))
 }
 
+/// Returns the heights of the consensus states of the client which are older than its trusting
+/// period, and should be pruned upon update. Consensus states are visited in ascending order of
+/// height, and so of timestamp, up to the first one which has not expired. The consensus state at
+/// the latest height of the client is never pruned.
+fn expired_consensus_heights(
+    ctx: &dyn ClientReader,
+    client_id: &ClientId,
+    client_state: &dyn ClientState,
+) -> Result<Vec<Height>, ClientError> {
+    let now = ctx.host_timestamp()?;
+
+    let mut expired_heights = Vec::new();
+    for height in ctx.consensus_state_heights(client_id)? {
+        if height >= client_state.latest_height() {
+            break;
+        }
+
+        let consensus_state = ctx.consensus_state(client_id, &height)?;
+        match now.duration_since(&consensus_state.timestamp()) {
+            Some(elapsed) if client_state.expired(elapsed) => expired_heights.push(height),
+            _ => break,
+        }
+    }
+
+    Ok(expired_heights)
+}
+
+/// XXX: temporary solution until we get rid of `ClientReader`
+#[cfg(feature = "val_exec_ctx")]
+fn new_expired_consensus_heights(
+    ctx: &dyn ValidationContext,
+    client_id: &ClientId,
+    client_state: &dyn ClientState,
+) -> Result<Vec<Height>, ContextError> {
+    let now = ctx.host_timestamp()?;
+
+    let mut expired_heights = Vec::new();
+    for height in ctx.consensus_state_heights(client_id)? {
+        if height >= client_state.latest_height() {
+            break;
+        }
+
+        let consensus_state = ctx.consensus_state(client_id, &height)?;
+        match now.duration_since(&consensus_state.timestamp()) {
+            Some(elapsed) if client_state.expired(elapsed) => expired_heights.push(height),
+            _ => break,
+        }
+    }
+
+    Ok(expired_heights)
+}
+
 #[cfg(test)]
 mod tests {
     use core::str::FromStr;
@@ -231,13 +330,15 @@ mod tests {
 
     use crate::clients::ics07_tendermint::client_type as tm_client_type;
     use crate::clients::ics07_tendermint::consensus_state::ConsensusState as TmConsensusState;
-    use crate::core::ics02_client::client_state::ClientState;
-    use crate::core::ics02_client::consensus_state::downcast_consensus_state;
+    use crate::core::ics02_client::client_state::{ClientState, Status};
+    use crate::core::ics02_client::consensus_state::{downcast_consensus_state, ConsensusState};
+    use crate::core::ics02_client::context::{ClientKeeper, ClientReader};
     use crate::core::ics02_client::error::ClientError;
     use crate::core::ics02_client::handler::dispatch;
-    use crate::core::ics02_client::handler::ClientResult::Update;
+    use crate::core::ics02_client::handler::ClientResult::{Misbehaviour, Update};
     use crate::core::ics02_client::msgs::update_client::MsgUpdateClient;
     use crate::core::ics02_client::msgs::ClientMsg;
+    use crate::core::ics04_channel::context::ChannelReader;
     use crate::core::ics24_host::identifier::{ChainId, ClientId};
     use crate::events::IbcEvent;
     use crate::handler::HandlerOutput;
@@ -246,6 +347,7 @@ mod tests {
     use crate::mock::context::MockContext;
     use crate::mock::header::MockHeader;
     use crate::mock::host::{HostBlock, HostType};
+    use crate::mock::misbehaviour::Misbehaviour as MockMisbehaviour;
     use crate::test_utils::get_dummy_account_id;
     use crate::timestamp::Timestamp;
     use crate::Height;
@@ -319,6 +421,53 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_update_inactive_client() {
+        let client_id = ClientId::default();
+        let signer = get_dummy_account_id();
+        let client_height = Height::new(0, 42).unwrap();
+
+        let tests = vec![
+            (
+                MockClientState::new(MockHeader::new(client_height))
+                    .with_frozen_height(client_height),
+                Status::Frozen,
+            ),
+            // No consensus state is stored at the latest height of this client.
+            (
+                MockClientState::new(MockHeader::new(Height::new(0, 43).unwrap())),
+                Status::Expired,
+            ),
+        ];
+
+        for (client_state, expected_status) in tests {
+            let mut ctx = MockContext::default().with_client(&client_id, client_height);
+            ClientKeeper::store_client_state(&mut ctx, client_id.clone(), client_state.into_box())
+                .unwrap();
+
+            let msg = MsgUpdateClient {
+                client_id: client_id.clone(),
+                header: MockHeader::new(Height::new(0, 46).unwrap()).into(),
+                signer: signer.clone(),
+            };
+
+            let output = dispatch(&ctx, ClientMsg::UpdateClient(msg));
+
+            match output {
+                Err(ClientError::ClientNotActive {
+                    client_id: inactive_client_id,
+                    status,
+                }) => {
+                    assert_eq!(inactive_client_id, client_id);
+                    assert_eq!(status, expected_status);
+                }
+                _ => {
+                    panic!("expected ClientNotActive error, instead got {:?}", output)
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_update_client_ok_multiple() {
         let client_ids = vec![
@@ -609,6 +758,142 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_update_synthetic_tendermint_client_prunes_expired_consensus_states() {
+        let client_id = ClientId::new(tm_client_type(), 0).unwrap();
+        let expired_height = Height::new(1, 5).unwrap();
+        let client_height = Height::new(1, 20).unwrap();
+        let update_height = Height::new(1, 21).unwrap();
+        let chain_id_b = ChainId::new("mockgaiaB".to_string(), 1);
+
+        let mut ctx = MockContext::new(
+            ChainId::new("mockgaiaA".to_string(), 1),
+            HostType::Mock,
+            5,
+            Height::new(1, 1).unwrap(),
+        )
+        .with_client_parametrized_history_with_chain_id(
+            chain_id_b.clone(),
+            &client_id,
+            client_height,
+            Some(tm_client_type()), // The target host chain (B) is synthetic TM.
+            Some(client_height),
+        );
+
+        // A consensus state much older than the trusting period of the client.
+        let mut expired_consensus_state = downcast_consensus_state::<TmConsensusState>(
+            ctx.latest_consensus_states(&client_id, &client_height)
+                .as_ref(),
+        )
+        .unwrap()
+        .clone();
+        expired_consensus_state.timestamp = tendermint::Time::from_unix_timestamp(0, 0).unwrap();
+        ctx.store_consensus_state(
+            client_id.clone(),
+            expired_height,
+            expired_consensus_state.into_box(),
+        )
+        .unwrap();
+        ctx.store_update_time(client_id.clone(), expired_height, Timestamp::now())
+            .unwrap();
+
+        let ctx_b = MockContext::new(chain_id_b, HostType::SyntheticTendermint, 5, update_height);
+
+        let mut block = ctx_b.host_block(&update_height).unwrap().clone();
+        block.set_trusted_height(client_height);
+
+        let msg = MsgUpdateClient {
+            client_id: client_id.clone(),
+            header: block.into(),
+            signer: get_dummy_account_id(),
+        };
+
+        let output = dispatch(&ctx, ClientMsg::UpdateClient(msg)).unwrap();
+        match &output.result {
+            Update(upd_res) => assert_eq!(upd_res.pruned_heights, vec![expired_height]),
+            _ => panic!("update handler result has incorrect type"),
+        }
+
+        ctx.store_client_result(output.result).unwrap();
+
+        assert_eq!(
+            ctx.consensus_state_heights(&client_id).unwrap(),
+            vec![Height::new(1, 19).unwrap(), client_height, update_height]
+        );
+        assert!(ctx
+            .prev_consensus_state(&client_id, &Height::new(1, 19).unwrap())
+            .unwrap()
+            .is_none());
+        assert!(ChannelReader::client_update_time(&ctx, &client_id, &expired_height).is_err());
+    }
+
+    #[test]
+    fn test_update_synthetic_tendermint_client_bft_time_violation_freezes() {
+        let client_id = ClientId::new(tm_client_type(), 0).unwrap();
+        let client_height = Height::new(1, 20).unwrap();
+        let trusted_height = Height::new(1, 19).unwrap();
+        let update_height = Height::new(1, 21).unwrap();
+        let chain_id_b = ChainId::new("mockgaiaB".to_string(), 1);
+
+        let mut ctx = MockContext::new(
+            ChainId::new("mockgaiaA".to_string(), 1),
+            HostType::Mock,
+            5,
+            Height::new(1, 1).unwrap(),
+        )
+        .with_client_parametrized_history_with_chain_id(
+            chain_id_b.clone(),
+            &client_id,
+            client_height,
+            Some(tm_client_type()), // The target host chain (B) is synthetic TM.
+            Some(client_height),
+        );
+
+        // A header which is signed by the validators trusted at `trusted_height`, but whose
+        // timestamp is no later than that of the consensus state at the lower `client_height`.
+        let block = HostBlock::SyntheticTendermint(Box::new({
+            let timestamp = ctx
+                .latest_consensus_states(&client_id, &client_height)
+                .timestamp();
+            let mut tm_block = HostBlock::generate_tm_block(
+                chain_id_b,
+                update_height.revision_height(),
+                timestamp,
+            );
+            tm_block.trusted_height = trusted_height;
+            tm_block
+        }));
+
+        let msg = MsgUpdateClient {
+            client_id: client_id.clone(),
+            header: block.into(),
+            signer: get_dummy_account_id(),
+        };
+
+        let output = dispatch(&ctx, ClientMsg::UpdateClient(msg)).unwrap();
+
+        match &output.result {
+            Misbehaviour(res) => {
+                assert_eq!(res.client_id, client_id);
+                assert_eq!(res.client_state.frozen_height(), Some(update_height));
+                assert_eq!(res.client_state.latest_height(), client_height);
+            }
+            _ => panic!("update handler result has incorrect type"),
+        }
+
+        let misbehaviour_event =
+            downcast!(output.events.first().unwrap() => IbcEvent::ClientMisbehaviour).unwrap();
+        assert_eq!(misbehaviour_event.client_id(), &client_id);
+        assert_eq!(misbehaviour_event.client_type(), &tm_client_type());
+
+        ctx.store_client_result(output.result).unwrap();
+
+        assert!(ClientReader::client_state(&ctx, &client_id)
+            .unwrap()
+            .is_frozen());
+        assert!(ClientReader::consensus_state(&ctx, &client_id, &update_height).is_err());
+    }
+
     #[test]
     fn test_update_client_events() {
         let client_id = ClientId::default();
@@ -635,4 +920,40 @@ mod tests {
         assert_eq!(update_client_event.consensus_heights(), &vec![height]);
         assert_eq!(update_client_event.header(), &header);
     }
+
+    #[test]
+    fn test_update_client_with_misbehaviour() {
+        let client_id = ClientId::default();
+        let signer = get_dummy_account_id();
+
+        let timestamp = Timestamp::now();
+
+        let ctx = MockContext::default().with_client(&client_id, Height::new(0, 42).unwrap());
+        let height = Height::new(0, 46).unwrap();
+        let msg = MsgUpdateClient {
+            client_id: client_id.clone(),
+            header: MockMisbehaviour {
+                client_id: client_id.clone(),
+                header1: MockHeader::new(height).with_timestamp(timestamp),
+                header2: MockHeader::new(height).with_timestamp(timestamp),
+            }
+            .into(),
+            signer,
+        };
+
+        let output = dispatch(&ctx, ClientMsg::UpdateClient(msg)).unwrap();
+
+        match output.result {
+            Misbehaviour(res) => {
+                assert_eq!(res.client_id, client_id);
+                assert!(res.client_state.frozen_height().is_some());
+            }
+            _ => panic!("update handler result has incorrect type"),
+        }
+
+        let misbehaviour_event =
+            downcast!(output.events.first().unwrap() => IbcEvent::ClientMisbehaviour).unwrap();
+        assert_eq!(misbehaviour_event.client_id(), &client_id);
+        assert_eq!(misbehaviour_event.client_type(), &mock_client_type());
+    }
 }
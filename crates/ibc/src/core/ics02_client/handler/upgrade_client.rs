@@ -12,11 +12,15 @@ use crate::core::ics02_client::msgs::upgrade_client::MsgUpgradeClient;
 use crate::core::ics24_host::identifier::ClientId;
 use crate::events::IbcEvent;
 use crate::handler::{HandlerOutput, HandlerResult};
+use crate::timestamp::Timestamp;
+use crate::Height;
 
 #[cfg(feature = "val_exec_ctx")]
 use crate::core::context::ContextError;
 #[cfg(feature = "val_exec_ctx")]
-use crate::core::ics24_host::path::{ClientConsensusStatePath, ClientStatePath};
+use crate::core::ics24_host::path::{
+    ClientConsensusStatePath, ClientStatePath, ClientUpdateHeightPath, ClientUpdateTimePath,
+};
 #[cfg(feature = "val_exec_ctx")]
 use crate::core::{ExecutionContext, ValidationContext};
 
@@ -26,6 +30,8 @@ pub struct UpgradeClientResult {
     pub client_id: ClientId,
     pub client_state: Box<dyn ClientState>,
     pub consensus_state: Box<dyn ConsensusState>,
+    pub processed_time: Timestamp,
+    pub processed_height: Height,
 }
 
 #[cfg(feature = "val_exec_ctx")]
@@ -38,11 +44,12 @@ where
     // Read client state from the host chain store.
     let old_client_state = ctx.client_state(&client_id)?;
 
-    if old_client_state.is_frozen() {
-        return Err(ClientError::ClientFrozen { client_id }.into());
+    let status = old_client_state.new_status(ctx, &client_id);
+    if !old_client_state.allows_upgrade(status) {
+        return Err(ClientError::ClientNotActive { client_id, status }.into());
     }
 
-    let upgrade_client_state = ctx.decode_client_state(msg.client_state)?;
+    let upgrade_client_state = ctx.decode_client_state(msg.client_state.clone())?;
 
     if old_client_state.latest_height() >= upgrade_client_state.latest_height() {
         return Err(ClientError::LowUpgradeHeight {
@@ -52,6 +59,18 @@ where
         .into());
     }
 
+    // Read the consensus state at the old client's latest height, whose root the upgrade
+    // proofs are verified against.
+    let old_consensus_state = ctx.consensus_state(&client_id, &old_client_state.latest_height())?;
+
+    old_client_state.verify_upgrade_and_update_state(
+        msg.client_state,
+        msg.consensus_state,
+        msg.proof_upgrade_client,
+        msg.proof_upgrade_consensus_state,
+        old_consensus_state.root(),
+    )?;
+
     Ok(())
 }
 
@@ -62,25 +81,33 @@ where
 {
     let MsgUpgradeClient { client_id, .. } = msg;
 
-    let upgrade_client_state = ctx.decode_client_state(msg.client_state)?;
+    let old_client_state = ctx.client_state(&client_id)?;
+    let old_consensus_state = ctx.consensus_state(&client_id, &old_client_state.latest_height())?;
 
     let UpdatedState {
         client_state,
         consensus_state,
-    } = upgrade_client_state.verify_upgrade_and_update_state(
-        msg.consensus_state.clone(),
-        msg.proof_upgrade_client.clone(),
+    } = old_client_state.verify_upgrade_and_update_state(
+        msg.client_state,
+        msg.consensus_state,
+        msg.proof_upgrade_client,
         msg.proof_upgrade_consensus_state,
+        old_consensus_state.root(),
     )?;
 
-    // Not implemented yet: https://github.com/informalsystems/ibc-rs/issues/722
-    // todo!()
-
     ctx.store_client_state(ClientStatePath(client_id.clone()), client_state.clone())?;
     ctx.store_consensus_state(
         ClientConsensusStatePath::new(client_id.clone(), client_state.latest_height()),
         consensus_state,
     )?;
+    ctx.store_update_time(
+        ClientUpdateTimePath::new(client_id.clone(), client_state.latest_height()),
+        ctx.host_timestamp()?,
+    )?;
+    ctx.store_update_height(
+        ClientUpdateHeightPath::new(client_id.clone(), client_state.latest_height()),
+        ctx.host_height()?,
+    )?;
 
     ctx.emit_ibc_event(IbcEvent::UpgradeClient(UpgradeClient::new(
         client_id,
@@ -101,11 +128,12 @@ pub(crate) fn process(
     // Read client state from the host chain store.
     let old_client_state = ctx.client_state(&client_id)?;
 
-    if old_client_state.is_frozen() {
-        return Err(ClientError::ClientFrozen { client_id });
+    let status = old_client_state.status(ctx, &client_id);
+    if !old_client_state.allows_upgrade(status) {
+        return Err(ClientError::ClientNotActive { client_id, status });
     }
 
-    let upgrade_client_state = ctx.decode_client_state(msg.client_state)?;
+    let upgrade_client_state = ctx.decode_client_state(msg.client_state.clone())?;
 
     if old_client_state.latest_height() >= upgrade_client_state.latest_height() {
         return Err(ClientError::LowUpgradeHeight {
@@ -114,18 +142,21 @@ pub(crate) fn process(
         });
     }
 
+    // Read the consensus state at the old client's latest height, whose root the upgrade
+    // proofs are verified against.
+    let old_consensus_state = ctx.consensus_state(&client_id, &old_client_state.latest_height())?;
+
     let UpdatedState {
         client_state,
         consensus_state,
-    } = upgrade_client_state.verify_upgrade_and_update_state(
-        msg.consensus_state.clone(),
-        msg.proof_upgrade_client.clone(),
+    } = old_client_state.verify_upgrade_and_update_state(
+        msg.client_state,
+        msg.consensus_state,
+        msg.proof_upgrade_client,
         msg.proof_upgrade_consensus_state,
+        old_consensus_state.root(),
     )?;
 
-    // Not implemented yet: https://github.com/informalsystems/ibc-rs/issues/722
-    // todo!()
-
     let client_type = client_state.client_type();
     let consensus_height = client_state.latest_height();
 
@@ -133,6 +164,8 @@ pub(crate) fn process(
         client_id: client_id.clone(),
         client_state,
         consensus_state,
+        processed_time: ctx.host_timestamp()?,
+        processed_height: ctx.host_height()?,
     });
 
     output.emit(IbcEvent::UpgradeClient(UpgradeClient::new(
@@ -151,13 +184,33 @@ mod tests {
 
     use core::str::FromStr;
 
+    use core::time::Duration;
+
+    use ibc_proto::google::protobuf::Any;
+    use prost::Message;
+    use tendermint::{Hash, Time};
+
+    use crate::clients::ics07_tendermint::client_state::test_util::get_dummy_upgrade_proofs;
+    use crate::clients::ics07_tendermint::client_state::{
+        AllowUpdate, ClientState as TmClientState,
+    };
+    use crate::clients::ics07_tendermint::client_type as tm_client_type;
+    use crate::clients::ics07_tendermint::consensus_state::ConsensusState as TmConsensusState;
+    use crate::core::ics02_client::client_state::{ClientState, Status};
+    use crate::core::ics02_client::consensus_state::ConsensusState;
+    use crate::core::ics02_client::context::{ClientKeeper, ClientReader};
     use crate::core::ics02_client::error::ClientError;
     use crate::core::ics02_client::handler::dispatch;
     use crate::core::ics02_client::handler::ClientResult::Upgrade;
     use crate::core::ics02_client::msgs::upgrade_client::MsgUpgradeClient;
     use crate::core::ics02_client::msgs::ClientMsg;
+    use crate::core::ics02_client::trust_threshold::TrustThreshold;
+    use crate::core::ics04_channel::context::ChannelReader;
+    use crate::core::ics23_commitment::specs::ProofSpecs;
+    use crate::core::ics24_host::identifier::ChainId;
     use crate::core::ics24_host::identifier::ClientId;
     use crate::handler::HandlerOutput;
+    use crate::host_functions::HostFunctionsManager;
     use crate::mock::client_state::client_type as mock_client_type;
     use crate::mock::client_state::MockClientState;
     use crate::mock::consensus_state::MockConsensusState;
@@ -207,6 +260,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_upgrade_client_records_processed_metadata() {
+        let client_id = ClientId::default();
+        let upgrade_height = Height::new(1, 26).unwrap();
+
+        let mut ctx = MockContext::default().with_client(&client_id, Height::new(0, 42).unwrap());
+
+        let msg = MsgUpgradeClient {
+            client_id: client_id.clone(),
+            client_state: MockClientState::new(MockHeader::new(upgrade_height)).into(),
+            consensus_state: MockConsensusState::new(MockHeader::new(upgrade_height)).into(),
+            proof_upgrade_client: Default::default(),
+            proof_upgrade_consensus_state: Default::default(),
+            signer: get_dummy_account_id(),
+        };
+
+        let output = dispatch(&ctx, ClientMsg::UpgradeClient(msg)).unwrap();
+        ctx.store_client_result(output.result).unwrap();
+
+        assert_eq!(
+            ChannelReader::client_update_time(&ctx, &client_id, &upgrade_height).unwrap(),
+            ClientReader::host_timestamp(&ctx).unwrap()
+        );
+        assert_eq!(
+            ChannelReader::client_update_height(&ctx, &client_id, &upgrade_height).unwrap(),
+            ClientReader::host_height(&ctx).unwrap()
+        );
+    }
+
     #[test]
     fn test_upgrade_nonexisting_client() {
         let client_id = ClientId::from_str("mockclient1").unwrap();
@@ -298,4 +380,109 @@ mod tests {
         assert_eq!(upgrade_client_event.client_type(), &mock_client_type());
         assert_eq!(upgrade_client_event.consensus_height(), &upgrade_height);
     }
+    #[test]
+    fn test_upgrade_expired_client() {
+        let client_id = ClientId::from_str("07-tendermint-0").unwrap();
+        let upgrade_path = vec!["upgrade".to_string(), "upgradedIBCState".to_string()];
+        let client_height = Height::new(0, 10).unwrap();
+
+        let tm_client_state = |chain_id: ChainId, latest_height: Height, after_expiry: bool| {
+            TmClientState::<HostFunctionsManager>::new(
+                chain_id,
+                TrustThreshold::ONE_THIRD,
+                Duration::new(64000, 0),
+                Duration::new(128000, 0),
+                Duration::new(3, 0),
+                latest_height,
+                ProofSpecs::default(),
+                upgrade_path.clone(),
+                AllowUpdate {
+                    after_expiry,
+                    after_misbehaviour: false,
+                },
+                None,
+            )
+            .unwrap()
+        };
+
+        for after_expiry in [true, false] {
+            let mut ctx = MockContext::default().with_client_parametrized(
+                &client_id,
+                client_height,
+                Some(tm_client_type()),
+                None,
+            );
+
+            let upgraded_client_state = tm_client_state(
+                ChainId::new("ibc".to_string(), 1),
+                Height::new(1, 1).unwrap(),
+                after_expiry,
+            );
+            let upgraded_consensus_state = TmConsensusState::new(
+                vec![1; 32].into(),
+                ClientReader::host_timestamp(&ctx)
+                    .unwrap()
+                    .into_tm_time()
+                    .unwrap(),
+                Hash::Sha256([2; 32]),
+            );
+
+            let mut committed_client_state = upgraded_client_state.clone();
+            committed_client_state.zero_custom_fields();
+            let (proof_upgrade_client, proof_upgrade_consensus_state, root) =
+                get_dummy_upgrade_proofs(
+                    &upgrade_path,
+                    client_height.revision_height(),
+                    Any::from(committed_client_state).encode_to_vec(),
+                    Any::from(upgraded_consensus_state.clone()).encode_to_vec(),
+                );
+
+            // The latest consensus state of the client is older than its trusting period
+            let client_state = tm_client_state(
+                ChainId::new("ibc".to_string(), 0),
+                client_height,
+                after_expiry,
+            );
+            ctx.store_client_state(client_id.clone(), client_state.clone().into_box())
+                .unwrap();
+            ctx.store_consensus_state(
+                client_id.clone(),
+                client_height,
+                TmConsensusState::new(
+                    root,
+                    Time::from_unix_timestamp(1_000_000, 0).unwrap(),
+                    Hash::Sha256([2; 32]),
+                )
+                .into_box(),
+            )
+            .unwrap();
+            assert_eq!(client_state.status(&ctx, &client_id), Status::Expired);
+
+            let msg = MsgUpgradeClient {
+                client_id: client_id.clone(),
+                client_state: upgraded_client_state.into(),
+                consensus_state: upgraded_consensus_state.into(),
+                proof_upgrade_client,
+                proof_upgrade_consensus_state,
+                signer: get_dummy_account_id(),
+            };
+
+            match dispatch(&ctx, ClientMsg::UpgradeClient(msg)) {
+                Ok(output) if after_expiry => {
+                    ctx.store_client_result(output.result).unwrap();
+
+                    let client_state = ClientReader::client_state(&ctx, &client_id).unwrap();
+                    assert_eq!(client_state.latest_height(), Height::new(1, 1).unwrap());
+                    assert_eq!(client_state.status(&ctx, &client_id), Status::Active);
+                }
+                Err(ClientError::ClientNotActive { status, .. }) if !after_expiry => {
+                    assert_eq!(status, Status::Expired);
+                }
+                res => panic!(
+                    "unexpected result upgrading an expired client with after_expiry {}: {:?}",
+                    after_expiry, res
+                ),
+            }
+        }
+    }
 }
@@ -11,4 +11,6 @@ pub mod header;
 pub mod height;
 pub mod misbehaviour;
 pub mod msgs;
+pub mod params;
+pub mod registry;
 pub mod trust_threshold;
@@ -6,11 +6,13 @@
 
 use crate::core::ics02_client::msgs::create_client::MsgCreateClient;
 use crate::core::ics02_client::msgs::misbehaviour::MsgSubmitMisbehaviour;
+use crate::core::ics02_client::msgs::recover_client::MsgRecoverClient;
 use crate::core::ics02_client::msgs::update_client::MsgUpdateClient;
 use crate::core::ics02_client::msgs::upgrade_client::MsgUpgradeClient;
 
 pub mod create_client;
 pub mod misbehaviour;
+pub mod recover_client;
 pub mod update_client;
 pub mod upgrade_client;
 
@@ -21,4 +23,5 @@ pub enum ClientMsg {
     UpdateClient(MsgUpdateClient),
     Misbehaviour(MsgSubmitMisbehaviour),
     UpgradeClient(MsgUpgradeClient),
+    RecoverClient(MsgRecoverClient),
 }
@@ -0,0 +1,126 @@
+//! Definition of domain type message `MsgRecoverClient`.
+
+use crate::prelude::*;
+
+use ibc_proto::protobuf::Protobuf;
+
+use crate::core::ics02_client::error::ClientError;
+use crate::core::ics24_host::identifier::ClientId;
+use crate::signer::Signer;
+use crate::tx_msg::Msg;
+
+pub const TYPE_URL: &str = "/ibc.core.client.v1.MsgRecoverClient";
+
+/// Raw `MsgRecoverClient`, as defined by ibc-go.
+///
+/// XXX: to be replaced by its `ibc-proto` counterpart once available.
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct RawMsgRecoverClient {
+    /// the client identifier for the client to be updated if the proposal passes
+    #[prost(string, tag = "1")]
+    pub subject_client_id: String,
+    /// the substitute client identifier for the client which will replace the subject client
+    #[prost(string, tag = "2")]
+    pub substitute_client_id: String,
+    /// the authority signing the message
+    #[prost(string, tag = "3")]
+    pub signer: String,
+}
+
+/// A type of message that recovers a frozen or expired on-chain (IBC) client, the subject, by
+/// substituting it with a healthy one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MsgRecoverClient {
+    pub subject_client_id: ClientId,
+    pub substitute_client_id: ClientId,
+    pub signer: Signer,
+}
+
+impl MsgRecoverClient {
+    pub fn new(
+        subject_client_id: ClientId,
+        substitute_client_id: ClientId,
+        signer: Signer,
+    ) -> Self {
+        MsgRecoverClient {
+            subject_client_id,
+            substitute_client_id,
+            signer,
+        }
+    }
+}
+
+impl Msg for MsgRecoverClient {
+    type Raw = RawMsgRecoverClient;
+
+    fn type_url(&self) -> String {
+        TYPE_URL.to_string()
+    }
+}
+
+impl Protobuf<RawMsgRecoverClient> for MsgRecoverClient {}
+
+impl TryFrom<RawMsgRecoverClient> for MsgRecoverClient {
+    type Error = ClientError;
+
+    fn try_from(raw: RawMsgRecoverClient) -> Result<Self, Self::Error> {
+        Ok(MsgRecoverClient {
+            subject_client_id: raw
+                .subject_client_id
+                .parse()
+                .map_err(ClientError::InvalidClientIdentifier)?,
+            substitute_client_id: raw
+                .substitute_client_id
+                .parse()
+                .map_err(ClientError::InvalidClientIdentifier)?,
+            signer: raw.signer.parse().map_err(ClientError::Signer)?,
+        })
+    }
+}
+
+impl From<MsgRecoverClient> for RawMsgRecoverClient {
+    fn from(ics_msg: MsgRecoverClient) -> Self {
+        RawMsgRecoverClient {
+            subject_client_id: ics_msg.subject_client_id.to_string(),
+            substitute_client_id: ics_msg.substitute_client_id.to_string(),
+            signer: ics_msg.signer.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use test_log::test;
+
+    use super::RawMsgRecoverClient;
+    use crate::core::ics02_client::msgs::recover_client::MsgRecoverClient;
+    use crate::core::ics24_host::identifier::ClientId;
+    use crate::prelude::*;
+    use crate::test_utils::get_dummy_account_id;
+
+    #[test]
+    fn msg_recover_client_serialization() {
+        let subject_client_id: ClientId = "07-tendermint-0".parse().unwrap();
+        let substitute_client_id: ClientId = "07-tendermint-1".parse().unwrap();
+        let signer = get_dummy_account_id();
+
+        let msg = MsgRecoverClient::new(subject_client_id, substitute_client_id, signer);
+        let raw = RawMsgRecoverClient::from(msg.clone());
+        let msg_back = MsgRecoverClient::try_from(raw.clone()).unwrap();
+        let raw_back = RawMsgRecoverClient::from(msg_back.clone());
+        assert_eq!(msg, msg_back);
+        assert_eq!(raw, raw_back);
+    }
+
+    #[test]
+    fn msg_recover_client_invalid_subject_client_id() {
+        let raw = RawMsgRecoverClient {
+            subject_client_id: "".to_string(),
+            substitute_client_id: "07-tendermint-1".to_string(),
+            signer: get_dummy_account_id().to_string(),
+        };
+
+        assert!(MsgRecoverClient::try_from(raw).is_err());
+    }
+}
@@ -17,6 +17,8 @@ pub const TYPE_URL: &str = "/ibc.core.client.v1.MsgUpdateClient";
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct MsgUpdateClient {
     pub client_id: ClientId,
+    /// The client message, which is either a header or evidence of misbehaviour. The client
+    /// decides which it is, and is frozen in the latter case.
     pub header: Any,
     pub signer: Signer,
 }
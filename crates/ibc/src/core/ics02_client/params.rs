@@ -0,0 +1,130 @@
+//! Defines the parameters of the client submodule, which hosts typically set through governance.
+
+use crate::prelude::*;
+
+use ibc_proto::ibc::core::client::v1::Params as RawParams;
+use ibc_proto::protobuf::Protobuf;
+
+use crate::core::ics02_client::client_type::ClientType;
+use crate::core::ics02_client::error::ClientError;
+
+/// The wildcard which allows clients of any type to be created.
+pub const ALLOW_ALL_CLIENTS: &str = "*";
+
+/// The parameters of the client submodule.
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClientParams {
+    /// The types of the clients which may be created, or `ALLOW_ALL_CLIENTS` to allow them all.
+    pub allowed_clients: Vec<ClientType>,
+}
+
+impl ClientParams {
+    /// Checks whether or not clients of the given type may be created.
+    pub fn is_allowed_client(&self, client_type: &ClientType) -> bool {
+        self.allowed_clients
+            .iter()
+            .any(|allowed| allowed.as_str() == ALLOW_ALL_CLIENTS || allowed == client_type)
+    }
+}
+
+/// Allows clients of any type to be created.
+impl Default for ClientParams {
+    fn default() -> Self {
+        Self {
+            allowed_clients: vec![ClientType::new(ALLOW_ALL_CLIENTS.to_string())],
+        }
+    }
+}
+
+impl Protobuf<RawParams> for ClientParams {}
+
+impl TryFrom<RawParams> for ClientParams {
+    type Error = ClientError;
+
+    fn try_from(raw: RawParams) -> Result<Self, Self::Error> {
+        let mut allowed_clients: Vec<ClientType> = Vec::with_capacity(raw.allowed_clients.len());
+        for client_type in raw.allowed_clients {
+            if client_type.trim().is_empty() {
+                return Err(ClientError::InvalidClientParams {
+                    reason: "allowed client type cannot be blank".to_string(),
+                });
+            }
+            let client_type = ClientType::new(client_type);
+            if allowed_clients.contains(&client_type) {
+                return Err(ClientError::InvalidClientParams {
+                    reason: format!("allowed client type {client_type} is duplicated"),
+                });
+            }
+            allowed_clients.push(client_type);
+        }
+
+        Ok(Self { allowed_clients })
+    }
+}
+
+impl From<ClientParams> for RawParams {
+    fn from(value: ClientParams) -> Self {
+        Self {
+            allowed_clients: value
+                .allowed_clients
+                .into_iter()
+                .map(|client_type| client_type.as_str().to_string())
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_log::test;
+
+    use crate::clients::ics07_tendermint::client_type as tm_client_type;
+    use crate::mock::client_state::client_type as mock_client_type;
+
+    #[test]
+    fn allowed_clients() {
+        assert!(ClientParams::default().is_allowed_client(&tm_client_type()));
+
+        let params = ClientParams {
+            allowed_clients: vec![tm_client_type()],
+        };
+        assert!(params.is_allowed_client(&tm_client_type()));
+        assert!(!params.is_allowed_client(&mock_client_type()));
+
+        let params = ClientParams {
+            allowed_clients: vec![],
+        };
+        assert!(!params.is_allowed_client(&tm_client_type()));
+    }
+
+    #[test]
+    fn raw_params() {
+        let params = ClientParams {
+            allowed_clients: vec![tm_client_type(), mock_client_type()],
+        };
+        let raw = RawParams::from(params.clone());
+        assert_eq!(ClientParams::try_from(raw).unwrap(), params);
+
+        for allowed_clients in [vec!["".to_string()], vec!["a".to_string(), "a".to_string()]] {
+            assert!(matches!(
+                ClientParams::try_from(RawParams { allowed_clients }),
+                Err(ClientError::InvalidClientParams { .. })
+            ));
+        }
+    }
+}
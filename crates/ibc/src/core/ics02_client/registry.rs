@@ -0,0 +1,465 @@
+//! A registry of the client types supported by a host, through which the `Any`-encoded states and
+//! messages of clients are decoded into the corresponding light client types.
+
+use crate::prelude::*;
+
+use alloc::collections::btree_map::BTreeMap;
+use alloc::sync::Arc;
+use core::fmt::{Debug, Formatter};
+
+use ibc_proto::google::protobuf::Any;
+
+use crate::clients::ics06_solomachine::client_state::{
+    ClientState as SmClientState, SOLOMACHINE_CLIENT_STATE_TYPE_URL,
+};
+use crate::clients::ics06_solomachine::client_type as sm_client_type;
+use crate::clients::ics06_solomachine::consensus_state::{
+    ConsensusState as SmConsensusState, SOLOMACHINE_CONSENSUS_STATE_TYPE_URL,
+};
+use crate::clients::ics06_solomachine::header::{Header as SmHeader, SOLOMACHINE_HEADER_TYPE_URL};
+use crate::clients::ics06_solomachine::misbehaviour::{
+    Misbehaviour as SmMisbehaviour, SOLOMACHINE_MISBEHAVIOUR_TYPE_URL,
+};
+use crate::clients::ics07_tendermint::client_state::{
+    ClientState as TmClientState, TENDERMINT_CLIENT_STATE_TYPE_URL,
+};
+use crate::clients::ics07_tendermint::client_type as tm_client_type;
+use crate::clients::ics07_tendermint::consensus_state::{
+    ConsensusState as TmConsensusState, TENDERMINT_CONSENSUS_STATE_TYPE_URL,
+};
+use crate::clients::ics07_tendermint::header::{Header as TmHeader, TENDERMINT_HEADER_TYPE_URL};
+use crate::clients::ics07_tendermint::misbehaviour::{
+    Misbehaviour as TmMisbehaviour, TENDERMINT_MISBEHAVIOUR_TYPE_URL,
+};
+use crate::clients::ics09_localhost::client_state::{
+    ClientState as LocalhostClientState, LOCALHOST_CLIENT_STATE_TYPE_URL,
+};
+use crate::clients::ics09_localhost::client_type as localhost_client_type;
+use crate::clients::ics09_localhost::consensus_state::{
+    ConsensusState as LocalhostConsensusState, LOCALHOST_CONSENSUS_STATE_TYPE_URL,
+};
+use crate::core::ics02_client::client_state::ClientState;
+use crate::core::ics02_client::client_type::ClientType;
+use crate::core::ics02_client::consensus_state::ConsensusState;
+use crate::core::ics02_client::error::ClientError;
+use crate::core::ics02_client::header::Header;
+use crate::core::ics02_client::misbehaviour::Misbehaviour;
+use crate::host_functions::{HostFunctionsManager, HostFunctionsProvider};
+
+/// Decodes an `Any` into a boxed light client type.
+pub type DecodeFn<T> = Arc<dyn Fn(Any) -> Result<Box<T>, ClientError> + Send + Sync>;
+
+/// A decoder registered under a type URL, along with the client type it belongs to.
+struct Registration<T: ?Sized> {
+    client_type: ClientType,
+    decode: DecodeFn<T>,
+}
+
+impl<T: ?Sized> Clone for Registration<T> {
+    fn clone(&self) -> Self {
+        Self {
+            client_type: self.client_type.clone(),
+            decode: self.decode.clone(),
+        }
+    }
+}
+
+type Registrations<T> = BTreeMap<String, Registration<T>>;
+
+/// Maps the type URLs of client states, consensus states, headers and misbehaviours to decoders
+/// of the light client types they belong to.
+///
+/// Hosts register the client types they support, including their own client implementations, so
+/// that the core handlers can decode client states without any knowledge of the concrete types.
+/// [`ClientTypeRegistry::default`] knows about the clients of this crate which need no input from
+/// the host, i.e. all of them but the Wasm client, whose engine has to be registered with
+/// [`register_client_state_with`](ClientTypeRegistry::register_client_state_with).
+#[derive(Clone)]
+pub struct ClientTypeRegistry {
+    client_states: Registrations<dyn ClientState>,
+    consensus_states: Registrations<dyn ConsensusState>,
+    headers: Registrations<dyn Header>,
+    misbehaviours: Registrations<dyn Misbehaviour>,
+}
+
+impl ClientTypeRegistry {
+    /// Returns an empty registry.
+    pub fn new() -> Self {
+        Self {
+            client_states: BTreeMap::new(),
+            consensus_states: BTreeMap::new(),
+            headers: BTreeMap::new(),
+            misbehaviours: BTreeMap::new(),
+        }
+    }
+
+    pub fn register_client_state<CS>(
+        &mut self,
+        client_type: ClientType,
+        type_url: &str,
+    ) -> Result<(), ClientError>
+    where
+        CS: ClientState + TryFrom<Any, Error = ClientError> + 'static,
+    {
+        self.register_client_state_with(client_type, type_url, |any| {
+            <CS as TryFrom<Any>>::try_from(any).map(CS::into_box)
+        })
+    }
+
+    /// Registers a custom decoder for client states, e.g. one which attaches host resources to
+    /// the decoded client state.
+    pub fn register_client_state_with<F>(
+        &mut self,
+        client_type: ClientType,
+        type_url: &str,
+        decode: F,
+    ) -> Result<(), ClientError>
+    where
+        F: Fn(Any) -> Result<Box<dyn ClientState>, ClientError> + Send + Sync + 'static,
+    {
+        register(
+            &mut self.client_states,
+            client_type,
+            type_url,
+            Arc::new(decode),
+        )
+    }
+
+    pub fn register_consensus_state<CS>(
+        &mut self,
+        client_type: ClientType,
+        type_url: &str,
+    ) -> Result<(), ClientError>
+    where
+        CS: ConsensusState + TryFrom<Any, Error = ClientError> + 'static,
+    {
+        register(
+            &mut self.consensus_states,
+            client_type,
+            type_url,
+            Arc::new(|any| <CS as TryFrom<Any>>::try_from(any).map(CS::into_box)),
+        )
+    }
+
+    pub fn register_header<H>(
+        &mut self,
+        client_type: ClientType,
+        type_url: &str,
+    ) -> Result<(), ClientError>
+    where
+        H: Header + TryFrom<Any, Error = ClientError> + 'static,
+    {
+        register(
+            &mut self.headers,
+            client_type,
+            type_url,
+            Arc::new(|any| <H as TryFrom<Any>>::try_from(any).map(H::into_box)),
+        )
+    }
+
+    pub fn register_misbehaviour<M>(
+        &mut self,
+        client_type: ClientType,
+        type_url: &str,
+    ) -> Result<(), ClientError>
+    where
+        M: Misbehaviour + TryFrom<Any, Error = ClientError> + 'static,
+    {
+        register(
+            &mut self.misbehaviours,
+            client_type,
+            type_url,
+            Arc::new(|any| {
+                let misbehaviour: Box<dyn Misbehaviour> =
+                    Box::new(<M as TryFrom<Any>>::try_from(any)?);
+                Ok(misbehaviour)
+            }),
+        )
+    }
+
+    pub fn decode_client_state(
+        &self,
+        client_state: Any,
+    ) -> Result<Box<dyn ClientState>, ClientError> {
+        match self.client_states.get(&client_state.type_url) {
+            Some(registration) => (registration.decode)(client_state),
+            None => Err(ClientError::UnknownClientStateType {
+                client_state_type: client_state.type_url,
+            }),
+        }
+    }
+
+    pub fn decode_consensus_state(
+        &self,
+        consensus_state: Any,
+    ) -> Result<Box<dyn ConsensusState>, ClientError> {
+        match self.consensus_states.get(&consensus_state.type_url) {
+            Some(registration) => (registration.decode)(consensus_state),
+            None => Err(ClientError::UnknownConsensusStateType {
+                consensus_state_type: consensus_state.type_url,
+            }),
+        }
+    }
+
+    pub fn decode_header(&self, header: Any) -> Result<Box<dyn Header>, ClientError> {
+        match self.headers.get(&header.type_url) {
+            Some(registration) => (registration.decode)(header),
+            None => Err(ClientError::UnknownHeaderType {
+                header_type: header.type_url,
+            }),
+        }
+    }
+
+    pub fn decode_misbehaviour(
+        &self,
+        misbehaviour: Any,
+    ) -> Result<Box<dyn Misbehaviour>, ClientError> {
+        match self.misbehaviours.get(&misbehaviour.type_url) {
+            Some(registration) => (registration.decode)(misbehaviour),
+            None => Err(ClientError::UnknownMisbehaviourType {
+                misbehaviour_type: misbehaviour.type_url,
+            }),
+        }
+    }
+
+    /// Returns the client type that `type_url` is registered for, if any.
+    pub fn client_type(&self, type_url: &str) -> Option<&ClientType> {
+        self.client_states
+            .get(type_url)
+            .map(|r| &r.client_type)
+            .or_else(|| self.consensus_states.get(type_url).map(|r| &r.client_type))
+            .or_else(|| self.headers.get(type_url).map(|r| &r.client_type))
+            .or_else(|| self.misbehaviours.get(type_url).map(|r| &r.client_type))
+    }
+
+    /// Whether `type_url` is registered as the type of a misbehaviour.
+    pub fn is_misbehaviour(&self, type_url: &str) -> bool {
+        self.misbehaviours.contains_key(type_url)
+    }
+
+    /// Whether client states of `client_type` can be decoded.
+    pub fn contains(&self, client_type: &ClientType) -> bool {
+        self.client_states
+            .values()
+            .any(|r| &r.client_type == client_type)
+    }
+
+    /// Checks that `type_url` is not registered for a client type other than `client_type`.
+    ///
+    /// Type URLs which are not registered at all are accepted, and left for the client to decode.
+    pub fn check_client_type(
+        &self,
+        client_type: &ClientType,
+        type_url: &str,
+    ) -> Result<(), ClientError> {
+        match self.client_type(type_url) {
+            Some(registered) if registered != client_type => Err(ClientError::MismatchedTypeUrl {
+                type_url: type_url.to_string(),
+                registered: registered.clone(),
+                client_type: client_type.clone(),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns a registry of the same clients as [`ClientTypeRegistry::default`], whose
+    /// Tendermint client verifies headers and proofs with the host functions `H`.
+    pub fn with_host_functions<H: HostFunctionsProvider>() -> Self {
+        let mut registry = Self::new();
+
+        registry
+            .register_builtin_clients::<H>()
+            .expect("built-in type URLs are distinct");
+
+        registry
+    }
+
+    fn register_builtin_clients<H: HostFunctionsProvider>(&mut self) -> Result<(), ClientError> {
+        self.register_client_state::<TmClientState<H>>(
+            tm_client_type(),
+            TENDERMINT_CLIENT_STATE_TYPE_URL,
+        )?;
+        self.register_consensus_state::<TmConsensusState>(
+            tm_client_type(),
+            TENDERMINT_CONSENSUS_STATE_TYPE_URL,
+        )?;
+        self.register_header::<TmHeader>(tm_client_type(), TENDERMINT_HEADER_TYPE_URL)?;
+        self.register_misbehaviour::<TmMisbehaviour>(
+            tm_client_type(),
+            TENDERMINT_MISBEHAVIOUR_TYPE_URL,
+        )?;
+
+        self.register_client_state::<SmClientState>(
+            sm_client_type(),
+            SOLOMACHINE_CLIENT_STATE_TYPE_URL,
+        )?;
+        self.register_consensus_state::<SmConsensusState>(
+            sm_client_type(),
+            SOLOMACHINE_CONSENSUS_STATE_TYPE_URL,
+        )?;
+        self.register_header::<SmHeader>(sm_client_type(), SOLOMACHINE_HEADER_TYPE_URL)?;
+        self.register_misbehaviour::<SmMisbehaviour>(
+            sm_client_type(),
+            SOLOMACHINE_MISBEHAVIOUR_TYPE_URL,
+        )?;
+
+        self.register_client_state::<LocalhostClientState>(
+            localhost_client_type(),
+            LOCALHOST_CLIENT_STATE_TYPE_URL,
+        )?;
+        self.register_consensus_state::<LocalhostConsensusState>(
+            localhost_client_type(),
+            LOCALHOST_CONSENSUS_STATE_TYPE_URL,
+        )
+    }
+}
+
+fn register<T: ?Sized>(
+    registrations: &mut Registrations<T>,
+    client_type: ClientType,
+    type_url: &str,
+    decode: DecodeFn<T>,
+) -> Result<(), ClientError> {
+    if registrations.contains_key(type_url) {
+        return Err(ClientError::TypeUrlAlreadyRegistered {
+            type_url: type_url.to_string(),
+        });
+    }
+
+    registrations.insert(
+        type_url.to_string(),
+        Registration {
+            client_type,
+            decode,
+        },
+    );
+
+    Ok(())
+}
+
+/// Registers the Tendermint, solo machine and localhost clients.
+impl Default for ClientTypeRegistry {
+    fn default() -> Self {
+        Self::with_host_functions::<HostFunctionsManager>()
+    }
+}
+
+impl Debug for ClientTypeRegistry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ClientTypeRegistry")
+            .field("client_states", &self.client_states.keys())
+            .field("consensus_states", &self.consensus_states.keys())
+            .field("headers", &self.headers.keys())
+            .field("misbehaviours", &self.misbehaviours.keys())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_log::test;
+
+    use crate::clients::ics07_tendermint::client_state::test_util::get_dummy_tendermint_client_state;
+    use crate::clients::ics07_tendermint::header::test_util::get_dummy_tendermint_header;
+    use crate::core::ics02_client::msgs::create_client::MsgCreateClient;
+    use crate::core::ics02_client::msgs::ClientMsg;
+    use crate::core::ics26_routing::msgs::MsgEnvelope;
+    use crate::mock::client_state::{
+        client_type as mock_client_type, MockClientState, MOCK_CLIENT_STATE_TYPE_URL,
+    };
+    use crate::mock::consensus_state::{MockConsensusState, MOCK_CONSENSUS_STATE_TYPE_URL};
+    use crate::mock::context::MockContext;
+    use crate::mock::header::MockHeader;
+    use crate::test_utils::get_dummy_account_id;
+    use crate::Height;
+
+    #[test]
+    fn decode_registered_client_states() {
+        let registry = ClientTypeRegistry::default();
+        let tm_client_state = get_dummy_tendermint_client_state(get_dummy_tendermint_header());
+
+        let decoded = registry
+            .decode_client_state(tm_client_state.clone().into())
+            .unwrap();
+        assert_eq!(decoded.client_type(), tm_client_type());
+
+        let mock_client_state =
+            MockClientState::new(MockHeader::new(Height::new(0, 1).unwrap())).into();
+        assert!(matches!(
+            registry.decode_client_state(mock_client_state),
+            Err(ClientError::UnknownClientStateType { .. })
+        ));
+    }
+
+    #[test]
+    fn register_type_url_once() {
+        let mut registry = ClientTypeRegistry::new();
+
+        registry
+            .register_client_state::<MockClientState>(
+                mock_client_type(),
+                MOCK_CLIENT_STATE_TYPE_URL,
+            )
+            .unwrap();
+        assert!(matches!(
+            registry.register_client_state::<MockClientState>(
+                mock_client_type(),
+                MOCK_CLIENT_STATE_TYPE_URL
+            ),
+            Err(ClientError::TypeUrlAlreadyRegistered { .. })
+        ));
+        assert!(registry.contains(&mock_client_type()));
+        assert!(!registry.contains(&tm_client_type()));
+    }
+
+    #[test]
+    fn check_client_type_of_type_url() {
+        let registry = ClientTypeRegistry::default();
+
+        registry
+            .check_client_type(&tm_client_type(), TENDERMINT_HEADER_TYPE_URL)
+            .unwrap();
+        assert!(registry
+            .check_client_type(&sm_client_type(), TENDERMINT_HEADER_TYPE_URL)
+            .is_err());
+
+        // Unknown type URLs are left for the client to decode
+        registry
+            .check_client_type(&sm_client_type(), "/unknown.Header")
+            .unwrap();
+    }
+
+    #[test]
+    fn host_without_client_type() {
+        let height = Height::new(0, 1).unwrap();
+        let create_client = || {
+            MsgEnvelope::Client(ClientMsg::CreateClient(MsgCreateClient::new(
+                MockClientState::new(MockHeader::new(height)).into(),
+                MockConsensusState::new(MockHeader::new(height)).into(),
+                get_dummy_account_id(),
+            )))
+        };
+
+        let mut ctx =
+            MockContext::default().with_client_type_registry(ClientTypeRegistry::default());
+        assert!(ctx.deliver(create_client()).is_err());
+
+        let mut registry = ClientTypeRegistry::default();
+        registry
+            .register_client_state::<MockClientState>(
+                mock_client_type(),
+                MOCK_CLIENT_STATE_TYPE_URL,
+            )
+            .unwrap();
+        registry
+            .register_consensus_state::<MockConsensusState>(
+                mock_client_type(),
+                MOCK_CONSENSUS_STATE_TYPE_URL,
+            )
+            .unwrap();
+
+        let mut ctx = MockContext::default().with_client_type_registry(registry);
+        ctx.deliver(create_client()).unwrap();
+    }
+}
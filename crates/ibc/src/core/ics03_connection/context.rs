@@ -7,6 +7,7 @@ use crate::core::ics02_client::consensus_state::ConsensusState;
 use crate::core::ics03_connection::connection::ConnectionEnd;
 use crate::core::ics03_connection::error::ConnectionError;
 use crate::core::ics03_connection::handler::ConnectionResult;
+use crate::core::ics03_connection::params::ConnectionParams;
 use crate::core::ics03_connection::version::{get_compatible_versions, pick_version, Version};
 use crate::core::ics23_commitment::commitment::CommitmentPrefix;
 use crate::core::ics24_host::identifier::{ClientId, ConnectionId};
@@ -74,6 +75,9 @@ pub trait ConnectionReader {
     /// `ConnectionKeeper::increase_connection_counter`.
     fn connection_counter(&self) -> Result<u64, ConnectionError>;
 
+    /// Returns the parameters of the connection submodule.
+    fn connection_params(&self) -> Result<ConnectionParams, ConnectionError>;
+
     /// Validates the `ClientState` of the client on the counterparty chain.
     fn validate_self_client(&self, counterparty_client_state: Any) -> Result<(), ConnectionError>;
 }
@@ -117,4 +121,7 @@ pub trait ConnectionKeeper {
     /// Increases the counter which keeps track of how many connections have been created.
     /// Should never fail.
     fn increase_connection_counter(&mut self);
+
+    /// Stores the parameters of the connection submodule, e.g. upon a governance proposal.
+    fn store_connection_params(&mut self, params: ConnectionParams) -> Result<(), ConnectionError>;
 }
@@ -0,0 +1,129 @@
+//! Verification of the delay period of a connection, which must pass between the update of the
+//! underlying client to some height and the verification of packet proofs at that height.
+
+use crate::prelude::*;
+
+use core::time::Duration;
+
+use crate::core::ics03_connection::connection::ConnectionEnd;
+use crate::core::ics03_connection::error::ConnectionError;
+use crate::core::ics04_channel::context::ChannelReader;
+use crate::timestamp::Timestamp;
+use crate::Height;
+
+#[cfg(feature = "val_exec_ctx")]
+use crate::core::ValidationContext;
+
+/// Checks that the delay period of `connection_end` has passed since its client was updated to
+/// `proof_height`, so that proofs at that height may be verified.
+pub(crate) fn verify_conn_delay_passed(
+    ctx: &dyn ChannelReader,
+    proof_height: Height,
+    connection_end: &ConnectionEnd,
+) -> Result<(), ConnectionError> {
+    let current_time = ctx
+        .host_timestamp()
+        .map_err(|_| ConnectionError::MissingHostTimestamp)?;
+    let current_height = ctx
+        .host_height()
+        .map_err(|_| ConnectionError::MissingHostHeight)?;
+
+    let client_id = connection_end.client_id();
+    let processed_time = ctx
+        .client_update_time(client_id, &proof_height)
+        .map_err(|_| ConnectionError::ProcessedTimeNotFound {
+            client_id: client_id.clone(),
+            height: proof_height,
+        })?;
+    let processed_height = ctx
+        .client_update_height(client_id, &proof_height)
+        .map_err(|_| ConnectionError::ProcessedHeightNotFound {
+            client_id: client_id.clone(),
+            height: proof_height,
+        })?;
+
+    let delay_period_time = connection_end.delay_period();
+    let delay_period_blocks = ctx
+        .block_delay(&delay_period_time)
+        .map_err(|_| ConnectionError::MissingConnectionParams)?;
+
+    verify_delay_passed(
+        current_time,
+        current_height,
+        processed_time,
+        processed_height,
+        delay_period_time,
+        delay_period_blocks,
+    )
+}
+
+#[cfg(feature = "val_exec_ctx")]
+pub(crate) fn new_verify_conn_delay_passed(
+    ctx: &dyn ValidationContext,
+    proof_height: Height,
+    connection_end: &ConnectionEnd,
+) -> Result<(), ConnectionError> {
+    let current_time = ctx
+        .host_timestamp()
+        .map_err(|_| ConnectionError::MissingHostTimestamp)?;
+    let current_height = ctx
+        .host_height()
+        .map_err(|_| ConnectionError::MissingHostHeight)?;
+
+    let client_id = connection_end.client_id();
+    let processed_time = ctx
+        .client_update_time(client_id, &proof_height)
+        .map_err(|_| ConnectionError::ProcessedTimeNotFound {
+            client_id: client_id.clone(),
+            height: proof_height,
+        })?;
+    let processed_height = ctx
+        .client_update_height(client_id, &proof_height)
+        .map_err(|_| ConnectionError::ProcessedHeightNotFound {
+            client_id: client_id.clone(),
+            height: proof_height,
+        })?;
+
+    let delay_period_time = connection_end.delay_period();
+    let delay_period_blocks = ctx
+        .block_delay(&delay_period_time)
+        .map_err(|_| ConnectionError::MissingConnectionParams)?;
+
+    verify_delay_passed(
+        current_time,
+        current_height,
+        processed_time,
+        processed_height,
+        delay_period_time,
+        delay_period_blocks,
+    )
+}
+
+/// Verify the time and height delays
+fn verify_delay_passed(
+    current_time: Timestamp,
+    current_height: Height,
+    processed_time: Timestamp,
+    processed_height: Height,
+    delay_period_time: Duration,
+    delay_period_blocks: u64,
+) -> Result<(), ConnectionError> {
+    let earliest_time =
+        (processed_time + delay_period_time).map_err(ConnectionError::TimestampOverflow)?;
+    if !(current_time == earliest_time || current_time.after(&earliest_time)) {
+        return Err(ConnectionError::NotEnoughTimeElapsed {
+            current_time,
+            earliest_time,
+        });
+    }
+
+    let earliest_height = processed_height.add(delay_period_blocks);
+    if current_height < earliest_height {
+        return Err(ConnectionError::NotEnoughBlocksElapsed {
+            current_height,
+            earliest_height,
+        });
+    }
+
+    Ok(())
+}
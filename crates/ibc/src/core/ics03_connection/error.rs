@@ -3,6 +3,7 @@ use crate::core::ics03_connection::version::Version;
 use crate::core::ics24_host::error::ValidationError;
 use crate::core::ics24_host::identifier::{ClientId, ConnectionId};
 use crate::signer::SignerError;
+use crate::timestamp::{Timestamp, TimestampOverflowError};
 use crate::Height;
 
 use alloc::string::String;
@@ -64,6 +65,30 @@ pub enum ConnectionError {
     },
     /// invalid client state: `{reason}`
     InvalidClientState { reason: String },
+    /// processed time for the client `{client_id}` at height `{height}` not found
+    ProcessedTimeNotFound { client_id: ClientId, height: Height },
+    /// processed height for the client `{client_id}` at height `{height}` not found
+    ProcessedHeightNotFound { client_id: ClientId, height: Height },
+    /// timestamp overflowed error: `{0}`
+    TimestampOverflow(TimestampOverflowError),
+    /// not enough time elapsed, current timestamp `{current_time}` is still less than earliest acceptable timestamp `{earliest_time}`
+    NotEnoughTimeElapsed {
+        current_time: Timestamp,
+        earliest_time: Timestamp,
+    },
+    /// not enough blocks elapsed, current height `{current_height}` is still less than earliest acceptable height `{earliest_height}`
+    NotEnoughBlocksElapsed {
+        current_height: Height,
+        earliest_height: Height,
+    },
+    /// invalid connection parameters: `{reason}`
+    InvalidConnectionParams { reason: String },
+    /// the host timestamp could not be read
+    MissingHostTimestamp,
+    /// the host height could not be read
+    MissingHostHeight,
+    /// the connection parameters could not be read
+    MissingConnectionParams,
     /// other error: `{description}`
     Other { description: String },
 }
@@ -76,6 +101,7 @@ impl std::error::Error for ConnectionError {
             Self::InvalidIdentifier(e) => Some(e),
             Self::VerifyConnectionState(e) => Some(e),
             Self::Signer(e) => Some(e),
+            Self::TimestampOverflow(e) => Some(e),
             Self::ConsensusStateVerificationFailure {
                 client_error: e, ..
             } => Some(e),
@@ -1,12 +1,16 @@
 //! This module implements the processing logic for ICS3 (connection open
 //! handshake) messages.
 
+use crate::core::ics02_client::client_state::ClientState;
+use crate::core::ics02_client::context::ClientReader;
 use crate::core::ics03_connection::connection::ConnectionEnd;
 use crate::core::ics03_connection::context::ConnectionReader;
 use crate::core::ics03_connection::error::ConnectionError;
 use crate::core::ics03_connection::msgs::ConnectionMsg;
+use crate::core::ics04_channel::context::ChannelReader;
 use crate::core::ics24_host::identifier::ConnectionId;
 use crate::handler::HandlerOutput;
+use crate::prelude::*;
 
 pub mod conn_open_ack;
 pub mod conn_open_confirm;
@@ -36,6 +40,10 @@ pub struct ConnectionResult {
 
     /// The connection end, which the handler produced as a result of processing the message.
     pub connection_end: ConnectionEnd,
+
+    /// The state of the client of the connection once the proofs of the message were verified
+    /// against it, if the message carries any proofs.
+    pub client_state: Option<Box<dyn ClientState>>,
 }
 
 /// General entry point for processing any type of message related to the ICS3 connection open
@@ -45,7 +53,7 @@ pub(crate) fn dispatch<Ctx>(
     msg: ConnectionMsg,
 ) -> Result<HandlerOutput<ConnectionResult>, ConnectionError>
 where
-    Ctx: ConnectionReader,
+    Ctx: ConnectionReader + ClientReader + ChannelReader,
 {
     match msg {
         ConnectionMsg::OpenInit(msg) => conn_open_init::process(ctx, msg),
@@ -1,21 +1,28 @@
 //! Protocol logic specific to processing ICS3 messages of type `MsgConnectionOpenAck`.
 use crate::prelude::*;
 
+use ibc_proto::protobuf::Protobuf;
+use prost::Message;
+
+use crate::core::ics02_client::context::ClientReader;
+use crate::core::ics02_client::error::ClientError;
 use crate::core::ics03_connection::connection::{ConnectionEnd, Counterparty, State};
 use crate::core::ics03_connection::context::ConnectionReader;
 use crate::core::ics03_connection::error::ConnectionError;
 use crate::core::ics03_connection::events::OpenAck;
 use crate::core::ics03_connection::handler::ConnectionResult;
 use crate::core::ics03_connection::msgs::conn_open_ack::MsgConnectionOpenAck;
+use crate::core::ics04_channel::context::ChannelReader;
+use crate::core::ics24_host::path::{ClientConsensusStatePath, ClientStatePath, ConnectionsPath};
 use crate::events::IbcEvent;
 use crate::handler::{HandlerOutput, HandlerResult};
 
 #[cfg(feature = "val_exec_ctx")]
-use crate::core::context::ContextError;
+use crate::core::context::{store_verified_client_state, ContextError};
 #[cfg(feature = "val_exec_ctx")]
-use crate::core::ics24_host::identifier::ClientId;
+use crate::core::ics02_client::client_state::ClientState;
 #[cfg(feature = "val_exec_ctx")]
-use crate::core::ics24_host::path::ConnectionsPath;
+use crate::core::ics24_host::identifier::ClientId;
 #[cfg(feature = "val_exec_ctx")]
 use crate::core::{ExecutionContext, ValidationContext};
 
@@ -53,7 +60,9 @@ where
     ctx_a.validate_self_client(msg.client_state_of_a_on_b.clone())?;
 
     if !(vars.conn_end_on_a.state_matches(&State::Init)
-        && vars.conn_end_on_a.versions().contains(&msg.version))
+        && msg
+            .version
+            .is_supported_version(vars.conn_end_on_a.versions()))
     {
         return Err(ConnectionError::ConnectionMismatch {
             connection_id: msg.conn_id_on_a.clone(),
@@ -61,85 +70,126 @@ where
         .into());
     }
 
-    // Proof verification.
-    {
-        let client_state_of_b_on_a =
-            ctx_a
-                .client_state(vars.client_id_on_a())
-                .map_err(|_| ConnectionError::Other {
-                    description: "failed to fetch client state".to_string(),
-                })?;
-        let consensus_state_of_b_on_a = ctx_a
-            .consensus_state(vars.client_id_on_a(), &msg.proofs_height_on_b)
+    verify_proofs(ctx_a, msg, vars)?;
+
+    Ok(())
+}
+
+/// Verifies the proofs of the message, and returns the state of the client once they were
+/// verified against it.
+#[cfg(feature = "val_exec_ctx")]
+fn verify_proofs<Ctx>(
+    ctx_a: &Ctx,
+    msg: &MsgConnectionOpenAck,
+    vars: &LocalVars,
+) -> Result<Box<dyn ClientState>, ContextError>
+where
+    Ctx: ValidationContext,
+{
+    let mut client_state_of_b_on_a =
+        ctx_a
+            .client_state(vars.client_id_on_a())
             .map_err(|_| ConnectionError::Other {
-                description: "failed to fetch client consensus state".to_string(),
+                description: "failed to fetch client state".to_string(),
             })?;
 
-        let prefix_on_a = ctx_a.commitment_prefix();
-        let prefix_on_b = vars.conn_end_on_a.counterparty().prefix();
+    // The client must be active.
+    let status = client_state_of_b_on_a.new_status(ctx_a, vars.client_id_on_a());
+    if !status.is_active() {
+        return Err(ClientError::ClientNotActive {
+            client_id: vars.client_id_on_a().clone(),
+            status,
+        }
+        .into());
+    }
 
-        {
-            let expected_conn_end_on_b = ConnectionEnd::new(
-                State::TryOpen,
-                vars.client_id_on_b().clone(),
-                Counterparty::new(
-                    vars.client_id_on_a().clone(),
-                    Some(msg.conn_id_on_a.clone()),
-                    prefix_on_a,
-                ),
-                vec![msg.version.clone()],
-                vars.conn_end_on_a.delay_period(),
-            );
+    let consensus_state_of_b_on_a = ctx_a
+        .consensus_state(vars.client_id_on_a(), &msg.proofs_height_on_b)
+        .map_err(|_| ConnectionError::Other {
+            description: "failed to fetch client consensus state".to_string(),
+        })?;
 
-            client_state_of_b_on_a
-                .verify_connection_state(
-                    msg.proofs_height_on_b,
-                    prefix_on_b,
-                    &msg.proof_conn_end_on_b,
-                    consensus_state_of_b_on_a.root(),
-                    &msg.conn_id_on_b,
-                    &expected_conn_end_on_b,
-                )
-                .map_err(ConnectionError::VerifyConnectionState)?;
-        }
+    let prefix_on_a = ctx_a.commitment_prefix();
+    let prefix_on_b = vars.conn_end_on_a.counterparty().prefix();
 
-        client_state_of_b_on_a
-            .verify_client_full_state(
-                msg.proofs_height_on_b,
-                prefix_on_b,
-                &msg.proof_client_state_of_a_on_b,
-                consensus_state_of_b_on_a.root(),
-                vars.client_id_on_b(),
-                msg.client_state_of_a_on_b.clone(),
-            )
-            .map_err(|e| ConnectionError::ClientStateVerificationFailure {
-                client_id: vars.client_id_on_a().clone(),
-                client_error: e,
-            })?;
+    client_state_of_b_on_a
+        .validate_proof_height(msg.proofs_height_on_b)
+        .map_err(ConnectionError::Client)?;
 
-        let expected_consensus_state_of_a_on_b = ctx_a
-            .host_consensus_state(&msg.consensus_height_of_a_on_b)
-            .map_err(|_| ConnectionError::Other {
-                description: "failed to fetch host consensus state".to_string(),
-            })?;
+    {
+        let expected_conn_end_on_b = ConnectionEnd::new(
+            State::TryOpen,
+            vars.client_id_on_b().clone(),
+            Counterparty::new(
+                vars.client_id_on_a().clone(),
+                Some(msg.conn_id_on_a.clone()),
+                prefix_on_a,
+            ),
+            vec![msg.version.clone()],
+            vars.conn_end_on_a.delay_period(),
+        );
+
+        let value = expected_conn_end_on_b.encode_vec().map_err(|e| {
+            ConnectionError::VerifyConnectionState(ClientError::InvalidConnectionEnd(e))
+        })?;
 
         client_state_of_b_on_a
-            .verify_client_consensus_state(
-                msg.proofs_height_on_b,
+            .new_verify_membership(
+                ctx_a,
                 prefix_on_b,
-                &msg.proof_consensus_state_of_a_on_b,
+                &msg.proof_conn_end_on_b,
                 consensus_state_of_b_on_a.root(),
-                vars.client_id_on_b(),
-                msg.consensus_height_of_a_on_b,
-                expected_consensus_state_of_a_on_b.as_ref(),
+                ConnectionsPath(msg.conn_id_on_b.clone()).into(),
+                value,
             )
-            .map_err(|e| ConnectionError::ConsensusStateVerificationFailure {
-                height: msg.proofs_height_on_b,
-                client_error: e,
-            })?;
+            .map_err(ConnectionError::VerifyConnectionState)?;
     }
 
-    Ok(())
+    client_state_of_b_on_a
+        .new_verify_membership(
+            ctx_a,
+            prefix_on_b,
+            &msg.proof_client_state_of_a_on_b,
+            consensus_state_of_b_on_a.root(),
+            ClientStatePath(vars.client_id_on_b().clone()).into(),
+            msg.client_state_of_a_on_b.encode_to_vec(),
+        )
+        .map_err(|e| ConnectionError::ClientStateVerificationFailure {
+            client_id: vars.client_id_on_a().clone(),
+            client_error: e,
+        })?;
+
+    let expected_consensus_state_of_a_on_b = ctx_a
+        .host_consensus_state(&msg.consensus_height_of_a_on_b)
+        .map_err(|_| ConnectionError::Other {
+            description: "failed to fetch host consensus state".to_string(),
+        })?;
+    let value = expected_consensus_state_of_a_on_b
+        .encode_vec()
+        .map_err(|e| ConnectionError::ConsensusStateVerificationFailure {
+            height: msg.proofs_height_on_b,
+            client_error: ClientError::InvalidAnyConsensusState(e),
+        })?;
+
+    client_state_of_b_on_a
+        .new_verify_membership(
+            ctx_a,
+            prefix_on_b,
+            &msg.proof_consensus_state_of_a_on_b,
+            consensus_state_of_b_on_a.root(),
+            ClientConsensusStatePath::new(
+                vars.client_id_on_b().clone(),
+                msg.consensus_height_of_a_on_b,
+            )
+            .into(),
+            value,
+        )
+        .map_err(|e| ConnectionError::ConsensusStateVerificationFailure {
+            height: msg.proofs_height_on_b,
+            client_error: e,
+        })?;
+
+    Ok(client_state_of_b_on_a)
 }
 
 #[cfg(feature = "val_exec_ctx")]
@@ -169,6 +219,9 @@ where
 
     ctx_a.log_message("success: conn_open_ack verification passed".to_string());
 
+    let client_state_of_b_on_a = verify_proofs(ctx_a, &msg, &vars)?;
+    store_verified_client_state(ctx_a, vars.client_id_on_a(), client_state_of_b_on_a)?;
+
     {
         let new_conn_end_on_a = {
             let mut counterparty = vars.conn_end_on_a.counterparty().clone();
@@ -213,8 +266,8 @@ impl LocalVars {
 }
 
 /// Per our convention, this message is processed on chain A.
-pub(crate) fn process(
-    ctx_a: &dyn ConnectionReader,
+pub(crate) fn process<Ctx: ConnectionReader + ClientReader + ChannelReader>(
+    ctx_a: &Ctx,
     msg: MsgConnectionOpenAck,
 ) -> HandlerResult<ConnectionResult, ConnectionError> {
     let mut output = HandlerOutput::builder();
@@ -228,9 +281,9 @@ pub(crate) fn process(
 
     ctx_a.validate_self_client(msg.client_state_of_a_on_b.clone())?;
 
-    let conn_end_on_a = ctx_a.connection_end(&msg.conn_id_on_a)?;
+    let conn_end_on_a = ConnectionReader::connection_end(ctx_a, &msg.conn_id_on_a)?;
     if !(conn_end_on_a.state_matches(&State::Init)
-        && conn_end_on_a.versions().contains(&msg.version))
+        && msg.version.is_supported_version(conn_end_on_a.versions()))
     {
         return Err(ConnectionError::ConnectionMismatch {
             connection_id: msg.conn_id_on_a,
@@ -241,14 +294,31 @@ pub(crate) fn process(
     let client_id_on_b = conn_end_on_a.counterparty().client_id();
 
     // Proof verification.
-    {
-        let client_state_of_b_on_a = ctx_a.client_state(client_id_on_a)?;
-        let consensus_state_of_b_on_a =
-            ctx_a.client_consensus_state(conn_end_on_a.client_id(), &msg.proofs_height_on_b)?;
+    let client_state_of_b_on_a = {
+        let mut client_state_of_b_on_a = ConnectionReader::client_state(ctx_a, client_id_on_a)?;
+
+        // The client must be active.
+        let status = client_state_of_b_on_a.status(ctx_a, client_id_on_a);
+        if !status.is_active() {
+            return Err(ConnectionError::Client(ClientError::ClientNotActive {
+                client_id: client_id_on_a.clone(),
+                status,
+            }));
+        }
+
+        let consensus_state_of_b_on_a = ConnectionReader::client_consensus_state(
+            ctx_a,
+            conn_end_on_a.client_id(),
+            &msg.proofs_height_on_b,
+        )?;
 
         let prefix_on_a = ctx_a.commitment_prefix();
         let prefix_on_b = conn_end_on_a.counterparty().prefix();
 
+        client_state_of_b_on_a
+            .validate_proof_height(msg.proofs_height_on_b)
+            .map_err(ConnectionError::Client)?;
+
         {
             let expected_conn_end_on_b = ConnectionEnd::new(
                 State::TryOpen,
@@ -262,26 +332,30 @@ pub(crate) fn process(
                 conn_end_on_a.delay_period(),
             );
 
+            let value = expected_conn_end_on_b.encode_vec().map_err(|e| {
+                ConnectionError::VerifyConnectionState(ClientError::InvalidConnectionEnd(e))
+            })?;
+
             client_state_of_b_on_a
-                .verify_connection_state(
-                    msg.proofs_height_on_b,
+                .verify_membership(
+                    ctx_a,
                     prefix_on_b,
                     &msg.proof_conn_end_on_b,
                     consensus_state_of_b_on_a.root(),
-                    &msg.conn_id_on_b,
-                    &expected_conn_end_on_b,
+                    ConnectionsPath(msg.conn_id_on_b.clone()).into(),
+                    value,
                 )
                 .map_err(ConnectionError::VerifyConnectionState)?;
         }
 
         client_state_of_b_on_a
-            .verify_client_full_state(
-                msg.proofs_height_on_b,
+            .verify_membership(
+                ctx_a,
                 prefix_on_b,
                 &msg.proof_client_state_of_a_on_b,
                 consensus_state_of_b_on_a.root(),
-                client_id_on_b,
-                msg.client_state_of_a_on_b,
+                ClientStatePath(client_id_on_b.clone()).into(),
+                msg.client_state_of_a_on_b.encode_to_vec(),
             )
             .map_err(|e| ConnectionError::ClientStateVerificationFailure {
                 client_id: conn_end_on_a.client_id().clone(),
@@ -289,22 +363,33 @@ pub(crate) fn process(
             })?;
 
         let expected_consensus_state_of_a_on_b =
-            ctx_a.host_consensus_state(&msg.consensus_height_of_a_on_b)?;
+            ConnectionReader::host_consensus_state(ctx_a, &msg.consensus_height_of_a_on_b)?;
+        let value = expected_consensus_state_of_a_on_b
+            .encode_vec()
+            .map_err(|e| ConnectionError::ConsensusStateVerificationFailure {
+                height: msg.proofs_height_on_b,
+                client_error: ClientError::InvalidAnyConsensusState(e),
+            })?;
         client_state_of_b_on_a
-            .verify_client_consensus_state(
-                msg.proofs_height_on_b,
+            .verify_membership(
+                ctx_a,
                 prefix_on_b,
                 &msg.proof_consensus_state_of_a_on_b,
                 consensus_state_of_b_on_a.root(),
-                conn_end_on_a.counterparty().client_id(),
-                msg.consensus_height_of_a_on_b,
-                expected_consensus_state_of_a_on_b.as_ref(),
+                ClientConsensusStatePath::new(
+                    client_id_on_b.clone(),
+                    msg.consensus_height_of_a_on_b,
+                )
+                .into(),
+                value,
             )
             .map_err(|e| ConnectionError::ConsensusStateVerificationFailure {
                 height: msg.proofs_height_on_b,
                 client_error: e,
             })?;
-    }
+
+        client_state_of_b_on_a
+    };
 
     // Success
     output.emit(IbcEvent::OpenAckConnection(OpenAck::new(
@@ -331,6 +416,7 @@ pub(crate) fn process(
             connection_id: msg.conn_id_on_a,
             connection_id_state: ConnectionIdState::Reused,
             connection_end: new_conn_end_on_a,
+            client_state: Some(client_state_of_b_on_a),
         }
     };
 
@@ -1,21 +1,27 @@
 //! Protocol logic specific to processing ICS3 messages of type `MsgConnectionOpenConfirm`.
 use crate::prelude::*;
 
+use ibc_proto::protobuf::Protobuf;
+
+use crate::core::ics02_client::context::ClientReader;
+use crate::core::ics02_client::error::ClientError;
 use crate::core::ics03_connection::connection::{ConnectionEnd, Counterparty, State};
 use crate::core::ics03_connection::context::ConnectionReader;
 use crate::core::ics03_connection::error::ConnectionError;
 use crate::core::ics03_connection::events::OpenConfirm;
 use crate::core::ics03_connection::handler::{ConnectionIdState, ConnectionResult};
 use crate::core::ics03_connection::msgs::conn_open_confirm::MsgConnectionOpenConfirm;
+use crate::core::ics04_channel::context::ChannelReader;
+use crate::core::ics24_host::path::ConnectionsPath;
 use crate::events::IbcEvent;
 use crate::handler::{HandlerOutput, HandlerResult};
 
 #[cfg(feature = "val_exec_ctx")]
-use crate::core::context::ContextError;
+use crate::core::context::{store_verified_client_state, ContextError};
 #[cfg(feature = "val_exec_ctx")]
-use crate::core::ics24_host::identifier::{ClientId, ConnectionId};
+use crate::core::ics02_client::client_state::ClientState;
 #[cfg(feature = "val_exec_ctx")]
-use crate::core::ics24_host::path::ConnectionsPath;
+use crate::core::ics24_host::identifier::{ClientId, ConnectionId};
 #[cfg(feature = "val_exec_ctx")]
 use crate::core::{ExecutionContext, ValidationContext};
 
@@ -45,52 +51,84 @@ where
         .into());
     }
 
+    verify_proofs(ctx_b, msg, vars)?;
+
+    Ok(())
+}
+
+/// Verifies the proof of the message, and returns the state of the client once it was verified
+/// against it.
+#[cfg(feature = "val_exec_ctx")]
+fn verify_proofs<Ctx>(
+    ctx_b: &Ctx,
+    msg: &MsgConnectionOpenConfirm,
+    vars: &LocalVars,
+) -> Result<Box<dyn ClientState>, ContextError>
+where
+    Ctx: ValidationContext,
+{
+    let conn_end_on_b = vars.conn_end_on_b();
     let client_id_on_a = vars.client_id_on_a();
     let client_id_on_b = vars.client_id_on_b();
     let conn_id_on_a = vars.conn_id_on_a()?;
 
-    // Verify proofs
-    {
-        let client_state_of_a_on_b =
-            ctx_b
-                .client_state(client_id_on_b)
-                .map_err(|_| ConnectionError::Other {
-                    description: "failed to fetch client state".to_string(),
-                })?;
-        let consensus_state_of_a_on_b = ctx_b
-            .consensus_state(client_id_on_b, &msg.proof_height_on_a)
+    let mut client_state_of_a_on_b =
+        ctx_b
+            .client_state(client_id_on_b)
             .map_err(|_| ConnectionError::Other {
-                description: "failed to fetch client consensus state".to_string(),
+                description: "failed to fetch client state".to_string(),
             })?;
 
-        let prefix_on_a = conn_end_on_b.counterparty().prefix();
-        let prefix_on_b = ctx_b.commitment_prefix();
+    // The client must be active.
+    let status = client_state_of_a_on_b.new_status(ctx_b, client_id_on_b);
+    if !status.is_active() {
+        return Err(ClientError::ClientNotActive {
+            client_id: client_id_on_b.clone(),
+            status,
+        }
+        .into());
+    }
 
-        let expected_conn_end_on_a = ConnectionEnd::new(
-            State::Open,
-            client_id_on_a.clone(),
-            Counterparty::new(
-                client_id_on_b.clone(),
-                Some(msg.conn_id_on_b.clone()),
-                prefix_on_b,
-            ),
-            conn_end_on_b.versions().to_vec(),
-            conn_end_on_b.delay_period(),
-        );
+    let consensus_state_of_a_on_b = ctx_b
+        .consensus_state(client_id_on_b, &msg.proof_height_on_a)
+        .map_err(|_| ConnectionError::Other {
+            description: "failed to fetch client consensus state".to_string(),
+        })?;
 
-        client_state_of_a_on_b
-            .verify_connection_state(
-                msg.proof_height_on_a,
-                prefix_on_a,
-                &msg.proof_conn_end_on_a,
-                consensus_state_of_a_on_b.root(),
-                conn_id_on_a,
-                &expected_conn_end_on_a,
-            )
-            .map_err(ConnectionError::VerifyConnectionState)?;
-    }
+    let prefix_on_a = conn_end_on_b.counterparty().prefix();
+    let prefix_on_b = ctx_b.commitment_prefix();
 
-    Ok(())
+    let expected_conn_end_on_a = ConnectionEnd::new(
+        State::Open,
+        client_id_on_a.clone(),
+        Counterparty::new(
+            client_id_on_b.clone(),
+            Some(msg.conn_id_on_b.clone()),
+            prefix_on_b,
+        ),
+        conn_end_on_b.versions().to_vec(),
+        conn_end_on_b.delay_period(),
+    );
+
+    let value = expected_conn_end_on_a.encode_vec().map_err(|e| {
+        ConnectionError::VerifyConnectionState(ClientError::InvalidConnectionEnd(e))
+    })?;
+
+    client_state_of_a_on_b
+        .validate_proof_height(msg.proof_height_on_a)
+        .map_err(ConnectionError::Client)?;
+    client_state_of_a_on_b
+        .new_verify_membership(
+            ctx_b,
+            prefix_on_a,
+            &msg.proof_conn_end_on_a,
+            consensus_state_of_a_on_b.root(),
+            ConnectionsPath(conn_id_on_a.clone()).into(),
+            value,
+        )
+        .map_err(ConnectionError::VerifyConnectionState)?;
+
+    Ok(client_state_of_a_on_b)
 }
 
 #[cfg(feature = "val_exec_ctx")]
@@ -126,6 +164,9 @@ where
     )));
     ctx_b.log_message("success: conn_open_confirm verification passed".to_string());
 
+    let client_state_of_a_on_b = verify_proofs(ctx_b, msg, &vars)?;
+    store_verified_client_state(ctx_b, client_id_on_b, client_state_of_a_on_b)?;
+
     {
         let new_conn_end_on_b = {
             let mut new_conn_end_on_b = vars.conn_end_on_b;
@@ -177,13 +218,13 @@ impl LocalVars {
 }
 
 /// Per our convention, this message is processed on chain B.
-pub(crate) fn process(
-    ctx_b: &dyn ConnectionReader,
+pub(crate) fn process<Ctx: ConnectionReader + ClientReader + ChannelReader>(
+    ctx_b: &Ctx,
     msg: MsgConnectionOpenConfirm,
 ) -> HandlerResult<ConnectionResult, ConnectionError> {
     let mut output = HandlerOutput::builder();
 
-    let conn_end_on_b = ctx_b.connection_end(&msg.conn_id_on_b)?;
+    let conn_end_on_b = ConnectionReader::connection_end(ctx_b, &msg.conn_id_on_b)?;
     if !conn_end_on_b.state_matches(&State::TryOpen) {
         return Err(ConnectionError::ConnectionMismatch {
             connection_id: msg.conn_id_on_b,
@@ -197,10 +238,23 @@ pub(crate) fn process(
         .ok_or(ConnectionError::InvalidCounterparty)?;
 
     // Verify proofs
-    {
-        let client_state_of_a_on_b = ctx_b.client_state(client_id_on_b)?;
-        let consensus_state_of_a_on_b =
-            ctx_b.client_consensus_state(client_id_on_b, &msg.proof_height_on_a)?;
+    let client_state_of_a_on_b = {
+        let mut client_state_of_a_on_b = ConnectionReader::client_state(ctx_b, client_id_on_b)?;
+
+        // The client must be active.
+        let status = client_state_of_a_on_b.status(ctx_b, client_id_on_b);
+        if !status.is_active() {
+            return Err(ConnectionError::Client(ClientError::ClientNotActive {
+                client_id: client_id_on_b.clone(),
+                status,
+            }));
+        }
+
+        let consensus_state_of_a_on_b = ConnectionReader::client_consensus_state(
+            ctx_b,
+            client_id_on_b,
+            &msg.proof_height_on_a,
+        )?;
 
         let prefix_on_a = conn_end_on_b.counterparty().prefix();
         let prefix_on_b = ctx_b.commitment_prefix();
@@ -217,17 +271,26 @@ pub(crate) fn process(
             conn_end_on_b.delay_period(),
         );
 
+        let value = expected_conn_end_on_a.encode_vec().map_err(|e| {
+            ConnectionError::VerifyConnectionState(ClientError::InvalidConnectionEnd(e))
+        })?;
+
+        client_state_of_a_on_b
+            .validate_proof_height(msg.proof_height_on_a)
+            .map_err(ConnectionError::Client)?;
         client_state_of_a_on_b
-            .verify_connection_state(
-                msg.proof_height_on_a,
+            .verify_membership(
+                ctx_b,
                 prefix_on_a,
                 &msg.proof_conn_end_on_a,
                 consensus_state_of_a_on_b.root(),
-                conn_id_on_a,
-                &expected_conn_end_on_a,
+                ConnectionsPath(conn_id_on_a.clone()).into(),
+                value,
             )
             .map_err(ConnectionError::VerifyConnectionState)?;
-    }
+
+        client_state_of_a_on_b
+    };
 
     // Success
     output.emit(IbcEvent::OpenConfirmConnection(OpenConfirm::new(
@@ -250,6 +313,7 @@ pub(crate) fn process(
             connection_id: msg.conn_id_on_b,
             connection_id_state: ConnectionIdState::Reused,
             connection_end: new_conn_end_on_b,
+            client_state: Some(client_state_of_a_on_b),
         }
     };
 
@@ -29,9 +29,7 @@ where
     ctx_a.client_state(&msg.client_id_on_a)?;
 
     if let Some(version) = msg.version {
-        if !ctx_a.get_compatible_versions().contains(&version) {
-            return Err(ConnectionError::VersionNotSupported { version }.into());
-        }
+        version.verify_is_supported(&ctx_a.get_compatible_versions())?;
     }
 
     Ok(())
@@ -43,13 +41,9 @@ where
     Ctx: ExecutionContext,
 {
     let versions = match msg.version {
-        Some(version) => {
-            if ctx_a.get_compatible_versions().contains(&version) {
-                Ok(vec![version])
-            } else {
-                Err(ConnectionError::VersionNotSupported { version })
-            }
-        }
+        Some(version) => version
+            .verify_is_supported(&ctx_a.get_compatible_versions())
+            .map(|_| vec![version]),
         None => Ok(ctx_a.get_compatible_versions()),
     }?;
 
@@ -103,13 +97,9 @@ pub(crate) fn process(
     ctx_a.client_state(&msg.client_id_on_a)?;
 
     let versions = match msg.version {
-        Some(version) => {
-            if ctx_a.get_compatible_versions().contains(&version) {
-                Ok(vec![version])
-            } else {
-                Err(ConnectionError::VersionNotSupported { version })
-            }
-        }
+        Some(version) => version
+            .verify_is_supported(&ctx_a.get_compatible_versions())
+            .map(|_| vec![version]),
         None => Ok(ctx_a.get_compatible_versions()),
     }?;
 
@@ -132,6 +122,7 @@ pub(crate) fn process(
         connection_id: conn_id_on_a.clone(),
         connection_end: conn_end_on_a,
         connection_id_state: ConnectionIdState::Generated,
+        client_state: None,
     };
 
     output.log(format!(
@@ -205,6 +196,17 @@ mod tests {
             &msg_conn_init_default.client_id_on_a,
             Height::new(0, 10).unwrap(),
         );
+        // A host which offers a custom feature, and no ordered channels
+        let custom_versions = vec![Version::new(
+            "1".to_string(),
+            vec![
+                "ORDER_UNORDERED".to_string(),
+                "ORDER_ORDERED_ALLOW_TIMEOUT".to_string(),
+            ],
+        )];
+        let custom_context = good_context
+            .clone()
+            .with_connection_versions(custom_versions.clone());
 
         let tests: Vec<Test> = vec![
             Test {
@@ -224,10 +226,25 @@ mod tests {
             Test {
                 name: "No version in MsgConnectionOpenInit msg".to_string(),
                 ctx: good_context.clone(),
-                msg: ConnectionMsg::OpenInit(msg_conn_init_no_version),
+                msg: ConnectionMsg::OpenInit(msg_conn_init_no_version.clone()),
                 expected_versions: ConnectionReader::get_compatible_versions(&good_context),
                 want_pass: true,
             },
+            Test {
+                name: "Version unsupported by the host in MsgConnectionOpenInit msg".to_string(),
+                ctx: custom_context.clone(),
+                msg: ConnectionMsg::OpenInit(msg_conn_init_default.clone()),
+                expected_versions: vec![],
+                want_pass: false,
+            },
+            Test {
+                name: "No version in MsgConnectionOpenInit msg on a host with custom versions"
+                    .to_string(),
+                ctx: custom_context,
+                msg: ConnectionMsg::OpenInit(msg_conn_init_no_version.clone()),
+                expected_versions: custom_versions,
+                want_pass: true,
+            },
             Test {
                 name: "Good parameters".to_string(),
                 ctx: good_context,
@@ -1,24 +1,33 @@
 //! Protocol logic specific to processing ICS3 messages of type `MsgConnectionOpenTry`.
 use crate::prelude::*;
 
+use ibc_proto::protobuf::Protobuf;
+use prost::Message;
+
+use crate::core::ics02_client::context::ClientReader;
+use crate::core::ics02_client::error::ClientError;
 use crate::core::ics03_connection::connection::{ConnectionEnd, Counterparty, State};
 use crate::core::ics03_connection::context::ConnectionReader;
 use crate::core::ics03_connection::error::ConnectionError;
 use crate::core::ics03_connection::events::OpenTry;
 use crate::core::ics03_connection::handler::ConnectionResult;
 use crate::core::ics03_connection::msgs::conn_open_try::MsgConnectionOpenTry;
+use crate::core::ics04_channel::context::ChannelReader;
 use crate::core::ics24_host::identifier::ConnectionId;
+use crate::core::ics24_host::path::{ClientConsensusStatePath, ClientStatePath, ConnectionsPath};
 use crate::events::IbcEvent;
 use crate::handler::{HandlerOutput, HandlerResult};
 
 use super::ConnectionIdState;
 
 #[cfg(feature = "val_exec_ctx")]
-use crate::core::context::ContextError;
+use crate::core::context::{store_verified_client_state, ContextError};
+#[cfg(feature = "val_exec_ctx")]
+use crate::core::ics02_client::client_state::ClientState;
 #[cfg(feature = "val_exec_ctx")]
 use crate::core::ics24_host::identifier::ClientId;
 #[cfg(feature = "val_exec_ctx")]
-use crate::core::ics24_host::path::{ClientConnectionsPath, ConnectionsPath};
+use crate::core::ics24_host::path::ClientConnectionsPath;
 #[cfg(feature = "val_exec_ctx")]
 use crate::core::{ExecutionContext, ValidationContext};
 
@@ -54,82 +63,120 @@ where
         .into());
     }
 
+    verify_proofs(ctx_b, msg, vars)?;
+
+    Ok(())
+}
+
+/// Verifies the proofs of the message, and returns the state of the client once they were
+/// verified against it.
+#[cfg(feature = "val_exec_ctx")]
+fn verify_proofs<Ctx>(
+    ctx_b: &Ctx,
+    msg: &MsgConnectionOpenTry,
+    vars: &LocalVars,
+) -> Result<Box<dyn ClientState>, ContextError>
+where
+    Ctx: ValidationContext,
+{
     let client_id_on_a = msg.counterparty.client_id();
 
-    // Verify proofs
-    {
-        let client_state_of_a_on_b =
-            ctx_b
-                .client_state(vars.conn_end_on_b.client_id())
-                .map_err(|_| ConnectionError::Other {
-                    description: "failed to fetch client state".to_string(),
-                })?;
-        let consensus_state_of_a_on_b = ctx_b
-            .consensus_state(&msg.client_id_on_b, &msg.proofs_height_on_a)
+    let mut client_state_of_a_on_b =
+        ctx_b
+            .client_state(vars.conn_end_on_b.client_id())
             .map_err(|_| ConnectionError::Other {
-                description: "failed to fetch client consensus state".to_string(),
+                description: "failed to fetch client state".to_string(),
             })?;
 
-        let prefix_on_a = vars.conn_end_on_b.counterparty().prefix();
-        let prefix_on_b = ctx_b.commitment_prefix();
+    // The client must be active.
+    let status = client_state_of_a_on_b.new_status(ctx_b, vars.conn_end_on_b.client_id());
+    if !status.is_active() {
+        return Err(ClientError::ClientNotActive {
+            client_id: vars.conn_end_on_b.client_id().clone(),
+            status,
+        }
+        .into());
+    }
 
-        {
-            let expected_conn_end_on_a = ConnectionEnd::new(
-                State::Init,
-                client_id_on_a.clone(),
-                Counterparty::new(msg.client_id_on_b.clone(), None, prefix_on_b),
-                msg.versions_on_a.clone(),
-                msg.delay_period,
-            );
+    let consensus_state_of_a_on_b = ctx_b
+        .consensus_state(&msg.client_id_on_b, &msg.proofs_height_on_a)
+        .map_err(|_| ConnectionError::Other {
+            description: "failed to fetch client consensus state".to_string(),
+        })?;
 
-            client_state_of_a_on_b
-                .verify_connection_state(
-                    msg.proofs_height_on_a,
-                    prefix_on_a,
-                    &msg.proof_conn_end_on_a,
-                    consensus_state_of_a_on_b.root(),
-                    &vars.conn_id_on_a,
-                    &expected_conn_end_on_a,
-                )
-                .map_err(ConnectionError::VerifyConnectionState)?;
-        }
+    let prefix_on_a = vars.conn_end_on_b.counterparty().prefix();
+    let prefix_on_b = ctx_b.commitment_prefix();
 
-        client_state_of_a_on_b
-            .verify_client_full_state(
-                msg.proofs_height_on_a,
-                prefix_on_a,
-                &msg.proof_client_state_of_b_on_a,
-                consensus_state_of_a_on_b.root(),
-                client_id_on_a,
-                msg.client_state_of_b_on_a.clone(),
-            )
-            .map_err(|e| ConnectionError::ClientStateVerificationFailure {
-                client_id: msg.client_id_on_b.clone(),
-                client_error: e,
-            })?;
+    client_state_of_a_on_b
+        .validate_proof_height(msg.proofs_height_on_a)
+        .map_err(ConnectionError::Client)?;
+
+    {
+        let expected_conn_end_on_a = ConnectionEnd::new(
+            State::Init,
+            client_id_on_a.clone(),
+            Counterparty::new(msg.client_id_on_b.clone(), None, prefix_on_b),
+            msg.versions_on_a.clone(),
+            msg.delay_period,
+        );
+
+        let value = expected_conn_end_on_a.encode_vec().map_err(|e| {
+            ConnectionError::VerifyConnectionState(ClientError::InvalidConnectionEnd(e))
+        })?;
 
-        let expected_consensus_state_of_b_on_a = ctx_b
-            .host_consensus_state(&msg.consensus_height_of_b_on_a)
-            .map_err(|_| ConnectionError::Other {
-                description: "failed to fetch host consensus state".to_string(),
-            })?;
         client_state_of_a_on_b
-            .verify_client_consensus_state(
-                msg.proofs_height_on_a,
+            .new_verify_membership(
+                ctx_b,
                 prefix_on_a,
-                &msg.proof_consensus_state_of_b_on_a,
+                &msg.proof_conn_end_on_a,
                 consensus_state_of_a_on_b.root(),
-                client_id_on_a,
-                msg.consensus_height_of_b_on_a,
-                expected_consensus_state_of_b_on_a.as_ref(),
+                ConnectionsPath(vars.conn_id_on_a.clone()).into(),
+                value,
             )
-            .map_err(|e| ConnectionError::ClientStateVerificationFailure {
-                client_id: client_id_on_a.clone(),
-                client_error: e,
-            })?;
+            .map_err(ConnectionError::VerifyConnectionState)?;
     }
 
-    Ok(())
+    client_state_of_a_on_b
+        .new_verify_membership(
+            ctx_b,
+            prefix_on_a,
+            &msg.proof_client_state_of_b_on_a,
+            consensus_state_of_a_on_b.root(),
+            ClientStatePath(client_id_on_a.clone()).into(),
+            msg.client_state_of_b_on_a.encode_to_vec(),
+        )
+        .map_err(|e| ConnectionError::ClientStateVerificationFailure {
+            client_id: msg.client_id_on_b.clone(),
+            client_error: e,
+        })?;
+
+    let expected_consensus_state_of_b_on_a = ctx_b
+        .host_consensus_state(&msg.consensus_height_of_b_on_a)
+        .map_err(|_| ConnectionError::Other {
+            description: "failed to fetch host consensus state".to_string(),
+        })?;
+    let value = expected_consensus_state_of_b_on_a
+        .encode_vec()
+        .map_err(|e| ConnectionError::ClientStateVerificationFailure {
+            client_id: client_id_on_a.clone(),
+            client_error: ClientError::InvalidAnyConsensusState(e),
+        })?;
+    client_state_of_a_on_b
+        .new_verify_membership(
+            ctx_b,
+            prefix_on_a,
+            &msg.proof_consensus_state_of_b_on_a,
+            consensus_state_of_a_on_b.root(),
+            ClientConsensusStatePath::new(client_id_on_a.clone(), msg.consensus_height_of_b_on_a)
+                .into(),
+            value,
+        )
+        .map_err(|e| ConnectionError::ClientStateVerificationFailure {
+            client_id: client_id_on_a.clone(),
+            client_error: e,
+        })?;
+
+    Ok(client_state_of_a_on_b)
 }
 
 #[cfg(feature = "val_exec_ctx")]
@@ -163,6 +210,9 @@ where
     )));
     ctx_b.log_message("success: conn_open_try verification passed".to_string());
 
+    let client_state_of_a_on_b = verify_proofs(ctx_b, &msg, &vars)?;
+    store_verified_client_state(ctx_b, &msg.client_id_on_b, client_state_of_a_on_b)?;
+
     ctx_b.increase_connection_counter();
     ctx_b.store_connection_to_client(
         ClientConnectionsPath(msg.client_id_on_b),
@@ -210,8 +260,8 @@ impl LocalVars {
 }
 
 /// Per our convention, this message is processed on chain B.
-pub(crate) fn process(
-    ctx_b: &dyn ConnectionReader,
+pub(crate) fn process<Ctx: ConnectionReader + ClientReader + ChannelReader>(
+    ctx_b: &Ctx,
     msg: MsgConnectionOpenTry,
 ) -> HandlerResult<ConnectionResult, ConnectionError> {
     let mut output = HandlerOutput::builder();
@@ -245,14 +295,32 @@ pub(crate) fn process(
         .ok_or(ConnectionError::InvalidCounterparty)?;
 
     // Verify proofs
-    {
-        let client_state_of_a_on_b = ctx_b.client_state(conn_end_on_b.client_id())?;
-        let consensus_state_of_a_on_b =
-            ctx_b.client_consensus_state(&msg.client_id_on_b, &msg.proofs_height_on_a)?;
+    let client_state_of_a_on_b = {
+        let mut client_state_of_a_on_b =
+            ConnectionReader::client_state(ctx_b, conn_end_on_b.client_id())?;
+
+        // The client must be active.
+        let status = client_state_of_a_on_b.status(ctx_b, conn_end_on_b.client_id());
+        if !status.is_active() {
+            return Err(ConnectionError::Client(ClientError::ClientNotActive {
+                client_id: conn_end_on_b.client_id().clone(),
+                status,
+            }));
+        }
+
+        let consensus_state_of_a_on_b = ConnectionReader::client_consensus_state(
+            ctx_b,
+            &msg.client_id_on_b,
+            &msg.proofs_height_on_a,
+        )?;
 
         let prefix_on_a = conn_end_on_b.counterparty().prefix();
         let prefix_on_b = ctx_b.commitment_prefix();
 
+        client_state_of_a_on_b
+            .validate_proof_height(msg.proofs_height_on_a)
+            .map_err(ConnectionError::Client)?;
+
         {
             let versions_on_a = msg.versions_on_a;
             let expected_conn_end_on_a = ConnectionEnd::new(
@@ -262,27 +330,30 @@ pub(crate) fn process(
                 versions_on_a,
                 msg.delay_period,
             );
+            let value = expected_conn_end_on_a.encode_vec().map_err(|e| {
+                ConnectionError::VerifyConnectionState(ClientError::InvalidConnectionEnd(e))
+            })?;
 
             client_state_of_a_on_b
-                .verify_connection_state(
-                    msg.proofs_height_on_a,
+                .verify_membership(
+                    ctx_b,
                     prefix_on_a,
                     &msg.proof_conn_end_on_a,
                     consensus_state_of_a_on_b.root(),
-                    conn_id_on_a,
-                    &expected_conn_end_on_a,
+                    ConnectionsPath(conn_id_on_a.clone()).into(),
+                    value,
                 )
                 .map_err(ConnectionError::VerifyConnectionState)?;
         }
 
         client_state_of_a_on_b
-            .verify_client_full_state(
-                msg.proofs_height_on_a,
+            .verify_membership(
+                ctx_b,
                 prefix_on_a,
                 &msg.proof_client_state_of_b_on_a,
                 consensus_state_of_a_on_b.root(),
-                client_id_on_a,
-                msg.client_state_of_b_on_a,
+                ClientStatePath(client_id_on_a.clone()).into(),
+                msg.client_state_of_b_on_a.encode_to_vec(),
             )
             .map_err(|e| ConnectionError::ClientStateVerificationFailure {
                 client_id: conn_end_on_b.client_id().clone(),
@@ -290,22 +361,33 @@ pub(crate) fn process(
             })?;
 
         let expected_consensus_state_of_b_on_a =
-            ctx_b.host_consensus_state(&msg.consensus_height_of_b_on_a)?;
+            ConnectionReader::host_consensus_state(ctx_b, &msg.consensus_height_of_b_on_a)?;
+        let value = expected_consensus_state_of_b_on_a
+            .encode_vec()
+            .map_err(|e| ConnectionError::ConsensusStateVerificationFailure {
+                height: msg.proofs_height_on_a,
+                client_error: ClientError::InvalidAnyConsensusState(e),
+            })?;
         client_state_of_a_on_b
-            .verify_client_consensus_state(
-                msg.proofs_height_on_a,
+            .verify_membership(
+                ctx_b,
                 prefix_on_a,
                 &msg.proof_consensus_state_of_b_on_a,
                 consensus_state_of_a_on_b.root(),
-                client_id_on_a,
-                msg.consensus_height_of_b_on_a,
-                expected_consensus_state_of_b_on_a.as_ref(),
+                ClientConsensusStatePath::new(
+                    client_id_on_a.clone(),
+                    msg.consensus_height_of_b_on_a,
+                )
+                .into(),
+                value,
             )
             .map_err(|e| ConnectionError::ConsensusStateVerificationFailure {
                 height: msg.proofs_height_on_a,
                 client_error: e,
             })?;
-    }
+
+        client_state_of_a_on_b
+    };
 
     // Success
     output.emit(IbcEvent::OpenTryConnection(OpenTry::new(
@@ -320,6 +402,7 @@ pub(crate) fn process(
         connection_id: conn_id_on_b,
         connection_end: conn_end_on_b,
         connection_id_state: ConnectionIdState::Generated,
+        client_state: Some(client_state_of_a_on_b),
     };
 
     Ok(output.with_result(result))
@@ -366,9 +449,10 @@ mod tests {
         );
         let client_consensus_state_height = 10;
 
+        // The client of the host on the counterparty must be behind the host.
         let msg_conn_try = MsgConnectionOpenTry::try_from(get_dummy_raw_msg_conn_open_try(
             client_consensus_state_height,
-            host_chain_height.revision_height(),
+            host_chain_height.revision_height() - 1,
         ))
         .unwrap();
 
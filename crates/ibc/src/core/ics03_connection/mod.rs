@@ -4,9 +4,11 @@
 pub mod connection;
 /// Context definitions (dependencies for the protocol).
 pub mod context;
+pub(crate) mod delay;
 pub mod error;
 pub mod events;
 /// Message processing logic (protocol) for ICS 03.
 pub mod handler;
 pub mod msgs;
+pub mod params;
 pub mod version;
@@ -0,0 +1,162 @@
+//! Defines the parameters of the connection submodule, which hosts typically set through
+//! governance.
+
+use crate::prelude::*;
+
+use core::time::Duration;
+
+use ibc_proto::ibc::core::connection::v1::Params as RawParams;
+use ibc_proto::protobuf::Protobuf;
+
+use crate::core::ics03_connection::error::ConnectionError;
+
+/// The default maximum expected time per block.
+pub const DEFAULT_MAX_EXPECTED_TIME_PER_BLOCK: Duration = Duration::from_secs(30);
+
+/// The parameters of the connection submodule.
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConnectionParams {
+    /// The largest amount of time the host might reasonably take to produce the next block under
+    /// normal operating conditions, which is used to enforce the block delay of connections.
+    pub max_expected_time_per_block: Duration,
+}
+
+#[cfg(feature = "borsh")]
+mod sealed {
+    use super::*;
+
+    #[derive(borsh::BorshSerialize, borsh::BorshDeserialize)]
+    struct InnerConnectionParams {
+        max_expected_time_per_block_secs: u64,
+        max_expected_time_per_block_nanos: u32,
+    }
+
+    impl From<InnerConnectionParams> for ConnectionParams {
+        fn from(value: InnerConnectionParams) -> Self {
+            Self {
+                max_expected_time_per_block: Duration::new(
+                    value.max_expected_time_per_block_secs,
+                    value.max_expected_time_per_block_nanos,
+                ),
+            }
+        }
+    }
+
+    impl From<ConnectionParams> for InnerConnectionParams {
+        fn from(value: ConnectionParams) -> Self {
+            Self {
+                max_expected_time_per_block_secs: value.max_expected_time_per_block.as_secs(),
+                max_expected_time_per_block_nanos: value.max_expected_time_per_block.subsec_nanos(),
+            }
+        }
+    }
+
+    impl borsh::BorshSerialize for ConnectionParams {
+        fn serialize<W: borsh::maybestd::io::Write>(
+            &self,
+            writer: &mut W,
+        ) -> borsh::maybestd::io::Result<()> {
+            let value = InnerConnectionParams::from(self.clone());
+            borsh::BorshSerialize::serialize(&value, writer)
+        }
+    }
+
+    impl borsh::BorshDeserialize for ConnectionParams {
+        fn deserialize(buf: &mut &[u8]) -> borsh::maybestd::io::Result<Self> {
+            let result = InnerConnectionParams::deserialize(buf)?;
+            Ok(ConnectionParams::from(result))
+        }
+    }
+}
+
+impl Default for ConnectionParams {
+    fn default() -> Self {
+        Self {
+            max_expected_time_per_block: DEFAULT_MAX_EXPECTED_TIME_PER_BLOCK,
+        }
+    }
+}
+
+impl Protobuf<RawParams> for ConnectionParams {}
+
+impl TryFrom<RawParams> for ConnectionParams {
+    type Error = ConnectionError;
+
+    fn try_from(raw: RawParams) -> Result<Self, Self::Error> {
+        if raw.max_expected_time_per_block == 0 {
+            return Err(ConnectionError::InvalidConnectionParams {
+                reason: "max expected time per block cannot be zero".to_string(),
+            });
+        }
+
+        Ok(Self {
+            max_expected_time_per_block: Duration::from_nanos(raw.max_expected_time_per_block),
+        })
+    }
+}
+
+impl From<ConnectionParams> for RawParams {
+    /// The raw parameters count nanoseconds in a `u64`, so that a maximum expected time per block
+    /// beyond `u64::MAX` nanoseconds (about 584 years) is clamped to it.
+    fn from(value: ConnectionParams) -> Self {
+        Self {
+            max_expected_time_per_block: u64::try_from(
+                value.max_expected_time_per_block.as_nanos(),
+            )
+            .unwrap_or(u64::MAX),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_log::test;
+
+    use crate::core::ics04_channel::context::calculate_block_delay;
+
+    #[test]
+    fn block_delay() {
+        let params = ConnectionParams {
+            max_expected_time_per_block: Duration::from_secs(5),
+        };
+        assert_eq!(calculate_block_delay(&Duration::from_secs(10), &params), 2);
+        assert_eq!(calculate_block_delay(&Duration::from_secs(11), &params), 3);
+        assert_eq!(calculate_block_delay(&Duration::ZERO, &params), 0);
+    }
+
+    #[test]
+    fn raw_params() {
+        let params = ConnectionParams {
+            max_expected_time_per_block: Duration::from_secs(5),
+        };
+        let raw = RawParams::from(params.clone());
+        assert_eq!(raw.max_expected_time_per_block, 5_000_000_000);
+        assert_eq!(ConnectionParams::try_from(raw).unwrap(), params);
+
+        let params = ConnectionParams {
+            max_expected_time_per_block: Duration::MAX,
+        };
+        assert_eq!(
+            RawParams::from(params).max_expected_time_per_block,
+            u64::MAX
+        );
+
+        assert!(matches!(
+            ConnectionParams::try_from(RawParams {
+                max_expected_time_per_block: 0
+            }),
+            Err(ConnectionError::InvalidConnectionParams { .. })
+        ));
+    }
+}
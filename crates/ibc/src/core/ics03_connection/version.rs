@@ -32,10 +32,53 @@ pub struct Version {
 }
 
 impl Version {
+    /// Creates a version with the given `identifier` and `features`, e.g. for a host which offers
+    /// custom features or restricts the channel orderings in its supported versions.
+    pub fn new(identifier: String, features: Vec<String>) -> Self {
+        Self {
+            identifier,
+            features,
+        }
+    }
+
+    pub fn identifier(&self) -> &str {
+        &self.identifier
+    }
+
+    pub fn features(&self) -> &[String] {
+        &self.features
+    }
+
     /// Checks whether or not the given feature is supported in this versin
     pub fn is_supported_feature(&self, feature: String) -> bool {
         self.features.contains(&feature)
     }
+
+    /// Checks whether or not this version is among the `supported_versions`, i.e. whether one of
+    /// them has the same identifier and supports all the features of this version.
+    pub fn is_supported_version(&self, supported_versions: &[Version]) -> bool {
+        supported_versions.iter().any(|supported| {
+            supported.identifier == self.identifier
+                && self
+                    .features
+                    .iter()
+                    .all(|feature| supported.features.contains(feature))
+        })
+    }
+
+    /// Verifies that this version is among the `supported_versions`.
+    pub fn verify_is_supported(
+        &self,
+        supported_versions: &[Version],
+    ) -> Result<(), ConnectionError> {
+        if self.is_supported_version(supported_versions) {
+            Ok(())
+        } else {
+            Err(ConnectionError::VersionNotSupported {
+                version: self.clone(),
+            })
+        }
+    }
 }
 
 impl Protobuf<RawVersion> for Version {}
@@ -74,6 +117,7 @@ impl Default for Version {
             features: vec![
                 Order::Ordered.as_str().to_owned(),
                 Order::Unordered.as_str().to_owned(),
+                Order::OrderedAllowTimeout.as_str().to_owned(),
             ],
         }
     }
@@ -90,12 +134,17 @@ impl Display for Version {
     }
 }
 
-/// Returns the lists of supported versions
+/// Returns the lists of supported versions, which hosts offer unless they configure their own
+/// through their connection context.
 pub fn get_compatible_versions() -> Vec<Version> {
     vec![Version::default()]
 }
 
 /// Selects a version from the intersection of locally supported and counterparty versions.
+///
+/// The picked version only keeps the features supported on both ends, in the order of the
+/// locally supported version, so that the connection negotiates the channel orderings which
+/// either end can rely on. A version whose features are disjoint on both ends is not common.
 pub fn pick_version(
     supported_versions: &[Version],
     counterparty_versions: &[Version],
@@ -111,7 +160,16 @@ pub fn pick_version(
                     return Err(ConnectionError::EmptyFeatures);
                 }
             }
-            intersection.append(&mut vec![s.clone()]);
+            let features: Vec<String> = s
+                .features
+                .iter()
+                .filter(|feature| c.features.contains(feature))
+                .cloned()
+                .collect();
+            if features.is_empty() {
+                continue;
+            }
+            intersection.push(Version::new(s.identifier.clone(), features));
         }
     }
     intersection.sort_by(|a, b| a.identifier.cmp(&b.identifier));
@@ -168,11 +226,11 @@ mod tests {
                 Version::default(),
                 Version {
                     identifier: "3".to_string(),
-                    features: Vec::new(),
+                    features: vec!["ORDER_ORDERED".to_string()],
                 },
                 Version {
                     identifier: "4".to_string(),
-                    features: Vec::new(),
+                    features: vec!["ORDER_ORDERED".to_string()],
                 },
             ]
             .into_iter()
@@ -180,23 +238,24 @@ mod tests {
             vec![
                 Version {
                     identifier: "2".to_string(),
-                    features: Vec::new(),
+                    features: vec!["ORDER_ORDERED".to_string()],
                 },
                 Version {
                     identifier: "4".to_string(),
-                    features: Vec::new(),
+                    features: vec!["ORDER_ORDERED".to_string()],
                 },
                 Version {
                     identifier: "3".to_string(),
-                    features: Vec::new(),
+                    features: vec!["ORDER_ORDERED".to_string()],
                 },
             ]
             .into_iter()
             .collect(),
-            // Should pick version 3 as it's the lowest of the intersection {3, 4}
+            // Should pick version 3 as it's the lowest of the intersection {3, 4}, whose versions
+            // share a feature
             Version {
                 identifier: "3".to_string(),
-                features: Vec::new(),
+                features: vec!["ORDER_ORDERED".to_string()],
             },
         )
     }
@@ -218,6 +277,50 @@ mod tests {
         )
     }
 
+    fn intersecting() -> (Vec<Version>, Vec<Version>, Version) {
+        (
+            vec![Version::new(
+                "1".to_string(),
+                vec![
+                    "ORDER_ORDERED".to_string(),
+                    "ORDER_UNORDERED".to_string(),
+                    "ORDER_ORDERED_ALLOW_TIMEOUT".to_string(),
+                ],
+            )],
+            vec![Version::new(
+                "1".to_string(),
+                vec![
+                    "ORDER_ORDERED_ALLOW_TIMEOUT".to_string(),
+                    "ORDER_UNORDERED".to_string(),
+                ],
+            )],
+            // Should only keep the features of both ends, in the local order
+            Version::new(
+                "1".to_string(),
+                vec![
+                    "ORDER_UNORDERED".to_string(),
+                    "ORDER_ORDERED_ALLOW_TIMEOUT".to_string(),
+                ],
+            ),
+        )
+    }
+
+    fn disjoint_features() -> (Vec<Version>, Vec<Version>) {
+        (
+            vec![Version::new(
+                "1".to_string(),
+                vec!["ORDER_ORDERED".to_string()],
+            )],
+            vec![Version::new(
+                "1".to_string(),
+                vec![
+                    "ORDER_UNORDERED".to_string(),
+                    "ORDER_ORDERED_ALLOW_TIMEOUT".to_string(),
+                ],
+            )],
+        )
+    }
+
     #[test]
     fn verify() {
         struct Test {
@@ -300,6 +403,20 @@ mod tests {
                 picked: Err(ConnectionError::NoCommonVersion),
                 want_pass: false,
             },
+            Test {
+                name: "Intersecting features".to_string(),
+                supported: intersecting().0,
+                counterparty: intersecting().1,
+                picked: Ok(intersecting().2),
+                want_pass: true,
+            },
+            Test {
+                name: "Disjoint features".to_string(),
+                supported: disjoint_features().0,
+                counterparty: disjoint_features().1,
+                picked: Err(ConnectionError::NoCommonVersion),
+                want_pass: false,
+            },
         ];
 
         for test in tests {
@@ -317,6 +434,28 @@ mod tests {
             }
         }
     }
+    #[test]
+    fn supported() {
+        let supported = get_compatible_versions();
+
+        assert!(Version::default().is_supported_version(&supported));
+        // A subset of the features of a supported version is supported
+        assert!(
+            Version::new("1".to_string(), vec!["ORDER_UNORDERED".to_string()])
+                .is_supported_version(&supported)
+        );
+        assert!(Version::new(
+            "1".to_string(),
+            vec!["ORDER_ORDERED_ALLOW_TIMEOUT".to_string()]
+        )
+        .is_supported_version(&supported));
+        assert!(
+            !Version::new("1".to_string(), vec!["ORDER_UNKNOWN".to_string()])
+                .is_supported_version(&supported)
+        );
+        assert!(!Version::new("2".to_string(), Vec::new()).is_supported_version(&supported));
+    }
+
     #[test]
     fn serialize() {
         let def = Version::default();
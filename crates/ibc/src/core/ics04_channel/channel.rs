@@ -7,13 +7,61 @@ use core::str::FromStr;
 use ibc_proto::protobuf::Protobuf;
 
 use ibc_proto::ibc::core::channel::v1::{
-    Channel as RawChannel, Counterparty as RawCounterparty,
+    Channel as ProtoChannel, Counterparty as RawCounterparty,
     IdentifiedChannel as RawIdentifiedChannel,
 };
 
-use crate::core::ics04_channel::{error::ChannelError, Version};
+use crate::core::ics04_channel::{error::ChannelError, packet::Sequence, Version};
 use crate::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
 
+/// Raw `Channel`, as defined by ibc-go, which extends its `ibc-proto` counterpart with the
+/// sequence of the latest upgrade attempted on the channel.
+///
+/// XXX: to be replaced by its `ibc-proto` counterpart once available. Until then, channel ends
+/// converted from or to the `ibc-proto` `Channel` and `IdentifiedChannel` lose their upgrade
+/// sequence, which is read as 0.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RawChannel {
+    #[prost(int32, tag = "1")]
+    pub state: i32,
+    #[prost(int32, tag = "2")]
+    pub ordering: i32,
+    #[prost(message, optional, tag = "3")]
+    pub counterparty: Option<RawCounterparty>,
+    #[prost(string, repeated, tag = "4")]
+    pub connection_hops: Vec<String>,
+    #[prost(string, tag = "5")]
+    pub version: String,
+    #[prost(uint64, tag = "6")]
+    pub upgrade_sequence: u64,
+}
+
+/// Channel ends embedded in `ibc-proto` messages have not attempted any upgrade yet.
+impl From<ProtoChannel> for RawChannel {
+    fn from(value: ProtoChannel) -> Self {
+        RawChannel {
+            state: value.state,
+            ordering: value.ordering,
+            counterparty: value.counterparty,
+            connection_hops: value.connection_hops,
+            version: value.version,
+            upgrade_sequence: 0,
+        }
+    }
+}
+
+impl From<RawChannel> for ProtoChannel {
+    fn from(value: RawChannel) -> Self {
+        ProtoChannel {
+            state: value.state,
+            ordering: value.ordering,
+            counterparty: value.counterparty,
+            connection_hops: value.connection_hops,
+            version: value.version,
+        }
+    }
+}
+
 #[cfg_attr(
     feature = "parity-scale-codec",
     derive(
@@ -56,6 +104,8 @@ impl TryFrom<RawIdentifiedChannel> for IdentifiedChannelEnd {
             counterparty: value.counterparty,
             connection_hops: value.connection_hops,
             version: value.version,
+            // `IdentifiedChannel` does not carry the upgrade sequence, see `RawChannel`.
+            upgrade_sequence: 0,
         };
 
         Ok(IdentifiedChannelEnd {
@@ -105,14 +155,21 @@ pub struct ChannelEnd {
     pub remote: Counterparty,
     pub connection_hops: Vec<ConnectionId>,
     pub version: Version,
+    /// The sequence of the latest upgrade attempted on the channel.
+    pub upgrade_sequence: Sequence,
 }
 
 impl Display for ChannelEnd {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
         write!(
             f,
-            "ChannelEnd {{ state: {}, ordering: {}, remote: {}, connection_hops: {}, version: {} }}",
-            self.state, self.ordering, self.remote, PrettySlice(&self.connection_hops), self.version
+            "ChannelEnd {{ state: {}, ordering: {}, remote: {}, connection_hops: {}, version: {}, upgrade_sequence: {} }}",
+            self.state,
+            self.ordering,
+            self.remote,
+            PrettySlice(&self.connection_hops),
+            self.version,
+            self.upgrade_sequence
         )
     }
 }
@@ -125,6 +182,7 @@ impl Default for ChannelEnd {
             remote: Counterparty::default(),
             connection_hops: Vec::new(),
             version: Version::default(),
+            upgrade_sequence: Sequence::default(),
         }
     }
 }
@@ -159,13 +217,25 @@ impl TryFrom<RawChannel> for ChannelEnd {
 
         let version = value.version.into();
 
-        Ok(ChannelEnd::new(
-            chan_state,
-            chan_ordering,
-            remote,
-            connection_hops,
-            version,
-        ))
+        let mut channel_end =
+            ChannelEnd::new(chan_state, chan_ordering, remote, connection_hops, version);
+        channel_end.set_upgrade_sequence(value.upgrade_sequence.into());
+
+        Ok(channel_end)
+    }
+}
+
+impl TryFrom<ProtoChannel> for ChannelEnd {
+    type Error = ChannelError;
+
+    fn try_from(value: ProtoChannel) -> Result<Self, Self::Error> {
+        RawChannel::from(value).try_into()
+    }
+}
+
+impl From<ChannelEnd> for ProtoChannel {
+    fn from(value: ChannelEnd) -> Self {
+        RawChannel::from(value).into()
     }
 }
 
@@ -181,6 +251,7 @@ impl From<ChannelEnd> for RawChannel {
                 .map(|v| v.as_str().to_string())
                 .collect(),
             version: value.version.to_string(),
+            upgrade_sequence: value.upgrade_sequence.into(),
         }
     }
 }
@@ -200,6 +271,7 @@ impl ChannelEnd {
             remote,
             connection_hops,
             version,
+            upgrade_sequence: Sequence::default(),
         }
     }
 
@@ -216,6 +288,10 @@ impl ChannelEnd {
         self.remote.channel_id = Some(c);
     }
 
+    pub fn set_upgrade_sequence(&mut self, sequence: Sequence) {
+        self.upgrade_sequence = sequence;
+    }
+
     /// Returns `true` if this `ChannelEnd` is in state [`State::Open`].
     pub fn is_open(&self) -> bool {
         self.state_matches(&State::Open)
@@ -241,6 +317,16 @@ impl ChannelEnd {
         &self.version
     }
 
+    pub fn upgrade_sequence(&self) -> Sequence {
+        self.upgrade_sequence
+    }
+
+    /// Returns `true` if this `ChannelEnd` is flushing its in-flight packets as
+    /// part of a channel upgrade.
+    pub fn is_flushing(&self) -> bool {
+        matches!(self.state, State::Flushing | State::FlushComplete)
+    }
+
     pub fn validate_basic(&self) -> Result<(), ChannelError> {
         if self.connection_hops.len() != 1 {
             return Err(ChannelError::InvalidConnectionHopsLength {
@@ -378,6 +464,9 @@ pub enum Order {
     None = 0isize,
     Unordered = 1isize,
     Ordered = 2isize,
+    /// Packets are delivered in order, but a packet that timed out on the
+    /// receiving end is skipped instead of closing the channel.
+    OrderedAllowTimeout = 3isize,
 }
 
 impl Default for Order {
@@ -400,6 +489,7 @@ impl Order {
             Self::None => "ORDER_NONE_UNSPECIFIED",
             Self::Unordered => "ORDER_UNORDERED",
             Self::Ordered => "ORDER_ORDERED",
+            Self::OrderedAllowTimeout => "ORDER_ORDERED_ALLOW_TIMEOUT",
         }
     }
 
@@ -409,11 +499,18 @@ impl Order {
             0 => Ok(Self::None),
             1 => Ok(Self::Unordered),
             2 => Ok(Self::Ordered),
+            3 => Ok(Self::OrderedAllowTimeout),
             _ => Err(ChannelError::UnknownOrderType {
                 type_id: nr.to_string(),
             }),
         }
     }
+
+    /// Returns true if packets on the channel must be received in the order
+    /// they were sent, i.e. the channel tracks its next sequence numbers.
+    pub fn is_ordered(&self) -> bool {
+        matches!(self, Self::Ordered | Self::OrderedAllowTimeout)
+    }
 }
 
 impl FromStr for Order {
@@ -424,6 +521,7 @@ impl FromStr for Order {
             "uninitialized" => Ok(Self::None),
             "unordered" => Ok(Self::Unordered),
             "ordered" => Ok(Self::Ordered),
+            "ordered_allow_timeout" => Ok(Self::OrderedAllowTimeout),
             _ => Err(ChannelError::UnknownOrderType {
                 type_id: s.to_string(),
             }),
@@ -451,6 +549,11 @@ pub enum State {
     TryOpen = 2isize,
     Open = 3isize,
     Closed = 4isize,
+    /// The channel is being upgraded, and waits for its in-flight packets to
+    /// be acknowledged or timed out.
+    Flushing = 5isize,
+    /// The channel is being upgraded, and has no more in-flight packets.
+    FlushComplete = 6isize,
 }
 
 impl State {
@@ -462,6 +565,8 @@ impl State {
             Self::TryOpen => "TRYOPEN",
             Self::Open => "OPEN",
             Self::Closed => "CLOSED",
+            Self::Flushing => "FLUSHING",
+            Self::FlushComplete => "FLUSHCOMPLETE",
         }
     }
 
@@ -473,6 +578,8 @@ impl State {
             2 => Ok(Self::TryOpen),
             3 => Ok(Self::Open),
             4 => Ok(Self::Closed),
+            5 => Ok(Self::Flushing),
+            6 => Ok(Self::FlushComplete),
             _ => Err(ChannelError::UnknownState { state: s }),
         }
     }
@@ -505,9 +612,9 @@ impl Display for State {
 
 #[cfg(test)]
 pub mod test_util {
+    use crate::core::ics04_channel::channel::RawChannel;
     use crate::core::ics24_host::identifier::{ConnectionId, PortId};
     use crate::prelude::*;
-    use ibc_proto::ibc::core::channel::v1::Channel as RawChannel;
     use ibc_proto::ibc::core::channel::v1::Counterparty as RawCounterparty;
 
     /// Returns a dummy `RawCounterparty`, for testing only!
@@ -527,6 +634,7 @@ pub mod test_util {
             counterparty: Some(get_dummy_raw_counterparty(channel_id)),
             connection_hops: vec![ConnectionId::default().to_string()],
             version: "".to_string(), // The version is not validated.
+            upgrade_sequence: 0,
         }
     }
 }
@@ -539,10 +647,11 @@ mod tests {
     use core::str::FromStr;
     use test_log::test;
 
-    use ibc_proto::ibc::core::channel::v1::Channel as RawChannel;
+    use ibc_proto::ibc::core::channel::v1::Channel as ProtoChannel;
+    use ibc_proto::protobuf::Protobuf;
 
     use crate::core::ics04_channel::channel::test_util::get_dummy_raw_channel_end;
-    use crate::core::ics04_channel::channel::ChannelEnd;
+    use crate::core::ics04_channel::channel::{ChannelEnd, RawChannel};
 
     #[test]
     fn channel_end_try_from_raw() {
@@ -633,6 +742,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn channel_end_encoding_carries_upgrade_sequence() {
+        let raw_channel_end = RawChannel {
+            upgrade_sequence: 3,
+            ..get_dummy_raw_channel_end(ChannelId::default().to_string())
+        };
+
+        let channel_end = ChannelEnd::try_from(raw_channel_end.clone()).unwrap();
+        assert_eq!(channel_end.upgrade_sequence(), 3.into());
+        assert_eq!(RawChannel::from(channel_end.clone()), raw_channel_end);
+
+        let decoded = ChannelEnd::decode_vec(&channel_end.encode_vec().unwrap()).unwrap();
+        assert_eq!(decoded, channel_end);
+
+        // The `ibc-proto` `Channel` drops the upgrade sequence.
+        let decoded = ChannelEnd::try_from(ProtoChannel::from(channel_end)).unwrap();
+        assert_eq!(decoded.upgrade_sequence(), 0.into());
+    }
+
     #[test]
     fn parse_channel_ordering_type() {
         use super::Order;
@@ -658,6 +786,11 @@ mod tests {
                 want_res: Order::Ordered,
                 want_err: false,
             },
+            Test {
+                ordering: "ORDER_ORDERED_ALLOW_TIMEOUT",
+                want_res: Order::OrderedAllowTimeout,
+                want_err: false,
+            },
             Test {
                 ordering: "UNKNOWN_ORDER",
                 want_res: Order::None,
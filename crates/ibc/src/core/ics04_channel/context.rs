@@ -7,6 +7,7 @@ use num_traits::float::FloatCore;
 
 use crate::core::ics02_client::consensus_state::ConsensusState;
 use crate::core::ics03_connection::connection::ConnectionEnd;
+use crate::core::ics03_connection::params::ConnectionParams;
 use crate::core::ics04_channel::channel::ChannelEnd;
 use crate::core::ics04_channel::commitment::{AcknowledgementCommitment, PacketCommitment};
 use crate::core::ics04_channel::handler::recv_packet::RecvPacketResult;
@@ -161,13 +162,16 @@ pub trait ChannelReader {
     /// `ChannelKeeper::increase_channel_counter`.
     fn channel_counter(&self) -> Result<u64, ChannelError>;
 
-    /// Returns the maximum expected time per block
-    fn max_expected_time_per_block(&self) -> Duration;
+    /// Returns the parameters of the connection submodule.
+    fn connection_params(&self) -> Result<ConnectionParams, ChannelError>;
 
     /// Calculates the block delay period using the connection's delay period and the maximum
-    /// expected time per block.
-    fn block_delay(&self, delay_period_time: &Duration) -> u64 {
-        calculate_block_delay(delay_period_time, &self.max_expected_time_per_block())
+    /// expected time per block of the connection parameters.
+    fn block_delay(&self, delay_period_time: &Duration) -> Result<u64, ChannelError> {
+        Ok(calculate_block_delay(
+            delay_period_time,
+            &self.connection_params()?,
+        ))
     }
 }
 
@@ -322,12 +326,29 @@ pub trait ChannelKeeper {
                     port_id,
                     channel_id,
                     next_seq_recv,
+                    ..
                 } => self.store_next_sequence_recv(port_id, channel_id, next_seq_recv)?,
+                RecvPacketResult::TimedOut {
+                    port_id,
+                    channel_id,
+                    sequence,
+                    next_seq_recv,
+                    ..
+                } => {
+                    self.store_packet_receipt(
+                        port_id.clone(),
+                        channel_id.clone(),
+                        sequence,
+                        Receipt::Timeout,
+                    )?;
+                    self.store_next_sequence_recv(port_id, channel_id, next_seq_recv)?
+                }
                 RecvPacketResult::Unordered {
                     port_id,
                     channel_id,
                     sequence,
                     receipt,
+                    ..
                 } => self.store_packet_receipt(port_id, channel_id, sequence, receipt)?,
                 RecvPacketResult::NoOp => unreachable!(),
             },
@@ -348,6 +369,10 @@ pub trait ChannelKeeper {
             }
             PacketResult::Timeout(res) => {
                 self.delete_packet_commitment(&res.port_id, &res.channel_id, &res.seq)?;
+                if let Some(s) = res.seq_number {
+                    //Ordered Channel allowing timeouts
+                    self.store_next_sequence_ack(res.port_id.clone(), res.channel_id.clone(), s)?;
+                }
                 if let Some(c) = res.channel {
                     // Ordered Channel: closes channel
                     self.store_channel(res.port_id, res.channel_id, c)
@@ -438,10 +463,10 @@ pub trait ChannelKeeper {
     fn increase_channel_counter(&mut self);
 }
 
-pub fn calculate_block_delay(
-    delay_period_time: &Duration,
-    max_expected_time_per_block: &Duration,
-) -> u64 {
+/// Returns the number of blocks the host is expected to produce in `delay_period_time`, given
+/// the maximum expected time per block of the connection `params`.
+pub fn calculate_block_delay(delay_period_time: &Duration, params: &ConnectionParams) -> u64 {
+    let max_expected_time_per_block = params.max_expected_time_per_block;
     if max_expected_time_per_block.is_zero() {
         return 0;
     }
@@ -76,14 +76,38 @@ pub enum ChannelError {
     ConnectionNotOpen { connection_id: ConnectionId },
     /// Undefined counterparty connection for `{connection_id}`
     UndefinedConnectionCounterparty { connection_id: ConnectionId },
-    /// Client with id `{client_id}` is frozen
-    FrozenClient { client_id: ClientId },
+    /// client error: `{0}`
+    Client(client_error::ClientError),
     /// Channel `{channel_id}` should not be state `{state}`
     InvalidChannelState { channel_id: ChannelId, state: State },
     /// invalid proof: empty proof
     InvalidProof,
     /// identifier error: `{0}`
     Identifier(ValidationError),
+    /// invalid channel upgrade: `{reason}`
+    InvalidUpgrade { reason: String },
+    /// no upgrade in progress for port `{port_id}` and channel `{channel_id}`
+    UpgradeNotFound {
+        port_id: PortId,
+        channel_id: ChannelId,
+    },
+    /// no upgrade error receipt for port `{port_id}` and channel `{channel_id}`
+    UpgradeErrorReceiptNotFound {
+        port_id: PortId,
+        channel_id: ChannelId,
+    },
+    /// the module bound to port `{port_id}` does not support channel upgrades
+    UpgradeNotSupported { port_id: PortId },
+    /// invalid upgrade sequence `{actual}`: must be greater than the channel upgrade sequence `{current}`
+    InvalidUpgradeSequence { current: Sequence, actual: Sequence },
+    /// the proposed upgrade is incompatible with the counterparty upgrade: `{reason}`
+    IncompatibleUpgrade { reason: String },
+    /// the channel upgrade has timed out
+    UpgradeTimedOut,
+    /// the channel upgrade has not timed out on the counterparty chain yet
+    UpgradeTimeoutNotReached,
+    /// channel upgrade messages can only be handled by the validation and execution contexts
+    UpgradeUnsupportedByLegacyHandler,
 }
 
 #[derive(Debug, Display)]
@@ -205,6 +229,7 @@ impl std::error::Error for ChannelError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match &self {
             Self::Connection(e) => Some(e),
+            Self::Client(e) => Some(e),
             Self::Port(e) => Some(e),
             Self::Identifier(e) => Some(e),
             Self::Signer(e) => Some(e),
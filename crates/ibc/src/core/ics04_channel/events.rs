@@ -13,9 +13,10 @@ use crate::prelude::*;
 use crate::timestamp::Timestamp;
 
 use self::channel_attributes::{
-    ChannelIdAttribute, ConnectionIdAttribute, CounterpartyChannelIdAttribute,
-    CounterpartyPortIdAttribute, PortIdAttribute, VersionAttribute,
-    COUNTERPARTY_CHANNEL_ID_ATTRIBUTE_KEY,
+    ChannelIdAttribute, ChannelStateAttribute, ConnectionIdAttribute,
+    CounterpartyChannelIdAttribute, CounterpartyPortIdAttribute, PortIdAttribute,
+    UpgradeConnectionIdAttribute, UpgradeOrderingAttribute, UpgradeSequenceAttribute,
+    UpgradeVersionAttribute, VersionAttribute, COUNTERPARTY_CHANNEL_ID_ATTRIBUTE_KEY,
 };
 use self::packet_attributes::{
     AcknowledgementAttribute, ChannelOrderingAttribute, DstChannelIdAttribute, DstPortIdAttribute,
@@ -23,7 +24,7 @@ use self::packet_attributes::{
     SrcPortIdAttribute, TimeoutHeightAttribute, TimeoutTimestampAttribute,
 };
 
-use super::channel::Order;
+use super::channel::{Order, State};
 use super::msgs::acknowledgement::Acknowledgement;
 use super::packet::Sequence;
 use super::timeout::TimeoutHeight;
@@ -457,6 +458,601 @@ impl From<CloseConfirm> for abci::Event {
     }
 }
 
+/// Emitted when chain A initiates an upgrade of one of its channels.
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UpgradeInit {
+    port_id: PortIdAttribute,
+    channel_id: ChannelIdAttribute,
+    counterparty_port_id: CounterpartyPortIdAttribute,
+    counterparty_channel_id: CounterpartyChannelIdAttribute,
+    upgrade_connection_id: UpgradeConnectionIdAttribute,
+    upgrade_version: UpgradeVersionAttribute,
+    upgrade_ordering: UpgradeOrderingAttribute,
+    upgrade_sequence: UpgradeSequenceAttribute,
+}
+
+impl UpgradeInit {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        port_id: PortId,
+        channel_id: ChannelId,
+        counterparty_port_id: PortId,
+        counterparty_channel_id: ChannelId,
+        upgrade_connection_id: ConnectionId,
+        upgrade_version: Version,
+        upgrade_ordering: Order,
+        upgrade_sequence: Sequence,
+    ) -> Self {
+        Self {
+            port_id: port_id.into(),
+            channel_id: channel_id.into(),
+            counterparty_port_id: counterparty_port_id.into(),
+            counterparty_channel_id: counterparty_channel_id.into(),
+            upgrade_connection_id: upgrade_connection_id.into(),
+            upgrade_version: upgrade_version.into(),
+            upgrade_ordering: upgrade_ordering.into(),
+            upgrade_sequence: upgrade_sequence.into(),
+        }
+    }
+    pub fn port_id(&self) -> &PortId {
+        &self.port_id.port_id
+    }
+    pub fn channel_id(&self) -> &ChannelId {
+        &self.channel_id.channel_id
+    }
+    pub fn counterparty_port_id(&self) -> &PortId {
+        &self.counterparty_port_id.counterparty_port_id
+    }
+    pub fn counterparty_channel_id(&self) -> &ChannelId {
+        &self.counterparty_channel_id.counterparty_channel_id
+    }
+    pub fn upgrade_connection_id(&self) -> &ConnectionId {
+        &self.upgrade_connection_id.upgrade_connection_id
+    }
+    pub fn upgrade_version(&self) -> &Version {
+        &self.upgrade_version.upgrade_version
+    }
+    pub fn upgrade_ordering(&self) -> &Order {
+        &self.upgrade_ordering.upgrade_ordering
+    }
+    pub fn upgrade_sequence(&self) -> Sequence {
+        self.upgrade_sequence.upgrade_sequence
+    }
+}
+
+impl From<UpgradeInit> for abci::Event {
+    fn from(o: UpgradeInit) -> Self {
+        abci::Event {
+            kind: IbcEventType::UpgradeInitChannel.as_str().to_owned(),
+            attributes: vec![
+                o.port_id.into(),
+                o.channel_id.into(),
+                o.counterparty_port_id.into(),
+                o.counterparty_channel_id.into(),
+                o.upgrade_connection_id.into(),
+                o.upgrade_version.into(),
+                o.upgrade_ordering.into(),
+                o.upgrade_sequence.into(),
+            ],
+        }
+    }
+}
+
+/// Emitted when chain B agrees to an upgrade proposed by chain A, and starts flushing the
+/// in-flight packets of its channel end.
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UpgradeTry {
+    port_id: PortIdAttribute,
+    channel_id: ChannelIdAttribute,
+    counterparty_port_id: CounterpartyPortIdAttribute,
+    counterparty_channel_id: CounterpartyChannelIdAttribute,
+    upgrade_connection_id: UpgradeConnectionIdAttribute,
+    upgrade_version: UpgradeVersionAttribute,
+    upgrade_ordering: UpgradeOrderingAttribute,
+    upgrade_sequence: UpgradeSequenceAttribute,
+}
+
+impl UpgradeTry {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        port_id: PortId,
+        channel_id: ChannelId,
+        counterparty_port_id: PortId,
+        counterparty_channel_id: ChannelId,
+        upgrade_connection_id: ConnectionId,
+        upgrade_version: Version,
+        upgrade_ordering: Order,
+        upgrade_sequence: Sequence,
+    ) -> Self {
+        Self {
+            port_id: port_id.into(),
+            channel_id: channel_id.into(),
+            counterparty_port_id: counterparty_port_id.into(),
+            counterparty_channel_id: counterparty_channel_id.into(),
+            upgrade_connection_id: upgrade_connection_id.into(),
+            upgrade_version: upgrade_version.into(),
+            upgrade_ordering: upgrade_ordering.into(),
+            upgrade_sequence: upgrade_sequence.into(),
+        }
+    }
+    pub fn port_id(&self) -> &PortId {
+        &self.port_id.port_id
+    }
+    pub fn channel_id(&self) -> &ChannelId {
+        &self.channel_id.channel_id
+    }
+    pub fn counterparty_port_id(&self) -> &PortId {
+        &self.counterparty_port_id.counterparty_port_id
+    }
+    pub fn counterparty_channel_id(&self) -> &ChannelId {
+        &self.counterparty_channel_id.counterparty_channel_id
+    }
+    pub fn upgrade_connection_id(&self) -> &ConnectionId {
+        &self.upgrade_connection_id.upgrade_connection_id
+    }
+    pub fn upgrade_version(&self) -> &Version {
+        &self.upgrade_version.upgrade_version
+    }
+    pub fn upgrade_ordering(&self) -> &Order {
+        &self.upgrade_ordering.upgrade_ordering
+    }
+    pub fn upgrade_sequence(&self) -> Sequence {
+        self.upgrade_sequence.upgrade_sequence
+    }
+}
+
+impl From<UpgradeTry> for abci::Event {
+    fn from(o: UpgradeTry) -> Self {
+        abci::Event {
+            kind: IbcEventType::UpgradeTryChannel.as_str().to_owned(),
+            attributes: vec![
+                o.port_id.into(),
+                o.channel_id.into(),
+                o.counterparty_port_id.into(),
+                o.counterparty_channel_id.into(),
+                o.upgrade_connection_id.into(),
+                o.upgrade_version.into(),
+                o.upgrade_ordering.into(),
+                o.upgrade_sequence.into(),
+            ],
+        }
+    }
+}
+
+/// Emitted when chain A acknowledges the upgrade agreed on by chain B.
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UpgradeAck {
+    port_id: PortIdAttribute,
+    channel_id: ChannelIdAttribute,
+    counterparty_port_id: CounterpartyPortIdAttribute,
+    counterparty_channel_id: CounterpartyChannelIdAttribute,
+    upgrade_connection_id: UpgradeConnectionIdAttribute,
+    upgrade_version: UpgradeVersionAttribute,
+    upgrade_ordering: UpgradeOrderingAttribute,
+    upgrade_sequence: UpgradeSequenceAttribute,
+}
+
+impl UpgradeAck {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        port_id: PortId,
+        channel_id: ChannelId,
+        counterparty_port_id: PortId,
+        counterparty_channel_id: ChannelId,
+        upgrade_connection_id: ConnectionId,
+        upgrade_version: Version,
+        upgrade_ordering: Order,
+        upgrade_sequence: Sequence,
+    ) -> Self {
+        Self {
+            port_id: port_id.into(),
+            channel_id: channel_id.into(),
+            counterparty_port_id: counterparty_port_id.into(),
+            counterparty_channel_id: counterparty_channel_id.into(),
+            upgrade_connection_id: upgrade_connection_id.into(),
+            upgrade_version: upgrade_version.into(),
+            upgrade_ordering: upgrade_ordering.into(),
+            upgrade_sequence: upgrade_sequence.into(),
+        }
+    }
+    pub fn port_id(&self) -> &PortId {
+        &self.port_id.port_id
+    }
+    pub fn channel_id(&self) -> &ChannelId {
+        &self.channel_id.channel_id
+    }
+    pub fn counterparty_port_id(&self) -> &PortId {
+        &self.counterparty_port_id.counterparty_port_id
+    }
+    pub fn counterparty_channel_id(&self) -> &ChannelId {
+        &self.counterparty_channel_id.counterparty_channel_id
+    }
+    pub fn upgrade_connection_id(&self) -> &ConnectionId {
+        &self.upgrade_connection_id.upgrade_connection_id
+    }
+    pub fn upgrade_version(&self) -> &Version {
+        &self.upgrade_version.upgrade_version
+    }
+    pub fn upgrade_ordering(&self) -> &Order {
+        &self.upgrade_ordering.upgrade_ordering
+    }
+    pub fn upgrade_sequence(&self) -> Sequence {
+        self.upgrade_sequence.upgrade_sequence
+    }
+}
+
+impl From<UpgradeAck> for abci::Event {
+    fn from(o: UpgradeAck) -> Self {
+        abci::Event {
+            kind: IbcEventType::UpgradeAckChannel.as_str().to_owned(),
+            attributes: vec![
+                o.port_id.into(),
+                o.channel_id.into(),
+                o.counterparty_port_id.into(),
+                o.counterparty_channel_id.into(),
+                o.upgrade_connection_id.into(),
+                o.upgrade_version.into(),
+                o.upgrade_ordering.into(),
+                o.upgrade_sequence.into(),
+            ],
+        }
+    }
+}
+
+/// Emitted when chain B learns that chain A acknowledged the upgrade. The channel state tells
+/// whether chain B is done flushing its in-flight packets.
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UpgradeConfirm {
+    port_id: PortIdAttribute,
+    channel_id: ChannelIdAttribute,
+    counterparty_port_id: CounterpartyPortIdAttribute,
+    counterparty_channel_id: CounterpartyChannelIdAttribute,
+    channel_state: ChannelStateAttribute,
+    upgrade_sequence: UpgradeSequenceAttribute,
+}
+
+impl UpgradeConfirm {
+    pub fn new(
+        port_id: PortId,
+        channel_id: ChannelId,
+        counterparty_port_id: PortId,
+        counterparty_channel_id: ChannelId,
+        channel_state: State,
+        upgrade_sequence: Sequence,
+    ) -> Self {
+        Self {
+            port_id: port_id.into(),
+            channel_id: channel_id.into(),
+            counterparty_port_id: counterparty_port_id.into(),
+            counterparty_channel_id: counterparty_channel_id.into(),
+            channel_state: channel_state.into(),
+            upgrade_sequence: upgrade_sequence.into(),
+        }
+    }
+    pub fn port_id(&self) -> &PortId {
+        &self.port_id.port_id
+    }
+    pub fn channel_id(&self) -> &ChannelId {
+        &self.channel_id.channel_id
+    }
+    pub fn counterparty_port_id(&self) -> &PortId {
+        &self.counterparty_port_id.counterparty_port_id
+    }
+    pub fn counterparty_channel_id(&self) -> &ChannelId {
+        &self.counterparty_channel_id.counterparty_channel_id
+    }
+    pub fn channel_state(&self) -> &State {
+        &self.channel_state.channel_state
+    }
+    pub fn upgrade_sequence(&self) -> Sequence {
+        self.upgrade_sequence.upgrade_sequence
+    }
+}
+
+impl From<UpgradeConfirm> for abci::Event {
+    fn from(o: UpgradeConfirm) -> Self {
+        abci::Event {
+            kind: IbcEventType::UpgradeConfirmChannel.as_str().to_owned(),
+            attributes: vec![
+                o.port_id.into(),
+                o.channel_id.into(),
+                o.counterparty_port_id.into(),
+                o.counterparty_channel_id.into(),
+                o.channel_state.into(),
+                o.upgrade_sequence.into(),
+            ],
+        }
+    }
+}
+
+/// Emitted when an upgrade is completed and the channel is open again with the upgraded
+/// ordering, connection hops and version.
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UpgradeOpen {
+    port_id: PortIdAttribute,
+    channel_id: ChannelIdAttribute,
+    counterparty_port_id: CounterpartyPortIdAttribute,
+    counterparty_channel_id: CounterpartyChannelIdAttribute,
+    upgrade_connection_id: UpgradeConnectionIdAttribute,
+    upgrade_version: UpgradeVersionAttribute,
+    upgrade_ordering: UpgradeOrderingAttribute,
+    upgrade_sequence: UpgradeSequenceAttribute,
+}
+
+impl UpgradeOpen {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        port_id: PortId,
+        channel_id: ChannelId,
+        counterparty_port_id: PortId,
+        counterparty_channel_id: ChannelId,
+        upgrade_connection_id: ConnectionId,
+        upgrade_version: Version,
+        upgrade_ordering: Order,
+        upgrade_sequence: Sequence,
+    ) -> Self {
+        Self {
+            port_id: port_id.into(),
+            channel_id: channel_id.into(),
+            counterparty_port_id: counterparty_port_id.into(),
+            counterparty_channel_id: counterparty_channel_id.into(),
+            upgrade_connection_id: upgrade_connection_id.into(),
+            upgrade_version: upgrade_version.into(),
+            upgrade_ordering: upgrade_ordering.into(),
+            upgrade_sequence: upgrade_sequence.into(),
+        }
+    }
+    pub fn port_id(&self) -> &PortId {
+        &self.port_id.port_id
+    }
+    pub fn channel_id(&self) -> &ChannelId {
+        &self.channel_id.channel_id
+    }
+    pub fn counterparty_port_id(&self) -> &PortId {
+        &self.counterparty_port_id.counterparty_port_id
+    }
+    pub fn counterparty_channel_id(&self) -> &ChannelId {
+        &self.counterparty_channel_id.counterparty_channel_id
+    }
+    pub fn upgrade_connection_id(&self) -> &ConnectionId {
+        &self.upgrade_connection_id.upgrade_connection_id
+    }
+    pub fn upgrade_version(&self) -> &Version {
+        &self.upgrade_version.upgrade_version
+    }
+    pub fn upgrade_ordering(&self) -> &Order {
+        &self.upgrade_ordering.upgrade_ordering
+    }
+    pub fn upgrade_sequence(&self) -> Sequence {
+        self.upgrade_sequence.upgrade_sequence
+    }
+}
+
+impl From<UpgradeOpen> for abci::Event {
+    fn from(o: UpgradeOpen) -> Self {
+        abci::Event {
+            kind: IbcEventType::UpgradeOpenChannel.as_str().to_owned(),
+            attributes: vec![
+                o.port_id.into(),
+                o.channel_id.into(),
+                o.counterparty_port_id.into(),
+                o.counterparty_channel_id.into(),
+                o.upgrade_connection_id.into(),
+                o.upgrade_version.into(),
+                o.upgrade_ordering.into(),
+                o.upgrade_sequence.into(),
+            ],
+        }
+    }
+}
+
+/// Emitted when an upgrade is aborted because the counterparty did not complete it in time.
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UpgradeTimeout {
+    port_id: PortIdAttribute,
+    channel_id: ChannelIdAttribute,
+    counterparty_port_id: CounterpartyPortIdAttribute,
+    counterparty_channel_id: CounterpartyChannelIdAttribute,
+    upgrade_sequence: UpgradeSequenceAttribute,
+}
+
+impl UpgradeTimeout {
+    pub fn new(
+        port_id: PortId,
+        channel_id: ChannelId,
+        counterparty_port_id: PortId,
+        counterparty_channel_id: ChannelId,
+        upgrade_sequence: Sequence,
+    ) -> Self {
+        Self {
+            port_id: port_id.into(),
+            channel_id: channel_id.into(),
+            counterparty_port_id: counterparty_port_id.into(),
+            counterparty_channel_id: counterparty_channel_id.into(),
+            upgrade_sequence: upgrade_sequence.into(),
+        }
+    }
+    pub fn port_id(&self) -> &PortId {
+        &self.port_id.port_id
+    }
+    pub fn channel_id(&self) -> &ChannelId {
+        &self.channel_id.channel_id
+    }
+    pub fn counterparty_port_id(&self) -> &PortId {
+        &self.counterparty_port_id.counterparty_port_id
+    }
+    pub fn counterparty_channel_id(&self) -> &ChannelId {
+        &self.counterparty_channel_id.counterparty_channel_id
+    }
+    pub fn upgrade_sequence(&self) -> Sequence {
+        self.upgrade_sequence.upgrade_sequence
+    }
+}
+
+impl From<UpgradeTimeout> for abci::Event {
+    fn from(o: UpgradeTimeout) -> Self {
+        abci::Event {
+            kind: IbcEventType::UpgradeTimeoutChannel.as_str().to_owned(),
+            attributes: vec![
+                o.port_id.into(),
+                o.channel_id.into(),
+                o.counterparty_port_id.into(),
+                o.counterparty_channel_id.into(),
+                o.upgrade_sequence.into(),
+            ],
+        }
+    }
+}
+
+/// Emitted when an upgrade is aborted because the counterparty cancelled it.
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UpgradeCancel {
+    port_id: PortIdAttribute,
+    channel_id: ChannelIdAttribute,
+    counterparty_port_id: CounterpartyPortIdAttribute,
+    counterparty_channel_id: CounterpartyChannelIdAttribute,
+    upgrade_sequence: UpgradeSequenceAttribute,
+}
+
+impl UpgradeCancel {
+    pub fn new(
+        port_id: PortId,
+        channel_id: ChannelId,
+        counterparty_port_id: PortId,
+        counterparty_channel_id: ChannelId,
+        upgrade_sequence: Sequence,
+    ) -> Self {
+        Self {
+            port_id: port_id.into(),
+            channel_id: channel_id.into(),
+            counterparty_port_id: counterparty_port_id.into(),
+            counterparty_channel_id: counterparty_channel_id.into(),
+            upgrade_sequence: upgrade_sequence.into(),
+        }
+    }
+    pub fn port_id(&self) -> &PortId {
+        &self.port_id.port_id
+    }
+    pub fn channel_id(&self) -> &ChannelId {
+        &self.channel_id.channel_id
+    }
+    pub fn counterparty_port_id(&self) -> &PortId {
+        &self.counterparty_port_id.counterparty_port_id
+    }
+    pub fn counterparty_channel_id(&self) -> &ChannelId {
+        &self.counterparty_channel_id.counterparty_channel_id
+    }
+    pub fn upgrade_sequence(&self) -> Sequence {
+        self.upgrade_sequence.upgrade_sequence
+    }
+}
+
+impl From<UpgradeCancel> for abci::Event {
+    fn from(o: UpgradeCancel) -> Self {
+        abci::Event {
+            kind: IbcEventType::UpgradeCancelChannel.as_str().to_owned(),
+            attributes: vec![
+                o.port_id.into(),
+                o.channel_id.into(),
+                o.counterparty_port_id.into(),
+                o.counterparty_channel_id.into(),
+                o.upgrade_sequence.into(),
+            ],
+        }
+    }
+}
+
 /// A `ChannelClosed` event is emitted when a channel is closed as a result of a packet timing out. Note that
 /// since optimistic packet sends (i.e. send a packet before channel handshake is complete) are supported,
 /// we might not have a counterparty channel id value yet. This would happen if a packet is sent right
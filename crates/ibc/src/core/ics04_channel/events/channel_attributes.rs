@@ -1,10 +1,15 @@
 ///! This module holds all the abci event attributes for IBC events emitted
 ///! during the channel handshake.
+use crate::prelude::*;
 use derive_more::From;
 use tendermint::abci;
 
 use crate::core::{
-    ics04_channel::Version,
+    ics04_channel::{
+        channel::{Order, State},
+        packet::Sequence,
+        Version,
+    },
     ics24_host::identifier::{ChannelId, ConnectionId, PortId},
 };
 
@@ -16,6 +21,11 @@ const PORT_ID_ATTRIBUTE_KEY: &str = "port_id";
 pub const COUNTERPARTY_CHANNEL_ID_ATTRIBUTE_KEY: &str = "counterparty_channel_id";
 const COUNTERPARTY_PORT_ID_ATTRIBUTE_KEY: &str = "counterparty_port_id";
 const VERSION_ATTRIBUTE_KEY: &str = "version";
+const CHANNEL_STATE_ATTRIBUTE_KEY: &str = "channel_state";
+const UPGRADE_CONNECTION_HOPS_ATTRIBUTE_KEY: &str = "upgrade_connection_hops";
+const UPGRADE_VERSION_ATTRIBUTE_KEY: &str = "upgrade_version";
+const UPGRADE_ORDERING_ATTRIBUTE_KEY: &str = "upgrade_ordering";
+const UPGRADE_SEQUENCE_ATTRIBUTE_KEY: &str = "upgrade_sequence";
 #[cfg_attr(
     feature = "parity-scale-codec",
     derive(
@@ -171,3 +181,135 @@ impl From<VersionAttribute> for abci::EventAttribute {
         (VERSION_ATTRIBUTE_KEY, attr.version.as_str()).into()
     }
 }
+
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, From, PartialEq, Eq)]
+pub struct ChannelStateAttribute {
+    pub channel_state: State,
+}
+
+impl From<ChannelStateAttribute> for abci::EventAttribute {
+    fn from(attr: ChannelStateAttribute) -> Self {
+        (CHANNEL_STATE_ATTRIBUTE_KEY, attr.channel_state.as_string()).into()
+    }
+}
+
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, From, PartialEq, Eq)]
+pub struct UpgradeConnectionIdAttribute {
+    pub upgrade_connection_id: ConnectionId,
+}
+
+impl From<UpgradeConnectionIdAttribute> for abci::EventAttribute {
+    fn from(attr: UpgradeConnectionIdAttribute) -> Self {
+        (
+            UPGRADE_CONNECTION_HOPS_ATTRIBUTE_KEY,
+            attr.upgrade_connection_id.as_str(),
+        )
+            .into()
+    }
+}
+
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, From, PartialEq, Eq)]
+pub struct UpgradeVersionAttribute {
+    pub upgrade_version: Version,
+}
+
+impl From<UpgradeVersionAttribute> for abci::EventAttribute {
+    fn from(attr: UpgradeVersionAttribute) -> Self {
+        (UPGRADE_VERSION_ATTRIBUTE_KEY, attr.upgrade_version.as_str()).into()
+    }
+}
+
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, From, PartialEq, Eq)]
+pub struct UpgradeOrderingAttribute {
+    pub upgrade_ordering: Order,
+}
+
+impl From<UpgradeOrderingAttribute> for abci::EventAttribute {
+    fn from(attr: UpgradeOrderingAttribute) -> Self {
+        (
+            UPGRADE_ORDERING_ATTRIBUTE_KEY,
+            attr.upgrade_ordering.as_str(),
+        )
+            .into()
+    }
+}
+
+#[cfg_attr(
+    feature = "parity-scale-codec",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, From, PartialEq, Eq)]
+pub struct UpgradeSequenceAttribute {
+    pub upgrade_sequence: Sequence,
+}
+
+impl From<UpgradeSequenceAttribute> for abci::EventAttribute {
+    fn from(attr: UpgradeSequenceAttribute) -> Self {
+        (
+            UPGRADE_SEQUENCE_ATTRIBUTE_KEY,
+            attr.upgrade_sequence.to_string(),
+        )
+            .into()
+    }
+}
@@ -2,6 +2,8 @@
 use crate::events::{IbcEvent, ModuleEvent};
 use crate::prelude::*;
 
+use crate::core::ics02_client::client_state::ClientState;
+use crate::core::ics02_client::context::ClientReader;
 use crate::core::ics04_channel::channel::ChannelEnd;
 use crate::core::ics04_channel::context::ChannelReader;
 use crate::core::ics04_channel::error::{ChannelError, PacketError};
@@ -25,6 +27,22 @@ pub mod chan_open_ack;
 pub mod chan_open_confirm;
 pub mod chan_open_init;
 pub mod chan_open_try;
+#[cfg(feature = "val_exec_ctx")]
+pub mod chan_upgrade;
+#[cfg(feature = "val_exec_ctx")]
+pub mod chan_upgrade_ack;
+#[cfg(feature = "val_exec_ctx")]
+pub mod chan_upgrade_cancel;
+#[cfg(feature = "val_exec_ctx")]
+pub mod chan_upgrade_confirm;
+#[cfg(feature = "val_exec_ctx")]
+pub mod chan_upgrade_init;
+#[cfg(feature = "val_exec_ctx")]
+pub mod chan_upgrade_open;
+#[cfg(feature = "val_exec_ctx")]
+pub mod chan_upgrade_timeout;
+#[cfg(feature = "val_exec_ctx")]
+pub mod chan_upgrade_try;
 pub mod recv_packet;
 pub mod send_packet;
 pub mod timeout;
@@ -48,6 +66,9 @@ pub struct ChannelResult {
     pub channel_id: ChannelId,
     pub channel_id_state: ChannelIdState,
     pub channel_end: ChannelEnd,
+    /// The state of the client underlying the channel once the proofs of the message were
+    /// verified against it, if the message carries any proofs.
+    pub client_state: Option<Box<dyn ClientState>>,
 }
 
 pub struct ModuleExtras {
@@ -83,7 +104,7 @@ pub(crate) fn channel_dispatch<Ctx>(
     msg: &ChannelMsg,
 ) -> Result<(Vec<String>, ChannelResult), ChannelError>
 where
-    Ctx: ChannelReader,
+    Ctx: ChannelReader + ClientReader,
 {
     let output = match msg {
         ChannelMsg::OpenInit(msg) => chan_open_init::process(ctx, msg),
@@ -92,6 +113,13 @@ where
         ChannelMsg::OpenConfirm(msg) => chan_open_confirm::process(ctx, msg),
         ChannelMsg::CloseInit(msg) => chan_close_init::process(ctx, msg),
         ChannelMsg::CloseConfirm(msg) => chan_close_confirm::process(ctx, msg),
+        ChannelMsg::UpgradeInit(_)
+        | ChannelMsg::UpgradeTry(_)
+        | ChannelMsg::UpgradeAck(_)
+        | ChannelMsg::UpgradeConfirm(_)
+        | ChannelMsg::UpgradeOpen(_)
+        | ChannelMsg::UpgradeTimeout(_)
+        | ChannelMsg::UpgradeCancel(_) => Err(ChannelError::UpgradeUnsupportedByLegacyHandler),
     }?;
 
     let HandlerOutput { result, log, .. } = output;
@@ -149,6 +177,13 @@ where
         ChannelMsg::CloseConfirm(msg) => {
             cb.on_chan_close_confirm(&msg.port_id_on_b, &result.channel_id)
         }
+        ChannelMsg::UpgradeInit(_)
+        | ChannelMsg::UpgradeTry(_)
+        | ChannelMsg::UpgradeAck(_)
+        | ChannelMsg::UpgradeConfirm(_)
+        | ChannelMsg::UpgradeOpen(_)
+        | ChannelMsg::UpgradeTimeout(_)
+        | ChannelMsg::UpgradeCancel(_) => Err(ChannelError::UpgradeUnsupportedByLegacyHandler),
     }
 }
 
@@ -214,6 +249,14 @@ pub(crate) fn channel_events(
                 .expect("counterparty channel id must exist after channel open ack"),
             connection_id,
         )),
+        // Upgrade messages are rejected by `channel_dispatch` in the first place.
+        ChannelMsg::UpgradeInit(_)
+        | ChannelMsg::UpgradeTry(_)
+        | ChannelMsg::UpgradeAck(_)
+        | ChannelMsg::UpgradeConfirm(_)
+        | ChannelMsg::UpgradeOpen(_)
+        | ChannelMsg::UpgradeTimeout(_)
+        | ChannelMsg::UpgradeCancel(_) => return Vec::new(),
     };
 
     vec![event]
@@ -240,7 +283,7 @@ pub(crate) fn packet_dispatch<Ctx>(
     msg: &PacketMsg,
 ) -> Result<(HandlerOutputBuilder<()>, PacketResult), PacketError>
 where
-    Ctx: ChannelReader,
+    Ctx: ChannelReader + ClientReader,
 {
     let output = match msg {
         PacketMsg::Recv(msg) => recv_packet::process(ctx, msg),
@@ -329,7 +372,7 @@ fn process_write_ack(
         result,
         log,
         events,
-    } = write_acknowledgement::process(ctx, packet, acknowledgement.as_ref().to_vec().into())?;
+    } = write_acknowledgement::process(ctx, packet, acknowledgement.as_ref().to_vec().try_into()?)?;
 
     // store write ack result
     ctx.store_packet_result(result)?;
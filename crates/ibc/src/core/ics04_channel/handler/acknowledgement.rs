@@ -1,25 +1,183 @@
+use crate::core::ics02_client::client_state::ClientState;
+use crate::core::ics02_client::context::ClientReader;
+use crate::core::ics02_client::error::ClientError;
 use crate::core::ics03_connection::connection::State as ConnectionState;
+use crate::core::ics03_connection::delay::verify_conn_delay_passed;
+use crate::core::ics04_channel::channel::Counterparty;
 use crate::core::ics04_channel::channel::State;
-use crate::core::ics04_channel::channel::{Counterparty, Order};
 use crate::core::ics04_channel::error::ChannelError;
 use crate::core::ics04_channel::events::AcknowledgePacket;
 use crate::core::ics04_channel::msgs::acknowledgement::MsgAcknowledgement;
 use crate::core::ics04_channel::packet::{PacketResult, Sequence};
 use crate::core::ics04_channel::{context::ChannelReader, error::PacketError};
 use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::core::ics24_host::path::AcksPath;
 use crate::events::IbcEvent;
 use crate::handler::{HandlerOutput, HandlerResult};
 use crate::prelude::*;
 
+#[cfg(feature = "val_exec_ctx")]
+pub(crate) use val_exec_ctx::*;
+#[cfg(feature = "val_exec_ctx")]
+pub(crate) mod val_exec_ctx {
+    use super::*;
+    use crate::core::ics03_connection::connection::ConnectionEnd;
+    use crate::core::ics03_connection::delay::new_verify_conn_delay_passed;
+    use crate::core::{ContextError, ValidationContext};
+
+    pub fn validate<Ctx>(ctx_a: &Ctx, msg: &MsgAcknowledgement) -> Result<(), ContextError>
+    where
+        Ctx: ValidationContext,
+    {
+        let packet = &msg.packet;
+        let chan_end_on_a =
+            ctx_a.channel_end(&(packet.port_on_a.clone(), packet.chan_on_a.clone()))?;
+
+        // In-flight packets are still acknowledged while the channel is flushing for an upgrade.
+        if !chan_end_on_a.state_matches(&State::Open)
+            && !chan_end_on_a.state_matches(&State::Flushing)
+        {
+            return Err(PacketError::ChannelClosed {
+                channel_id: packet.chan_on_a.clone(),
+            }
+            .into());
+        }
+
+        let counterparty =
+            Counterparty::new(packet.port_on_b.clone(), Some(packet.chan_on_b.clone()));
+
+        if !chan_end_on_a.counterparty_matches(&counterparty) {
+            return Err(PacketError::InvalidPacketCounterparty {
+                port_id: packet.port_on_b.clone(),
+                channel_id: packet.chan_on_b.clone(),
+            }
+            .into());
+        }
+
+        let conn_id_on_a = &chan_end_on_a.connection_hops()[0];
+        let conn_end_on_a = ctx_a.connection_end(conn_id_on_a)?;
+
+        if !conn_end_on_a.state_matches(&ConnectionState::Open) {
+            return Err(PacketError::ConnectionNotOpen {
+                connection_id: chan_end_on_a.connection_hops()[0].clone(),
+            }
+            .into());
+        }
+
+        // Verify packet commitment
+        let packet_commitment = ctx_a.get_packet_commitment(&(
+            packet.port_on_a.clone(),
+            packet.chan_on_a.clone(),
+            packet.sequence,
+        ))?;
+
+        if packet_commitment
+            != ctx_a.packet_commitment(
+                &packet.data,
+                &packet.timeout_height_on_b,
+                &packet.timeout_timestamp_on_b,
+            )
+        {
+            return Err(PacketError::IncorrectPacketCommitment {
+                sequence: packet.sequence,
+            }
+            .into());
+        }
+
+        if chan_end_on_a.ordering.is_ordered() {
+            let next_seq_ack = ctx_a
+                .get_next_sequence_ack(&(packet.port_on_a.clone(), packet.chan_on_a.clone()))?;
+
+            if packet.sequence != next_seq_ack {
+                return Err(PacketError::InvalidPacketSequence {
+                    given_sequence: packet.sequence,
+                    next_sequence: next_seq_ack,
+                }
+                .into());
+            }
+        }
+
+        verify_proofs(ctx_a, msg, &conn_end_on_a)?;
+
+        Ok(())
+    }
+
+    /// Verifies the proof of the message, and returns the state of the client once it was verified
+    /// against it.
+    pub fn verify_proofs<Ctx>(
+        ctx_a: &Ctx,
+        msg: &MsgAcknowledgement,
+        conn_end_on_a: &ConnectionEnd,
+    ) -> Result<Box<dyn ClientState>, ContextError>
+    where
+        Ctx: ValidationContext,
+    {
+        let packet = &msg.packet;
+
+        let client_id_on_a = conn_end_on_a.client_id();
+        let mut client_state_on_a = ctx_a.client_state(client_id_on_a)?;
+
+        // The client must be active.
+        let status = client_state_on_a.new_status(ctx_a, client_id_on_a);
+        if !status.is_active() {
+            return Err(ClientError::ClientNotActive {
+                client_id: client_id_on_a.clone(),
+                status,
+            }
+            .into());
+        }
+
+        let consensus_state = ctx_a.consensus_state(client_id_on_a, &msg.proof_height_on_b)?;
+
+        let ack_commitment = ctx_a.ack_commitment(&msg.acknowledgement);
+
+        // Verify the proof for the packet against the chain store.
+        client_state_on_a
+            .validate_proof_height(msg.proof_height_on_b)
+            .map_err(|e| ChannelError::PacketVerificationFailed {
+                sequence: packet.sequence,
+                client_error: e,
+            })
+            .map_err(PacketError::Channel)?;
+        new_verify_conn_delay_passed(ctx_a, msg.proof_height_on_b, conn_end_on_a)
+            .map_err(PacketError::Connection)?;
+
+        client_state_on_a
+            .new_verify_membership(
+                ctx_a,
+                conn_end_on_a.counterparty().prefix(),
+                &msg.proof_acked_on_b,
+                consensus_state.root(),
+                AcksPath {
+                    port_id: packet.port_on_b.clone(),
+                    channel_id: packet.chan_on_b.clone(),
+                    sequence: packet.sequence,
+                }
+                .into(),
+                ack_commitment.into_vec(),
+            )
+            .map_err(|e| ChannelError::PacketVerificationFailed {
+                sequence: packet.sequence,
+                client_error: e,
+            })
+            .map_err(PacketError::Channel)?;
+
+        Ok(client_state_on_a)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct AckPacketResult {
     pub port_id: PortId,
     pub channel_id: ChannelId,
     pub seq: Sequence,
     pub seq_number: Option<Sequence>,
+    /// The state of the client underlying the channel once the proof of the message was verified
+    /// against it.
+    pub client_state: Box<dyn ClientState>,
 }
 
-pub(crate) fn process<Ctx: ChannelReader>(
+pub(crate) fn process<Ctx: ChannelReader + ClientReader>(
     ctx_a: &Ctx,
     msg: &MsgAcknowledgement,
 ) -> HandlerResult<PacketResult, PacketError> {
@@ -74,17 +232,20 @@ pub(crate) fn process<Ctx: ChannelReader>(
     }
 
     // Verify proofs
-    {
+    let client_state_on_a = {
         let client_id_on_a = conn_end_on_a.client_id();
-        let client_state_on_a = ctx_a
-            .client_state(client_id_on_a)
-            .map_err(PacketError::Channel)?;
-
-        // The client must not be frozen.
-        if client_state_on_a.is_frozen() {
-            return Err(PacketError::FrozenClient {
-                client_id: client_id_on_a.clone(),
-            });
+        let mut client_state_on_a =
+            ChannelReader::client_state(ctx_a, client_id_on_a).map_err(PacketError::Channel)?;
+
+        // The client must be active.
+        let status = client_state_on_a.status(ctx_a, client_id_on_a);
+        if !status.is_active() {
+            return Err(PacketError::Channel(ChannelError::Client(
+                ClientError::ClientNotActive {
+                    client_id: client_id_on_a.clone(),
+                    status,
+                },
+            )));
         }
 
         let consensus_state = ctx_a
@@ -95,25 +256,39 @@ pub(crate) fn process<Ctx: ChannelReader>(
 
         // Verify the proof for the packet against the chain store.
         client_state_on_a
-            .verify_packet_acknowledgement(
+            .validate_proof_height(msg.proof_height_on_b)
+            .map_err(|e| ChannelError::PacketVerificationFailed {
+                sequence: packet.sequence,
+                client_error: e,
+            })
+            .map_err(PacketError::Channel)?;
+        verify_conn_delay_passed(ctx_a, msg.proof_height_on_b, &conn_end_on_a)
+            .map_err(PacketError::Connection)?;
+
+        client_state_on_a
+            .verify_membership(
                 ctx_a,
-                msg.proof_height_on_b,
-                &conn_end_on_a,
+                conn_end_on_a.counterparty().prefix(),
                 &msg.proof_acked_on_b,
                 consensus_state.root(),
-                &packet.port_on_b,
-                &packet.chan_on_b,
-                packet.sequence,
-                ack_commitment,
+                AcksPath {
+                    port_id: packet.port_on_b.clone(),
+                    channel_id: packet.chan_on_b.clone(),
+                    sequence: packet.sequence,
+                }
+                .into(),
+                ack_commitment.into_vec(),
             )
             .map_err(|e| ChannelError::PacketVerificationFailed {
                 sequence: packet.sequence,
                 client_error: e,
             })
             .map_err(PacketError::Channel)?;
-    }
 
-    let result = if chan_end_on_a.order_matches(&Order::Ordered) {
+        client_state_on_a
+    };
+
+    let result = if chan_end_on_a.ordering.is_ordered() {
         let next_seq_ack = ctx_a.get_next_sequence_ack(&packet.port_on_a, &packet.chan_on_a)?;
 
         if packet.sequence != next_seq_ack {
@@ -128,6 +303,7 @@ pub(crate) fn process<Ctx: ChannelReader>(
             channel_id: packet.chan_on_a.clone(),
             seq: packet.sequence,
             seq_number: Some(next_seq_ack.increment()),
+            client_state: client_state_on_a,
         })
     } else {
         PacketResult::Ack(AckPacketResult {
@@ -135,6 +311,7 @@ pub(crate) fn process<Ctx: ChannelReader>(
             channel_id: packet.chan_on_a.clone(),
             seq: packet.sequence,
             seq_number: None,
+            client_state: client_state_on_a,
         })
     };
 
@@ -163,11 +340,21 @@ mod tests {
     use crate::core::ics04_channel::handler::acknowledgement::process;
     use crate::core::ics04_channel::msgs::acknowledgement::test_util::get_dummy_raw_msg_acknowledgement;
     use crate::core::ics04_channel::msgs::acknowledgement::MsgAcknowledgement;
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::core::ics04_channel::msgs::PacketMsg;
     use crate::core::ics04_channel::Version;
     use crate::core::ics24_host::identifier::{ClientId, ConnectionId};
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::core::ics26_routing::context::ModuleId;
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::core::ics26_routing::msgs::MsgEnvelope;
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::core::{ExecutionContext, ValidationContext};
     use crate::events::IbcEvent;
     use crate::mock::context::MockContext;
     use crate::prelude::*;
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::test_utils::DummyTransferModule;
     use crate::timestamp::ZERO_DURATION;
 
     #[test]
@@ -189,7 +376,8 @@ mod tests {
         .unwrap();
         let packet = msg.packet.clone();
 
-        let data = context.packet_commitment(
+        let data = ChannelReader::packet_commitment(
+            &context,
             &packet.data,
             &packet.timeout_height_on_b,
             &packet.timeout_timestamp_on_b,
@@ -247,6 +435,41 @@ mod tests {
         .collect();
 
         for test in tests {
+            #[cfg(feature = "val_exec_ctx")]
+            {
+                let mut ctx = test.ctx.clone();
+                let module_id: ModuleId = "dummymodule".parse().unwrap();
+                ctx.scope_port_to_module(test.msg.packet.port_on_a.clone(), module_id.clone());
+                ctx.add_route(module_id, DummyTransferModule::new(ctx.ibc_store_share()))
+                    .unwrap();
+
+                let msg_envelope = MsgEnvelope::Packet(PacketMsg::Ack(test.msg.clone()));
+                let res = ValidationContext::validate(&ctx, msg_envelope.clone());
+                assert_eq!(
+                    res.is_ok(),
+                    test.want_pass,
+                    "ack_packet: validation mismatch for test: {}, error: {:?}",
+                    test.name,
+                    res
+                );
+
+                if test.want_pass {
+                    ExecutionContext::execute(&mut ctx, msg_envelope).unwrap();
+
+                    assert!(ChannelReader::get_packet_commitment(
+                        &ctx,
+                        &test.msg.packet.port_on_a,
+                        &test.msg.packet.chan_on_a,
+                        &test.msg.packet.sequence,
+                    )
+                    .is_err());
+                    assert!(matches!(
+                        ctx.ibc_store.lock().events.first(),
+                        Some(IbcEvent::AcknowledgePacket(_))
+                    ));
+                }
+            }
+
             let res = process(&test.ctx, &test.msg);
             // Additionally check the events and the output objects in the result.
             match res {
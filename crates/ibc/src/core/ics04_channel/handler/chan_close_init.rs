@@ -7,6 +7,51 @@ use crate::core::ics04_channel::handler::{ChannelIdState, ChannelResult};
 use crate::core::ics04_channel::msgs::chan_close_init::MsgChannelCloseInit;
 use crate::handler::{HandlerOutput, HandlerResult};
 
+#[cfg(feature = "val_exec_ctx")]
+pub(crate) use val_exec_ctx::*;
+#[cfg(feature = "val_exec_ctx")]
+pub(crate) mod val_exec_ctx {
+    use super::*;
+    use crate::core::{ContextError, ValidationContext};
+
+    pub fn validate<Ctx>(ctx_a: &Ctx, msg: &MsgChannelCloseInit) -> Result<(), ContextError>
+    where
+        Ctx: ValidationContext,
+    {
+        let chan_end_on_a =
+            ctx_a.channel_end(&(msg.port_id_on_a.clone(), msg.chan_id_on_a.clone()))?;
+
+        // Validate that the channel end is in a state where it can be closed.
+        if chan_end_on_a.state_matches(&State::Closed) {
+            return Err(ChannelError::InvalidChannelState {
+                channel_id: msg.chan_id_on_a.clone(),
+                state: chan_end_on_a.state,
+            }
+            .into());
+        }
+
+        // An OPEN IBC connection running on the local (host) chain should exist.
+        if chan_end_on_a.connection_hops().len() != 1 {
+            return Err(ChannelError::InvalidConnectionHopsLength {
+                expected: 1,
+                actual: chan_end_on_a.connection_hops().len(),
+            }
+            .into());
+        }
+
+        let conn_end_on_a = ctx_a.connection_end(&chan_end_on_a.connection_hops()[0])?;
+
+        if !conn_end_on_a.state_matches(&ConnectionState::Open) {
+            return Err(ChannelError::ConnectionNotOpen {
+                connection_id: chan_end_on_a.connection_hops()[0].clone(),
+            }
+            .into());
+        }
+
+        Ok(())
+    }
+}
+
 /// Per our convention, this message is processed on chain A.
 pub(crate) fn process<Ctx: ChannelReader>(
     ctx_a: &Ctx,
@@ -53,6 +98,7 @@ pub(crate) fn process<Ctx: ChannelReader>(
         channel_id: msg.chan_id_on_a.clone(),
         channel_id_state: ChannelIdState::Reused,
         channel_end: new_chan_end_on_a,
+        client_state: None,
     };
 
     Ok(output.with_result(result))
@@ -78,8 +124,18 @@ mod tests {
     use crate::core::ics04_channel::Version;
     use crate::core::ics24_host::identifier::{ClientId, ConnectionId};
 
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::core::ics26_routing::context::ModuleId;
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::core::ics26_routing::msgs::MsgEnvelope;
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::core::{ExecutionContext, ValidationContext};
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::events::IbcEvent;
     use crate::mock::client_state::client_type as mock_client_type;
     use crate::mock::context::MockContext;
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::test_utils::DummyTransferModule;
     use crate::timestamp::ZERO_DURATION;
 
     #[test]
@@ -111,7 +167,8 @@ mod tests {
 
         let context = {
             let default_context = MockContext::default();
-            let client_consensus_state_height = default_context.host_height().unwrap();
+            let client_consensus_state_height =
+                ChannelReader::host_height(&default_context).unwrap();
 
             default_context
                 .with_client(&client_id, client_consensus_state_height)
@@ -123,6 +180,45 @@ mod tests {
                 )
         };
 
+        #[cfg(feature = "val_exec_ctx")]
+        {
+            let mut ctx = context.clone();
+            let module_id: ModuleId = "dummymodule".parse().unwrap();
+            ctx.scope_port_to_module(msg_chan_close_init.port_id_on_a.clone(), module_id.clone());
+            ctx.add_route(module_id, DummyTransferModule::new(ctx.ibc_store_share()))
+                .unwrap();
+
+            let res = ValidationContext::validate(
+                &ctx,
+                MsgEnvelope::Channel(ChannelMsg::CloseInit(msg_chan_close_init.clone())),
+            );
+            assert!(
+                res.is_ok(),
+                "chan_close_init: validation failed, error: {:?}",
+                res
+            );
+
+            ExecutionContext::execute(
+                &mut ctx,
+                MsgEnvelope::Channel(ChannelMsg::CloseInit(msg_chan_close_init.clone())),
+            )
+            .unwrap();
+
+            let chan_end = ValidationContext::channel_end(
+                &ctx,
+                &(
+                    msg_chan_close_init.port_id_on_a.clone(),
+                    msg_chan_close_init.chan_id_on_a.clone(),
+                ),
+            )
+            .unwrap();
+            assert_eq!(chan_end.state().clone(), ChannelState::Closed);
+            assert!(matches!(
+                ctx.ibc_store.lock().events.first(),
+                Some(IbcEvent::CloseInitChannel(_))
+            ));
+        }
+
         channel_dispatch(&context, &ChannelMsg::CloseInit(msg_chan_close_init)).unwrap();
     }
 }
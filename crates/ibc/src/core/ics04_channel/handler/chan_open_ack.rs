@@ -1,15 +1,134 @@
 //! Protocol logic specific to ICS4 messages of type `MsgChannelOpenAck`.
+use crate::core::ics02_client::context::ClientReader;
+use crate::core::ics02_client::error::ClientError;
 use crate::core::ics03_connection::connection::State as ConnectionState;
 use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, State};
 use crate::core::ics04_channel::context::ChannelReader;
 use crate::core::ics04_channel::error::ChannelError;
 use crate::core::ics04_channel::handler::{ChannelIdState, ChannelResult};
 use crate::core::ics04_channel::msgs::chan_open_ack::MsgChannelOpenAck;
+use crate::core::ics24_host::path::ChannelEndsPath;
 use crate::handler::{HandlerOutput, HandlerResult};
 use crate::prelude::*;
+use ibc_proto::protobuf::Protobuf;
+
+#[cfg(feature = "val_exec_ctx")]
+pub(crate) use val_exec_ctx::*;
+#[cfg(feature = "val_exec_ctx")]
+pub(crate) mod val_exec_ctx {
+    use super::*;
+    use crate::core::ics02_client::client_state::ClientState;
+    use crate::core::ics03_connection::connection::ConnectionEnd;
+    use crate::core::{ContextError, ValidationContext};
+
+    pub fn validate<Ctx>(ctx_a: &Ctx, msg: &MsgChannelOpenAck) -> Result<(), ContextError>
+    where
+        Ctx: ValidationContext,
+    {
+        let chan_end_on_a =
+            ctx_a.channel_end(&(msg.port_id_on_a.clone(), msg.chan_id_on_a.clone()))?;
+
+        // Validate that the channel end is in a state where it can be ack.
+        if !chan_end_on_a.state_matches(&State::Init) {
+            return Err(ChannelError::InvalidChannelState {
+                channel_id: msg.chan_id_on_a.clone(),
+                state: chan_end_on_a.state,
+            }
+            .into());
+        }
+
+        // An OPEN IBC connection running on the local (host) chain should exist.
+        if chan_end_on_a.connection_hops().len() != 1 {
+            return Err(ChannelError::InvalidConnectionHopsLength {
+                expected: 1,
+                actual: chan_end_on_a.connection_hops().len(),
+            }
+            .into());
+        }
+
+        let conn_end_on_a = ctx_a.connection_end(&chan_end_on_a.connection_hops()[0])?;
+
+        if !conn_end_on_a.state_matches(&ConnectionState::Open) {
+            return Err(ChannelError::ConnectionNotOpen {
+                connection_id: chan_end_on_a.connection_hops()[0].clone(),
+            }
+            .into());
+        }
+
+        verify_proofs(ctx_a, msg, &chan_end_on_a, &conn_end_on_a)?;
+
+        Ok(())
+    }
+
+    /// Verifies the proof of the message, and returns the state of the client once it was verified
+    /// against it.
+    pub fn verify_proofs<Ctx>(
+        ctx_a: &Ctx,
+        msg: &MsgChannelOpenAck,
+        chan_end_on_a: &ChannelEnd,
+        conn_end_on_a: &ConnectionEnd,
+    ) -> Result<Box<dyn ClientState>, ContextError>
+    where
+        Ctx: ValidationContext,
+    {
+        let client_id_on_a = conn_end_on_a.client_id();
+        let mut client_state_of_b_on_a = ctx_a.client_state(client_id_on_a)?;
+        let consensus_state_of_b_on_a =
+            ctx_a.consensus_state(client_id_on_a, &msg.proof_height_on_b)?;
+        let prefix_on_b = conn_end_on_a.counterparty().prefix();
+        let port_id_on_b = &chan_end_on_a.counterparty().port_id;
+        let conn_id_on_b = conn_end_on_a.counterparty().connection_id().ok_or(
+            ChannelError::UndefinedConnectionCounterparty {
+                connection_id: chan_end_on_a.connection_hops()[0].clone(),
+            },
+        )?;
+
+        // The client must be active.
+        let status = client_state_of_b_on_a.new_status(ctx_a, client_id_on_a);
+        if !status.is_active() {
+            return Err(ClientError::ClientNotActive {
+                client_id: client_id_on_a.clone(),
+                status,
+            }
+            .into());
+        }
+
+        let expected_chan_end_on_b = ChannelEnd::new(
+            State::TryOpen,
+            // Note: Both ends of a channel must have the same ordering, so it's
+            // fine to use A's ordering here
+            *chan_end_on_a.ordering(),
+            Counterparty::new(msg.port_id_on_a.clone(), Some(msg.chan_id_on_a.clone())),
+            vec![conn_id_on_b.clone()],
+            msg.version_on_b.clone(),
+        );
+
+        // Verify the proof for the channel state against the expected channel end.
+        // A counterparty channel id of None in not possible, and is checked by validate_basic in msg.
+        let value = expected_chan_end_on_b
+            .encode_vec()
+            .map_err(|e| ChannelError::VerifyChannelFailed(ClientError::InvalidChannelEnd(e)))?;
+
+        client_state_of_b_on_a
+            .validate_proof_height(msg.proof_height_on_b)
+            .map_err(ChannelError::VerifyChannelFailed)?;
+        client_state_of_b_on_a
+            .new_verify_membership(
+                ctx_a,
+                prefix_on_b,
+                &msg.proof_chan_end_on_b,
+                consensus_state_of_b_on_a.root(),
+                ChannelEndsPath(port_id_on_b.clone(), msg.chan_id_on_b.clone()).into(),
+                value,
+            )
+            .map_err(ChannelError::VerifyChannelFailed)?;
+
+        Ok(client_state_of_b_on_a)
+    }
+}
 
 /// Per our convention, this message is processed on chain A.
-pub(crate) fn process<Ctx: ChannelReader>(
+pub(crate) fn process<Ctx: ChannelReader + ClientReader>(
     ctx_a: &Ctx,
     msg: &MsgChannelOpenAck,
 ) -> HandlerResult<ChannelResult, ChannelError> {
@@ -44,9 +163,9 @@ pub(crate) fn process<Ctx: ChannelReader>(
     }
 
     // Verify proofs
-    {
+    let client_state_of_b_on_a = {
         let client_id_on_a = conn_end_on_a.client_id();
-        let client_state_of_b_on_a = ctx_a.client_state(client_id_on_a)?;
+        let mut client_state_of_b_on_a = ChannelReader::client_state(ctx_a, client_id_on_a)?;
         let consensus_state_of_b_on_a =
             ctx_a.client_consensus_state(client_id_on_a, &msg.proof_height_on_b)?;
         let prefix_on_b = conn_end_on_a.counterparty().prefix();
@@ -57,11 +176,13 @@ pub(crate) fn process<Ctx: ChannelReader>(
             },
         )?;
 
-        // The client must not be frozen.
-        if client_state_of_b_on_a.is_frozen() {
-            return Err(ChannelError::FrozenClient {
+        // The client must be active.
+        let status = client_state_of_b_on_a.status(ctx_a, client_id_on_a);
+        if !status.is_active() {
+            return Err(ChannelError::Client(ClientError::ClientNotActive {
                 client_id: client_id_on_a.clone(),
-            });
+                status,
+            }));
         }
 
         let expected_chan_end_on_b = ChannelEnd::new(
@@ -76,18 +197,26 @@ pub(crate) fn process<Ctx: ChannelReader>(
 
         // Verify the proof for the channel state against the expected channel end.
         // A counterparty channel id of None in not possible, and is checked by validate_basic in msg.
+        let value = expected_chan_end_on_b
+            .encode_vec()
+            .map_err(|e| ChannelError::VerifyChannelFailed(ClientError::InvalidChannelEnd(e)))?;
+
+        client_state_of_b_on_a
+            .validate_proof_height(msg.proof_height_on_b)
+            .map_err(ChannelError::VerifyChannelFailed)?;
         client_state_of_b_on_a
-            .verify_channel_state(
-                msg.proof_height_on_b,
+            .verify_membership(
+                ctx_a,
                 prefix_on_b,
                 &msg.proof_chan_end_on_b,
                 consensus_state_of_b_on_a.root(),
-                port_id_on_b,
-                &msg.chan_id_on_b,
-                &expected_chan_end_on_b,
+                ChannelEndsPath(port_id_on_b.clone(), msg.chan_id_on_b.clone()).into(),
+                value,
             )
             .map_err(ChannelError::VerifyChannelFailed)?;
-    }
+
+        client_state_of_b_on_a
+    };
 
     output.log("success: channel open ack");
 
@@ -107,6 +236,7 @@ pub(crate) fn process<Ctx: ChannelReader>(
         channel_id: msg.chan_id_on_a.clone(),
         channel_id_state: ChannelIdState::Reused,
         channel_end: new_chan_end_on_a,
+        client_state: Some(client_state_of_b_on_a),
     };
 
     Ok(output.with_result(result))
@@ -134,8 +264,18 @@ mod tests {
     use crate::core::ics04_channel::msgs::chan_open_try::MsgChannelOpenTry;
     use crate::core::ics04_channel::msgs::ChannelMsg;
     use crate::core::ics24_host::identifier::ConnectionId;
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::core::ics26_routing::context::ModuleId;
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::core::ics26_routing::msgs::MsgEnvelope;
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::core::{ExecutionContext, ValidationContext};
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::events::IbcEvent;
     use crate::mock::context::MockContext;
     use crate::prelude::*;
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::test_utils::DummyTransferModule;
     use crate::Height;
 
     // TODO: The tests here are very fragile and complex.
@@ -283,7 +423,7 @@ mod tests {
                         msg_chan_ack.chan_id_on_a.clone(),
                         chan_end,
                     ),
-                msg: ChannelMsg::OpenAck(msg_chan_ack),
+                msg: ChannelMsg::OpenAck(msg_chan_ack.clone()),
                 want_pass: true,
             },
         ]
@@ -291,6 +431,42 @@ mod tests {
         .collect();
 
         for test in tests {
+            #[cfg(feature = "val_exec_ctx")]
+            {
+                let mut ctx = test.ctx.clone();
+                let module_id: ModuleId = "dummymodule".parse().unwrap();
+                ctx.scope_port_to_module(msg_chan_ack.port_id_on_a.clone(), module_id.clone());
+                ctx.add_route(module_id, DummyTransferModule::new(ctx.ibc_store_share()))
+                    .unwrap();
+
+                let res = ValidationContext::validate(&ctx, MsgEnvelope::Channel(test.msg.clone()));
+                assert_eq!(
+                    res.is_ok(),
+                    test.want_pass,
+                    "chan_open_ack: validation mismatch, error: {:?}",
+                    res
+                );
+
+                if test.want_pass {
+                    ExecutionContext::execute(&mut ctx, MsgEnvelope::Channel(test.msg.clone()))
+                        .unwrap();
+
+                    let chan_end = ValidationContext::channel_end(
+                        &ctx,
+                        &(
+                            msg_chan_ack.port_id_on_a.clone(),
+                            msg_chan_ack.chan_id_on_a.clone(),
+                        ),
+                    )
+                    .unwrap();
+                    assert_eq!(chan_end.state().clone(), State::Open);
+                    assert!(matches!(
+                        ctx.ibc_store.lock().events.first(),
+                        Some(IbcEvent::OpenAckChannel(_))
+                    ));
+                }
+            }
+
             let res = channel_dispatch(&test.ctx, &test.msg);
             // Additionally check the events and the output objects in the result.
             match res {
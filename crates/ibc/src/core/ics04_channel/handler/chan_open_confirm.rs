@@ -1,15 +1,136 @@
 //! Protocol logic specific to ICS4 messages of type `MsgChannelOpenConfirm`.
+use crate::core::ics02_client::context::ClientReader;
+use crate::core::ics02_client::error::ClientError;
 use crate::core::ics03_connection::connection::State as ConnectionState;
 use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, State};
 use crate::core::ics04_channel::context::ChannelReader;
 use crate::core::ics04_channel::error::ChannelError;
 use crate::core::ics04_channel::handler::{ChannelIdState, ChannelResult};
 use crate::core::ics04_channel::msgs::chan_open_confirm::MsgChannelOpenConfirm;
+use crate::core::ics24_host::path::ChannelEndsPath;
 use crate::handler::{HandlerOutput, HandlerResult};
 use crate::prelude::*;
+use ibc_proto::protobuf::Protobuf;
+
+#[cfg(feature = "val_exec_ctx")]
+pub(crate) use val_exec_ctx::*;
+#[cfg(feature = "val_exec_ctx")]
+pub(crate) mod val_exec_ctx {
+    use super::*;
+    use crate::core::ics02_client::client_state::ClientState;
+    use crate::core::ics03_connection::connection::ConnectionEnd;
+    use crate::core::{ContextError, ValidationContext};
+
+    pub fn validate<Ctx>(ctx_b: &Ctx, msg: &MsgChannelOpenConfirm) -> Result<(), ContextError>
+    where
+        Ctx: ValidationContext,
+    {
+        let chan_end_on_b =
+            ctx_b.channel_end(&(msg.port_id_on_b.clone(), msg.chan_id_on_b.clone()))?;
+
+        // Validate that the channel end is in a state where it can be confirmed.
+        if !chan_end_on_b.state_matches(&State::TryOpen) {
+            return Err(ChannelError::InvalidChannelState {
+                channel_id: msg.chan_id_on_b.clone(),
+                state: chan_end_on_b.state,
+            }
+            .into());
+        }
+
+        // An OPEN IBC connection running on the local (host) chain should exist.
+        if chan_end_on_b.connection_hops().len() != 1 {
+            return Err(ChannelError::InvalidConnectionHopsLength {
+                expected: 1,
+                actual: chan_end_on_b.connection_hops().len(),
+            }
+            .into());
+        }
+
+        let conn_end_on_b = ctx_b.connection_end(&chan_end_on_b.connection_hops()[0])?;
+
+        if !conn_end_on_b.state_matches(&ConnectionState::Open) {
+            return Err(ChannelError::ConnectionNotOpen {
+                connection_id: chan_end_on_b.connection_hops()[0].clone(),
+            }
+            .into());
+        }
+
+        verify_proofs(ctx_b, msg, &chan_end_on_b, &conn_end_on_b)?;
+
+        Ok(())
+    }
+
+    /// Verifies the proof of the message, and returns the state of the client once it was verified
+    /// against it.
+    pub fn verify_proofs<Ctx>(
+        ctx_b: &Ctx,
+        msg: &MsgChannelOpenConfirm,
+        chan_end_on_b: &ChannelEnd,
+        conn_end_on_b: &ConnectionEnd,
+    ) -> Result<Box<dyn ClientState>, ContextError>
+    where
+        Ctx: ValidationContext,
+    {
+        let client_id_on_b = conn_end_on_b.client_id();
+        let mut client_state_of_a_on_b = ctx_b.client_state(client_id_on_b)?;
+        let consensus_state_of_a_on_b =
+            ctx_b.consensus_state(client_id_on_b, &msg.proof_height_on_a)?;
+        let prefix_on_a = conn_end_on_b.counterparty().prefix();
+        let port_id_on_a = &chan_end_on_b.counterparty().port_id;
+        let chan_id_on_a = chan_end_on_b
+            .counterparty()
+            .channel_id()
+            .ok_or(ChannelError::InvalidCounterpartyChannelId)?;
+        let conn_id_on_a = conn_end_on_b.counterparty().connection_id().ok_or(
+            ChannelError::UndefinedConnectionCounterparty {
+                connection_id: chan_end_on_b.connection_hops()[0].clone(),
+            },
+        )?;
+
+        // The client must be active.
+        let status = client_state_of_a_on_b.new_status(ctx_b, client_id_on_b);
+        if !status.is_active() {
+            return Err(ClientError::ClientNotActive {
+                client_id: client_id_on_b.clone(),
+                status,
+            }
+            .into());
+        }
+
+        let expected_chan_end_on_a = ChannelEnd::new(
+            State::Init,
+            *chan_end_on_b.ordering(),
+            Counterparty::new(msg.port_id_on_b.clone(), None),
+            vec![conn_id_on_a.clone()],
+            chan_end_on_b.version.clone(),
+        );
+
+        // Verify the proof for the channel state against the expected channel end.
+        // A counterparty channel id of None in not possible, and is checked in msg.
+        let value = expected_chan_end_on_a
+            .encode_vec()
+            .map_err(|e| ChannelError::VerifyChannelFailed(ClientError::InvalidChannelEnd(e)))?;
+
+        client_state_of_a_on_b
+            .validate_proof_height(msg.proof_height_on_a)
+            .map_err(ChannelError::VerifyChannelFailed)?;
+        client_state_of_a_on_b
+            .new_verify_membership(
+                ctx_b,
+                prefix_on_a,
+                &msg.proof_chan_end_on_a,
+                consensus_state_of_a_on_b.root(),
+                ChannelEndsPath(port_id_on_a.clone(), chan_id_on_a.clone()).into(),
+                value,
+            )
+            .map_err(ChannelError::VerifyChannelFailed)?;
+
+        Ok(client_state_of_a_on_b)
+    }
+}
 
 /// Per our convention, this message is processed on chain B.
-pub(crate) fn process<Ctx: ChannelReader>(
+pub(crate) fn process<Ctx: ChannelReader + ClientReader>(
     ctx_b: &Ctx,
     msg: &MsgChannelOpenConfirm,
 ) -> HandlerResult<ChannelResult, ChannelError> {
@@ -43,9 +164,9 @@ pub(crate) fn process<Ctx: ChannelReader>(
     }
 
     // Verify proofs
-    {
+    let client_state_of_a_on_b = {
         let client_id_on_b = conn_end_on_b.client_id();
-        let client_state_of_a_on_b = ctx_b.client_state(client_id_on_b)?;
+        let mut client_state_of_a_on_b = ChannelReader::client_state(ctx_b, client_id_on_b)?;
         let consensus_state_of_a_on_b =
             ctx_b.client_consensus_state(client_id_on_b, &msg.proof_height_on_a)?;
         let prefix_on_a = conn_end_on_b.counterparty().prefix();
@@ -60,11 +181,13 @@ pub(crate) fn process<Ctx: ChannelReader>(
             },
         )?;
 
-        // The client must not be frozen.
-        if client_state_of_a_on_b.is_frozen() {
-            return Err(ChannelError::FrozenClient {
+        // The client must be active.
+        let status = client_state_of_a_on_b.status(ctx_b, client_id_on_b);
+        if !status.is_active() {
+            return Err(ChannelError::Client(ClientError::ClientNotActive {
                 client_id: client_id_on_b.clone(),
-            });
+                status,
+            }));
         }
 
         let expected_chan_end_on_a = ChannelEnd::new(
@@ -77,18 +200,26 @@ pub(crate) fn process<Ctx: ChannelReader>(
 
         // Verify the proof for the channel state against the expected channel end.
         // A counterparty channel id of None in not possible, and is checked in msg.
+        let value = expected_chan_end_on_a
+            .encode_vec()
+            .map_err(|e| ChannelError::VerifyChannelFailed(ClientError::InvalidChannelEnd(e)))?;
+
+        client_state_of_a_on_b
+            .validate_proof_height(msg.proof_height_on_a)
+            .map_err(ChannelError::VerifyChannelFailed)?;
         client_state_of_a_on_b
-            .verify_channel_state(
-                msg.proof_height_on_a,
+            .verify_membership(
+                ctx_b,
                 prefix_on_a,
                 &msg.proof_chan_end_on_a,
                 consensus_state_of_a_on_b.root(),
-                port_id_on_a,
-                chan_id_on_a,
-                &expected_chan_end_on_a,
+                ChannelEndsPath(port_id_on_a.clone(), chan_id_on_a.clone()).into(),
+                value,
             )
             .map_err(ChannelError::VerifyChannelFailed)?;
-    }
+
+        client_state_of_a_on_b
+    };
 
     output.log("success: channel open confirm ");
 
@@ -100,6 +231,7 @@ pub(crate) fn process<Ctx: ChannelReader>(
         channel_id: msg.chan_id_on_b.clone(),
         channel_id_state: ChannelIdState::Reused,
         channel_end: chan_end_on_b,
+        client_state: Some(client_state_of_a_on_b),
     };
 
     Ok(output.with_result(result))
@@ -124,8 +256,18 @@ mod tests {
     use crate::core::ics04_channel::msgs::ChannelMsg;
     use crate::core::ics04_channel::Version;
     use crate::core::ics24_host::identifier::{ClientId, ConnectionId};
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::core::ics26_routing::context::ModuleId;
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::core::ics26_routing::msgs::MsgEnvelope;
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::core::{ExecutionContext, ValidationContext};
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::events::IbcEvent;
     use crate::mock::client_state::client_type as mock_client_type;
     use crate::mock::context::MockContext;
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::test_utils::DummyTransferModule;
     use crate::timestamp::ZERO_DURATION;
     use crate::Height;
 
@@ -182,13 +324,49 @@ mod tests {
                     msg_chan_confirm.chan_id_on_b.clone(),
                     chan_end,
                 ),
-            msg: ChannelMsg::OpenConfirm(msg_chan_confirm),
+            msg: ChannelMsg::OpenConfirm(msg_chan_confirm.clone()),
             want_pass: true,
         }]
         .into_iter()
         .collect();
 
         for test in tests {
+            #[cfg(feature = "val_exec_ctx")]
+            {
+                let mut ctx = test.ctx.clone();
+                let module_id: ModuleId = "dummymodule".parse().unwrap();
+                ctx.scope_port_to_module(msg_chan_confirm.port_id_on_b.clone(), module_id.clone());
+                ctx.add_route(module_id, DummyTransferModule::new(ctx.ibc_store_share()))
+                    .unwrap();
+
+                let res = ValidationContext::validate(&ctx, MsgEnvelope::Channel(test.msg.clone()));
+                assert_eq!(
+                    res.is_ok(),
+                    test.want_pass,
+                    "chan_open_confirm: validation mismatch, error: {:?}",
+                    res
+                );
+
+                if test.want_pass {
+                    ExecutionContext::execute(&mut ctx, MsgEnvelope::Channel(test.msg.clone()))
+                        .unwrap();
+
+                    let chan_end = ValidationContext::channel_end(
+                        &ctx,
+                        &(
+                            msg_chan_confirm.port_id_on_b.clone(),
+                            msg_chan_confirm.chan_id_on_b.clone(),
+                        ),
+                    )
+                    .unwrap();
+                    assert_eq!(chan_end.state().clone(), State::Open);
+                    assert!(matches!(
+                        ctx.ibc_store.lock().events.first(),
+                        Some(IbcEvent::OpenConfirmChannel(_))
+                    ));
+                }
+            }
+
             let res = channel_dispatch(&test.ctx, &test.msg);
             // Additionally check the events and the output objects in the result.
             match res {
@@ -9,6 +9,42 @@ use crate::core::ics24_host::identifier::ChannelId;
 use crate::handler::{HandlerOutput, HandlerResult};
 use crate::prelude::*;
 
+#[cfg(feature = "val_exec_ctx")]
+pub(crate) use val_exec_ctx::*;
+#[cfg(feature = "val_exec_ctx")]
+pub(crate) mod val_exec_ctx {
+    use super::*;
+    use crate::core::{ContextError, ValidationContext};
+
+    pub fn validate<Ctx>(ctx_a: &Ctx, msg: &MsgChannelOpenInit) -> Result<(), ContextError>
+    where
+        Ctx: ValidationContext,
+    {
+        if msg.connection_hops_on_a.len() != 1 {
+            return Err(ChannelError::InvalidConnectionHopsLength {
+                expected: 1,
+                actual: msg.connection_hops_on_a.len(),
+            }
+            .into());
+        }
+
+        // An IBC connection running on the local (host) chain should exist.
+        let conn_end_on_a = ctx_a.connection_end(&msg.connection_hops_on_a[0])?;
+
+        let conn_version = match conn_end_on_a.versions() {
+            [version] => version,
+            _ => return Err(ChannelError::InvalidVersionLengthConnection.into()),
+        };
+
+        let channel_feature = msg.ordering.to_string();
+        if !conn_version.is_supported_feature(channel_feature) {
+            return Err(ChannelError::ChannelFeatureNotSuportedByConnection.into());
+        }
+
+        Ok(())
+    }
+}
+
 /// Per our convention, this message is processed on chain A.
 pub(crate) fn process<Ctx: ChannelReader>(
     ctx_a: &Ctx,
@@ -55,6 +91,7 @@ pub(crate) fn process<Ctx: ChannelReader>(
         channel_id: chan_id_on_a,
         channel_end: chan_end_on_a,
         channel_id_state: ChannelIdState::Generated,
+        client_state: None,
     };
 
     Ok(output.with_result(result))
@@ -70,14 +107,24 @@ mod tests {
     use crate::core::ics03_connection::connection::State as ConnectionState;
     use crate::core::ics03_connection::msgs::conn_open_init::test_util::get_dummy_raw_msg_conn_open_init;
     use crate::core::ics03_connection::msgs::conn_open_init::MsgConnectionOpenInit;
-    use crate::core::ics03_connection::version::get_compatible_versions;
-    use crate::core::ics04_channel::channel::State;
+    use crate::core::ics03_connection::version::{get_compatible_versions, Version};
+    use crate::core::ics04_channel::channel::{Order, State};
     use crate::core::ics04_channel::handler::channel_dispatch;
     use crate::core::ics04_channel::msgs::chan_open_init::test_util::get_dummy_raw_msg_chan_open_init;
     use crate::core::ics04_channel::msgs::chan_open_init::MsgChannelOpenInit;
     use crate::core::ics04_channel::msgs::ChannelMsg;
     use crate::core::ics24_host::identifier::ConnectionId;
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::core::ics26_routing::context::ModuleId;
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::core::ics26_routing::msgs::MsgEnvelope;
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::core::{ExecutionContext, ValidationContext};
     use crate::mock::context::MockContext;
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::test_utils::DummyTransferModule;
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::{core::ics24_host::identifier::ChannelId, events::IbcEvent};
 
     #[test]
     fn chan_open_init_msg_processing() {
@@ -104,6 +151,18 @@ mod tests {
             msg_conn_init.delay_period,
         );
 
+        // A connection which only negotiated unordered channels
+        let unordered_conn_end = ConnectionEnd::new(
+            ConnectionState::Init,
+            msg_conn_init.client_id_on_a.clone(),
+            msg_conn_init.counterparty.clone(),
+            vec![Version::new(
+                "1".to_string(),
+                vec![Order::Unordered.as_str().to_string()],
+            )],
+            msg_conn_init.delay_period,
+        );
+
         let cid = ConnectionId::default();
 
         let tests: Vec<Test> = vec![
@@ -113,10 +172,19 @@ mod tests {
                 msg: ChannelMsg::OpenInit(msg_chan_init.clone()),
                 want_pass: false,
             },
+            Test {
+                name: "Processing fails because the connection does not support the ordering"
+                    .to_string(),
+                ctx: context
+                    .clone()
+                    .with_connection(cid.clone(), unordered_conn_end),
+                msg: ChannelMsg::OpenInit(msg_chan_init.clone()),
+                want_pass: false,
+            },
             Test {
                 name: "Good parameters".to_string(),
                 ctx: context.with_connection(cid, init_conn_end),
-                msg: ChannelMsg::OpenInit(msg_chan_init),
+                msg: ChannelMsg::OpenInit(msg_chan_init.clone()),
                 want_pass: true,
             },
         ]
@@ -124,6 +192,41 @@ mod tests {
         .collect();
 
         for test in tests {
+            #[cfg(feature = "val_exec_ctx")]
+            {
+                let mut ctx = test.ctx.clone();
+                let module_id: ModuleId = "dummymodule".parse().unwrap();
+                ctx.scope_port_to_module(msg_chan_init.port_id_on_a.clone(), module_id.clone());
+                ctx.add_route(module_id, DummyTransferModule::new(ctx.ibc_store_share()))
+                    .unwrap();
+
+                let res = ValidationContext::validate(&ctx, MsgEnvelope::Channel(test.msg.clone()));
+                assert_eq!(
+                    res.is_ok(),
+                    test.want_pass,
+                    "chan_open_init: validation mismatch for test: {}, error: {:?}",
+                    test.name,
+                    res
+                );
+
+                if test.want_pass {
+                    ExecutionContext::execute(&mut ctx, MsgEnvelope::Channel(test.msg.clone()))
+                        .unwrap();
+
+                    let chan_end_on_a = ValidationContext::channel_end(
+                        &ctx,
+                        &(msg_chan_init.port_id_on_a.clone(), ChannelId::new(0)),
+                    )
+                    .unwrap();
+                    assert_eq!(chan_end_on_a.state().clone(), State::Init);
+                    assert_eq!(ValidationContext::channel_counter(&ctx).unwrap(), 1);
+                    assert!(matches!(
+                        ctx.ibc_store.lock().events.first(),
+                        Some(IbcEvent::OpenInitChannel(_))
+                    ));
+                }
+            }
+
             let res = channel_dispatch(&test.ctx, &test.msg);
             // Additionally check the events and the output objects in the result.
             match res {
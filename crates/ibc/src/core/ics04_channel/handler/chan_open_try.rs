@@ -1,5 +1,7 @@
 //! Protocol logic specific to ICS4 messages of type `MsgChannelOpenTry`.
 
+use crate::core::ics02_client::context::ClientReader;
+use crate::core::ics02_client::error::ClientError;
 use crate::core::ics03_connection::connection::State as ConnectionState;
 use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, State};
 use crate::core::ics04_channel::context::ChannelReader;
@@ -8,14 +10,18 @@ use crate::core::ics04_channel::handler::{ChannelIdState, ChannelResult};
 use crate::core::ics04_channel::msgs::chan_open_try::MsgChannelOpenTry;
 use crate::core::ics04_channel::Version;
 use crate::core::ics24_host::identifier::ChannelId;
+use crate::core::ics24_host::path::ChannelEndsPath;
 use crate::handler::{HandlerOutput, HandlerResult};
 use crate::prelude::*;
+use ibc_proto::protobuf::Protobuf;
 
 #[cfg(feature = "val_exec_ctx")]
 pub(crate) use val_exec_ctx::*;
 #[cfg(feature = "val_exec_ctx")]
 pub(crate) mod val_exec_ctx {
     use super::*;
+    use crate::core::ics02_client::client_state::ClientState;
+    use crate::core::ics03_connection::connection::ConnectionEnd;
     use crate::core::{ContextError, ValidationContext};
 
     pub fn validate<Ctx>(ctx_b: &Ctx, msg: &MsgChannelOpenTry) -> Result<(), ContextError>
@@ -53,57 +59,76 @@ pub(crate) mod val_exec_ctx {
                 .map_err(ContextError::ChannelError);
         }
 
-        // Verify proofs
-        {
-            let client_id_on_b = conn_end_on_b.client_id();
-            let client_state_of_a_on_b = ctx_b.client_state(client_id_on_b)?;
-            let consensus_state_of_a_on_b =
-                ctx_b.consensus_state(client_id_on_b, &msg.proof_height_on_a)?;
-            let prefix_on_a = conn_end_on_b.counterparty().prefix();
-            let port_id_on_a = &&msg.port_id_on_a;
-            let chan_id_on_a = msg.chan_id_on_a.clone();
-            let conn_id_on_a = conn_end_on_b.counterparty().connection_id().ok_or(
-                ChannelError::UndefinedConnectionCounterparty {
-                    connection_id: msg.connection_hops_on_b[0].clone(),
-                },
-            )?;
+        verify_proofs(ctx_b, msg, &conn_end_on_b)?;
 
-            // The client must not be frozen.
-            if client_state_of_a_on_b.is_frozen() {
-                return Err(ChannelError::FrozenClient {
-                    client_id: client_id_on_b.clone(),
-                })
-                .map_err(ContextError::ChannelError);
-            }
+        Ok(())
+    }
 
-            let expected_chan_end_on_a = ChannelEnd::new(
-                State::Init,
-                msg.ordering,
-                Counterparty::new(msg.port_id_on_b.clone(), None),
-                vec![conn_id_on_a.clone()],
-                msg.version_supported_on_a.clone(),
-            );
-
-            // Verify the proof for the channel state against the expected channel end.
-            // A counterparty channel id of None in not possible, and is checked by validate_basic in msg.
-            client_state_of_a_on_b
-                .verify_channel_state(
-                    msg.proof_height_on_a,
-                    prefix_on_a,
-                    &msg.proof_chan_end_on_a,
-                    consensus_state_of_a_on_b.root(),
-                    port_id_on_a,
-                    &chan_id_on_a,
-                    &expected_chan_end_on_a,
-                )
-                .map_err(ChannelError::VerifyChannelFailed)?;
+    /// Verifies the proof of the message, and returns the state of the client once it was verified
+    /// against it.
+    pub fn verify_proofs<Ctx>(
+        ctx_b: &Ctx,
+        msg: &MsgChannelOpenTry,
+        conn_end_on_b: &ConnectionEnd,
+    ) -> Result<Box<dyn ClientState>, ContextError>
+    where
+        Ctx: ValidationContext,
+    {
+        let client_id_on_b = conn_end_on_b.client_id();
+        let mut client_state_of_a_on_b = ctx_b.client_state(client_id_on_b)?;
+        let consensus_state_of_a_on_b =
+            ctx_b.consensus_state(client_id_on_b, &msg.proof_height_on_a)?;
+        let prefix_on_a = conn_end_on_b.counterparty().prefix();
+        let chan_id_on_a = msg.chan_id_on_a.clone();
+        let conn_id_on_a = conn_end_on_b.counterparty().connection_id().ok_or(
+            ChannelError::UndefinedConnectionCounterparty {
+                connection_id: msg.connection_hops_on_b[0].clone(),
+            },
+        )?;
+
+        // The client must be active.
+        let status = client_state_of_a_on_b.new_status(ctx_b, client_id_on_b);
+        if !status.is_active() {
+            return Err(ClientError::ClientNotActive {
+                client_id: client_id_on_b.clone(),
+                status,
+            }
+            .into());
         }
 
-        Ok(())
+        let expected_chan_end_on_a = ChannelEnd::new(
+            State::Init,
+            msg.ordering,
+            Counterparty::new(msg.port_id_on_b.clone(), None),
+            vec![conn_id_on_a.clone()],
+            msg.version_supported_on_a.clone(),
+        );
+
+        // Verify the proof for the channel state against the expected channel end.
+        // A counterparty channel id of None in not possible, and is checked by validate_basic in msg.
+        let value = expected_chan_end_on_a
+            .encode_vec()
+            .map_err(|e| ChannelError::VerifyChannelFailed(ClientError::InvalidChannelEnd(e)))?;
+
+        client_state_of_a_on_b
+            .validate_proof_height(msg.proof_height_on_a)
+            .map_err(ChannelError::VerifyChannelFailed)?;
+        client_state_of_a_on_b
+            .new_verify_membership(
+                ctx_b,
+                prefix_on_a,
+                &msg.proof_chan_end_on_a,
+                consensus_state_of_a_on_b.root(),
+                ChannelEndsPath(msg.port_id_on_a.clone(), chan_id_on_a.clone()).into(),
+                value,
+            )
+            .map_err(ChannelError::VerifyChannelFailed)?;
+
+        Ok(client_state_of_a_on_b)
     }
 }
 /// Per our convention, this message is processed on chain B.
-pub(crate) fn process<Ctx: ChannelReader>(
+pub(crate) fn process<Ctx: ChannelReader + ClientReader>(
     ctx_b: &Ctx,
     msg: &MsgChannelOpenTry,
 ) -> HandlerResult<ChannelResult, ChannelError> {
@@ -135,13 +160,12 @@ pub(crate) fn process<Ctx: ChannelReader>(
     }
 
     // Verify proofs
-    {
+    let client_state_of_a_on_b = {
         let client_id_on_b = conn_end_on_b.client_id();
-        let client_state_of_a_on_b = ctx_b.client_state(client_id_on_b)?;
+        let mut client_state_of_a_on_b = ChannelReader::client_state(ctx_b, client_id_on_b)?;
         let consensus_state_of_a_on_b =
             ctx_b.client_consensus_state(client_id_on_b, &msg.proof_height_on_a)?;
         let prefix_on_a = conn_end_on_b.counterparty().prefix();
-        let port_id_on_a = &&msg.port_id_on_a;
         let chan_id_on_a = msg.chan_id_on_a.clone();
         let conn_id_on_a = conn_end_on_b.counterparty().connection_id().ok_or(
             ChannelError::UndefinedConnectionCounterparty {
@@ -149,11 +173,13 @@ pub(crate) fn process<Ctx: ChannelReader>(
             },
         )?;
 
-        // The client must not be frozen.
-        if client_state_of_a_on_b.is_frozen() {
-            return Err(ChannelError::FrozenClient {
+        // The client must be active.
+        let status = client_state_of_a_on_b.status(ctx_b, client_id_on_b);
+        if !status.is_active() {
+            return Err(ChannelError::Client(ClientError::ClientNotActive {
                 client_id: client_id_on_b.clone(),
-            });
+                status,
+            }));
         }
 
         let expected_chan_end_on_a = ChannelEnd::new(
@@ -166,18 +192,26 @@ pub(crate) fn process<Ctx: ChannelReader>(
 
         // Verify the proof for the channel state against the expected channel end.
         // A counterparty channel id of None in not possible, and is checked by validate_basic in msg.
+        let value = expected_chan_end_on_a
+            .encode_vec()
+            .map_err(|e| ChannelError::VerifyChannelFailed(ClientError::InvalidChannelEnd(e)))?;
+
         client_state_of_a_on_b
-            .verify_channel_state(
-                msg.proof_height_on_a,
+            .validate_proof_height(msg.proof_height_on_a)
+            .map_err(ChannelError::VerifyChannelFailed)?;
+        client_state_of_a_on_b
+            .verify_membership(
+                ctx_b,
                 prefix_on_a,
                 &msg.proof_chan_end_on_a,
                 consensus_state_of_a_on_b.root(),
-                port_id_on_a,
-                &chan_id_on_a,
-                &expected_chan_end_on_a,
+                ChannelEndsPath(msg.port_id_on_a.clone(), chan_id_on_a.clone()).into(),
+                value,
             )
             .map_err(ChannelError::VerifyChannelFailed)?;
-    }
+
+        client_state_of_a_on_b
+    };
 
     let chan_end_on_b = ChannelEnd::new(
         State::TryOpen,
@@ -199,6 +233,7 @@ pub(crate) fn process<Ctx: ChannelReader>(
         channel_id: chan_id_on_b,
         channel_end: chan_end_on_b,
         channel_id_state: ChannelIdState::Generated,
+        client_state: Some(client_state_of_a_on_b),
     };
 
     Ok(output.with_result(result))
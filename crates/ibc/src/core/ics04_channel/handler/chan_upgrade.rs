@@ -0,0 +1,684 @@
+//! Helpers shared by the handlers of the channel upgrade handshake messages.
+use crate::core::ics02_client::client_state::ClientState;
+use crate::core::ics02_client::error::ClientError;
+use crate::core::ics03_connection::connection::{ConnectionEnd, State as ConnectionState};
+use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
+use crate::core::ics04_channel::error::ChannelError;
+use crate::core::ics04_channel::packet::Sequence;
+use crate::core::ics04_channel::upgrade::{ErrorReceipt, Upgrade, UpgradeFields};
+use crate::core::ics23_commitment::commitment::CommitmentProofBytes;
+use crate::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
+use crate::core::ics24_host::path::{
+    ChannelEndsPath, ChannelUpgradeErrorPath, ChannelUpgradePath, Path,
+};
+use crate::core::{ContextError, ValidationContext};
+use crate::prelude::*;
+use crate::Height;
+use ibc_proto::protobuf::Protobuf;
+
+/// Returns the fields of the channel end which an upgrade can change.
+pub(crate) fn current_upgrade_fields(chan_end: &ChannelEnd) -> UpgradeFields {
+    UpgradeFields::new(
+        chan_end.ordering,
+        chan_end.connection_hops.clone(),
+        chan_end.version.clone(),
+    )
+}
+
+/// Returns the connection end with the given identifier, which must be open.
+pub(crate) fn open_connection_end<Ctx>(
+    ctx: &Ctx,
+    conn_id: &ConnectionId,
+) -> Result<ConnectionEnd, ContextError>
+where
+    Ctx: ValidationContext,
+{
+    let conn_end = ctx.connection_end(conn_id)?;
+
+    if !conn_end.state_matches(&ConnectionState::Open) {
+        return Err(ChannelError::ConnectionNotOpen {
+            connection_id: conn_id.clone(),
+        }
+        .into());
+    }
+
+    Ok(conn_end)
+}
+
+/// Returns the open connection end underlying the given channel end.
+pub(crate) fn channel_connection_end<Ctx>(
+    ctx: &Ctx,
+    chan_end: &ChannelEnd,
+) -> Result<ConnectionEnd, ContextError>
+where
+    Ctx: ValidationContext,
+{
+    if chan_end.connection_hops().len() != 1 {
+        return Err(ChannelError::InvalidConnectionHopsLength {
+            expected: 1,
+            actual: chan_end.connection_hops().len(),
+        }
+        .into());
+    }
+
+    open_connection_end(ctx, &chan_end.connection_hops()[0])
+}
+
+/// Returns the identifier of the counterparty of the given connection end.
+pub(crate) fn counterparty_connection_id(
+    conn_id: &ConnectionId,
+    conn_end: &ConnectionEnd,
+) -> Result<ConnectionId, ChannelError> {
+    conn_end.counterparty().connection_id().cloned().ok_or(
+        ChannelError::UndefinedConnectionCounterparty {
+            connection_id: conn_id.clone(),
+        },
+    )
+}
+
+/// Checks that the ordering of an upgraded channel is supported by the version of its
+/// connection.
+pub(crate) fn check_ordering_supported(
+    conn_end: &ConnectionEnd,
+    ordering: &Order,
+) -> Result<(), ChannelError> {
+    let conn_version = match conn_end.versions() {
+        [version] => version,
+        _ => return Err(ChannelError::InvalidVersionLengthConnection),
+    };
+
+    if !conn_version.is_supported_feature(ordering.to_string()) {
+        return Err(ChannelError::ChannelFeatureNotSuportedByConnection);
+    }
+
+    Ok(())
+}
+
+/// Builds the channel end expected on the counterparty chain, in the given state and with the
+/// given fields, whose counterparty is the local channel end.
+pub(crate) fn expected_counterparty_chan_end(
+    state: State,
+    fields_on_cp: UpgradeFields,
+    port_id: &PortId,
+    chan_id: &ChannelId,
+    upgrade_sequence: Sequence,
+) -> ChannelEnd {
+    let mut chan_end_on_cp = ChannelEnd::new(
+        state,
+        fields_on_cp.ordering,
+        Counterparty::new(port_id.clone(), Some(chan_id.clone())),
+        fields_on_cp.connection_hops,
+        fields_on_cp.version,
+    );
+    chan_end_on_cp.set_upgrade_sequence(upgrade_sequence);
+
+    chan_end_on_cp
+}
+
+/// Verifies the proof of the counterparty channel end against the expected one, with the client
+/// of the counterparty chain.
+pub(crate) fn verify_counterparty_chan_end<Ctx>(
+    ctx: &Ctx,
+    client_state_of_cp: &mut dyn ClientState,
+    chan_end: &ChannelEnd,
+    conn_end: &ConnectionEnd,
+    proof: &CommitmentProofBytes,
+    proof_height: Height,
+    expected_chan_end_on_cp: &ChannelEnd,
+) -> Result<(), ContextError>
+where
+    Ctx: ValidationContext,
+{
+    let (port_id_on_cp, chan_id_on_cp) = counterparty_port_channel_id(chan_end)?;
+    let value = expected_chan_end_on_cp
+        .encode_vec()
+        .map_err(|e| ChannelError::VerifyChannelFailed(ClientError::InvalidChannelEnd(e)))?;
+
+    verify_counterparty_membership(
+        ctx,
+        client_state_of_cp,
+        conn_end,
+        proof,
+        proof_height,
+        ChannelEndsPath(port_id_on_cp, chan_id_on_cp).into(),
+        value,
+    )
+}
+
+/// Verifies the proof of the upgrade stored by the counterparty against the expected one, with the
+/// client of the counterparty chain.
+pub(crate) fn verify_counterparty_upgrade<Ctx>(
+    ctx: &Ctx,
+    client_state_of_cp: &mut dyn ClientState,
+    chan_end: &ChannelEnd,
+    conn_end: &ConnectionEnd,
+    proof: &CommitmentProofBytes,
+    proof_height: Height,
+    expected_upgrade_on_cp: &Upgrade,
+) -> Result<(), ContextError>
+where
+    Ctx: ValidationContext,
+{
+    let (port_id_on_cp, chan_id_on_cp) = counterparty_port_channel_id(chan_end)?;
+    let value = expected_upgrade_on_cp
+        .encode_vec()
+        .map_err(|e| ChannelError::InvalidUpgrade {
+            reason: e.to_string(),
+        })?;
+
+    verify_counterparty_membership(
+        ctx,
+        client_state_of_cp,
+        conn_end,
+        proof,
+        proof_height,
+        ChannelUpgradePath(port_id_on_cp, chan_id_on_cp).into(),
+        value,
+    )
+}
+
+/// Verifies the proof of the error receipt stored by the counterparty against the expected one,
+/// with the client of the counterparty chain.
+pub(crate) fn verify_counterparty_error_receipt<Ctx>(
+    ctx: &Ctx,
+    client_state_of_cp: &mut dyn ClientState,
+    chan_end: &ChannelEnd,
+    conn_end: &ConnectionEnd,
+    proof: &CommitmentProofBytes,
+    proof_height: Height,
+    expected_receipt_on_cp: &ErrorReceipt,
+) -> Result<(), ContextError>
+where
+    Ctx: ValidationContext,
+{
+    let (port_id_on_cp, chan_id_on_cp) = counterparty_port_channel_id(chan_end)?;
+    let value = expected_receipt_on_cp
+        .encode_vec()
+        .map_err(|e| ChannelError::InvalidUpgrade {
+            reason: e.to_string(),
+        })?;
+
+    verify_counterparty_membership(
+        ctx,
+        client_state_of_cp,
+        conn_end,
+        proof,
+        proof_height,
+        ChannelUpgradeErrorPath(port_id_on_cp, chan_id_on_cp).into(),
+        value,
+    )
+}
+
+fn counterparty_port_channel_id(
+    chan_end: &ChannelEnd,
+) -> Result<(PortId, ChannelId), ChannelError> {
+    let port_id_on_cp = chan_end.counterparty().port_id().clone();
+    let chan_id_on_cp = chan_end
+        .counterparty()
+        .channel_id()
+        .cloned()
+        .ok_or(ChannelError::InvalidCounterpartyChannelId)?;
+
+    Ok((port_id_on_cp, chan_id_on_cp))
+}
+
+fn verify_counterparty_membership<Ctx>(
+    ctx: &Ctx,
+    client_state_of_cp: &mut dyn ClientState,
+    conn_end: &ConnectionEnd,
+    proof: &CommitmentProofBytes,
+    proof_height: Height,
+    path: Path,
+    value: Vec<u8>,
+) -> Result<(), ContextError>
+where
+    Ctx: ValidationContext,
+{
+    let client_id = conn_end.client_id();
+
+    // The client must be active.
+    let status = client_state_of_cp.new_status(ctx, client_id);
+    if !status.is_active() {
+        return Err(ClientError::ClientNotActive {
+            client_id: client_id.clone(),
+            status,
+        }
+        .into());
+    }
+
+    client_state_of_cp
+        .validate_proof_height(proof_height)
+        .map_err(ChannelError::VerifyChannelFailed)?;
+
+    let consensus_state_of_cp = ctx.consensus_state(client_id, &proof_height)?;
+
+    client_state_of_cp
+        .new_verify_membership(
+            ctx,
+            conn_end.counterparty().prefix(),
+            proof,
+            consensus_state_of_cp.root(),
+            path,
+            value,
+        )
+        .map_err(ChannelError::VerifyChannelFailed)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use test_log::test;
+
+    use crate::core::ics03_connection::connection::Counterparty as ConnectionCounterparty;
+    use crate::core::ics03_connection::msgs::test_util::get_dummy_raw_counterparty;
+    use crate::core::ics03_connection::version::get_compatible_versions;
+    use crate::core::ics04_channel::commitment::PacketCommitment;
+    use crate::core::ics04_channel::msgs::chan_upgrade_ack::MsgChannelUpgradeAck;
+    use crate::core::ics04_channel::msgs::chan_upgrade_cancel::test_util::get_dummy_raw_msg_chan_upgrade_cancel;
+    use crate::core::ics04_channel::msgs::chan_upgrade_cancel::MsgChannelUpgradeCancel;
+    use crate::core::ics04_channel::msgs::chan_upgrade_confirm::MsgChannelUpgradeConfirm;
+    use crate::core::ics04_channel::msgs::chan_upgrade_init::test_util::get_dummy_raw_msg_chan_upgrade_init;
+    use crate::core::ics04_channel::msgs::chan_upgrade_init::MsgChannelUpgradeInit;
+    use crate::core::ics04_channel::msgs::chan_upgrade_open::MsgChannelUpgradeOpen;
+    use crate::core::ics04_channel::msgs::chan_upgrade_timeout::MsgChannelUpgradeTimeout;
+    use crate::core::ics04_channel::msgs::chan_upgrade_try::test_util::get_dummy_raw_msg_chan_upgrade_try;
+    use crate::core::ics04_channel::msgs::chan_upgrade_try::MsgChannelUpgradeTry;
+    use crate::core::ics04_channel::msgs::ChannelMsg;
+    use crate::core::ics04_channel::upgrade::test_util::get_dummy_raw_upgrade;
+    use crate::core::ics04_channel::upgrade::UpgradeTimeout;
+    use crate::core::ics04_channel::Version;
+    use crate::core::ics24_host::identifier::ClientId;
+    use crate::core::ics26_routing::context::ModuleId;
+    use crate::core::ics26_routing::error::RouterError;
+    use crate::core::ics26_routing::msgs::MsgEnvelope;
+    use crate::core::{ExecutionContext, ValidationContext};
+    use crate::events::IbcEvent;
+    use crate::mock::client_state::client_type as mock_client_type;
+    use crate::mock::context::MockContext;
+    use crate::signer::Signer;
+    use crate::test_utils::{get_dummy_account_id, get_dummy_proof, DummyTransferModule};
+    use crate::timestamp::{Timestamp, ZERO_DURATION};
+
+    use super::*;
+
+    /// Returns a context whose open channel, on an open connection, can be upgraded to the
+    /// dummy upgrade fields.
+    fn upgradable_context() -> MockContext {
+        let client_id = ClientId::new(mock_client_type(), 24).unwrap();
+        let conn_end = ConnectionEnd::new(
+            ConnectionState::Open,
+            client_id.clone(),
+            ConnectionCounterparty::try_from(get_dummy_raw_counterparty()).unwrap(),
+            get_compatible_versions(),
+            ZERO_DURATION,
+        );
+        let chan_end = ChannelEnd::new(
+            State::Open,
+            Order::Unordered,
+            Counterparty::new(PortId::default(), Some(ChannelId::default())),
+            vec![ConnectionId::default()],
+            Version::default(),
+        );
+
+        let ctx = MockContext::default();
+        let height = ValidationContext::host_height(&ctx).unwrap();
+        let mut ctx = ctx
+            .with_client(&client_id, height)
+            .with_connection(ConnectionId::default(), conn_end)
+            .with_channel(PortId::default(), ChannelId::default(), chan_end)
+            .with_send_sequence(PortId::default(), ChannelId::default(), 1.into());
+
+        let module_id: ModuleId = "dummymodule".parse().unwrap();
+        ctx.scope_port_to_module(PortId::default(), module_id.clone());
+        ctx.add_route(module_id, DummyTransferModule::new(ctx.ibc_store_share()))
+            .unwrap();
+
+        ctx
+    }
+
+    fn deliver(ctx: &mut MockContext, msg: ChannelMsg) -> Result<(), RouterError> {
+        ValidationContext::validate(ctx, MsgEnvelope::Channel(msg.clone()))?;
+        ExecutionContext::execute(ctx, MsgEnvelope::Channel(msg))
+    }
+
+    /// Takes the events emitted so far by the IBC handlers, leaving out those of the modules.
+    fn take_core_events(ctx: &MockContext) -> Vec<IbcEvent> {
+        core::mem::take(&mut ctx.ibc_store.lock().events)
+            .into_iter()
+            .filter(|event| !matches!(event, IbcEvent::AppModule(_)))
+            .collect()
+    }
+
+    fn port_chan_id() -> (PortId, ChannelId) {
+        (PortId::default(), ChannelId::default())
+    }
+
+    fn proof_height(ctx: &MockContext) -> Height {
+        ValidationContext::host_height(ctx).unwrap()
+    }
+
+    fn signer() -> Signer {
+        get_dummy_account_id()
+    }
+
+    fn msg_upgrade_init() -> MsgChannelUpgradeInit {
+        MsgChannelUpgradeInit::try_from(get_dummy_raw_msg_chan_upgrade_init()).unwrap()
+    }
+
+    fn msg_upgrade_try(ctx_a: &MockContext) -> MsgChannelUpgradeTry {
+        MsgChannelUpgradeTry::try_from(get_dummy_raw_msg_chan_upgrade_try(
+            proof_height(ctx_a).revision_height(),
+        ))
+        .unwrap()
+    }
+
+    fn msg_upgrade_ack(ctx_b: &MockContext) -> MsgChannelUpgradeAck {
+        MsgChannelUpgradeAck {
+            port_id_on_a: PortId::default(),
+            chan_id_on_a: ChannelId::default(),
+            counterparty_upgrade: ValidationContext::channel_upgrade(ctx_b, &port_chan_id())
+                .unwrap(),
+            proof_chan_end_on_b: get_dummy_proof().try_into().unwrap(),
+            proof_upgrade_on_b: get_dummy_proof().try_into().unwrap(),
+            proof_height_on_b: proof_height(ctx_b),
+            signer: signer(),
+        }
+    }
+
+    fn msg_upgrade_confirm(ctx_a: &MockContext) -> MsgChannelUpgradeConfirm {
+        MsgChannelUpgradeConfirm {
+            port_id_on_b: PortId::default(),
+            chan_id_on_b: ChannelId::default(),
+            counterparty_chan_state: ValidationContext::channel_end(ctx_a, &port_chan_id())
+                .unwrap()
+                .state,
+            counterparty_upgrade: ValidationContext::channel_upgrade(ctx_a, &port_chan_id())
+                .unwrap(),
+            proof_chan_end_on_a: get_dummy_proof().try_into().unwrap(),
+            proof_upgrade_on_a: get_dummy_proof().try_into().unwrap(),
+            proof_height_on_a: proof_height(ctx_a),
+            signer: signer(),
+        }
+    }
+
+    fn msg_upgrade_open(ctx_cp: &MockContext) -> MsgChannelUpgradeOpen {
+        let chan_end_on_cp = ValidationContext::channel_end(ctx_cp, &port_chan_id()).unwrap();
+
+        MsgChannelUpgradeOpen {
+            port_id: PortId::default(),
+            chan_id: ChannelId::default(),
+            counterparty_chan_state: chan_end_on_cp.state,
+            counterparty_upgrade_sequence: chan_end_on_cp.upgrade_sequence(),
+            proof_chan_end: get_dummy_proof().try_into().unwrap(),
+            proof_height: proof_height(ctx_cp),
+            signer: signer(),
+        }
+    }
+
+    #[test]
+    fn chan_upgrade_handshake() {
+        let mut ctx_a = upgradable_context();
+        let mut ctx_b = upgradable_context();
+        let upgraded_version = Version::from("ics20-1".to_string());
+
+        deliver(&mut ctx_a, ChannelMsg::UpgradeInit(msg_upgrade_init())).unwrap();
+        let chan_end_on_a = ValidationContext::channel_end(&ctx_a, &port_chan_id()).unwrap();
+        assert_eq!(chan_end_on_a.state, State::Open);
+        assert_eq!(chan_end_on_a.upgrade_sequence(), 1.into());
+        match take_core_events(&ctx_a).as_slice() {
+            [IbcEvent::UpgradeInitChannel(event)] => {
+                assert_eq!(event.upgrade_version(), &upgraded_version);
+                assert_eq!(event.upgrade_sequence(), 1.into());
+            }
+            events => panic!("unexpected events: {events:?}"),
+        }
+
+        deliver(&mut ctx_b, ChannelMsg::UpgradeTry(msg_upgrade_try(&ctx_a))).unwrap();
+        let chan_end_on_b = ValidationContext::channel_end(&ctx_b, &port_chan_id()).unwrap();
+        assert_eq!(chan_end_on_b.state, State::Flushing);
+        assert_eq!(chan_end_on_b.upgrade_sequence(), 1.into());
+        assert!(ValidationContext::channel_upgrade(&ctx_b, &port_chan_id())
+            .unwrap()
+            .timeout
+            .is_set());
+        match take_core_events(&ctx_b).as_slice() {
+            [IbcEvent::UpgradeTryChannel(event)] => {
+                assert_eq!(event.upgrade_version(), &upgraded_version);
+                assert_eq!(event.upgrade_sequence(), 1.into());
+            }
+            events => panic!("unexpected events: {events:?}"),
+        }
+
+        // Without in-flight packets, chain A is done flushing right away.
+        deliver(&mut ctx_a, ChannelMsg::UpgradeAck(msg_upgrade_ack(&ctx_b))).unwrap();
+        let chan_end_on_a = ValidationContext::channel_end(&ctx_a, &port_chan_id()).unwrap();
+        assert_eq!(chan_end_on_a.state, State::FlushComplete);
+        assert!(matches!(
+            take_core_events(&ctx_a).as_slice(),
+            [IbcEvent::UpgradeAckChannel(_)]
+        ));
+
+        // Both ends have flushed: chain B completes its upgrade on confirm.
+        deliver(
+            &mut ctx_b,
+            ChannelMsg::UpgradeConfirm(msg_upgrade_confirm(&ctx_a)),
+        )
+        .unwrap();
+        let chan_end_on_b = ValidationContext::channel_end(&ctx_b, &port_chan_id()).unwrap();
+        assert_eq!(chan_end_on_b.state, State::Open);
+        assert_eq!(chan_end_on_b.version, upgraded_version);
+        assert!(ValidationContext::channel_upgrade(&ctx_b, &port_chan_id()).is_err());
+        match take_core_events(&ctx_b).as_slice() {
+            [IbcEvent::UpgradeConfirmChannel(confirm), IbcEvent::UpgradeOpenChannel(open)] => {
+                assert_eq!(confirm.channel_state(), &State::FlushComplete);
+                assert_eq!(open.upgrade_version(), &upgraded_version);
+            }
+            events => panic!("unexpected events: {events:?}"),
+        }
+
+        deliver(
+            &mut ctx_a,
+            ChannelMsg::UpgradeOpen(msg_upgrade_open(&ctx_b)),
+        )
+        .unwrap();
+        let chan_end_on_a = ValidationContext::channel_end(&ctx_a, &port_chan_id()).unwrap();
+        assert_eq!(chan_end_on_a.state, State::Open);
+        assert_eq!(chan_end_on_a.version, upgraded_version);
+        assert!(ValidationContext::channel_upgrade(&ctx_a, &port_chan_id()).is_err());
+        match take_core_events(&ctx_a).as_slice() {
+            [IbcEvent::UpgradeOpenChannel(event)] => {
+                assert_eq!(event.upgrade_version(), &upgraded_version);
+                assert_eq!(event.upgrade_sequence(), 1.into());
+            }
+            events => panic!("unexpected events: {events:?}"),
+        }
+    }
+
+    #[test]
+    fn chan_upgrade_open_moves_channel_to_upgraded_connection() {
+        let upgraded_conn_id = ConnectionId::new(1);
+
+        let ctx = upgradable_context();
+        let conn_end = ValidationContext::connection_end(&ctx, &ConnectionId::default()).unwrap();
+        let mut chan_end = ValidationContext::channel_end(&ctx, &port_chan_id()).unwrap();
+        chan_end.set_state(State::FlushComplete);
+        chan_end.set_upgrade_sequence(1.into());
+        let mut upgrade = Upgrade::try_from(get_dummy_raw_upgrade()).unwrap();
+        upgrade.fields.connection_hops = vec![upgraded_conn_id.clone()];
+
+        let mut ctx = ctx
+            .with_connection(upgraded_conn_id.clone(), conn_end)
+            .with_channel(PortId::default(), ChannelId::default(), chan_end)
+            .with_channel_upgrade(PortId::default(), ChannelId::default(), upgrade);
+        ExecutionContext::store_connection_channels(
+            &mut ctx,
+            ConnectionId::default(),
+            port_chan_id(),
+        )
+        .unwrap();
+
+        let msg = msg_upgrade_open(&ctx);
+        deliver(&mut ctx, ChannelMsg::UpgradeOpen(msg)).unwrap();
+
+        let chan_end = ValidationContext::channel_end(&ctx, &port_chan_id()).unwrap();
+        assert_eq!(chan_end.connection_hops, vec![upgraded_conn_id.clone()]);
+        assert!(
+            ValidationContext::connection_channels(&ctx, &ConnectionId::default())
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            ValidationContext::connection_channels(&ctx, &upgraded_conn_id).unwrap(),
+            vec![port_chan_id()]
+        );
+    }
+
+    #[test]
+    fn chan_upgrade_init_rejected() {
+        let msg = msg_upgrade_init();
+
+        // The proposed fields must differ from the current ones.
+        let mut ctx = upgradable_context();
+        let mut identical = msg.clone();
+        identical.fields.version = Version::default();
+        assert!(deliver(&mut ctx, ChannelMsg::UpgradeInit(identical)).is_err());
+
+        // Only an open channel can be upgraded.
+        let mut closed_chan_end = ValidationContext::channel_end(&ctx, &port_chan_id()).unwrap();
+        closed_chan_end.set_state(State::Closed);
+        let mut ctx = ctx.with_channel(PortId::default(), ChannelId::default(), closed_chan_end);
+        assert!(deliver(&mut ctx, ChannelMsg::UpgradeInit(msg)).is_err());
+    }
+
+    #[test]
+    fn chan_upgrade_flushes_in_flight_packets() {
+        let mut ctx_a = upgradable_context().with_packet_commitment(
+            PortId::default(),
+            ChannelId::default(),
+            1.into(),
+            PacketCommitment::from(vec![0]),
+        );
+        let mut ctx_b = upgradable_context();
+
+        deliver(&mut ctx_a, ChannelMsg::UpgradeInit(msg_upgrade_init())).unwrap();
+        deliver(&mut ctx_b, ChannelMsg::UpgradeTry(msg_upgrade_try(&ctx_a))).unwrap();
+        deliver(&mut ctx_a, ChannelMsg::UpgradeAck(msg_upgrade_ack(&ctx_b))).unwrap();
+
+        let chan_end_on_a = ValidationContext::channel_end(&ctx_a, &port_chan_id()).unwrap();
+        assert_eq!(chan_end_on_a.state, State::Flushing);
+
+        // Chain B cannot complete the upgrade before chain A flushed its packets.
+        deliver(
+            &mut ctx_b,
+            ChannelMsg::UpgradeConfirm(msg_upgrade_confirm(&ctx_a)),
+        )
+        .unwrap();
+        let chan_end_on_b = ValidationContext::channel_end(&ctx_b, &port_chan_id()).unwrap();
+        assert_eq!(chan_end_on_b.state, State::FlushComplete);
+
+        assert!(deliver(
+            &mut ctx_a,
+            ChannelMsg::UpgradeOpen(msg_upgrade_open(&ctx_b))
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn chan_upgrade_timeout() {
+        let flushing_context = |timeout_height: u64| {
+            let ctx = upgradable_context();
+            let mut chan_end = ValidationContext::channel_end(&ctx, &port_chan_id()).unwrap();
+            chan_end.set_state(State::Flushing);
+            chan_end.set_upgrade_sequence(1.into());
+
+            let mut upgrade = Upgrade::try_from(get_dummy_raw_upgrade()).unwrap();
+            upgrade.timeout = UpgradeTimeout::new(
+                Height::new(0, timeout_height).unwrap().into(),
+                Timestamp::none(),
+            );
+
+            ctx.with_channel(PortId::default(), ChannelId::default(), chan_end)
+                .with_channel_upgrade(PortId::default(), ChannelId::default(), upgrade)
+        };
+        let msg_upgrade_timeout = |ctx: &MockContext, state_on_cp: State| {
+            let mut chan_end_on_cp = ValidationContext::channel_end(ctx, &port_chan_id()).unwrap();
+            chan_end_on_cp.set_state(state_on_cp);
+            chan_end_on_cp.set_upgrade_sequence(Sequence::default());
+
+            MsgChannelUpgradeTimeout {
+                port_id: PortId::default(),
+                chan_id: ChannelId::default(),
+                counterparty_chan_end: chan_end_on_cp,
+                proof_chan_end: get_dummy_proof().try_into().unwrap(),
+                proof_height: proof_height(ctx),
+                signer: signer(),
+            }
+        };
+
+        // The upgrade has not timed out yet at the proof height.
+        let mut ctx = flushing_context(u64::MAX);
+        let msg = msg_upgrade_timeout(&ctx, State::Open);
+        assert!(deliver(&mut ctx, ChannelMsg::UpgradeTimeout(msg)).is_err());
+
+        // The counterparty is about to complete the upgrade.
+        let mut ctx = flushing_context(1);
+        let msg = msg_upgrade_timeout(&ctx, State::FlushComplete);
+        assert!(deliver(&mut ctx, ChannelMsg::UpgradeTimeout(msg)).is_err());
+
+        let msg = msg_upgrade_timeout(&ctx, State::Open);
+        deliver(&mut ctx, ChannelMsg::UpgradeTimeout(msg)).unwrap();
+
+        let chan_end = ValidationContext::channel_end(&ctx, &port_chan_id()).unwrap();
+        assert_eq!(chan_end.state, State::Open);
+        assert_eq!(chan_end.version, Version::default());
+        assert!(ValidationContext::channel_upgrade(&ctx, &port_chan_id()).is_err());
+        assert_eq!(
+            ValidationContext::channel_upgrade_error(&ctx, &port_chan_id())
+                .unwrap()
+                .sequence,
+            1.into()
+        );
+        assert!(matches!(
+            take_core_events(&ctx).as_slice(),
+            [IbcEvent::UpgradeTimeoutChannel(_)]
+        ));
+    }
+
+    #[test]
+    fn chan_upgrade_cancel() {
+        let mut ctx = upgradable_context();
+        deliver(&mut ctx, ChannelMsg::UpgradeInit(msg_upgrade_init())).unwrap();
+        take_core_events(&ctx);
+
+        let msg = MsgChannelUpgradeCancel::try_from(get_dummy_raw_msg_chan_upgrade_cancel(
+            proof_height(&ctx).revision_height(),
+        ))
+        .unwrap();
+
+        // An error receipt of a previous upgrade cannot cancel the one in progress.
+        let mut stale_msg = msg.clone();
+        stale_msg.error_receipt.sequence = Sequence::default();
+        assert!(deliver(&mut ctx, ChannelMsg::UpgradeCancel(stale_msg)).is_err());
+
+        deliver(&mut ctx, ChannelMsg::UpgradeCancel(msg)).unwrap();
+
+        let chan_end = ValidationContext::channel_end(&ctx, &port_chan_id()).unwrap();
+        assert_eq!(chan_end.state, State::Open);
+        assert_eq!(chan_end.upgrade_sequence(), 1.into());
+        assert!(ValidationContext::channel_upgrade(&ctx, &port_chan_id()).is_err());
+        assert!(matches!(
+            take_core_events(&ctx).as_slice(),
+            [IbcEvent::UpgradeCancelChannel(_)]
+        ));
+
+        // Nothing is left to cancel.
+        let msg = MsgChannelUpgradeCancel::try_from(get_dummy_raw_msg_chan_upgrade_cancel(
+            proof_height(&ctx).revision_height(),
+        ))
+        .unwrap();
+        assert!(deliver(&mut ctx, ChannelMsg::UpgradeCancel(msg)).is_err());
+    }
+}
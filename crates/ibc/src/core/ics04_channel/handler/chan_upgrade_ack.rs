@@ -0,0 +1,128 @@
+//! Protocol logic specific to ICS4 messages of type `MsgChannelUpgradeAck`.
+use crate::core::ics02_client::client_state::ClientState;
+use crate::core::ics04_channel::channel::{ChannelEnd, State};
+use crate::core::ics04_channel::error::ChannelError;
+use crate::core::ics04_channel::handler::chan_upgrade::{
+    channel_connection_end, counterparty_connection_id, current_upgrade_fields,
+    expected_counterparty_chan_end, open_connection_end, verify_counterparty_chan_end,
+    verify_counterparty_upgrade,
+};
+use crate::core::ics04_channel::msgs::chan_upgrade_ack::MsgChannelUpgradeAck;
+use crate::core::ics04_channel::upgrade::UpgradeFields;
+use crate::core::{ContextError, ValidationContext};
+use crate::prelude::*;
+
+/// Per our convention, this message is processed on chain A.
+pub fn validate<Ctx>(ctx_a: &Ctx, msg: &MsgChannelUpgradeAck) -> Result<(), ContextError>
+where
+    Ctx: ValidationContext,
+{
+    let port_chan_id_on_a = (msg.port_id_on_a.clone(), msg.chan_id_on_a.clone());
+    let chan_end_on_a = ctx_a.channel_end(&port_chan_id_on_a)?;
+
+    if !chan_end_on_a.state_matches(&State::Open) {
+        return Err(ChannelError::InvalidChannelState {
+            channel_id: msg.chan_id_on_a.clone(),
+            state: chan_end_on_a.state,
+        }
+        .into());
+    }
+
+    let upgrade_on_a = ctx_a.channel_upgrade(&port_chan_id_on_a)?;
+
+    verify_proofs(ctx_a, msg, &chan_end_on_a)?;
+
+    // Both chains must have agreed on the same upgrade.
+    {
+        let fields_on_a = &upgrade_on_a.fields;
+        let fields_on_b = &msg.counterparty_upgrade.fields;
+
+        if fields_on_a.ordering != fields_on_b.ordering
+            || fields_on_a.version != fields_on_b.version
+        {
+            return Err(ChannelError::IncompatibleUpgrade {
+                reason: "the upgraded orderings or versions differ".to_string(),
+            }
+            .into());
+        }
+
+        let upgraded_conn_id_on_a = &fields_on_a.connection_hops[0];
+        let upgraded_conn_end_on_a = open_connection_end(ctx_a, upgraded_conn_id_on_a)?;
+        let upgraded_conn_id_on_b =
+            counterparty_connection_id(upgraded_conn_id_on_a, &upgraded_conn_end_on_a)?;
+        if upgraded_conn_id_on_b != fields_on_b.connection_hops[0] {
+            return Err(ChannelError::IncompatibleUpgrade {
+                reason: format!(
+                    "connection {} is not the counterparty of the upgraded connection {upgraded_conn_id_on_a}",
+                    fields_on_b.connection_hops[0]
+                ),
+            }
+            .into());
+        }
+    }
+
+    // The upgrade must not have timed out on the host chain.
+    {
+        let timeout = msg.counterparty_upgrade.timeout;
+        if !timeout.is_set() {
+            return Err(ChannelError::InvalidUpgrade {
+                reason: "the counterparty upgrade has no timeout".to_string(),
+            }
+            .into());
+        }
+
+        if timeout.has_expired(ctx_a.host_height()?, &ctx_a.host_timestamp()?) {
+            return Err(ChannelError::UpgradeTimedOut.into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Verifies the proofs of the message, and returns the state of the client once they were
+/// verified against it.
+pub(crate) fn verify_proofs<Ctx>(
+    ctx_a: &Ctx,
+    msg: &MsgChannelUpgradeAck,
+    chan_end_on_a: &ChannelEnd,
+) -> Result<Box<dyn ClientState>, ContextError>
+where
+    Ctx: ValidationContext,
+{
+    let conn_end_on_a = channel_connection_end(ctx_a, chan_end_on_a)?;
+    let mut client_state_of_b_on_a = ctx_a.client_state(conn_end_on_a.client_id())?;
+    let conn_id_on_b =
+        counterparty_connection_id(&chan_end_on_a.connection_hops()[0], &conn_end_on_a)?;
+
+    let current_fields_on_b = UpgradeFields {
+        connection_hops: vec![conn_id_on_b],
+        ..current_upgrade_fields(chan_end_on_a)
+    };
+    let expected_chan_end_on_b = expected_counterparty_chan_end(
+        State::Flushing,
+        current_fields_on_b,
+        &msg.port_id_on_a,
+        &msg.chan_id_on_a,
+        chan_end_on_a.upgrade_sequence(),
+    );
+    verify_counterparty_chan_end(
+        ctx_a,
+        client_state_of_b_on_a.as_mut(),
+        chan_end_on_a,
+        &conn_end_on_a,
+        &msg.proof_chan_end_on_b,
+        msg.proof_height_on_b,
+        &expected_chan_end_on_b,
+    )?;
+    verify_counterparty_upgrade(
+        ctx_a,
+        client_state_of_b_on_a.as_mut(),
+        chan_end_on_a,
+        &conn_end_on_a,
+        &msg.proof_upgrade_on_b,
+        msg.proof_height_on_b,
+        &msg.counterparty_upgrade,
+    )?;
+
+    Ok(client_state_of_b_on_a)
+}
@@ -0,0 +1,61 @@
+//! Protocol logic specific to ICS4 messages of type `MsgChannelUpgradeCancel`.
+use crate::core::ics02_client::client_state::ClientState;
+use crate::core::ics04_channel::channel::ChannelEnd;
+use crate::core::ics04_channel::error::ChannelError;
+use crate::core::ics04_channel::handler::chan_upgrade::{
+    channel_connection_end, verify_counterparty_error_receipt,
+};
+use crate::core::ics04_channel::msgs::chan_upgrade_cancel::MsgChannelUpgradeCancel;
+use crate::core::{ContextError, ValidationContext};
+use crate::prelude::*;
+
+/// Can be processed on either chain, once the counterparty chain aborted the upgrade.
+pub fn validate<Ctx>(ctx: &Ctx, msg: &MsgChannelUpgradeCancel) -> Result<(), ContextError>
+where
+    Ctx: ValidationContext,
+{
+    let port_chan_id = (msg.port_id.clone(), msg.chan_id.clone());
+    let chan_end = ctx.channel_end(&port_chan_id)?;
+
+    // Ensures that an upgrade is in progress.
+    ctx.channel_upgrade(&port_chan_id)?;
+
+    // The error receipt must not be about a previous upgrade.
+    if msg.error_receipt.sequence < chan_end.upgrade_sequence() {
+        return Err(ChannelError::InvalidUpgradeSequence {
+            current: chan_end.upgrade_sequence(),
+            actual: msg.error_receipt.sequence,
+        }
+        .into());
+    }
+
+    verify_proofs(ctx, msg, &chan_end)?;
+
+    Ok(())
+}
+
+/// Verifies the proof of the message, and returns the state of the client once it was verified
+/// against it.
+pub(crate) fn verify_proofs<Ctx>(
+    ctx: &Ctx,
+    msg: &MsgChannelUpgradeCancel,
+    chan_end: &ChannelEnd,
+) -> Result<Box<dyn ClientState>, ContextError>
+where
+    Ctx: ValidationContext,
+{
+    let conn_end = channel_connection_end(ctx, chan_end)?;
+    let mut client_state_of_cp = ctx.client_state(conn_end.client_id())?;
+
+    verify_counterparty_error_receipt(
+        ctx,
+        client_state_of_cp.as_mut(),
+        chan_end,
+        &conn_end,
+        &msg.proof_error_receipt,
+        msg.proof_height,
+        &msg.error_receipt,
+    )?;
+
+    Ok(client_state_of_cp)
+}
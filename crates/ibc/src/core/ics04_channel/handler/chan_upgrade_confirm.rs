@@ -0,0 +1,106 @@
+//! Protocol logic specific to ICS4 messages of type `MsgChannelUpgradeConfirm`.
+use crate::core::ics02_client::client_state::ClientState;
+use crate::core::ics04_channel::channel::{ChannelEnd, State};
+use crate::core::ics04_channel::error::ChannelError;
+use crate::core::ics04_channel::handler::chan_upgrade::{
+    channel_connection_end, counterparty_connection_id, current_upgrade_fields,
+    expected_counterparty_chan_end, verify_counterparty_chan_end, verify_counterparty_upgrade,
+};
+use crate::core::ics04_channel::msgs::chan_upgrade_confirm::MsgChannelUpgradeConfirm;
+use crate::core::ics04_channel::upgrade::UpgradeFields;
+use crate::core::{ContextError, ValidationContext};
+use crate::prelude::*;
+
+/// Per our convention, this message is processed on chain B.
+pub fn validate<Ctx>(ctx_b: &Ctx, msg: &MsgChannelUpgradeConfirm) -> Result<(), ContextError>
+where
+    Ctx: ValidationContext,
+{
+    let port_chan_id_on_b = (msg.port_id_on_b.clone(), msg.chan_id_on_b.clone());
+    let chan_end_on_b = ctx_b.channel_end(&port_chan_id_on_b)?;
+
+    if !chan_end_on_b.state_matches(&State::Flushing) {
+        return Err(ChannelError::InvalidChannelState {
+            channel_id: msg.chan_id_on_b.clone(),
+            state: chan_end_on_b.state,
+        }
+        .into());
+    }
+
+    if !matches!(
+        msg.counterparty_chan_state,
+        State::Flushing | State::FlushComplete
+    ) {
+        return Err(ChannelError::InvalidUpgrade {
+            reason: format!(
+                "the counterparty channel end is in state {} instead of flushing",
+                msg.counterparty_chan_state
+            ),
+        }
+        .into());
+    }
+
+    // Ensures that an upgrade is in progress.
+    ctx_b.channel_upgrade(&port_chan_id_on_b)?;
+
+    verify_proofs(ctx_b, msg, &chan_end_on_b)?;
+
+    // The upgrade must not have timed out on the host chain.
+    if msg
+        .counterparty_upgrade
+        .timeout
+        .has_expired(ctx_b.host_height()?, &ctx_b.host_timestamp()?)
+    {
+        return Err(ChannelError::UpgradeTimedOut.into());
+    }
+
+    Ok(())
+}
+
+/// Verifies the proofs of the message, and returns the state of the client once they were
+/// verified against it.
+pub(crate) fn verify_proofs<Ctx>(
+    ctx_b: &Ctx,
+    msg: &MsgChannelUpgradeConfirm,
+    chan_end_on_b: &ChannelEnd,
+) -> Result<Box<dyn ClientState>, ContextError>
+where
+    Ctx: ValidationContext,
+{
+    let conn_end_on_b = channel_connection_end(ctx_b, chan_end_on_b)?;
+    let mut client_state_of_a_on_b = ctx_b.client_state(conn_end_on_b.client_id())?;
+    let conn_id_on_a =
+        counterparty_connection_id(&chan_end_on_b.connection_hops()[0], &conn_end_on_b)?;
+
+    let current_fields_on_a = UpgradeFields {
+        connection_hops: vec![conn_id_on_a],
+        ..current_upgrade_fields(chan_end_on_b)
+    };
+    let expected_chan_end_on_a = expected_counterparty_chan_end(
+        msg.counterparty_chan_state,
+        current_fields_on_a,
+        &msg.port_id_on_b,
+        &msg.chan_id_on_b,
+        chan_end_on_b.upgrade_sequence(),
+    );
+    verify_counterparty_chan_end(
+        ctx_b,
+        client_state_of_a_on_b.as_mut(),
+        chan_end_on_b,
+        &conn_end_on_b,
+        &msg.proof_chan_end_on_a,
+        msg.proof_height_on_a,
+        &expected_chan_end_on_a,
+    )?;
+    verify_counterparty_upgrade(
+        ctx_b,
+        client_state_of_a_on_b.as_mut(),
+        chan_end_on_b,
+        &conn_end_on_b,
+        &msg.proof_upgrade_on_a,
+        msg.proof_height_on_a,
+        &msg.counterparty_upgrade,
+    )?;
+
+    Ok(client_state_of_a_on_b)
+}
@@ -0,0 +1,41 @@
+//! Protocol logic specific to ICS4 messages of type `MsgChannelUpgradeInit`.
+use crate::core::ics04_channel::channel::State;
+use crate::core::ics04_channel::error::ChannelError;
+use crate::core::ics04_channel::handler::chan_upgrade::{
+    check_ordering_supported, current_upgrade_fields, open_connection_end,
+};
+use crate::core::ics04_channel::msgs::chan_upgrade_init::MsgChannelUpgradeInit;
+use crate::core::{ContextError, ValidationContext};
+use crate::prelude::*;
+
+/// Per our convention, this message is processed on chain A.
+pub fn validate<Ctx>(ctx_a: &Ctx, msg: &MsgChannelUpgradeInit) -> Result<(), ContextError>
+where
+    Ctx: ValidationContext,
+{
+    let chan_end_on_a = ctx_a.channel_end(&(msg.port_id_on_a.clone(), msg.chan_id_on_a.clone()))?;
+
+    // Only an open channel can be upgraded.
+    if !chan_end_on_a.state_matches(&State::Open) {
+        return Err(ChannelError::InvalidChannelState {
+            channel_id: msg.chan_id_on_a.clone(),
+            state: chan_end_on_a.state,
+        }
+        .into());
+    }
+
+    msg.fields.validate_basic()?;
+
+    if msg.fields == current_upgrade_fields(&chan_end_on_a) {
+        return Err(ChannelError::InvalidUpgrade {
+            reason: "the proposed upgrade fields are identical to the current ones".to_string(),
+        }
+        .into());
+    }
+
+    // The upgraded channel must run on an open connection which supports its ordering.
+    let conn_end_on_a = open_connection_end(ctx_a, &msg.fields.connection_hops[0])?;
+    check_ordering_supported(&conn_end_on_a, &msg.fields.ordering)?;
+
+    Ok(())
+}
@@ -0,0 +1,116 @@
+//! Protocol logic specific to ICS4 messages of type `MsgChannelUpgradeOpen`.
+use crate::core::ics02_client::client_state::ClientState;
+use crate::core::ics04_channel::channel::{ChannelEnd, State};
+use crate::core::ics04_channel::error::ChannelError;
+use crate::core::ics04_channel::handler::chan_upgrade::{
+    channel_connection_end, counterparty_connection_id, current_upgrade_fields,
+    expected_counterparty_chan_end, open_connection_end, verify_counterparty_chan_end,
+};
+use crate::core::ics04_channel::msgs::chan_upgrade_open::MsgChannelUpgradeOpen;
+use crate::core::ics04_channel::upgrade::UpgradeFields;
+use crate::core::{ContextError, ValidationContext};
+use crate::prelude::*;
+
+/// Can be processed on either chain, once its channel end has flushed all its packets.
+pub fn validate<Ctx>(ctx: &Ctx, msg: &MsgChannelUpgradeOpen) -> Result<(), ContextError>
+where
+    Ctx: ValidationContext,
+{
+    let port_chan_id = (msg.port_id.clone(), msg.chan_id.clone());
+    let chan_end = ctx.channel_end(&port_chan_id)?;
+
+    if !chan_end.state_matches(&State::FlushComplete) {
+        return Err(ChannelError::InvalidChannelState {
+            channel_id: msg.chan_id.clone(),
+            state: chan_end.state,
+        }
+        .into());
+    }
+
+    // Ensures that an upgrade is in progress.
+    ctx.channel_upgrade(&port_chan_id)?;
+
+    verify_proofs(ctx, msg, &chan_end)?;
+
+    Ok(())
+}
+
+/// Verifies the proof of the message, and returns the state of the client once it was verified
+/// against it.
+pub(crate) fn verify_proofs<Ctx>(
+    ctx: &Ctx,
+    msg: &MsgChannelUpgradeOpen,
+    chan_end: &ChannelEnd,
+) -> Result<Box<dyn ClientState>, ContextError>
+where
+    Ctx: ValidationContext,
+{
+    let upgrade = ctx.channel_upgrade(&(msg.port_id.clone(), msg.chan_id.clone()))?;
+    let conn_end = channel_connection_end(ctx, chan_end)?;
+    let mut client_state_of_cp = ctx.client_state(conn_end.client_id())?;
+
+    // The counterparty channel end has either flushed its packets too, or already completed
+    // the upgrade.
+    let expected_chan_end_on_cp = match msg.counterparty_chan_state {
+        State::FlushComplete => {
+            let conn_id_on_cp =
+                counterparty_connection_id(&chan_end.connection_hops()[0], &conn_end)?;
+
+            expected_counterparty_chan_end(
+                State::FlushComplete,
+                UpgradeFields {
+                    connection_hops: vec![conn_id_on_cp],
+                    ..current_upgrade_fields(chan_end)
+                },
+                &msg.port_id,
+                &msg.chan_id,
+                chan_end.upgrade_sequence(),
+            )
+        }
+        State::Open => {
+            if msg.counterparty_upgrade_sequence < chan_end.upgrade_sequence() {
+                return Err(ChannelError::InvalidUpgradeSequence {
+                    current: chan_end.upgrade_sequence(),
+                    actual: msg.counterparty_upgrade_sequence,
+                }
+                .into());
+            }
+
+            let upgraded_conn_id = &upgrade.fields.connection_hops[0];
+            let upgraded_conn_end = open_connection_end(ctx, upgraded_conn_id)?;
+            let upgraded_conn_id_on_cp =
+                counterparty_connection_id(upgraded_conn_id, &upgraded_conn_end)?;
+
+            expected_counterparty_chan_end(
+                State::Open,
+                UpgradeFields {
+                    connection_hops: vec![upgraded_conn_id_on_cp],
+                    ..upgrade.fields
+                },
+                &msg.port_id,
+                &msg.chan_id,
+                msg.counterparty_upgrade_sequence,
+            )
+        }
+        state => {
+            return Err(ChannelError::InvalidUpgrade {
+                reason: format!(
+                "the counterparty channel end is in state {state} instead of flushcomplete or open"
+            ),
+            }
+            .into())
+        }
+    };
+
+    verify_counterparty_chan_end(
+        ctx,
+        client_state_of_cp.as_mut(),
+        chan_end,
+        &conn_end,
+        &msg.proof_chan_end,
+        msg.proof_height,
+        &expected_chan_end_on_cp,
+    )?;
+
+    Ok(client_state_of_cp)
+}
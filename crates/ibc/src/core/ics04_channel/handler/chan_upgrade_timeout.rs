@@ -0,0 +1,106 @@
+//! Protocol logic specific to ICS4 messages of type `MsgChannelUpgradeTimeout`.
+use crate::core::ics02_client::client_state::ClientState;
+use crate::core::ics04_channel::channel::{ChannelEnd, State};
+use crate::core::ics04_channel::error::ChannelError;
+use crate::core::ics04_channel::handler::chan_upgrade::{
+    channel_connection_end, counterparty_connection_id, open_connection_end,
+    verify_counterparty_chan_end,
+};
+use crate::core::ics04_channel::msgs::chan_upgrade_timeout::MsgChannelUpgradeTimeout;
+use crate::core::{ContextError, ValidationContext};
+use crate::prelude::*;
+
+/// Can be processed on either chain, once the counterparty chain failed to complete the
+/// upgrade in time.
+pub fn validate<Ctx>(ctx: &Ctx, msg: &MsgChannelUpgradeTimeout) -> Result<(), ContextError>
+where
+    Ctx: ValidationContext,
+{
+    let port_chan_id = (msg.port_id.clone(), msg.chan_id.clone());
+    let chan_end = ctx.channel_end(&port_chan_id)?;
+
+    if !chan_end.is_flushing() {
+        return Err(ChannelError::InvalidChannelState {
+            channel_id: msg.chan_id.clone(),
+            state: chan_end.state,
+        }
+        .into());
+    }
+
+    let upgrade = ctx.channel_upgrade(&port_chan_id)?;
+
+    let conn_end = channel_connection_end(ctx, &chan_end)?;
+
+    // The upgrade must have timed out on the counterparty chain at the proof height.
+    {
+        let consensus_state_of_cp = ctx.consensus_state(conn_end.client_id(), &msg.proof_height)?;
+
+        if !upgrade.timeout.is_set()
+            || !upgrade
+                .timeout
+                .has_expired(msg.proof_height, &consensus_state_of_cp.timestamp())
+        {
+            return Err(ChannelError::UpgradeTimeoutNotReached.into());
+        }
+    }
+
+    // The counterparty channel end must not be about to complete the upgrade, or have
+    // completed it already.
+    {
+        let chan_end_on_cp = &msg.counterparty_chan_end;
+
+        if chan_end_on_cp.state_matches(&State::FlushComplete) {
+            return Err(ChannelError::InvalidUpgrade {
+                reason: "the counterparty channel end has already flushed its packets".to_string(),
+            }
+            .into());
+        }
+
+        if chan_end_on_cp.state_matches(&State::Open) {
+            let upgraded_conn_id = &upgrade.fields.connection_hops[0];
+            let upgraded_conn_end = open_connection_end(ctx, upgraded_conn_id)?;
+            let upgraded_conn_id_on_cp =
+                counterparty_connection_id(upgraded_conn_id, &upgraded_conn_end)?;
+
+            if chan_end_on_cp.ordering == upgrade.fields.ordering
+                && chan_end_on_cp.version == upgrade.fields.version
+                && chan_end_on_cp.connection_hops == vec![upgraded_conn_id_on_cp]
+            {
+                return Err(ChannelError::InvalidUpgrade {
+                    reason: "the counterparty channel end has already been upgraded".to_string(),
+                }
+                .into());
+            }
+        }
+    }
+
+    verify_proofs(ctx, msg, &chan_end)?;
+
+    Ok(())
+}
+
+/// Verifies the proof of the message, and returns the state of the client once it was verified
+/// against it.
+pub(crate) fn verify_proofs<Ctx>(
+    ctx: &Ctx,
+    msg: &MsgChannelUpgradeTimeout,
+    chan_end: &ChannelEnd,
+) -> Result<Box<dyn ClientState>, ContextError>
+where
+    Ctx: ValidationContext,
+{
+    let conn_end = channel_connection_end(ctx, chan_end)?;
+    let mut client_state_of_cp = ctx.client_state(conn_end.client_id())?;
+
+    verify_counterparty_chan_end(
+        ctx,
+        client_state_of_cp.as_mut(),
+        chan_end,
+        &conn_end,
+        &msg.proof_chan_end,
+        msg.proof_height,
+        &msg.counterparty_chan_end,
+    )?;
+
+    Ok(client_state_of_cp)
+}
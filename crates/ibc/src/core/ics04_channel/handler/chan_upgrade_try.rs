@@ -0,0 +1,131 @@
+//! Protocol logic specific to ICS4 messages of type `MsgChannelUpgradeTry`.
+use crate::core::ics02_client::client_state::ClientState;
+use crate::core::ics04_channel::channel::{ChannelEnd, State};
+use crate::core::ics04_channel::error::ChannelError;
+use crate::core::ics04_channel::handler::chan_upgrade::{
+    channel_connection_end, check_ordering_supported, counterparty_connection_id,
+    current_upgrade_fields, expected_counterparty_chan_end, open_connection_end,
+    verify_counterparty_chan_end, verify_counterparty_upgrade,
+};
+use crate::core::ics04_channel::msgs::chan_upgrade_try::MsgChannelUpgradeTry;
+use crate::core::ics04_channel::packet::Sequence;
+use crate::core::ics04_channel::upgrade::{Upgrade, UpgradeFields, UpgradeTimeout};
+use crate::core::{ContextError, ValidationContext};
+use crate::prelude::*;
+
+/// Per our convention, this message is processed on chain B.
+pub fn validate<Ctx>(ctx_b: &Ctx, msg: &MsgChannelUpgradeTry) -> Result<(), ContextError>
+where
+    Ctx: ValidationContext,
+{
+    let port_chan_id_on_b = (msg.port_id_on_b.clone(), msg.chan_id_on_b.clone());
+    let chan_end_on_b = ctx_b.channel_end(&port_chan_id_on_b)?;
+
+    // Only an open channel can be upgraded.
+    if !chan_end_on_b.state_matches(&State::Open) {
+        return Err(ChannelError::InvalidChannelState {
+            channel_id: msg.chan_id_on_b.clone(),
+            state: chan_end_on_b.state,
+        }
+        .into());
+    }
+
+    // Upgrades initialized concurrently on both chains are not supported.
+    if ctx_b.channel_upgrade(&port_chan_id_on_b).is_ok() {
+        return Err(ChannelError::InvalidUpgrade {
+            reason: "an upgrade of the channel is already in progress".to_string(),
+        }
+        .into());
+    }
+
+    if msg.counterparty_upgrade_sequence <= chan_end_on_b.upgrade_sequence() {
+        return Err(ChannelError::InvalidUpgradeSequence {
+            current: chan_end_on_b.upgrade_sequence(),
+            actual: msg.counterparty_upgrade_sequence,
+        }
+        .into());
+    }
+
+    // The upgraded channel must run on an open connection, whose counterparty is the one
+    // proposed on chain A, and which supports its ordering.
+    {
+        let proposed_conn_id_on_b = &msg.proposed_connection_hops[0];
+        let proposed_conn_end_on_b = open_connection_end(ctx_b, proposed_conn_id_on_b)?;
+
+        let proposed_conn_id_on_a =
+            counterparty_connection_id(proposed_conn_id_on_b, &proposed_conn_end_on_b)?;
+        if proposed_conn_id_on_a != msg.counterparty_upgrade_fields.connection_hops[0] {
+            return Err(ChannelError::IncompatibleUpgrade {
+                reason: format!(
+                    "connection {proposed_conn_id_on_b} is not the counterparty of the proposed connection {}",
+                    msg.counterparty_upgrade_fields.connection_hops[0]
+                ),
+            }
+            .into());
+        }
+
+        check_ordering_supported(
+            &proposed_conn_end_on_b,
+            &msg.counterparty_upgrade_fields.ordering,
+        )?;
+    }
+
+    verify_proofs(ctx_b, msg, &chan_end_on_b)?;
+
+    Ok(())
+}
+
+/// Verifies the proofs of the message, and returns the state of the client once they were
+/// verified against it.
+pub(crate) fn verify_proofs<Ctx>(
+    ctx_b: &Ctx,
+    msg: &MsgChannelUpgradeTry,
+    chan_end_on_b: &ChannelEnd,
+) -> Result<Box<dyn ClientState>, ContextError>
+where
+    Ctx: ValidationContext,
+{
+    let conn_end_on_b = channel_connection_end(ctx_b, chan_end_on_b)?;
+    let mut client_state_of_a_on_b = ctx_b.client_state(conn_end_on_b.client_id())?;
+    let conn_id_on_a =
+        counterparty_connection_id(&chan_end_on_b.connection_hops()[0], &conn_end_on_b)?;
+
+    let current_fields_on_a = UpgradeFields {
+        connection_hops: vec![conn_id_on_a],
+        ..current_upgrade_fields(chan_end_on_b)
+    };
+    let expected_chan_end_on_a = expected_counterparty_chan_end(
+        State::Open,
+        current_fields_on_a,
+        &msg.port_id_on_b,
+        &msg.chan_id_on_b,
+        msg.counterparty_upgrade_sequence,
+    );
+    verify_counterparty_chan_end(
+        ctx_b,
+        client_state_of_a_on_b.as_mut(),
+        chan_end_on_b,
+        &conn_end_on_b,
+        &msg.proof_chan_end_on_a,
+        msg.proof_height_on_a,
+        &expected_chan_end_on_a,
+    )?;
+
+    // The upgrade stored on chain A only gets its timeout and next sequence on ack.
+    let expected_upgrade_on_a = Upgrade::new(
+        msg.counterparty_upgrade_fields.clone(),
+        UpgradeTimeout::default(),
+        Sequence::default(),
+    );
+    verify_counterparty_upgrade(
+        ctx_b,
+        client_state_of_a_on_b.as_mut(),
+        chan_end_on_b,
+        &conn_end_on_b,
+        &msg.proof_upgrade_on_a,
+        msg.proof_height_on_a,
+        &expected_upgrade_on_a,
+    )?;
+
+    Ok(client_state_of_a_on_b)
+}
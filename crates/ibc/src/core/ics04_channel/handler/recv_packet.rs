@@ -1,17 +1,181 @@
+use crate::core::ics02_client::client_state::ClientState;
+use crate::core::ics02_client::context::ClientReader;
+use crate::core::ics02_client::error::ClientError;
+use crate::core::ics02_client::height::Height;
 use crate::core::ics03_connection::connection::State as ConnectionState;
+use crate::core::ics03_connection::delay::verify_conn_delay_passed;
 use crate::core::ics04_channel::channel::{Counterparty, Order, State};
 use crate::core::ics04_channel::context::ChannelReader;
 use crate::core::ics04_channel::error::ChannelError;
 use crate::core::ics04_channel::error::PacketError;
 use crate::core::ics04_channel::events::ReceivePacket;
 use crate::core::ics04_channel::msgs::recv_packet::MsgRecvPacket;
-use crate::core::ics04_channel::packet::{PacketResult, Receipt, Sequence};
+use crate::core::ics04_channel::packet::{Packet, PacketResult, Receipt, Sequence};
 use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::core::ics24_host::path::CommitmentsPath;
 use crate::events::IbcEvent;
 use crate::handler::{HandlerOutput, HandlerResult};
-use crate::timestamp::Expiry;
+use crate::timestamp::{Expiry, Timestamp};
+use alloc::boxed::Box;
 use alloc::string::ToString;
 
+#[cfg(feature = "val_exec_ctx")]
+pub(crate) use val_exec_ctx::*;
+#[cfg(feature = "val_exec_ctx")]
+pub(crate) mod val_exec_ctx {
+    use super::*;
+    use crate::core::ics03_connection::connection::ConnectionEnd;
+    use crate::core::ics03_connection::delay::new_verify_conn_delay_passed;
+    use crate::core::{ContextError, ValidationContext};
+
+    pub fn validate<Ctx>(ctx_b: &Ctx, msg: &MsgRecvPacket) -> Result<(), ContextError>
+    where
+        Ctx: ValidationContext,
+    {
+        let chan_end_on_b =
+            ctx_b.channel_end(&(msg.packet.port_on_b.clone(), msg.packet.chan_on_b.clone()))?;
+
+        // Packets sent before an upgrade started are still received while it is in progress.
+        if !chan_end_on_b.state_matches(&State::Open) && !chan_end_on_b.is_flushing() {
+            return Err(PacketError::InvalidChannelState {
+                channel_id: msg.packet.chan_on_a.clone(),
+                state: chan_end_on_b.state,
+            }
+            .into());
+        }
+
+        let counterparty = Counterparty::new(
+            msg.packet.port_on_a.clone(),
+            Some(msg.packet.chan_on_a.clone()),
+        );
+
+        if !chan_end_on_b.counterparty_matches(&counterparty) {
+            return Err(PacketError::InvalidPacketCounterparty {
+                port_id: msg.packet.port_on_a.clone(),
+                channel_id: msg.packet.chan_on_a.clone(),
+            }
+            .into());
+        }
+
+        let conn_id_on_b = &chan_end_on_b.connection_hops()[0];
+        let conn_end_on_b = ctx_b.connection_end(conn_id_on_b)?;
+
+        if !conn_end_on_b.state_matches(&ConnectionState::Open) {
+            return Err(PacketError::ConnectionNotOpen {
+                connection_id: chan_end_on_b.connection_hops()[0].clone(),
+            }
+            .into());
+        }
+
+        // A timed out packet is still received on channels that allow timeouts,
+        // so that its sequence gets skipped.
+        if !chan_end_on_b.order_matches(&Order::OrderedAllowTimeout) {
+            verify_packet_not_timed_out(
+                &msg.packet,
+                ctx_b.host_height()?,
+                ctx_b.host_timestamp()?,
+            )?;
+        }
+
+        verify_proofs(ctx_b, msg, &conn_end_on_b)?;
+
+        if chan_end_on_b.ordering.is_ordered() {
+            let next_seq_recv = ctx_b.get_next_sequence_recv(&(
+                msg.packet.port_on_b.clone(),
+                msg.packet.chan_on_b.clone(),
+            ))?;
+            if msg.packet.sequence > next_seq_recv {
+                return Err(PacketError::InvalidPacketSequence {
+                    given_sequence: msg.packet.sequence,
+                    next_sequence: next_seq_recv,
+                }
+                .into());
+            }
+        } else {
+            // A packet that was already received is a no-op, and is not an error.
+            let packet_rec = ctx_b.get_packet_receipt(&(
+                msg.packet.port_on_b.clone(),
+                msg.packet.chan_on_b.clone(),
+                msg.packet.sequence,
+            ));
+
+            match packet_rec {
+                Ok(_receipt) => {}
+                Err(ContextError::PacketError(PacketError::PacketReceiptNotFound { sequence }))
+                    if sequence == msg.packet.sequence => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Verifies the proof of the message, and returns the state of the client once it was verified
+    /// against it.
+    pub fn verify_proofs<Ctx>(
+        ctx_b: &Ctx,
+        msg: &MsgRecvPacket,
+        conn_end_on_b: &ConnectionEnd,
+    ) -> Result<Box<dyn ClientState>, ContextError>
+    where
+        Ctx: ValidationContext,
+    {
+        let client_id_on_b = conn_end_on_b.client_id();
+        let mut client_state_of_a_on_b = ctx_b.client_state(client_id_on_b)?;
+
+        // The client must be active.
+        let status = client_state_of_a_on_b.new_status(ctx_b, client_id_on_b);
+        if !status.is_active() {
+            return Err(ClientError::ClientNotActive {
+                client_id: client_id_on_b.clone(),
+                status,
+            }
+            .into());
+        }
+
+        let consensus_state_of_a_on_b =
+            ctx_b.consensus_state(client_id_on_b, &msg.proof_height_on_a)?;
+
+        let expected_commitment_on_a = ctx_b.packet_commitment(
+            &msg.packet.data,
+            &msg.packet.timeout_height_on_b,
+            &msg.packet.timeout_timestamp_on_b,
+        );
+        // Verify the proof for the packet against the chain store.
+        client_state_of_a_on_b
+            .validate_proof_height(msg.proof_height_on_a)
+            .map_err(|e| ChannelError::PacketVerificationFailed {
+                sequence: msg.packet.sequence,
+                client_error: e,
+            })
+            .map_err(PacketError::Channel)?;
+        new_verify_conn_delay_passed(ctx_b, msg.proof_height_on_a, conn_end_on_b)
+            .map_err(PacketError::Connection)?;
+
+        client_state_of_a_on_b
+            .new_verify_membership(
+                ctx_b,
+                conn_end_on_b.counterparty().prefix(),
+                &msg.proof_commitment_on_a,
+                consensus_state_of_a_on_b.root(),
+                CommitmentsPath {
+                    port_id: msg.packet.port_on_a.clone(),
+                    channel_id: msg.packet.chan_on_a.clone(),
+                    sequence: msg.packet.sequence,
+                }
+                .into(),
+                expected_commitment_on_a.into_vec(),
+            )
+            .map_err(|e| ChannelError::PacketVerificationFailed {
+                sequence: msg.packet.sequence,
+                client_error: e,
+            })
+            .map_err(PacketError::Channel)?;
+
+        Ok(client_state_of_a_on_b)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum RecvPacketResult {
     NoOp,
@@ -20,16 +184,55 @@ pub enum RecvPacketResult {
         channel_id: ChannelId,
         sequence: Sequence,
         receipt: Receipt,
+        /// The state of the client underlying the channel once the proof of the message was verified
+        /// against it.
+        client_state: Box<dyn ClientState>,
     },
     Ordered {
         port_id: PortId,
         channel_id: ChannelId,
         next_seq_recv: Sequence,
+        /// The state of the client underlying the channel once the proof of the message was verified
+        /// against it.
+        client_state: Box<dyn ClientState>,
+    },
+    /// The packet timed out on an `OrderedAllowTimeout` channel: its sequence
+    /// is skipped and a timeout receipt is stored, without the packet being
+    /// passed on to the module.
+    TimedOut {
+        port_id: PortId,
+        channel_id: ChannelId,
+        sequence: Sequence,
+        next_seq_recv: Sequence,
+        /// The state of the client underlying the channel once the proof of the message was verified
+        /// against it.
+        client_state: Box<dyn ClientState>,
     },
 }
 
+/// Checks that the packet has not timed out on the receiving chain, whose latest
+/// height and timestamp are given.
+pub(crate) fn verify_packet_not_timed_out(
+    packet: &Packet,
+    latest_height: Height,
+    latest_timestamp: Timestamp,
+) -> Result<(), PacketError> {
+    if packet.timeout_height_on_b.has_expired(latest_height) {
+        return Err(PacketError::LowPacketHeight {
+            chain_height: latest_height,
+            timeout_height: packet.timeout_height_on_b,
+        });
+    }
+
+    if let Expiry::Expired = latest_timestamp.check_expiry(&packet.timeout_timestamp_on_b) {
+        return Err(PacketError::LowPacketTimestamp);
+    }
+
+    Ok(())
+}
+
 /// Per our convention, this message is processed on chain B.
-pub(crate) fn process<Ctx: ChannelReader>(
+pub(crate) fn process<Ctx: ChannelReader + ClientReader>(
     ctx_b: &Ctx,
     msg: &MsgRecvPacket,
 ) -> HandlerResult<PacketResult, PacketError> {
@@ -70,30 +273,31 @@ pub(crate) fn process<Ctx: ChannelReader>(
     }
 
     let latest_height = ChannelReader::host_height(ctx_b).map_err(PacketError::Channel)?;
-    if msg.packet.timeout_height_on_b.has_expired(latest_height) {
-        return Err(PacketError::LowPacketHeight {
-            chain_height: latest_height,
-            timeout_height: msg.packet.timeout_height_on_b,
-        });
-    }
-
     let latest_timestamp = ChannelReader::host_timestamp(ctx_b).map_err(PacketError::Channel)?;
-    if let Expiry::Expired = latest_timestamp.check_expiry(&msg.packet.timeout_timestamp_on_b) {
-        return Err(PacketError::LowPacketTimestamp);
-    }
+    let timed_out = match verify_packet_not_timed_out(&msg.packet, latest_height, latest_timestamp)
+    {
+        Ok(()) => false,
+        // A timed out packet is still received on channels that allow timeouts,
+        // so that its sequence gets skipped.
+        Err(_) if chan_end_on_b.order_matches(&Order::OrderedAllowTimeout) => true,
+        Err(e) => return Err(e),
+    };
 
     // Verify proofs
-    {
+    let client_state_of_a_on_b = {
         let client_id_on_b = conn_end_on_b.client_id();
-        let client_state_of_a_on_b = ctx_b
-            .client_state(client_id_on_b)
-            .map_err(PacketError::Channel)?;
-
-        // The client must not be frozen.
-        if client_state_of_a_on_b.is_frozen() {
-            return Err(PacketError::FrozenClient {
-                client_id: client_id_on_b.clone(),
-            });
+        let mut client_state_of_a_on_b =
+            ChannelReader::client_state(ctx_b, client_id_on_b).map_err(PacketError::Channel)?;
+
+        // The client must be active.
+        let status = client_state_of_a_on_b.status(ctx_b, client_id_on_b);
+        if !status.is_active() {
+            return Err(PacketError::Channel(ChannelError::Client(
+                ClientError::ClientNotActive {
+                    client_id: client_id_on_b.clone(),
+                    status,
+                },
+            )));
         }
 
         let consensus_state_of_a_on_b = ctx_b
@@ -107,25 +311,39 @@ pub(crate) fn process<Ctx: ChannelReader>(
         );
         // Verify the proof for the packet against the chain store.
         client_state_of_a_on_b
-            .verify_packet_data(
+            .validate_proof_height(msg.proof_height_on_a)
+            .map_err(|e| ChannelError::PacketVerificationFailed {
+                sequence: msg.packet.sequence,
+                client_error: e,
+            })
+            .map_err(PacketError::Channel)?;
+        verify_conn_delay_passed(ctx_b, msg.proof_height_on_a, &conn_end_on_b)
+            .map_err(PacketError::Connection)?;
+
+        client_state_of_a_on_b
+            .verify_membership(
                 ctx_b,
-                msg.proof_height_on_a,
-                &conn_end_on_b,
+                conn_end_on_b.counterparty().prefix(),
                 &msg.proof_commitment_on_a,
                 consensus_state_of_a_on_b.root(),
-                &msg.packet.port_on_a,
-                &msg.packet.chan_on_a,
-                msg.packet.sequence,
-                expected_commitment_on_a,
+                CommitmentsPath {
+                    port_id: msg.packet.port_on_a.clone(),
+                    channel_id: msg.packet.chan_on_a.clone(),
+                    sequence: msg.packet.sequence,
+                }
+                .into(),
+                expected_commitment_on_a.into_vec(),
             )
             .map_err(|e| ChannelError::PacketVerificationFailed {
                 sequence: msg.packet.sequence,
                 client_error: e,
             })
             .map_err(PacketError::Channel)?;
-    }
 
-    let result = if chan_end_on_b.order_matches(&Order::Ordered) {
+        client_state_of_a_on_b
+    };
+
+    let result = if chan_end_on_b.ordering.is_ordered() {
         let next_seq_recv =
             ctx_b.get_next_sequence_recv(&msg.packet.port_on_b, &msg.packet.chan_on_b)?;
         if msg.packet.sequence > next_seq_recv {
@@ -137,11 +355,20 @@ pub(crate) fn process<Ctx: ChannelReader>(
 
         if msg.packet.sequence < next_seq_recv {
             PacketResult::Recv(RecvPacketResult::NoOp)
+        } else if timed_out {
+            PacketResult::Recv(RecvPacketResult::TimedOut {
+                port_id: msg.packet.port_on_b.clone(),
+                channel_id: msg.packet.chan_on_b.clone(),
+                sequence: msg.packet.sequence,
+                next_seq_recv: next_seq_recv.increment(),
+                client_state: client_state_of_a_on_b,
+            })
         } else {
             PacketResult::Recv(RecvPacketResult::Ordered {
                 port_id: msg.packet.port_on_b.clone(),
                 channel_id: msg.packet.chan_on_b.clone(),
                 next_seq_recv: next_seq_recv.increment(),
+                client_state: client_state_of_a_on_b,
             })
         }
     } else {
@@ -166,12 +393,18 @@ pub(crate) fn process<Ctx: ChannelReader>(
                     channel_id: msg.packet.chan_on_b.clone(),
                     sequence: msg.packet.sequence,
                     receipt: Receipt::Ok,
+                    client_state: client_state_of_a_on_b,
                 })
             }
             Err(_) => return Err(PacketError::ImplementationSpecific),
         }
     };
 
+    if let PacketResult::Recv(RecvPacketResult::TimedOut { .. }) = result {
+        output.log("success: timed out packet skipped");
+        return Ok(output.with_result(result));
+    }
+
     output.log("success: packet receive");
 
     output.emit(IbcEvent::ReceivePacket(ReceivePacket::new(
@@ -194,17 +427,36 @@ mod tests {
     use crate::core::ics03_connection::connection::State as ConnectionState;
     use crate::core::ics03_connection::version::get_compatible_versions;
     use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order, State};
-    use crate::core::ics04_channel::handler::recv_packet::process;
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::core::ics04_channel::error::ChannelError;
+    use crate::core::ics04_channel::handler::recv_packet::{process, RecvPacketResult};
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::core::ics04_channel::handler::ModuleExtras;
     use crate::core::ics04_channel::msgs::recv_packet::test_util::get_dummy_raw_msg_recv_packet;
     use crate::core::ics04_channel::msgs::recv_packet::MsgRecvPacket;
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::core::ics04_channel::msgs::PacketMsg;
     use crate::core::ics04_channel::Version;
     use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::core::ics26_routing::context::{Acknowledgement, Module, ModuleId};
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::core::ics26_routing::msgs::MsgEnvelope;
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::core::{ExecutionContext, ValidationContext};
     use crate::mock::context::MockContext;
     use crate::mock::ics18_relayer::context::RelayerContext;
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::signer::Signer;
     use crate::test_utils::get_dummy_account_id;
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::test_utils::DummyTransferModule;
     use crate::timestamp::Timestamp;
     use crate::timestamp::ZERO_DURATION;
-    use crate::{core::ics04_channel::packet::Packet, events::IbcEvent};
+    use crate::{
+        core::ics04_channel::packet::{Packet, PacketResult},
+        events::IbcEvent,
+    };
 
     #[test]
     fn recv_packet_processing() {
@@ -315,6 +567,34 @@ mod tests {
         .collect();
 
         for test in tests {
+            #[cfg(feature = "val_exec_ctx")]
+            {
+                let mut ctx = test.ctx.clone();
+                let module_id: ModuleId = "dummymodule".parse().unwrap();
+                ctx.scope_port_to_module(test.msg.packet.port_on_b.clone(), module_id.clone());
+                ctx.add_route(module_id, DummyTransferModule::new(ctx.ibc_store_share()))
+                    .unwrap();
+
+                let msg_envelope = MsgEnvelope::Packet(PacketMsg::Recv(test.msg.clone()));
+                let res = ValidationContext::validate(&ctx, msg_envelope.clone());
+                assert_eq!(
+                    res.is_ok(),
+                    test.want_pass,
+                    "recv_packet: validation mismatch for test: {}, error: {:?}",
+                    test.name,
+                    res
+                );
+
+                if test.want_pass {
+                    ExecutionContext::execute(&mut ctx, msg_envelope).unwrap();
+
+                    assert!(matches!(
+                        ctx.ibc_store.lock().events.first(),
+                        Some(IbcEvent::ReceivePacket(_))
+                    ));
+                }
+            }
+
             let res = process(&test.ctx, &test.msg);
             // Additionally check the events and the output objects in the result.
             match res {
@@ -346,4 +626,253 @@ mod tests {
             }
         }
     }
+    #[test]
+    fn recv_timed_out_packet_on_ordered_allow_timeout_channel() {
+        let context = MockContext::default();
+        let host_height = context.query_latest_height().unwrap().increment();
+        let client_height = host_height.increment();
+
+        let msg = MsgRecvPacket::try_from(get_dummy_raw_msg_recv_packet(
+            client_height.revision_height(),
+        ))
+        .unwrap();
+        let packet = Packet {
+            timeout_timestamp_on_b: Timestamp::from_nanoseconds(1).unwrap(),
+            ..msg.packet.clone()
+        };
+        let msg = MsgRecvPacket::new(
+            packet.clone(),
+            msg.proof_commitment_on_a,
+            msg.proof_height_on_a,
+            get_dummy_account_id(),
+        );
+
+        let chan_end_on_b = ChannelEnd::new(
+            State::Open,
+            Order::OrderedAllowTimeout,
+            Counterparty::new(packet.port_on_a.clone(), Some(packet.chan_on_a.clone())),
+            vec![ConnectionId::default()],
+            Version::new("ics20-1".to_string()),
+        );
+        let conn_end_on_b = ConnectionEnd::new(
+            ConnectionState::Open,
+            ClientId::default(),
+            ConnectionCounterparty::new(
+                ClientId::default(),
+                Some(ConnectionId::default()),
+                Default::default(),
+            ),
+            get_compatible_versions(),
+            ZERO_DURATION,
+        );
+
+        let context = context
+            .with_client(&ClientId::default(), client_height)
+            .with_connection(ConnectionId::default(), conn_end_on_b)
+            .with_channel(
+                packet.port_on_b.clone(),
+                packet.chan_on_b.clone(),
+                chan_end_on_b.clone(),
+            )
+            .with_height(host_height)
+            .with_recv_sequence(
+                packet.port_on_b.clone(),
+                packet.chan_on_b.clone(),
+                packet.sequence,
+            );
+
+        // The packet is not received, but its sequence is skipped.
+        let output = process(&context, &msg).unwrap();
+        assert!(output.events.is_empty());
+        assert!(matches!(
+            output.result,
+            PacketResult::Recv(RecvPacketResult::TimedOut { next_seq_recv, .. })
+                if next_seq_recv == packet.sequence.increment()
+        ));
+
+        // The timed out packet is rejected on a regular ordered channel.
+        let ordered_context = context.clone().with_channel(
+            packet.port_on_b.clone(),
+            packet.chan_on_b.clone(),
+            ChannelEnd {
+                ordering: Order::Ordered,
+                ..chan_end_on_b
+            },
+        );
+        assert!(process(&ordered_context, &msg).is_err());
+
+        #[cfg(feature = "val_exec_ctx")]
+        {
+            let mut ctx = context;
+            let module_id: ModuleId = "dummymodule".parse().unwrap();
+            ctx.scope_port_to_module(packet.port_on_b.clone(), module_id.clone());
+            ctx.add_route(module_id, DummyTransferModule::new(ctx.ibc_store_share()))
+                .unwrap();
+
+            let msg_envelope = MsgEnvelope::Packet(PacketMsg::Recv(msg));
+            ValidationContext::validate(&ctx, msg_envelope.clone()).unwrap();
+            ExecutionContext::execute(&mut ctx, msg_envelope).unwrap();
+
+            assert!(ctx.ibc_store.lock().events.is_empty());
+            assert_eq!(
+                ValidationContext::get_next_sequence_recv(
+                    &ctx,
+                    &(packet.port_on_b.clone(), packet.chan_on_b.clone())
+                )
+                .unwrap(),
+                packet.sequence.increment()
+            );
+        }
+    }
+
+    /// An acknowledgement without any data.
+    #[cfg(feature = "val_exec_ctx")]
+    struct EmptyAck;
+
+    #[cfg(feature = "val_exec_ctx")]
+    impl AsRef<[u8]> for EmptyAck {
+        fn as_ref(&self) -> &[u8] {
+            &[]
+        }
+    }
+
+    #[cfg(feature = "val_exec_ctx")]
+    impl Acknowledgement for EmptyAck {}
+
+    /// A module which acknowledges every packet it receives with an `EmptyAck`.
+    #[cfg(feature = "val_exec_ctx")]
+    #[derive(Debug)]
+    struct EmptyAckModule;
+
+    #[cfg(feature = "val_exec_ctx")]
+    impl Module for EmptyAckModule {
+        fn on_chan_open_init(
+            &mut self,
+            _order: Order,
+            _connection_hops: &[ConnectionId],
+            _port_id: &PortId,
+            _channel_id: &ChannelId,
+            _counterparty: &Counterparty,
+            version: &Version,
+        ) -> Result<(ModuleExtras, Version), ChannelError> {
+            Ok((ModuleExtras::empty(), version.clone()))
+        }
+
+        fn on_chan_open_init_validate(
+            &self,
+            _order: Order,
+            _connection_hops: &[ConnectionId],
+            _port_id: &PortId,
+            _channel_id: &ChannelId,
+            _counterparty: &Counterparty,
+            version: &Version,
+        ) -> Result<Version, ChannelError> {
+            Ok(version.clone())
+        }
+
+        fn on_chan_open_init_execute(
+            &mut self,
+            _order: Order,
+            _connection_hops: &[ConnectionId],
+            _port_id: &PortId,
+            _channel_id: &ChannelId,
+            _counterparty: &Counterparty,
+            version: &Version,
+        ) -> Result<(ModuleExtras, Version), ChannelError> {
+            Ok((ModuleExtras::empty(), version.clone()))
+        }
+
+        fn on_chan_open_try_validate(
+            &self,
+            _order: Order,
+            _connection_hops: &[ConnectionId],
+            _port_id: &PortId,
+            _channel_id: &ChannelId,
+            _counterparty: &Counterparty,
+            counterparty_version: &Version,
+        ) -> Result<Version, ChannelError> {
+            Ok(counterparty_version.clone())
+        }
+
+        fn on_chan_open_try_execute(
+            &mut self,
+            _order: Order,
+            _connection_hops: &[ConnectionId],
+            _port_id: &PortId,
+            _channel_id: &ChannelId,
+            _counterparty: &Counterparty,
+            counterparty_version: &Version,
+        ) -> Result<(ModuleExtras, Version), ChannelError> {
+            Ok((ModuleExtras::empty(), counterparty_version.clone()))
+        }
+
+        fn on_chan_open_try(
+            &mut self,
+            _order: Order,
+            _connection_hops: &[ConnectionId],
+            _port_id: &PortId,
+            _channel_id: &ChannelId,
+            _counterparty: &Counterparty,
+            counterparty_version: &Version,
+        ) -> Result<(ModuleExtras, Version), ChannelError> {
+            Ok((ModuleExtras::empty(), counterparty_version.clone()))
+        }
+
+        fn on_recv_packet_execute(
+            &mut self,
+            _packet: &Packet,
+            _relayer: &Signer,
+        ) -> (ModuleExtras, Option<Box<dyn Acknowledgement>>) {
+            (ModuleExtras::empty(), Some(Box::new(EmptyAck)))
+        }
+    }
+
+    #[cfg(feature = "val_exec_ctx")]
+    #[test]
+    fn recv_packet_with_empty_ack_leaves_store_untouched() {
+        let context = MockContext::default();
+        let host_height = context.query_latest_height().unwrap().increment();
+        let client_height = host_height.increment();
+
+        let msg = MsgRecvPacket::try_from(get_dummy_raw_msg_recv_packet(
+            client_height.revision_height(),
+        ))
+        .unwrap();
+        let packet = msg.packet.clone();
+
+        let chan_end_on_b = ChannelEnd::new(
+            State::Open,
+            Order::Unordered,
+            Counterparty::new(packet.port_on_a.clone(), Some(packet.chan_on_a.clone())),
+            vec![ConnectionId::default()],
+            Version::new("ics20-1".to_string()),
+        );
+        let conn_end_on_b = ConnectionEnd::new(
+            ConnectionState::Open,
+            ClientId::default(),
+            ConnectionCounterparty::new(
+                ClientId::default(),
+                Some(ConnectionId::default()),
+                Default::default(),
+            ),
+            get_compatible_versions(),
+            ZERO_DURATION,
+        );
+
+        let mut ctx = context
+            .with_client(&ClientId::default(), client_height)
+            .with_connection(ConnectionId::default(), conn_end_on_b)
+            .with_channel(packet.port_on_b.clone(), packet.chan_on_b, chan_end_on_b)
+            .with_height(host_height);
+        let module_id: ModuleId = "emptyackmodule".parse().unwrap();
+        ctx.scope_port_to_module(packet.port_on_b, module_id.clone());
+        ctx.add_route(module_id, EmptyAckModule).unwrap();
+
+        let msg_envelope = MsgEnvelope::Packet(PacketMsg::Recv(msg));
+        ValidationContext::validate(&ctx, msg_envelope.clone()).unwrap();
+
+        let store_before = format!("{:?}", ctx.ibc_store.lock());
+        assert!(ExecutionContext::execute(&mut ctx, msg_envelope).is_err());
+        assert_eq!(format!("{:?}", ctx.ibc_store.lock()), store_before);
+    }
 }
@@ -34,6 +34,14 @@ pub fn send_packet(
         });
     }
 
+    // No new packet can be sent while the channel flushes its in-flight packets for an upgrade.
+    if chan_end_on_a.is_flushing() {
+        return Err(PacketError::InvalidChannelState {
+            channel_id: packet.chan_on_a,
+            state: chan_end_on_a.state,
+        });
+    }
+
     let counterparty = Counterparty::new(packet.port_on_b.clone(), Some(packet.chan_on_b.clone()));
 
     if !chan_end_on_a.counterparty_matches(&counterparty) {
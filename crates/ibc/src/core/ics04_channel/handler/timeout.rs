@@ -1,15 +1,238 @@
+use crate::core::ics02_client::client_state::ClientState;
+use crate::core::ics02_client::context::ClientReader;
+use crate::core::ics02_client::error::ClientError;
+use crate::core::ics03_connection::delay::verify_conn_delay_passed;
 use crate::core::ics04_channel::channel::State;
 use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order};
 use crate::core::ics04_channel::error::ChannelError;
 use crate::core::ics04_channel::events::{ChannelClosed, TimeoutPacket};
 use crate::core::ics04_channel::msgs::timeout::MsgTimeout;
-use crate::core::ics04_channel::packet::{PacketResult, Sequence};
+use crate::core::ics04_channel::packet::{PacketResult, Receipt, Sequence};
 use crate::core::ics04_channel::{context::ChannelReader, error::PacketError};
 use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::core::ics24_host::path::{ReceiptsPath, SeqRecvsPath};
 use crate::events::IbcEvent;
 use crate::handler::{HandlerOutput, HandlerResult};
 use crate::prelude::*;
 use crate::timestamp::Expiry;
+use prost::Message;
+
+#[cfg(feature = "val_exec_ctx")]
+pub(crate) use val_exec_ctx::*;
+#[cfg(feature = "val_exec_ctx")]
+pub(crate) mod val_exec_ctx {
+    use super::*;
+    use crate::core::ics03_connection::connection::ConnectionEnd;
+    use crate::core::ics03_connection::delay::new_verify_conn_delay_passed;
+    use crate::core::{ContextError, ValidationContext};
+
+    pub fn validate<Ctx>(ctx_a: &Ctx, msg: &MsgTimeout) -> Result<(), ContextError>
+    where
+        Ctx: ValidationContext,
+    {
+        let chan_end_on_a =
+            ctx_a.channel_end(&(msg.packet.port_on_a.clone(), msg.packet.chan_on_a.clone()))?;
+
+        // In-flight packets can still time out while the channel is flushing for an upgrade.
+        if !chan_end_on_a.state_matches(&State::Open)
+            && !chan_end_on_a.state_matches(&State::Flushing)
+        {
+            return Err(PacketError::ChannelClosed {
+                channel_id: msg.packet.chan_on_a.clone(),
+            }
+            .into());
+        }
+
+        let counterparty = Counterparty::new(
+            msg.packet.port_on_b.clone(),
+            Some(msg.packet.chan_on_b.clone()),
+        );
+
+        if !chan_end_on_a.counterparty_matches(&counterparty) {
+            return Err(PacketError::InvalidPacketCounterparty {
+                port_id: msg.packet.port_on_b.clone(),
+                channel_id: msg.packet.chan_on_b.clone(),
+            }
+            .into());
+        }
+
+        let conn_id_on_a = chan_end_on_a.connection_hops()[0].clone();
+        let conn_end_on_a = ctx_a.connection_end(&conn_id_on_a)?;
+
+        //verify packet commitment
+        let commitment_on_a = ctx_a.get_packet_commitment(&(
+            msg.packet.port_on_a.clone(),
+            msg.packet.chan_on_a.clone(),
+            msg.packet.sequence,
+        ))?;
+
+        let expected_commitment_on_a = ctx_a.packet_commitment(
+            &msg.packet.data,
+            &msg.packet.timeout_height_on_b,
+            &msg.packet.timeout_timestamp_on_b,
+        );
+        if commitment_on_a != expected_commitment_on_a {
+            return Err(PacketError::IncorrectPacketCommitment {
+                sequence: msg.packet.sequence,
+            }
+            .into());
+        }
+
+        // On channels that allow timeouts, packets are timed out in order, along
+        // with their acknowledgements.
+        if chan_end_on_a.order_matches(&Order::OrderedAllowTimeout) {
+            let next_seq_ack = ctx_a.get_next_sequence_ack(&(
+                msg.packet.port_on_a.clone(),
+                msg.packet.chan_on_a.clone(),
+            ))?;
+
+            if msg.packet.sequence != next_seq_ack {
+                return Err(PacketError::InvalidPacketSequence {
+                    given_sequence: msg.packet.sequence,
+                    next_sequence: next_seq_ack,
+                }
+                .into());
+            }
+        }
+
+        verify_proofs(ctx_a, msg, &chan_end_on_a, &conn_end_on_a)?;
+
+        Ok(())
+    }
+
+    /// Verifies the proof of the message, and returns the state of the client once it was verified
+    /// against it.
+    pub fn verify_proofs<Ctx>(
+        ctx_a: &Ctx,
+        msg: &MsgTimeout,
+        chan_end_on_a: &ChannelEnd,
+        conn_end_on_a: &ConnectionEnd,
+    ) -> Result<Box<dyn ClientState>, ContextError>
+    where
+        Ctx: ValidationContext,
+    {
+        let client_id_on_a = conn_end_on_a.client_id();
+        let mut client_state_of_b_on_a = ctx_a.client_state(client_id_on_a)?;
+
+        // The client must be active.
+        let status = client_state_of_b_on_a.new_status(ctx_a, client_id_on_a);
+        if !status.is_active() {
+            return Err(ClientError::ClientNotActive {
+                client_id: client_id_on_a.clone(),
+                status,
+            }
+            .into());
+        }
+
+        // check that timeout height or timeout timestamp has passed on the other end
+        if msg
+            .packet
+            .timeout_height_on_b
+            .has_expired(msg.proof_height_on_b)
+        {
+            return Err(PacketError::PacketTimeoutHeightNotReached {
+                timeout_height: msg.packet.timeout_height_on_b,
+                chain_height: msg.proof_height_on_b,
+            }
+            .into());
+        }
+
+        let consensus_state_of_b_on_a =
+            ctx_a.consensus_state(client_id_on_a, &msg.proof_height_on_b)?;
+        let timestamp_of_b = consensus_state_of_b_on_a.timestamp();
+
+        if let Expiry::Expired = msg
+            .packet
+            .timeout_timestamp_on_b
+            .check_expiry(&timestamp_of_b)
+        {
+            return Err(PacketError::PacketTimeoutTimestampNotReached {
+                timeout_timestamp: msg.packet.timeout_timestamp_on_b,
+                chain_timestamp: timestamp_of_b,
+            }
+            .into());
+        }
+        client_state_of_b_on_a
+            .validate_proof_height(msg.proof_height_on_b)
+            .map_err(|e| ChannelError::PacketVerificationFailed {
+                sequence: msg.next_seq_recv_on_b,
+                client_error: e,
+            })
+            .map_err(PacketError::Channel)?;
+        new_verify_conn_delay_passed(ctx_a, msg.proof_height_on_b, conn_end_on_a)
+            .map_err(PacketError::Connection)?;
+
+        let next_seq_recv_verification_result = match chan_end_on_a.ordering {
+            // On a channel that allows timeouts, the counterparty skips the sequence of a
+            // timed out packet, which moves its next sequence to receive past it. It proves
+            // so with a timeout receipt, which no received packet ever leaves.
+            Order::OrderedAllowTimeout => {
+                if msg.next_seq_recv_on_b != msg.packet.sequence.increment() {
+                    return Err(PacketError::InvalidPacketSequence {
+                        given_sequence: msg.packet.sequence,
+                        next_sequence: msg.next_seq_recv_on_b,
+                    }
+                    .into());
+                }
+                client_state_of_b_on_a.new_verify_membership(
+                    ctx_a,
+                    conn_end_on_a.counterparty().prefix(),
+                    &msg.proof_unreceived_on_b,
+                    consensus_state_of_b_on_a.root(),
+                    ReceiptsPath {
+                        port_id: msg.packet.port_on_b.clone(),
+                        channel_id: msg.packet.chan_on_b.clone(),
+                        sequence: msg.packet.sequence,
+                    }
+                    .into(),
+                    Receipt::Timeout.commitment_bytes(),
+                )
+            }
+            Order::Ordered => {
+                if msg.packet.sequence < msg.next_seq_recv_on_b {
+                    return Err(PacketError::InvalidPacketSequence {
+                        given_sequence: msg.packet.sequence,
+                        next_sequence: msg.next_seq_recv_on_b,
+                    }
+                    .into());
+                }
+                let mut seq_bytes = Vec::new();
+                u64::from(msg.packet.sequence)
+                    .encode(&mut seq_bytes)
+                    .expect("buffer size too small");
+
+                client_state_of_b_on_a.new_verify_membership(
+                    ctx_a,
+                    conn_end_on_a.counterparty().prefix(),
+                    &msg.proof_unreceived_on_b,
+                    consensus_state_of_b_on_a.root(),
+                    SeqRecvsPath(msg.packet.port_on_b.clone(), msg.packet.chan_on_b.clone()).into(),
+                    seq_bytes,
+                )
+            }
+            Order::Unordered | Order::None => client_state_of_b_on_a.new_verify_non_membership(
+                ctx_a,
+                conn_end_on_a.counterparty().prefix(),
+                &msg.proof_unreceived_on_b,
+                consensus_state_of_b_on_a.root(),
+                ReceiptsPath {
+                    port_id: msg.packet.port_on_b.clone(),
+                    channel_id: msg.packet.chan_on_b.clone(),
+                    sequence: msg.packet.sequence,
+                }
+                .into(),
+            ),
+        };
+        next_seq_recv_verification_result
+            .map_err(|e| ChannelError::PacketVerificationFailed {
+                sequence: msg.next_seq_recv_on_b,
+                client_error: e,
+            })
+            .map_err(PacketError::Channel)?;
+
+        Ok(client_state_of_b_on_a)
+    }
+}
 
 #[derive(Clone, Debug)]
 pub struct TimeoutPacketResult {
@@ -17,6 +240,10 @@ pub struct TimeoutPacketResult {
     pub channel_id: ChannelId,
     pub seq: Sequence,
     pub channel: Option<ChannelEnd>,
+    pub seq_number: Option<Sequence>,
+    /// The state of the client underlying the channel once the proofs of the message were verified
+    /// against it.
+    pub client_state: Box<dyn ClientState>,
 }
 
 /// TimeoutPacket is called by a module which originally attempted to send a
@@ -25,7 +252,7 @@ pub struct TimeoutPacketResult {
 /// packet can no longer be executed and to allow the calling module to safely
 /// perform appropriate state transitions.
 /// Per our convention, this message is processed on chain A.
-pub(crate) fn process<Ctx: ChannelReader>(
+pub(crate) fn process<Ctx: ChannelReader + ClientReader>(
     ctx_a: &Ctx,
     msg: &MsgTimeout,
 ) -> HandlerResult<PacketResult, PacketError> {
@@ -76,12 +303,40 @@ pub(crate) fn process<Ctx: ChannelReader>(
         });
     }
 
+    // On channels that allow timeouts, packets are timed out in order, along
+    // with their acknowledgements.
+    let next_seq_ack = if chan_end_on_a.order_matches(&Order::OrderedAllowTimeout) {
+        let next_seq_ack =
+            ctx_a.get_next_sequence_ack(&msg.packet.port_on_a, &msg.packet.chan_on_a)?;
+
+        if msg.packet.sequence != next_seq_ack {
+            return Err(PacketError::InvalidPacketSequence {
+                given_sequence: msg.packet.sequence,
+                next_sequence: next_seq_ack,
+            });
+        }
+
+        Some(next_seq_ack.increment())
+    } else {
+        None
+    };
+
     // Verify proofs
-    {
+    let client_state_of_b_on_a = {
         let client_id_on_a = conn_end_on_a.client_id();
-        let client_state_of_b_on_a = ctx_a
-            .client_state(client_id_on_a)
-            .map_err(PacketError::Channel)?;
+        let mut client_state_of_b_on_a =
+            ChannelReader::client_state(ctx_a, client_id_on_a).map_err(PacketError::Channel)?;
+
+        // The client must be active.
+        let status = client_state_of_b_on_a.status(ctx_a, client_id_on_a);
+        if !status.is_active() {
+            return Err(PacketError::Channel(ChannelError::Client(
+                ClientError::ClientNotActive {
+                    client_id: client_id_on_a.clone(),
+                    status,
+                },
+            )));
+        }
 
         // check that timeout height or timeout timestamp has passed on the other end
         if msg
@@ -110,34 +365,74 @@ pub(crate) fn process<Ctx: ChannelReader>(
                 chain_timestamp: timestamp_of_b,
             });
         }
-        let next_seq_recv_verification_result = if chan_end_on_a.order_matches(&Order::Ordered) {
-            if msg.packet.sequence < msg.next_seq_recv_on_b {
-                return Err(PacketError::InvalidPacketSequence {
-                    given_sequence: msg.packet.sequence,
-                    next_sequence: msg.next_seq_recv_on_b,
-                });
+        client_state_of_b_on_a
+            .validate_proof_height(msg.proof_height_on_b)
+            .map_err(|e| ChannelError::PacketVerificationFailed {
+                sequence: msg.next_seq_recv_on_b,
+                client_error: e,
+            })
+            .map_err(PacketError::Channel)?;
+        verify_conn_delay_passed(ctx_a, msg.proof_height_on_b, &conn_end_on_a)
+            .map_err(PacketError::Connection)?;
+
+        let next_seq_recv_verification_result = match chan_end_on_a.ordering {
+            // On a channel that allows timeouts, the counterparty skips the sequence of a
+            // timed out packet, which moves its next sequence to receive past it. It proves
+            // so with a timeout receipt, which no received packet ever leaves.
+            Order::OrderedAllowTimeout => {
+                if msg.next_seq_recv_on_b != msg.packet.sequence.increment() {
+                    return Err(PacketError::InvalidPacketSequence {
+                        given_sequence: msg.packet.sequence,
+                        next_sequence: msg.next_seq_recv_on_b,
+                    });
+                }
+                client_state_of_b_on_a.verify_membership(
+                    ctx_a,
+                    conn_end_on_a.counterparty().prefix(),
+                    &msg.proof_unreceived_on_b,
+                    consensus_state_of_b_on_a.root(),
+                    ReceiptsPath {
+                        port_id: msg.packet.port_on_b.clone(),
+                        channel_id: msg.packet.chan_on_b.clone(),
+                        sequence: msg.packet.sequence,
+                    }
+                    .into(),
+                    Receipt::Timeout.commitment_bytes(),
+                )
             }
-            client_state_of_b_on_a.verify_next_sequence_recv(
-                ctx_a,
-                msg.proof_height_on_b,
-                &conn_end_on_a,
-                &msg.proof_unreceived_on_b,
-                consensus_state_of_b_on_a.root(),
-                &msg.packet.port_on_b,
-                &msg.packet.chan_on_b,
-                msg.packet.sequence,
-            )
-        } else {
-            client_state_of_b_on_a.verify_packet_receipt_absence(
+            Order::Ordered => {
+                if msg.packet.sequence < msg.next_seq_recv_on_b {
+                    return Err(PacketError::InvalidPacketSequence {
+                        given_sequence: msg.packet.sequence,
+                        next_sequence: msg.next_seq_recv_on_b,
+                    });
+                }
+                let mut seq_bytes = Vec::new();
+                u64::from(msg.packet.sequence)
+                    .encode(&mut seq_bytes)
+                    .expect("buffer size too small");
+
+                client_state_of_b_on_a.verify_membership(
+                    ctx_a,
+                    conn_end_on_a.counterparty().prefix(),
+                    &msg.proof_unreceived_on_b,
+                    consensus_state_of_b_on_a.root(),
+                    SeqRecvsPath(msg.packet.port_on_b.clone(), msg.packet.chan_on_b.clone()).into(),
+                    seq_bytes,
+                )
+            }
+            Order::Unordered | Order::None => client_state_of_b_on_a.verify_non_membership(
                 ctx_a,
-                msg.proof_height_on_b,
-                &conn_end_on_a,
+                conn_end_on_a.counterparty().prefix(),
                 &msg.proof_unreceived_on_b,
                 consensus_state_of_b_on_a.root(),
-                &msg.packet.port_on_b,
-                &msg.packet.chan_on_b,
-                msg.packet.sequence,
-            )
+                ReceiptsPath {
+                    port_id: msg.packet.port_on_b.clone(),
+                    channel_id: msg.packet.chan_on_b.clone(),
+                    sequence: msg.packet.sequence,
+                }
+                .into(),
+            ),
         };
         next_seq_recv_verification_result
             .map_err(|e| ChannelError::PacketVerificationFailed {
@@ -145,7 +440,9 @@ pub(crate) fn process<Ctx: ChannelReader>(
                 client_error: e,
             })
             .map_err(PacketError::Channel)?;
-    }
+
+        client_state_of_b_on_a
+    };
 
     output.log("success: packet timeout ");
 
@@ -174,6 +471,8 @@ pub(crate) fn process<Ctx: ChannelReader>(
         channel_id: msg.packet.chan_on_a.clone(),
         seq: msg.packet.sequence,
         channel: packet_res_chan,
+        seq_number: next_seq_ack,
+        client_state: client_state_of_b_on_a,
     });
 
     Ok(output.with_result(result))
@@ -193,11 +492,21 @@ mod tests {
     use crate::core::ics04_channel::handler::timeout::process;
     use crate::core::ics04_channel::msgs::timeout::test_util::get_dummy_raw_msg_timeout;
     use crate::core::ics04_channel::msgs::timeout::MsgTimeout;
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::core::ics04_channel::msgs::PacketMsg;
     use crate::core::ics04_channel::Version;
     use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::core::ics26_routing::context::ModuleId;
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::core::ics26_routing::msgs::MsgEnvelope;
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::core::{ExecutionContext, ValidationContext};
     use crate::events::IbcEvent;
     use crate::mock::context::MockContext;
     use crate::prelude::*;
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::test_utils::DummyTransferModule;
     use crate::timestamp::ZERO_DURATION;
 
     #[test]
@@ -228,7 +537,12 @@ mod tests {
         let mut msg_ok = msg.clone();
         msg_ok.packet.timeout_timestamp_on_b = Default::default();
 
-        let data = context.packet_commitment(
+        // On a channel that allows timeouts, the counterparty skipped the packet.
+        let mut msg_skipped = msg_ok.clone();
+        msg_skipped.next_seq_recv_on_b = msg_skipped.packet.sequence.increment();
+
+        let data = ChannelReader::packet_commitment(
+            &context,
             &msg_ok.packet.data,
             &msg_ok.packet.timeout_height_on_b,
             &msg_ok.packet.timeout_timestamp_on_b,
@@ -245,6 +559,9 @@ mod tests {
         let mut source_ordered_channel_end = chan_end_on_a.clone();
         source_ordered_channel_end.ordering = Order::Ordered;
 
+        let mut source_allow_timeout_channel_end = chan_end_on_a.clone();
+        source_allow_timeout_channel_end.ordering = Order::OrderedAllowTimeout;
+
         let conn_end_on_a = ConnectionEnd::new(
             ConnectionState::Open,
             ClientId::default(),
@@ -310,9 +627,9 @@ mod tests {
             },
             Test {
                 name: "Good parameters Ordered Channel".to_string(),
-                ctx: context
+                ctx: context.clone()
                     .with_client(&ClientId::default(), client_height)
-                    .with_connection(ConnectionId::default(), conn_end_on_a)
+                    .with_connection(ConnectionId::default(), conn_end_on_a.clone())
                     .with_channel(
                         packet.port_on_a.clone(),
                         packet.chan_on_a.clone(),
@@ -322,21 +639,148 @@ mod tests {
                         msg_ok.packet.port_on_a.clone(),
                         msg_ok.packet.chan_on_a.clone(),
                         msg_ok.packet.sequence,
-                        data,
+                        data.clone(),
                     )
                     .with_ack_sequence(
-                         packet.port_on_b,
-                         packet.chan_on_b,
+                         packet.port_on_b.clone(),
+                         packet.chan_on_b.clone(),
                          1.into(),
                      ),
-                msg: msg_ok,
+                msg: msg_ok.clone(),
+                want_pass: true,
+            },
+            Test {
+                name: "Good parameters OrderedAllowTimeout Channel".to_string(),
+                ctx: context.clone()
+                    .with_client(&ClientId::default(), client_height)
+                    .with_connection(ConnectionId::default(), conn_end_on_a.clone())
+                    .with_channel(
+                        packet.port_on_a.clone(),
+                        packet.chan_on_a.clone(),
+                        source_allow_timeout_channel_end.clone(),
+                    )
+                    .with_packet_commitment(
+                        msg_ok.packet.port_on_a.clone(),
+                        msg_ok.packet.chan_on_a.clone(),
+                        msg_ok.packet.sequence,
+                        data.clone(),
+                    )
+                    .with_ack_sequence(
+                        packet.port_on_a.clone(),
+                        packet.chan_on_a.clone(),
+                        msg_ok.packet.sequence,
+                    ),
+                msg: msg_skipped.clone(),
                 want_pass: true,
             },
+            Test {
+                name: "Processing fails because the counterparty did not skip the packet on an OrderedAllowTimeout Channel".to_string(),
+                ctx: context.clone()
+                    .with_client(&ClientId::default(), client_height)
+                    .with_connection(ConnectionId::default(), conn_end_on_a.clone())
+                    .with_channel(
+                        packet.port_on_a.clone(),
+                        packet.chan_on_a.clone(),
+                        source_allow_timeout_channel_end.clone(),
+                    )
+                    .with_packet_commitment(
+                        msg_ok.packet.port_on_a.clone(),
+                        msg_ok.packet.chan_on_a.clone(),
+                        msg_ok.packet.sequence,
+                        data.clone(),
+                    )
+                    .with_ack_sequence(
+                        packet.port_on_a.clone(),
+                        packet.chan_on_a.clone(),
+                        msg_ok.packet.sequence,
+                    ),
+                msg: msg_ok.clone(),
+                want_pass: false,
+            },
+            Test {
+                name: "Processing fails because the packet is not the next one to be acknowledged on an OrderedAllowTimeout Channel".to_string(),
+                ctx: context
+                    .with_client(&ClientId::default(), client_height)
+                    .with_connection(ConnectionId::default(), conn_end_on_a)
+                    .with_channel(
+                        packet.port_on_a.clone(),
+                        packet.chan_on_a.clone(),
+                        source_allow_timeout_channel_end,
+                    )
+                    .with_packet_commitment(
+                        msg_ok.packet.port_on_a.clone(),
+                        msg_ok.packet.chan_on_a.clone(),
+                        msg_ok.packet.sequence,
+                        data,
+                    )
+                    .with_ack_sequence(
+                        packet.port_on_a.clone(),
+                        packet.chan_on_a.clone(),
+                        msg_ok.packet.sequence.increment(),
+                    ),
+                msg: msg_skipped,
+                want_pass: false,
+            },
         ]
         .into_iter()
         .collect();
 
         for test in tests {
+            #[cfg(feature = "val_exec_ctx")]
+            {
+                let mut ctx = test.ctx.clone();
+                let module_id: ModuleId = "dummymodule".parse().unwrap();
+                ctx.scope_port_to_module(test.msg.packet.port_on_a.clone(), module_id.clone());
+                ctx.add_route(module_id, DummyTransferModule::new(ctx.ibc_store_share()))
+                    .unwrap();
+
+                let msg_envelope = MsgEnvelope::Packet(PacketMsg::Timeout(test.msg.clone()));
+                let res = ValidationContext::validate(&ctx, msg_envelope.clone());
+                assert_eq!(
+                    res.is_ok(),
+                    test.want_pass,
+                    "timeout_packet: validation mismatch for test: {}, error: {:?}",
+                    test.name,
+                    res
+                );
+
+                if test.want_pass {
+                    ExecutionContext::execute(&mut ctx, msg_envelope).unwrap();
+
+                    assert!(ChannelReader::get_packet_commitment(
+                        &ctx,
+                        &test.msg.packet.port_on_a,
+                        &test.msg.packet.chan_on_a,
+                        &test.msg.packet.sequence,
+                    )
+                    .is_err());
+                    assert!(matches!(
+                        ctx.ibc_store.lock().events.first(),
+                        Some(IbcEvent::TimeoutPacket(_))
+                    ));
+
+                    let chan_end_on_a = ChannelReader::channel_end(
+                        &ctx,
+                        &test.msg.packet.port_on_a,
+                        &test.msg.packet.chan_on_a,
+                    )
+                    .unwrap();
+                    if chan_end_on_a.order_matches(&Order::OrderedAllowTimeout) {
+                        // The channel stays open, and the next packet can be acknowledged.
+                        assert!(chan_end_on_a.state_matches(&State::Open));
+                        assert_eq!(
+                            ChannelReader::get_next_sequence_ack(
+                                &ctx,
+                                &test.msg.packet.port_on_a,
+                                &test.msg.packet.chan_on_a,
+                            )
+                            .unwrap(),
+                            test.msg.packet.sequence.increment()
+                        );
+                    }
+                }
+            }
+
             let res = process(&test.ctx, &test.msg);
             // Additionally check the events and the output objects in the result.
             match res {
@@ -350,10 +794,9 @@ mod tests {
                     );
 
                     let events = proto_output.events;
-                    let src_channel_end = test
-                        .ctx
-                        .channel_end(&packet.port_on_a, &packet.chan_on_a)
-                        .unwrap();
+                    let src_channel_end =
+                        ChannelReader::channel_end(&test.ctx, &packet.port_on_a, &packet.chan_on_a)
+                            .unwrap();
 
                     if src_channel_end.order_matches(&Order::Ordered) {
                         assert_eq!(events.len(), 2);
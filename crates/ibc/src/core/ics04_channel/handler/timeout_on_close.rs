@@ -1,18 +1,238 @@
+use crate::core::ics02_client::context::ClientReader;
+use crate::core::ics02_client::error::ClientError;
+use crate::core::ics03_connection::delay::verify_conn_delay_passed;
 use crate::core::ics04_channel::channel::State;
 use crate::core::ics04_channel::channel::{ChannelEnd, Counterparty, Order};
 use crate::core::ics04_channel::error::ChannelError;
 use crate::core::ics04_channel::events::{ChannelClosed, TimeoutPacket};
 use crate::core::ics04_channel::msgs::timeout_on_close::MsgTimeoutOnClose;
-use crate::core::ics04_channel::packet::PacketResult;
+use crate::core::ics04_channel::packet::{PacketResult, Receipt};
 use crate::core::ics04_channel::{
     context::ChannelReader, error::PacketError, handler::timeout::TimeoutPacketResult,
 };
+use crate::core::ics24_host::path::{ChannelEndsPath, ReceiptsPath, SeqRecvsPath};
 use crate::events::IbcEvent;
 use crate::handler::{HandlerOutput, HandlerResult};
 use crate::prelude::*;
+use ibc_proto::protobuf::Protobuf;
+use prost::Message;
+
+#[cfg(feature = "val_exec_ctx")]
+pub(crate) use val_exec_ctx::*;
+#[cfg(feature = "val_exec_ctx")]
+pub(crate) mod val_exec_ctx {
+    use super::*;
+    use crate::core::ics02_client::client_state::ClientState;
+    use crate::core::ics03_connection::connection::ConnectionEnd;
+    use crate::core::ics03_connection::delay::new_verify_conn_delay_passed;
+    use crate::core::{ContextError, ValidationContext};
+
+    pub fn validate<Ctx>(ctx_a: &Ctx, msg: &MsgTimeoutOnClose) -> Result<(), ContextError>
+    where
+        Ctx: ValidationContext,
+    {
+        let packet = &msg.packet;
+
+        let chan_end_on_a =
+            ctx_a.channel_end(&(packet.port_on_a.clone(), packet.chan_on_a.clone()))?;
+
+        let counterparty =
+            Counterparty::new(packet.port_on_b.clone(), Some(packet.chan_on_b.clone()));
+
+        if !chan_end_on_a.counterparty_matches(&counterparty) {
+            return Err(PacketError::InvalidPacketCounterparty {
+                port_id: packet.port_on_b.clone(),
+                channel_id: packet.chan_on_b.clone(),
+            }
+            .into());
+        }
+
+        //verify the packet was sent, check the store
+        let commitment_on_a = ctx_a.get_packet_commitment(&(
+            packet.port_on_a.clone(),
+            packet.chan_on_a.clone(),
+            packet.sequence,
+        ))?;
+
+        let expected_commitment_on_a = ctx_a.packet_commitment(
+            &packet.data,
+            &packet.timeout_height_on_b,
+            &packet.timeout_timestamp_on_b,
+        );
+        if commitment_on_a != expected_commitment_on_a {
+            return Err(PacketError::IncorrectPacketCommitment {
+                sequence: packet.sequence,
+            }
+            .into());
+        }
+
+        // On channels that allow timeouts, packets are timed out in order, along
+        // with their acknowledgements.
+        if chan_end_on_a.order_matches(&Order::OrderedAllowTimeout) {
+            let next_seq_ack = ctx_a
+                .get_next_sequence_ack(&(packet.port_on_a.clone(), packet.chan_on_a.clone()))?;
+
+            if packet.sequence != next_seq_ack {
+                return Err(PacketError::InvalidPacketSequence {
+                    given_sequence: packet.sequence,
+                    next_sequence: next_seq_ack,
+                }
+                .into());
+            }
+        }
+
+        let conn_id_on_a = chan_end_on_a.connection_hops()[0].clone();
+        let conn_end_on_a = ctx_a.connection_end(&conn_id_on_a)?;
+
+        verify_proofs(ctx_a, msg, &chan_end_on_a, &conn_end_on_a)?;
+
+        Ok(())
+    }
+
+    /// Verifies the proofs of the message, and returns the state of the client once they were
+    /// verified against it.
+    pub fn verify_proofs<Ctx>(
+        ctx_a: &Ctx,
+        msg: &MsgTimeoutOnClose,
+        chan_end_on_a: &ChannelEnd,
+        conn_end_on_a: &ConnectionEnd,
+    ) -> Result<Box<dyn ClientState>, ContextError>
+    where
+        Ctx: ValidationContext,
+    {
+        let packet = &msg.packet;
+
+        let client_id_on_a = conn_end_on_a.client_id();
+        let mut client_state_of_b_on_a = ctx_a.client_state(client_id_on_a)?;
+
+        // The client must be active.
+        let status = client_state_of_b_on_a.new_status(ctx_a, client_id_on_a);
+        if !status.is_active() {
+            return Err(ClientError::ClientNotActive {
+                client_id: client_id_on_a.clone(),
+                status,
+            }
+            .into());
+        }
+
+        let consensus_state_of_b_on_a =
+            ctx_a.consensus_state(client_id_on_a, &msg.proof_height_on_b)?;
+        let prefix_on_b = conn_end_on_a.counterparty().prefix();
+        let port_id_on_b = &chan_end_on_a.counterparty().port_id;
+        let chan_id_on_b =
+            chan_end_on_a
+                .counterparty()
+                .channel_id()
+                .ok_or(PacketError::Channel(
+                    ChannelError::InvalidCounterpartyChannelId,
+                ))?;
+        let conn_id_on_b = conn_end_on_a.counterparty().connection_id().ok_or(
+            PacketError::UndefinedConnectionCounterparty {
+                connection_id: chan_end_on_a.connection_hops()[0].clone(),
+            },
+        )?;
+        let expected_conn_hops_on_b = vec![conn_id_on_b.clone()];
+        let expected_counterparty =
+            Counterparty::new(packet.port_on_a.clone(), Some(packet.chan_on_a.clone()));
+        let expected_chan_end_on_b = ChannelEnd::new(
+            State::Closed,
+            *chan_end_on_a.ordering(),
+            expected_counterparty,
+            expected_conn_hops_on_b,
+            chan_end_on_a.version().clone(),
+        );
+
+        // Verify the proof for the channel state against the expected channel end.
+        // A counterparty channel id of None in not possible, and is checked by validate_basic in msg.
+        let value = expected_chan_end_on_b
+            .encode_vec()
+            .map_err(|e| ChannelError::VerifyChannelFailed(ClientError::InvalidChannelEnd(e)))?;
+
+        client_state_of_b_on_a
+            .validate_proof_height(msg.proof_height_on_b)
+            .map_err(ChannelError::VerifyChannelFailed)?;
+        client_state_of_b_on_a
+            .new_verify_membership(
+                ctx_a,
+                prefix_on_b,
+                &msg.proof_close_on_b,
+                consensus_state_of_b_on_a.root(),
+                ChannelEndsPath(port_id_on_b.clone(), chan_id_on_b.clone()).into(),
+                value,
+            )
+            .map_err(ChannelError::VerifyChannelFailed)
+            .map_err(PacketError::Channel)?;
+
+        new_verify_conn_delay_passed(ctx_a, msg.proof_height_on_b, conn_end_on_a)
+            .map_err(PacketError::Connection)?;
+
+        let next_seq_recv_verification_result = match chan_end_on_a.ordering {
+            // On a channel that allows timeouts, the counterparty may have skipped the
+            // timed out packet before its channel end was closed, which it proves with a
+            // timeout receipt.
+            Order::OrderedAllowTimeout if msg.next_seq_recv_on_b == packet.sequence.increment() => {
+                client_state_of_b_on_a.new_verify_membership(
+                    ctx_a,
+                    conn_end_on_a.counterparty().prefix(),
+                    &msg.proof_unreceived_on_b,
+                    consensus_state_of_b_on_a.root(),
+                    ReceiptsPath {
+                        port_id: packet.port_on_b.clone(),
+                        channel_id: packet.chan_on_b.clone(),
+                        sequence: packet.sequence,
+                    }
+                    .into(),
+                    Receipt::Timeout.commitment_bytes(),
+                )
+            }
+            Order::Ordered | Order::OrderedAllowTimeout => {
+                if packet.sequence < msg.next_seq_recv_on_b {
+                    return Err(PacketError::InvalidPacketSequence {
+                        given_sequence: packet.sequence,
+                        next_sequence: msg.next_seq_recv_on_b,
+                    }
+                    .into());
+                }
+                let mut seq_bytes = Vec::new();
+                u64::from(packet.sequence)
+                    .encode(&mut seq_bytes)
+                    .expect("buffer size too small");
+
+                client_state_of_b_on_a.new_verify_membership(
+                    ctx_a,
+                    conn_end_on_a.counterparty().prefix(),
+                    &msg.proof_unreceived_on_b,
+                    consensus_state_of_b_on_a.root(),
+                    SeqRecvsPath(packet.port_on_b.clone(), packet.chan_on_b.clone()).into(),
+                    seq_bytes,
+                )
+            }
+            Order::Unordered | Order::None => client_state_of_b_on_a.new_verify_non_membership(
+                ctx_a,
+                conn_end_on_a.counterparty().prefix(),
+                &msg.proof_unreceived_on_b,
+                consensus_state_of_b_on_a.root(),
+                ReceiptsPath {
+                    port_id: packet.port_on_b.clone(),
+                    channel_id: packet.chan_on_b.clone(),
+                    sequence: packet.sequence,
+                }
+                .into(),
+            ),
+        };
+        next_seq_recv_verification_result
+            .map_err(|e| ChannelError::PacketVerificationFailed {
+                sequence: msg.next_seq_recv_on_b,
+                client_error: e,
+            })
+            .map_err(PacketError::Channel)?;
+
+        Ok(client_state_of_b_on_a)
+    }
+}
 
 /// Per our convention, this message is processed on chain A.
-pub(crate) fn process<Ctx: ChannelReader>(
+pub(crate) fn process<Ctx: ChannelReader + ClientReader>(
     ctx_a: &Ctx,
     msg: &MsgTimeoutOnClose,
 ) -> HandlerResult<PacketResult, PacketError> {
@@ -48,23 +268,43 @@ pub(crate) fn process<Ctx: ChannelReader>(
         });
     }
 
+    // On channels that allow timeouts, packets are timed out in order, along
+    // with their acknowledgements.
+    let next_seq_ack = if chan_end_on_a.order_matches(&Order::OrderedAllowTimeout) {
+        let next_seq_ack = ctx_a.get_next_sequence_ack(&packet.port_on_a, &packet.chan_on_a)?;
+
+        if packet.sequence != next_seq_ack {
+            return Err(PacketError::InvalidPacketSequence {
+                given_sequence: packet.sequence,
+                next_sequence: next_seq_ack,
+            });
+        }
+
+        Some(next_seq_ack.increment())
+    } else {
+        None
+    };
+
     let conn_id_on_a = chan_end_on_a.connection_hops()[0].clone();
     let conn_end_on_a = ctx_a
         .connection_end(&conn_id_on_a)
         .map_err(PacketError::Channel)?;
 
     // Verify proofs
-    {
+    let client_state_of_b_on_a = {
         let client_id_on_a = conn_end_on_a.client_id();
-        let client_state_of_b_on_a = ctx_a
-            .client_state(client_id_on_a)
-            .map_err(PacketError::Channel)?;
-
-        // The client must not be frozen.
-        if client_state_of_b_on_a.is_frozen() {
-            return Err(PacketError::FrozenClient {
-                client_id: client_id_on_a.clone(),
-            });
+        let mut client_state_of_b_on_a =
+            ChannelReader::client_state(ctx_a, client_id_on_a).map_err(PacketError::Channel)?;
+
+        // The client must be active.
+        let status = client_state_of_b_on_a.status(ctx_a, client_id_on_a);
+        if !status.is_active() {
+            return Err(PacketError::Channel(ChannelError::Client(
+                ClientError::ClientNotActive {
+                    client_id: client_id_on_a.clone(),
+                    status,
+                },
+            )));
         }
 
         let consensus_state_of_b_on_a = ctx_a
@@ -97,47 +337,82 @@ pub(crate) fn process<Ctx: ChannelReader>(
 
         // Verify the proof for the channel state against the expected channel end.
         // A counterparty channel id of None in not possible, and is checked by validate_basic in msg.
+        let value = expected_chan_end_on_b
+            .encode_vec()
+            .map_err(|e| ChannelError::VerifyChannelFailed(ClientError::InvalidChannelEnd(e)))
+            .map_err(PacketError::Channel)?;
+
+        client_state_of_b_on_a
+            .validate_proof_height(msg.proof_height_on_b)
+            .map_err(ChannelError::VerifyChannelFailed)
+            .map_err(PacketError::Channel)?;
         client_state_of_b_on_a
-            .verify_channel_state(
-                msg.proof_height_on_b,
+            .verify_membership(
+                ctx_a,
                 prefix_on_b,
-                &msg.proof_unreceived_on_b,
+                &msg.proof_close_on_b,
                 consensus_state_of_b_on_a.root(),
-                port_id_on_b,
-                chan_id_on_b,
-                &expected_chan_end_on_b,
+                ChannelEndsPath(port_id_on_b.clone(), chan_id_on_b.clone()).into(),
+                value,
             )
             .map_err(ChannelError::VerifyChannelFailed)
             .map_err(PacketError::Channel)?;
 
-        let next_seq_recv_verification_result = if chan_end_on_a.order_matches(&Order::Ordered) {
-            if packet.sequence < msg.next_seq_recv_on_b {
-                return Err(PacketError::InvalidPacketSequence {
-                    given_sequence: packet.sequence,
-                    next_sequence: msg.next_seq_recv_on_b,
-                });
+        verify_conn_delay_passed(ctx_a, msg.proof_height_on_b, &conn_end_on_a)
+            .map_err(PacketError::Connection)?;
+
+        let next_seq_recv_verification_result = match chan_end_on_a.ordering {
+            // On a channel that allows timeouts, the counterparty may have skipped the
+            // timed out packet before its channel end was closed, which it proves with a
+            // timeout receipt.
+            Order::OrderedAllowTimeout if msg.next_seq_recv_on_b == packet.sequence.increment() => {
+                client_state_of_b_on_a.verify_membership(
+                    ctx_a,
+                    conn_end_on_a.counterparty().prefix(),
+                    &msg.proof_unreceived_on_b,
+                    consensus_state_of_b_on_a.root(),
+                    ReceiptsPath {
+                        port_id: packet.port_on_b.clone(),
+                        channel_id: packet.chan_on_b.clone(),
+                        sequence: packet.sequence,
+                    }
+                    .into(),
+                    Receipt::Timeout.commitment_bytes(),
+                )
             }
-            client_state_of_b_on_a.verify_next_sequence_recv(
-                ctx_a,
-                msg.proof_height_on_b,
-                &conn_end_on_a,
-                &msg.proof_unreceived_on_b,
-                consensus_state_of_b_on_a.root(),
-                &packet.port_on_b,
-                &packet.chan_on_b,
-                packet.sequence,
-            )
-        } else {
-            client_state_of_b_on_a.verify_packet_receipt_absence(
+            Order::Ordered | Order::OrderedAllowTimeout => {
+                if packet.sequence < msg.next_seq_recv_on_b {
+                    return Err(PacketError::InvalidPacketSequence {
+                        given_sequence: packet.sequence,
+                        next_sequence: msg.next_seq_recv_on_b,
+                    });
+                }
+                let mut seq_bytes = Vec::new();
+                u64::from(packet.sequence)
+                    .encode(&mut seq_bytes)
+                    .expect("buffer size too small");
+
+                client_state_of_b_on_a.verify_membership(
+                    ctx_a,
+                    conn_end_on_a.counterparty().prefix(),
+                    &msg.proof_unreceived_on_b,
+                    consensus_state_of_b_on_a.root(),
+                    SeqRecvsPath(packet.port_on_b.clone(), packet.chan_on_b.clone()).into(),
+                    seq_bytes,
+                )
+            }
+            Order::Unordered | Order::None => client_state_of_b_on_a.verify_non_membership(
                 ctx_a,
-                msg.proof_height_on_b,
-                &conn_end_on_a,
+                conn_end_on_a.counterparty().prefix(),
                 &msg.proof_unreceived_on_b,
                 consensus_state_of_b_on_a.root(),
-                &packet.port_on_b,
-                &packet.chan_on_b,
-                packet.sequence,
-            )
+                ReceiptsPath {
+                    port_id: packet.port_on_b.clone(),
+                    channel_id: packet.chan_on_b.clone(),
+                    sequence: packet.sequence,
+                }
+                .into(),
+            ),
         };
         next_seq_recv_verification_result
             .map_err(|e| ChannelError::PacketVerificationFailed {
@@ -145,6 +420,8 @@ pub(crate) fn process<Ctx: ChannelReader>(
                 client_error: e,
             })
             .map_err(PacketError::Channel)?;
+
+        client_state_of_b_on_a
     };
 
     output.log("success: packet timeout");
@@ -173,6 +450,8 @@ pub(crate) fn process<Ctx: ChannelReader>(
         channel_id: packet.chan_on_a.clone(),
         seq: packet.sequence,
         channel: packet_res_chan,
+        seq_number: next_seq_ack,
+        client_state: client_state_of_b_on_a,
     });
 
     Ok(output.with_result(result))
@@ -193,10 +472,20 @@ mod tests {
     use crate::core::ics04_channel::handler::timeout_on_close::process;
     use crate::core::ics04_channel::msgs::timeout_on_close::test_util::get_dummy_raw_msg_timeout_on_close;
     use crate::core::ics04_channel::msgs::timeout_on_close::MsgTimeoutOnClose;
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::core::ics04_channel::msgs::PacketMsg;
     use crate::core::ics04_channel::Version;
     use crate::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::core::ics26_routing::context::ModuleId;
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::core::ics26_routing::msgs::MsgEnvelope;
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::core::{ExecutionContext, ValidationContext};
     use crate::events::IbcEvent;
     use crate::mock::context::MockContext;
+    #[cfg(feature = "val_exec_ctx")]
+    use crate::test_utils::DummyTransferModule;
     use crate::timestamp::ZERO_DURATION;
 
     #[test]
@@ -222,7 +511,8 @@ mod tests {
         .unwrap();
         let packet = msg.packet.clone();
 
-        let data = context.packet_commitment(
+        let data = ChannelReader::packet_commitment(
+            &context,
             &msg.packet.data,
             &msg.packet.timeout_height_on_b,
             &msg.packet.timeout_timestamp_on_b,
@@ -248,6 +538,30 @@ mod tests {
             ZERO_DURATION,
         );
 
+        let allow_timeout_context = context
+            .clone()
+            .with_client(&ClientId::default(), client_height)
+            .with_connection(ConnectionId::default(), conn_end_on_a.clone())
+            .with_channel(
+                packet.port_on_a.clone(),
+                packet.chan_on_a.clone(),
+                ChannelEnd {
+                    ordering: Order::OrderedAllowTimeout,
+                    ..chan_end_on_a.clone()
+                },
+            )
+            .with_packet_commitment(
+                msg.packet.port_on_a.clone(),
+                msg.packet.chan_on_a.clone(),
+                msg.packet.sequence,
+                data.clone(),
+            )
+            .with_ack_sequence(
+                packet.port_on_a.clone(),
+                packet.chan_on_a.clone(),
+                msg.packet.sequence,
+            );
+
         let tests: Vec<Test> = vec![
             Test {
                 name: "Processing fails because no channel exists in the context".to_string(),
@@ -271,23 +585,78 @@ mod tests {
             Test {
                 name: "Good parameters".to_string(),
                 ctx: context
+                    .clone()
                     .with_client(&ClientId::default(), client_height)
-                    .with_connection(ConnectionId::default(), conn_end_on_a)
-                    .with_channel(packet.port_on_a, packet.chan_on_a, chan_end_on_a)
+                    .with_connection(ConnectionId::default(), conn_end_on_a.clone())
+                    .with_channel(
+                        packet.port_on_a.clone(),
+                        packet.chan_on_a.clone(),
+                        chan_end_on_a.clone(),
+                    )
                     .with_packet_commitment(
                         msg.packet.port_on_a.clone(),
                         msg.packet.chan_on_a.clone(),
                         msg.packet.sequence,
-                        data,
+                        data.clone(),
                     ),
                 msg: msg.clone(),
                 want_pass: true,
             },
+            Test {
+                name: "Good parameters OrderedAllowTimeout channel".to_string(),
+                ctx: allow_timeout_context.clone(),
+                msg: msg.clone(),
+                want_pass: true,
+            },
+            Test {
+                name: "Good parameters OrderedAllowTimeout channel whose counterparty skipped the packet before closing".to_string(),
+                ctx: allow_timeout_context,
+                msg: MsgTimeoutOnClose {
+                    next_seq_recv_on_b: msg.packet.sequence.increment(),
+                    ..msg.clone()
+                },
+                want_pass: true,
+            },
         ]
         .into_iter()
         .collect();
 
         for test in tests {
+            #[cfg(feature = "val_exec_ctx")]
+            {
+                let mut ctx = test.ctx.clone();
+                let module_id: ModuleId = "dummymodule".parse().unwrap();
+                ctx.scope_port_to_module(test.msg.packet.port_on_a.clone(), module_id.clone());
+                ctx.add_route(module_id, DummyTransferModule::new(ctx.ibc_store_share()))
+                    .unwrap();
+
+                let msg_envelope = MsgEnvelope::Packet(PacketMsg::TimeoutOnClose(test.msg.clone()));
+                let res = ValidationContext::validate(&ctx, msg_envelope.clone());
+                assert_eq!(
+                    res.is_ok(),
+                    test.want_pass,
+                    "timeout_on_close: validation mismatch for test: {}, error: {:?}",
+                    test.name,
+                    res
+                );
+
+                if test.want_pass {
+                    ExecutionContext::execute(&mut ctx, msg_envelope).unwrap();
+
+                    assert!(ChannelReader::get_packet_commitment(
+                        &ctx,
+                        &test.msg.packet.port_on_a,
+                        &test.msg.packet.chan_on_a,
+                        &test.msg.packet.sequence,
+                    )
+                    .is_err());
+                    assert!(matches!(
+                        ctx.ibc_store.lock().events.first(),
+                        Some(IbcEvent::TimeoutPacket(_))
+                    ));
+                }
+            }
+
             let res = process(&test.ctx, &test.msg);
             // Additionally check the events and the output objects in the result.
             match res {
@@ -301,10 +670,12 @@ mod tests {
                     );
 
                     let events = proto_output.events;
-                    let src_channel_end = test
-                        .ctx
-                        .channel_end(&msg.packet.port_on_a, &msg.packet.chan_on_a)
-                        .unwrap();
+                    let src_channel_end = ChannelReader::channel_end(
+                        &test.ctx,
+                        &msg.packet.port_on_a,
+                        &msg.packet.chan_on_a,
+                    )
+                    .unwrap();
 
                     if src_channel_end.order_matches(&Order::Ordered) {
                         assert_eq!(events.len(), 2);
@@ -1,5 +1,5 @@
-//! Message definitions for all ICS4 domain types: channel open & close handshake datagrams, as well
-//! as packets.
+//! Message definitions for all ICS4 domain types: channel open, close & upgrade handshake
+//! datagrams, as well as packets.
 
 use crate::core::ics04_channel::error::ChannelError;
 use crate::core::ics04_channel::msgs::acknowledgement::MsgAcknowledgement;
@@ -9,9 +9,17 @@ use crate::core::ics04_channel::msgs::chan_open_ack::MsgChannelOpenAck;
 use crate::core::ics04_channel::msgs::chan_open_confirm::MsgChannelOpenConfirm;
 use crate::core::ics04_channel::msgs::chan_open_init::MsgChannelOpenInit;
 use crate::core::ics04_channel::msgs::chan_open_try::MsgChannelOpenTry;
+use crate::core::ics04_channel::msgs::chan_upgrade_ack::MsgChannelUpgradeAck;
+use crate::core::ics04_channel::msgs::chan_upgrade_cancel::MsgChannelUpgradeCancel;
+use crate::core::ics04_channel::msgs::chan_upgrade_confirm::MsgChannelUpgradeConfirm;
+use crate::core::ics04_channel::msgs::chan_upgrade_init::MsgChannelUpgradeInit;
+use crate::core::ics04_channel::msgs::chan_upgrade_open::MsgChannelUpgradeOpen;
+use crate::core::ics04_channel::msgs::chan_upgrade_timeout::MsgChannelUpgradeTimeout;
+use crate::core::ics04_channel::msgs::chan_upgrade_try::MsgChannelUpgradeTry;
 use crate::core::ics04_channel::msgs::recv_packet::MsgRecvPacket;
 use crate::core::ics04_channel::msgs::timeout::MsgTimeout;
 use crate::core::ics04_channel::msgs::timeout_on_close::MsgTimeoutOnClose;
+use crate::core::ics24_host::identifier::{ChannelId, PortId};
 use crate::core::ics26_routing::context::{ModuleId, RouterContext};
 
 // Opening handshake messages.
@@ -24,6 +32,15 @@ pub mod chan_open_try;
 pub mod chan_close_confirm;
 pub mod chan_close_init;
 
+// Upgrade handshake messages.
+pub mod chan_upgrade_ack;
+pub mod chan_upgrade_cancel;
+pub mod chan_upgrade_confirm;
+pub mod chan_upgrade_init;
+pub mod chan_upgrade_open;
+pub mod chan_upgrade_timeout;
+pub mod chan_upgrade_try;
+
 // Packet specific messages.
 pub mod acknowledgement;
 pub mod recv_packet;
@@ -39,6 +56,13 @@ pub enum ChannelMsg {
     OpenConfirm(MsgChannelOpenConfirm),
     CloseInit(MsgChannelCloseInit),
     CloseConfirm(MsgChannelCloseConfirm),
+    UpgradeInit(MsgChannelUpgradeInit),
+    UpgradeTry(MsgChannelUpgradeTry),
+    UpgradeAck(MsgChannelUpgradeAck),
+    UpgradeConfirm(MsgChannelUpgradeConfirm),
+    UpgradeOpen(MsgChannelUpgradeOpen),
+    UpgradeTimeout(MsgChannelUpgradeTimeout),
+    UpgradeCancel(MsgChannelUpgradeCancel),
 }
 
 impl ChannelMsg {
@@ -50,6 +74,13 @@ impl ChannelMsg {
             ChannelMsg::OpenConfirm(msg) => &msg.port_id_on_b,
             ChannelMsg::CloseInit(msg) => &msg.port_id_on_a,
             ChannelMsg::CloseConfirm(msg) => &msg.port_id_on_b,
+            ChannelMsg::UpgradeInit(msg) => &msg.port_id_on_a,
+            ChannelMsg::UpgradeTry(msg) => &msg.port_id_on_b,
+            ChannelMsg::UpgradeAck(msg) => &msg.port_id_on_a,
+            ChannelMsg::UpgradeConfirm(msg) => &msg.port_id_on_b,
+            ChannelMsg::UpgradeOpen(msg) => &msg.port_id,
+            ChannelMsg::UpgradeTimeout(msg) => &msg.port_id,
+            ChannelMsg::UpgradeCancel(msg) => &msg.port_id,
         };
         let module_id = ctx
             .lookup_module_by_port(port_id)
@@ -79,4 +110,14 @@ impl PacketMsg {
             .map_err(ChannelError::Port)?;
         Ok(module_id)
     }
+
+    /// Returns the port and channel identifiers of the channel end on the host chain.
+    pub(crate) fn port_channel_id(&self) -> (&PortId, &ChannelId) {
+        match self {
+            PacketMsg::Recv(msg) => (&msg.packet.port_on_b, &msg.packet.chan_on_b),
+            PacketMsg::Ack(msg) => (&msg.packet.port_on_a, &msg.packet.chan_on_a),
+            PacketMsg::Timeout(msg) => (&msg.packet.port_on_a, &msg.packet.chan_on_a),
+            PacketMsg::TimeoutOnClose(msg) => (&msg.packet.port_on_a, &msg.packet.chan_on_a),
+        }
+    }
 }
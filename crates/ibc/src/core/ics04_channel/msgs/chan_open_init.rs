@@ -86,7 +86,7 @@ pub mod test_util {
     pub fn get_dummy_raw_msg_chan_open_init() -> RawMsgChannelOpenInit {
         RawMsgChannelOpenInit {
             port_id: PortId::default().to_string(),
-            channel: Some(get_dummy_raw_channel_end("".to_string())),
+            channel: Some(get_dummy_raw_channel_end("".to_string()).into()),
             signer: get_dummy_bech32_account(),
         }
     }
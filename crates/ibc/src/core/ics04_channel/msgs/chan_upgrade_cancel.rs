@@ -0,0 +1,210 @@
+use crate::prelude::*;
+
+use ibc_proto::ibc::core::client::v1::Height as RawHeight;
+use ibc_proto::protobuf::Protobuf;
+
+use crate::core::ics04_channel::error::ChannelError;
+use crate::core::ics04_channel::upgrade::{ErrorReceipt, RawErrorReceipt};
+use crate::core::ics23_commitment::commitment::CommitmentProofBytes;
+use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::signer::Signer;
+use crate::tx_msg::Msg;
+use crate::Height;
+
+pub const TYPE_URL: &str = "/ibc.core.channel.v1.MsgChannelUpgradeCancel";
+
+/// Raw `MsgChannelUpgradeCancel`, as defined by ibc-go.
+///
+/// XXX: to be replaced by its `ibc-proto` counterpart once available.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RawMsgChannelUpgradeCancel {
+    #[prost(string, tag = "1")]
+    pub port_id: String,
+    #[prost(string, tag = "2")]
+    pub channel_id: String,
+    #[prost(message, optional, tag = "3")]
+    pub error_receipt: Option<RawErrorReceipt>,
+    #[prost(bytes = "vec", tag = "4")]
+    pub proof_error_receipt: Vec<u8>,
+    #[prost(message, optional, tag = "5")]
+    pub proof_height: Option<RawHeight>,
+    #[prost(string, tag = "6")]
+    pub signer: String,
+}
+
+///
+/// Message definition for cancelling a channel upgrade which was aborted by the counterparty
+/// chain (`ChanUpgradeCancel` datagram).
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MsgChannelUpgradeCancel {
+    pub port_id: PortId,
+    pub chan_id: ChannelId,
+    /// The error receipt written by the counterparty chain
+    pub error_receipt: ErrorReceipt,
+    pub proof_error_receipt: CommitmentProofBytes,
+    pub proof_height: Height,
+    pub signer: Signer,
+}
+
+impl Msg for MsgChannelUpgradeCancel {
+    type Raw = RawMsgChannelUpgradeCancel;
+
+    fn type_url(&self) -> String {
+        TYPE_URL.to_string()
+    }
+}
+
+impl Protobuf<RawMsgChannelUpgradeCancel> for MsgChannelUpgradeCancel {}
+
+impl TryFrom<RawMsgChannelUpgradeCancel> for MsgChannelUpgradeCancel {
+    type Error = ChannelError;
+
+    fn try_from(raw_msg: RawMsgChannelUpgradeCancel) -> Result<Self, Self::Error> {
+        Ok(MsgChannelUpgradeCancel {
+            port_id: raw_msg.port_id.parse().map_err(ChannelError::Identifier)?,
+            chan_id: raw_msg
+                .channel_id
+                .parse()
+                .map_err(ChannelError::Identifier)?,
+            error_receipt: raw_msg
+                .error_receipt
+                .ok_or(ChannelError::InvalidUpgrade {
+                    reason: "missing error receipt".to_string(),
+                })?
+                .try_into()?,
+            proof_error_receipt: raw_msg
+                .proof_error_receipt
+                .try_into()
+                .map_err(|_| ChannelError::InvalidProof)?,
+            proof_height: raw_msg
+                .proof_height
+                .and_then(|raw_height| raw_height.try_into().ok())
+                .ok_or(ChannelError::MissingHeight)?,
+            signer: raw_msg.signer.parse().map_err(ChannelError::Signer)?,
+        })
+    }
+}
+
+impl From<MsgChannelUpgradeCancel> for RawMsgChannelUpgradeCancel {
+    fn from(domain_msg: MsgChannelUpgradeCancel) -> Self {
+        RawMsgChannelUpgradeCancel {
+            port_id: domain_msg.port_id.to_string(),
+            channel_id: domain_msg.chan_id.to_string(),
+            error_receipt: Some(domain_msg.error_receipt.into()),
+            proof_error_receipt: domain_msg.proof_error_receipt.into(),
+            proof_height: Some(domain_msg.proof_height.into()),
+            signer: domain_msg.signer.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test_util {
+    use super::RawMsgChannelUpgradeCancel;
+    use crate::prelude::*;
+
+    use ibc_proto::ibc::core::client::v1::Height as RawHeight;
+
+    use crate::core::ics04_channel::upgrade::RawErrorReceipt;
+    use crate::core::ics24_host::identifier::{ChannelId, PortId};
+    use crate::test_utils::{get_dummy_bech32_account, get_dummy_proof};
+
+    /// Returns a dummy `RawMsgChannelUpgradeCancel`, for testing only!
+    pub fn get_dummy_raw_msg_chan_upgrade_cancel(proof_height: u64) -> RawMsgChannelUpgradeCancel {
+        RawMsgChannelUpgradeCancel {
+            port_id: PortId::default().to_string(),
+            channel_id: ChannelId::default().to_string(),
+            error_receipt: Some(RawErrorReceipt {
+                sequence: 1,
+                message: "upgrade aborted".to_string(),
+            }),
+            proof_error_receipt: get_dummy_proof(),
+            proof_height: Some(RawHeight {
+                revision_number: 0,
+                revision_height: proof_height,
+            }),
+            signer: get_dummy_bech32_account(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use test_log::test;
+
+    use crate::core::ics04_channel::msgs::chan_upgrade_cancel::test_util::get_dummy_raw_msg_chan_upgrade_cancel;
+    use crate::core::ics04_channel::msgs::chan_upgrade_cancel::{
+        MsgChannelUpgradeCancel, RawMsgChannelUpgradeCancel,
+    };
+
+    #[test]
+    fn parse_channel_upgrade_cancel_msg() {
+        struct Test {
+            name: String,
+            raw: RawMsgChannelUpgradeCancel,
+            want_pass: bool,
+        }
+
+        let default_raw_msg = get_dummy_raw_msg_chan_upgrade_cancel(10);
+
+        let tests: Vec<Test> = vec![
+            Test {
+                name: "Good parameters".to_string(),
+                raw: default_raw_msg.clone(),
+                want_pass: true,
+            },
+            Test {
+                name: "Bad channel, name too short".to_string(),
+                raw: RawMsgChannelUpgradeCancel {
+                    channel_id: "chshort".to_string(),
+                    ..default_raw_msg.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "Missing error receipt".to_string(),
+                raw: RawMsgChannelUpgradeCancel {
+                    error_receipt: None,
+                    ..default_raw_msg.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "Missing proof of the error receipt".to_string(),
+                raw: RawMsgChannelUpgradeCancel {
+                    proof_error_receipt: Vec::new(),
+                    ..default_raw_msg
+                },
+                want_pass: false,
+            },
+        ]
+        .into_iter()
+        .collect();
+
+        for test in tests {
+            let msg = MsgChannelUpgradeCancel::try_from(test.raw.clone());
+
+            assert_eq!(
+                test.want_pass,
+                msg.is_ok(),
+                "MsgChanUpgradeCancel::try_from failed for test {}, \nmsg {:?} with error {:?}",
+                test.name,
+                test.raw,
+                msg.err(),
+            );
+        }
+    }
+
+    #[test]
+    fn to_and_from() {
+        let raw = get_dummy_raw_msg_chan_upgrade_cancel(10);
+        let msg = MsgChannelUpgradeCancel::try_from(raw.clone()).unwrap();
+        let raw_back = RawMsgChannelUpgradeCancel::from(msg.clone());
+        let msg_back = MsgChannelUpgradeCancel::try_from(raw_back.clone()).unwrap();
+        assert_eq!(raw, raw_back);
+        assert_eq!(msg, msg_back);
+    }
+}
@@ -0,0 +1,235 @@
+use crate::prelude::*;
+
+use ibc_proto::ibc::core::client::v1::Height as RawHeight;
+use ibc_proto::protobuf::Protobuf;
+
+use crate::core::ics04_channel::channel::State;
+use crate::core::ics04_channel::error::ChannelError;
+use crate::core::ics04_channel::upgrade::{RawUpgrade, Upgrade};
+use crate::core::ics23_commitment::commitment::CommitmentProofBytes;
+use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::signer::Signer;
+use crate::tx_msg::Msg;
+use crate::Height;
+
+pub const TYPE_URL: &str = "/ibc.core.channel.v1.MsgChannelUpgradeConfirm";
+
+/// Raw `MsgChannelUpgradeConfirm`, as defined by ibc-go.
+///
+/// XXX: to be replaced by its `ibc-proto` counterpart once available.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RawMsgChannelUpgradeConfirm {
+    #[prost(string, tag = "1")]
+    pub port_id: String,
+    #[prost(string, tag = "2")]
+    pub channel_id: String,
+    #[prost(int32, tag = "3")]
+    pub counterparty_channel_state: i32,
+    #[prost(message, optional, tag = "4")]
+    pub counterparty_upgrade: Option<RawUpgrade>,
+    #[prost(bytes = "vec", tag = "5")]
+    pub proof_channel: Vec<u8>,
+    #[prost(bytes = "vec", tag = "6")]
+    pub proof_upgrade: Vec<u8>,
+    #[prost(message, optional, tag = "7")]
+    pub proof_height: Option<RawHeight>,
+    #[prost(string, tag = "8")]
+    pub signer: String,
+}
+
+///
+/// Message definition for the fourth step in the channel upgrade handshake (`ChanUpgradeConfirm`
+/// datagram). Per our convention, this message is sent to chain B.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MsgChannelUpgradeConfirm {
+    pub port_id_on_b: PortId,
+    pub chan_id_on_b: ChannelId,
+    /// The state of the channel end on chain A, either `FLUSHING` or `FLUSHCOMPLETE`
+    pub counterparty_chan_state: State,
+    /// The upgrade stored on chain A
+    pub counterparty_upgrade: Upgrade,
+    pub proof_chan_end_on_a: CommitmentProofBytes,
+    pub proof_upgrade_on_a: CommitmentProofBytes,
+    pub proof_height_on_a: Height,
+    pub signer: Signer,
+}
+
+impl Msg for MsgChannelUpgradeConfirm {
+    type Raw = RawMsgChannelUpgradeConfirm;
+
+    fn type_url(&self) -> String {
+        TYPE_URL.to_string()
+    }
+}
+
+impl Protobuf<RawMsgChannelUpgradeConfirm> for MsgChannelUpgradeConfirm {}
+
+impl TryFrom<RawMsgChannelUpgradeConfirm> for MsgChannelUpgradeConfirm {
+    type Error = ChannelError;
+
+    fn try_from(raw_msg: RawMsgChannelUpgradeConfirm) -> Result<Self, Self::Error> {
+        Ok(MsgChannelUpgradeConfirm {
+            port_id_on_b: raw_msg.port_id.parse().map_err(ChannelError::Identifier)?,
+            chan_id_on_b: raw_msg
+                .channel_id
+                .parse()
+                .map_err(ChannelError::Identifier)?,
+            counterparty_chan_state: State::from_i32(raw_msg.counterparty_channel_state)?,
+            counterparty_upgrade: raw_msg
+                .counterparty_upgrade
+                .ok_or(ChannelError::InvalidUpgrade {
+                    reason: "missing counterparty upgrade".to_string(),
+                })?
+                .try_into()?,
+            proof_chan_end_on_a: raw_msg
+                .proof_channel
+                .try_into()
+                .map_err(|_| ChannelError::InvalidProof)?,
+            proof_upgrade_on_a: raw_msg
+                .proof_upgrade
+                .try_into()
+                .map_err(|_| ChannelError::InvalidProof)?,
+            proof_height_on_a: raw_msg
+                .proof_height
+                .and_then(|raw_height| raw_height.try_into().ok())
+                .ok_or(ChannelError::MissingHeight)?,
+            signer: raw_msg.signer.parse().map_err(ChannelError::Signer)?,
+        })
+    }
+}
+
+impl From<MsgChannelUpgradeConfirm> for RawMsgChannelUpgradeConfirm {
+    fn from(domain_msg: MsgChannelUpgradeConfirm) -> Self {
+        RawMsgChannelUpgradeConfirm {
+            port_id: domain_msg.port_id_on_b.to_string(),
+            channel_id: domain_msg.chan_id_on_b.to_string(),
+            counterparty_channel_state: domain_msg.counterparty_chan_state as i32,
+            counterparty_upgrade: Some(domain_msg.counterparty_upgrade.into()),
+            proof_channel: domain_msg.proof_chan_end_on_a.into(),
+            proof_upgrade: domain_msg.proof_upgrade_on_a.into(),
+            proof_height: Some(domain_msg.proof_height_on_a.into()),
+            signer: domain_msg.signer.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test_util {
+    use super::RawMsgChannelUpgradeConfirm;
+    use crate::prelude::*;
+
+    use ibc_proto::ibc::core::client::v1::Height as RawHeight;
+
+    use crate::core::ics04_channel::channel::State;
+    use crate::core::ics04_channel::upgrade::test_util::get_dummy_raw_upgrade;
+    use crate::core::ics24_host::identifier::{ChannelId, PortId};
+    use crate::test_utils::{get_dummy_bech32_account, get_dummy_proof};
+
+    /// Returns a dummy `RawMsgChannelUpgradeConfirm`, for testing only!
+    pub fn get_dummy_raw_msg_chan_upgrade_confirm(
+        proof_height: u64,
+    ) -> RawMsgChannelUpgradeConfirm {
+        RawMsgChannelUpgradeConfirm {
+            port_id: PortId::default().to_string(),
+            channel_id: ChannelId::default().to_string(),
+            counterparty_channel_state: State::Flushing as i32,
+            counterparty_upgrade: Some(get_dummy_raw_upgrade()),
+            proof_channel: get_dummy_proof(),
+            proof_upgrade: get_dummy_proof(),
+            proof_height: Some(RawHeight {
+                revision_number: 0,
+                revision_height: proof_height,
+            }),
+            signer: get_dummy_bech32_account(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use test_log::test;
+
+    use crate::core::ics04_channel::msgs::chan_upgrade_confirm::test_util::get_dummy_raw_msg_chan_upgrade_confirm;
+    use crate::core::ics04_channel::msgs::chan_upgrade_confirm::{
+        MsgChannelUpgradeConfirm, RawMsgChannelUpgradeConfirm,
+    };
+
+    #[test]
+    fn parse_channel_upgrade_confirm_msg() {
+        struct Test {
+            name: String,
+            raw: RawMsgChannelUpgradeConfirm,
+            want_pass: bool,
+        }
+
+        let default_raw_msg = get_dummy_raw_msg_chan_upgrade_confirm(10);
+
+        let tests: Vec<Test> = vec![
+            Test {
+                name: "Good parameters".to_string(),
+                raw: default_raw_msg.clone(),
+                want_pass: true,
+            },
+            Test {
+                name: "Unknown counterparty channel state".to_string(),
+                raw: RawMsgChannelUpgradeConfirm {
+                    counterparty_channel_state: 10,
+                    ..default_raw_msg.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "Missing counterparty upgrade".to_string(),
+                raw: RawMsgChannelUpgradeConfirm {
+                    counterparty_upgrade: None,
+                    ..default_raw_msg.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "Missing proof of the upgrade".to_string(),
+                raw: RawMsgChannelUpgradeConfirm {
+                    proof_upgrade: Vec::new(),
+                    ..default_raw_msg.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "Missing proof height".to_string(),
+                raw: RawMsgChannelUpgradeConfirm {
+                    proof_height: None,
+                    ..default_raw_msg
+                },
+                want_pass: false,
+            },
+        ]
+        .into_iter()
+        .collect();
+
+        for test in tests {
+            let msg = MsgChannelUpgradeConfirm::try_from(test.raw.clone());
+
+            assert_eq!(
+                test.want_pass,
+                msg.is_ok(),
+                "MsgChanUpgradeConfirm::try_from failed for test {}, \nmsg {:?} with error {:?}",
+                test.name,
+                test.raw,
+                msg.err(),
+            );
+        }
+    }
+
+    #[test]
+    fn to_and_from() {
+        let raw = get_dummy_raw_msg_chan_upgrade_confirm(10);
+        let msg = MsgChannelUpgradeConfirm::try_from(raw.clone()).unwrap();
+        let raw_back = RawMsgChannelUpgradeConfirm::from(msg.clone());
+        let msg_back = MsgChannelUpgradeConfirm::try_from(raw_back.clone()).unwrap();
+        assert_eq!(raw, raw_back);
+        assert_eq!(msg, msg_back);
+    }
+}
@@ -0,0 +1,194 @@
+use crate::prelude::*;
+
+use ibc_proto::protobuf::Protobuf;
+
+use crate::core::ics04_channel::error::ChannelError;
+use crate::core::ics04_channel::upgrade::{RawUpgradeFields, UpgradeFields};
+use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::signer::Signer;
+use crate::tx_msg::Msg;
+
+pub const TYPE_URL: &str = "/ibc.core.channel.v1.MsgChannelUpgradeInit";
+
+/// Raw `MsgChannelUpgradeInit`, as defined by ibc-go.
+///
+/// XXX: to be replaced by its `ibc-proto` counterpart once available.
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct RawMsgChannelUpgradeInit {
+    #[prost(string, tag = "1")]
+    pub port_id: String,
+    #[prost(string, tag = "2")]
+    pub channel_id: String,
+    #[prost(message, optional, tag = "3")]
+    pub fields: Option<RawUpgradeFields>,
+    #[prost(string, tag = "4")]
+    pub signer: String,
+}
+
+///
+/// Message definition for the first step in the channel upgrade handshake (`ChanUpgradeInit`
+/// datagram). Per our convention, this message is sent to chain A.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MsgChannelUpgradeInit {
+    pub port_id_on_a: PortId,
+    pub chan_id_on_a: ChannelId,
+    /// The proposed ordering, connection hops and version of the upgraded channel
+    pub fields: UpgradeFields,
+    pub signer: Signer,
+}
+
+impl Msg for MsgChannelUpgradeInit {
+    type Raw = RawMsgChannelUpgradeInit;
+
+    fn type_url(&self) -> String {
+        TYPE_URL.to_string()
+    }
+}
+
+impl Protobuf<RawMsgChannelUpgradeInit> for MsgChannelUpgradeInit {}
+
+impl TryFrom<RawMsgChannelUpgradeInit> for MsgChannelUpgradeInit {
+    type Error = ChannelError;
+
+    fn try_from(raw_msg: RawMsgChannelUpgradeInit) -> Result<Self, Self::Error> {
+        Ok(MsgChannelUpgradeInit {
+            port_id_on_a: raw_msg.port_id.parse().map_err(ChannelError::Identifier)?,
+            chan_id_on_a: raw_msg
+                .channel_id
+                .parse()
+                .map_err(ChannelError::Identifier)?,
+            fields: raw_msg
+                .fields
+                .ok_or(ChannelError::InvalidUpgrade {
+                    reason: "missing upgrade fields".to_string(),
+                })?
+                .try_into()?,
+            signer: raw_msg.signer.parse().map_err(ChannelError::Signer)?,
+        })
+    }
+}
+
+impl From<MsgChannelUpgradeInit> for RawMsgChannelUpgradeInit {
+    fn from(domain_msg: MsgChannelUpgradeInit) -> Self {
+        RawMsgChannelUpgradeInit {
+            port_id: domain_msg.port_id_on_a.to_string(),
+            channel_id: domain_msg.chan_id_on_a.to_string(),
+            fields: Some(domain_msg.fields.into()),
+            signer: domain_msg.signer.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test_util {
+    use super::RawMsgChannelUpgradeInit;
+    use crate::prelude::*;
+
+    use crate::core::ics04_channel::upgrade::test_util::get_dummy_raw_upgrade_fields;
+    use crate::core::ics24_host::identifier::{ChannelId, PortId};
+    use crate::test_utils::get_dummy_bech32_account;
+
+    /// Returns a dummy `RawMsgChannelUpgradeInit`, for testing only!
+    pub fn get_dummy_raw_msg_chan_upgrade_init() -> RawMsgChannelUpgradeInit {
+        RawMsgChannelUpgradeInit {
+            port_id: PortId::default().to_string(),
+            channel_id: ChannelId::default().to_string(),
+            fields: Some(get_dummy_raw_upgrade_fields()),
+            signer: get_dummy_bech32_account(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use test_log::test;
+
+    use crate::core::ics04_channel::msgs::chan_upgrade_init::test_util::get_dummy_raw_msg_chan_upgrade_init;
+    use crate::core::ics04_channel::msgs::chan_upgrade_init::{
+        MsgChannelUpgradeInit, RawMsgChannelUpgradeInit,
+    };
+    use crate::core::ics04_channel::upgrade::test_util::get_dummy_raw_upgrade_fields;
+    use crate::core::ics04_channel::upgrade::RawUpgradeFields;
+
+    #[test]
+    fn parse_channel_upgrade_init_msg() {
+        struct Test {
+            name: String,
+            raw: RawMsgChannelUpgradeInit,
+            want_pass: bool,
+        }
+
+        let default_raw_msg = get_dummy_raw_msg_chan_upgrade_init();
+
+        let tests: Vec<Test> = vec![
+            Test {
+                name: "Good parameters".to_string(),
+                raw: default_raw_msg.clone(),
+                want_pass: true,
+            },
+            Test {
+                name: "Bad port, name too short".to_string(),
+                raw: RawMsgChannelUpgradeInit {
+                    port_id: "p".to_string(),
+                    ..default_raw_msg.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "Bad channel, name too short".to_string(),
+                raw: RawMsgChannelUpgradeInit {
+                    channel_id: "chshort".to_string(),
+                    ..default_raw_msg.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "Missing upgrade fields".to_string(),
+                raw: RawMsgChannelUpgradeInit {
+                    fields: None,
+                    ..default_raw_msg.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "Bad upgrade fields, no connection hops".to_string(),
+                raw: RawMsgChannelUpgradeInit {
+                    fields: Some(RawUpgradeFields {
+                        connection_hops: Vec::new(),
+                        ..get_dummy_raw_upgrade_fields()
+                    }),
+                    ..default_raw_msg
+                },
+                want_pass: false,
+            },
+        ]
+        .into_iter()
+        .collect();
+
+        for test in tests {
+            let msg = MsgChannelUpgradeInit::try_from(test.raw.clone());
+
+            assert_eq!(
+                test.want_pass,
+                msg.is_ok(),
+                "MsgChanUpgradeInit::try_from failed for test {}, \nmsg {:?} with error {:?}",
+                test.name,
+                test.raw,
+                msg.err(),
+            );
+        }
+    }
+
+    #[test]
+    fn to_and_from() {
+        let raw = get_dummy_raw_msg_chan_upgrade_init();
+        let msg = MsgChannelUpgradeInit::try_from(raw.clone()).unwrap();
+        let raw_back = RawMsgChannelUpgradeInit::from(msg.clone());
+        let msg_back = MsgChannelUpgradeInit::try_from(raw_back.clone()).unwrap();
+        assert_eq!(raw, raw_back);
+        assert_eq!(msg, msg_back);
+    }
+}
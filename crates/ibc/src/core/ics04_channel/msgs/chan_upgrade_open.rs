@@ -0,0 +1,210 @@
+use crate::prelude::*;
+
+use ibc_proto::ibc::core::client::v1::Height as RawHeight;
+use ibc_proto::protobuf::Protobuf;
+
+use crate::core::ics04_channel::channel::State;
+use crate::core::ics04_channel::error::ChannelError;
+use crate::core::ics04_channel::packet::Sequence;
+use crate::core::ics23_commitment::commitment::CommitmentProofBytes;
+use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::signer::Signer;
+use crate::tx_msg::Msg;
+use crate::Height;
+
+pub const TYPE_URL: &str = "/ibc.core.channel.v1.MsgChannelUpgradeOpen";
+
+/// Raw `MsgChannelUpgradeOpen`, as defined by ibc-go.
+///
+/// XXX: to be replaced by its `ibc-proto` counterpart once available.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RawMsgChannelUpgradeOpen {
+    #[prost(string, tag = "1")]
+    pub port_id: String,
+    #[prost(string, tag = "2")]
+    pub channel_id: String,
+    #[prost(int32, tag = "3")]
+    pub counterparty_channel_state: i32,
+    #[prost(uint64, tag = "4")]
+    pub counterparty_upgrade_sequence: u64,
+    #[prost(bytes = "vec", tag = "5")]
+    pub proof_channel: Vec<u8>,
+    #[prost(message, optional, tag = "6")]
+    pub proof_height: Option<RawHeight>,
+    #[prost(string, tag = "7")]
+    pub signer: String,
+}
+
+///
+/// Message definition for the last step in the channel upgrade handshake (`ChanUpgradeOpen`
+/// datagram). This message can be sent to either chain, once its channel end has flushed all
+/// its in-flight packets.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MsgChannelUpgradeOpen {
+    pub port_id: PortId,
+    pub chan_id: ChannelId,
+    /// The state of the counterparty channel end, either `FLUSHCOMPLETE` or `OPEN`
+    pub counterparty_chan_state: State,
+    pub counterparty_upgrade_sequence: Sequence,
+    pub proof_chan_end: CommitmentProofBytes,
+    pub proof_height: Height,
+    pub signer: Signer,
+}
+
+impl Msg for MsgChannelUpgradeOpen {
+    type Raw = RawMsgChannelUpgradeOpen;
+
+    fn type_url(&self) -> String {
+        TYPE_URL.to_string()
+    }
+}
+
+impl Protobuf<RawMsgChannelUpgradeOpen> for MsgChannelUpgradeOpen {}
+
+impl TryFrom<RawMsgChannelUpgradeOpen> for MsgChannelUpgradeOpen {
+    type Error = ChannelError;
+
+    fn try_from(raw_msg: RawMsgChannelUpgradeOpen) -> Result<Self, Self::Error> {
+        Ok(MsgChannelUpgradeOpen {
+            port_id: raw_msg.port_id.parse().map_err(ChannelError::Identifier)?,
+            chan_id: raw_msg
+                .channel_id
+                .parse()
+                .map_err(ChannelError::Identifier)?,
+            counterparty_chan_state: State::from_i32(raw_msg.counterparty_channel_state)?,
+            counterparty_upgrade_sequence: raw_msg.counterparty_upgrade_sequence.into(),
+            proof_chan_end: raw_msg
+                .proof_channel
+                .try_into()
+                .map_err(|_| ChannelError::InvalidProof)?,
+            proof_height: raw_msg
+                .proof_height
+                .and_then(|raw_height| raw_height.try_into().ok())
+                .ok_or(ChannelError::MissingHeight)?,
+            signer: raw_msg.signer.parse().map_err(ChannelError::Signer)?,
+        })
+    }
+}
+
+impl From<MsgChannelUpgradeOpen> for RawMsgChannelUpgradeOpen {
+    fn from(domain_msg: MsgChannelUpgradeOpen) -> Self {
+        RawMsgChannelUpgradeOpen {
+            port_id: domain_msg.port_id.to_string(),
+            channel_id: domain_msg.chan_id.to_string(),
+            counterparty_channel_state: domain_msg.counterparty_chan_state as i32,
+            counterparty_upgrade_sequence: domain_msg.counterparty_upgrade_sequence.into(),
+            proof_channel: domain_msg.proof_chan_end.into(),
+            proof_height: Some(domain_msg.proof_height.into()),
+            signer: domain_msg.signer.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test_util {
+    use super::RawMsgChannelUpgradeOpen;
+    use crate::prelude::*;
+
+    use ibc_proto::ibc::core::client::v1::Height as RawHeight;
+
+    use crate::core::ics04_channel::channel::State;
+    use crate::core::ics24_host::identifier::{ChannelId, PortId};
+    use crate::test_utils::{get_dummy_bech32_account, get_dummy_proof};
+
+    /// Returns a dummy `RawMsgChannelUpgradeOpen`, for testing only!
+    pub fn get_dummy_raw_msg_chan_upgrade_open(proof_height: u64) -> RawMsgChannelUpgradeOpen {
+        RawMsgChannelUpgradeOpen {
+            port_id: PortId::default().to_string(),
+            channel_id: ChannelId::default().to_string(),
+            counterparty_channel_state: State::FlushComplete as i32,
+            counterparty_upgrade_sequence: 1,
+            proof_channel: get_dummy_proof(),
+            proof_height: Some(RawHeight {
+                revision_number: 0,
+                revision_height: proof_height,
+            }),
+            signer: get_dummy_bech32_account(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use test_log::test;
+
+    use crate::core::ics04_channel::msgs::chan_upgrade_open::test_util::get_dummy_raw_msg_chan_upgrade_open;
+    use crate::core::ics04_channel::msgs::chan_upgrade_open::{
+        MsgChannelUpgradeOpen, RawMsgChannelUpgradeOpen,
+    };
+
+    #[test]
+    fn parse_channel_upgrade_open_msg() {
+        struct Test {
+            name: String,
+            raw: RawMsgChannelUpgradeOpen,
+            want_pass: bool,
+        }
+
+        let default_raw_msg = get_dummy_raw_msg_chan_upgrade_open(10);
+
+        let tests: Vec<Test> = vec![
+            Test {
+                name: "Good parameters".to_string(),
+                raw: default_raw_msg.clone(),
+                want_pass: true,
+            },
+            Test {
+                name: "Bad channel, name too short".to_string(),
+                raw: RawMsgChannelUpgradeOpen {
+                    channel_id: "chshort".to_string(),
+                    ..default_raw_msg.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "Unknown counterparty channel state".to_string(),
+                raw: RawMsgChannelUpgradeOpen {
+                    counterparty_channel_state: 10,
+                    ..default_raw_msg.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "Missing proof of the channel end".to_string(),
+                raw: RawMsgChannelUpgradeOpen {
+                    proof_channel: Vec::new(),
+                    ..default_raw_msg
+                },
+                want_pass: false,
+            },
+        ]
+        .into_iter()
+        .collect();
+
+        for test in tests {
+            let msg = MsgChannelUpgradeOpen::try_from(test.raw.clone());
+
+            assert_eq!(
+                test.want_pass,
+                msg.is_ok(),
+                "MsgChanUpgradeOpen::try_from failed for test {}, \nmsg {:?} with error {:?}",
+                test.name,
+                test.raw,
+                msg.err(),
+            );
+        }
+    }
+
+    #[test]
+    fn to_and_from() {
+        let raw = get_dummy_raw_msg_chan_upgrade_open(10);
+        let msg = MsgChannelUpgradeOpen::try_from(raw.clone()).unwrap();
+        let raw_back = RawMsgChannelUpgradeOpen::from(msg.clone());
+        let msg_back = MsgChannelUpgradeOpen::try_from(raw_back.clone()).unwrap();
+        assert_eq!(raw, raw_back);
+        assert_eq!(msg, msg_back);
+    }
+}
@@ -0,0 +1,207 @@
+use crate::prelude::*;
+
+use ibc_proto::ibc::core::client::v1::Height as RawHeight;
+use ibc_proto::protobuf::Protobuf;
+
+use crate::core::ics04_channel::channel::{ChannelEnd, RawChannel};
+use crate::core::ics04_channel::error::ChannelError;
+use crate::core::ics23_commitment::commitment::CommitmentProofBytes;
+use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::signer::Signer;
+use crate::tx_msg::Msg;
+use crate::Height;
+
+pub const TYPE_URL: &str = "/ibc.core.channel.v1.MsgChannelUpgradeTimeout";
+
+/// Raw `MsgChannelUpgradeTimeout`, as defined by ibc-go.
+///
+/// XXX: to be replaced by its `ibc-proto` counterpart once available.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RawMsgChannelUpgradeTimeout {
+    #[prost(string, tag = "1")]
+    pub port_id: String,
+    #[prost(string, tag = "2")]
+    pub channel_id: String,
+    #[prost(message, optional, tag = "3")]
+    pub counterparty_channel: Option<RawChannel>,
+    #[prost(bytes = "vec", tag = "4")]
+    pub proof_channel: Vec<u8>,
+    #[prost(message, optional, tag = "5")]
+    pub proof_height: Option<RawHeight>,
+    #[prost(string, tag = "6")]
+    pub signer: String,
+}
+
+///
+/// Message definition for aborting a channel upgrade which did not complete on the counterparty
+/// chain before the upgrade timeout (`ChanUpgradeTimeout` datagram).
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MsgChannelUpgradeTimeout {
+    pub port_id: PortId,
+    pub chan_id: ChannelId,
+    /// The counterparty channel end at the proof height
+    pub counterparty_chan_end: ChannelEnd,
+    pub proof_chan_end: CommitmentProofBytes,
+    pub proof_height: Height,
+    pub signer: Signer,
+}
+
+impl Msg for MsgChannelUpgradeTimeout {
+    type Raw = RawMsgChannelUpgradeTimeout;
+
+    fn type_url(&self) -> String {
+        TYPE_URL.to_string()
+    }
+}
+
+impl Protobuf<RawMsgChannelUpgradeTimeout> for MsgChannelUpgradeTimeout {}
+
+impl TryFrom<RawMsgChannelUpgradeTimeout> for MsgChannelUpgradeTimeout {
+    type Error = ChannelError;
+
+    fn try_from(raw_msg: RawMsgChannelUpgradeTimeout) -> Result<Self, Self::Error> {
+        Ok(MsgChannelUpgradeTimeout {
+            port_id: raw_msg.port_id.parse().map_err(ChannelError::Identifier)?,
+            chan_id: raw_msg
+                .channel_id
+                .parse()
+                .map_err(ChannelError::Identifier)?,
+            counterparty_chan_end: raw_msg
+                .counterparty_channel
+                .ok_or(ChannelError::MissingChannel)?
+                .try_into()?,
+            proof_chan_end: raw_msg
+                .proof_channel
+                .try_into()
+                .map_err(|_| ChannelError::InvalidProof)?,
+            proof_height: raw_msg
+                .proof_height
+                .and_then(|raw_height| raw_height.try_into().ok())
+                .ok_or(ChannelError::MissingHeight)?,
+            signer: raw_msg.signer.parse().map_err(ChannelError::Signer)?,
+        })
+    }
+}
+
+impl From<MsgChannelUpgradeTimeout> for RawMsgChannelUpgradeTimeout {
+    fn from(domain_msg: MsgChannelUpgradeTimeout) -> Self {
+        RawMsgChannelUpgradeTimeout {
+            port_id: domain_msg.port_id.to_string(),
+            channel_id: domain_msg.chan_id.to_string(),
+            counterparty_channel: Some(domain_msg.counterparty_chan_end.into()),
+            proof_channel: domain_msg.proof_chan_end.into(),
+            proof_height: Some(domain_msg.proof_height.into()),
+            signer: domain_msg.signer.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test_util {
+    use super::RawMsgChannelUpgradeTimeout;
+    use crate::prelude::*;
+
+    use ibc_proto::ibc::core::client::v1::Height as RawHeight;
+
+    use crate::core::ics04_channel::channel::test_util::get_dummy_raw_channel_end;
+    use crate::core::ics24_host::identifier::{ChannelId, PortId};
+    use crate::test_utils::{get_dummy_bech32_account, get_dummy_proof};
+
+    /// Returns a dummy `RawMsgChannelUpgradeTimeout`, for testing only!
+    pub fn get_dummy_raw_msg_chan_upgrade_timeout(
+        proof_height: u64,
+    ) -> RawMsgChannelUpgradeTimeout {
+        RawMsgChannelUpgradeTimeout {
+            port_id: PortId::default().to_string(),
+            channel_id: ChannelId::default().to_string(),
+            counterparty_channel: Some(get_dummy_raw_channel_end(ChannelId::default().to_string())),
+            proof_channel: get_dummy_proof(),
+            proof_height: Some(RawHeight {
+                revision_number: 0,
+                revision_height: proof_height,
+            }),
+            signer: get_dummy_bech32_account(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use test_log::test;
+
+    use crate::core::ics04_channel::msgs::chan_upgrade_timeout::test_util::get_dummy_raw_msg_chan_upgrade_timeout;
+    use crate::core::ics04_channel::msgs::chan_upgrade_timeout::{
+        MsgChannelUpgradeTimeout, RawMsgChannelUpgradeTimeout,
+    };
+
+    #[test]
+    fn parse_channel_upgrade_timeout_msg() {
+        struct Test {
+            name: String,
+            raw: RawMsgChannelUpgradeTimeout,
+            want_pass: bool,
+        }
+
+        let default_raw_msg = get_dummy_raw_msg_chan_upgrade_timeout(10);
+
+        let tests: Vec<Test> = vec![
+            Test {
+                name: "Good parameters".to_string(),
+                raw: default_raw_msg.clone(),
+                want_pass: true,
+            },
+            Test {
+                name: "Bad port, name too short".to_string(),
+                raw: RawMsgChannelUpgradeTimeout {
+                    port_id: "p".to_string(),
+                    ..default_raw_msg.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "Missing counterparty channel end".to_string(),
+                raw: RawMsgChannelUpgradeTimeout {
+                    counterparty_channel: None,
+                    ..default_raw_msg.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "Missing proof height".to_string(),
+                raw: RawMsgChannelUpgradeTimeout {
+                    proof_height: None,
+                    ..default_raw_msg
+                },
+                want_pass: false,
+            },
+        ]
+        .into_iter()
+        .collect();
+
+        for test in tests {
+            let msg = MsgChannelUpgradeTimeout::try_from(test.raw.clone());
+
+            assert_eq!(
+                test.want_pass,
+                msg.is_ok(),
+                "MsgChanUpgradeTimeout::try_from failed for test {}, \nmsg {:?} with error {:?}",
+                test.name,
+                test.raw,
+                msg.err(),
+            );
+        }
+    }
+
+    #[test]
+    fn to_and_from() {
+        let raw = get_dummy_raw_msg_chan_upgrade_timeout(10);
+        let msg = MsgChannelUpgradeTimeout::try_from(raw.clone()).unwrap();
+        let raw_back = RawMsgChannelUpgradeTimeout::from(msg.clone());
+        let msg_back = MsgChannelUpgradeTimeout::try_from(raw_back.clone()).unwrap();
+        assert_eq!(raw, raw_back);
+        assert_eq!(msg, msg_back);
+    }
+}
@@ -0,0 +1,267 @@
+use crate::prelude::*;
+
+use core::str::FromStr;
+
+use ibc_proto::ibc::core::client::v1::Height as RawHeight;
+use ibc_proto::protobuf::Protobuf;
+
+use crate::core::ics04_channel::error::ChannelError;
+use crate::core::ics04_channel::packet::Sequence;
+use crate::core::ics04_channel::upgrade::{RawUpgradeFields, UpgradeFields};
+use crate::core::ics23_commitment::commitment::CommitmentProofBytes;
+use crate::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
+use crate::signer::Signer;
+use crate::tx_msg::Msg;
+use crate::Height;
+
+pub const TYPE_URL: &str = "/ibc.core.channel.v1.MsgChannelUpgradeTry";
+
+/// Raw `MsgChannelUpgradeTry`, as defined by ibc-go.
+///
+/// XXX: to be replaced by its `ibc-proto` counterpart once available.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RawMsgChannelUpgradeTry {
+    #[prost(string, tag = "1")]
+    pub port_id: String,
+    #[prost(string, tag = "2")]
+    pub channel_id: String,
+    #[prost(string, repeated, tag = "3")]
+    pub proposed_upgrade_connection_hops: Vec<String>,
+    #[prost(message, optional, tag = "4")]
+    pub counterparty_upgrade_fields: Option<RawUpgradeFields>,
+    #[prost(uint64, tag = "5")]
+    pub counterparty_upgrade_sequence: u64,
+    #[prost(bytes = "vec", tag = "6")]
+    pub proof_channel: Vec<u8>,
+    #[prost(bytes = "vec", tag = "7")]
+    pub proof_upgrade: Vec<u8>,
+    #[prost(message, optional, tag = "8")]
+    pub proof_height: Option<RawHeight>,
+    #[prost(string, tag = "9")]
+    pub signer: String,
+}
+
+///
+/// Message definition for the second step in the channel upgrade handshake (`ChanUpgradeTry`
+/// datagram). Per our convention, this message is sent to chain B.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MsgChannelUpgradeTry {
+    pub port_id_on_b: PortId,
+    pub chan_id_on_b: ChannelId,
+    /// The connection hops of the upgraded channel on chain B
+    pub proposed_connection_hops: Vec<ConnectionId>,
+    /// The upgrade fields proposed on chain A
+    pub counterparty_upgrade_fields: UpgradeFields,
+    /// The upgrade sequence of the channel end on chain A
+    pub counterparty_upgrade_sequence: Sequence,
+    pub proof_chan_end_on_a: CommitmentProofBytes,
+    pub proof_upgrade_on_a: CommitmentProofBytes,
+    pub proof_height_on_a: Height,
+    pub signer: Signer,
+}
+
+impl Msg for MsgChannelUpgradeTry {
+    type Raw = RawMsgChannelUpgradeTry;
+
+    fn type_url(&self) -> String {
+        TYPE_URL.to_string()
+    }
+}
+
+impl Protobuf<RawMsgChannelUpgradeTry> for MsgChannelUpgradeTry {}
+
+impl TryFrom<RawMsgChannelUpgradeTry> for MsgChannelUpgradeTry {
+    type Error = ChannelError;
+
+    fn try_from(raw_msg: RawMsgChannelUpgradeTry) -> Result<Self, Self::Error> {
+        let proposed_connection_hops = raw_msg
+            .proposed_upgrade_connection_hops
+            .iter()
+            .map(|conn_id| ConnectionId::from_str(conn_id))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(ChannelError::Identifier)?;
+
+        if proposed_connection_hops.len() != 1 {
+            return Err(ChannelError::InvalidConnectionHopsLength {
+                expected: 1,
+                actual: proposed_connection_hops.len(),
+            });
+        }
+
+        Ok(MsgChannelUpgradeTry {
+            port_id_on_b: raw_msg.port_id.parse().map_err(ChannelError::Identifier)?,
+            chan_id_on_b: raw_msg
+                .channel_id
+                .parse()
+                .map_err(ChannelError::Identifier)?,
+            proposed_connection_hops,
+            counterparty_upgrade_fields: raw_msg
+                .counterparty_upgrade_fields
+                .ok_or(ChannelError::InvalidUpgrade {
+                    reason: "missing counterparty upgrade fields".to_string(),
+                })?
+                .try_into()?,
+            counterparty_upgrade_sequence: raw_msg.counterparty_upgrade_sequence.into(),
+            proof_chan_end_on_a: raw_msg
+                .proof_channel
+                .try_into()
+                .map_err(|_| ChannelError::InvalidProof)?,
+            proof_upgrade_on_a: raw_msg
+                .proof_upgrade
+                .try_into()
+                .map_err(|_| ChannelError::InvalidProof)?,
+            proof_height_on_a: raw_msg
+                .proof_height
+                .and_then(|raw_height| raw_height.try_into().ok())
+                .ok_or(ChannelError::MissingHeight)?,
+            signer: raw_msg.signer.parse().map_err(ChannelError::Signer)?,
+        })
+    }
+}
+
+impl From<MsgChannelUpgradeTry> for RawMsgChannelUpgradeTry {
+    fn from(domain_msg: MsgChannelUpgradeTry) -> Self {
+        RawMsgChannelUpgradeTry {
+            port_id: domain_msg.port_id_on_b.to_string(),
+            channel_id: domain_msg.chan_id_on_b.to_string(),
+            proposed_upgrade_connection_hops: domain_msg
+                .proposed_connection_hops
+                .iter()
+                .map(|conn_id| conn_id.to_string())
+                .collect(),
+            counterparty_upgrade_fields: Some(domain_msg.counterparty_upgrade_fields.into()),
+            counterparty_upgrade_sequence: domain_msg.counterparty_upgrade_sequence.into(),
+            proof_channel: domain_msg.proof_chan_end_on_a.into(),
+            proof_upgrade: domain_msg.proof_upgrade_on_a.into(),
+            proof_height: Some(domain_msg.proof_height_on_a.into()),
+            signer: domain_msg.signer.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test_util {
+    use super::RawMsgChannelUpgradeTry;
+    use crate::prelude::*;
+
+    use ibc_proto::ibc::core::client::v1::Height as RawHeight;
+
+    use crate::core::ics04_channel::upgrade::test_util::get_dummy_raw_upgrade_fields;
+    use crate::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
+    use crate::test_utils::{get_dummy_bech32_account, get_dummy_proof};
+
+    /// Returns a dummy `RawMsgChannelUpgradeTry`, for testing only!
+    pub fn get_dummy_raw_msg_chan_upgrade_try(proof_height: u64) -> RawMsgChannelUpgradeTry {
+        RawMsgChannelUpgradeTry {
+            port_id: PortId::default().to_string(),
+            channel_id: ChannelId::default().to_string(),
+            proposed_upgrade_connection_hops: vec![ConnectionId::default().to_string()],
+            counterparty_upgrade_fields: Some(get_dummy_raw_upgrade_fields()),
+            counterparty_upgrade_sequence: 1,
+            proof_channel: get_dummy_proof(),
+            proof_upgrade: get_dummy_proof(),
+            proof_height: Some(RawHeight {
+                revision_number: 0,
+                revision_height: proof_height,
+            }),
+            signer: get_dummy_bech32_account(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use test_log::test;
+
+    use crate::core::ics04_channel::msgs::chan_upgrade_try::test_util::get_dummy_raw_msg_chan_upgrade_try;
+    use crate::core::ics04_channel::msgs::chan_upgrade_try::{
+        MsgChannelUpgradeTry, RawMsgChannelUpgradeTry,
+    };
+
+    #[test]
+    fn parse_channel_upgrade_try_msg() {
+        struct Test {
+            name: String,
+            raw: RawMsgChannelUpgradeTry,
+            want_pass: bool,
+        }
+
+        let default_raw_msg = get_dummy_raw_msg_chan_upgrade_try(10);
+
+        let tests: Vec<Test> = vec![
+            Test {
+                name: "Good parameters".to_string(),
+                raw: default_raw_msg.clone(),
+                want_pass: true,
+            },
+            Test {
+                name: "Bad channel, name too short".to_string(),
+                raw: RawMsgChannelUpgradeTry {
+                    channel_id: "chshort".to_string(),
+                    ..default_raw_msg.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "No proposed connection hops".to_string(),
+                raw: RawMsgChannelUpgradeTry {
+                    proposed_upgrade_connection_hops: Vec::new(),
+                    ..default_raw_msg.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "Missing counterparty upgrade fields".to_string(),
+                raw: RawMsgChannelUpgradeTry {
+                    counterparty_upgrade_fields: None,
+                    ..default_raw_msg.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "Missing proof of the upgrade".to_string(),
+                raw: RawMsgChannelUpgradeTry {
+                    proof_upgrade: Vec::new(),
+                    ..default_raw_msg.clone()
+                },
+                want_pass: false,
+            },
+            Test {
+                name: "Missing proof height".to_string(),
+                raw: RawMsgChannelUpgradeTry {
+                    proof_height: None,
+                    ..default_raw_msg
+                },
+                want_pass: false,
+            },
+        ]
+        .into_iter()
+        .collect();
+
+        for test in tests {
+            let msg = MsgChannelUpgradeTry::try_from(test.raw.clone());
+
+            assert_eq!(
+                test.want_pass,
+                msg.is_ok(),
+                "MsgChanUpgradeTry::try_from failed for test {}, \nmsg {:?} with error {:?}",
+                test.name,
+                test.raw,
+                msg.err(),
+            );
+        }
+    }
+
+    #[test]
+    fn to_and_from() {
+        let raw = get_dummy_raw_msg_chan_upgrade_try(10);
+        let msg = MsgChannelUpgradeTry::try_from(raw.clone()).unwrap();
+        let raw_back = RawMsgChannelUpgradeTry::from(msg.clone());
+        let msg_back = MsgChannelUpgradeTry::try_from(raw_back.clone()).unwrap();
+        assert_eq!(raw, raw_back);
+        assert_eq!(msg, msg_back);
+    }
+}
@@ -1,3 +1,7 @@
+// The `parity-scale-codec` derive checks the variant indices of enums with a `usize as usize`
+// cast per variant.
+#![cfg_attr(feature = "parity-scale-codec", allow(trivial_numeric_casts))]
+
 use crate::prelude::*;
 
 use core::str::FromStr;
@@ -48,6 +52,20 @@ pub enum PacketResult {
 #[derive(Clone, Debug)]
 pub enum Receipt {
     Ok,
+    /// The packet timed out on an `OrderedAllowTimeout` channel, so that its sequence was
+    /// skipped instead of the packet being received.
+    Timeout,
+}
+
+impl Receipt {
+    /// Returns the value stored under the receipt path of the packet, which the counterparty
+    /// verifies proofs of timeout receipts against.
+    pub fn commitment_bytes(&self) -> Vec<u8> {
+        match self {
+            Self::Ok => vec![1],
+            Self::Timeout => vec![2],
+        }
+    }
 }
 
 impl core::fmt::Display for PacketMsgType {
@@ -0,0 +1,374 @@
+//! Types of the channel upgrade handshake, through which the ordering, the connection hops and
+//! the version of an open channel are changed without opening a new channel.
+
+use crate::prelude::*;
+
+use core::str::FromStr;
+use core::time::Duration;
+
+use ibc_proto::ibc::core::client::v1::Height as RawHeight;
+use ibc_proto::protobuf::Protobuf;
+
+use crate::core::ics04_channel::channel::Order;
+use crate::core::ics04_channel::error::ChannelError;
+use crate::core::ics04_channel::packet::Sequence;
+use crate::core::ics04_channel::timeout::TimeoutHeight;
+use crate::core::ics04_channel::Version;
+use crate::core::ics24_host::identifier::ConnectionId;
+use crate::timestamp::{Expiry, Timestamp};
+use crate::Height;
+
+/// The default period, as in ibc-go, given to the counterparty chain to complete an upgrade
+/// before it can be timed out.
+pub const DEFAULT_UPGRADE_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Raw `UpgradeFields`, as defined by ibc-go.
+///
+/// XXX: to be replaced by its `ibc-proto` counterpart once available.
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct RawUpgradeFields {
+    #[prost(int32, tag = "1")]
+    pub ordering: i32,
+    #[prost(string, repeated, tag = "2")]
+    pub connection_hops: Vec<String>,
+    #[prost(string, tag = "3")]
+    pub version: String,
+}
+
+/// Raw `Timeout`, as defined by ibc-go.
+///
+/// XXX: to be replaced by its `ibc-proto` counterpart once available.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RawTimeout {
+    /// block height after which the upgrade times out
+    #[prost(message, optional, tag = "1")]
+    pub height: Option<RawHeight>,
+    /// block timestamp (in nanoseconds) after which the upgrade times out
+    #[prost(uint64, tag = "2")]
+    pub timestamp: u64,
+}
+
+/// Raw `Upgrade`, as defined by ibc-go.
+///
+/// XXX: to be replaced by its `ibc-proto` counterpart once available.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RawUpgrade {
+    #[prost(message, optional, tag = "1")]
+    pub fields: Option<RawUpgradeFields>,
+    #[prost(message, optional, tag = "2")]
+    pub timeout: Option<RawTimeout>,
+    #[prost(uint64, tag = "3")]
+    pub next_sequence_send: u64,
+}
+
+/// Raw `ErrorReceipt`, as defined by ibc-go.
+///
+/// XXX: to be replaced by its `ibc-proto` counterpart once available.
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct RawErrorReceipt {
+    /// the channel upgrade sequence
+    #[prost(uint64, tag = "1")]
+    pub sequence: u64,
+    /// the error message detailing the cause of failure
+    #[prost(string, tag = "2")]
+    pub message: String,
+}
+
+/// The fields of a channel end which are changed by an upgrade.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UpgradeFields {
+    pub ordering: Order,
+    pub connection_hops: Vec<ConnectionId>,
+    pub version: Version,
+}
+
+impl UpgradeFields {
+    pub fn new(ordering: Order, connection_hops: Vec<ConnectionId>, version: Version) -> Self {
+        Self {
+            ordering,
+            connection_hops,
+            version,
+        }
+    }
+
+    pub fn validate_basic(&self) -> Result<(), ChannelError> {
+        if self.ordering == Order::None {
+            return Err(ChannelError::InvalidUpgrade {
+                reason: "the ordering of the upgraded channel must be specified".to_string(),
+            });
+        }
+
+        if self.connection_hops.len() != 1 {
+            return Err(ChannelError::InvalidConnectionHopsLength {
+                expected: 1,
+                actual: self.connection_hops.len(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl TryFrom<RawUpgradeFields> for UpgradeFields {
+    type Error = ChannelError;
+
+    fn try_from(raw: RawUpgradeFields) -> Result<Self, Self::Error> {
+        let connection_hops = raw
+            .connection_hops
+            .iter()
+            .map(|conn_id| ConnectionId::from_str(conn_id))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(ChannelError::Identifier)?;
+
+        let fields = UpgradeFields {
+            ordering: Order::from_i32(raw.ordering)?,
+            connection_hops,
+            version: raw.version.into(),
+        };
+        fields.validate_basic()?;
+
+        Ok(fields)
+    }
+}
+
+impl From<UpgradeFields> for RawUpgradeFields {
+    fn from(value: UpgradeFields) -> Self {
+        RawUpgradeFields {
+            ordering: value.ordering as i32,
+            connection_hops: value
+                .connection_hops
+                .iter()
+                .map(|conn_id| conn_id.to_string())
+                .collect(),
+            version: value.version.to_string(),
+        }
+    }
+}
+
+/// The height or timestamp of the counterparty chain past which an upgrade times out.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct UpgradeTimeout {
+    pub height: TimeoutHeight,
+    pub timestamp: Timestamp,
+}
+
+impl UpgradeTimeout {
+    pub fn new(height: TimeoutHeight, timestamp: Timestamp) -> Self {
+        Self { height, timestamp }
+    }
+
+    /// Returns `true` if at least one of the timeout height and timestamp is set.
+    pub fn is_set(&self) -> bool {
+        self.height != TimeoutHeight::Never || self.timestamp != Timestamp::none()
+    }
+
+    /// Checks whether the upgrade has timed out on a chain at the given height and timestamp.
+    pub fn has_expired(&self, height: Height, timestamp: &Timestamp) -> bool {
+        self.height.has_expired(height)
+            || matches!(timestamp.check_expiry(&self.timestamp), Expiry::Expired)
+    }
+}
+
+impl TryFrom<RawTimeout> for UpgradeTimeout {
+    type Error = ChannelError;
+
+    fn try_from(raw: RawTimeout) -> Result<Self, Self::Error> {
+        Ok(UpgradeTimeout {
+            height: raw.height.try_into().map_err(ChannelError::Client)?,
+            timestamp: Timestamp::from_nanoseconds(raw.timestamp).map_err(|e| {
+                ChannelError::InvalidUpgrade {
+                    reason: e.to_string(),
+                }
+            })?,
+        })
+    }
+}
+
+impl From<UpgradeTimeout> for RawTimeout {
+    fn from(value: UpgradeTimeout) -> Self {
+        RawTimeout {
+            height: value.height.into(),
+            timestamp: value.timestamp.nanoseconds(),
+        }
+    }
+}
+
+/// A proposed upgrade of a channel end, stored while the upgrade handshake is in progress.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Upgrade {
+    pub fields: UpgradeFields,
+    pub timeout: UpgradeTimeout,
+    /// The next sequence of the channel at the time it started flushing its in-flight packets.
+    pub next_sequence_send: Sequence,
+}
+
+impl Upgrade {
+    pub fn new(
+        fields: UpgradeFields,
+        timeout: UpgradeTimeout,
+        next_sequence_send: Sequence,
+    ) -> Self {
+        Self {
+            fields,
+            timeout,
+            next_sequence_send,
+        }
+    }
+}
+
+impl Protobuf<RawUpgrade> for Upgrade {}
+
+impl TryFrom<RawUpgrade> for Upgrade {
+    type Error = ChannelError;
+
+    fn try_from(raw: RawUpgrade) -> Result<Self, Self::Error> {
+        Ok(Upgrade {
+            fields: raw
+                .fields
+                .ok_or(ChannelError::InvalidUpgrade {
+                    reason: "missing upgrade fields".to_string(),
+                })?
+                .try_into()?,
+            timeout: raw.timeout.unwrap_or_default().try_into()?,
+            next_sequence_send: raw.next_sequence_send.into(),
+        })
+    }
+}
+
+impl From<Upgrade> for RawUpgrade {
+    fn from(value: Upgrade) -> Self {
+        RawUpgrade {
+            fields: Some(value.fields.into()),
+            timeout: Some(value.timeout.into()),
+            next_sequence_send: value.next_sequence_send.into(),
+        }
+    }
+}
+
+/// Records that the upgrade of a channel end with the given sequence was aborted, so that the
+/// counterparty can cancel it too.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ErrorReceipt {
+    pub sequence: Sequence,
+    pub message: String,
+}
+
+impl ErrorReceipt {
+    pub fn new(sequence: Sequence, message: String) -> Self {
+        Self { sequence, message }
+    }
+}
+
+impl Protobuf<RawErrorReceipt> for ErrorReceipt {}
+
+impl TryFrom<RawErrorReceipt> for ErrorReceipt {
+    type Error = ChannelError;
+
+    fn try_from(raw: RawErrorReceipt) -> Result<Self, Self::Error> {
+        Ok(ErrorReceipt {
+            sequence: raw.sequence.into(),
+            message: raw.message,
+        })
+    }
+}
+
+impl From<ErrorReceipt> for RawErrorReceipt {
+    fn from(value: ErrorReceipt) -> Self {
+        RawErrorReceipt {
+            sequence: value.sequence.into(),
+            message: value.message,
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod test_util {
+    use super::*;
+
+    /// Returns dummy `RawUpgradeFields`, for testing only!
+    pub fn get_dummy_raw_upgrade_fields() -> RawUpgradeFields {
+        RawUpgradeFields {
+            ordering: Order::Unordered as i32,
+            connection_hops: vec![ConnectionId::default().to_string()],
+            version: "ics20-1".to_string(),
+        }
+    }
+
+    /// Returns a dummy `RawUpgrade`, for testing only!
+    pub fn get_dummy_raw_upgrade() -> RawUpgrade {
+        RawUpgrade {
+            fields: Some(get_dummy_raw_upgrade_fields()),
+            timeout: Some(RawTimeout {
+                height: Some(RawHeight {
+                    revision_number: 0,
+                    revision_height: 0,
+                }),
+                timestamp: 10,
+            }),
+            next_sequence_send: 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_util::{get_dummy_raw_upgrade, get_dummy_raw_upgrade_fields};
+    use super::*;
+
+    use test_log::test;
+
+    #[test]
+    fn upgrade_try_from_raw() {
+        let raw = get_dummy_raw_upgrade();
+        let upgrade = Upgrade::try_from(raw.clone()).unwrap();
+        assert_eq!(RawUpgrade::from(upgrade), raw);
+
+        let raw_no_fields = RawUpgrade {
+            fields: None,
+            ..get_dummy_raw_upgrade()
+        };
+        assert!(Upgrade::try_from(raw_no_fields).is_err());
+
+        let raw_no_ordering = RawUpgrade {
+            fields: Some(RawUpgradeFields {
+                ordering: Order::None as i32,
+                ..get_dummy_raw_upgrade_fields()
+            }),
+            ..get_dummy_raw_upgrade()
+        };
+        assert!(Upgrade::try_from(raw_no_ordering).is_err());
+
+        let raw_no_hops = RawUpgrade {
+            fields: Some(RawUpgradeFields {
+                connection_hops: Vec::new(),
+                ..get_dummy_raw_upgrade_fields()
+            }),
+            ..get_dummy_raw_upgrade()
+        };
+        assert!(Upgrade::try_from(raw_no_hops).is_err());
+    }
+
+    #[test]
+    fn upgrade_timeout_expiry() {
+        let height = Height::new(0, 10).unwrap();
+        let timestamp = Timestamp::from_nanoseconds(10).unwrap();
+
+        let no_timeout = UpgradeTimeout::default();
+        assert!(!no_timeout.is_set());
+        assert!(!no_timeout.has_expired(height, &timestamp));
+
+        let height_timeout = UpgradeTimeout::new(height.into(), Timestamp::none());
+        assert!(height_timeout.is_set());
+        assert!(!height_timeout.has_expired(height, &timestamp));
+        assert!(height_timeout.has_expired(height.increment(), &timestamp));
+
+        let timestamp_timeout = UpgradeTimeout::new(TimeoutHeight::Never, timestamp);
+        assert!(timestamp_timeout.is_set());
+        assert!(!timestamp_timeout.has_expired(height, &timestamp));
+        assert!(timestamp_timeout.has_expired(height, &Timestamp::from_nanoseconds(11).unwrap()));
+    }
+}
@@ -7,7 +7,7 @@ use ibc_proto::ibc::core::commitment::v1::MerkleRoot;
 use ics23::commitment_proof::Proof;
 use ics23::{
     calculate_existence_root, verify_membership, verify_non_membership, CommitmentProof,
-    NonExistenceProof,
+    HostFunctionsProvider, NonExistenceProof,
 };
 
 use crate::core::ics23_commitment::commitment::{CommitmentPrefix, CommitmentRoot};
@@ -68,7 +68,9 @@ impl From<MerkleProof> for RawMerkleProof {
 }
 
 impl MerkleProof {
-    pub fn verify_membership(
+    /// Verifies that `value` is stored at `keys` under `root`, starting from the proof at
+    /// `start_index`. Hashing is done with the host functions `H`.
+    pub fn verify_membership<H: HostFunctionsProvider>(
         &self,
         specs: &ProofSpecs,
         root: MerkleRoot,
@@ -107,17 +109,10 @@ impl MerkleProof {
         {
             match &proof.proof {
                 Some(Proof::Exist(existence_proof)) => {
-                    subroot =
-                        calculate_existence_root::<ics23::HostFunctionsManager>(existence_proof)
-                            .map_err(|_| CommitmentError::InvalidMerkleProof)?;
+                    subroot = calculate_existence_root::<H>(existence_proof)
+                        .map_err(|_| CommitmentError::InvalidMerkleProof)?;
 
-                    if !verify_membership::<ics23::HostFunctionsManager>(
-                        proof,
-                        spec,
-                        &subroot,
-                        key.as_bytes(),
-                        &value,
-                    ) {
+                    if !verify_membership::<H>(proof, spec, &subroot, key.as_bytes(), &value) {
                         return Err(CommitmentError::VerificationFailure);
                     }
                     value = subroot.clone();
@@ -133,7 +128,9 @@ impl MerkleProof {
         Ok(())
     }
 
-    pub fn verify_non_membership(
+    /// Verifies that no value is stored at `keys` under `root`. Hashing is done with the host
+    /// functions `H`.
+    pub fn verify_non_membership<H: HostFunctionsProvider>(
         &self,
         specs: &ProofSpecs,
         root: MerkleRoot,
@@ -170,19 +167,14 @@ impl MerkleProof {
             .ok_or(CommitmentError::InvalidMerkleProof)?;
         match &proof.proof {
             Some(Proof::Nonexist(non_existence_proof)) => {
-                let subroot = calculate_non_existence_root(non_existence_proof)?;
+                let subroot = calculate_non_existence_root::<H>(non_existence_proof)?;
 
-                if !verify_non_membership::<ics23::HostFunctionsManager>(
-                    proof,
-                    spec,
-                    &subroot,
-                    key.as_bytes(),
-                ) {
+                if !verify_non_membership::<H>(proof, spec, &subroot, key.as_bytes()) {
                     return Err(CommitmentError::VerificationFailure);
                 }
 
                 // verify membership proofs starting from index 1 with value = subroot
-                self.verify_membership(specs, root, keys, subroot, 1)
+                self.verify_membership::<H>(specs, root, keys, subroot, 1)
             }
             _ => Err(CommitmentError::InvalidMerkleProof),
         }
@@ -190,13 +182,13 @@ impl MerkleProof {
 }
 
 // TODO move to ics23
-fn calculate_non_existence_root(proof: &NonExistenceProof) -> Result<Vec<u8>, CommitmentError> {
+fn calculate_non_existence_root<H: HostFunctionsProvider>(
+    proof: &NonExistenceProof,
+) -> Result<Vec<u8>, CommitmentError> {
     if let Some(left) = &proof.left {
-        calculate_existence_root::<ics23::HostFunctionsManager>(left)
-            .map_err(|_| CommitmentError::InvalidMerkleProof)
+        calculate_existence_root::<H>(left).map_err(|_| CommitmentError::InvalidMerkleProof)
     } else if let Some(right) = &proof.right {
-        calculate_existence_root::<ics23::HostFunctionsManager>(right)
-            .map_err(|_| CommitmentError::InvalidMerkleProof)
+        calculate_existence_root::<H>(right).map_err(|_| CommitmentError::InvalidMerkleProof)
     } else {
         Err(CommitmentError::InvalidMerkleProof)
     }
@@ -32,6 +32,8 @@ pub enum Path {
     ClientType(ClientTypePath),
     ClientState(ClientStatePath),
     ClientConsensusState(ClientConsensusStatePath),
+    ClientUpdateTime(ClientUpdateTimePath),
+    ClientUpdateHeight(ClientUpdateHeightPath),
     ClientConnections(ClientConnectionsPath),
     Connections(ConnectionsPath),
     Ports(PortsPath),
@@ -43,6 +45,8 @@ pub enum Path {
     Acks(AcksPath),
     Receipts(ReceiptsPath),
     Upgrade(ClientUpgradePath),
+    ChannelUpgrade(ChannelUpgradePath),
+    ChannelUpgradeError(ChannelUpgradeErrorPath),
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Display)]
@@ -71,6 +75,46 @@ impl ClientConsensusStatePath {
     }
 }
 
+/// The time at which the consensus state of a client at some height was processed by the host,
+/// as recorded by ibc-go next to the consensus state.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Display)]
+#[display(fmt = "clients/{client_id}/consensusStates/{epoch}-{height}/processedTime")]
+pub struct ClientUpdateTimePath {
+    pub client_id: ClientId,
+    pub epoch: u64,
+    pub height: u64,
+}
+
+impl ClientUpdateTimePath {
+    pub fn new(client_id: ClientId, height: Height) -> ClientUpdateTimePath {
+        ClientUpdateTimePath {
+            client_id,
+            epoch: height.revision_number(),
+            height: height.revision_height(),
+        }
+    }
+}
+
+/// The height at which the consensus state of a client at some height was processed by the
+/// host, as recorded by ibc-go next to the consensus state.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Display)]
+#[display(fmt = "clients/{client_id}/consensusStates/{epoch}-{height}/processedHeight")]
+pub struct ClientUpdateHeightPath {
+    pub client_id: ClientId,
+    pub epoch: u64,
+    pub height: u64,
+}
+
+impl ClientUpdateHeightPath {
+    pub fn new(client_id: ClientId, height: Height) -> ClientUpdateHeightPath {
+        ClientUpdateHeightPath {
+            client_id,
+            epoch: height.revision_number(),
+            height: height.revision_height(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Display)]
 #[display(fmt = "clients/{_0}/connections")]
 pub struct ClientConnectionsPath(pub ClientId);
@@ -123,6 +167,14 @@ pub struct ReceiptsPath {
     pub sequence: Sequence,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Display)]
+#[display(fmt = "channelUpgrades/upgrades/ports/{_0}/channels/{_1}")]
+pub struct ChannelUpgradePath(pub PortId, pub ChannelId);
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Display)]
+#[display(fmt = "channelUpgrades/upgradeError/ports/{_0}/channels/{_1}")]
+pub struct ChannelUpgradeErrorPath(pub PortId, pub ChannelId);
+
 /// Paths that are specific for client upgrades.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Display)]
 pub enum ClientUpgradePath {
@@ -177,6 +229,7 @@ impl FromStr for Path {
             .or_else(|| parse_acks(&components))
             .or_else(|| parse_receipts(&components))
             .or_else(|| parse_upgrades(&components))
+            .or_else(|| parse_channel_upgrades(&components))
             .ok_or(PathError::ParseFailure {
                 path: s.to_string(),
             })
@@ -205,15 +258,12 @@ fn parse_client_paths(components: &[&str]) -> Option<Path> {
             "connections" => Some(ClientConnectionsPath(client_id).into()),
             _ => None,
         }
-    } else if components.len() == 4 {
+    } else if components.len() == 4 || components.len() == 5 {
         if "consensusStates" != components[2] {
             return None;
         }
 
-        let epoch_height = match components.last() {
-            Some(eh) => *eh,
-            None => return None,
-        };
+        let epoch_height = components[3];
 
         let epoch_height: Vec<&str> = epoch_height.split('-').collect();
 
@@ -234,14 +284,33 @@ fn parse_client_paths(components: &[&str]) -> Option<Path> {
             Err(_) => return None,
         };
 
-        Some(
-            ClientConsensusStatePath {
-                client_id,
-                epoch,
-                height,
-            }
-            .into(),
-        )
+        match components.get(4) {
+            None => Some(
+                ClientConsensusStatePath {
+                    client_id,
+                    epoch,
+                    height,
+                }
+                .into(),
+            ),
+            Some(&"processedTime") => Some(
+                ClientUpdateTimePath {
+                    client_id,
+                    epoch,
+                    height,
+                }
+                .into(),
+            ),
+            Some(&"processedHeight") => Some(
+                ClientUpdateHeightPath {
+                    client_id,
+                    epoch,
+                    height,
+                }
+                .into(),
+            ),
+            Some(_) => None,
+        }
     } else {
         None
     }
@@ -589,6 +658,42 @@ fn parse_upgrades(components: &[&str]) -> Option<Path> {
     }
 }
 
+fn parse_channel_upgrades(components: &[&str]) -> Option<Path> {
+    if components.len() != 6 {
+        return None;
+    }
+
+    let first = match components.first() {
+        Some(f) => *f,
+        None => return None,
+    };
+
+    if first != "channelUpgrades" {
+        return None;
+    }
+
+    let port = parse_ports(&components[2..=3]);
+    let channel = parse_channels(&components[4..=5]);
+
+    let port_id = if let Some(Path::Ports(PortsPath(port_id))) = port {
+        port_id
+    } else {
+        return None;
+    };
+
+    let channel_id = if let Some(SubPath::Channels(channel_id)) = channel {
+        channel_id
+    } else {
+        return None;
+    };
+
+    match components[1] {
+        "upgrades" => Some(ChannelUpgradePath(port_id, channel_id).into()),
+        "upgradeError" => Some(ChannelUpgradeErrorPath(port_id, channel_id).into()),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -672,6 +777,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn client_update_time_path_parses() {
+        let path = "clients/07-tendermint-0/consensusStates/15-31/processedTime";
+        let path = Path::from_str(path);
+
+        assert!(path.is_ok());
+        assert_eq!(
+            path.unwrap(),
+            Path::ClientUpdateTime(ClientUpdateTimePath {
+                client_id: ClientId::default(),
+                epoch: 15,
+                height: 31,
+            })
+        );
+    }
+
+    #[test]
+    fn client_update_height_path_parses() {
+        let path = "clients/07-tendermint-0/consensusStates/15-31/processedHeight";
+        let path = Path::from_str(path);
+
+        assert!(path.is_ok());
+        assert_eq!(
+            path.unwrap(),
+            Path::ClientUpdateHeight(ClientUpdateHeightPath {
+                client_id: ClientId::default(),
+                epoch: 15,
+                height: 31,
+            })
+        );
+    }
+
     #[test]
     fn client_connections_path_parses() {
         let path = "clients/07-tendermint-0/connections";
@@ -1000,4 +1137,56 @@ mod tests {
             Path::Upgrade(ClientUpgradePath::UpgradedClientConsensusState(0)),
         );
     }
+
+    #[test]
+    fn test_parse_channel_upgrades_fn() {
+        let path = "channelUpgrades/upgrades/ports/defaultPort/channels/channel-0";
+        let components: Vec<&str> = path.split('/').collect();
+
+        assert_eq!(
+            parse_channel_upgrades(&components),
+            Some(Path::ChannelUpgrade(ChannelUpgradePath(
+                PortId::default(),
+                ChannelId::default()
+            ))),
+        );
+
+        let path = "channelUpgrades/upgradeError/ports/defaultPort/channels/channel-0";
+        let components: Vec<&str> = path.split('/').collect();
+
+        assert_eq!(
+            parse_channel_upgrades(&components),
+            Some(Path::ChannelUpgradeError(ChannelUpgradeErrorPath(
+                PortId::default(),
+                ChannelId::default()
+            ))),
+        );
+    }
+
+    #[test]
+    fn channel_upgrade_path_parses() {
+        let path = "channelUpgrades/upgrades/ports/defaultPort/channels/channel-0";
+        let path = Path::from_str(path);
+
+        assert!(path.is_ok());
+        assert_eq!(
+            path.unwrap(),
+            Path::ChannelUpgrade(ChannelUpgradePath(PortId::default(), ChannelId::default())),
+        );
+    }
+
+    #[test]
+    fn channel_upgrade_error_path_parses() {
+        let path = "channelUpgrades/upgradeError/ports/defaultPort/channels/channel-0";
+        let path = Path::from_str(path);
+
+        assert!(path.is_ok());
+        assert_eq!(
+            path.unwrap(),
+            Path::ChannelUpgradeError(ChannelUpgradeErrorPath(
+                PortId::default(),
+                ChannelId::default()
+            )),
+        );
+    }
 }
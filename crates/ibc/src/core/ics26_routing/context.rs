@@ -121,6 +121,30 @@ pub trait Module: Send + Sync + AsAnyMut + Debug {
         version: &Version,
     ) -> Result<(ModuleExtras, Version), ChannelError>;
 
+    #[cfg(feature = "val_exec_ctx")]
+    #[allow(clippy::too_many_arguments)]
+    fn on_chan_open_init_validate(
+        &self,
+        order: Order,
+        connection_hops: &[ConnectionId],
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty: &Counterparty,
+        version: &Version,
+    ) -> Result<Version, ChannelError>;
+
+    #[cfg(feature = "val_exec_ctx")]
+    #[allow(clippy::too_many_arguments)]
+    fn on_chan_open_init_execute(
+        &mut self,
+        order: Order,
+        connection_hops: &[ConnectionId],
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        counterparty: &Counterparty,
+        version: &Version,
+    ) -> Result<(ModuleExtras, Version), ChannelError>;
+
     #[cfg(feature = "val_exec_ctx")]
     #[allow(clippy::too_many_arguments)]
     fn on_chan_open_try_validate(
@@ -156,6 +180,26 @@ pub trait Module: Send + Sync + AsAnyMut + Debug {
         counterparty_version: &Version,
     ) -> Result<(ModuleExtras, Version), ChannelError>;
 
+    #[cfg(feature = "val_exec_ctx")]
+    fn on_chan_open_ack_validate(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty_version: &Version,
+    ) -> Result<(), ChannelError> {
+        Ok(())
+    }
+
+    #[cfg(feature = "val_exec_ctx")]
+    fn on_chan_open_ack_execute(
+        &mut self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty_version: &Version,
+    ) -> Result<ModuleExtras, ChannelError> {
+        Ok(ModuleExtras::empty())
+    }
+
     fn on_chan_open_ack(
         &mut self,
         _port_id: &PortId,
@@ -165,6 +209,24 @@ pub trait Module: Send + Sync + AsAnyMut + Debug {
         Ok(ModuleExtras::empty())
     }
 
+    #[cfg(feature = "val_exec_ctx")]
+    fn on_chan_open_confirm_validate(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<(), ChannelError> {
+        Ok(())
+    }
+
+    #[cfg(feature = "val_exec_ctx")]
+    fn on_chan_open_confirm_execute(
+        &mut self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<ModuleExtras, ChannelError> {
+        Ok(ModuleExtras::empty())
+    }
+
     fn on_chan_open_confirm(
         &mut self,
         _port_id: &PortId,
@@ -173,6 +235,24 @@ pub trait Module: Send + Sync + AsAnyMut + Debug {
         Ok(ModuleExtras::empty())
     }
 
+    #[cfg(feature = "val_exec_ctx")]
+    fn on_chan_close_init_validate(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<(), ChannelError> {
+        Ok(())
+    }
+
+    #[cfg(feature = "val_exec_ctx")]
+    fn on_chan_close_init_execute(
+        &mut self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<ModuleExtras, ChannelError> {
+        Ok(ModuleExtras::empty())
+    }
+
     fn on_chan_close_init(
         &mut self,
         _port_id: &PortId,
@@ -181,6 +261,24 @@ pub trait Module: Send + Sync + AsAnyMut + Debug {
         Ok(ModuleExtras::empty())
     }
 
+    #[cfg(feature = "val_exec_ctx")]
+    fn on_chan_close_confirm_validate(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<(), ChannelError> {
+        Ok(())
+    }
+
+    #[cfg(feature = "val_exec_ctx")]
+    fn on_chan_close_confirm_execute(
+        &mut self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+    ) -> Result<ModuleExtras, ChannelError> {
+        Ok(ModuleExtras::empty())
+    }
+
     fn on_chan_close_confirm(
         &mut self,
         _port_id: &PortId,
@@ -189,6 +287,129 @@ pub trait Module: Send + Sync + AsAnyMut + Debug {
         Ok(ModuleExtras::empty())
     }
 
+    /// Validates the upgrade of the channel proposed on the local chain, and returns the version
+    /// of the upgraded channel. Channel upgrades are rejected by default.
+    #[cfg(feature = "val_exec_ctx")]
+    fn on_chan_upgrade_init_validate(
+        &self,
+        port_id: &PortId,
+        _channel_id: &ChannelId,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        _version: &Version,
+    ) -> Result<Version, ChannelError> {
+        Err(ChannelError::UpgradeNotSupported {
+            port_id: port_id.clone(),
+        })
+    }
+
+    #[cfg(feature = "val_exec_ctx")]
+    fn on_chan_upgrade_init_execute(
+        &mut self,
+        port_id: &PortId,
+        _channel_id: &ChannelId,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        _version: &Version,
+    ) -> Result<(ModuleExtras, Version), ChannelError> {
+        Err(ChannelError::UpgradeNotSupported {
+            port_id: port_id.clone(),
+        })
+    }
+
+    /// Validates the upgrade of the channel proposed on the counterparty chain, and returns the
+    /// version of the upgraded channel. Channel upgrades are rejected by default.
+    #[cfg(feature = "val_exec_ctx")]
+    fn on_chan_upgrade_try_validate(
+        &self,
+        port_id: &PortId,
+        _channel_id: &ChannelId,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        _counterparty_version: &Version,
+    ) -> Result<Version, ChannelError> {
+        Err(ChannelError::UpgradeNotSupported {
+            port_id: port_id.clone(),
+        })
+    }
+
+    #[cfg(feature = "val_exec_ctx")]
+    fn on_chan_upgrade_try_execute(
+        &mut self,
+        port_id: &PortId,
+        _channel_id: &ChannelId,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        _counterparty_version: &Version,
+    ) -> Result<(ModuleExtras, Version), ChannelError> {
+        Err(ChannelError::UpgradeNotSupported {
+            port_id: port_id.clone(),
+        })
+    }
+
+    /// Validates the version of the upgraded channel picked by the counterparty chain.
+    /// Channel upgrades are rejected by default.
+    #[cfg(feature = "val_exec_ctx")]
+    fn on_chan_upgrade_ack_validate(
+        &self,
+        port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty_version: &Version,
+    ) -> Result<(), ChannelError> {
+        Err(ChannelError::UpgradeNotSupported {
+            port_id: port_id.clone(),
+        })
+    }
+
+    #[cfg(feature = "val_exec_ctx")]
+    fn on_chan_upgrade_ack_execute(
+        &mut self,
+        port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty_version: &Version,
+    ) -> Result<ModuleExtras, ChannelError> {
+        Err(ChannelError::UpgradeNotSupported {
+            port_id: port_id.clone(),
+        })
+    }
+
+    /// Called once the upgrade is complete and the channel is open again with its new ordering,
+    /// connection hops and version. The upgrade cannot fail at this point.
+    #[cfg(feature = "val_exec_ctx")]
+    fn on_chan_upgrade_open_execute(
+        &mut self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        _version: &Version,
+    ) -> ModuleExtras {
+        ModuleExtras::empty()
+    }
+
+    /// Validates a received packet before any state is written. Note that application-level
+    /// failures should rather be reported through an error acknowledgement in
+    /// `on_recv_packet_execute`, as failing here rejects the whole message.
+    #[cfg(feature = "val_exec_ctx")]
+    fn on_recv_packet_validate(
+        &self,
+        _packet: &Packet,
+        _relayer: &Signer,
+    ) -> Result<(), PacketError> {
+        Ok(())
+    }
+
+    /// Executes the application logic for a received packet. Returning `None` as the
+    /// acknowledgement means that it will be written asynchronously.
+    #[cfg(feature = "val_exec_ctx")]
+    fn on_recv_packet_execute(
+        &mut self,
+        _packet: &Packet,
+        _relayer: &Signer,
+    ) -> (ModuleExtras, Option<Box<dyn Acknowledgement>>) {
+        (ModuleExtras::empty(), None)
+    }
+
     fn on_recv_packet(
         &self,
         _output: &mut ModuleOutputBuilder,
@@ -198,6 +419,26 @@ pub trait Module: Send + Sync + AsAnyMut + Debug {
         OnRecvPacketAck::Nil(Box::new(|_| Ok(())))
     }
 
+    #[cfg(feature = "val_exec_ctx")]
+    fn on_acknowledgement_packet_validate(
+        &self,
+        _packet: &Packet,
+        _acknowledgement: &GenericAcknowledgement,
+        _relayer: &Signer,
+    ) -> Result<(), PacketError> {
+        Ok(())
+    }
+
+    #[cfg(feature = "val_exec_ctx")]
+    fn on_acknowledgement_packet_execute(
+        &mut self,
+        _packet: &Packet,
+        _acknowledgement: &GenericAcknowledgement,
+        _relayer: &Signer,
+    ) -> Result<ModuleExtras, PacketError> {
+        Ok(ModuleExtras::empty())
+    }
+
     fn on_acknowledgement_packet(
         &mut self,
         _output: &mut ModuleOutputBuilder,
@@ -208,6 +449,24 @@ pub trait Module: Send + Sync + AsAnyMut + Debug {
         Ok(())
     }
 
+    #[cfg(feature = "val_exec_ctx")]
+    fn on_timeout_packet_validate(
+        &self,
+        _packet: &Packet,
+        _relayer: &Signer,
+    ) -> Result<(), PacketError> {
+        Ok(())
+    }
+
+    #[cfg(feature = "val_exec_ctx")]
+    fn on_timeout_packet_execute(
+        &mut self,
+        _packet: &Packet,
+        _relayer: &Signer,
+    ) -> Result<ModuleExtras, PacketError> {
+        Ok(ModuleExtras::empty())
+    }
+
     fn on_timeout_packet(
         &mut self,
         _output: &mut ModuleOutputBuilder,
@@ -3,8 +3,14 @@ use crate::prelude::*;
 
 use ibc_proto::google::protobuf::Any;
 
+use crate::clients::ics09_localhost as localhost;
+use crate::core::ics02_client::client_state::ClientState;
+use crate::core::ics02_client::context::ClientReader;
 use crate::core::ics02_client::handler::dispatch as ics2_msg_dispatcher;
+use crate::core::ics02_client::handler::ClientResult;
+use crate::core::ics03_connection::context::ConnectionReader;
 use crate::core::ics03_connection::handler::dispatch as ics3_msg_dispatcher;
+use crate::core::ics04_channel::context::ChannelReader;
 use crate::core::ics04_channel::handler::{
     channel_callback, channel_dispatch, channel_validate, recv_packet::RecvPacketResult,
 };
@@ -13,6 +19,7 @@ use crate::core::ics04_channel::handler::{
     packet_dispatch as ics4_packet_msg_dispatcher,
 };
 use crate::core::ics04_channel::packet::PacketResult;
+use crate::core::ics24_host::identifier::ClientId;
 use crate::core::ics26_routing::context::RouterContext;
 use crate::core::ics26_routing::error::RouterError;
 use crate::core::ics26_routing::msgs::MsgEnvelope::{self, Channel, Client, Connection, Packet};
@@ -60,10 +67,30 @@ where
             let handler_output =
                 ics2_msg_dispatcher(ctx, msg).map_err(|e| RouterError::ContextError(e.into()))?;
 
+            let creates_localhost = matches!(
+                &handler_output.result,
+                ClientResult::Create(res) if res.client_id == localhost::client_id()
+            );
+
             // Apply the result to the context (host chain store).
             ctx.store_client_result(handler_output.result)
                 .map_err(|e| RouterError::ContextError(e.into()))?;
 
+            // The localhost client comes with its sentinel connection, so that no connection
+            // handshake is needed to open channels over it
+            if creates_localhost {
+                let connection_end =
+                    localhost::connection_end(ConnectionReader::commitment_prefix(ctx));
+                ctx.store_connection(localhost::connection_id(), connection_end)
+                    .and_then(|_| {
+                        ctx.store_connection_to_client(
+                            localhost::connection_id(),
+                            localhost::client_id(),
+                        )
+                    })
+                    .map_err(|e| RouterError::ContextError(e.into()))?;
+            }
+
             HandlerOutput::builder()
                 .with_log(handler_output.log)
                 .with_events(handler_output.events)
@@ -73,10 +100,13 @@ where
         Connection(msg) => {
             let handler_output =
                 ics3_msg_dispatcher(ctx, msg).map_err(|e| RouterError::ContextError(e.into()))?;
+            let client_id = handler_output.result.connection_end.client_id().clone();
+            let client_state = handler_output.result.client_state.clone();
 
             // Apply any results to the host chain store.
             ctx.store_connection_result(handler_output.result)
                 .map_err(|e| RouterError::ContextError(e.into()))?;
+            store_verified_client_state(ctx, &client_id, client_state)?;
 
             HandlerOutput::builder()
                 .with_log(handler_output.log)
@@ -110,9 +140,19 @@ where
                 &channel_result.channel_end.version,
             );
 
+            let client_id = ConnectionReader::connection_end(
+                ctx,
+                &channel_result.channel_end.connection_hops[0],
+            )
+            .map_err(|e| RouterError::ContextError(e.into()))?
+            .client_id()
+            .clone();
+            let client_state = channel_result.client_state.clone();
+
             // Apply any results to the host chain store.
             ctx.store_channel_result(channel_result)
                 .map_err(|e| RouterError::ContextError(e.into()))?;
+            store_verified_client_state(ctx, &client_id, client_state)?;
 
             dispatch_output
                 .with_events(dispatch_events)
@@ -138,12 +178,38 @@ where
                 return Ok(handler_builder.with_result(()));
             }
 
-            let cb_result = ics4_packet_callback(ctx, &module_id, &msg, &mut handler_builder);
-            cb_result.map_err(|e| RouterError::ContextError(e.into()))?;
+            // A timed out packet only skips its sequence, and never reaches the module.
+            if !matches!(
+                packet_result,
+                PacketResult::Recv(RecvPacketResult::TimedOut { .. })
+            ) {
+                let cb_result = ics4_packet_callback(ctx, &module_id, &msg, &mut handler_builder);
+                cb_result.map_err(|e| RouterError::ContextError(e.into()))?;
+            }
+
+            let (port_id, chan_id) = msg.port_channel_id();
+            let client_id = ChannelReader::channel_end(ctx, port_id, chan_id)
+                .and_then(|chan_end| {
+                    ChannelReader::connection_end(ctx, &chan_end.connection_hops[0])
+                })
+                .map_err(|e| RouterError::ContextError(e.into()))?
+                .client_id()
+                .clone();
+            let client_state = match &packet_result {
+                PacketResult::Recv(
+                    RecvPacketResult::Unordered { client_state, .. }
+                    | RecvPacketResult::Ordered { client_state, .. }
+                    | RecvPacketResult::TimedOut { client_state, .. },
+                ) => Some(client_state.clone()),
+                PacketResult::Ack(res) => Some(res.client_state.clone()),
+                PacketResult::Timeout(res) => Some(res.client_state.clone()),
+                _ => None,
+            };
 
             // Apply any results to the host chain store.
             ctx.store_packet_result(packet_result)
                 .map_err(|e| RouterError::ContextError(e.into()))?;
+            store_verified_client_state(ctx, &client_id, client_state)?;
 
             handler_builder.with_result(())
         }
@@ -152,6 +218,31 @@ where
     Ok(output)
 }
 
+/// Stores the state of a client once the proofs of a message were verified against it, if
+/// proof verification changed the state of the client.
+fn store_verified_client_state<Ctx>(
+    ctx: &mut Ctx,
+    client_id: &ClientId,
+    client_state: Option<Box<dyn ClientState>>,
+) -> Result<(), RouterError>
+where
+    Ctx: RouterContext,
+{
+    let client_state = match client_state {
+        Some(client_state) => client_state,
+        None => return Ok(()),
+    };
+
+    let stored_client_state = ClientReader::client_state(ctx, client_id)
+        .map_err(|e| RouterError::ContextError(e.into()))?;
+    if *stored_client_state == *client_state {
+        return Ok(());
+    }
+
+    ctx.store_client_state(client_id.clone(), client_state)
+        .map_err(|e| RouterError::ContextError(e.into()))
+}
+
 #[cfg(test)]
 mod tests {
     use core::default::Default;
@@ -196,9 +287,11 @@ mod tests {
         chan_open_init::{test_util::get_dummy_raw_msg_chan_open_init, MsgChannelOpenInit},
         chan_open_try::{test_util::get_dummy_raw_msg_chan_open_try, MsgChannelOpenTry},
         recv_packet::{test_util::get_dummy_raw_msg_recv_packet, MsgRecvPacket},
+        timeout::{test_util::get_dummy_raw_msg_timeout, MsgTimeout},
         timeout_on_close::{test_util::get_dummy_raw_msg_timeout_on_close, MsgTimeoutOnClose},
         ChannelMsg, PacketMsg,
     };
+    use crate::core::ics04_channel::packet::Receipt;
     use crate::core::ics04_channel::timeout::TimeoutHeight;
     use crate::core::ics04_channel::Version as ChannelVersion;
     use crate::core::ics23_commitment::commitment::test_util::get_dummy_merkle_proof;
@@ -289,9 +382,10 @@ mod tests {
         let msg_conn_init =
             MsgConnectionOpenInit::try_from(get_dummy_raw_msg_conn_open_init()).unwrap();
 
+        // The client of the host on the counterparty must be behind the host.
         let correct_msg_conn_try = MsgConnectionOpenTry::try_from(get_dummy_raw_msg_conn_open_try(
             client_height,
-            client_height,
+            client_height - 1,
         ))
         .unwrap();
 
@@ -303,7 +397,7 @@ mod tests {
 
         let msg_conn_ack = MsgConnectionOpenAck::try_from(get_dummy_raw_msg_conn_open_ack(
             client_height,
-            client_height,
+            client_height - 1,
         ))
         .unwrap();
 
@@ -834,4 +928,169 @@ mod tests {
 
         assert!(matches!(event, IbcEvent::CloseConfirmChannel(_)));
     }
+
+    /// Builds the context of one end of an open `OrderedAllowTimeout` channel, whose ports,
+    /// channel, connection and client identifiers are the defaults on both ends.
+    fn get_allow_timeout_channel_ctx(client_height: Height) -> MockContext {
+        let module_id: ModuleId = MODULE_ID_STR.parse().unwrap();
+        let mut ctx = MockContext::default()
+            .with_client(&ClientId::default(), client_height)
+            .with_connection(
+                ConnectionId::default(),
+                ConnectionEnd::new(
+                    ConnState::Open,
+                    ClientId::default(),
+                    ConnCounterparty::new(
+                        ClientId::default(),
+                        Some(ConnectionId::default()),
+                        CommitmentPrefix::default(),
+                    ),
+                    vec![ConnVersion::default()],
+                    Duration::ZERO,
+                ),
+            )
+            .with_channel(
+                PortId::default(),
+                ChannelId::default(),
+                ChannelEnd::new(
+                    ChannelState::Open,
+                    ChannelOrder::OrderedAllowTimeout,
+                    ChannelCounterparty::new(PortId::default(), Some(ChannelId::default())),
+                    vec![ConnectionId::default()],
+                    ChannelVersion::default(),
+                ),
+            );
+        ctx.scope_port_to_module(PortId::default(), module_id.clone());
+
+        #[cfg(feature = "val_exec_ctx")]
+        ctx.add_route(
+            module_id.clone(),
+            DummyTransferModule::new(ctx.ibc_store_share()),
+        )
+        .unwrap();
+
+        let module = DummyTransferModule::new(ctx.ibc_store_share());
+        let router = MockRouterBuilder::default()
+            .add_route(module_id, module)
+            .unwrap()
+            .build();
+        ctx.with_router(router)
+    }
+
+    #[test]
+    /// A packet which timed out on an `OrderedAllowTimeout` channel is skipped on the receiving
+    /// end and timed out on the sending end, after which the channel carries on with the next
+    /// packet.
+    fn ordered_allow_timeout_channel_skips_timed_out_packet() {
+        type DeliverFn = fn(&mut MockContext, MsgEnvelope) -> Result<(), String>;
+
+        let deliver_fns: Vec<DeliverFn> = vec![
+            |ctx, msg| ctx.deliver(msg).map_err(|e| e.to_string()),
+            #[cfg(feature = "val_exec_ctx")]
+            |ctx, msg| {
+                use crate::core::{ExecutionContext, ValidationContext};
+
+                ValidationContext::validate(ctx, msg.clone()).map_err(|e| e.to_string())?;
+                ExecutionContext::execute(ctx, msg).map_err(|e| e.to_string())
+            },
+        ];
+
+        let client_height = Height::new(0, 5).unwrap();
+        let port_id = PortId::default();
+        let chan_id = ChannelId::default();
+
+        for deliver in deliver_fns {
+            let mut ctx_a = get_allow_timeout_channel_ctx(client_height);
+            let ctx_b = get_allow_timeout_channel_ctx(client_height);
+
+            // The first packet times out before it reaches the receiving end, unlike the second.
+            let mut msg_recv_timed_out = MsgRecvPacket::try_from(get_dummy_raw_msg_recv_packet(
+                client_height.revision_height(),
+            ))
+            .unwrap();
+            msg_recv_timed_out.packet.timeout_height_on_b = TimeoutHeight::no_timeout();
+            msg_recv_timed_out.packet.timeout_timestamp_on_b =
+                Timestamp::from_nanoseconds(1).unwrap();
+            let packet_timed_out = msg_recv_timed_out.packet.clone();
+
+            let mut msg_recv = msg_recv_timed_out.clone();
+            msg_recv.packet.sequence = 2.into();
+            msg_recv.packet.timeout_timestamp_on_b =
+                (Timestamp::now() + Duration::from_secs(600)).unwrap();
+            let packet = msg_recv.packet.clone();
+
+            for packet in [&packet_timed_out, &packet] {
+                let commitment = ChannelReader::packet_commitment(
+                    &ctx_a,
+                    &packet.data,
+                    &packet.timeout_height_on_b,
+                    &packet.timeout_timestamp_on_b,
+                );
+                ctx_a = ctx_a.with_packet_commitment(
+                    port_id.clone(),
+                    chan_id.clone(),
+                    packet.sequence,
+                    commitment,
+                );
+            }
+            let mut ctx_a = ctx_a
+                .with_send_sequence(port_id.clone(), chan_id.clone(), 3.into())
+                .with_ack_sequence(port_id.clone(), chan_id.clone(), 1.into());
+            let mut ctx_b = ctx_b.with_recv_sequence(port_id.clone(), chan_id.clone(), 1.into());
+
+            // The receiving end skips the timed out packet, and leaves a timeout receipt.
+            deliver(
+                &mut ctx_b,
+                MsgEnvelope::Packet(PacketMsg::Recv(msg_recv_timed_out)),
+            )
+            .unwrap();
+            let next_seq_recv_on_b =
+                ChannelReader::get_next_sequence_recv(&ctx_b, &port_id, &chan_id).unwrap();
+            assert_eq!(next_seq_recv_on_b, 2.into());
+            assert!(matches!(
+                ChannelReader::get_packet_receipt(&ctx_b, &port_id, &chan_id, &1.into()),
+                Ok(Receipt::Timeout)
+            ));
+
+            // The sending end times the packet out, which leaves the channel open.
+            let mut msg_timeout = MsgTimeout::try_from(get_dummy_raw_msg_timeout(
+                client_height.revision_height(),
+                0,
+                0,
+            ))
+            .unwrap();
+            msg_timeout.packet = packet_timed_out.clone();
+            msg_timeout.next_seq_recv_on_b = next_seq_recv_on_b;
+            deliver(
+                &mut ctx_a,
+                MsgEnvelope::Packet(PacketMsg::Timeout(msg_timeout)),
+            )
+            .unwrap();
+            assert!(ChannelReader::channel_end(&ctx_a, &port_id, &chan_id)
+                .unwrap()
+                .state_matches(&ChannelState::Open));
+            assert_eq!(
+                ChannelReader::get_next_sequence_ack(&ctx_a, &port_id, &chan_id).unwrap(),
+                2.into()
+            );
+
+            // The next packet is received and acknowledged in order.
+            deliver(&mut ctx_b, MsgEnvelope::Packet(PacketMsg::Recv(msg_recv))).unwrap();
+            assert_eq!(
+                ChannelReader::get_next_sequence_recv(&ctx_b, &port_id, &chan_id).unwrap(),
+                3.into()
+            );
+
+            let msg_ack = MsgAcknowledgement::try_from(get_dummy_raw_msg_ack_with_packet(
+                packet.into(),
+                client_height.revision_height(),
+            ))
+            .unwrap();
+            deliver(&mut ctx_a, MsgEnvelope::Packet(PacketMsg::Ack(msg_ack))).unwrap();
+            assert_eq!(
+                ChannelReader::get_next_sequence_ack(&ctx_a, &port_id, &chan_id).unwrap(),
+                3.into()
+            );
+        }
+    }
 }
@@ -2,13 +2,17 @@ use crate::prelude::*;
 
 use ibc_proto::google::protobuf::Any;
 
-use crate::core::ics02_client::msgs::{create_client, update_client, upgrade_client, ClientMsg};
+use crate::core::ics02_client::msgs::{
+    create_client, recover_client, update_client, upgrade_client, ClientMsg,
+};
 use crate::core::ics03_connection::msgs::{
     conn_open_ack, conn_open_confirm, conn_open_init, conn_open_try, ConnectionMsg,
 };
 use crate::core::ics04_channel::msgs::{
     acknowledgement, chan_close_confirm, chan_close_init, chan_open_ack, chan_open_confirm,
-    chan_open_init, chan_open_try, recv_packet, timeout, timeout_on_close, ChannelMsg, PacketMsg,
+    chan_open_init, chan_open_try, chan_upgrade_ack, chan_upgrade_cancel, chan_upgrade_confirm,
+    chan_upgrade_init, chan_upgrade_open, chan_upgrade_timeout, chan_upgrade_try, recv_packet,
+    timeout, timeout_on_close, ChannelMsg, PacketMsg,
 };
 use crate::core::ics26_routing::error::RouterError;
 use ibc_proto::protobuf::Protobuf;
@@ -44,6 +48,11 @@ impl TryFrom<Any> for MsgEnvelope {
                     .map_err(RouterError::MalformedMessageBytes)?;
                 Ok(MsgEnvelope::Client(ClientMsg::UpgradeClient(domain_msg)))
             }
+            recover_client::TYPE_URL => {
+                let domain_msg = recover_client::MsgRecoverClient::decode_vec(&any_msg.value)
+                    .map_err(RouterError::MalformedMessageBytes)?;
+                Ok(MsgEnvelope::Client(ClientMsg::RecoverClient(domain_msg)))
+            }
 
             // ICS03
             conn_open_init::TYPE_URL => {
@@ -103,6 +112,46 @@ impl TryFrom<Any> for MsgEnvelope {
                         .map_err(RouterError::MalformedMessageBytes)?;
                 Ok(MsgEnvelope::Channel(ChannelMsg::CloseConfirm(domain_msg)))
             }
+            chan_upgrade_init::TYPE_URL => {
+                let domain_msg =
+                    chan_upgrade_init::MsgChannelUpgradeInit::decode_vec(&any_msg.value)
+                        .map_err(RouterError::MalformedMessageBytes)?;
+                Ok(MsgEnvelope::Channel(ChannelMsg::UpgradeInit(domain_msg)))
+            }
+            chan_upgrade_try::TYPE_URL => {
+                let domain_msg = chan_upgrade_try::MsgChannelUpgradeTry::decode_vec(&any_msg.value)
+                    .map_err(RouterError::MalformedMessageBytes)?;
+                Ok(MsgEnvelope::Channel(ChannelMsg::UpgradeTry(domain_msg)))
+            }
+            chan_upgrade_ack::TYPE_URL => {
+                let domain_msg = chan_upgrade_ack::MsgChannelUpgradeAck::decode_vec(&any_msg.value)
+                    .map_err(RouterError::MalformedMessageBytes)?;
+                Ok(MsgEnvelope::Channel(ChannelMsg::UpgradeAck(domain_msg)))
+            }
+            chan_upgrade_confirm::TYPE_URL => {
+                let domain_msg =
+                    chan_upgrade_confirm::MsgChannelUpgradeConfirm::decode_vec(&any_msg.value)
+                        .map_err(RouterError::MalformedMessageBytes)?;
+                Ok(MsgEnvelope::Channel(ChannelMsg::UpgradeConfirm(domain_msg)))
+            }
+            chan_upgrade_open::TYPE_URL => {
+                let domain_msg =
+                    chan_upgrade_open::MsgChannelUpgradeOpen::decode_vec(&any_msg.value)
+                        .map_err(RouterError::MalformedMessageBytes)?;
+                Ok(MsgEnvelope::Channel(ChannelMsg::UpgradeOpen(domain_msg)))
+            }
+            chan_upgrade_timeout::TYPE_URL => {
+                let domain_msg =
+                    chan_upgrade_timeout::MsgChannelUpgradeTimeout::decode_vec(&any_msg.value)
+                        .map_err(RouterError::MalformedMessageBytes)?;
+                Ok(MsgEnvelope::Channel(ChannelMsg::UpgradeTimeout(domain_msg)))
+            }
+            chan_upgrade_cancel::TYPE_URL => {
+                let domain_msg =
+                    chan_upgrade_cancel::MsgChannelUpgradeCancel::decode_vec(&any_msg.value)
+                        .map_err(RouterError::MalformedMessageBytes)?;
+                Ok(MsgEnvelope::Channel(ChannelMsg::UpgradeCancel(domain_msg)))
+            }
             // ICS04 packet messages
             recv_packet::TYPE_URL => {
                 let domain_msg = recv_packet::MsgRecvPacket::decode_vec(&any_msg.value)
@@ -1,3 +1,7 @@
+// The `parity-scale-codec` derive checks the variant indices of enums with a `usize as usize`
+// cast per variant.
+#![cfg_attr(feature = "parity-scale-codec", allow(trivial_numeric_casts))]
+
 use crate::prelude::*;
 
 use core::convert::{TryFrom, TryInto};
@@ -80,6 +84,7 @@ const CREATE_CLIENT_EVENT: &str = "create_client";
 const UPDATE_CLIENT_EVENT: &str = "update_client";
 const CLIENT_MISBEHAVIOUR_EVENT: &str = "client_misbehaviour";
 const UPGRADE_CLIENT_EVENT: &str = "upgrade_client";
+const RECOVER_CLIENT_EVENT: &str = "recover_client";
 /// Connection event types
 const CONNECTION_INIT_EVENT: &str = "connection_open_init";
 const CONNECTION_TRY_EVENT: &str = "connection_open_try";
@@ -92,6 +97,13 @@ const CHANNEL_OPEN_ACK_EVENT: &str = "channel_open_ack";
 const CHANNEL_OPEN_CONFIRM_EVENT: &str = "channel_open_confirm";
 const CHANNEL_CLOSE_INIT_EVENT: &str = "channel_close_init";
 const CHANNEL_CLOSE_CONFIRM_EVENT: &str = "channel_close_confirm";
+const CHANNEL_UPGRADE_INIT_EVENT: &str = "channel_upgrade_init";
+const CHANNEL_UPGRADE_TRY_EVENT: &str = "channel_upgrade_try";
+const CHANNEL_UPGRADE_ACK_EVENT: &str = "channel_upgrade_ack";
+const CHANNEL_UPGRADE_CONFIRM_EVENT: &str = "channel_upgrade_confirm";
+const CHANNEL_UPGRADE_OPEN_EVENT: &str = "channel_upgrade_open";
+const CHANNEL_UPGRADE_TIMEOUT_EVENT: &str = "channel_upgrade_timeout";
+const CHANNEL_UPGRADE_CANCEL_EVENT: &str = "channel_upgrade_cancel";
 /// Packet event types
 const SEND_PACKET_EVENT: &str = "send_packet";
 const RECEIVE_PACKET_EVENT: &str = "receive_packet";
@@ -107,6 +119,7 @@ pub enum IbcEventType {
     CreateClient,
     UpdateClient,
     UpgradeClient,
+    RecoverClient,
     ClientMisbehaviour,
     OpenInitConnection,
     OpenTryConnection,
@@ -118,6 +131,13 @@ pub enum IbcEventType {
     OpenConfirmChannel,
     CloseInitChannel,
     CloseConfirmChannel,
+    UpgradeInitChannel,
+    UpgradeTryChannel,
+    UpgradeAckChannel,
+    UpgradeConfirmChannel,
+    UpgradeOpenChannel,
+    UpgradeTimeoutChannel,
+    UpgradeCancelChannel,
     ChannelClosed,
     SendPacket,
     ReceivePacket,
@@ -133,6 +153,7 @@ impl IbcEventType {
             IbcEventType::CreateClient => CREATE_CLIENT_EVENT,
             IbcEventType::UpdateClient => UPDATE_CLIENT_EVENT,
             IbcEventType::UpgradeClient => UPGRADE_CLIENT_EVENT,
+            IbcEventType::RecoverClient => RECOVER_CLIENT_EVENT,
             IbcEventType::ClientMisbehaviour => CLIENT_MISBEHAVIOUR_EVENT,
             IbcEventType::OpenInitConnection => CONNECTION_INIT_EVENT,
             IbcEventType::OpenTryConnection => CONNECTION_TRY_EVENT,
@@ -144,6 +165,13 @@ impl IbcEventType {
             IbcEventType::OpenConfirmChannel => CHANNEL_OPEN_CONFIRM_EVENT,
             IbcEventType::CloseInitChannel => CHANNEL_CLOSE_INIT_EVENT,
             IbcEventType::CloseConfirmChannel => CHANNEL_CLOSE_CONFIRM_EVENT,
+            IbcEventType::UpgradeInitChannel => CHANNEL_UPGRADE_INIT_EVENT,
+            IbcEventType::UpgradeTryChannel => CHANNEL_UPGRADE_TRY_EVENT,
+            IbcEventType::UpgradeAckChannel => CHANNEL_UPGRADE_ACK_EVENT,
+            IbcEventType::UpgradeConfirmChannel => CHANNEL_UPGRADE_CONFIRM_EVENT,
+            IbcEventType::UpgradeOpenChannel => CHANNEL_UPGRADE_OPEN_EVENT,
+            IbcEventType::UpgradeTimeoutChannel => CHANNEL_UPGRADE_TIMEOUT_EVENT,
+            IbcEventType::UpgradeCancelChannel => CHANNEL_UPGRADE_CANCEL_EVENT,
             IbcEventType::ChannelClosed => CHANNEL_CLOSED_EVENT,
             IbcEventType::SendPacket => SEND_PACKET_EVENT,
             IbcEventType::ReceivePacket => RECEIVE_PACKET_EVENT,
@@ -163,6 +191,7 @@ impl FromStr for IbcEventType {
             CREATE_CLIENT_EVENT => Ok(IbcEventType::CreateClient),
             UPDATE_CLIENT_EVENT => Ok(IbcEventType::UpdateClient),
             UPGRADE_CLIENT_EVENT => Ok(IbcEventType::UpgradeClient),
+            RECOVER_CLIENT_EVENT => Ok(IbcEventType::RecoverClient),
             CLIENT_MISBEHAVIOUR_EVENT => Ok(IbcEventType::ClientMisbehaviour),
             CONNECTION_INIT_EVENT => Ok(IbcEventType::OpenInitConnection),
             CONNECTION_TRY_EVENT => Ok(IbcEventType::OpenTryConnection),
@@ -174,6 +203,13 @@ impl FromStr for IbcEventType {
             CHANNEL_OPEN_CONFIRM_EVENT => Ok(IbcEventType::OpenConfirmChannel),
             CHANNEL_CLOSE_INIT_EVENT => Ok(IbcEventType::CloseInitChannel),
             CHANNEL_CLOSE_CONFIRM_EVENT => Ok(IbcEventType::CloseConfirmChannel),
+            CHANNEL_UPGRADE_INIT_EVENT => Ok(IbcEventType::UpgradeInitChannel),
+            CHANNEL_UPGRADE_TRY_EVENT => Ok(IbcEventType::UpgradeTryChannel),
+            CHANNEL_UPGRADE_ACK_EVENT => Ok(IbcEventType::UpgradeAckChannel),
+            CHANNEL_UPGRADE_CONFIRM_EVENT => Ok(IbcEventType::UpgradeConfirmChannel),
+            CHANNEL_UPGRADE_OPEN_EVENT => Ok(IbcEventType::UpgradeOpenChannel),
+            CHANNEL_UPGRADE_TIMEOUT_EVENT => Ok(IbcEventType::UpgradeTimeoutChannel),
+            CHANNEL_UPGRADE_CANCEL_EVENT => Ok(IbcEventType::UpgradeCancelChannel),
             SEND_PACKET_EVENT => Ok(IbcEventType::SendPacket),
             RECEIVE_PACKET_EVENT => Ok(IbcEventType::ReceivePacket),
             WRITE_ACK_EVENT => Ok(IbcEventType::WriteAck),
@@ -207,6 +243,7 @@ pub enum IbcEvent {
     CreateClient(ClientEvents::CreateClient),
     UpdateClient(ClientEvents::UpdateClient),
     UpgradeClient(ClientEvents::UpgradeClient),
+    RecoverClient(ClientEvents::RecoverClient),
     ClientMisbehaviour(ClientEvents::ClientMisbehaviour),
 
     OpenInitConnection(ConnectionEvents::OpenInit),
@@ -220,6 +257,13 @@ pub enum IbcEvent {
     OpenConfirmChannel(ChannelEvents::OpenConfirm),
     CloseInitChannel(ChannelEvents::CloseInit),
     CloseConfirmChannel(ChannelEvents::CloseConfirm),
+    UpgradeInitChannel(ChannelEvents::UpgradeInit),
+    UpgradeTryChannel(ChannelEvents::UpgradeTry),
+    UpgradeAckChannel(ChannelEvents::UpgradeAck),
+    UpgradeConfirmChannel(ChannelEvents::UpgradeConfirm),
+    UpgradeOpenChannel(ChannelEvents::UpgradeOpen),
+    UpgradeTimeoutChannel(ChannelEvents::UpgradeTimeout),
+    UpgradeCancelChannel(ChannelEvents::UpgradeCancel),
 
     SendPacket(ChannelEvents::SendPacket),
     ReceivePacket(ChannelEvents::ReceivePacket),
@@ -239,6 +283,7 @@ impl TryFrom<IbcEvent> for abci::Event {
             IbcEvent::CreateClient(event) => event.into(),
             IbcEvent::UpdateClient(event) => event.into(),
             IbcEvent::UpgradeClient(event) => event.into(),
+            IbcEvent::RecoverClient(event) => event.into(),
             IbcEvent::ClientMisbehaviour(event) => event.into(),
             IbcEvent::OpenInitConnection(event) => event.into(),
             IbcEvent::OpenTryConnection(event) => event.into(),
@@ -250,6 +295,13 @@ impl TryFrom<IbcEvent> for abci::Event {
             IbcEvent::OpenConfirmChannel(event) => event.into(),
             IbcEvent::CloseInitChannel(event) => event.into(),
             IbcEvent::CloseConfirmChannel(event) => event.into(),
+            IbcEvent::UpgradeInitChannel(event) => event.into(),
+            IbcEvent::UpgradeTryChannel(event) => event.into(),
+            IbcEvent::UpgradeAckChannel(event) => event.into(),
+            IbcEvent::UpgradeConfirmChannel(event) => event.into(),
+            IbcEvent::UpgradeOpenChannel(event) => event.into(),
+            IbcEvent::UpgradeTimeoutChannel(event) => event.into(),
+            IbcEvent::UpgradeCancelChannel(event) => event.into(),
             IbcEvent::SendPacket(event) => event.try_into().map_err(Error::Channel)?,
             IbcEvent::ReceivePacket(event) => event.try_into().map_err(Error::Channel)?,
             IbcEvent::WriteAcknowledgement(event) => event.try_into().map_err(Error::Channel)?,
@@ -268,6 +320,7 @@ impl IbcEvent {
             IbcEvent::UpdateClient(_) => IbcEventType::UpdateClient,
             IbcEvent::ClientMisbehaviour(_) => IbcEventType::ClientMisbehaviour,
             IbcEvent::UpgradeClient(_) => IbcEventType::UpgradeClient,
+            IbcEvent::RecoverClient(_) => IbcEventType::RecoverClient,
             IbcEvent::OpenInitConnection(_) => IbcEventType::OpenInitConnection,
             IbcEvent::OpenTryConnection(_) => IbcEventType::OpenTryConnection,
             IbcEvent::OpenAckConnection(_) => IbcEventType::OpenAckConnection,
@@ -278,6 +331,13 @@ impl IbcEvent {
             IbcEvent::OpenConfirmChannel(_) => IbcEventType::OpenConfirmChannel,
             IbcEvent::CloseInitChannel(_) => IbcEventType::CloseInitChannel,
             IbcEvent::CloseConfirmChannel(_) => IbcEventType::CloseConfirmChannel,
+            IbcEvent::UpgradeInitChannel(_) => IbcEventType::UpgradeInitChannel,
+            IbcEvent::UpgradeTryChannel(_) => IbcEventType::UpgradeTryChannel,
+            IbcEvent::UpgradeAckChannel(_) => IbcEventType::UpgradeAckChannel,
+            IbcEvent::UpgradeConfirmChannel(_) => IbcEventType::UpgradeConfirmChannel,
+            IbcEvent::UpgradeOpenChannel(_) => IbcEventType::UpgradeOpenChannel,
+            IbcEvent::UpgradeTimeoutChannel(_) => IbcEventType::UpgradeTimeoutChannel,
+            IbcEvent::UpgradeCancelChannel(_) => IbcEventType::UpgradeCancelChannel,
             IbcEvent::SendPacket(_) => IbcEventType::SendPacket,
             IbcEvent::ReceivePacket(_) => IbcEventType::ReceivePacket,
             IbcEvent::WriteAcknowledgement(_) => IbcEventType::WriteAck,
@@ -0,0 +1,158 @@
+//! Cryptographic primitives which the host provides to light clients, for verifying the headers
+//! and the proofs of counterparty chains.
+//!
+//! Hosts may supply their own implementation, for example to call into native functions of their
+//! runtime rather than to run the cryptography in it.
+
+use crate::prelude::*;
+
+use core::fmt::Debug;
+
+use ed25519_consensus::{Signature, VerificationKey};
+
+/// The hashing and signature verification functions of the host.
+///
+/// It extends the host functions of `ics23`, which are used to verify Merkle proofs, with the
+/// signature schemes of Tendermint validators.
+pub trait HostFunctionsProvider:
+    ics23::HostFunctionsProvider + Clone + Debug + Default + PartialEq + Eq + Send + Sync + 'static
+{
+    /// Verifies an ed25519 `signature` of `message` by the signer with `public_key`.
+    fn ed25519_verify(signature: &[u8], message: &[u8], public_key: &[u8]) -> bool;
+
+    /// Verifies a secp256k1 `signature` of `message` by the signer with `public_key`, which is
+    /// given in SEC1 encoding.
+    fn secp256k1_verify(signature: &[u8], message: &[u8], public_key: &[u8]) -> bool;
+
+    /// Verifies a batch of ed25519 signatures, given as `(signature, message, public_key)`
+    /// triples, and returns whether they are all valid.
+    ///
+    /// A batch which fails verification may still hold valid signatures, so callers fall back to
+    /// `ed25519_verify` to find out which ones are invalid. By default, the signatures are
+    /// verified one by one.
+    fn ed25519_batch_verify(batch: &[(&[u8], &[u8], &[u8])]) -> bool {
+        batch.iter().all(|(signature, message, public_key)| {
+            Self::ed25519_verify(signature, message, public_key)
+        })
+    }
+}
+
+/// Host functions which run in software, with the implementations used by `ics23`.
+///
+/// ed25519 signatures are verified with `ed25519-consensus`, which follows the ZIP-215 validation
+/// rules. Unlike the verification of `ed25519-dalek`, these rules are the same for single and
+/// batch verification, so that whether a commit verifies never depends on how it is verified.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HostFunctionsManager;
+
+impl ics23::HostFunctionsProvider for HostFunctionsManager {
+    fn sha2_256(message: &[u8]) -> [u8; 32] {
+        ics23::HostFunctionsManager::sha2_256(message)
+    }
+
+    fn sha2_512(message: &[u8]) -> [u8; 64] {
+        ics23::HostFunctionsManager::sha2_512(message)
+    }
+
+    fn sha2_512_truncated(message: &[u8]) -> [u8; 32] {
+        ics23::HostFunctionsManager::sha2_512_truncated(message)
+    }
+
+    fn sha3_512(message: &[u8]) -> [u8; 64] {
+        ics23::HostFunctionsManager::sha3_512(message)
+    }
+
+    fn ripemd160(message: &[u8]) -> [u8; 20] {
+        ics23::HostFunctionsManager::ripemd160(message)
+    }
+}
+
+impl HostFunctionsProvider for HostFunctionsManager {
+    fn ed25519_verify(signature: &[u8], message: &[u8], public_key: &[u8]) -> bool {
+        match (
+            Signature::try_from(signature),
+            VerificationKey::try_from(public_key),
+        ) {
+            (Ok(signature), Ok(public_key)) => public_key.verify(&signature, message).is_ok(),
+            _ => false,
+        }
+    }
+
+    /// With the `secp256k1` feature, the ECDSA signature, in its 64 bytes compact encoding, is
+    /// verified over the SHA-256 hash of `message` with `k256`, as `tendermint` does.
+    #[cfg(feature = "secp256k1")]
+    fn secp256k1_verify(signature: &[u8], message: &[u8], public_key: &[u8]) -> bool {
+        use k256::ecdsa::{signature::Verifier, Signature, VerifyingKey};
+
+        match (
+            Signature::try_from(signature),
+            VerifyingKey::from_sec1_bytes(public_key),
+        ) {
+            (Ok(signature), Ok(public_key)) => public_key.verify(message, &signature).is_ok(),
+            _ => false,
+        }
+    }
+
+    /// Without the `secp256k1` feature, secp256k1 signatures are always rejected. Hosts whose
+    /// counterparties have secp256k1 validators must enable the feature, or supply their own
+    /// host functions.
+    #[cfg(not(feature = "secp256k1"))]
+    fn secp256k1_verify(_signature: &[u8], _message: &[u8], _public_key: &[u8]) -> bool {
+        false
+    }
+
+    /// With the `ed25519-batch` feature, the signatures are verified at once with the ZIP-215
+    /// batch verifier of `ed25519-consensus`, which accepts a batch if and only if each of its
+    /// signatures passes `ed25519_verify`. The random coefficients of the batch only bound the
+    /// (negligible) probability of accepting an invalid batch, and never make a valid batch fail.
+    #[cfg(feature = "ed25519-batch")]
+    fn ed25519_batch_verify(batch: &[(&[u8], &[u8], &[u8])]) -> bool {
+        use ed25519_consensus::{batch::Verifier, VerificationKeyBytes};
+
+        let mut verifier = Verifier::new();
+        for (signature, message, public_key) in batch {
+            match (
+                Signature::try_from(*signature),
+                VerificationKeyBytes::try_from(*public_key),
+            ) {
+                (Ok(signature), Ok(public_key)) => {
+                    verifier.queue((public_key, signature, *message))
+                }
+                _ => return false,
+            }
+        }
+
+        verifier.verify(rand_core::OsRng).is_ok()
+    }
+}
+
+#[cfg(all(test, feature = "secp256k1"))]
+mod tests {
+    use super::*;
+
+    use k256::ecdsa::{signature::Signer, Signature, SigningKey};
+
+    #[test]
+    fn secp256k1_verify() {
+        let signing_key = SigningKey::from_bytes(&[1; 32]).unwrap();
+        let public_key = signing_key.verifying_key().to_bytes();
+        let message = b"sign bytes";
+        let signature: Signature = signing_key.sign(message);
+
+        assert!(HostFunctionsManager::secp256k1_verify(
+            signature.as_ref(),
+            message,
+            public_key.as_slice()
+        ));
+        assert!(!HostFunctionsManager::secp256k1_verify(
+            signature.as_ref(),
+            b"other sign bytes",
+            public_key.as_slice()
+        ));
+        assert!(!HostFunctionsManager::secp256k1_verify(
+            &[0; 64],
+            message,
+            public_key.as_slice()
+        ));
+    }
+}
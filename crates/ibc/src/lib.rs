@@ -53,6 +53,7 @@ pub mod dynamic_typing;
 mod erased;
 pub mod events;
 pub mod handler;
+pub mod host_functions;
 pub mod signer;
 pub mod timestamp;
 pub mod tx_msg;
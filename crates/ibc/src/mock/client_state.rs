@@ -2,29 +2,25 @@ use crate::prelude::*;
 
 use alloc::collections::btree_map::BTreeMap as HashMap;
 use core::time::Duration;
-use dyn_clone::clone_box;
 use ibc_proto::ibc::core::commitment::v1::MerkleProof;
 
 use ibc_proto::google::protobuf::Any;
 use ibc_proto::ibc::mock::ClientState as RawMockClientState;
 use ibc_proto::protobuf::Protobuf;
 
-use crate::core::ics02_client::client_state::{ClientState, UpdatedState, UpgradeOptions};
+use crate::core::ics02_client::client_state::{
+    downcast_client_state, ClientState, UpdatedState, UpgradeOptions,
+};
 use crate::core::ics02_client::client_type::ClientType;
 use crate::core::ics02_client::consensus_state::ConsensusState;
 use crate::core::ics02_client::context::ClientReader;
 use crate::core::ics02_client::error::ClientError;
-use crate::core::ics03_connection::connection::ConnectionEnd;
-use crate::core::ics04_channel::channel::ChannelEnd;
-use crate::core::ics04_channel::commitment::{AcknowledgementCommitment, PacketCommitment};
 use crate::core::ics04_channel::context::ChannelReader;
-use crate::core::ics04_channel::packet::Sequence;
 use crate::core::ics23_commitment::commitment::{
     CommitmentPrefix, CommitmentProofBytes, CommitmentRoot,
 };
 use crate::core::ics23_commitment::merkle::apply_prefix;
-use crate::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId};
-use crate::core::ics24_host::path::ClientConsensusStatePath;
+use crate::core::ics24_host::identifier::{ChainId, ClientId};
 use crate::core::ics24_host::Path;
 use crate::mock::client_state::client_type as mock_client_type;
 use crate::mock::consensus_state::MockConsensusState;
@@ -285,131 +281,91 @@ impl ClientState for MockClientState {
 
     fn verify_upgrade_and_update_state(
         &self,
-        consensus_state: Any,
+        upgraded_client_state: Any,
+        upgraded_consensus_state: Any,
         _proof_upgrade_client: MerkleProof,
         _proof_upgrade_consensus_state: MerkleProof,
+        _root: &CommitmentRoot,
     ) -> Result<UpdatedState, ClientError> {
-        let consensus_state = MockConsensusState::try_from(consensus_state)?;
+        let client_state = MockClientState::try_from(upgraded_client_state)?;
+        let consensus_state = MockConsensusState::try_from(upgraded_consensus_state)?;
         Ok(UpdatedState {
-            client_state: clone_box(self),
+            client_state: client_state.into_box(),
             consensus_state: consensus_state.into_box(),
         })
     }
 
-    fn verify_client_consensus_state(
+    fn check_substitute_and_update_state(
         &self,
-        _height: Height,
-        prefix: &CommitmentPrefix,
-        _proof: &CommitmentProofBytes,
-        _root: &CommitmentRoot,
-        client_id: &ClientId,
-        consensus_height: Height,
-        _expected_consensus_state: &dyn ConsensusState,
-    ) -> Result<(), ClientError> {
-        let client_prefixed_path = Path::ClientConsensusState(ClientConsensusStatePath {
-            client_id: client_id.clone(),
-            epoch: consensus_height.revision_number(),
-            height: consensus_height.revision_height(),
-        })
-        .to_string();
-
-        let _path = apply_prefix(prefix, vec![client_prefixed_path]);
-
-        Ok(())
+        substitute_client_state: &dyn ClientState,
+    ) -> Result<Box<dyn ClientState>, ClientError> {
+        let substitute_client_state =
+            downcast_client_state::<MockClientState>(substitute_client_state).ok_or_else(|| {
+                ClientError::ClientArgsTypeMismatch {
+                    client_type: mock_client_type(),
+                }
+            })?;
+
+        Ok(MockClientState::new(substitute_client_state.header).into_box())
     }
 
-    fn verify_connection_state(
-        &self,
-        _height: Height,
-        _prefix: &CommitmentPrefix,
-        _proof: &CommitmentProofBytes,
-        _root: &CommitmentRoot,
-        _connection_id: &ConnectionId,
-        _expected_connection_end: &ConnectionEnd,
-    ) -> Result<(), ClientError> {
+    fn validate_proof_height(&self, _proof_height: Height) -> Result<(), ClientError> {
         Ok(())
     }
 
-    fn verify_channel_state(
-        &self,
-        _height: Height,
-        _prefix: &CommitmentPrefix,
+    fn verify_membership(
+        &mut self,
+        _ctx: &dyn ChannelReader,
+        prefix: &CommitmentPrefix,
         _proof: &CommitmentProofBytes,
         _root: &CommitmentRoot,
-        _port_id: &PortId,
-        _channel_id: &ChannelId,
-        _expected_channel_end: &ChannelEnd,
+        path: Path,
+        _value: Vec<u8>,
     ) -> Result<(), ClientError> {
-        Ok(())
-    }
+        let _path = apply_prefix(prefix, vec![path.to_string()]);
 
-    fn verify_client_full_state(
-        &self,
-        _height: Height,
-        _prefix: &CommitmentPrefix,
-        _proof: &CommitmentProofBytes,
-        _root: &CommitmentRoot,
-        _client_id: &ClientId,
-        _expected_client_state: Any,
-    ) -> Result<(), ClientError> {
         Ok(())
     }
 
-    fn verify_packet_data(
-        &self,
+    fn verify_non_membership(
+        &mut self,
         _ctx: &dyn ChannelReader,
-        _height: Height,
-        _connection_end: &ConnectionEnd,
+        prefix: &CommitmentPrefix,
         _proof: &CommitmentProofBytes,
         _root: &CommitmentRoot,
-        _port_id: &PortId,
-        _channel_id: &ChannelId,
-        _sequence: Sequence,
-        _commitment: PacketCommitment,
+        path: Path,
     ) -> Result<(), ClientError> {
-        Ok(())
-    }
+        let _path = apply_prefix(prefix, vec![path.to_string()]);
 
-    fn verify_packet_acknowledgement(
-        &self,
-        _ctx: &dyn ChannelReader,
-        _height: Height,
-        _connection_end: &ConnectionEnd,
-        _proof: &CommitmentProofBytes,
-        _root: &CommitmentRoot,
-        _port_id: &PortId,
-        _channel_id: &ChannelId,
-        _sequence: Sequence,
-        _ack: AcknowledgementCommitment,
-    ) -> Result<(), ClientError> {
         Ok(())
     }
 
-    fn verify_next_sequence_recv(
-        &self,
-        _ctx: &dyn ChannelReader,
-        _height: Height,
-        _connection_end: &ConnectionEnd,
+    #[cfg(feature = "val_exec_ctx")]
+    fn new_verify_membership(
+        &mut self,
+        _ctx: &dyn ValidationContext,
+        prefix: &CommitmentPrefix,
         _proof: &CommitmentProofBytes,
         _root: &CommitmentRoot,
-        _port_id: &PortId,
-        _channel_id: &ChannelId,
-        _sequence: Sequence,
+        path: Path,
+        _value: Vec<u8>,
     ) -> Result<(), ClientError> {
+        let _path = apply_prefix(prefix, vec![path.to_string()]);
+
         Ok(())
     }
 
-    fn verify_packet_receipt_absence(
-        &self,
-        _ctx: &dyn ChannelReader,
-        _height: Height,
-        _connection_end: &ConnectionEnd,
+    #[cfg(feature = "val_exec_ctx")]
+    fn new_verify_non_membership(
+        &mut self,
+        _ctx: &dyn ValidationContext,
+        prefix: &CommitmentPrefix,
         _proof: &CommitmentProofBytes,
         _root: &CommitmentRoot,
-        _port_id: &PortId,
-        _channel_id: &ChannelId,
-        _sequence: Sequence,
+        path: Path,
     ) -> Result<(), ClientError> {
+        let _path = apply_prefix(prefix, vec![path.to_string()]);
+
         Ok(())
     }
 }
@@ -18,36 +18,53 @@ use tracing::debug;
 
 use crate::clients::ics07_tendermint::client_state::test_util::get_dummy_tendermint_client_state;
 use crate::clients::ics07_tendermint::client_state::ClientState as TmClientState;
+use crate::clients::ics08_wasm::client_state::{
+    ClientState as WasmClientState, WASM_CLIENT_STATE_TYPE_URL,
+};
+use crate::clients::ics08_wasm::client_type as wasm_client_type;
+use crate::clients::ics08_wasm::consensus_state::{
+    ConsensusState as WasmConsensusState, WASM_CONSENSUS_STATE_TYPE_URL,
+};
 use crate::core::ics02_client::client_state::ClientState;
 use crate::core::ics02_client::client_type::ClientType;
 use crate::core::ics02_client::consensus_state::ConsensusState;
 use crate::core::ics02_client::context::{ClientKeeper, ClientReader};
 use crate::core::ics02_client::error::ClientError;
 use crate::core::ics02_client::header::Header;
+use crate::core::ics02_client::params::ClientParams;
+use crate::core::ics02_client::registry::ClientTypeRegistry;
 use crate::core::ics03_connection::connection::ConnectionEnd;
 use crate::core::ics03_connection::context::{ConnectionKeeper, ConnectionReader};
 use crate::core::ics03_connection::error::ConnectionError;
+use crate::core::ics03_connection::params::ConnectionParams;
+use crate::core::ics03_connection::version::{
+    get_compatible_versions, Version as ConnectionVersion,
+};
 use crate::core::ics04_channel::channel::ChannelEnd;
 use crate::core::ics04_channel::commitment::{AcknowledgementCommitment, PacketCommitment};
 use crate::core::ics04_channel::context::{ChannelKeeper, ChannelReader};
 use crate::core::ics04_channel::error::{ChannelError, PacketError};
 use crate::core::ics04_channel::packet::{Receipt, Sequence};
+use crate::core::ics04_channel::upgrade::{ErrorReceipt, Upgrade};
 use crate::core::ics05_port::context::PortReader;
 use crate::core::ics05_port::error::PortError;
 use crate::core::ics23_commitment::commitment::CommitmentPrefix;
+use crate::core::ics23_commitment::specs::ProofSpecs;
 use crate::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortId};
 use crate::core::ics26_routing::context::{Module, ModuleId, Router, RouterBuilder, RouterContext};
 use crate::core::ics26_routing::handler::{deliver, dispatch, MsgReceipt};
 use crate::core::ics26_routing::msgs::MsgEnvelope;
 use crate::events::IbcEvent;
 use crate::mock::client_state::{
-    client_type as mock_client_type, MockClientRecord, MockClientState,
+    client_type as mock_client_type, MockClientRecord, MockClientState, MOCK_CLIENT_STATE_TYPE_URL,
 };
-use crate::mock::consensus_state::MockConsensusState;
-use crate::mock::header::MockHeader;
+use crate::mock::consensus_state::{MockConsensusState, MOCK_CONSENSUS_STATE_TYPE_URL};
+use crate::mock::header::{MockHeader, MOCK_HEADER_TYPE_URL};
 use crate::mock::host::{HostBlock, HostType};
 use crate::mock::ics18_relayer::context::RelayerContext;
 use crate::mock::ics18_relayer::error::RelayerError;
+use crate::mock::misbehaviour::{Misbehaviour as MockMisbehaviour, MOCK_MISBEHAVIOUR_TYPE_URL};
+use crate::mock::wasm_engine::MockWasmEngine;
 use crate::signer::Signer;
 use crate::timestamp::Timestamp;
 use crate::Height;
@@ -81,6 +98,12 @@ pub struct MockContext {
     /// ICS26 router impl
     router: MockRouter,
 
+    /// The client types supported by this context.
+    client_type_registry: ClientTypeRegistry,
+
+    /// The connection versions supported by this context.
+    connection_versions: Vec<ConnectionVersion>,
+
     /// To implement ValidationContext Router
     #[cfg(feature = "val_exec_ctx")]
     new_router: BTreeMap<ModuleId, Arc<dyn Module>>,
@@ -100,6 +123,44 @@ impl Default for MockContext {
     }
 }
 
+/// The clients of this crate, along with the mock client and a Wasm client running on the mock
+/// engine.
+fn mock_client_type_registry() -> ClientTypeRegistry {
+    let mut registry = ClientTypeRegistry::default();
+
+    registry
+        .register_client_state::<MockClientState>(mock_client_type(), MOCK_CLIENT_STATE_TYPE_URL)
+        .expect("mock client state type url is not registered");
+    registry
+        .register_consensus_state::<MockConsensusState>(
+            mock_client_type(),
+            MOCK_CONSENSUS_STATE_TYPE_URL,
+        )
+        .expect("mock consensus state type url is not registered");
+    registry
+        .register_header::<MockHeader>(mock_client_type(), MOCK_HEADER_TYPE_URL)
+        .expect("mock header type url is not registered");
+    registry
+        .register_misbehaviour::<MockMisbehaviour>(mock_client_type(), MOCK_MISBEHAVIOUR_TYPE_URL)
+        .expect("mock misbehaviour type url is not registered");
+
+    registry
+        .register_client_state_with(wasm_client_type(), WASM_CLIENT_STATE_TYPE_URL, |any| {
+            Ok(WasmClientState::try_from(any)?
+                .with_engine(Arc::new(MockWasmEngine))
+                .into_box())
+        })
+        .expect("wasm client state type url is not registered");
+    registry
+        .register_consensus_state::<WasmConsensusState>(
+            wasm_client_type(),
+            WASM_CONSENSUS_STATE_TYPE_URL,
+        )
+        .expect("wasm consensus state type url is not registered");
+
+    registry
+}
+
 /// A manual clone impl is provided because the tests are oblivious to the fact that the `ibc_store`
 /// is a shared ptr.
 impl Clone for MockContext {
@@ -118,6 +179,8 @@ impl Clone for MockContext {
                     block_time: self.block_time,
                     ibc_store,
                     router: self.router.clone(),
+                    client_type_registry: self.client_type_registry.clone(),
+                    connection_versions: self.connection_versions.clone(),
                 }
             } else {
                 Self {
@@ -128,6 +191,8 @@ impl Clone for MockContext {
                     block_time: self.block_time,
                     ibc_store,
                     router: self.router.clone(),
+                    client_type_registry: self.client_type_registry.clone(),
+                    connection_versions: self.connection_versions.clone(),
                     new_router: self.new_router.clone(),
                 }
             }
@@ -192,8 +257,15 @@ impl MockContext {
                         })
                         .collect(),
                     block_time,
-                    ibc_store: Arc::new(Mutex::new(MockIbcStore::default())),
+                    ibc_store: Arc::new(Mutex::new(MockIbcStore {
+                        connection_params: ConnectionParams {
+                            max_expected_time_per_block: block_time,
+                        },
+                        ..Default::default()
+                    })),
                     router: Default::default(),
+                    client_type_registry: mock_client_type_registry(),
+                    connection_versions: get_compatible_versions(),
                 }
             } else {
                 MockContext {
@@ -216,8 +288,15 @@ impl MockContext {
                         })
                         .collect(),
                     block_time,
-                    ibc_store: Arc::new(Mutex::new(MockIbcStore::default())),
+                    ibc_store: Arc::new(Mutex::new(MockIbcStore {
+                        connection_params: ConnectionParams {
+                            max_expected_time_per_block: block_time,
+                        },
+                        ..Default::default()
+                    })),
                     router: Default::default(),
+                    client_type_registry: mock_client_type_registry(),
+                    connection_versions: get_compatible_versions(),
                     new_router: BTreeMap::new(),
                 }
             }
@@ -299,7 +378,7 @@ impl MockContext {
             .lock()
             .clients
             .insert(client_id.clone(), client_record);
-        self
+        self.with_client_processed_metadata(client_id, &[cs_height])
     }
 
     pub fn with_client_parametrized_history(
@@ -385,6 +464,26 @@ impl MockContext {
             .lock()
             .clients
             .insert(client_id.clone(), client_record);
+        self.with_client_processed_metadata(client_id, &[prev_cs_height, cs_height])
+    }
+
+    /// Records the current host time and height as the time and height at which the consensus
+    /// states of the client at `heights` were processed, as if the client had just been updated
+    /// to them.
+    fn with_client_processed_metadata(self, client_id: &ClientId, heights: &[Height]) -> Self {
+        let processed_time = ClientReader::host_timestamp(&self).unwrap();
+        let processed_height = self.latest_height();
+        {
+            let mut ibc_store = self.ibc_store.lock();
+            for height in heights {
+                ibc_store
+                    .client_processed_times
+                    .insert((client_id.clone(), *height), processed_time);
+                ibc_store
+                    .client_processed_heights
+                    .insert((client_id.clone(), *height), processed_height);
+            }
+        }
         self
     }
 
@@ -417,6 +516,21 @@ impl MockContext {
         self
     }
 
+    pub fn with_channel_upgrade(
+        self,
+        port_id: PortId,
+        chan_id: ChannelId,
+        upgrade: Upgrade,
+    ) -> Self {
+        self.ibc_store
+            .lock()
+            .channel_upgrades
+            .entry(port_id)
+            .or_default()
+            .insert(chan_id, upgrade);
+        self
+    }
+
     pub fn with_send_sequence(
         self,
         port_id: PortId,
@@ -483,6 +597,23 @@ impl MockContext {
         }
     }
 
+    /// Replaces the client types supported by this context, e.g. to register another client.
+    pub fn with_client_type_registry(self, client_type_registry: ClientTypeRegistry) -> Self {
+        Self {
+            client_type_registry,
+            ..self
+        }
+    }
+
+    /// Replaces the connection versions supported by this context, e.g. to offer custom features
+    /// or to restrict the channel orderings.
+    pub fn with_connection_versions(self, connection_versions: Vec<ConnectionVersion>) -> Self {
+        Self {
+            connection_versions,
+            ..self
+        }
+    }
+
     pub fn with_packet_commitment(
         self,
         port_id: PortId,
@@ -505,6 +636,24 @@ impl MockContext {
         Self { router, ..self }
     }
 
+    /// Allows `authority` to recover frozen or expired clients in this context.
+    pub fn with_client_recovery_authority(self, authority: Signer) -> Self {
+        self.ibc_store.lock().client_recovery_authority = Some(authority);
+        self
+    }
+
+    /// Replaces the parameters of the client submodule, e.g. to restrict the allowed clients.
+    pub fn with_client_params(self, params: ClientParams) -> Self {
+        self.ibc_store.lock().client_params = params;
+        self
+    }
+
+    /// Replaces the parameters of the connection submodule.
+    pub fn with_connection_params(self, params: ConnectionParams) -> Self {
+        self.ibc_store.lock().connection_params = params;
+        self
+    }
+
     /// Accessor for a block of the local (host) chain from this context.
     /// Returns `None` if the block at the requested height does not exist.
     pub fn host_block(&self, target_height: &Height) -> Option<&HostBlock> {
@@ -683,6 +832,27 @@ pub struct MockIbcStore {
 
     // Used by unordered channel
     pub packet_receipt: PortChannelIdMap<BTreeMap<Sequence, Receipt>>,
+
+    /// The events emitted through `ExecutionContext::emit_ibc_event`.
+    pub events: Vec<IbcEvent>,
+
+    /// The messages logged through `ExecutionContext::log_message`.
+    pub logs: Vec<String>,
+
+    /// The signer allowed to recover frozen or expired clients, if any.
+    pub client_recovery_authority: Option<Signer>,
+
+    /// The parameters of the client submodule.
+    pub client_params: ClientParams,
+
+    /// The parameters of the connection submodule.
+    pub connection_params: ConnectionParams,
+
+    /// The upgrades in progress of the channels.
+    pub channel_upgrades: PortChannelIdMap<Upgrade>,
+
+    /// The error receipts of the last aborted upgrade of the channels.
+    pub channel_upgrade_errors: PortChannelIdMap<ErrorReceipt>,
 }
 
 #[derive(Default)]
@@ -979,8 +1149,8 @@ impl ChannelReader for MockContext {
         Ok(self.ibc_store.lock().channel_ids_counter)
     }
 
-    fn max_expected_time_per_block(&self) -> Duration {
-        self.block_time
+    fn connection_params(&self) -> Result<ConnectionParams, ChannelError> {
+        ConnectionReader::connection_params(self).map_err(ChannelError::Connection)
     }
 }
 
@@ -1200,11 +1370,89 @@ impl ConnectionReader for MockContext {
         ClientReader::host_consensus_state(self, height).map_err(ConnectionError::Client)
     }
 
+    fn get_compatible_versions(&self) -> Vec<ConnectionVersion> {
+        self.connection_versions.clone()
+    }
+
     fn connection_counter(&self) -> Result<u64, ConnectionError> {
         Ok(self.ibc_store.lock().connection_ids_counter)
     }
 
-    fn validate_self_client(&self, _counterparty_client_state: Any) -> Result<(), ConnectionError> {
+    fn connection_params(&self) -> Result<ConnectionParams, ConnectionError> {
+        Ok(self.ibc_store.lock().connection_params.clone())
+    }
+
+    /// Checks that the counterparty's client of this host is a client of the host type, which
+    /// tracks this chain, is not frozen and is not ahead of the host. The client of a synthetic
+    /// Tendermint host must also use the default proof specs.
+    fn validate_self_client(&self, counterparty_client_state: Any) -> Result<(), ConnectionError> {
+        let (latest_height, is_frozen) =
+            match self.host_chain_type {
+                HostType::Mock => {
+                    let client_state = MockClientState::try_from(counterparty_client_state)
+                        .map_err(|_| ConnectionError::InvalidClientState {
+                            reason: "client must be a mock client".to_string(),
+                        })?;
+
+                    (client_state.latest_height(), client_state.is_frozen())
+                }
+                HostType::SyntheticTendermint => {
+                    let client_state: TmClientState =
+                        TmClientState::try_from(counterparty_client_state).map_err(|_| {
+                            ConnectionError::InvalidClientState {
+                                reason: "client must be a tendermint client".to_string(),
+                            }
+                        })?;
+
+                    if client_state.chain_id != self.host_chain_id {
+                        return Err(ConnectionError::InvalidClientState {
+                            reason: format!(
+                                "invalid chain-id. expected: {}, got: {}",
+                                self.host_chain_id, client_state.chain_id
+                            ),
+                        });
+                    }
+
+                    if client_state.proof_specs != ProofSpecs::default() {
+                        return Err(ConnectionError::InvalidClientState {
+                            reason: format!(
+                                "client has invalid proof specs. expected: {:?}, got: {:?}",
+                                ProofSpecs::default(),
+                                client_state.proof_specs
+                            ),
+                        });
+                    }
+
+                    (client_state.latest_height(), client_state.is_frozen())
+                }
+            };
+
+        if is_frozen {
+            return Err(ConnectionError::InvalidClientState {
+                reason: "client is frozen".to_string(),
+            });
+        }
+
+        let self_revision_number = self.host_chain_id.version();
+        if self_revision_number != latest_height.revision_number() {
+            return Err(ConnectionError::InvalidClientState {
+                reason: format!(
+                    "client is not in the same revision as the chain. expected: {}, got: {}",
+                    self_revision_number,
+                    latest_height.revision_number()
+                ),
+            });
+        }
+
+        let host_height = self.latest_height();
+        if latest_height >= host_height {
+            return Err(ConnectionError::InvalidClientState {
+                reason: format!(
+                    "client has latest height {latest_height} greater than or equal to chain height {host_height}"
+                ),
+            });
+        }
+
         Ok(())
     }
 }
@@ -1237,6 +1485,11 @@ impl ConnectionKeeper for MockContext {
     fn increase_connection_counter(&mut self) {
         self.ibc_store.lock().connection_ids_counter += 1;
     }
+
+    fn store_connection_params(&mut self, params: ConnectionParams) -> Result<(), ConnectionError> {
+        self.ibc_store.lock().connection_params = params;
+        Ok(())
+    }
 }
 
 impl ClientReader for MockContext {
@@ -1265,16 +1518,8 @@ impl ClientReader for MockContext {
         }
     }
 
-    fn decode_client_state(&self, client_state: Any) -> Result<Box<dyn ClientState>, ClientError> {
-        if let Ok(client_state) = TmClientState::try_from(client_state.clone()) {
-            Ok(client_state.into_box())
-        } else if let Ok(client_state) = MockClientState::try_from(client_state.clone()) {
-            Ok(client_state.into_box())
-        } else {
-            Err(ClientError::UnknownClientStateType {
-                client_state_type: client_state.type_url,
-            })
-        }
+    fn client_type_registry(&self) -> &ClientTypeRegistry {
+        &self.client_type_registry
     }
 
     fn consensus_state(
@@ -1359,6 +1604,21 @@ impl ClientReader for MockContext {
         Ok(None)
     }
 
+    fn consensus_state_heights(&self, client_id: &ClientId) -> Result<Vec<Height>, ClientError> {
+        let ibc_store = self.ibc_store.lock();
+        let client_record =
+            ibc_store
+                .clients
+                .get(client_id)
+                .ok_or_else(|| ClientError::ClientNotFound {
+                    client_id: client_id.clone(),
+                })?;
+
+        let mut heights: Vec<Height> = client_record.consensus_states.keys().cloned().collect();
+        heights.sort();
+        Ok(heights)
+    }
+
     fn host_height(&self) -> Result<Height, ClientError> {
         Ok(self.latest_height())
     }
@@ -1390,6 +1650,19 @@ impl ClientReader for MockContext {
     fn client_counter(&self) -> Result<u64, ClientError> {
         Ok(self.ibc_store.lock().client_ids_counter)
     }
+
+    fn client_params(&self) -> Result<ClientParams, ClientError> {
+        Ok(self.ibc_store.lock().client_params.clone())
+    }
+
+    fn validate_client_recovery_authority(&self, signer: &Signer) -> Result<(), ClientError> {
+        match &self.ibc_store.lock().client_recovery_authority {
+            Some(authority) if authority == signer => Ok(()),
+            _ => Err(ClientError::UnauthorizedClientRecovery {
+                signer: signer.clone(),
+            }),
+        }
+    }
 }
 
 impl ClientKeeper for MockContext {
@@ -1453,10 +1726,26 @@ impl ClientKeeper for MockContext {
         Ok(())
     }
 
+    fn delete_consensus_state(
+        &mut self,
+        client_id: ClientId,
+        height: Height,
+    ) -> Result<(), ClientError> {
+        if let Some(client_record) = self.ibc_store.lock().clients.get_mut(&client_id) {
+            let _ = client_record.consensus_states.remove(&height);
+        }
+        Ok(())
+    }
+
     fn increase_client_counter(&mut self) {
         self.ibc_store.lock().client_ids_counter += 1
     }
 
+    fn store_client_params(&mut self, params: ClientParams) -> Result<(), ClientError> {
+        self.ibc_store.lock().client_params = params;
+        Ok(())
+    }
+
     fn store_update_time(
         &mut self,
         client_id: ClientId,
@@ -1484,6 +1773,32 @@ impl ClientKeeper for MockContext {
             .insert((client_id, height), host_height);
         Ok(())
     }
+
+    fn delete_update_time(
+        &mut self,
+        client_id: ClientId,
+        height: Height,
+    ) -> Result<(), ClientError> {
+        let _ = self
+            .ibc_store
+            .lock()
+            .client_processed_times
+            .remove(&(client_id, height));
+        Ok(())
+    }
+
+    fn delete_update_height(
+        &mut self,
+        client_id: ClientId,
+        height: Height,
+    ) -> Result<(), ClientError> {
+        let _ = self
+            .ibc_store
+            .lock()
+            .client_processed_heights
+            .remove(&(client_id, height));
+        Ok(())
+    }
 }
 
 impl RelayerContext for MockContext {
@@ -1526,7 +1841,27 @@ mod val_exec_ctx {
 
     use crate::core::context::ContextError;
     use crate::core::context::Router as NewRouter;
-    use crate::core::ValidationContext;
+    use crate::core::ics24_host::path::{
+        ChannelUpgradeErrorPath, ChannelUpgradePath, ClientConnectionsPath,
+        ClientConsensusStatePath, ClientStatePath, ClientTypePath, ClientUpdateHeightPath,
+        ClientUpdateTimePath, CommitmentsPath, ConnectionsPath, ReceiptsPath,
+    };
+    use crate::core::{ExecutionContext, ValidationContext};
+
+    impl MockContext {
+        /// Registers `module` against `module_id` in the router used by the
+        /// `ValidationContext` and `ExecutionContext` entrypoints.
+        pub fn add_route(
+            &mut self,
+            module_id: ModuleId,
+            module: impl Module,
+        ) -> Result<(), String> {
+            match self.new_router.insert(module_id, Arc::new(module)) {
+                None => Ok(()),
+                Some(_) => Err("Duplicate module_id".to_owned()),
+            }
+        }
+    }
 
     impl NewRouter for MockContext {
         fn get_route(&self, module_id: &ModuleId) -> Option<&dyn Module> {
@@ -1550,11 +1885,8 @@ mod val_exec_ctx {
             ClientReader::client_state(self, client_id).map_err(ContextError::ClientError)
         }
 
-        fn decode_client_state(
-            &self,
-            client_state: Any,
-        ) -> Result<Box<dyn ClientState>, ContextError> {
-            ClientReader::decode_client_state(self, client_state).map_err(ContextError::ClientError)
+        fn client_type_registry(&self) -> &ClientTypeRegistry {
+            ClientReader::client_type_registry(self)
         }
 
         fn consensus_state(
@@ -1584,10 +1916,22 @@ mod val_exec_ctx {
                 .map_err(ContextError::ClientError)
         }
 
+        fn consensus_state_heights(
+            &self,
+            client_id: &ClientId,
+        ) -> Result<Vec<Height>, ContextError> {
+            ClientReader::consensus_state_heights(self, client_id)
+                .map_err(ContextError::ClientError)
+        }
+
         fn host_height(&self) -> Result<Height, ContextError> {
             Ok(self.latest_height())
         }
 
+        fn host_timestamp(&self) -> Result<Timestamp, ContextError> {
+            ClientReader::host_timestamp(self).map_err(ContextError::ClientError)
+        }
+
         fn pending_host_consensus_state(&self) -> Result<Box<dyn ConsensusState>, ContextError> {
             ClientReader::pending_host_consensus_state(self).map_err(ContextError::ClientError)
         }
@@ -1604,15 +1948,24 @@ mod val_exec_ctx {
             ClientReader::client_counter(self).map_err(ContextError::ClientError)
         }
 
+        fn client_params(&self) -> Result<ClientParams, ContextError> {
+            ClientReader::client_params(self).map_err(ContextError::ClientError)
+        }
+
+        fn validate_client_recovery_authority(&self, signer: &Signer) -> Result<(), ContextError> {
+            ClientReader::validate_client_recovery_authority(self, signer)
+                .map_err(ContextError::ClientError)
+        }
+
         fn connection_end(&self, conn_id: &ConnectionId) -> Result<ConnectionEnd, ContextError> {
             ConnectionReader::connection_end(self, conn_id).map_err(ContextError::ConnectionError)
         }
 
         fn validate_self_client(
             &self,
-            _counterparty_client_state: Any,
+            counterparty_client_state: Any,
         ) -> Result<(), ConnectionError> {
-            Ok(())
+            ConnectionReader::validate_self_client(self, counterparty_client_state)
         }
 
         fn commitment_prefix(&self) -> CommitmentPrefix {
@@ -1623,6 +1976,14 @@ mod val_exec_ctx {
             ConnectionReader::connection_counter(self).map_err(ContextError::ConnectionError)
         }
 
+        fn connection_params(&self) -> Result<ConnectionParams, ContextError> {
+            ConnectionReader::connection_params(self).map_err(ContextError::ConnectionError)
+        }
+
+        fn get_compatible_versions(&self) -> Vec<ConnectionVersion> {
+            ConnectionReader::get_compatible_versions(self)
+        }
+
         fn channel_end(
             &self,
             port_channel_id: &(PortId, ChannelId),
@@ -1712,8 +2073,376 @@ mod val_exec_ctx {
             ChannelReader::channel_counter(self).map_err(ContextError::ChannelError)
         }
 
-        fn max_expected_time_per_block(&self) -> Duration {
-            ChannelReader::max_expected_time_per_block(self)
+        fn channel_upgrade(
+            &self,
+            port_channel_id: &(PortId, ChannelId),
+        ) -> Result<Upgrade, ContextError> {
+            self.ibc_store
+                .lock()
+                .channel_upgrades
+                .get(&port_channel_id.0)
+                .and_then(|upgrades| upgrades.get(&port_channel_id.1))
+                .cloned()
+                .ok_or_else(|| {
+                    ChannelError::UpgradeNotFound {
+                        port_id: port_channel_id.0.clone(),
+                        channel_id: port_channel_id.1.clone(),
+                    }
+                    .into()
+                })
+        }
+
+        fn channel_upgrade_error(
+            &self,
+            port_channel_id: &(PortId, ChannelId),
+        ) -> Result<ErrorReceipt, ContextError> {
+            self.ibc_store
+                .lock()
+                .channel_upgrade_errors
+                .get(&port_channel_id.0)
+                .and_then(|receipts| receipts.get(&port_channel_id.1))
+                .cloned()
+                .ok_or_else(|| {
+                    ChannelError::UpgradeErrorReceiptNotFound {
+                        port_id: port_channel_id.0.clone(),
+                        channel_id: port_channel_id.1.clone(),
+                    }
+                    .into()
+                })
+        }
+
+        fn has_inflight_packets(
+            &self,
+            port_channel_id: &(PortId, ChannelId),
+        ) -> Result<bool, ContextError> {
+            Ok(self
+                .ibc_store
+                .lock()
+                .packet_commitment
+                .get(&port_channel_id.0)
+                .and_then(|commitments| commitments.get(&port_channel_id.1))
+                .map_or(false, |commitments| !commitments.is_empty()))
+        }
+    }
+
+    impl ExecutionContext for MockContext {
+        fn store_client_type(
+            &mut self,
+            client_type_path: ClientTypePath,
+            client_type: ClientType,
+        ) -> Result<(), ContextError> {
+            ClientKeeper::store_client_type(self, client_type_path.0, client_type)
+                .map_err(ContextError::ClientError)
+        }
+
+        fn store_client_state(
+            &mut self,
+            client_state_path: ClientStatePath,
+            client_state: Box<dyn ClientState>,
+        ) -> Result<(), ContextError> {
+            ClientKeeper::store_client_state(self, client_state_path.0, client_state)
+                .map_err(ContextError::ClientError)
+        }
+
+        fn store_consensus_state(
+            &mut self,
+            consensus_state_path: ClientConsensusStatePath,
+            consensus_state: Box<dyn ConsensusState>,
+        ) -> Result<(), ContextError> {
+            let height = Height::new(consensus_state_path.epoch, consensus_state_path.height)
+                .map_err(ContextError::ClientError)?;
+            ClientKeeper::store_consensus_state(
+                self,
+                consensus_state_path.client_id,
+                height,
+                consensus_state,
+            )
+            .map_err(ContextError::ClientError)
+        }
+
+        fn delete_consensus_state(
+            &mut self,
+            consensus_state_path: ClientConsensusStatePath,
+        ) -> Result<(), ContextError> {
+            let height = Height::new(consensus_state_path.epoch, consensus_state_path.height)
+                .map_err(ContextError::ClientError)?;
+            ClientKeeper::delete_consensus_state(self, consensus_state_path.client_id, height)
+                .map_err(ContextError::ClientError)
+        }
+
+        fn increase_client_counter(&mut self) {
+            ClientKeeper::increase_client_counter(self)
+        }
+
+        fn store_client_params(&mut self, params: ClientParams) -> Result<(), ContextError> {
+            ClientKeeper::store_client_params(self, params).map_err(ContextError::ClientError)
+        }
+
+        fn store_update_time(
+            &mut self,
+            client_update_time_path: ClientUpdateTimePath,
+            timestamp: Timestamp,
+        ) -> Result<(), ContextError> {
+            let height = Height::new(
+                client_update_time_path.epoch,
+                client_update_time_path.height,
+            )
+            .map_err(ContextError::ClientError)?;
+            ClientKeeper::store_update_time(
+                self,
+                client_update_time_path.client_id,
+                height,
+                timestamp,
+            )
+            .map_err(ContextError::ClientError)
+        }
+
+        fn store_update_height(
+            &mut self,
+            client_update_height_path: ClientUpdateHeightPath,
+            host_height: Height,
+        ) -> Result<(), ContextError> {
+            let height = Height::new(
+                client_update_height_path.epoch,
+                client_update_height_path.height,
+            )
+            .map_err(ContextError::ClientError)?;
+            ClientKeeper::store_update_height(
+                self,
+                client_update_height_path.client_id,
+                height,
+                host_height,
+            )
+            .map_err(ContextError::ClientError)
+        }
+
+        fn delete_update_time(
+            &mut self,
+            client_update_time_path: ClientUpdateTimePath,
+        ) -> Result<(), ContextError> {
+            let height = Height::new(
+                client_update_time_path.epoch,
+                client_update_time_path.height,
+            )
+            .map_err(ContextError::ClientError)?;
+            ClientKeeper::delete_update_time(self, client_update_time_path.client_id, height)
+                .map_err(ContextError::ClientError)
+        }
+
+        fn delete_update_height(
+            &mut self,
+            client_update_height_path: ClientUpdateHeightPath,
+        ) -> Result<(), ContextError> {
+            let height = Height::new(
+                client_update_height_path.epoch,
+                client_update_height_path.height,
+            )
+            .map_err(ContextError::ClientError)?;
+            ClientKeeper::delete_update_height(self, client_update_height_path.client_id, height)
+                .map_err(ContextError::ClientError)
+        }
+
+        fn store_connection(
+            &mut self,
+            connections_path: ConnectionsPath,
+            connection_end: ConnectionEnd,
+        ) -> Result<(), ContextError> {
+            ConnectionKeeper::store_connection(self, connections_path.0, connection_end)
+                .map_err(ContextError::ConnectionError)
+        }
+
+        fn store_connection_to_client(
+            &mut self,
+            client_connections_path: ClientConnectionsPath,
+            conn_id: ConnectionId,
+        ) -> Result<(), ContextError> {
+            ConnectionKeeper::store_connection_to_client(self, conn_id, client_connections_path.0)
+                .map_err(ContextError::ConnectionError)
+        }
+
+        fn increase_connection_counter(&mut self) {
+            ConnectionKeeper::increase_connection_counter(self)
+        }
+
+        fn store_connection_params(
+            &mut self,
+            params: ConnectionParams,
+        ) -> Result<(), ContextError> {
+            ConnectionKeeper::store_connection_params(self, params)
+                .map_err(ContextError::ConnectionError)
+        }
+
+        fn store_packet_commitment(
+            &mut self,
+            commitments_path: CommitmentsPath,
+            commitment: PacketCommitment,
+        ) -> Result<(), ContextError> {
+            ChannelKeeper::store_packet_commitment(
+                self,
+                commitments_path.port_id,
+                commitments_path.channel_id,
+                commitments_path.sequence,
+                commitment,
+            )
+            .map_err(ContextError::PacketError)
+        }
+
+        fn delete_packet_commitment(&mut self, key: CommitmentsPath) -> Result<(), ContextError> {
+            ChannelKeeper::delete_packet_commitment(
+                self,
+                &key.port_id,
+                &key.channel_id,
+                &key.sequence,
+            )
+            .map_err(ContextError::PacketError)
+        }
+
+        fn store_packet_receipt(
+            &mut self,
+            path: ReceiptsPath,
+            receipt: Receipt,
+        ) -> Result<(), ContextError> {
+            ChannelKeeper::store_packet_receipt(
+                self,
+                path.port_id,
+                path.channel_id,
+                path.sequence,
+                receipt,
+            )
+            .map_err(ContextError::PacketError)
+        }
+
+        fn store_packet_acknowledgement(
+            &mut self,
+            key: (PortId, ChannelId, Sequence),
+            ack_commitment: AcknowledgementCommitment,
+        ) -> Result<(), ContextError> {
+            ChannelKeeper::store_packet_acknowledgement(self, key.0, key.1, key.2, ack_commitment)
+                .map_err(ContextError::PacketError)
+        }
+
+        fn delete_packet_acknowledgement(
+            &mut self,
+            key: (PortId, ChannelId, Sequence),
+        ) -> Result<(), ContextError> {
+            ChannelKeeper::delete_packet_acknowledgement(self, &key.0, &key.1, &key.2)
+                .map_err(ContextError::PacketError)
+        }
+
+        fn store_connection_channels(
+            &mut self,
+            conn_id: ConnectionId,
+            port_channel_id: (PortId, ChannelId),
+        ) -> Result<(), ContextError> {
+            ChannelKeeper::store_connection_channels(
+                self,
+                conn_id,
+                port_channel_id.0,
+                port_channel_id.1,
+            )
+            .map_err(ContextError::ChannelError)
+        }
+
+        fn delete_connection_channels(
+            &mut self,
+            conn_id: ConnectionId,
+            port_channel_id: (PortId, ChannelId),
+        ) -> Result<(), ContextError> {
+            if let Some(channels) = self.ibc_store.lock().connection_channels.get_mut(&conn_id) {
+                channels.retain(|channel| channel != &port_channel_id);
+            }
+            Ok(())
+        }
+
+        fn store_channel(
+            &mut self,
+            port_channel_id: (PortId, ChannelId),
+            channel_end: ChannelEnd,
+        ) -> Result<(), ContextError> {
+            ChannelKeeper::store_channel(self, port_channel_id.0, port_channel_id.1, channel_end)
+                .map_err(ContextError::ChannelError)
+        }
+
+        fn store_next_sequence_send(
+            &mut self,
+            port_channel_id: (PortId, ChannelId),
+            seq: Sequence,
+        ) -> Result<(), ContextError> {
+            ChannelKeeper::store_next_sequence_send(self, port_channel_id.0, port_channel_id.1, seq)
+                .map_err(ContextError::PacketError)
+        }
+
+        fn store_next_sequence_recv(
+            &mut self,
+            port_channel_id: (PortId, ChannelId),
+            seq: Sequence,
+        ) -> Result<(), ContextError> {
+            ChannelKeeper::store_next_sequence_recv(self, port_channel_id.0, port_channel_id.1, seq)
+                .map_err(ContextError::PacketError)
+        }
+
+        fn store_next_sequence_ack(
+            &mut self,
+            port_channel_id: (PortId, ChannelId),
+            seq: Sequence,
+        ) -> Result<(), ContextError> {
+            ChannelKeeper::store_next_sequence_ack(self, port_channel_id.0, port_channel_id.1, seq)
+                .map_err(ContextError::PacketError)
+        }
+
+        fn increase_channel_counter(&mut self) {
+            ChannelKeeper::increase_channel_counter(self)
+        }
+
+        fn store_channel_upgrade(
+            &mut self,
+            channel_upgrade_path: ChannelUpgradePath,
+            upgrade: Upgrade,
+        ) -> Result<(), ContextError> {
+            self.ibc_store
+                .lock()
+                .channel_upgrades
+                .entry(channel_upgrade_path.0)
+                .or_default()
+                .insert(channel_upgrade_path.1, upgrade);
+            Ok(())
+        }
+
+        fn delete_channel_upgrade(
+            &mut self,
+            channel_upgrade_path: ChannelUpgradePath,
+        ) -> Result<(), ContextError> {
+            if let Some(upgrades) = self
+                .ibc_store
+                .lock()
+                .channel_upgrades
+                .get_mut(&channel_upgrade_path.0)
+            {
+                upgrades.remove(&channel_upgrade_path.1);
+            }
+            Ok(())
+        }
+
+        fn store_channel_upgrade_error(
+            &mut self,
+            channel_upgrade_error_path: ChannelUpgradeErrorPath,
+            error_receipt: ErrorReceipt,
+        ) -> Result<(), ContextError> {
+            self.ibc_store
+                .lock()
+                .channel_upgrade_errors
+                .entry(channel_upgrade_error_path.0)
+                .or_default()
+                .insert(channel_upgrade_error_path.1, error_receipt);
+            Ok(())
+        }
+
+        fn emit_ibc_event(&mut self, event: IbcEvent) {
+            self.ibc_store.lock().events.push(event);
+        }
+
+        fn log_message(&mut self, message: String) {
+            self.ibc_store.lock().logs.push(message);
         }
     }
 }
@@ -1882,6 +2611,92 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_validate_self_client() {
+        use crate::clients::ics07_tendermint::client_state::test_util::get_dummy_tendermint_client_state;
+        use crate::clients::ics07_tendermint::header::Header as TmHeader;
+        use crate::core::ics03_connection::context::ConnectionReader;
+        use crate::core::ics03_connection::error::ConnectionError;
+        use crate::mock::client_state::MockClientState;
+        use crate::mock::header::MockHeader;
+        use crate::mock::host::HostBlock;
+        use crate::timestamp::Timestamp;
+
+        let mock_client_state = |height: Height| MockClientState::new(MockHeader::new(height));
+        let tm_client_state = |chain_id: ChainId, height: u64| {
+            let header: TmHeader =
+                HostBlock::generate_tm_block(chain_id, height, Timestamp::now()).into();
+            get_dummy_tendermint_client_state(header.signed_header.header)
+        };
+        let is_invalid = |res: Result<(), ConnectionError>| {
+            matches!(res, Err(ConnectionError::InvalidClientState { .. }))
+        };
+
+        // A mock host at height 0-5
+        let ctx = MockContext::default();
+        let chain_id = ChainId::new("mockgaia".to_string(), 0);
+
+        assert!(ConnectionReader::validate_self_client(
+            &ctx,
+            mock_client_state(Height::new(0, 4).unwrap()).into()
+        )
+        .is_ok());
+        // The client must be behind the host
+        assert!(is_invalid(ConnectionReader::validate_self_client(
+            &ctx,
+            mock_client_state(Height::new(0, 5).unwrap()).into()
+        )));
+        // The client must be in the revision of the host
+        assert!(is_invalid(ConnectionReader::validate_self_client(
+            &ctx,
+            mock_client_state(Height::new(1, 4).unwrap()).into()
+        )));
+        // The client must be of the host type
+        assert!(is_invalid(ConnectionReader::validate_self_client(
+            &ctx,
+            tm_client_state(chain_id, 4).into()
+        )));
+
+        // A synthetic Tendermint host at height 1-5
+        let chain_id = ChainId::new("mockgaia".to_string(), 1);
+        let ctx = MockContext::new(
+            chain_id.clone(),
+            HostType::SyntheticTendermint,
+            5,
+            Height::new(1, 5).unwrap(),
+        );
+
+        assert!(ConnectionReader::validate_self_client(
+            &ctx,
+            tm_client_state(chain_id.clone(), 4).into()
+        )
+        .is_ok());
+        // The client must track the host chain
+        assert!(is_invalid(ConnectionReader::validate_self_client(
+            &ctx,
+            tm_client_state(ChainId::new("othergaia".to_string(), 1), 4).into()
+        )));
+        // The client must not be frozen
+        assert!(is_invalid(ConnectionReader::validate_self_client(
+            &ctx,
+            tm_client_state(chain_id.clone(), 4)
+                .with_frozen_height(Height::new(1, 3).unwrap())
+                .into()
+        )));
+        // The client must use the proof specs of the host
+        let mut client_state = tm_client_state(chain_id.clone(), 4);
+        client_state.proof_specs = vec![ics23::iavl_spec()].into();
+        assert!(is_invalid(ConnectionReader::validate_self_client(
+            &ctx,
+            client_state.into()
+        )));
+        // The client must be behind the host
+        assert!(is_invalid(ConnectionReader::validate_self_client(
+            &ctx,
+            tm_client_state(chain_id, 5).into()
+        )));
+    }
+
     #[test]
     fn test_router() {
         #[derive(Default)]
@@ -1913,6 +2728,32 @@ mod tests {
                 Ok((ModuleExtras::empty(), version.clone()))
             }
 
+            #[cfg(feature = "val_exec_ctx")]
+            fn on_chan_open_init_validate(
+                &self,
+                _order: Order,
+                _connection_hops: &[ConnectionId],
+                _port_id: &PortId,
+                _channel_id: &ChannelId,
+                _counterparty: &Counterparty,
+                version: &Version,
+            ) -> Result<Version, ChannelError> {
+                Ok(version.clone())
+            }
+
+            #[cfg(feature = "val_exec_ctx")]
+            fn on_chan_open_init_execute(
+                &mut self,
+                _order: Order,
+                _connection_hops: &[ConnectionId],
+                _port_id: &PortId,
+                _channel_id: &ChannelId,
+                _counterparty: &Counterparty,
+                version: &Version,
+            ) -> Result<(ModuleExtras, Version), ChannelError> {
+                Ok((ModuleExtras::empty(), version.clone()))
+            }
+
             #[cfg(feature = "val_exec_ctx")]
             fn on_chan_open_try_validate(
                 &self,
@@ -1984,6 +2825,32 @@ mod tests {
                 Ok((ModuleExtras::empty(), version.clone()))
             }
 
+            #[cfg(feature = "val_exec_ctx")]
+            fn on_chan_open_init_validate(
+                &self,
+                _order: Order,
+                _connection_hops: &[ConnectionId],
+                _port_id: &PortId,
+                _channel_id: &ChannelId,
+                _counterparty: &Counterparty,
+                version: &Version,
+            ) -> Result<Version, ChannelError> {
+                Ok(version.clone())
+            }
+
+            #[cfg(feature = "val_exec_ctx")]
+            fn on_chan_open_init_execute(
+                &mut self,
+                _order: Order,
+                _connection_hops: &[ConnectionId],
+                _port_id: &PortId,
+                _channel_id: &ChannelId,
+                _counterparty: &Counterparty,
+                version: &Version,
+            ) -> Result<(ModuleExtras, Version), ChannelError> {
+                Ok((ModuleExtras::empty(), version.clone()))
+            }
+
             #[cfg(feature = "val_exec_ctx")]
             fn on_chan_open_try_validate(
                 &self,
@@ -0,0 +1,280 @@
+//! An offline misbehaviour detector for Tendermint clients. It cross-checks the headers a client
+//! was updated with against the headers of a witness of the counterparty chain, and builds the
+//! `MsgSubmitMisbehaviour` messages which freeze the client on a fork.
+
+use crate::prelude::*;
+
+use alloc::collections::btree_map::BTreeMap;
+
+use crate::clients::ics07_tendermint::header::{
+    headers_compatible, Header as TmHeader, TENDERMINT_HEADER_TYPE_URL,
+};
+use crate::clients::ics07_tendermint::misbehaviour::Misbehaviour as TmMisbehaviour;
+use crate::core::ics02_client::error::ClientError;
+use crate::core::ics02_client::events::UpdateClient;
+use crate::core::ics02_client::msgs::misbehaviour::MsgSubmitMisbehaviour;
+use crate::core::ics24_host::identifier::ClientId;
+use crate::signer::Signer;
+use crate::Height;
+
+/// Tracks the headers a Tendermint client was updated with, and detects the witness headers which
+/// conflict with them, i.e. which are evidence of a fork or of a BFT time violation.
+#[derive(Clone, Debug)]
+pub struct MisbehaviourDetector {
+    client_id: ClientId,
+    signer: Signer,
+    client_headers: BTreeMap<Height, TmHeader>,
+}
+
+impl MisbehaviourDetector {
+    /// Creates a detector for the client with `client_id`, whose misbehaviour messages are signed
+    /// by `signer`.
+    pub fn new(client_id: ClientId, signer: Signer) -> Self {
+        Self {
+            client_id,
+            signer,
+            client_headers: BTreeMap::new(),
+        }
+    }
+
+    pub fn client_id(&self) -> &ClientId {
+        &self.client_id
+    }
+
+    /// Returns the headers the client was updated with, in ascending order of height.
+    pub fn client_headers(&self) -> impl Iterator<Item = &TmHeader> {
+        self.client_headers.values()
+    }
+
+    /// Tracks a header the client was updated with.
+    pub fn track_header(&mut self, header: TmHeader) {
+        self.client_headers.insert(header.height(), header);
+    }
+
+    /// Tracks the header carried by an `UpdateClient` event. Events of other clients, and client
+    /// messages which are not Tendermint headers (e.g. misbehaviours), are ignored.
+    pub fn track_update(&mut self, event: &UpdateClient) -> Result<(), ClientError> {
+        if event.client_id() != &self.client_id
+            || event.header().type_url != TENDERMINT_HEADER_TYPE_URL
+        {
+            return Ok(());
+        }
+
+        let header = TmHeader::try_from(event.header().clone())?;
+        self.track_header(header);
+
+        Ok(())
+    }
+
+    /// Cross-checks a header fetched from a witness against the tracked headers, and returns the
+    /// message submitting the misbehaviour of the first tracked header it conflicts with, if any.
+    ///
+    /// The witness header is given the trusted height and validator set of the conflicting
+    /// tracked header, so that the client can verify both headers from the same consensus state.
+    pub fn check_witness_header(
+        &self,
+        witness_header: &TmHeader,
+    ) -> Result<Option<MsgSubmitMisbehaviour>, ClientError> {
+        let client_header = match self
+            .client_headers
+            .values()
+            .find(|h| !headers_compatible(&h.signed_header, &witness_header.signed_header))
+        {
+            Some(client_header) => client_header.clone(),
+            None => return Ok(None),
+        };
+
+        let witness_header = TmHeader {
+            trusted_height: client_header.trusted_height,
+            trusted_validator_set: client_header.trusted_validator_set.clone(),
+            ..witness_header.clone()
+        };
+
+        // The ctor expects the first header to be at the greater height
+        let (header1, header2) = if witness_header.height() >= client_header.height() {
+            (witness_header, client_header)
+        } else {
+            (client_header, witness_header)
+        };
+
+        let misbehaviour = TmMisbehaviour::new(self.client_id.clone(), header1, header2)?;
+
+        Ok(Some(MsgSubmitMisbehaviour {
+            client_id: self.client_id.clone(),
+            misbehaviour: misbehaviour.into(),
+            signer: self.signer.clone(),
+        }))
+    }
+
+    /// Cross-checks all the given witness headers against the tracked headers, and returns the
+    /// messages submitting the misbehaviours found, in the order of the witness headers.
+    pub fn detect<'a>(
+        &self,
+        witness_headers: impl IntoIterator<Item = &'a TmHeader>,
+    ) -> Result<Vec<MsgSubmitMisbehaviour>, ClientError> {
+        witness_headers
+            .into_iter()
+            .filter_map(|h| self.check_witness_header(h).transpose())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_log::test;
+
+    use crate::clients::ics07_tendermint::client_type as tm_client_type;
+    use crate::core::ics02_client::handler::{dispatch, ClientResult};
+    use crate::core::ics02_client::msgs::update_client::MsgUpdateClient;
+    use crate::core::ics02_client::msgs::ClientMsg;
+    use crate::core::ics24_host::identifier::ChainId;
+    use crate::events::IbcEvent;
+    use crate::mock::context::MockContext;
+    use crate::mock::host::{HostBlock, HostType};
+    use crate::test_utils::get_dummy_account_id;
+    use crate::timestamp::Timestamp;
+    use crate::{downcast, Height};
+
+    /// Updates the client of chain B on chain A from height 20 to height 21,
+    /// returning chain A, the id of chain B, the header of the update and its
+    /// event.
+    fn update_client_on_a(client_id: &ClientId) -> (MockContext, ChainId, TmHeader, UpdateClient) {
+        let client_height = Height::new(1, 20).unwrap();
+        let update_height = Height::new(1, 21).unwrap();
+        let chain_id_b = ChainId::new("mockgaiaB".to_string(), 1);
+
+        let ctx_a = MockContext::new(
+            ChainId::new("mockgaiaA".to_string(), 1),
+            HostType::Mock,
+            5,
+            Height::new(1, 1).unwrap(),
+        )
+        .with_client_parametrized_with_chain_id(
+            chain_id_b.clone(),
+            client_id,
+            client_height,
+            Some(tm_client_type()),
+            Some(client_height),
+        );
+
+        let ctx_b = MockContext::new(
+            chain_id_b.clone(),
+            HostType::SyntheticTendermint,
+            5,
+            update_height,
+        );
+
+        // Update the client of chain B on chain A to height 21.
+        let mut block = ctx_b.host_block(&update_height).unwrap().clone();
+        block.set_trusted_height(client_height);
+        let client_header: TmHeader = block.clone().try_into_tm_block().unwrap().into();
+
+        let msg = MsgUpdateClient {
+            client_id: client_id.clone(),
+            header: block.into(),
+            signer: get_dummy_account_id(),
+        };
+        let output = dispatch(&ctx_a, ClientMsg::UpdateClient(msg)).unwrap();
+        let update_event =
+            downcast!(output.events.into_iter().next().unwrap() => IbcEvent::UpdateClient).unwrap();
+
+        (ctx_a, chain_id_b, client_header, update_event)
+    }
+
+    #[test]
+    fn test_track_client_updates() {
+        let client_id = ClientId::new(tm_client_type(), 0).unwrap();
+        let (_, _, client_header, update_event) = update_client_on_a(&client_id);
+
+        let mut detector = MisbehaviourDetector::new(client_id, get_dummy_account_id());
+        detector.track_update(&update_event).unwrap();
+        assert_eq!(
+            detector.client_headers().collect::<Vec<_>>(),
+            vec![&client_header]
+        );
+
+        // Updates of other clients are ignored.
+        let mut detector = MisbehaviourDetector::new(
+            ClientId::new(tm_client_type(), 1).unwrap(),
+            get_dummy_account_id(),
+        );
+        detector.track_update(&update_event).unwrap();
+        assert_eq!(detector.client_headers().count(), 0);
+    }
+
+    #[test]
+    fn test_detect_misbehaviour() {
+        struct Test {
+            name: String,
+            witness_header: TmHeader,
+            want_misbehaviour: bool,
+        }
+
+        let client_id = ClientId::new(tm_client_type(), 0).unwrap();
+        let (ctx_a, chain_id_b, client_header, update_event) = update_client_on_a(&client_id);
+
+        let mut detector = MisbehaviourDetector::new(client_id.clone(), get_dummy_account_id());
+        detector.track_update(&update_event).unwrap();
+
+        let tests: Vec<Test> = vec![
+            Test {
+                name: "Witness header matching the client header".to_string(),
+                witness_header: client_header.clone(),
+                want_misbehaviour: false,
+            },
+            Test {
+                name: "Fork: witness header for a different block at the same height".to_string(),
+                witness_header: HostBlock::generate_tm_block(
+                    chain_id_b.clone(),
+                    client_header.height().revision_height(),
+                    Timestamp::now(),
+                )
+                .into(),
+                want_misbehaviour: true,
+            },
+            Test {
+                name: "BFT time violation: higher witness header with the same timestamp"
+                    .to_string(),
+                witness_header: HostBlock::generate_tm_block(
+                    chain_id_b,
+                    client_header.height().revision_height() + 1,
+                    client_header.signed_header.header.time.into(),
+                )
+                .into(),
+                want_misbehaviour: true,
+            },
+        ];
+
+        for test in tests {
+            let msgs = detector.detect([&test.witness_header]).unwrap();
+
+            if !test.want_misbehaviour {
+                assert!(msgs.is_empty(), "{}: unexpected misbehaviour", test.name);
+                continue;
+            }
+
+            assert_eq!(msgs.len(), 1, "{}: misbehaviour not detected", test.name);
+            assert_eq!(msgs[0].client_id, client_id);
+
+            let misbehaviour = TmMisbehaviour::try_from(msgs[0].misbehaviour.clone()).unwrap();
+            assert_eq!(
+                misbehaviour.header1().signed_header,
+                test.witness_header.signed_header
+            );
+            assert_eq!(misbehaviour.header2(), &client_header);
+
+            // The submitted misbehaviour freezes the client.
+            match dispatch(&ctx_a, ClientMsg::Misbehaviour(msgs[0].clone()))
+                .unwrap()
+                .result
+            {
+                ClientResult::Misbehaviour(res) => {
+                    assert!(res.client_state.is_frozen(), "{}", test.name)
+                }
+                _ => panic!("misbehaviour handler result has incorrect type"),
+            }
+        }
+    }
+}
@@ -14,3 +14,7 @@ pub mod host;
 pub mod ics18_relayer;
 #[cfg(any(test, feature = "mocks", feature = "mocks-no-std"))]
 pub mod misbehaviour;
+#[cfg(any(test, feature = "mocks"))]
+pub mod misbehaviour_detector;
+#[cfg(any(test, feature = "mocks", feature = "mocks-no-std"))]
+pub mod wasm_engine;
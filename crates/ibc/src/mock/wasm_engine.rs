@@ -0,0 +1,144 @@
+//! A mock of a Wasm engine, which runs a stand-in for a light client contract that tracks mock
+//! chains. For testing the Wasm client and its use by the ICS02 handlers.
+
+use crate::prelude::*;
+
+use core::time::Duration;
+
+use ibc_proto::ibc::core::client::v1::Height as RawHeight;
+use ibc_proto::ibc::core::commitment::v1::MerkleProof;
+use ibc_proto::ibc::mock::{Header as RawMockHeader, Misbehaviour as RawMisbehaviour};
+use prost::Message;
+
+use crate::clients::ics08_wasm::client_message::ClientMessage;
+use crate::clients::ics08_wasm::client_state::ClientState as WasmClientState;
+use crate::clients::ics08_wasm::consensus_state::ConsensusState as WasmConsensusState;
+use crate::clients::ics08_wasm::engine::WasmEngine;
+use crate::core::ics02_client::error::ClientError;
+use crate::core::ics23_commitment::commitment::{
+    CommitmentPrefix, CommitmentProofBytes, CommitmentRoot,
+};
+use crate::core::ics24_host::Path;
+use crate::mock::header::MockHeader;
+use crate::mock::misbehaviour::Misbehaviour;
+use crate::Height;
+
+/// A Wasm engine whose contract accepts mock headers and misbehaviours as client messages, and
+/// any proof.
+///
+/// The contract keeps the frozen height of the client as the client state data, which is empty
+/// while the client is active.
+#[derive(Clone, Debug, Default)]
+pub struct MockWasmEngine;
+
+impl WasmEngine for MockWasmEngine {
+    fn frozen_height(&self, client_state: &WasmClientState) -> Option<Height> {
+        RawHeight::decode(client_state.data.as_slice())
+            .ok()
+            .and_then(|height| height.try_into().ok())
+    }
+
+    fn expired(&self, _client_state: &WasmClientState, _elapsed: Duration) -> bool {
+        false
+    }
+
+    fn update_state(
+        &self,
+        client_state: &WasmClientState,
+        _latest_consensus_state: &WasmConsensusState,
+        client_message: &ClientMessage,
+    ) -> Result<(WasmClientState, WasmConsensusState), ClientError> {
+        let header = MockHeader::try_from(
+            RawMockHeader::decode(client_message.data.as_slice()).map_err(ClientError::Decode)?,
+        )?;
+
+        if client_state.latest_height() >= header.height() {
+            return Err(ClientError::LowHeaderHeight {
+                header_height: header.height(),
+                latest_height: client_state.latest_height(),
+            });
+        }
+
+        let mut client_state = client_state.clone();
+        client_state.latest_height = header.height();
+        let consensus_state =
+            WasmConsensusState::new(vec![], header.timestamp, CommitmentRoot::from(vec![0]));
+
+        Ok((client_state, consensus_state))
+    }
+
+    fn check_for_misbehaviour(
+        &self,
+        _client_state: &WasmClientState,
+        client_message: &ClientMessage,
+    ) -> Result<bool, ClientError> {
+        Ok(RawMisbehaviour::decode(client_message.data.as_slice())
+            .map(|misbehaviour| misbehaviour.header1.is_some() && misbehaviour.header2.is_some())
+            .unwrap_or(false))
+    }
+
+    fn check_misbehaviour(
+        &self,
+        client_state: &WasmClientState,
+        client_message: &ClientMessage,
+    ) -> Result<WasmClientState, ClientError> {
+        let misbehaviour = Misbehaviour::try_from(
+            RawMisbehaviour::decode(client_message.data.as_slice()).map_err(ClientError::Decode)?,
+        )?;
+
+        if misbehaviour.header1.height() != misbehaviour.header2.height() {
+            return Err(ClientError::InvalidHeight);
+        }
+
+        let mut client_state = client_state.clone();
+        client_state.data = RawHeight::from(misbehaviour.header1.height()).encode_to_vec();
+
+        Ok(client_state)
+    }
+
+    fn verify_upgrade(
+        &self,
+        _client_state: &WasmClientState,
+        upgraded_client_state: &WasmClientState,
+        upgraded_consensus_state: &WasmConsensusState,
+        _proof_upgrade_client: MerkleProof,
+        _proof_upgrade_consensus_state: MerkleProof,
+        _root: &CommitmentRoot,
+    ) -> Result<(WasmClientState, WasmConsensusState), ClientError> {
+        Ok((
+            upgraded_client_state.clone(),
+            upgraded_consensus_state.clone(),
+        ))
+    }
+
+    fn check_substitute(
+        &self,
+        _client_state: &WasmClientState,
+        substitute_client_state: &WasmClientState,
+    ) -> Result<WasmClientState, ClientError> {
+        Ok(substitute_client_state.clone())
+    }
+
+    fn verify_membership(
+        &self,
+        _client_state: &WasmClientState,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        _path: Path,
+        _value: Vec<u8>,
+    ) -> Result<(), ClientError> {
+        Ok(())
+    }
+
+    fn verify_non_membership(
+        &self,
+        _client_state: &WasmClientState,
+        _prefix: &CommitmentPrefix,
+        _proof: &CommitmentProofBytes,
+        _root: &CommitmentRoot,
+        _path: Path,
+    ) -> Result<(), ClientError> {
+        Ok(())
+    }
+}
@@ -95,6 +95,32 @@ impl Module for DummyTransferModule {
             version.clone(),
         ))
     }
+    #[cfg(feature = "val_exec_ctx")]
+    fn on_chan_open_init_validate(
+        &self,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty: &Counterparty,
+        version: &Version,
+    ) -> Result<Version, ChannelError> {
+        Ok(version.clone())
+    }
+
+    #[cfg(feature = "val_exec_ctx")]
+    fn on_chan_open_init_execute(
+        &mut self,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty: &Counterparty,
+        version: &Version,
+    ) -> Result<(ModuleExtras, Version), ChannelError> {
+        Ok((ModuleExtras::empty(), version.clone()))
+    }
+
     #[cfg(feature = "val_exec_ctx")]
     fn on_chan_open_try_validate(
         &self,
@@ -138,6 +164,74 @@ impl Module for DummyTransferModule {
             counterparty_version.clone(),
         ))
     }
+
+    #[cfg(feature = "val_exec_ctx")]
+    fn on_chan_upgrade_init_validate(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        version: &Version,
+    ) -> Result<Version, ChannelError> {
+        Ok(version.clone())
+    }
+
+    #[cfg(feature = "val_exec_ctx")]
+    fn on_chan_upgrade_init_execute(
+        &mut self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        version: &Version,
+    ) -> Result<(ModuleExtras, Version), ChannelError> {
+        Ok((ModuleExtras::empty(), version.clone()))
+    }
+
+    #[cfg(feature = "val_exec_ctx")]
+    fn on_chan_upgrade_try_validate(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        counterparty_version: &Version,
+    ) -> Result<Version, ChannelError> {
+        Ok(counterparty_version.clone())
+    }
+
+    #[cfg(feature = "val_exec_ctx")]
+    fn on_chan_upgrade_try_execute(
+        &mut self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _order: Order,
+        _connection_hops: &[ConnectionId],
+        counterparty_version: &Version,
+    ) -> Result<(ModuleExtras, Version), ChannelError> {
+        Ok((ModuleExtras::empty(), counterparty_version.clone()))
+    }
+
+    #[cfg(feature = "val_exec_ctx")]
+    fn on_chan_upgrade_ack_validate(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty_version: &Version,
+    ) -> Result<(), ChannelError> {
+        Ok(())
+    }
+
+    #[cfg(feature = "val_exec_ctx")]
+    fn on_chan_upgrade_ack_execute(
+        &mut self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _counterparty_version: &Version,
+    ) -> Result<ModuleExtras, ChannelError> {
+        Ok(ModuleExtras::empty())
+    }
 }
 
 impl TokenTransferKeeper for DummyTransferModule {